@@ -15,7 +15,7 @@
 //! }
 //! ```
 
-use super::{FormatHandler, FormatManagedBlock};
+use super::{FormatHandler, FormatManagedBlock, split_bom};
 use serde_json::{Map, Value};
 use uuid::Uuid;
 
@@ -35,7 +35,7 @@ impl JsonFormatHandler {
 
 impl FormatHandler for JsonFormatHandler {
     fn parse_blocks(&self, content: &str) -> Vec<FormatManagedBlock> {
-        let Ok(json) = serde_json::from_str::<Value>(content) else {
+        let Ok(json) = serde_json::from_str::<Value>(split_bom(content).1) else {
             return Vec::new();
         };
 
@@ -59,10 +59,11 @@ impl FormatHandler for JsonFormatHandler {
 
     fn write_block(&self, content: &str, uuid: Uuid, block_content: &str) -> String {
         // Parse existing JSON or create empty object
-        let mut json: Value = if content.trim().is_empty() {
+        let (bom, body) = split_bom(content);
+        let mut json: Value = if body.trim().is_empty() {
             Value::Object(Map::new())
         } else {
-            serde_json::from_str(content).unwrap_or(Value::Object(Map::new()))
+            serde_json::from_str(body).unwrap_or(Value::Object(Map::new()))
         };
 
         // Parse the block content as JSON
@@ -97,31 +98,39 @@ impl FormatHandler for JsonFormatHandler {
         }
 
         // Pretty print with 4-space indentation
-        serde_json::to_string_pretty(&json).unwrap_or_else(|_| content.to_string())
+        serde_json::to_string_pretty(&json)
+            .map(|result| format!("{bom}{result}"))
+            .unwrap_or_else(|_| content.to_string())
     }
 
-    fn remove_block(&self, content: &str, uuid: Uuid) -> String {
-        let Ok(mut json) = serde_json::from_str::<Value>(content) else {
-            return content.to_string();
+    fn remove_block_reported(&self, content: &str, uuid: Uuid) -> (String, bool) {
+        let unchanged = || (content.to_string(), false);
+        let (bom, body) = split_bom(content);
+        let Ok(mut json) = serde_json::from_str::<Value>(body) else {
+            return unchanged();
         };
 
         let Some(obj) = json.as_object_mut() else {
-            return content.to_string();
+            return unchanged();
         };
 
         // Get the managed section
-        if let Some(managed) = obj.get_mut(MANAGED_KEY)
-            && let Some(managed_obj) = managed.as_object_mut()
-        {
-            managed_obj.remove(&uuid.to_string());
+        let Some(managed_obj) = obj.get_mut(MANAGED_KEY).and_then(Value::as_object_mut) else {
+            return unchanged();
+        };
+        if managed_obj.remove(&uuid.to_string()).is_none() {
+            return unchanged();
+        }
 
-            // If managed section is now empty, remove it entirely
-            if managed_obj.is_empty() {
-                obj.remove(MANAGED_KEY);
-            }
+        // If managed section is now empty, remove it entirely
+        if managed_obj.is_empty() {
+            obj.remove(MANAGED_KEY);
         }
 
-        serde_json::to_string_pretty(&json).unwrap_or_else(|_| content.to_string())
+        match serde_json::to_string_pretty(&json) {
+            Ok(result) => (format!("{bom}{result}"), true),
+            Err(_) => unchanged(),
+        }
     }
 }
 
@@ -259,6 +268,23 @@ mod tests {
         assert!(parsed.get(MANAGED_KEY).is_none());
     }
 
+    #[test]
+    fn test_remove_block_reported() {
+        let handler = JsonFormatHandler::new();
+        let uuid = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+        let existing = r#"{"user.setting": true, "__repo_managed__": {"550e8400-e29b-41d4-a716-446655440000": {"a": 1}}}"#;
+
+        let (result, removed) = handler.remove_block_reported(existing, uuid);
+        assert!(removed);
+        assert!(!handler.has_block(&result, uuid));
+
+        // Absent: content is returned as-is, not reformatted
+        let absent = r#"{"user.setting": true}"#;
+        let (result, removed) = handler.remove_block_reported(absent, uuid);
+        assert!(!removed);
+        assert_eq!(result, absent);
+    }
+
     #[test]
     fn test_remove_block_keeps_other_blocks() {
         let handler = JsonFormatHandler::new();
@@ -354,7 +380,10 @@ mod tests {
     fn test_write_block_overwrites_non_object_managed_key() {
         let handler = JsonFormatHandler::new();
         // Simulate user data that happens to use the reserved key as a non-object
-        let existing = format!(r#"{{"{MANAGED_KEY}": "user string value"}}"#, MANAGED_KEY = MANAGED_KEY);
+        let existing = format!(
+            r#"{{"{MANAGED_KEY}": "user string value"}}"#,
+            MANAGED_KEY = MANAGED_KEY
+        );
         let uuid = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
 
         let result = handler.write_block(&existing, uuid, r#"{"setting": true}"#);
@@ -362,8 +391,28 @@ mod tests {
 
         // The managed key should now be an object with the block, overwriting the string
         assert!(parsed[MANAGED_KEY].is_object());
-        assert!(parsed[MANAGED_KEY]["550e8400-e29b-41d4-a716-446655440000"]["setting"]
-            .as_bool()
-            .unwrap());
+        assert!(
+            parsed[MANAGED_KEY]["550e8400-e29b-41d4-a716-446655440000"]["setting"]
+                .as_bool()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_block_keeps_bom() {
+        let handler = JsonFormatHandler::new();
+        let uuid = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+        let content = "\u{FEFF}{\"editor.formatOnSave\": true}";
+
+        let result = handler.write_block(content, uuid, r#"{"setting": true}"#);
+        let body = result.strip_prefix('\u{FEFF}').unwrap();
+        let parsed: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(parsed["editor.formatOnSave"], true);
+        assert_eq!(handler.parse_blocks(&result).len(), 1);
+
+        let (removed, found) = handler.remove_block_reported(&result, uuid);
+        assert!(found);
+        assert!(removed.starts_with('\u{FEFF}'));
+        assert!(!removed.contains(MANAGED_KEY));
     }
 }
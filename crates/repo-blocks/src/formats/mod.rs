@@ -9,6 +9,17 @@ pub mod yaml;
 
 use uuid::Uuid;
 
+/// Split a leading UTF-8 byte order mark off `content`
+///
+/// Returns the mark, empty if there is none, and the text after it. The
+/// structured handlers parse the text and keep the mark on what they write.
+fn split_bom(content: &str) -> (&str, &str) {
+    match content.strip_prefix('\u{FEFF}') {
+        Some(rest) => ("\u{FEFF}", rest),
+        None => ("", content),
+    }
+}
+
 /// A parsed managed block from a file
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FormatManagedBlock {
@@ -27,9 +38,16 @@ pub trait FormatHandler: Send + Sync {
     /// Returns the new file content with the block added/updated
     fn write_block(&self, content: &str, uuid: Uuid, block_content: &str) -> String;
 
+    /// Remove a managed block from the content
+    /// Returns the new file content and whether the block was found; content
+    /// without the block is returned unchanged
+    fn remove_block_reported(&self, content: &str, uuid: Uuid) -> (String, bool);
+
     /// Remove a managed block from the content
     /// Returns the new file content with the block removed
-    fn remove_block(&self, content: &str, uuid: Uuid) -> String;
+    fn remove_block(&self, content: &str, uuid: Uuid) -> String {
+        self.remove_block_reported(content, uuid).0
+    }
 
     /// Check if a block with this UUID exists
     fn has_block(&self, content: &str, uuid: Uuid) -> bool {
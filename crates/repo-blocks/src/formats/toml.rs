@@ -15,7 +15,7 @@
 //! serde = "1.0"
 //! ```
 
-use super::{FormatHandler, FormatManagedBlock};
+use super::{FormatHandler, FormatManagedBlock, split_bom};
 use uuid::Uuid;
 
 /// The reserved table name for managed blocks in TOML files
@@ -34,7 +34,7 @@ impl TomlFormatHandler {
 
 impl FormatHandler for TomlFormatHandler {
     fn parse_blocks(&self, content: &str) -> Vec<FormatManagedBlock> {
-        let Ok(table) = content.parse::<toml::Table>() else {
+        let Ok(table) = split_bom(content).1.parse::<toml::Table>() else {
             return Vec::new();
         };
 
@@ -61,10 +61,11 @@ impl FormatHandler for TomlFormatHandler {
 
     fn write_block(&self, content: &str, uuid: Uuid, block_content: &str) -> String {
         // Parse existing TOML or create empty table
-        let mut table: toml::Table = if content.trim().is_empty() {
+        let (bom, body) = split_bom(content);
+        let mut table: toml::Table = if body.trim().is_empty() {
             toml::Table::new()
         } else {
-            content.parse().unwrap_or_default()
+            body.parse().unwrap_or_default()
         };
 
         // Parse the block content as TOML value
@@ -83,27 +84,38 @@ impl FormatHandler for TomlFormatHandler {
             managed_table.insert(uuid.to_string(), block_value);
         }
 
-        toml::to_string_pretty(&table).unwrap_or_else(|_| content.to_string())
+        toml::to_string_pretty(&table)
+            .map(|result| format!("{bom}{result}"))
+            .unwrap_or_else(|_| content.to_string())
     }
 
-    fn remove_block(&self, content: &str, uuid: Uuid) -> String {
-        let Ok(mut table) = content.parse::<toml::Table>() else {
-            return content.to_string();
+    fn remove_block_reported(&self, content: &str, uuid: Uuid) -> (String, bool) {
+        let unchanged = || (content.to_string(), false);
+        let (bom, body) = split_bom(content);
+        let Ok(mut table) = body.parse::<toml::Table>() else {
+            return unchanged();
         };
 
         // Get the managed table
-        if let Some(managed) = table.get_mut(MANAGED_TABLE)
-            && let Some(managed_table) = managed.as_table_mut()
-        {
-            managed_table.remove(&uuid.to_string());
-
-            // If managed table is now empty, remove it entirely
-            if managed_table.is_empty() {
-                table.remove(MANAGED_TABLE);
-            }
+        let Some(managed_table) = table
+            .get_mut(MANAGED_TABLE)
+            .and_then(toml::Value::as_table_mut)
+        else {
+            return unchanged();
+        };
+        if managed_table.remove(&uuid.to_string()).is_none() {
+            return unchanged();
+        }
+
+        // If managed table is now empty, remove it entirely
+        if managed_table.is_empty() {
+            table.remove(MANAGED_TABLE);
         }
 
-        toml::to_string_pretty(&table).unwrap_or_else(|_| content.to_string())
+        match toml::to_string_pretty(&table) {
+            Ok(result) => (format!("{bom}{result}"), true),
+            Err(_) => unchanged(),
+        }
     }
 }
 
@@ -235,6 +247,23 @@ setting = "value"
         assert!(parsed.get(MANAGED_TABLE).is_none());
     }
 
+    #[test]
+    fn test_remove_block_reported() {
+        let handler = TomlFormatHandler::new();
+        let uuid = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+        let existing = "[project]\nname = \"test\"\n\n[repo_managed.\"550e8400-e29b-41d4-a716-446655440000\"]\na = 1\n";
+
+        let (result, removed) = handler.remove_block_reported(existing, uuid);
+        assert!(removed);
+        assert!(!handler.has_block(&result, uuid));
+
+        // Absent: content is returned as-is, not reformatted
+        let absent = "# user comment\n[project]\nname   = \"test\"\n";
+        let (result, removed) = handler.remove_block_reported(absent, uuid);
+        assert!(!removed);
+        assert_eq!(result, absent);
+    }
+
     #[test]
     fn test_remove_block_keeps_other_blocks() {
         let handler = TomlFormatHandler::new();
@@ -294,4 +323,15 @@ a = 1
         let blocks = handler.parse_blocks(&result);
         assert_eq!(blocks.len(), 2);
     }
+
+    #[test]
+    fn test_write_block_keeps_bom() {
+        let handler = TomlFormatHandler::new();
+        let uuid = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+
+        let result = handler.write_block("\u{FEFF}[project]\nname = \"test\"\n", uuid, "key = 1");
+        assert!(result.starts_with('\u{FEFF}'));
+        assert!(result.contains("name = \"test\""));
+        assert_eq!(handler.get_block(&result, uuid).unwrap(), "key = 1");
+    }
 }
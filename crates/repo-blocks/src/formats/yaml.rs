@@ -13,7 +13,7 @@
 //! another_setting: false
 //! ```
 
-use super::{FormatHandler, FormatManagedBlock};
+use super::{FormatHandler, FormatManagedBlock, split_bom};
 use regex::Regex;
 use std::sync::LazyLock;
 use uuid::Uuid;
@@ -101,17 +101,18 @@ impl FormatHandler for YamlFormatHandler {
         } else {
             // Append new block
             let block = format!("{}\n{}\n{}", open_marker, block_content, close_marker);
-            if content.trim().is_empty() {
-                block
+            let (bom, body) = split_bom(content);
+            if body.trim().is_empty() {
+                format!("{bom}{block}")
             } else {
                 format!("{}\n\n{}", content.trim_end(), block)
             }
         }
     }
 
-    fn remove_block(&self, content: &str, uuid: Uuid) -> String {
+    fn remove_block_reported(&self, content: &str, uuid: Uuid) -> (String, bool) {
         if !self.has_block(content, uuid) {
-            return content.to_string();
+            return (content.to_string(), false);
         }
 
         // Match the block including surrounding newlines
@@ -124,11 +125,12 @@ impl FormatHandler for YamlFormatHandler {
         let result = re.replace(content, "\n").to_string();
 
         // Clean up extra newlines
-        result
+        let result = result
             .trim_start_matches('\n')
             .trim_end_matches('\n')
             .to_string()
-            + "\n"
+            + "\n";
+        (result, true)
     }
 }
 
@@ -233,6 +235,21 @@ another: false"#;
         assert!(!result.contains("repo:block"));
     }
 
+    #[test]
+    fn test_remove_block_reported() {
+        let handler = YamlFormatHandler::new();
+        let uuid = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+        let existing = "user: true\n# repo:block:550e8400-e29b-41d4-a716-446655440000\nmanaged: value\n# /repo:block:550e8400-e29b-41d4-a716-446655440000\n";
+
+        let (result, removed) = handler.remove_block_reported(existing, uuid);
+        assert!(removed);
+        assert_eq!(result, "user: true\n");
+
+        let (result, removed) = handler.remove_block_reported(&result, uuid);
+        assert!(!removed);
+        assert_eq!(result, "user: true\n");
+    }
+
     #[test]
     fn test_has_block() {
         let handler = YamlFormatHandler::new();
@@ -43,4 +43,4 @@ pub use formats::{
     FormatHandler, FormatManagedBlock, JsonFormatHandler, TomlFormatHandler, YamlFormatHandler,
 };
 pub use parser::{Block, find_block, has_block, parse_blocks};
-pub use writer::{insert_block, remove_block, update_block, upsert_block};
+pub use writer::{insert_block, remove_block, remove_block_reported, update_block, upsert_block};
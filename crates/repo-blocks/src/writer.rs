@@ -28,18 +28,37 @@ fn format_block(uuid: &str, block_content: &str) -> String {
     )
 }
 
+/// Replaces the content of the block with the given UUID, leaving the rest
+/// of `content` untouched.
+fn replace_block(content: &str, uuid: &str, new_content: &str) -> String {
+    // Build regex to match this specific block
+    let pattern = format!(
+        r"(?s)<!-- repo:block:{} -->\n.*?\n<!-- /repo:block:{} -->",
+        regex::escape(uuid),
+        regex::escape(uuid)
+    );
+    let re = Regex::new(&pattern).expect("UUID should produce valid regex pattern");
+
+    let replacement = format_block(uuid, new_content);
+    re.replace(content, replacement.as_str()).to_string()
+}
+
 /// Inserts a new block at the end of the content.
 ///
 /// If the content is empty, the block is added directly.
 /// If the content has existing text, the block is appended with a newline separator.
 ///
+/// If a block with the same UUID already exists, its content is updated in
+/// place instead, so inserting twice never creates a duplicate block. Prefer
+/// [`upsert_block`], which makes that intent explicit.
+///
 /// # Arguments
 /// * `content` - The existing content
 /// * `uuid` - The UUID for the new block
 /// * `block_content` - The content to place inside the block
 ///
 /// # Returns
-/// The content with the new block appended.
+/// The content with the new block appended, or with the existing block updated.
 ///
 /// # Example
 /// ```
@@ -50,6 +69,10 @@ fn format_block(uuid: &str, block_content: &str) -> String {
 /// assert!(result.contains("<!-- repo:block:abc-123 -->"));
 /// ```
 pub fn insert_block(content: &str, uuid: &str, block_content: &str) -> String {
+    if has_block(content, uuid) {
+        return replace_block(content, uuid, block_content);
+    }
+
     let block = format_block(uuid, block_content);
 
     if content.is_empty() {
@@ -92,16 +115,7 @@ pub fn update_block(content: &str, uuid: &str, new_content: &str) -> Result<Stri
         });
     }
 
-    // Build regex to match this specific block
-    let pattern = format!(
-        r"(?s)<!-- repo:block:{} -->\n.*?\n<!-- /repo:block:{} -->",
-        regex::escape(uuid),
-        regex::escape(uuid)
-    );
-    let re = Regex::new(&pattern).expect("UUID should produce valid regex pattern");
-
-    let replacement = format_block(uuid, new_content);
-    Ok(re.replace(content, replacement.as_str()).to_string())
+    Ok(replace_block(content, uuid, new_content))
 }
 
 /// Removes a block from the content.
@@ -132,11 +146,36 @@ pub fn update_block(content: &str, uuid: &str, new_content: &str) -> Result<Stri
 /// assert!(!result.contains("block content"));
 /// ```
 pub fn remove_block(content: &str, uuid: &str) -> Result<String> {
-    if !has_block(content, uuid) {
-        return Err(Error::BlockNotFound {
+    match remove_block_reported(content, uuid) {
+        (result, true) => Ok(result),
+        (_, false) => Err(Error::BlockNotFound {
             uuid: uuid.to_string(),
             path: PathBuf::from("<content>"),
-        });
+        }),
+    }
+}
+
+/// Removes a block from the content, reporting whether it was there.
+///
+/// Unlike [`remove_block`], a missing block is not an error: the content is
+/// returned unchanged with `false`, so callers can skip writing the file.
+///
+/// # Example
+/// ```
+/// use repo_blocks::writer::remove_block_reported;
+///
+/// let content = "<!-- repo:block:abc-123 -->\nblock\n<!-- /repo:block:abc-123 -->";
+/// let (result, removed) = remove_block_reported(content, "abc-123");
+/// assert!(removed);
+/// assert!(!result.contains("block"));
+///
+/// let (result, removed) = remove_block_reported("no blocks", "abc-123");
+/// assert!(!removed);
+/// assert_eq!(result, "no blocks");
+/// ```
+pub fn remove_block_reported(content: &str, uuid: &str) -> (String, bool) {
+    if !has_block(content, uuid) {
+        return (content.to_string(), false);
     }
 
     // Build regex to match this specific block, including surrounding newlines
@@ -152,13 +191,15 @@ pub fn remove_block(content: &str, uuid: &str) -> Result<String> {
     // Clean up any leading/trailing whitespace issues
     let result = result.trim_start_matches('\n').to_string();
 
-    Ok(result)
+    (result, true)
 }
 
 /// Inserts a new block or updates an existing one.
 ///
-/// If a block with the given UUID exists, its content is updated.
-/// Otherwise, a new block is inserted at the end.
+/// This is the idempotent way to write a block: if a block with the given
+/// UUID exists, its content is updated in place; otherwise a new block is
+/// inserted at the end. Calling it repeatedly with the same UUID always
+/// leaves exactly one block holding the latest content.
 ///
 /// # Arguments
 /// * `content` - The existing content
@@ -241,6 +282,36 @@ mod tests {
         assert!(!result.contains("old"));
     }
 
+    #[test]
+    fn test_upsert_twice_yields_one_block() {
+        use crate::parser::parse_blocks;
+
+        let mut content = "header".to_string();
+        content = upsert_block(&content, "abc-123", "first").unwrap();
+        content = upsert_block(&content, "abc-123", "second").unwrap();
+
+        let blocks = parse_blocks(&content);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].uuid, "abc-123");
+        assert_eq!(blocks[0].content, "second");
+        assert!(content.starts_with("header"));
+    }
+
+    #[test]
+    fn test_insert_existing_uuid_updates_in_place() {
+        use crate::parser::parse_blocks;
+
+        let mut content = insert_block("", "first", "AAA");
+        content = insert_block(&content, "second", "BBB");
+        content = insert_block(&content, "first", "UPDATED");
+
+        let blocks = parse_blocks(&content);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].uuid, "first");
+        assert_eq!(blocks[0].content, "UPDATED");
+        assert_eq!(blocks[1].content, "BBB");
+    }
+
     #[test]
     fn test_insert_to_empty_file() {
         let result = insert_block("", "test-uuid", "my content");
@@ -330,6 +401,20 @@ Footer"#;
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_remove_block_reported() {
+        let content = "Header\n<!-- repo:block:gone -->\nold\n<!-- /repo:block:gone -->\nFooter";
+
+        let (result, removed) = remove_block_reported(content, "gone");
+        assert!(removed);
+        assert!(!result.contains("repo:block:gone"));
+        assert!(result.contains("Header") && result.contains("Footer"));
+
+        let (again, removed) = remove_block_reported(&result, "gone");
+        assert!(!removed);
+        assert_eq!(again, result);
+    }
+
     #[test]
     fn test_remove_preserves_other_blocks() {
         let content = r#"<!-- repo:block:keep-1 -->
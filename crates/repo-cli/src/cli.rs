@@ -1,7 +1,10 @@
 //! CLI argument parsing using clap derive
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
+use std::path::PathBuf;
+
+use crate::output::OutputFormat;
 
 /// Repository Manager - Manage tool configurations for your repository
 #[derive(Parser, Debug)]
@@ -12,6 +15,27 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub verbose: bool,
 
+    /// Read back and checksum every file write (for unreliable filesystems)
+    ///
+    /// Can also be enabled permanently with `verify_writes = true` under
+    /// `[core]` in .repository/config.toml.
+    #[arg(long, global = true)]
+    pub verify_writes: bool,
+
+    /// Print results as text, json or yaml
+    ///
+    /// With json or yaml, every command prints one document with its result
+    /// to stdout and its progress messages to stderr. A command's `--json`
+    /// flag is the same as `--output-format json`.
+    #[arg(
+        long = "output-format",
+        id = "output_format",
+        global = true,
+        value_enum,
+        default_value_t = OutputFormat::Text
+    )]
+    pub output: OutputFormat,
+
     /// The command to run
     #[command(subcommand)]
     pub command: Option<Commands>,
@@ -27,6 +51,31 @@ pub enum Commands {
         json: bool,
     },
 
+    /// Run health checks and suggest fixes for any problems
+    ///
+    /// Checks the git repository, that config.toml parses, that the ledger
+    /// is readable, that every configured preset's provider is healthy, and
+    /// lints the configuration. Exits non-zero if any check fails.
+    Doctor {
+        /// Output as JSON for scripting
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show the detected repository context
+    ///
+    /// Reports the repository root, mode, container, active worktree, and
+    /// config path resolved from the current directory.
+    ///
+    /// Examples:
+    ///   repo context            # Show detected context
+    ///   repo context --explain  # Also show each detection step
+    Context {
+        /// Print the step-by-step detection trail
+        #[arg(long)]
+        explain: bool,
+    },
+
     /// Preview what sync would change
     Diff {
         /// Output as JSON for scripting
@@ -34,6 +83,22 @@ pub enum Commands {
         json: bool,
     },
 
+    /// Show the history of operations that changed the configuration
+    ///
+    /// Examples:
+    ///   repo history             # Last 20 operations
+    ///   repo log --limit 5       # Last 5 operations
+    #[command(alias = "log")]
+    History {
+        /// Maximum number of entries to show (newest first)
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+
+        /// Output as JSON for scripting
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Initialize a new repository configuration
     ///
     /// Creates a .repository/ directory with config.toml.
@@ -44,6 +109,9 @@ pub enum Commands {
     ///   repo init --interactive      # Guided setup
     ///   repo init -t claude -t cursor # With specific tools
     ///   repo init -e vaultspec        # With extensions
+    ///   repo init --nested api        # Sub-package of the enclosing repository
+    ///   repo init --template backend-service  # Start from a template
+    ///   repo init --list-templates    # Show available templates
     Init {
         /// Project name (creates folder if not ".")
         #[arg(default_value = ".")]
@@ -72,10 +140,43 @@ pub enum Commands {
         /// Interactive mode for guided setup
         #[arg(short, long)]
         interactive: bool,
+
+        /// Initialize inside an existing repository, recording it as the
+        /// parent (refused otherwise)
+        #[arg(long)]
+        nested: bool,
+
+        /// Start from a template: a name from the templates directory, or
+        /// a git URL with an optional #<name>
+        #[arg(long, value_name = "NAME|URL")]
+        template: Option<String>,
+
+        /// List the available templates and exit
+        #[arg(long, conflicts_with = "template")]
+        list_templates: bool,
     },
 
     /// Check repository configuration for drift
-    Check,
+    ///
+    /// With --watch, keeps checking and prints the status whenever it
+    /// changes. On Ctrl-C it exits with the last status: 0 healthy,
+    /// 1 missing, 2 drifted, 3 broken.
+    ///
+    /// Examples:
+    ///   repo check
+    ///   repo check --watch       # Re-check every 2 seconds
+    ///   repo check --watch 10    # Re-check every 10 seconds
+    Check {
+        /// Keep checking, every SECONDS (default 2)
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            num_args = 0..=1,
+            default_missing_value = "2",
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        watch: Option<u64>,
+    },
 
     /// Synchronize tool configurations
     Sync {
@@ -86,6 +187,19 @@ pub enum Commands {
         /// Output as JSON for CI/CD integration
         #[arg(long)]
         json: bool,
+
+        /// Group the reported changes (e.g. by the rule that caused them)
+        #[arg(long, value_enum)]
+        report_by: Option<ReportBy>,
+
+        /// Commit the synchronized changes with this message
+        ///
+        /// Only the files sync wrote or removed are committed; other
+        /// changes in the working tree are left alone. The ledger is left
+        /// out unless `commit_ledger = true` is set under `[core]` in
+        /// config.toml.
+        #[arg(long, value_name = "MESSAGE")]
+        commit: Option<String>,
     },
 
     /// Fix configuration drift automatically
@@ -108,6 +222,10 @@ pub enum Commands {
         /// Name of the tool (use 'repo list-tools' to see options)
         name: String,
 
+        /// Add the tool even if it is not built in or defined in .repository/tools/
+        #[arg(long)]
+        allow_unknown: bool,
+
         /// Preview changes without applying them
         #[arg(long)]
         dry_run: bool,
@@ -153,6 +271,10 @@ pub enum Commands {
         /// Optional tags
         #[arg(short, long)]
         tags: Vec<String>,
+        /// Warn about malformed front-matter and coerce it where possible,
+        /// instead of failing
+        #[arg(long)]
+        lenient: bool,
     },
 
     /// Remove a rule from the repository
@@ -171,16 +293,37 @@ pub enum Commands {
         json: bool,
     },
 
+    /// Check rule files for structural problems
+    ///
+    /// Fails on invalid IDs, empty bodies, unparsable definitions, duplicate
+    /// IDs, and `requires` entries naming unknown rules. Exits non-zero when
+    /// any are found, so it can run as a pre-commit hook.
+    RulesValidate {
+        /// Output as JSON for scripting
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Show config drift between expected and actual state
+    ///
+    /// With `--tool`, shows a unified diff of what sync would write to each
+    /// of that tool's files instead.
     RulesDiff {
         /// Output as JSON for scripting
         #[arg(long)]
         json: bool,
+
+        /// Preview the synced output of this tool
+        #[arg(long)]
+        tool: Option<String>,
     },
 
-    /// Export rules to AGENTS.md format
+    /// Export rules to AGENTS.md format or as a JSON rule catalog
+    ///
+    /// `catalog-json` prints every rule definition with its full metadata,
+    /// sorted by ID, for generating documentation.
     RulesExport {
-        /// Output format (agents)
+        /// Output format (agents, catalog-json)
         #[arg(long, default_value = "agents")]
         format: String,
     },
@@ -189,11 +332,96 @@ pub enum Commands {
     RulesImport {
         /// Path to the file to import
         file: String,
+        /// Warn about malformed front-matter and coerce it where possible,
+        /// instead of skipping the rule
+        #[arg(long)]
+        lenient: bool,
+    },
+
+    /// Export the repository's configuration and rules as a bundle
+    ///
+    /// The bundle holds every config.toml section but [core] (tools,
+    /// presets, rules, MCP servers, ...), the rule files and the registry
+    /// rules, ready for `repo import` in another repository.
+    ///
+    /// Examples:
+    ///   repo export                                 # Print the bundle
+    ///   repo export --output bundle.toml            # Write it to a file
+    ///   repo export --output bundle.tar.gz --archive
+    Export {
+        /// File to write the bundle to (prints TOML when omitted)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Write a gzipped tar archive instead of TOML
+        #[arg(long, requires = "output")]
+        archive: bool,
+    },
+
+    /// Apply a bundle from `repo export` to this repository, then sync
+    ///
+    /// By default the bundle is merged: missing tools, presets and rules are
+    /// added and existing ones kept, asking before overwriting a rule the
+    /// repository defines differently. With --replace, the bundle's config
+    /// sections and rules overwrite the repository's.
+    ///
+    /// Examples:
+    ///   repo import bundle.toml
+    ///   repo import bundle.tar.gz --replace
+    ///   repo import bundle.toml --no-input     # Fail on rule conflicts
+    Import {
+        /// Bundle file (TOML or archive)
+        file: PathBuf,
+
+        /// Add what is missing and keep existing entries (default)
+        #[arg(long, conflicts_with = "replace")]
+        merge: bool,
+
+        /// Overwrite rule files and config sections with the bundle's
+        #[arg(long)]
+        replace: bool,
+
+        /// Fail instead of asking when rules conflict
+        #[arg(long)]
+        no_input: bool,
+    },
+
+    /// Adopt hand-written AI config files as managed rules
+    ///
+    /// Splits CLAUDE.md, .cursorrules and similar files into one rule per
+    /// `## ` section and adds them to the rule registry. Run `repo sync`
+    /// afterwards to regenerate the files from the registry.
+    ///
+    /// Examples:
+    ///   repo adopt --analyze-only --json    # Report candidate rules
+    ///   repo adopt                          # Adopt them
+    ///   repo adopt --bundle plan/shared.md  # Import a shared rule set first
+    Adopt {
+        /// Report candidate rules without changing anything
+        #[arg(long)]
+        analyze_only: bool,
+
+        /// Output as JSON for scripting
+        #[arg(long)]
+        json: bool,
+
+        /// AGENTS.md bundle to import before adopting local files
+        #[arg(long, conflicts_with = "analyze_only")]
+        bundle: Option<PathBuf>,
+    },
+
+    /// Operations across several repositories
+    Ws {
+        /// Workspace action to perform
+        #[command(subcommand)]
+        action: WsAction,
     },
 
     /// List available tools
     ///
-    /// Shows all tools that can be added to your repository.
+    /// Shows all tools that can be added to your repository: the built-in
+    /// tools plus those defined in `.repository/tools/` or by installed
+    /// extensions.
     ///
     /// Examples:
     ///   repo list-tools                # Show all tools
@@ -242,6 +470,13 @@ pub enum Commands {
         source: String,
     },
 
+    /// Git commands that know about managed files
+    Git {
+        /// Git action to perform
+        #[command(subcommand)]
+        action: GitAction,
+    },
+
     /// Generate shell completions
     ///
     /// Outputs completion script for your shell.
@@ -272,6 +507,13 @@ pub enum Commands {
         name: String,
     },
 
+    /// Work with tool definitions
+    Tool {
+        /// Tool action to perform
+        #[command(subcommand)]
+        action: ToolAction,
+    },
+
     /// Manage lifecycle hooks
     ///
     /// Configure hooks that run before/after branch creation, deletion,
@@ -300,22 +542,71 @@ pub enum Commands {
         action: ExtensionAction,
     },
 
+    /// Manage MCP servers across tools
+    ///
+    /// Install, remove and list MCP servers in a tool's config, or sync the
+    /// servers declared under [mcp.servers] in config.toml to every enabled
+    /// tool that supports MCP.
+    ///
+    /// Examples:
+    ///   repo mcp add github --tool cursor --command npx --args -y --args server-github
+    ///   repo mcp list
+    ///   repo mcp sync
+    Mcp {
+        #[command(subcommand)]
+        action: McpAction,
+    },
+
+    /// Check the environments of configured presets
+    ///
+    /// Examples:
+    ///   repo presets check
+    ///   repo presets check --json --fail-on broken
+    Presets {
+        #[command(subcommand)]
+        action: PresetsAction,
+    },
+
     /// Open a worktree in an editor/IDE
     ///
     /// Launches the specified editor in the target worktree directory.
     /// Runs sync before opening to ensure configs are up to date.
     ///
+    /// Any tool whose definition sets `open_command` can be opened; without
+    /// --tool, `[open] default_tool` in config.toml is used, then the first
+    /// installed tool.
+    ///
     /// Examples:
-    ///   repo open feature-x                # Open with auto-detected editor
+    ///   repo open feature-x                # Open with the default editor
     ///   repo open feature-x --tool cursor  # Open with Cursor
     ///   repo open feature-x --tool vscode  # Open with VS Code
+    ///   repo open --list                   # Show tools that can be opened
     Open {
         /// Name of the worktree to open
-        worktree: String,
+        #[arg(required_unless_present = "list")]
+        worktree: Option<String>,
 
-        /// Editor to use (cursor, vscode, zed). Auto-detected if not specified.
+        /// Editor to use (e.g. cursor, vscode, zed). Auto-detected if not specified.
         #[arg(short, long)]
         tool: Option<String>,
+
+        /// List the tools that can be opened and their commands
+        #[arg(long, conflicts_with_all = ["worktree", "tool"])]
+        list: bool,
+    },
+}
+
+/// Git actions
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum GitAction {
+    /// Show branch, upstream and working tree status
+    ///
+    /// Also lists repo-managed files with uncommitted changes, which would
+    /// be carried over or block a branch switch.
+    Status {
+        /// Output as JSON for scripting
+        #[arg(long)]
+        json: bool,
     },
 }
 
@@ -339,12 +630,26 @@ pub enum BranchAction {
     },
 
     /// List all branch worktrees
-    List,
+    ///
+    /// Shows each branch's commits ahead of and behind its upstream and
+    /// whether its worktree has uncommitted changes.
+    List {
+        /// Skip the uncommitted-changes check, which reads every worktree
+        #[arg(long)]
+        fast: bool,
+    },
 
     /// Switch to a branch (or worktree in worktrees mode)
     Checkout {
         /// Branch name to checkout
         name: String,
+
+        /// Sync tool configurations into the worktree (worktrees mode)
+        ///
+        /// Can also be enabled permanently with `auto_sync_on_switch = true`
+        /// under `[sync]` in config.toml.
+        #[arg(long)]
+        sync: bool,
     },
 
     /// Rename a branch (and its worktree in worktrees mode)
@@ -355,16 +660,136 @@ pub enum BranchAction {
         /// New branch name
         new: String,
     },
+
+    /// Remove branches (and their worktrees) merged into the main branch
+    ///
+    /// The main and current branches are kept, as are worktrees with
+    /// uncommitted or untracked changes unless --force is given.
+    Prune {
+        /// Show what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also remove worktrees with uncommitted or untracked changes
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Multi-repository actions
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum WsAction {
+    /// Plan adoption of hand-written rules across repositories
+    ///
+    /// Rules with identical content in two or more repositories become a
+    /// shared rule set; the rest stay with their repository.
+    ///
+    /// Examples:
+    ///   repo ws adopt-plan ../api ../web ../cli --output plan/
+    AdoptPlan {
+        /// Repositories to analyze
+        #[arg(required = true)]
+        repos: Vec<PathBuf>,
+
+        /// Directory to write shared.md, residual/<repo>.md and plan.json to
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Output as JSON for scripting
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Tool definition actions
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum ToolAction {
+    /// Generate a commented tool definition in .repository/tools/
+    ///
+    /// Examples:
+    ///   repo tool scaffold mytool --config-path .mytool/rules.md
+    ///   repo tool scaffold mytool --config-path .mytool.json --format json
+    ///   repo tool scaffold cursor --from cursor   # Start from the builtin
+    Scaffold {
+        /// Slug of the new tool (also the file name)
+        name: String,
+
+        /// Primary config file, relative to the repository root
+        #[arg(long, required_unless_present = "from")]
+        config_path: Option<String>,
+
+        /// File format (inferred from the config path if omitted)
+        #[arg(long, value_enum)]
+        format: Option<ToolFormat>,
+
+        /// Start from the effective definition of a builtin tool
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Print the definition instead of writing it
+        #[arg(long)]
+        stdout: bool,
+
+        /// Overwrite an existing definition file
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 /// Configuration management actions
 #[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
 pub enum ConfigAction {
     /// Display the current configuration
+    ///
+    /// Examples:
+    ///   repo config show              # Summary of .repository/config.toml
+    ///   repo config show --effective  # All layers merged, as TOML
+    ///   repo config show --diff       # Where effective differs from committed
     Show {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+
+        /// Show the effective configuration (global, org, repository and
+        /// local layers merged) as a config.toml document
+        #[arg(long, conflicts_with = "json")]
+        effective: bool,
+
+        /// Show, per key, where the effective configuration differs from
+        /// the committed config.toml and which layer caused the difference
+        #[arg(long, conflicts_with_all = ["json", "effective"])]
+        diff: bool,
+    },
+
+    /// Convert the repository to another mode without re-initializing
+    ///
+    /// Converting to worktrees moves the checkout into main/ and the git
+    /// database to .gt/. Converting to standard requires main/ to be the
+    /// only worktree.
+    ///
+    /// Examples:
+    ///   repo config set-mode worktrees --dry-run  # Print the planned moves
+    ///   repo config set-mode standard
+    SetMode {
+        /// Mode to convert to: standard or worktrees
+        mode: String,
+
+        /// Print the planned moves without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Rewrite config.toml in canonical form, keeping comments
+    ///
+    /// Sorts the tools, orders the tables and normalizes spacing.
+    ///
+    /// Examples:
+    ///   repo config fmt          # Rewrite config.toml
+    ///   repo config fmt --check  # Fail if config.toml is not canonical (for CI)
+    Fmt {
+        /// Exit with an error instead of rewriting if the file is not canonical
+        #[arg(long)]
+        check: bool,
     },
 }
 
@@ -406,6 +831,10 @@ pub enum ExtensionAction {
         /// Don't activate the extension after installing
         #[arg(long)]
         no_activate: bool,
+
+        /// Only report required presets instead of configuring and applying them
+        #[arg(long)]
+        no_deps: bool,
     },
 
     /// Add a known extension by name
@@ -432,6 +861,206 @@ pub enum ExtensionAction {
         #[arg(long)]
         json: bool,
     },
+
+    /// Verify installed extensions against the lock file
+    ///
+    /// Checks each locked extension's directory, version, commit, and entry
+    /// points. Exits non-zero if any check fails.
+    Verify {
+        /// Output as JSON for scripting
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// MCP server management actions
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum McpAction {
+    /// Install an MCP server into a tool's config
+    ///
+    /// Overwrites a server of the same name, backing up the config first.
+    ///
+    /// Examples:
+    ///   repo mcp add github --tool cursor --command npx --args -y --args server-github
+    ///   repo mcp add docs --tool vscode --url https://example.com/mcp
+    ///   repo mcp add search --tool claude --command search-mcp --env API_KEY='${API_KEY}' --expand-env
+    Add {
+        /// Name of the server
+        name: String,
+
+        /// Tool whose config to install into
+        #[arg(long)]
+        tool: String,
+
+        /// Command that starts a local (stdio) server
+        #[arg(long, required_unless_present = "url", conflicts_with = "url")]
+        command: Option<String>,
+
+        /// Argument to pass to the command (repeatable)
+        #[arg(long = "args", allow_hyphen_values = true, requires = "command")]
+        args: Vec<String>,
+
+        /// URL of a remote (HTTP) server
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Environment variable for the server, as KEY=VALUE (repeatable)
+        #[arg(long = "env", value_name = "KEY=VALUE")]
+        env: Vec<String>,
+
+        /// Expand ${VAR} placeholders in env values and args now, from the
+        /// current environment, instead of leaving them for the tool
+        #[arg(long)]
+        expand_env: bool,
+
+        /// Where to install the server
+        #[arg(long, value_enum, default_value = "project")]
+        scope: McpScopeArg,
+    },
+
+    /// Remove an MCP server from a tool's config
+    Remove {
+        /// Name of the server
+        name: String,
+
+        /// Tool whose config to remove from
+        #[arg(long)]
+        tool: String,
+
+        /// Which config to remove from
+        #[arg(long, value_enum, default_value = "project")]
+        scope: McpScopeArg,
+    },
+
+    /// List the MCP servers installed in each tool's config
+    ///
+    /// Values of secret-looking env keys (*_TOKEN, *_KEY, *_SECRET, and
+    /// core.secret_patterns) are redacted.
+    List {
+        /// Only list servers for this tool
+        #[arg(long)]
+        tool: Option<String>,
+
+        /// Which configs to read
+        #[arg(long, value_enum, default_value = "project")]
+        scope: McpScopeArg,
+
+        /// Output as JSON for scripting
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Sync the servers declared in config.toml to every enabled MCP tool
+    ///
+    /// Servers removed from [mcp.servers] since the last sync are removed
+    /// from tool configs; servers added by hand are left alone.
+    Sync,
+}
+
+/// Preset subcommands
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum PresetsAction {
+    /// Run every configured preset's provider check
+    ///
+    /// Prints each preset's status with the provider's details. Exits
+    /// non-zero if any preset is at least as unhealthy as --fail-on.
+    Check {
+        /// Output as JSON for scripting
+        #[arg(long)]
+        json: bool,
+
+        /// Lowest status that makes the command fail
+        #[arg(long, value_enum, default_value = "missing")]
+        fail_on: PresetFailOn,
+
+        /// Seconds to wait for each provider check
+        #[arg(long, default_value_t = 30)]
+        timeout: u64,
+    },
+}
+
+impl Commands {
+    /// Whether the command was given its `--json` flag
+    pub fn json_flag(&self) -> bool {
+        match self {
+            Commands::Status { json }
+            | Commands::Doctor { json }
+            | Commands::Diff { json, .. }
+            | Commands::History { json, .. }
+            | Commands::Sync { json, .. }
+            | Commands::RulesLint { json, .. }
+            | Commands::RulesValidate { json, .. }
+            | Commands::RulesDiff { json, .. }
+            | Commands::Adopt { json, .. } => *json,
+            Commands::Git {
+                action: GitAction::Status { json },
+            }
+            | Commands::Ws {
+                action: WsAction::AdoptPlan { json, .. },
+            }
+            | Commands::Config {
+                action: ConfigAction::Show { json, .. },
+            }
+            | Commands::Extension {
+                action: ExtensionAction::List { json } | ExtensionAction::Verify { json },
+            }
+            | Commands::Mcp {
+                action: McpAction::List { json, .. },
+            }
+            | Commands::Presets {
+                action: PresetsAction::Check { json, .. },
+            } => *json,
+            _ => false,
+        }
+    }
+}
+
+/// Failure threshold for `repo presets check`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresetFailOn {
+    /// Fail on any preset that is not healthy, including outdated ones
+    Degraded,
+    /// Fail on missing, drifted or broken presets
+    Missing,
+    /// Fail on drifted or broken presets
+    Drifted,
+    /// Fail only on broken presets
+    Broken,
+    /// Never fail
+    Never,
+}
+
+/// Config scope for `repo mcp`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McpScopeArg {
+    /// The repository's config, which can be committed
+    Project,
+    /// The user's config in their home directory
+    User,
+}
+
+/// Config file format for `repo tool scaffold`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolFormat {
+    /// Plain text
+    Text,
+    /// JSON
+    Json,
+    /// TOML
+    Toml,
+    /// YAML
+    Yaml,
+    /// Markdown
+    Markdown,
+    /// A directory with one file per rule
+    Directory,
+}
+
+/// Grouping for `repo sync` change reports
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportBy {
+    /// Group changed files by the rule (or settings) that caused them
+    Rule,
 }
 
 #[cfg(test)]
@@ -459,6 +1088,15 @@ mod tests {
         assert!(cli.command.is_none());
     }
 
+    #[test]
+    fn parse_verify_writes_flag() {
+        let cli = Cli::parse_from(["repo", "sync", "--verify-writes"]);
+        assert!(cli.verify_writes);
+
+        let cli = Cli::parse_from(["repo", "sync"]);
+        assert!(!cli.verify_writes);
+    }
+
     #[test]
     fn parse_short_verbose_flag() {
         let cli = Cli::parse_from(["repo", "-v"]);
@@ -520,6 +1158,9 @@ mod tests {
                 extensions,
                 remote,
                 interactive,
+                nested,
+                template,
+                list_templates,
             }) => {
                 assert_eq!(name, "project");
                 assert_eq!(mode, "worktree");
@@ -528,11 +1169,80 @@ mod tests {
                 assert_eq!(extensions, vec!["vaultspec"]);
                 assert_eq!(remote, Some("https://github.com/user/repo.git".to_string()));
                 assert!(!interactive);
+                assert!(!nested);
+                assert_eq!(template, None);
+                assert!(!list_templates);
             }
             _ => panic!("Expected Init command"),
         }
     }
 
+    #[test]
+    fn parse_import_command() {
+        let cli = Cli::parse_from(["repo", "import", "bundle.toml", "--replace", "--no-input"]);
+        match cli.command {
+            Some(Commands::Import {
+                file,
+                merge,
+                replace,
+                no_input,
+            }) => {
+                assert_eq!(file, PathBuf::from("bundle.toml"));
+                assert!(!merge);
+                assert!(replace);
+                assert!(no_input);
+            }
+            _ => panic!("Expected Import command"),
+        }
+        assert!(Cli::try_parse_from(["repo", "import", "b.toml", "--merge", "--replace"]).is_err());
+        assert!(Cli::try_parse_from(["repo", "export", "--archive"]).is_err());
+    }
+
+    #[test]
+    fn parse_export_output_beside_global_output_format() {
+        let cli = Cli::parse_from([
+            "repo",
+            "export",
+            "--output",
+            "bundle.tar.gz",
+            "--archive",
+            "--output-format",
+            "json",
+        ]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Export {
+                output: Some(PathBuf::from("bundle.tar.gz")),
+                archive: true
+            })
+        );
+        assert_eq!(cli.output, OutputFormat::Json);
+    }
+
+    #[test]
+    fn parse_init_command_nested() {
+        let cli = Cli::parse_from(["repo", "init", "--nested", "api"]);
+        match cli.command {
+            Some(Commands::Init { name, nested, .. }) => {
+                assert_eq!(name, "api");
+                assert!(nested);
+            }
+            _ => panic!("Expected Init command"),
+        }
+    }
+
+    #[test]
+    fn parse_init_command_template() {
+        let cli = Cli::parse_from(["repo", "init", "--template", "backend-service"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Init { template: Some(t), .. }) if t == "backend-service"
+        ));
+        assert!(
+            Cli::try_parse_from(["repo", "init", "--template", "a", "--list-templates"]).is_err()
+        );
+    }
+
     #[test]
     fn parse_init_command_interactive() {
         let cli = Cli::parse_from(["repo", "init", "--interactive"]);
@@ -547,7 +1257,24 @@ mod tests {
     #[test]
     fn parse_check_command() {
         let cli = Cli::parse_from(["repo", "check"]);
-        assert!(matches!(cli.command, Some(Commands::Check)));
+        assert!(matches!(cli.command, Some(Commands::Check { watch: None })));
+    }
+
+    #[test]
+    fn parse_check_watch() {
+        let cli = Cli::parse_from(["repo", "check", "--watch"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Check { watch: Some(2) })
+        ));
+
+        let cli = Cli::parse_from(["repo", "check", "--watch", "10"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Check { watch: Some(10) })
+        ));
+
+        assert!(Cli::try_parse_from(["repo", "check", "--watch", "0"]).is_err());
     }
 
     #[test]
@@ -557,7 +1284,9 @@ mod tests {
             cli.command,
             Some(Commands::Sync {
                 dry_run: false,
-                json: false
+                json: false,
+                report_by: None,
+                commit: None
             })
         ));
     }
@@ -569,7 +1298,9 @@ mod tests {
             cli.command,
             Some(Commands::Sync {
                 dry_run: true,
-                json: false
+                json: false,
+                report_by: None,
+                commit: None
             })
         ));
     }
@@ -581,11 +1312,38 @@ mod tests {
             cli.command,
             Some(Commands::Sync {
                 dry_run: false,
-                json: true
+                json: true,
+                report_by: None,
+                commit: None
             })
         ));
     }
 
+    #[test]
+    fn parse_sync_command_report_by_rule() {
+        let cli = Cli::parse_from(["repo", "sync", "--report-by", "rule"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Sync {
+                dry_run: false,
+                json: false,
+                report_by: Some(ReportBy::Rule),
+                commit: None
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_sync_command_commit() {
+        let cli = Cli::parse_from(["repo", "sync", "--commit", "Sync configs"]);
+        match cli.command {
+            Some(Commands::Sync { commit, .. }) => {
+                assert_eq!(commit.as_deref(), Some("Sync configs"))
+            }
+            _ => panic!("Expected Sync command"),
+        }
+    }
+
     #[test]
     fn parse_fix_command() {
         let cli = Cli::parse_from(["repo", "fix"]);
@@ -605,8 +1363,13 @@ mod tests {
     fn parse_add_tool_command() {
         let cli = Cli::parse_from(["repo", "add-tool", "eslint"]);
         match cli.command {
-            Some(Commands::AddTool { name, dry_run }) => {
+            Some(Commands::AddTool {
+                name,
+                allow_unknown,
+                dry_run,
+            }) => {
                 assert_eq!(name, "eslint");
+                assert!(!allow_unknown);
                 assert!(!dry_run);
             }
             _ => panic!("Expected AddTool command"),
@@ -617,7 +1380,7 @@ mod tests {
     fn parse_add_tool_command_dry_run() {
         let cli = Cli::parse_from(["repo", "add-tool", "eslint", "--dry-run"]);
         match cli.command {
-            Some(Commands::AddTool { name, dry_run }) => {
+            Some(Commands::AddTool { name, dry_run, .. }) => {
                 assert_eq!(name, "eslint");
                 assert!(dry_run);
             }
@@ -625,6 +1388,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_add_tool_command_allow_unknown() {
+        let cli = Cli::parse_from(["repo", "add-tool", "eslint", "--allow-unknown"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::AddTool {
+                allow_unknown: true,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn parse_remove_tool_command() {
         let cli = Cli::parse_from(["repo", "remove-tool", "eslint"]);
@@ -711,10 +1486,12 @@ mod tests {
                 id,
                 instruction,
                 tags,
+                lenient,
             }) => {
                 assert_eq!(id, "python-style");
                 assert_eq!(instruction, "Use snake_case for variables.");
                 assert!(tags.is_empty());
+                assert!(!lenient);
             }
             _ => panic!("Expected AddRule command"),
         }
@@ -738,10 +1515,12 @@ mod tests {
                 id,
                 instruction,
                 tags,
+                lenient,
             }) => {
                 assert_eq!(id, "naming-conventions");
                 assert_eq!(instruction, "Follow consistent naming.");
                 assert_eq!(tags, vec!["style", "python"]);
+                assert!(!lenient);
             }
             _ => panic!("Expected AddRule command"),
         }
@@ -809,11 +1588,33 @@ mod tests {
         assert!(matches!(
             cli.command,
             Some(Commands::Branch {
-                action: BranchAction::List
+                action: BranchAction::List { fast: false }
+            })
+        ));
+
+        let cli = Cli::parse_from(["repo", "branch", "list", "--fast"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Branch {
+                action: BranchAction::List { fast: true }
             })
         ));
     }
 
+    #[test]
+    fn parse_branch_checkout_sync_flag() {
+        let cli = Cli::parse_from(["repo", "branch", "checkout", "feature-x", "--sync"]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Branch {
+                action: BranchAction::Checkout {
+                    name: "feature-x".to_string(),
+                    sync: true
+                }
+            })
+        );
+    }
+
     #[test]
     fn parse_branch_rename_command() {
         let cli = Cli::parse_from(["repo", "branch", "rename", "old-name", "new-name"]);
@@ -828,15 +1629,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_branch_prune_command() {
+        let cli = Cli::parse_from(["repo", "branch", "prune", "--dry-run"]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Branch {
+                action: BranchAction::Prune {
+                    dry_run: true,
+                    force: false
+                }
+            })
+        );
+    }
+
     #[test]
     fn verbose_flag_works_with_commands() {
         let cli = Cli::parse_from(["repo", "-v", "check"]);
         assert!(cli.verbose);
-        assert!(matches!(cli.command, Some(Commands::Check)));
+        assert!(matches!(cli.command, Some(Commands::Check { .. })));
 
         let cli = Cli::parse_from(["repo", "check", "--verbose"]);
         assert!(cli.verbose);
-        assert!(matches!(cli.command, Some(Commands::Check)));
+        assert!(matches!(cli.command, Some(Commands::Check { .. })));
     }
 
     #[test]
@@ -863,6 +1678,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_git_status_command() {
+        let cli = Cli::parse_from(["repo", "git", "status", "--json"]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Git {
+                action: GitAction::Status { json: true }
+            })
+        );
+    }
+
     #[test]
     fn parse_pull_command_defaults() {
         let cli = Cli::parse_from(["repo", "pull"]);
@@ -921,6 +1747,195 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parse_doctor_command() {
+        let cli = Cli::parse_from(["repo", "doctor", "--json"]);
+        assert!(matches!(cli.command, Some(Commands::Doctor { json: true })));
+    }
+
+    #[test]
+    fn parse_history_command() {
+        let cli = Cli::parse_from(["repo", "history"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::History {
+                limit: 20,
+                json: false
+            })
+        ));
+
+        let cli = Cli::parse_from(["repo", "history", "--limit", "5", "--json"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::History {
+                limit: 5,
+                json: true
+            })
+        ));
+
+        let cli = Cli::parse_from(["repo", "log", "-n", "3"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::History {
+                limit: 3,
+                json: false
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_adopt_command() {
+        let cli = Cli::parse_from(["repo", "adopt", "--analyze-only", "--json"]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Adopt {
+                analyze_only: true,
+                json: true,
+                bundle: None
+            })
+        );
+
+        let cli = Cli::parse_from(["repo", "adopt", "--bundle", "shared.md"]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Adopt {
+                analyze_only: false,
+                json: false,
+                bundle: Some(PathBuf::from("shared.md"))
+            })
+        );
+
+        assert!(
+            Cli::try_parse_from(["repo", "adopt", "--analyze-only", "--bundle", "x.md"]).is_err()
+        );
+    }
+
+    #[test]
+    fn parse_ws_adopt_plan_command() {
+        let cli = Cli::parse_from(["repo", "ws", "adopt-plan", "a", "b", "-o", "plan"]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Ws {
+                action: WsAction::AdoptPlan {
+                    repos: vec![PathBuf::from("a"), PathBuf::from("b")],
+                    output: Some(PathBuf::from("plan")),
+                    json: false
+                }
+            })
+        );
+
+        let cli = Cli::parse_from(["repo", "ws", "adopt-plan", "a", "b", "--output", "plan"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Ws {
+                action: WsAction::AdoptPlan { output: Some(ref dir), .. }
+            }) if dir.as_path() == std::path::Path::new("plan")
+        ));
+
+        assert!(Cli::try_parse_from(["repo", "ws", "adopt-plan"]).is_err());
+    }
+
+    #[test]
+    fn parse_config_show_effective() {
+        let cli = Cli::parse_from(["repo", "config", "show", "--effective"]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Config {
+                action: ConfigAction::Show {
+                    json: false,
+                    effective: true,
+                    diff: false
+                }
+            })
+        );
+
+        assert!(Cli::try_parse_from(["repo", "config", "show", "--effective", "--json"]).is_err());
+    }
+
+    #[test]
+    fn parse_config_show_diff() {
+        let cli = Cli::parse_from(["repo", "config", "show", "--diff"]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Config {
+                action: ConfigAction::Show {
+                    json: false,
+                    effective: false,
+                    diff: true
+                }
+            })
+        );
+
+        assert!(Cli::try_parse_from(["repo", "config", "show", "--diff", "--effective"]).is_err());
+    }
+
+    #[test]
+    fn parse_config_set_mode() {
+        let cli = Cli::parse_from(["repo", "config", "set-mode", "worktrees", "--dry-run"]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Config {
+                action: ConfigAction::SetMode {
+                    mode: "worktrees".to_string(),
+                    dry_run: true
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn parse_config_fmt() {
+        let cli = Cli::parse_from(["repo", "config", "fmt", "--check"]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Config {
+                action: ConfigAction::Fmt { check: true }
+            })
+        );
+    }
+
+    #[test]
+    fn parse_tool_scaffold_command() {
+        let cli = Cli::parse_from([
+            "repo",
+            "tool",
+            "scaffold",
+            "mytool",
+            "--config-path",
+            ".mytool/rules.md",
+            "--format",
+            "markdown",
+        ]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Tool {
+                action: ToolAction::Scaffold {
+                    name: "mytool".into(),
+                    config_path: Some(".mytool/rules.md".into()),
+                    format: Some(ToolFormat::Markdown),
+                    from: None,
+                    stdout: false,
+                    force: false,
+                }
+            })
+        );
+
+        let cli = Cli::parse_from(["repo", "tool", "scaffold", "cursor", "--from", "cursor"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Tool {
+                action: ToolAction::Scaffold {
+                    from: Some(_),
+                    config_path: None,
+                    ..
+                }
+            })
+        ));
+
+        // A config path is needed unless starting from a builtin
+        assert!(Cli::try_parse_from(["repo", "tool", "scaffold", "mytool"]).is_err());
+    }
+
     #[test]
     fn parse_completions_command() {
         let cli = Cli::parse_from(["repo", "completions", "bash"]);
@@ -965,6 +1980,122 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_mcp_add_command() {
+        let cli = Cli::parse_from([
+            "repo",
+            "mcp",
+            "add",
+            "github",
+            "--command",
+            "npx",
+            "--args",
+            "-y",
+            "--args",
+            "server-github",
+            "--tool",
+            "cursor",
+            "--env",
+            "GITHUB_TOKEN=x",
+        ]);
+        match cli.command {
+            Some(Commands::Mcp {
+                action:
+                    McpAction::Add {
+                        name,
+                        tool,
+                        command,
+                        args,
+                        url,
+                        env,
+                        expand_env,
+                        scope,
+                    },
+            }) => {
+                assert_eq!(name, "github");
+                assert_eq!(tool, "cursor");
+                assert_eq!(command.as_deref(), Some("npx"));
+                assert_eq!(args, vec!["-y", "server-github"]);
+                assert_eq!(url, None);
+                assert_eq!(env, vec!["GITHUB_TOKEN=x"]);
+                assert!(!expand_env);
+                assert_eq!(scope, McpScopeArg::Project);
+            }
+            _ => panic!("Expected Mcp Add command"),
+        }
+    }
+
+    #[test]
+    fn parse_mcp_add_requires_command_or_url() {
+        assert!(Cli::try_parse_from(["repo", "mcp", "add", "s", "--tool", "cursor"]).is_err());
+        assert!(
+            Cli::try_parse_from([
+                "repo",
+                "mcp",
+                "add",
+                "s",
+                "--tool",
+                "cursor",
+                "--command",
+                "x",
+                "--url",
+                "y",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_mcp_list_command() {
+        let cli = Cli::parse_from(["repo", "mcp", "list", "--tool", "zed", "--scope", "user"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Mcp {
+                action: McpAction::List {
+                    tool: Some(_),
+                    scope: McpScopeArg::User,
+                    json: false,
+                }
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_presets_check_command() {
+        let cli = Cli::parse_from(["repo", "presets", "check"]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Presets {
+                action: PresetsAction::Check {
+                    json: false,
+                    fail_on: PresetFailOn::Missing,
+                    timeout: 30,
+                }
+            })
+        );
+
+        let cli = Cli::parse_from([
+            "repo",
+            "presets",
+            "check",
+            "--json",
+            "--fail-on",
+            "never",
+            "--timeout",
+            "5",
+        ]);
+        assert_eq!(
+            cli.command,
+            Some(Commands::Presets {
+                action: PresetsAction::Check {
+                    json: true,
+                    fail_on: PresetFailOn::Never,
+                    timeout: 5,
+                }
+            })
+        );
+    }
+
     #[test]
     fn parse_hooks_remove_command() {
         let cli = Cli::parse_from(["repo", "hooks", "remove", "pre-sync"]);
@@ -982,26 +2113,55 @@ mod tests {
     fn parse_open_command() {
         let cli = Cli::parse_from(["repo", "open", "feature-x"]);
         match cli.command {
-            Some(Commands::Open { worktree, tool }) => {
-                assert_eq!(worktree, "feature-x");
+            Some(Commands::Open {
+                worktree,
+                tool,
+                list,
+            }) => {
+                assert_eq!(worktree.as_deref(), Some("feature-x"));
                 assert!(tool.is_none());
+                assert!(!list);
             }
             _ => panic!("Expected Open command"),
         }
     }
 
+    #[test]
+    fn parse_context_command() {
+        let cli = Cli::parse_from(["repo", "context", "--explain"]);
+        match cli.command {
+            Some(Commands::Context { explain }) => assert!(explain),
+            _ => panic!("Expected Context command"),
+        }
+    }
+
     #[test]
     fn parse_open_command_with_tool() {
         let cli = Cli::parse_from(["repo", "open", "feature-x", "--tool", "cursor"]);
         match cli.command {
-            Some(Commands::Open { worktree, tool }) => {
-                assert_eq!(worktree, "feature-x");
+            Some(Commands::Open { worktree, tool, .. }) => {
+                assert_eq!(worktree.as_deref(), Some("feature-x"));
                 assert_eq!(tool, Some("cursor".to_string()));
             }
             _ => panic!("Expected Open command"),
         }
     }
 
+    #[test]
+    fn parse_open_list() {
+        let cli = Cli::parse_from(["repo", "open", "--list"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Open {
+                worktree: None,
+                list: true,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["repo", "open"]).is_err());
+        assert!(Cli::try_parse_from(["repo", "open", "feature-x", "--list"]).is_err());
+    }
+
     #[test]
     fn parse_extension_install_command() {
         let cli = Cli::parse_from([
@@ -1016,10 +2176,12 @@ mod tests {
                     ExtensionAction::Install {
                         source,
                         no_activate,
+                        no_deps,
                     },
             }) => {
                 assert_eq!(source, "https://github.com/example/ext.git");
                 assert!(!no_activate);
+                assert!(!no_deps);
             }
             _ => panic!("Expected Extension Install command"),
         }
@@ -1040,6 +2202,7 @@ mod tests {
                     ExtensionAction::Install {
                         source,
                         no_activate,
+                        ..
                     },
             }) => {
                 assert_eq!(source, "https://github.com/example/ext.git");
@@ -1049,6 +2212,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_extension_install_no_deps() {
+        let cli = Cli::parse_from(["repo", "extension", "install", "./my-ext", "--no-deps"]);
+        match cli.command {
+            Some(Commands::Extension {
+                action:
+                    ExtensionAction::Install {
+                        source, no_deps, ..
+                    },
+            }) => {
+                assert_eq!(source, "./my-ext");
+                assert!(no_deps);
+            }
+            _ => panic!("Expected Extension Install command"),
+        }
+    }
+
     #[test]
     fn parse_extension_add_command() {
         let cli = Cli::parse_from(["repo", "extension", "add", "vaultspec"]);
@@ -1110,6 +2290,17 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parse_extension_verify() {
+        let cli = Cli::parse_from(["repo", "extension", "verify", "--json"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Extension {
+                action: ExtensionAction::Verify { json: true }
+            })
+        ));
+    }
+
     #[test]
     fn parse_ext_alias() {
         let cli = Cli::parse_from(["repo", "ext", "list"]);
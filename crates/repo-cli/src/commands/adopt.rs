@@ -0,0 +1,237 @@
+//! Adopt command implementations
+//!
+//! `repo adopt` moves a repository's hand-written AI config files into the
+//! rule registry; `repo ws adopt-plan` plans the same move across several
+//! repositories, extracting the rules they share.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+
+use repo_core::adopt::{self, AdoptAnalysis};
+use repo_core::history::Operation;
+use repo_fs::NormalizedPath;
+
+use super::history::record_config_change;
+use crate::error::{CliError, Result};
+use crate::output::{self, say};
+
+/// Run the adopt command
+///
+/// With `analyze_only`, reports the candidate rules without changing
+/// anything. Otherwise imports the rules from `bundle` (if given) and the
+/// remaining candidates into `.repository/rules/registry.toml`.
+pub fn run_adopt(path: &Path, analyze_only: bool, bundle: Option<&Path>) -> Result<()> {
+    if analyze_only {
+        let analysis = adopt::analyze(path)?;
+        if !output::emit(&analysis)? {
+            print_analysis(&analysis);
+        }
+        return Ok(());
+    }
+
+    if !path.join(".repository").join("config.toml").exists() {
+        return Err(CliError::user(
+            "No .repository/config.toml found. Run 'repo init' first.",
+        ));
+    }
+
+    let bundle = bundle
+        .map(|file| {
+            fs::read_to_string(file).map_err(|e| {
+                CliError::user(format!("Failed to read bundle {}: {}", file.display(), e))
+            })
+        })
+        .transpose()?;
+    let report = adopt::adopt(path, bundle.as_deref())?;
+
+    let added = report.imported.len() + report.adopted.len();
+    if added > 0 {
+        record_config_change(
+            path,
+            Operation::RuleAdd,
+            format!("Adopted {} rule(s)", added),
+            ".repository/rules/registry.toml",
+        );
+    }
+
+    if output::emit(&report)? {
+        return Ok(());
+    }
+
+    for id in &report.imported {
+        say!("   {} {} (from bundle)", "+".green(), id);
+    }
+    for id in &report.adopted {
+        say!("   {} {}", "+".green(), id);
+    }
+    for id in &report.skipped {
+        say!("   {} {} (already in registry)", "=".dimmed(), id);
+    }
+    say!(
+        "{} Adopted {} rule(s). Run 'repo sync' to regenerate tool files.",
+        "OK".green().bold(),
+        added
+    );
+    Ok(())
+}
+
+/// Run the ws adopt-plan command
+///
+/// Analyzes each repository, clusters identical rules, and prints the plan.
+/// With `dir`, also writes `shared.md`, `residual/<repo>.md` and
+/// `plan.json` there; each repository then runs
+/// `repo adopt --bundle shared.md` followed by `repo sync`.
+pub fn run_ws_adopt_plan(repos: &[PathBuf], dir: Option<&Path>) -> Result<()> {
+    let mut analyses = Vec::new();
+    for repo in repos {
+        let name = repo_name(repo)?;
+        if analyses
+            .iter()
+            .any(|(n, _): &(String, AdoptAnalysis)| *n == name)
+        {
+            return Err(CliError::user(format!(
+                "Duplicate repository name '{}'",
+                name
+            )));
+        }
+        analyses.push((name, adopt::analyze(repo)?));
+    }
+    let plan = adopt::plan(&analyses);
+
+    if let Some(dir) = dir {
+        let write = |path: PathBuf, content: &str| {
+            repo_fs::io::write_text(&NormalizedPath::new(path), content)
+        };
+        write(dir.join("shared.md"), &plan.shared_bundle())?;
+        for repo in plan.residual.keys() {
+            if let Some(bundle) = plan.residual_bundle(repo) {
+                write(dir.join("residual").join(format!("{}.md", repo)), &bundle)?;
+            }
+        }
+        write(dir.join("plan.json"), &serde_json::to_string_pretty(&plan)?)?;
+    }
+
+    if output::emit(&plan)? {
+        return Ok(());
+    }
+
+    say!(
+        "{} {} shared rule(s) across {} repositories:",
+        "=>".blue().bold(),
+        plan.shared.len(),
+        repos.len()
+    );
+    for cluster in plan.clusters.iter().filter(|c| c.shared) {
+        let members: Vec<&str> = cluster.members.iter().map(|m| m.repo.as_str()).collect();
+        say!("   {} ({})", cluster.id.bold(), members.join(", "));
+    }
+    for (repo, rules) in &plan.residual {
+        say!(
+            "{} {} residual rule(s) in {}",
+            "=>".blue().bold(),
+            rules.len(),
+            repo.bold()
+        );
+        for rule in rules {
+            say!("   {}", rule.id);
+        }
+    }
+    if let Some(dir) = dir {
+        say!("{} Plan written to {}", "OK".green().bold(), dir.display());
+    }
+    Ok(())
+}
+
+/// Repository name used in plans: the directory name
+fn repo_name(repo: &Path) -> Result<String> {
+    let canonical = repo
+        .canonicalize()
+        .map_err(|e| CliError::user(format!("Cannot access {}: {}", repo.display(), e)))?;
+    canonical
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .ok_or_else(|| CliError::user(format!("Cannot name repository {}", repo.display())))
+}
+
+/// Print candidate rules grouped by file
+fn print_analysis(analysis: &AdoptAnalysis) {
+    if analysis.candidates.is_empty() {
+        say!("No hand-written AI config files found.");
+    }
+    for file in &analysis.files {
+        say!("{} {}", "=>".blue().bold(), file.bold());
+        for candidate in analysis.candidates.iter().filter(|c| &c.source == file) {
+            say!(
+                "   {} {} ({} bytes, {})",
+                "+".green(),
+                candidate.id,
+                candidate.size,
+                &candidate.content_hash[..candidate.content_hash.len().min(15)]
+            );
+        }
+    }
+    for file in &analysis.managed_files {
+        say!("   {} {} (already managed)", "=".dimmed(), file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use repo_core::RuleRegistry;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_adopt_analyze_only_changes_nothing() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("CLAUDE.md"), "## Style\n\nUse tabs.\n").unwrap();
+
+        assert!(run_adopt(temp.path(), true, None).is_ok());
+        assert!(!temp.path().join(".repository").exists());
+    }
+
+    #[test]
+    fn test_adopt_requires_init() {
+        let temp = TempDir::new().unwrap();
+        assert!(run_adopt(temp.path(), false, None).is_err());
+    }
+
+    #[test]
+    fn test_ws_adopt_plan_writes_bundles() {
+        let temp = TempDir::new().unwrap();
+        let mut repos = Vec::new();
+        for (name, extra) in [("alpha", "## Alpha\n\nOnly alpha.\n"), ("beta", "")] {
+            let repo = temp.path().join(name);
+            fs::create_dir_all(&repo).unwrap();
+            fs::write(
+                repo.join("CLAUDE.md"),
+                format!("## Style\n\nUse tabs.\n\n{}", extra),
+            )
+            .unwrap();
+            repos.push(repo);
+        }
+        let out = temp.path().join("plan");
+
+        run_ws_adopt_plan(&repos, Some(&out)).unwrap();
+
+        let shared = fs::read_to_string(out.join("shared.md")).unwrap();
+        assert!(shared.contains("## style\n\nUse tabs."));
+        assert!(out.join("residual").join("alpha.md").exists());
+        assert!(!out.join("residual").join("beta.md").exists());
+
+        // Applying the shared bundle adopts it instead of the local copy
+        let alpha = &repos[0];
+        fs::create_dir_all(alpha.join(".repository")).unwrap();
+        fs::write(
+            alpha.join(".repository").join("config.toml"),
+            "tools = []\n",
+        )
+        .unwrap();
+        run_adopt(alpha, false, Some(&out.join("shared.md"))).unwrap();
+        let registry = RuleRegistry::load(alpha.join(".repository/rules/registry.toml")).unwrap();
+        let ids: Vec<&str> = registry.all_rules().iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["style", "alpha"]);
+    }
+}
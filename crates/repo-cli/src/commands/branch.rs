@@ -5,45 +5,118 @@
 use std::path::Path;
 
 use colored::Colorize;
+use serde::Serialize;
 
 use repo_core::config::Manifest;
-use repo_core::hooks::{HookContext, HookEvent, run_hooks};
-use repo_core::{Mode, ModeBackend, StandardBackend, WorktreeBackend};
+use repo_core::{
+    BranchInfo, BranchListOptions, BranchPruneOptions, Mode, ModeBackend, StandardBackend,
+    WorktreeBackend,
+};
 use repo_fs::NormalizedPath;
 
 use super::sync::detect_mode;
 use crate::error::Result;
+use crate::output::{self, say};
+
+/// A branch in `repo branch list`
+#[derive(Debug, Serialize)]
+pub struct BranchEntry {
+    pub name: String,
+    /// Worktree path, in worktrees mode
+    pub path: Option<String>,
+    pub current: bool,
+    pub main: bool,
+    pub locked: bool,
+    /// Commits ahead of the upstream; absent without one
+    pub ahead: Option<usize>,
+    /// Commits behind the upstream; absent without one
+    pub behind: Option<usize>,
+    /// Whether the working tree has changes; absent if not checked
+    pub dirty: Option<bool>,
+}
+
+impl From<&BranchInfo> for BranchEntry {
+    fn from(branch: &BranchInfo) -> Self {
+        Self {
+            name: branch.name.clone(),
+            path: branch.path.as_ref().map(|p| p.as_str().to_string()),
+            current: branch.is_current,
+            main: branch.is_main,
+            locked: branch.locked,
+            ahead: branch.ahead,
+            behind: branch.behind,
+            dirty: branch.dirty,
+        }
+    }
+}
+
+/// Result of adding, removing, checking out or renaming a branch
+#[derive(Debug, Serialize)]
+pub struct BranchChange {
+    /// `added`, `removed`, `checked-out` or `renamed`
+    pub action: &'static str,
+    pub branch: String,
+    /// The old name of a renamed branch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<String>,
+    /// Worktree path, in worktrees mode
+    pub path: Option<String>,
+}
+
+/// A merged branch `repo branch prune` found
+#[derive(Debug, Serialize)]
+pub struct PrunedBranch {
+    pub name: String,
+    pub path: Option<String>,
+    /// Whether the branch was (or, in a dry run, would be) removed
+    pub pruned: bool,
+    /// Why the branch was kept
+    pub reason: Option<String>,
+}
+
+/// Result of `repo branch prune`
+#[derive(Debug, Serialize)]
+pub struct PruneResult {
+    pub dry_run: bool,
+    pub branches: Vec<PrunedBranch>,
+}
 
 /// Create a ModeBackend for the given root and mode.
 ///
 /// Returns a boxed trait object that can be used for branch operations.
 pub fn create_backend(root: &NormalizedPath, mode: Mode) -> Result<Box<dyn ModeBackend>> {
+    create_backend_with_auto_sync(root, mode, false)
+}
+
+/// Create a ModeBackend that, in Worktrees mode, syncs tool configurations
+/// into the worktrees it checks out or creates when `auto_sync` is set.
+fn create_backend_with_auto_sync(
+    root: &NormalizedPath,
+    mode: Mode,
+    auto_sync: bool,
+) -> Result<Box<dyn ModeBackend>> {
     match mode {
         Mode::Standard => {
             let backend = StandardBackend::new(root.clone())?;
             Ok(Box::new(backend))
         }
         Mode::Worktrees => {
-            let backend = WorktreeBackend::new(root.clone())?;
+            let backend = WorktreeBackend::new(root.clone())?.with_auto_sync(auto_sync);
             Ok(Box::new(backend))
         }
     }
 }
 
-/// Load hooks from config.toml if it exists
-fn load_hooks(path: &Path) -> Vec<repo_core::hooks::HookConfig> {
+/// Load config.toml if it exists and parses
+fn load_manifest(path: &Path) -> Option<Manifest> {
     let config_path = path.join(".repository").join("config.toml");
-    if !config_path.exists() {
-        return Vec::new();
-    }
-    let content = match std::fs::read_to_string(&config_path) {
-        Ok(c) => c,
-        Err(_) => return Vec::new(),
-    };
-    match Manifest::parse(&content) {
-        Ok(m) => m.hooks,
-        Err(_) => Vec::new(),
-    }
+    let content = std::fs::read_to_string(config_path).ok()?;
+    Manifest::parse(&content).ok()
+}
+
+/// Whether config.toml sets `auto_sync_on_switch` under `[sync]`
+fn auto_sync_on_switch(path: &Path) -> bool {
+    load_manifest(path).is_some_and(|m| m.sync.auto_sync_on_switch)
 }
 
 /// Run the branch add command.
@@ -53,39 +126,29 @@ fn load_hooks(path: &Path) -> Vec<repo_core::hooks::HookConfig> {
 pub fn run_branch_add(path: &Path, name: &str, base: Option<&str>) -> Result<()> {
     let root = NormalizedPath::new(path);
     let mode = detect_mode(&root)?;
-    let backend = create_backend(&root, mode)?;
-    let hooks = load_hooks(path);
+    let backend = create_backend_with_auto_sync(&root, mode, auto_sync_on_switch(path))?;
 
     let base_display = base.unwrap_or("HEAD");
-    println!(
+    say!(
         "{} Creating branch {} (from {})...",
         "=>".blue().bold(),
         name.cyan(),
         base_display.yellow()
     );
 
-    // Pre-create hooks
-    let ctx = HookContext::for_branch(name, None);
-    if let Err(e) = run_hooks(&hooks, HookEvent::PreBranchCreate, &ctx, path) {
-        println!("{} Pre-create hook failed: {}", "warn:".yellow().bold(), e);
-    }
-
+    // The backend runs the branch hooks
     backend.create_branch(name, base)?;
-
-    // Post-create hooks
-    let worktree_path = match mode {
-        Mode::Worktrees => Some(root.join(name)),
-        Mode::Standard => None,
-    };
-    let ctx = HookContext::for_branch(name, worktree_path.as_ref().map(|p| p.as_ref()));
-    if let Err(e) = run_hooks(&hooks, HookEvent::PostBranchCreate, &ctx, path) {
-        println!("{} Post-create hook failed: {}", "warn:".yellow().bold(), e);
-    }
+    output::emit(&BranchChange {
+        action: "added",
+        branch: name.to_string(),
+        previous: None,
+        path: (mode == Mode::Worktrees).then(|| root.join(name).as_str().to_string()),
+    })?;
 
     match mode {
         Mode::Worktrees => {
             let wt_path = root.join(name);
-            println!(
+            say!(
                 "{} Branch {} created at {}",
                 "OK".green().bold(),
                 name.cyan(),
@@ -93,7 +156,7 @@ pub fn run_branch_add(path: &Path, name: &str, base: Option<&str>) -> Result<()>
             );
         }
         Mode::Standard => {
-            println!("{} Branch {} created.", "OK".green().bold(), name.cyan());
+            say!("{} Branch {} created.", "OK".green().bold(), name.cyan());
         }
     }
 
@@ -108,34 +171,28 @@ pub fn run_branch_remove(path: &Path, name: &str) -> Result<()> {
     let root = NormalizedPath::new(path);
     let mode = detect_mode(&root)?;
     let backend = create_backend(&root, mode)?;
-    let hooks = load_hooks(path);
 
-    println!("{} Removing branch {}...", "=>".blue().bold(), name.cyan());
-
-    // Pre-delete hooks
-    let ctx = HookContext::for_branch(name, None);
-    if let Err(e) = run_hooks(&hooks, HookEvent::PreBranchDelete, &ctx, path) {
-        println!("{} Pre-delete hook failed: {}", "warn:".yellow().bold(), e);
-    }
+    say!("{} Removing branch {}...", "=>".blue().bold(), name.cyan());
 
+    // The backend runs the branch hooks
     backend.delete_branch(name)?;
-
-    // Post-delete hooks
-    let ctx = HookContext::for_branch(name, None);
-    if let Err(e) = run_hooks(&hooks, HookEvent::PostBranchDelete, &ctx, path) {
-        println!("{} Post-delete hook failed: {}", "warn:".yellow().bold(), e);
-    }
+    output::emit(&BranchChange {
+        action: "removed",
+        branch: name.to_string(),
+        previous: None,
+        path: None,
+    })?;
 
     match mode {
         Mode::Worktrees => {
-            println!(
+            say!(
                 "{} Branch and worktree {} removed.",
                 "OK".green().bold(),
                 name.cyan()
             );
         }
         Mode::Standard => {
-            println!("{} Branch {} removed.", "OK".green().bold(), name.cyan());
+            say!("{} Branch {} removed.", "OK".green().bold(), name.cyan());
         }
     }
 
@@ -145,33 +202,42 @@ pub fn run_branch_remove(path: &Path, name: &str) -> Result<()> {
 /// Run the branch checkout command.
 ///
 /// Switches to a branch. In Standard mode, performs a git checkout.
-/// In Worktrees mode, returns the path to the worktree.
-pub fn run_branch_checkout(path: &Path, name: &str) -> Result<()> {
+/// In Worktrees mode, returns the path to the worktree, syncing tool
+/// configurations into it if `sync` is set or config.toml enables
+/// `auto_sync_on_switch`.
+pub fn run_branch_checkout(path: &Path, name: &str, sync: bool) -> Result<()> {
     let root = NormalizedPath::new(path);
     let mode = detect_mode(&root)?;
-    let backend = create_backend(&root, mode)?;
+    let auto_sync = sync || auto_sync_on_switch(path);
+    let backend = create_backend_with_auto_sync(&root, mode, auto_sync)?;
 
-    println!(
+    say!(
         "{} Switching to branch {}...",
         "=>".blue().bold(),
         name.cyan()
     );
 
     let working_dir = backend.switch_branch(name)?;
+    output::emit(&BranchChange {
+        action: "checked-out",
+        branch: name.to_string(),
+        previous: None,
+        path: (mode == Mode::Worktrees).then(|| working_dir.as_str().to_string()),
+    })?;
 
     match mode {
         Mode::Worktrees => {
-            println!(
+            say!(
                 "{} Worktree for {} is at:\n   {}",
                 "OK".green().bold(),
                 name.cyan(),
                 working_dir.as_str().yellow()
             );
-            println!();
-            println!("  {} {}", "cd".dimmed(), working_dir.as_str().cyan());
+            say!();
+            say!("  {} {}", "cd".dimmed(), working_dir.as_str().cyan());
         }
         Mode::Standard => {
-            println!(
+            say!(
                 "{} Switched to branch {}.",
                 "OK".green().bold(),
                 name.cyan()
@@ -191,7 +257,7 @@ pub fn run_branch_rename(path: &Path, old_name: &str, new_name: &str) -> Result<
     let mode = detect_mode(&root)?;
     let backend = create_backend(&root, mode)?;
 
-    println!(
+    say!(
         "{} Renaming branch {} to {}...",
         "=>".blue().bold(),
         old_name.cyan(),
@@ -199,11 +265,17 @@ pub fn run_branch_rename(path: &Path, old_name: &str, new_name: &str) -> Result<
     );
 
     backend.rename_branch(old_name, new_name)?;
+    output::emit(&BranchChange {
+        action: "renamed",
+        branch: new_name.to_string(),
+        previous: Some(old_name.to_string()),
+        path: (mode == Mode::Worktrees).then(|| root.join(new_name).as_str().to_string()),
+    })?;
 
     match mode {
         Mode::Worktrees => {
             let new_path = root.join(new_name);
-            println!(
+            say!(
                 "{} Branch renamed to {} (worktree at {})",
                 "OK".green().bold(),
                 new_name.cyan(),
@@ -211,7 +283,7 @@ pub fn run_branch_rename(path: &Path, old_name: &str, new_name: &str) -> Result<
             );
         }
         Mode::Standard => {
-            println!(
+            say!(
                 "{} Branch renamed from {} to {}.",
                 "OK".green().bold(),
                 old_name.cyan(),
@@ -225,21 +297,34 @@ pub fn run_branch_rename(path: &Path, old_name: &str, new_name: &str) -> Result<
 
 /// Run the branch list command.
 ///
-/// Lists all branches. Shows branch names with markers for current and main branches.
+/// Lists all branches. Shows branch names with markers for current and main branches,
+/// how far each is ahead of or behind its upstream and, unless `fast` is set,
+/// whether its working tree has uncommitted changes.
 /// In Worktrees mode, also shows the path to each worktree.
-pub fn run_branch_list(path: &Path) -> Result<()> {
+pub fn run_branch_list(path: &Path, fast: bool) -> Result<()> {
     let root = NormalizedPath::new(path);
     let mode = detect_mode(&root)?;
     let backend = create_backend(&root, mode)?;
 
-    let branches = backend.list_branches()?;
+    let branches = backend.list_branches_with(BranchListOptions { fast })?;
+    let entries: Vec<BranchEntry> = branches.iter().map(BranchEntry::from).collect();
+    if output::emit(&entries)? {
+        return Ok(());
+    }
 
     if branches.is_empty() {
-        println!("{} No branches found.", "=>".blue().bold());
+        say!("{} No branches found.", "=>".blue().bold());
         return Ok(());
     }
 
-    println!("{} Branches:", "=>".blue().bold());
+    say!("{} Branches:", "=>".blue().bold());
+
+    let name_width = branches.iter().map(|b| b.name.len()).max().unwrap_or(0);
+    let upstream_width = branches
+        .iter()
+        .map(|b| upstream_column(b).len())
+        .max()
+        .unwrap_or(0);
 
     for branch in branches {
         let mut line = String::new();
@@ -251,32 +336,140 @@ pub fn run_branch_list(path: &Path) -> Result<()> {
             line.push_str("    ");
         }
 
-        // Branch name
+        // Branch name, padded before coloring so the columns line up
+        let name = format!("{:<width$}", branch.name, width = name_width);
         let name_display = if branch.is_current {
-            branch.name.green().bold().to_string()
+            name.green().bold().to_string()
         } else if branch.is_main {
-            branch.name.cyan().to_string()
+            name.cyan().to_string()
         } else {
-            branch.name.clone()
+            name
         };
         line.push_str(&name_display);
 
+        // Upstream and working tree columns
+        let upstream = format!(
+            "{:<width$}",
+            upstream_column(&branch),
+            width = upstream_width
+        );
+        line.push_str(&format!("  {}", upstream.dimmed()));
+        match branch.dirty {
+            Some(true) => line.push_str(&format!("  {}", "dirty".yellow())),
+            Some(false) => line.push_str(&format!("  {}", "clean".green())),
+            None => line.push_str(&format!("  {:<5}", "")),
+        }
+
         // Main branch indicator
         if branch.is_main {
             line.push_str(&format!(" {}", "(default)".dimmed()));
         }
 
+        // Locked worktree indicator
+        if branch.locked {
+            line.push_str(&format!(" {}", "(locked)".yellow()));
+        }
+
         // Path for worktrees mode
         if let Some(path) = branch.path {
             line.push_str(&format!(" -> {}", path.as_str().dimmed()));
         }
 
-        println!("{}", line);
+        say!("{}", line.trim_end());
     }
 
     Ok(())
 }
 
+/// Run the branch prune command.
+///
+/// Removes branches merged into the main branch. In Worktrees mode, also
+/// removes their worktrees, keeping those with uncommitted or untracked
+/// changes unless `force` is set. With `dry_run`, only prints what would
+/// be removed.
+pub fn run_branch_prune(path: &Path, dry_run: bool, force: bool) -> Result<()> {
+    let root = NormalizedPath::new(path);
+    let mode = detect_mode(&root)?;
+    let backend = create_backend(&root, mode)?;
+
+    say!(
+        "{} Pruning branches merged into the main branch{}...",
+        "=>".blue().bold(),
+        if dry_run { " (dry run)" } else { "" }
+    );
+
+    let merged = backend.prune_merged_branches(BranchPruneOptions { dry_run, force })?;
+    let result = PruneResult {
+        dry_run,
+        branches: merged
+            .iter()
+            .map(|branch| PrunedBranch {
+                name: branch.name.clone(),
+                path: branch.path.as_ref().map(|p| p.as_str().to_string()),
+                pruned: branch.is_pruned(),
+                reason: branch.skipped.clone(),
+            })
+            .collect(),
+    };
+    if output::emit(&result)? {
+        return Ok(());
+    }
+
+    if merged.is_empty() {
+        say!("{} No merged branches to prune.", "OK".green().bold());
+        return Ok(());
+    }
+
+    let verb = if dry_run { "Would remove" } else { "Removed" };
+    for branch in &merged {
+        let location = branch
+            .path
+            .as_ref()
+            .map(|p| format!(" -> {}", p.as_str().dimmed()))
+            .unwrap_or_default();
+        match &branch.skipped {
+            None => say!("  {} {}{}", verb.green(), branch.name.cyan(), location),
+            Some(reason) => say!(
+                "  {} {}{} ({})",
+                "Kept".yellow(),
+                branch.name.cyan(),
+                location,
+                reason
+            ),
+        }
+    }
+
+    let pruned = merged.iter().filter(|b| b.is_pruned()).count();
+    let kept = merged.len() - pruned;
+    say!(
+        "{} {} {} merged branch{}, kept {}.",
+        "OK".green().bold(),
+        verb,
+        pruned,
+        if pruned == 1 { "" } else { "es" },
+        kept
+    );
+    if kept > 0 && !force {
+        say!(
+            "   Use {} to remove worktrees with changes.",
+            "--force".cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// Upstream column of the branch list, e.g. "2 ahead, 1 behind"
+fn upstream_column(branch: &BranchInfo) -> String {
+    match (branch.ahead, branch.behind) {
+        (Some(0), Some(0)) => "up to date".to_string(),
+        (Some(ahead), Some(0)) => format!("{} ahead", ahead),
+        (Some(0), Some(behind)) => format!("{} behind", behind),
+        (Some(ahead), Some(behind)) => format!("{} ahead, {} behind", ahead, behind),
+        _ => "no upstream".to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -357,7 +550,7 @@ mod tests {
         )
         .unwrap();
 
-        let result = run_branch_list(path);
+        let result = run_branch_list(path, false);
         assert!(result.is_ok());
     }
 
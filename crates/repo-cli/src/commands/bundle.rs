@@ -0,0 +1,253 @@
+//! Export and import command implementations
+//!
+//! `repo export` writes the repository's shareable configuration (every
+//! config.toml section but `[core]`, the rule files and the registry rules)
+//! as a [`ConfigBundle`]; `repo import` applies a bundle to the current
+//! repository and syncs the tool configurations and MCP servers.
+
+use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+
+use colored::Colorize;
+use dialoguer::Confirm;
+use serde::Serialize;
+
+use repo_core::{ConfigBundle, ImportMode, ImportSummary};
+use repo_fs::{NormalizedPath, RobustnessConfig};
+
+use crate::commands::mcp::{McpToolSync, check_mcp_sync, sync_mcp_servers};
+use crate::commands::sync::{SyncResult, resolve_root, sync};
+use crate::error::{CliError, Result};
+use crate::output::{self, say};
+
+/// Result of `repo export` with a file, for structured output
+#[derive(Debug, Serialize)]
+pub struct ExportResult {
+    pub file: String,
+    pub archive: bool,
+    /// Rule files and registry rules in the bundle
+    pub rules: usize,
+}
+
+/// Result of `repo import`
+#[derive(Debug, Serialize)]
+pub struct ImportResult {
+    #[serde(flatten)]
+    pub summary: ImportSummary,
+    /// The sync that followed the import
+    pub sync: SyncResult,
+    /// The MCP servers synced to each enabled tool after the import
+    pub mcp: Vec<McpToolSync>,
+}
+
+/// Run the export command
+///
+/// Writes the bundle to `file`, as a gzipped tar archive with `archive`,
+/// or prints it without a file: as TOML, or in the structured format.
+pub fn run_export(path: &Path, file: Option<&Path>, archive: bool) -> Result<()> {
+    let root = resolve_root(path)?;
+    let bundle = ConfigBundle::export(root.as_ref())?;
+
+    let Some(file) = file else {
+        if !output::emit(&bundle)? {
+            print!("{}", bundle.to_toml()?);
+        }
+        return Ok(());
+    };
+    let bytes = if archive {
+        bundle.to_archive()?
+    } else {
+        bundle.to_toml()?.into_bytes()
+    };
+    repo_fs::io::write_atomic(
+        &NormalizedPath::new(file),
+        &bytes,
+        RobustnessConfig::current(),
+    )?;
+
+    let rules = bundle.rule_files.len() + bundle.rules.len();
+    output::emit(&ExportResult {
+        file: file.display().to_string(),
+        archive,
+        rules,
+    })?;
+    say!(
+        "{} Exported configuration and {} rule(s) to {}",
+        "OK".green().bold(),
+        rules,
+        file.display().to_string().cyan()
+    );
+    Ok(())
+}
+
+/// Run the import command
+///
+/// Applies the bundle in `file` (TOML or archive), syncs, and pushes the
+/// MCP servers to the enabled tools as `repo mcp sync` does. In merge mode,
+/// rules the repository defines differently are kept unless the user
+/// chooses to overwrite them; with `no_input`, or without a terminal to
+/// ask on, such conflicts fail the import before anything is changed.
+pub fn run_import(path: &Path, file: &Path, mode: ImportMode, no_input: bool) -> Result<()> {
+    let root = resolve_root(path)?;
+    let bytes = fs::read(file)
+        .map_err(|e| CliError::user(format!("Cannot read {}: {}", file.display(), e)))?;
+    let bundle = ConfigBundle::read(&bytes)?;
+
+    let overwrite = match mode {
+        ImportMode::Merge => choose_overwrites(&bundle.conflicts(root.as_ref())?, no_input)?,
+        ImportMode::Replace => Vec::new(),
+    };
+
+    say!(
+        "{} Importing {} ({})...",
+        "=>".blue().bold(),
+        file.display().to_string().cyan(),
+        match mode {
+            ImportMode::Merge => "merge",
+            ImportMode::Replace => "replace",
+        }
+    );
+    let summary = bundle.apply(root.as_ref(), mode, &overwrite)?;
+
+    let sections = [
+        ("Tools added", &summary.tools_added, "+".green()),
+        ("Presets added", &summary.presets_added, "+".green()),
+        ("Rules added", &summary.rules_added, "+".green()),
+        ("Rules replaced", &summary.rules_replaced, "~".yellow()),
+        ("Rules kept", &summary.rules_kept, "=".dimmed()),
+    ];
+    for (label, items, marker) in sections {
+        if !items.is_empty() {
+            say!("   {}:", label);
+            for item in items {
+                say!("     {} {}", marker, item);
+            }
+        }
+    }
+    say!("{} Import complete.", "OK".green().bold());
+
+    let sync = sync(root.as_ref(), false, false, None)?;
+    let mcp = sync_mcp_servers(&root)?;
+    let result = ImportResult { summary, sync, mcp };
+    output::emit(&result)?;
+    check_mcp_sync(&result.mcp)
+}
+
+/// Ask which of the conflicting rules to overwrite
+fn choose_overwrites(conflicts: &[String], no_input: bool) -> Result<Vec<String>> {
+    if conflicts.is_empty() {
+        return Ok(Vec::new());
+    }
+    if no_input || !std::io::stdin().is_terminal() {
+        return Err(CliError::user(format!(
+            "Rule(s) {} differ from the bundle. Import interactively to choose which to \
+             overwrite, or with --replace to overwrite them all.",
+            conflicts.join(", ")
+        )));
+    }
+
+    let mut overwrite = Vec::new();
+    for id in conflicts {
+        let replace = Confirm::new()
+            .with_prompt(format!(
+                "Rule '{}' differs from the bundle. Overwrite it?",
+                id
+            ))
+            .default(false)
+            .interact()?;
+        if replace {
+            overwrite.push(id.clone());
+        }
+    }
+    Ok(overwrite)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_repo(dir: &Path, config: &str) {
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::create_dir_all(dir.join(".repository/rules")).unwrap();
+        fs::write(dir.join(".repository/config.toml"), config).unwrap();
+    }
+
+    #[test]
+    fn test_export_then_import() {
+        let source = TempDir::new().unwrap();
+        create_repo(
+            source.path(),
+            "tools = [\"claude\"]\n\n[core]\nmode = \"standard\"\n",
+        );
+        fs::write(
+            source.path().join(".repository/rules/style.md"),
+            "Use snake_case.",
+        )
+        .unwrap();
+
+        for archive in [false, true] {
+            let bundle_path = source.path().join("bundle.out");
+            run_export(source.path(), Some(&bundle_path), archive).unwrap();
+
+            let target = TempDir::new().unwrap();
+            create_repo(
+                target.path(),
+                "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n",
+            );
+            run_import(target.path(), &bundle_path, ImportMode::Merge, true).unwrap();
+
+            let config = fs::read_to_string(target.path().join(".repository/config.toml")).unwrap();
+            assert!(config.contains("\"claude\"") && config.contains("\"cursor\""));
+            assert!(target.path().join(".repository/rules/style.md").exists());
+            // Import syncs the tool configurations
+            assert!(target.path().join(".repository/ledger.toml").exists());
+            assert!(target.path().join("CLAUDE.md").exists());
+        }
+    }
+
+    #[test]
+    fn test_import_syncs_mcp_servers_to_tools() {
+        let source = TempDir::new().unwrap();
+        create_repo(
+            source.path(),
+            "tools = []\n\n[core]\nmode = \"standard\"\n\n\
+             [mcp.servers.docs.transport]\ntype = \"stdio\"\ncommand = \"docs-server\"\n",
+        );
+        let bundle_path = source.path().join("bundle.toml");
+        run_export(source.path(), Some(&bundle_path), false).unwrap();
+
+        let target = TempDir::new().unwrap();
+        create_repo(
+            target.path(),
+            "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n",
+        );
+        run_import(target.path(), &bundle_path, ImportMode::Merge, true).unwrap();
+
+        let content = fs::read_to_string(target.path().join(".cursor/mcp.json")).unwrap();
+        let config: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(config["mcpServers"]["docs"]["command"], "docs-server");
+    }
+
+    #[test]
+    fn test_import_conflict_without_input_fails() {
+        let source = TempDir::new().unwrap();
+        create_repo(source.path(), "tools = []\n\n[core]\nmode = \"standard\"\n");
+        fs::write(source.path().join(".repository/rules/style.md"), "Tabs.").unwrap();
+        let bundle_path = source.path().join("bundle.toml");
+        run_export(source.path(), Some(&bundle_path), false).unwrap();
+
+        let target = TempDir::new().unwrap();
+        create_repo(target.path(), "tools = []\n\n[core]\nmode = \"standard\"\n");
+        let rule = target.path().join(".repository/rules/style.md");
+        fs::write(&rule, "Spaces.").unwrap();
+
+        let err = run_import(target.path(), &bundle_path, ImportMode::Merge, true).unwrap_err();
+        assert!(err.to_string().contains("style"), "{err}");
+        assert_eq!(fs::read_to_string(&rule).unwrap(), "Spaces.");
+
+        run_import(target.path(), &bundle_path, ImportMode::Replace, true).unwrap();
+        assert_eq!(fs::read_to_string(&rule).unwrap(), "Tabs.");
+    }
+}
@@ -3,157 +3,409 @@
 use std::path::Path;
 
 use colored::Colorize;
-use repo_fs::NormalizedPath;
-use repo_tools::{ToolCategory, ToolRegistry};
+use serde::Serialize;
 
+use repo_core::{ConfigResolver, Mode, ModeMigration, format_config, json_to_toml_value};
+use repo_fs::{ConfigStore, NormalizedPath};
+use repo_tools::{ToolDispatcher, ToolResolution, ToolSource};
+
+use crate::commands::sync::resolve_root;
 use crate::commands::tool::load_manifest;
 use crate::error::{CliError, Result};
+use crate::output::{self, say};
 
 /// Path to config.toml within a repository
 const CONFIG_PATH: &str = ".repository/config.toml";
 
+/// The committed configuration, for structured output
+#[derive(Debug, Serialize)]
+pub struct ConfigSummary {
+    pub mode: String,
+    pub tools: Vec<String>,
+    pub rules: Vec<String>,
+    /// Names of the configured presets, sorted
+    pub presets: Vec<String>,
+}
+
+/// Result of `repo config set-mode`
+#[derive(Debug, Serialize)]
+pub struct SetModeResult {
+    pub from: String,
+    pub to: String,
+    /// Steps of the conversion, in order; empty if already in `to`
+    pub steps: Vec<String>,
+    pub dry_run: bool,
+}
+
+/// Result of `repo config fmt`
+#[derive(Debug, Serialize)]
+pub struct ConfigFmtResult {
+    pub path: &'static str,
+    /// Whether the file was already in canonical form
+    pub formatted: bool,
+    pub check: bool,
+}
+
+/// A tool's details, for structured output
+#[derive(Debug, Serialize)]
+pub struct ToolInfo {
+    pub name: String,
+    pub slug: String,
+    pub category: String,
+    /// `builtin`, `repository` or `extension`
+    pub implementation: &'static str,
+    /// Why this implementation is the active one
+    pub reason: String,
+    pub config_path: String,
+    pub additional_paths: Vec<String>,
+    pub supports_custom_instructions: bool,
+    pub supports_mcp: bool,
+    pub supports_rules_directory: bool,
+    /// Whether the tool is enabled in config.toml; absent without one
+    pub active: Option<bool>,
+}
+
 /// Display the current repository configuration
-pub fn run_config_show(path: &Path, json: bool) -> Result<()> {
+///
+/// With `effective`, prints the configuration resolved from all layers as
+/// a canonical TOML document instead. With `diff`, prints each key whose
+/// effective value differs from the committed `config.toml`.
+pub fn run_config_show(path: &Path, effective: bool, diff: bool) -> Result<()> {
     let config_path = NormalizedPath::new(path.join(CONFIG_PATH));
     let manifest = load_manifest(&config_path)?;
 
-    if json {
-        let output = serde_json::json!({
-            "mode": manifest.core.mode,
-            "tools": manifest.tools,
-            "rules": manifest.rules,
-            "presets": manifest.presets.keys().collect::<Vec<_>>(),
-        });
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&output).unwrap_or_default()
-        );
+    if diff {
+        return show_config_diff(path);
+    }
+
+    if effective {
+        let resolved = ConfigResolver::new(NormalizedPath::new(path)).resolve()?;
+        if !output::emit(&resolved)? {
+            print!("{}", resolved.to_toml()?);
+        }
+        return Ok(());
+    }
+
+    let mut presets: Vec<String> = manifest.presets.keys().cloned().collect();
+    presets.sort();
+    let summary = ConfigSummary {
+        mode: manifest.core.mode.clone(),
+        tools: manifest.tools.clone(),
+        rules: manifest.rules.clone(),
+        presets,
+    };
+    if output::emit(&summary)? {
         return Ok(());
     }
 
-    println!("{}", "Repository Configuration".bold());
-    println!();
+    say!("{}", "Repository Configuration".bold());
+    say!();
 
-    println!("  {:<12} {}", "Mode:".dimmed(), manifest.core.mode);
-    println!();
+    say!("  {:<12} {}", "Mode:".dimmed(), manifest.core.mode);
+    say!();
 
     // Tools
     if manifest.tools.is_empty() {
-        println!("  {:<12} {}", "Tools:".dimmed(), "(none)".dimmed());
+        say!("  {:<12} {}", "Tools:".dimmed(), "(none)".dimmed());
     } else {
-        println!("  {}:", "Tools".dimmed());
+        say!("  {}:", "Tools".dimmed());
         for tool in &manifest.tools {
-            println!("    {} {}", "+".green(), tool);
+            say!("    {} {}", "+".green(), tool);
         }
     }
-    println!();
+    say!();
 
     // Presets
     if manifest.presets.is_empty() {
-        println!("  {:<12} {}", "Presets:".dimmed(), "(none)".dimmed());
+        say!("  {:<12} {}", "Presets:".dimmed(), "(none)".dimmed());
     } else {
-        println!("  {}:", "Presets".dimmed());
+        say!("  {}:", "Presets".dimmed());
         for name in manifest.presets.keys() {
-            println!("    {} {}", "+".green(), name);
+            say!("    {} {}", "+".green(), name);
         }
     }
-    println!();
+    say!();
 
     // Rules
     let rule_count = manifest.rules.len();
     if rule_count == 0 {
-        println!("  {:<12} {}", "Rules:".dimmed(), "(none)".dimmed());
+        say!("  {:<12} {}", "Rules:".dimmed(), "(none)".dimmed());
     } else {
-        println!("  {}:", "Rules".dimmed());
+        say!("  {}:", "Rules".dimmed());
         for rule in &manifest.rules {
-            println!("    {} {}", "+".green(), rule);
+            say!("    {} {}", "+".green(), rule);
         }
     }
 
     Ok(())
 }
 
-/// Display detailed information about a specific tool
-pub fn run_tool_info(path: &Path, name: &str) -> Result<()> {
-    let registry = ToolRegistry::with_builtins();
-
-    let reg = registry.get(name).ok_or_else(|| {
+/// Convert the repository to `mode`
+///
+/// With `dry_run`, prints the planned steps without performing them.
+pub fn run_config_set_mode(path: &Path, mode: &str, dry_run: bool) -> Result<()> {
+    let root = resolve_root(path)?;
+    let mode: Mode = mode.parse().map_err(|_| {
         CliError::user(format!(
-            "Unknown tool '{}'. Use 'repo list-tools' to see available tools.",
-            name
+            "Invalid mode '{}'. Must be 'standard' or 'worktrees'.",
+            mode
         ))
     })?;
+    let migration = ModeMigration::plan(&root, mode)?;
+    output::emit(&SetModeResult {
+        from: migration.from.to_string(),
+        to: migration.to.to_string(),
+        steps: migration.steps.iter().map(ToString::to_string).collect(),
+        dry_run,
+    })?;
+
+    if migration.steps.is_empty() {
+        say!(
+            "{} Repository is already in {} mode.",
+            "OK".green().bold(),
+            mode
+        );
+        return Ok(());
+    }
+
+    let verb = if dry_run {
+        "Would convert"
+    } else {
+        "Converting"
+    };
+    say!(
+        "{} {} from {} to {} mode:",
+        "=>".blue().bold(),
+        verb,
+        migration.from,
+        migration.to
+    );
+    for step in &migration.steps {
+        say!("   {} {}", "-".dimmed(), step);
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    migration.apply()?;
+    say!(
+        "{} Repository is now in {} mode. Run {} to verify.",
+        "OK".green().bold(),
+        mode,
+        "repo check".cyan()
+    );
+    Ok(())
+}
+
+/// Rewrite config.toml in canonical form
+///
+/// With `check`, leaves the file alone and fails if it is not canonical.
+pub fn run_config_fmt(path: &Path, check: bool) -> Result<()> {
+    let config_path = resolve_root(path)?.join(CONFIG_PATH);
+    if !config_path.exists() {
+        return Err(CliError::user(format!(
+            "Config file not found: {}. Run 'repo init' first.",
+            config_path
+        )));
+    }
+    // Held from the read to the write, so no concurrent edit is lost
+    let lock = ConfigStore::new().lock(&config_path)?;
+    let content = lock.read()?;
+    let formatted = format_config(&content)?;
+    output::emit(&ConfigFmtResult {
+        path: CONFIG_PATH,
+        formatted: formatted == content,
+        check,
+    })?;
+
+    if formatted == content {
+        say!("{} {} is formatted.", "OK".green().bold(), CONFIG_PATH);
+        return Ok(());
+    }
+    if check {
+        return Err(CliError::user(format!(
+            "{} is not formatted. Run 'repo config fmt' to fix it.",
+            CONFIG_PATH
+        )));
+    }
+
+    lock.write(&formatted)?;
+    say!("{} Formatted {}.", "OK".green().bold(), CONFIG_PATH);
+    Ok(())
+}
+
+/// Display where the effective configuration differs from the committed one
+fn show_config_diff(path: &Path) -> Result<()> {
+    let differences = ConfigResolver::new(NormalizedPath::new(path)).diff_committed()?;
+    if output::emit(&differences)? {
+        return Ok(());
+    }
+
+    if differences.is_empty() {
+        say!(
+            "{} Effective configuration matches {}",
+            "OK".green().bold(),
+            CONFIG_PATH
+        );
+        return Ok(());
+    }
+
+    say!(
+        "{} from {} in {} key(s):",
+        "Effective configuration differs".bold(),
+        CONFIG_PATH,
+        differences.len()
+    );
+    for difference in &differences {
+        let render = |value: &Option<serde_json::Value>| match value {
+            Some(value) => json_to_toml_value(value),
+            None => "(not set)".dimmed().to_string(),
+        };
+        let layers = if difference.layers.is_empty() {
+            "(unknown)".dimmed().to_string()
+        } else {
+            difference
+                .layers
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        say!();
+        say!("  {}", difference.key.cyan());
+        say!(
+            "    {:<11} {}",
+            "committed:".dimmed(),
+            render(&difference.committed)
+        );
+        say!(
+            "    {:<11} {}",
+            "effective:".dimmed(),
+            render(&difference.effective)
+        );
+        say!("    {:<11} {}", "layer:".dimmed(), layers);
+    }
 
-    let category_str = match reg.category {
-        ToolCategory::Ide => "IDE",
-        ToolCategory::CliAgent => "CLI Agent",
-        ToolCategory::Autonomous => "Autonomous Agent",
-        ToolCategory::Copilot => "Copilot",
+    Ok(())
+}
+
+/// Display detailed information about a specific tool
+///
+/// Includes which implementation is active (built-in or a definition in
+/// `.repository/tools/`) and why.
+pub fn run_tool_info(path: &Path, name: &str) -> Result<()> {
+    let dispatcher = ToolDispatcher::from_repository(&NormalizedPath::new(path));
+
+    let (resolution, definition) = dispatcher
+        .resolution(name)
+        .zip(dispatcher.definition(name))
+        .ok_or_else(|| {
+            let unknown = dispatcher.validate_name(name).unwrap_err();
+            CliError::user(format!(
+                "{}. Use 'repo list-tools' to see available tools.",
+                unknown
+            ))
+        })?;
+
+    let (display_name, category_str) = match dispatcher.get_registration(name) {
+        Some(reg) => (reg.name.as_str(), reg.category.label()),
+        None => (definition.meta.name.as_str(), "Custom"),
     };
 
-    println!("{}", "Tool Information".bold());
-    println!();
-    println!("  {:<16} {}", "Name:".dimmed(), reg.name);
-    println!("  {:<16} {}", "Slug:".dimmed(), reg.slug);
-    println!("  {:<16} {}", "Category:".dimmed(), category_str);
-    println!(
+    let (source, implementation) = match resolution.source() {
+        ToolSource::Builtin => ("builtin", "builtin".to_string()),
+        ToolSource::Repository => (
+            "repository",
+            format!("repository (.repository/tools/{}.toml)", name),
+        ),
+        ToolSource::Extension => ("extension", "extension".to_string()),
+    };
+    let capabilities = &definition.capabilities;
+    let active = load_manifest(&NormalizedPath::new(path.join(CONFIG_PATH)))
+        .ok()
+        .map(|manifest| manifest.tools.iter().any(|t| t == name));
+
+    let info = ToolInfo {
+        name: display_name.to_string(),
+        slug: name.to_string(),
+        category: category_str.to_string(),
+        implementation: source,
+        reason: resolution.reason().to_string(),
+        config_path: definition.integration.config_path.clone(),
+        additional_paths: definition.integration.additional_paths.clone(),
+        supports_custom_instructions: capabilities.supports_custom_instructions,
+        supports_mcp: capabilities.supports_mcp,
+        supports_rules_directory: capabilities.supports_rules_directory,
+        active,
+    };
+    if output::emit(&info)? {
+        return Ok(());
+    }
+
+    say!("{}", "Tool Information".bold());
+    say!();
+    say!("  {:<16} {}", "Name:".dimmed(), display_name);
+    say!("  {:<16} {}", "Slug:".dimmed(), name);
+    say!("  {:<16} {}", "Category:".dimmed(), category_str);
+    say!("  {:<16} {}", "Implementation:".dimmed(), implementation);
+    let reason = resolution.reason();
+    if resolution == ToolResolution::BuiltinShadowed {
+        say!("  {:<16} {}", "".dimmed(), reason.yellow());
+    } else {
+        say!("  {:<16} {}", "".dimmed(), reason.dimmed());
+    }
+    say!(
         "  {:<16} {}",
         "Config path:".dimmed(),
-        reg.definition.integration.config_path
+        definition.integration.config_path
     );
 
-    if !reg.definition.integration.additional_paths.is_empty() {
-        for extra in &reg.definition.integration.additional_paths {
-            println!("  {:<16} {}", "".dimmed(), extra);
+    if !definition.integration.additional_paths.is_empty() {
+        for extra in &definition.integration.additional_paths {
+            say!("  {:<16} {}", "".dimmed(), extra);
         }
     }
 
     // Capabilities
-    println!();
-    println!("  {}:", "Capabilities".dimmed());
-    println!(
+    say!();
+    say!("  {}:", "Capabilities".dimmed());
+    say!(
         "    Instructions:  {}",
-        if reg.supports_instructions() {
+        if capabilities.supports_custom_instructions {
             "yes".green()
         } else {
             "no".dimmed()
         }
     );
-    println!(
+    say!(
         "    MCP:           {}",
-        if reg.supports_mcp() {
+        if capabilities.supports_mcp {
             "yes".green()
         } else {
             "no".dimmed()
         }
     );
-    println!(
+    say!(
         "    Rules dir:     {}",
-        if reg.supports_rules_directory() {
+        if capabilities.supports_rules_directory {
             "yes".green()
         } else {
             "no".dimmed()
         }
     );
 
-    // Check if active in current project
-    let config_path = NormalizedPath::new(path.join(CONFIG_PATH));
-    match load_manifest(&config_path) {
-        Ok(manifest) => {
-            let is_active = manifest.tools.iter().any(|t| t == name);
-            println!();
-            if is_active {
-                println!(
-                    "  {:<16} {}",
-                    "Status:".dimmed(),
-                    "Active (in current project)".green()
-                );
-            } else {
-                println!("  {:<16} {}", "Status:".dimmed(), "Not active".dimmed());
-            }
-        }
-        Err(_) => {
-            // No config.toml found, skip status
+    // Status in the current project, if it has a config.toml
+    if let Some(is_active) = active {
+        say!();
+        if is_active {
+            say!(
+                "  {:<16} {}",
+                "Status:".dimmed(),
+                "Active (in current project)".green()
+            );
+        } else {
+            say!("  {:<16} {}", "Status:".dimmed(), "Not active".dimmed());
         }
     }
 
@@ -180,25 +432,41 @@ mod tests {
             temp_dir.path(),
             "tools = [\"cursor\", \"claude\"]\n\n[core]\nmode = \"standard\"\n",
         );
-        let result = run_config_show(temp_dir.path(), false);
+        let result = run_config_show(temp_dir.path(), false, false);
         assert!(result.is_ok());
     }
 
     #[test]
-    fn test_config_show_json_runs() {
+    fn test_config_show_effective_runs() {
         let temp_dir = TempDir::new().unwrap();
         create_test_config(
             temp_dir.path(),
             "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n",
         );
-        let result = run_config_show(temp_dir.path(), true);
+        let result = run_config_show(temp_dir.path(), true, false);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_config_show_diff_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_config(
+            temp_dir.path(),
+            "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n",
+        );
+        std::fs::write(
+            temp_dir.path().join(".repository/config.local.toml"),
+            "tools = [\"vscode\"]\n",
+        )
+        .unwrap();
+        let result = run_config_show(temp_dir.path(), false, true);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_config_show_no_config() {
         let temp_dir = TempDir::new().unwrap();
-        let result = run_config_show(temp_dir.path(), false);
+        let result = run_config_show(temp_dir.path(), false, false);
         assert!(result.is_err());
     }
 
@@ -220,6 +488,81 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_tool_info_repository_tools() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_config(
+            temp_dir.path(),
+            "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n",
+        );
+        let tools_dir = temp_dir.path().join(".repository").join("tools");
+        std::fs::create_dir_all(&tools_dir).unwrap();
+        std::fs::write(
+            tools_dir.join("cursor.toml"),
+            "[meta]\nname = \"Cursor\"\nslug = \"cursor\"\noverride = true\n\n\
+             [integration]\nconfig_path = \".cursor/team.md\"\ntype = \"markdown\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tools_dir.join("inhouse.toml"),
+            "[meta]\nname = \"In-house\"\nslug = \"inhouse\"\n\n\
+             [integration]\nconfig_path = \".inhouse.md\"\ntype = \"markdown\"\n",
+        )
+        .unwrap();
+
+        assert!(run_tool_info(temp_dir.path(), "cursor").is_ok());
+        assert!(run_tool_info(temp_dir.path(), "inhouse").is_ok());
+    }
+
+    #[test]
+    fn test_config_set_mode_dry_run_changes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        repo_test_utils::git::real_git_repo_with_commit(temp_dir.path());
+        create_test_config(
+            temp_dir.path(),
+            "tools = []\n\n[core]\nmode = \"standard\"\n",
+        );
+
+        assert!(run_config_set_mode(temp_dir.path(), "worktrees", true).is_ok());
+        assert!(temp_dir.path().join(".git").is_dir());
+        assert!(!temp_dir.path().join("main").exists());
+
+        let err = run_config_set_mode(temp_dir.path(), "bare", true).unwrap_err();
+        assert!(err.to_string().contains("Invalid mode 'bare'"));
+        assert!(run_config_set_mode(temp_dir.path(), "standard", false).is_ok());
+    }
+
+    #[test]
+    fn test_config_fmt_sorts_tools() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_config(
+            temp_dir.path(),
+            "tools = [\"cursor\",\"claude\"]\n[core]\nmode=\"standard\"\n",
+        );
+
+        run_config_fmt(temp_dir.path(), false).unwrap();
+        let content =
+            std::fs::read_to_string(temp_dir.path().join(".repository/config.toml")).unwrap();
+        assert_eq!(
+            content,
+            "tools = [\"claude\", \"cursor\"]\n\n[core]\nmode = \"standard\"\n"
+        );
+    }
+
+    #[test]
+    fn test_config_fmt_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let unsorted = "tools = [\"cursor\", \"claude\"]\n\n[core]\nmode = \"standard\"\n";
+        create_test_config(temp_dir.path(), unsorted);
+
+        assert!(run_config_fmt(temp_dir.path(), true).is_err());
+        let config_path = temp_dir.path().join(".repository/config.toml");
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), unsorted);
+
+        run_config_fmt(temp_dir.path(), false).unwrap();
+        assert!(run_config_fmt(temp_dir.path(), true).is_ok());
+    }
+
     #[test]
     fn test_tool_info_without_config() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,126 @@
+//! Context command implementation
+//!
+//! Shows what repository context was detected for the current directory,
+//! optionally with the step-by-step detection trail.
+
+use std::path::Path;
+
+use colored::Colorize;
+use serde::Serialize;
+
+use repo_core::context::detect;
+
+use crate::error::Result;
+use crate::output::{self, say};
+
+/// The detected context, for structured output
+#[derive(Debug, Serialize)]
+pub struct ContextResult {
+    pub kind: String,
+    pub root: Option<String>,
+    pub mode: Option<String>,
+    pub container: Option<String>,
+    pub worktree: Option<String>,
+    pub config_path: Option<String>,
+    /// Every check performed during detection
+    pub trail: Vec<String>,
+}
+
+/// Run the context command
+///
+/// Prints the detected root, mode, container, worktree, and config path.
+/// With `explain`, also prints every check performed during detection.
+pub fn run_context(path: &Path, explain: bool) -> Result<()> {
+    let info = detect(path);
+
+    let result = ContextResult {
+        kind: info.kind.to_string(),
+        root: info.root.as_ref().map(|r| r.as_str().to_string()),
+        mode: info.mode.map(|m| m.to_string()),
+        container: info.container.as_ref().map(|c| c.as_str().to_string()),
+        worktree: info.worktree.clone(),
+        config_path: info.config_path.as_ref().map(|c| c.as_str().to_string()),
+        trail: info.trail.clone(),
+    };
+    if output::emit(&result)? {
+        return Ok(());
+    }
+
+    say!("{}", "Repository Context".bold().underline());
+    say!();
+    say!("  {}: {}", "Kind".bold(), info.kind.to_string().cyan());
+
+    let none = || "none".dimmed().to_string();
+    say!(
+        "  {}: {}",
+        "Root".bold(),
+        info.root
+            .as_ref()
+            .map(|r| r.as_str().yellow().to_string())
+            .unwrap_or_else(none)
+    );
+    say!(
+        "  {}: {}",
+        "Mode".bold(),
+        info.mode.map(|m| m.to_string()).unwrap_or_else(none)
+    );
+    say!(
+        "  {}: {}",
+        "Container".bold(),
+        info.container
+            .as_ref()
+            .map(|c| c.as_str().to_string())
+            .unwrap_or_else(none)
+    );
+    say!(
+        "  {}: {}",
+        "Worktree".bold(),
+        info.worktree.clone().unwrap_or_else(none)
+    );
+    say!(
+        "  {}: {}",
+        "Config".bold(),
+        info.config_path
+            .as_ref()
+            .map(|c| c.as_str().to_string())
+            .unwrap_or_else(none)
+    );
+
+    if explain {
+        say!();
+        say!("{}", "Detection trail".bold());
+        say!("{}", info.explain());
+    } else if !info.is_repo() || info.is_partial() {
+        say!();
+        say!("Run {} to see why.", "repo context --explain".cyan());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_context_in_repo() {
+        let temp = TempDir::new().unwrap();
+        let repo_dir = temp.path().join(".repository");
+        fs::create_dir_all(&repo_dir).unwrap();
+        fs::write(
+            repo_dir.join("config.toml"),
+            "[core]\nmode = \"standard\"\n",
+        )
+        .unwrap();
+
+        assert!(run_context(temp.path(), true).is_ok());
+    }
+
+    #[test]
+    fn test_context_not_a_repo() {
+        let temp = TempDir::new().unwrap();
+        assert!(run_context(temp.path(), false).is_ok());
+    }
+}
@@ -5,19 +5,50 @@
 use std::path::Path;
 
 use colored::Colorize;
-use serde_json::json;
+use serde::Serialize;
+use serde_json::{Value, json};
 
-use repo_core::{Mode, SyncEngine, SyncOptions};
+use repo_content::{Document, Format, SemanticDiff};
+use repo_core::{FilePreview, Mode, SyncEngine, SyncOptions};
 use repo_fs::NormalizedPath;
 
 use super::sync::{detect_mode, resolve_root};
 use crate::error::Result;
+use crate::output::{self, say};
+
+/// What sync would change, for structured output
+#[derive(Debug, Serialize)]
+pub struct DiffReport {
+    pub has_changes: bool,
+    pub changes: Vec<DiffChange>,
+    /// What each file would look like after the sync, change by change
+    pub files: Vec<FileDiff>,
+    pub errors: Vec<String>,
+    pub success: bool,
+}
+
+/// One action sync would take
+#[derive(Debug, Serialize)]
+pub struct DiffChange {
+    /// The action, without the dry-run prefix
+    pub action: String,
+    /// The action as the sync engine reported it
+    pub raw: String,
+}
+
+/// The semantic diff of one file sync would change
+#[derive(Debug, Serialize)]
+pub struct FileDiff {
+    pub tool: String,
+    pub file: String,
+    pub diff: Value,
+}
 
 /// Run the diff command
 ///
 /// Shows what changes sync would make without applying them.
 /// This is essentially a sync with dry_run=true, but with diff-style output.
-pub fn run_diff(path: &Path, json: bool) -> Result<()> {
+pub fn run_diff(path: &Path) -> Result<()> {
     let root = resolve_root(path)?;
     let mode = detect_mode(&root)?;
     let engine = SyncEngine::new(root.clone(), mode)?;
@@ -26,24 +57,31 @@ pub fn run_diff(path: &Path, json: bool) -> Result<()> {
     let options = SyncOptions { dry_run: true };
     let report = engine.sync_with_options(options)?;
 
-    if json {
-        // JSON output for CI/CD integration
-        let json_output = json!({
-            "has_changes": !report.actions.is_empty(),
-            "changes": report.actions.iter()
-                .map(|a| {
+    if output::is_structured() {
+        let mut errors = report.errors.clone();
+        let files = match engine.preview() {
+            Ok(previews) => previews.iter().map(file_diff).collect(),
+            Err(e) => {
+                errors.push(format!("Failed to preview changes: {}", e));
+                Vec::new()
+            }
+        };
+
+        output::emit(&DiffReport {
+            has_changes: !report.actions.is_empty(),
+            changes: report
+                .actions
+                .iter()
+                .map(|a| DiffChange {
                     // Strip "[dry-run] Would " prefix if present
-                    let clean = a.strip_prefix("[dry-run] Would ").unwrap_or(a);
-                    json!({
-                        "action": clean,
-                        "raw": a
-                    })
+                    action: a.strip_prefix("[dry-run] Would ").unwrap_or(a).to_string(),
+                    raw: a.clone(),
                 })
-                .collect::<Vec<_>>(),
-            "errors": report.errors,
-            "success": report.success,
-        });
-        println!("{}", serde_json::to_string_pretty(&json_output)?);
+                .collect(),
+            files,
+            errors,
+            success: report.success,
+        })?;
     } else {
         // Human-readable diff-style output
         print_diff_output(&report.actions, &report.errors, &root, mode);
@@ -52,27 +90,62 @@ pub fn run_diff(path: &Path, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// The semantic diff of a file sync would change
+///
+/// JSON, TOML and YAML files are compared value by value, reporting the
+/// path of each change; other files, and files that don't parse, line by
+/// line. A file that doesn't exist yet compares as empty.
+fn file_diff(preview: &FilePreview) -> FileDiff {
+    let format = Path::new(&preview.file)
+        .extension()
+        .and_then(|ext| Format::from_extension(&ext.to_string_lossy()))
+        .filter(|format| matches!(format, Format::Json | Format::Toml | Format::Yaml));
+    let normalize = |content: &Option<String>, format: Format| match content {
+        Some(content) => Document::parse_as(content, format).ok()?.normalize().ok(),
+        None => Some(json!({})),
+    };
+
+    let diff = format
+        .and_then(|format| {
+            let old = normalize(&preview.current, format)?;
+            let new = normalize(&preview.synced, format)?;
+            Some(SemanticDiff::compute(&old, &new))
+        })
+        .unwrap_or_else(|| {
+            SemanticDiff::compute_text(
+                preview.current.as_deref().unwrap_or(""),
+                preview.synced.as_deref().unwrap_or(""),
+            )
+        });
+
+    FileDiff {
+        tool: preview.tool.clone(),
+        file: preview.file.clone(),
+        diff: diff.to_json(),
+    }
+}
+
 /// Print human-readable diff-style output
 fn print_diff_output(actions: &[String], errors: &[String], root: &NormalizedPath, mode: Mode) {
     if actions.is_empty() && errors.is_empty() {
-        println!(
+        say!(
             "{} No changes needed. Repository is in sync.",
             "OK".green().bold()
         );
         return;
     }
 
-    println!(
+    say!(
         "{} {} ({})",
         "Diff".blue().bold(),
         root.as_str().yellow(),
         mode.to_string().cyan()
     );
-    println!();
+    say!();
 
     if !actions.is_empty() {
-        println!("{}", "Changes that would be made:".bold());
-        println!();
+        say!("{}", "Changes that would be made:".bold());
+        say!();
 
         for action in actions {
             // Format the action as a diff-style line
@@ -96,20 +169,20 @@ fn print_diff_output(actions: &[String], errors: &[String], root: &NormalizedPat
                     (" ".normal(), clean.normal())
                 };
 
-            println!("  {} {}", prefix, colored_action);
+            say!("  {} {}", prefix, colored_action);
         }
     }
 
     if !errors.is_empty() {
-        println!();
-        println!("{}", "Errors:".red().bold());
+        say!();
+        say!("{}", "Errors:".red().bold());
         for error in errors {
-            println!("  {} {}", "!".red(), error);
+            say!("  {} {}", "!".red(), error);
         }
     }
 
-    println!();
-    println!("Run {} to apply these changes.", "repo sync".cyan());
+    say!();
+    say!("Run {} to apply these changes.", "repo sync".cyan());
 }
 
 #[cfg(test)]
@@ -139,16 +212,43 @@ mode = "{}"
         let temp_dir = TempDir::new().unwrap();
         create_minimal_repo(temp_dir.path(), "standard");
 
-        let result = run_diff(temp_dir.path(), false);
+        let result = run_diff(temp_dir.path());
         assert!(result.is_ok());
     }
 
     #[test]
-    fn test_diff_json() {
-        let temp_dir = TempDir::new().unwrap();
-        create_minimal_repo(temp_dir.path(), "standard");
+    fn test_file_diff_reports_paths_for_structured_files() {
+        let preview = FilePreview {
+            tool: "vscode".to_string(),
+            file: ".vscode/settings.json".to_string(),
+            current: Some(r#"{"editor": {"tabSize": 2}, "files.exclude": {}}"#.to_string()),
+            synced: Some(r#"{"editor": {"tabSize": 4}}"#.to_string()),
+        };
+
+        let diff = serde_json::to_value(file_diff(&preview)).unwrap();
+        assert_eq!(diff["file"], ".vscode/settings.json");
+        assert_eq!(
+            diff["diff"]["changes"],
+            json!([
+                {"kind": "modified", "path": "editor.tabSize", "old": 2, "new": 4},
+                {"kind": "removed", "path": "files.exclude", "old": {}},
+            ])
+        );
+    }
 
-        let result = run_diff(temp_dir.path(), true);
-        assert!(result.is_ok());
+    #[test]
+    fn test_file_diff_compares_new_text_files_by_line() {
+        let preview = FilePreview {
+            tool: "claude".to_string(),
+            file: "CLAUDE.md".to_string(),
+            current: None,
+            synced: Some("# Rules\n".to_string()),
+        };
+
+        let diff = file_diff(&preview);
+        assert_eq!(
+            &diff.diff["changes"],
+            &json!([{"kind": "added", "uuid": null, "new": "# Rules\n"}])
+        );
     }
 }
@@ -0,0 +1,334 @@
+//! Doctor command implementation
+//!
+//! Runs a battery of health checks over a repository: the git layout,
+//! config.toml, the ledger, the configured presets' providers and the
+//! config lint rules. Each check passes, warns or fails, and problems come
+//! with a hint on how to fix them.
+
+use std::path::Path;
+use std::time::Duration;
+
+use colored::Colorize;
+use serde::Serialize;
+
+use repo_core::governance::{LintWarning, lint_rules};
+use repo_core::{Ledger, Manifest, SyncEngine, WarnLevel};
+use repo_fs::{NormalizedPath, WorkspaceLayout};
+
+use super::presets::check_presets;
+use super::sync::detect_mode;
+use crate::error::{CliError, Result};
+use crate::output::{self, say};
+
+/// How long a preset provider check may take
+const PRESET_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Outcome of a doctor check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DoctorStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// One doctor check and its outcome
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorCheck {
+    /// What was checked, e.g. `git` or `preset env:python`
+    pub name: String,
+    pub status: DoctorStatus,
+    /// What the check found
+    pub message: String,
+    /// How to fix a warning or failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl DoctorCheck {
+    fn pass(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: DoctorStatus::Pass,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn problem(
+        status: DoctorStatus,
+        name: impl Into<String>,
+        message: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+impl From<LintWarning> for DoctorCheck {
+    fn from(warning: LintWarning) -> Self {
+        let status = match warning.level {
+            WarnLevel::Error => DoctorStatus::Fail,
+            WarnLevel::Warning | WarnLevel::Info => DoctorStatus::Warn,
+            WarnLevel::Hint => DoctorStatus::Pass,
+        };
+        Self {
+            name: match warning.tool {
+                Some(tool) => format!("lint {}", tool),
+                None => "lint".to_string(),
+            },
+            status,
+            message: warning.message,
+            hint: (status != DoctorStatus::Pass)
+                .then(|| "Run 'repo rules-lint' for details".to_string()),
+        }
+    }
+}
+
+/// Run every doctor check on the repository containing `path`
+///
+/// Checks that need a valid config.toml are skipped when it is missing or
+/// does not parse, as the config failure already explains them.
+pub fn doctor_checks(path: &Path) -> Vec<DoctorCheck> {
+    let mut checks = Vec::new();
+
+    let root = match WorkspaceLayout::detect(path) {
+        Ok(layout) => {
+            checks.push(DoctorCheck::pass(
+                "git",
+                format!("Git repository at {} ({} layout)", layout.root, layout.mode),
+            ));
+            layout.root
+        }
+        Err(_) => {
+            checks.push(DoctorCheck::problem(
+                DoctorStatus::Fail,
+                "git",
+                "No git repository found",
+                "Run 'repo init' to create one, or run repo inside a git checkout",
+            ));
+            return checks;
+        }
+    };
+
+    let root = repo_core::context::detect(path).root.unwrap_or(root);
+    let manifest = match check_config(&root) {
+        Ok(manifest) => {
+            checks.push(DoctorCheck::pass(
+                "config",
+                ".repository/config.toml is valid",
+            ));
+            manifest
+        }
+        Err(check) => {
+            checks.push(check);
+            return checks;
+        }
+    };
+
+    checks.push(check_ledger(&root));
+
+    match check_presets(root.as_ref(), PRESET_TIMEOUT) {
+        Ok(results) => checks.extend(results.into_iter().map(|result| {
+            let name = format!("preset {}", result.preset);
+            let details = result.details.join("; ");
+            match result.status {
+                "healthy" => DoctorCheck::pass(name, "Healthy"),
+                "unsupported" => DoctorCheck::problem(
+                    DoctorStatus::Warn,
+                    name,
+                    "No provider is registered for this preset",
+                    "Check the preset name in config.toml, or remove it",
+                ),
+                status => DoctorCheck::problem(
+                    match status {
+                        "degraded" | "missing" => DoctorStatus::Warn,
+                        _ => DoctorStatus::Fail,
+                    },
+                    name,
+                    format!("{}: {}", status, details),
+                    "Run 'repo presets check' for details",
+                ),
+            }
+        })),
+        Err(e) => checks.push(DoctorCheck::problem(
+            DoctorStatus::Fail,
+            "presets",
+            e.to_string(),
+            "Run 'repo presets check' for details",
+        )),
+    }
+
+    let available: Vec<String> = repo_tools::ToolRegistry::with_builtins()
+        .list()
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let warnings = lint_rules(&manifest, &available);
+    if warnings.is_empty() {
+        checks.push(DoctorCheck::pass("lint", "No configuration issues"));
+    }
+    checks.extend(warnings.into_iter().map(DoctorCheck::from));
+
+    checks
+}
+
+/// Load and parse config.toml, or the check explaining why it can't be
+fn check_config(root: &NormalizedPath) -> std::result::Result<Manifest, DoctorCheck> {
+    let config_path = root.join(".repository/config.toml");
+    let content = std::fs::read_to_string(config_path.to_native()).map_err(|_| {
+        DoctorCheck::problem(
+            DoctorStatus::Fail,
+            "config",
+            ".repository/config.toml is missing",
+            "Run 'repo init' to create it",
+        )
+    })?;
+    Manifest::parse(&content).map_err(|e| {
+        DoctorCheck::problem(
+            DoctorStatus::Fail,
+            "config",
+            format!("Invalid .repository/config.toml: {}", e),
+            "Fix the error above, or restore the file from git",
+        )
+    })
+}
+
+/// Check that the ledger, if any, parses
+fn check_ledger(root: &NormalizedPath) -> DoctorCheck {
+    let engine = match detect_mode(root).and_then(|mode| Ok(SyncEngine::new(root.clone(), mode)?)) {
+        Ok(engine) => engine,
+        Err(e) => {
+            return DoctorCheck::problem(
+                DoctorStatus::Fail,
+                "ledger",
+                e.to_string(),
+                "Run 'repo context --explain' to see how the repository was detected",
+            );
+        }
+    };
+    let path = engine.ledger_path();
+    if !path.exists() {
+        return DoctorCheck::problem(
+            DoctorStatus::Warn,
+            "ledger",
+            "No ledger yet; nothing has been synced",
+            "Run 'repo sync' to generate tool configurations",
+        );
+    }
+    match Ledger::load(&path.to_native()) {
+        Ok(ledger) => DoctorCheck::pass(
+            "ledger",
+            format!("{} intent(s) recorded", ledger.intents().len()),
+        ),
+        Err(e) => DoctorCheck::problem(
+            DoctorStatus::Fail,
+            "ledger",
+            format!("Cannot read {}: {}", path, e),
+            "Delete the ledger and run 'repo sync' to rebuild it",
+        ),
+    }
+}
+
+/// Run the doctor command
+///
+/// Prints a pass/warn/fail table of the health checks, with a hint under
+/// each problem. Returns an error if any check failed.
+pub fn run_doctor(path: &Path) -> Result<()> {
+    let checks = doctor_checks(path);
+
+    if !output::emit(&checks)? {
+        say!("{} Running health checks:", "=>".blue().bold());
+        for check in &checks {
+            let label = match check.status {
+                DoctorStatus::Pass => "PASS".green().bold(),
+                DoctorStatus::Warn => "WARN".yellow().bold(),
+                DoctorStatus::Fail => "FAIL".red().bold(),
+            };
+            say!("   {} {:<20} {}", label, check.name.cyan(), check.message);
+            if let Some(hint) = &check.hint {
+                say!("        {} {}", "hint:".dimmed(), hint);
+            }
+        }
+    }
+
+    let failed = checks
+        .iter()
+        .filter(|c| c.status == DoctorStatus::Fail)
+        .count();
+    if failed > 0 {
+        return Err(CliError::user(format!("{} health check(s) failed", failed)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_repo(dir: &Path, config: &str) {
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::create_dir_all(dir.join(".repository")).unwrap();
+        fs::write(dir.join(".repository/config.toml"), config).unwrap();
+    }
+
+    fn status(checks: &[DoctorCheck], name: &str) -> DoctorStatus {
+        checks
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("no {} check in {:?}", name, checks))
+            .status
+    }
+
+    #[test]
+    fn test_doctor_without_git() {
+        let temp = TempDir::new().unwrap();
+        let checks = doctor_checks(temp.path());
+        assert_eq!(checks.len(), 1);
+        assert_eq!(status(&checks, "git"), DoctorStatus::Fail);
+        assert!(run_doctor(temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_doctor_broken_ledger() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(
+            temp.path(),
+            "tools = [\"claude\"]\n\n[core]\nmode = \"standard\"\n",
+        );
+        let checks = doctor_checks(temp.path());
+        assert_eq!(status(&checks, "config"), DoctorStatus::Pass);
+        assert_eq!(status(&checks, "ledger"), DoctorStatus::Warn);
+        // "No rules configured" is advisory
+        assert_eq!(status(&checks, "lint"), DoctorStatus::Warn);
+        assert!(run_doctor(temp.path()).is_ok());
+
+        fs::write(temp.path().join(".repository/ledger.toml"), "intents = 3").unwrap();
+        let checks = doctor_checks(temp.path());
+        assert_eq!(status(&checks, "ledger"), DoctorStatus::Fail);
+    }
+
+    #[test]
+    fn test_doctor_lint_warnings() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(
+            temp.path(),
+            "tools = [\"nonexistent\"]\n\n[core]\nmode = \"standard\"\n",
+        );
+        let checks = doctor_checks(temp.path());
+        assert!(
+            checks
+                .iter()
+                .any(|c| c.name.starts_with("lint") && c.status != DoctorStatus::Pass),
+            "{:?}",
+            checks
+        );
+    }
+}
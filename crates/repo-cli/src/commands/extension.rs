@@ -1,20 +1,143 @@
 //! Extension command implementations
 //!
-//! Extension lifecycle operations are not yet implemented. These handlers
-//! return errors to prevent callers from mistakenly believing an operation
-//! succeeded. The `list` command is the exception: it returns known extension
-//! types from the registry, which is a valid read-only operation.
+//! `install` supports git URLs and local extension directories, resolves the
+//! presets the extension depends on, and records the install in the extension lock file,
+//! which `verify` checks against disk. The remaining lifecycle operations are not yet
+//! implemented; these handlers return errors to prevent callers from
+//! mistakenly believing an operation succeeded. The `list` command returns
+//! known extension types from the registry, which is a valid read-only
+//! operation.
 
-use colored::Colorize;
-use repo_extensions::ExtensionRegistry;
+use std::path::Path;
 
 use crate::error::{CliError, Result};
+use crate::output::{self, say};
+use colored::Colorize;
+use repo_core::extensions::format_constraints;
+use repo_core::{Manifest, PresetApply, Repository};
+use repo_extensions::{ExtensionRegistry, LockDrift, PresetRequirement, VerifyReport};
+use serde::Serialize;
 
-/// Handle `repo extension install <source> [--no-activate]`
-pub fn handle_extension_install(source: &str, _no_activate: bool) -> Result<()> {
-    Err(CliError::user(format!(
-        "Extension install is not yet implemented. Source: {source}"
-    )))
+/// Result of `repo extension install`
+#[derive(Debug, Serialize)]
+pub struct InstallResult {
+    pub name: String,
+    pub version: String,
+    /// Where the extension was installed
+    pub path: String,
+    /// Presets added to config.toml for the extension
+    pub presets_added: Vec<String>,
+}
+
+/// An extension in `repo extension list`
+#[derive(Debug, Serialize)]
+pub struct ExtensionListEntry<'a> {
+    pub name: &'a str,
+    pub description: &'a str,
+    pub source: &'a str,
+    pub installed: bool,
+}
+
+/// Handle `repo extension install <source> [--no-activate] [--no-deps]`
+///
+/// Installs an extension from a git URL or a local directory containing a
+/// `repo_extension.toml`. Git sources are cloned at the `ref` configured for
+/// that source in config.toml, if any. Presets the extension requires are
+/// added to config.toml and applied, unless `no_deps` is set, in which case
+/// they are only reported. Tool slugs the extension shares with a built-in
+/// or repository tool are reported as collisions.
+pub fn handle_extension_install(
+    path: &Path,
+    source: &str,
+    _no_activate: bool,
+    no_deps: bool,
+) -> Result<()> {
+    let repo = Repository::open(path)?;
+    say!(
+        "{} Installing extension from {}",
+        "=>".blue().bold(),
+        source
+    );
+    let install = repo.install_extension(source, no_deps)?;
+    let name = &install.locked.name;
+
+    // Tools the repository already supplies take precedence over the extension's
+    for collision in &install.collisions {
+        say!("{} {}", "warn:".yellow().bold(), collision);
+    }
+
+    if no_deps {
+        report_preset_dependencies(&install.required, &repo.manifest()?);
+    }
+    for (preset, _) in &install.presets_added {
+        say!("   {} preset {}", "+".green(), preset.cyan());
+    }
+    for applied in &install.presets_applied {
+        report_preset_apply(applied);
+    }
+
+    output::emit(&InstallResult {
+        name: name.clone(),
+        version: install.locked.version.clone(),
+        path: install.path.display().to_string(),
+        presets_added: install
+            .presets_added
+            .iter()
+            .map(|(preset, _)| preset.clone())
+            .collect(),
+    })?;
+    say!(
+        "{} Extension {} installed.",
+        "OK".green().bold(),
+        name.cyan()
+    );
+    Ok(())
+}
+
+/// Print the presets an extension requires without modifying anything.
+fn report_preset_dependencies(required: &[PresetRequirement], manifest: &Manifest) {
+    for req in required {
+        let status = if manifest.presets.contains_key(&req.preset) {
+            "configured".green()
+        } else {
+            "not configured".yellow()
+        };
+        say!(
+            "   requires {} {} ({})",
+            req.preset.cyan(),
+            format_constraints(req),
+            status
+        );
+    }
+}
+
+/// Print what applying a preset did.
+///
+/// Provider failures are warnings: the preset is already saved to
+/// config.toml and can be re-applied later.
+fn report_preset_apply(applied: &PresetApply) {
+    if !applied.supported {
+        eprintln!(
+            "{} No provider registered for preset '{}'",
+            "warning:".yellow().bold(),
+            applied.preset
+        );
+        return;
+    }
+    for action in &applied.actions {
+        say!("   {} {}", "+".green(), action);
+    }
+    for error in &applied.errors {
+        eprintln!(
+            "{} {}: {}",
+            "warning:".yellow().bold(),
+            applied.preset,
+            error
+        );
+    }
+    if applied.rolled_back {
+        say!("   {} Rolled back partial changes", "-".red());
+    }
 }
 
 /// Handle `repo extension add <name>`
@@ -38,47 +161,95 @@ pub fn handle_extension_remove(name: &str) -> Result<()> {
     )))
 }
 
-/// Handle `repo extension list [--json]`
+/// Handle `repo extension verify`
 ///
-/// Lists known extension types from the built-in registry.
-/// No extensions are currently installed; this shows what is available.
-pub fn handle_extension_list(json: bool) -> Result<()> {
-    let registry = ExtensionRegistry::with_known();
-    let names = registry.known_extensions();
+/// Checks every locked extension against what is installed on disk and
+/// prints a per-extension report, followed by installed extensions missing
+/// from the lock. Returns an error if any check fails or any extension is
+/// not locked.
+pub fn handle_extension_verify(path: &Path) -> Result<()> {
+    let report = repo_extensions::verify(path)?;
 
-    if json {
-        let entries: Vec<serde_json::Value> = names
-            .iter()
-            .filter_map(|name| {
-                registry.get(name).map(|entry| {
-                    serde_json::json!({
-                        "name": entry.name,
-                        "description": entry.description,
-                        "source": entry.source,
-                        "installed": false,
-                    })
-                })
-            })
-            .collect();
-
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&entries).unwrap_or_default()
-        );
+    if output::emit(&report)? {
+        // The report is the result
+    } else if report.extensions.is_empty() && report.drift.is_empty() {
+        say!("{} No extensions in the lock file.", "=>".blue().bold());
     } else {
-        println!(
-            "{} Known extensions (none currently installed):",
-            "=>".blue().bold()
-        );
-        if names.is_empty() {
-            println!("   No extensions registered.");
-        } else {
-            for name in &names {
-                if let Some(entry) = registry.get(name) {
-                    println!("   {} - {}", entry.name.cyan(), entry.description.dimmed());
-                }
+        say!("{} Verifying installed extensions:", "=>".blue().bold());
+        for ext in &report.extensions {
+            let status = if ext.passed {
+                "PASS".green().bold()
+            } else {
+                "FAIL".red().bold()
+            };
+            say!("   {} {}", status, ext.name.cyan());
+            for check in &ext.checks {
+                let mark = if check.passed {
+                    "ok".green()
+                } else {
+                    "x".red()
+                };
+                say!("      {} {}: {}", mark, check.check, check.detail.dimmed());
             }
         }
+        for name in unlocked(&report) {
+            say!(
+                "   {} {} {}",
+                "EXTRA".yellow().bold(),
+                name.cyan(),
+                "(installed but not in the lock file)".dimmed()
+            );
+        }
+    }
+
+    let failed = report.failures().count() + unlocked(&report).count();
+    if failed > 0 {
+        return Err(CliError::user(format!(
+            "{failed} extension(s) do not match the lock file"
+        )));
+    }
+    Ok(())
+}
+
+/// Installed extensions the lock file does not list.
+fn unlocked(report: &VerifyReport) -> impl Iterator<Item = &str> {
+    report
+        .drift
+        .iter()
+        .filter(|d| matches!(d, LockDrift::Unlocked { .. }))
+        .map(LockDrift::name)
+}
+
+/// Handle `repo extension list`
+///
+/// Lists known extension types from the built-in registry.
+/// No extensions are currently installed; this shows what is available.
+pub fn handle_extension_list() -> Result<()> {
+    let registry = ExtensionRegistry::with_known();
+    let entries: Vec<ExtensionListEntry> = registry
+        .known_extensions()
+        .iter()
+        .filter_map(|name| registry.get(name))
+        .map(|entry| ExtensionListEntry {
+            name: &entry.name,
+            description: &entry.description,
+            source: &entry.source,
+            installed: false,
+        })
+        .collect();
+    if output::emit(&entries)? {
+        return Ok(());
+    }
+
+    say!(
+        "{} Known extensions (none currently installed):",
+        "=>".blue().bold()
+    );
+    if entries.is_empty() {
+        say!("   No extensions registered.");
+    }
+    for entry in &entries {
+        say!("   {} - {}", entry.name.cyan(), entry.description.dimmed());
     }
 
     Ok(())
@@ -88,15 +259,40 @@ pub fn handle_extension_list(json: bool) -> Result<()> {
 mod tests {
     use super::*;
 
+    use repo_extensions::installer::extension_dir;
+    use repo_extensions::{LockFile, MANIFEST_FILENAME};
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Path to config.toml within a repository
+    const CONFIG_PATH: &str = ".repository/config.toml";
+
+    fn setup_repo(dir: &Path, config: &str) {
+        let repo_dir = dir.join(".repository");
+        fs::create_dir_all(&repo_dir).unwrap();
+        fs::write(repo_dir.join("config.toml"), config).unwrap();
+    }
+
+    fn write_extension(dir: &Path, extra: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join(MANIFEST_FILENAME),
+            format!("[extension]\nname = \"my-ext\"\nversion = \"0.1.0\"\n{extra}"),
+        )
+        .unwrap();
+    }
+
+    fn read_manifest(dir: &Path) -> Manifest {
+        Manifest::parse(&fs::read_to_string(dir.join(CONFIG_PATH)).unwrap()).unwrap()
+    }
+
     #[test]
-    fn test_extension_install_returns_error() {
-        let result = handle_extension_install("test-source", false);
-        assert!(result.is_err(), "extension install must return an error");
+    fn test_extension_install_missing_source_returns_error() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(temp.path(), "[core]\nmode = \"standard\"\n");
+        let result = handle_extension_install(temp.path(), "test-source", false, false);
+        assert!(result.is_err(), "missing source must return an error");
         let err_msg = result.unwrap_err().to_string();
-        assert!(
-            err_msg.contains("not yet implemented"),
-            "error message should indicate not implemented, got: {err_msg}"
-        );
         assert!(
             err_msg.contains("test-source"),
             "error message should include the source, got: {err_msg}"
@@ -104,9 +300,165 @@ mod tests {
     }
 
     #[test]
-    fn test_extension_install_no_activate_returns_error() {
-        let result = handle_extension_install("https://example.com/ext.git", true);
-        assert!(result.is_err(), "extension install with no_activate must return an error");
+    fn test_extension_install_clones_git_source_at_configured_ref() {
+        let temp = TempDir::new().unwrap();
+
+        // A bare repository with v0.1.0 tagged and v0.2.0 on its default branch
+        let bare = temp.path().join("my-ext.git");
+        let repo = git2::Repository::init_bare(&bare).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let commit_version = |version: &str, parents: &[&git2::Commit]| {
+            let manifest = format!("[extension]\nname = \"my-ext\"\nversion = \"{version}\"\n");
+            let blob = repo.blob(manifest.as_bytes()).unwrap();
+            let mut tree = repo.treebuilder(None).unwrap();
+            tree.insert(MANIFEST_FILENAME, blob, 0o100644).unwrap();
+            let tree = repo.find_tree(tree.write().unwrap()).unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, version, &tree, parents)
+                .unwrap()
+        };
+        let tagged = commit_version("0.1.0", &[]);
+        let tagged_commit = repo.find_commit(tagged).unwrap();
+        repo.tag_lightweight("v0.1.0", tagged_commit.as_object(), false)
+            .unwrap();
+        commit_version("0.2.0", &[&tagged_commit]);
+
+        let url = format!("file://{}", bare.display());
+        setup_repo(
+            temp.path(),
+            &format!(
+                "[core]\nmode = \"standard\"\n\n[extensions.\"my-ext\"]\nsource = \"{url}\"\nref = \"v0.1.0\"\n"
+            ),
+        );
+
+        handle_extension_install(temp.path(), &url, false, false).unwrap();
+
+        let lock = LockFile::load(temp.path()).unwrap();
+        let locked = lock.get("my-ext").unwrap();
+        assert_eq!(locked.version, "0.1.0");
+        assert_eq!(locked.source, url);
+        assert_eq!(locked.commit, Some(tagged.to_string()));
+
+        // The ref stays configured for the next install
+        let manifest = read_manifest(temp.path());
+        assert_eq!(manifest.extensions["my-ext"]["ref"], "v0.1.0");
+        handle_extension_verify(temp.path()).unwrap();
+    }
+
+    #[test]
+    fn test_extension_install_local_without_dependencies() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(temp.path(), "[core]\nmode = \"standard\"\n");
+        let source = temp.path().join("src-ext");
+        write_extension(&source, "");
+
+        handle_extension_install(temp.path(), source.to_str().unwrap(), false, false).unwrap();
+
+        let manifest = read_manifest(temp.path());
+        assert!(manifest.extensions.contains_key("my-ext"));
+        assert!(manifest.presets.is_empty());
+        assert!(
+            extension_dir(temp.path(), "my-ext")
+                .join(MANIFEST_FILENAME)
+                .is_file()
+        );
+
+        // The install is locked and verifies cleanly
+        let lock = LockFile::load(temp.path()).unwrap();
+        assert_eq!(lock.get("my-ext").unwrap().version, "0.1.0");
+        handle_extension_verify(temp.path()).unwrap();
+    }
+
+    #[test]
+    fn test_extension_verify_fails_on_version_mismatch() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(temp.path(), "[core]\nmode = \"standard\"\n");
+        let source = temp.path().join("src-ext");
+        write_extension(&source, "");
+        handle_extension_install(temp.path(), source.to_str().unwrap(), false, false).unwrap();
+
+        // Tamper with the installed manifest
+        write_extension(&extension_dir(temp.path(), "my-ext"), "");
+        let installed = extension_dir(temp.path(), "my-ext").join(MANIFEST_FILENAME);
+        let content = fs::read_to_string(&installed).unwrap();
+        fs::write(&installed, content.replace("0.1.0", "0.2.0")).unwrap();
+
+        let err = handle_extension_verify(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("1 extension(s)"), "got: {err}");
+    }
+
+    #[test]
+    fn test_extension_verify_fails_on_unlocked_install() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(temp.path(), "[core]\nmode = \"standard\"\n");
+        write_extension(&extension_dir(temp.path(), "stray"), "");
+
+        let err = handle_extension_verify(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("1 extension(s)"), "got: {err}");
+    }
+
+    #[test]
+    fn test_extension_install_unsatisfiable_constraint_aborts() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(temp.path(), "[core]\nmode = \"standard\"\n");
+        let source = temp.path().join("src-ext");
+        write_extension(&source, "[requires.python]\nversion = \">=999\"\n");
+
+        let result = handle_extension_install(temp.path(), source.to_str().unwrap(), false, false);
+        let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("env:python >=999"), "got: {err_msg}");
+
+        // Nothing should have been written
+        let manifest = read_manifest(temp.path());
+        assert!(manifest.extensions.is_empty());
+        assert!(manifest.presets.is_empty());
+        assert!(!extension_dir(temp.path(), "my-ext").exists());
+        assert!(LockFile::load(temp.path()).unwrap().extensions.is_empty());
+    }
+
+    #[test]
+    fn test_extension_install_configured_preset_checks_pinned_version() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(
+            temp.path(),
+            "[core]\nmode = \"standard\"\n\n[presets.\"env:python\"]\nversion = \"3.11\"\n",
+        );
+        let source = temp.path().join("src-ext");
+        write_extension(&source, "[requires.python]\nversion = \">=3.13\"\n");
+
+        let result = handle_extension_install(temp.path(), source.to_str().unwrap(), false, false);
+        let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("found 3.11"), "got: {err_msg}");
+    }
+
+    #[test]
+    fn test_extension_install_configured_preset_satisfied() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(
+            temp.path(),
+            "[core]\nmode = \"standard\"\n\n[presets.\"env:python\"]\nversion = \"3.13\"\n",
+        );
+        let source = temp.path().join("src-ext");
+        write_extension(&source, "[requires.python]\nversion = \">=3.12\"\n");
+
+        handle_extension_install(temp.path(), source.to_str().unwrap(), false, false).unwrap();
+
+        let manifest = read_manifest(temp.path());
+        assert!(manifest.extensions.contains_key("my-ext"));
+        assert_eq!(manifest.presets["env:python"]["version"], "3.13");
+    }
+
+    #[test]
+    fn test_extension_install_no_deps_only_reports() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(temp.path(), "[core]\nmode = \"standard\"\n");
+        let source = temp.path().join("src-ext");
+        write_extension(&source, "[requires.python]\nversion = \">=999\"\n");
+
+        handle_extension_install(temp.path(), source.to_str().unwrap(), false, true).unwrap();
+
+        let manifest = read_manifest(temp.path());
+        assert!(manifest.extensions.contains_key("my-ext"));
+        assert!(manifest.presets.is_empty());
     }
 
     #[test]
@@ -157,13 +509,7 @@ mod tests {
     #[test]
     fn test_extension_list_succeeds() {
         // list is a valid operation that shows known extension types
-        let result = handle_extension_list(false);
+        let result = handle_extension_list();
         assert!(result.is_ok(), "extension list should succeed");
     }
-
-    #[test]
-    fn test_extension_list_json_succeeds() {
-        let result = handle_extension_list(true);
-        assert!(result.is_ok(), "extension list --json should succeed");
-    }
 }
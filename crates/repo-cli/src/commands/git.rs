@@ -1,32 +1,38 @@
-//! Git command implementations (push, pull, merge)
+//! Git command implementations (push, pull, merge, status)
 //!
 //! These commands use repo-git's free functions for network operations
 //! and LayoutProvider for repo/branch discovery.
 
+use std::collections::HashSet;
 use std::path::Path;
 
 use colored::Colorize;
 use git2::Repository;
+use serde::Serialize;
 
+use repo_core::SyncEngine;
 use repo_fs::NormalizedPath;
-use repo_git::{ClassicLayout, ContainerLayout, LayoutProvider};
+use repo_git::{CredentialOptions, LayoutProvider, WorkingTreeStatus};
 
-use super::sync::detect_mode;
+use super::sync::{detect_mode, resolve_root};
 use crate::error::Result;
-use repo_core::Mode;
+use crate::output::{self, say};
+
+/// Result of `repo push`, `repo pull` or `repo merge`
+#[derive(Debug, Serialize)]
+pub struct GitOperationResult<'a> {
+    /// `push`, `pull` or `merge`
+    pub operation: &'static str,
+    /// Remote pushed to or pulled from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<&'a str>,
+    /// Branch pushed, pulled or merged; absent for the current branch
+    pub branch: Option<&'a str>,
+}
 
 /// Create a LayoutProvider for git operations based on detected mode.
-fn create_git_provider(root: &NormalizedPath, mode: Mode) -> Result<Box<dyn LayoutProvider>> {
-    match mode {
-        Mode::Standard => {
-            let layout = ClassicLayout::new(root.clone())?;
-            Ok(Box::new(layout))
-        }
-        Mode::Worktrees => {
-            let layout = ContainerLayout::new(root.clone(), Default::default())?;
-            Ok(Box::new(layout))
-        }
-    }
+fn create_git_provider(root: &NormalizedPath) -> Result<Box<dyn LayoutProvider>> {
+    Ok(repo_core::Repository::open(root)?.git_layout()?)
 }
 
 /// Run the push command.
@@ -34,15 +40,14 @@ fn create_git_provider(root: &NormalizedPath, mode: Mode) -> Result<Box<dyn Layo
 /// Pushes the current branch to the specified remote.
 pub fn run_push(path: &Path, remote: Option<&str>, branch: Option<&str>) -> Result<()> {
     let root = NormalizedPath::new(path);
-    let mode = detect_mode(&root)?;
-    let provider = create_git_provider(&root, mode)?;
+    let provider = create_git_provider(&root)?;
     let repo =
         Repository::open(provider.main_worktree().to_native()).map_err(repo_git::Error::from)?;
 
     let remote_name = remote.unwrap_or("origin");
     let branch_display = branch.unwrap_or("current branch");
 
-    println!(
+    say!(
         "{} Pushing {} to {}...",
         "=>".blue().bold(),
         branch_display.cyan(),
@@ -50,9 +55,20 @@ pub fn run_push(path: &Path, remote: Option<&str>, branch: Option<&str>) -> Resu
     );
 
     let current_branch_fn = || provider.current_branch();
-    repo_git::push(&repo, remote, branch, current_branch_fn)?;
+    repo_git::push(
+        &repo,
+        remote,
+        branch,
+        current_branch_fn,
+        &CredentialOptions::default(),
+    )?;
 
-    println!(
+    output::emit(&GitOperationResult {
+        operation: "push",
+        remote: Some(remote_name),
+        branch,
+    })?;
+    say!(
         "{} Successfully pushed to {}",
         "OK".green().bold(),
         remote_name.yellow()
@@ -66,15 +82,14 @@ pub fn run_push(path: &Path, remote: Option<&str>, branch: Option<&str>) -> Resu
 /// Pulls changes from the specified remote.
 pub fn run_pull(path: &Path, remote: Option<&str>, branch: Option<&str>) -> Result<()> {
     let root = NormalizedPath::new(path);
-    let mode = detect_mode(&root)?;
-    let provider = create_git_provider(&root, mode)?;
+    let provider = create_git_provider(&root)?;
     let repo =
         Repository::open(provider.main_worktree().to_native()).map_err(repo_git::Error::from)?;
 
     let remote_name = remote.unwrap_or("origin");
     let branch_display = branch.unwrap_or("current branch");
 
-    println!(
+    say!(
         "{} Pulling {} from {}...",
         "=>".blue().bold(),
         branch_display.cyan(),
@@ -82,9 +97,21 @@ pub fn run_pull(path: &Path, remote: Option<&str>, branch: Option<&str>) -> Resu
     );
 
     let current_branch_fn = || provider.current_branch();
-    repo_git::pull(&repo, remote, branch, current_branch_fn, None)?;
+    repo_git::pull(
+        &repo,
+        remote,
+        branch,
+        current_branch_fn,
+        None,
+        &CredentialOptions::default(),
+    )?;
 
-    println!(
+    output::emit(&GitOperationResult {
+        operation: "pull",
+        remote: Some(remote_name),
+        branch,
+    })?;
+    say!(
         "{} Successfully pulled from {}",
         "OK".green().bold(),
         remote_name.yellow()
@@ -95,24 +122,29 @@ pub fn run_pull(path: &Path, remote: Option<&str>, branch: Option<&str>) -> Resu
 
 /// Run the merge command.
 ///
-/// Merges the source branch into the current branch.
+/// Merges the source branch into the current branch. A conflicting merge
+/// is aborted and reported with the conflicted files.
 pub fn run_merge(path: &Path, source: &str) -> Result<()> {
     let root = NormalizedPath::new(path);
-    let mode = detect_mode(&root)?;
-    let provider = create_git_provider(&root, mode)?;
+    let provider = create_git_provider(&root)?;
     let repo =
         Repository::open(provider.main_worktree().to_native()).map_err(repo_git::Error::from)?;
 
-    println!(
+    say!(
         "{} Merging {} into current branch...",
         "=>".blue().bold(),
         source.cyan()
     );
 
     let current_branch_fn = || provider.current_branch();
-    repo_git::merge(&repo, source, current_branch_fn, None)?;
+    repo_git::merge(&repo, source, current_branch_fn, None, true)?;
 
-    println!(
+    output::emit(&GitOperationResult {
+        operation: "merge",
+        remote: None,
+        branch: Some(source),
+    })?;
+    say!(
         "{} Successfully merged {}",
         "OK".green().bold(),
         source.cyan()
@@ -121,7 +153,168 @@ pub fn run_merge(path: &Path, source: &str) -> Result<()> {
     Ok(())
 }
 
+/// Git status of the active working directory, with managed files flagged.
+#[derive(Debug)]
+pub struct GitStatusReport {
+    /// Branch, upstream and working tree status
+    pub status: WorkingTreeStatus,
+    /// Managed files with staged or unstaged changes
+    pub managed_dirty: Vec<String>,
+}
+
+/// Result of `repo git status`, for structured output
+#[derive(Debug, Serialize)]
+pub struct GitStatusResult<'a> {
+    pub branch: Option<&'a str>,
+    pub ahead: usize,
+    pub behind: usize,
+    pub staged: &'a [String],
+    pub unstaged: &'a [String],
+    pub untracked: &'a [String],
+    pub managed_dirty: &'a [String],
+}
+
+/// Collect the git status of the working directory sync writes into.
+///
+/// Managed files are the projections recorded in the ledger; untracked
+/// managed files are not reported as dirty, since they survive a branch
+/// switch.
+pub fn git_status(path: &Path) -> Result<GitStatusReport> {
+    let root = resolve_root(path)?;
+    let mode = detect_mode(&root)?;
+    let engine = SyncEngine::new(root, mode)?;
+    let repo =
+        Repository::discover(engine.working_dir().to_native()).map_err(repo_git::Error::from)?;
+    let status = repo_git::working_tree_status(&repo)?;
+
+    // Ledger paths are relative to the working directory; git's are
+    // relative to the repository workdir
+    let working_dir = engine.working_dir().to_native();
+    let managed: HashSet<String> = engine
+        .load_ledger()?
+        .intents()
+        .iter()
+        .flat_map(|intent| intent.projections())
+        .filter_map(|projection| {
+            let file = working_dir.join(&projection.file);
+            let relative = file.strip_prefix(repo.workdir()?).ok()?;
+            Some(NormalizedPath::new(relative).as_str().to_string())
+        })
+        .collect();
+
+    let mut managed_dirty: Vec<String> = status
+        .staged
+        .iter()
+        .chain(&status.unstaged)
+        .filter(|file| managed.contains(file.as_str()))
+        .cloned()
+        .collect();
+    managed_dirty.sort();
+    managed_dirty.dedup();
+
+    Ok(GitStatusReport {
+        status,
+        managed_dirty,
+    })
+}
+
+/// Run the git status command.
+///
+/// Shows the branch, its upstream distance, and the working tree changes,
+/// and warns about managed files with uncommitted changes.
+pub fn run_git_status(path: &Path) -> Result<()> {
+    let report = git_status(path)?;
+    let status = &report.status;
+
+    let result = GitStatusResult {
+        branch: status.branch.as_deref(),
+        ahead: status.ahead,
+        behind: status.behind,
+        staged: &status.staged,
+        unstaged: &status.unstaged,
+        untracked: &status.untracked,
+        managed_dirty: &report.managed_dirty,
+    };
+    if output::emit(&result)? {
+        return Ok(());
+    }
+
+    let branch = status.branch.as_deref().unwrap_or("(detached HEAD)");
+    say!("{} On branch {}", "=>".blue().bold(), branch.cyan());
+    if status.ahead > 0 || status.behind > 0 {
+        say!(
+            "   {} ahead, {} behind upstream",
+            status.ahead.to_string().green(),
+            status.behind.to_string().yellow()
+        );
+    }
+
+    let sections = [
+        ("Staged", &status.staged),
+        ("Unstaged", &status.unstaged),
+        ("Untracked", &status.untracked),
+    ];
+    for (title, files) in sections {
+        if files.is_empty() {
+            continue;
+        }
+        say!("{}:", title.bold());
+        for file in files {
+            say!("   {}", file);
+        }
+    }
+
+    if sections.iter().all(|(_, files)| files.is_empty()) {
+        say!("{} Working tree clean.", "OK".green().bold());
+    }
+
+    if !report.managed_dirty.is_empty() {
+        say!(
+            "{} Managed files with uncommitted changes:",
+            "warning:".yellow().bold()
+        );
+        for file in &report.managed_dirty {
+            say!("   {}", file.yellow());
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    // Integration tests require real git repos - tested in mission_tests.rs
+    // Push, pull and merge integration tests require real git repos - tested
+    // in mission_tests.rs
+    use super::*;
+    use crate::commands::run_sync;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_git_status_flags_modified_managed_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        let repo = Repository::init(path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+
+        fs::create_dir_all(path.join(".repository")).unwrap();
+        fs::write(
+            path.join(".repository").join("config.toml"),
+            "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n",
+        )
+        .unwrap();
+        run_sync(path, false, false, Some("Sync tool configs")).unwrap();
+
+        fs::write(path.join(".cursorrules"), "edited by hand\n").unwrap();
+        fs::write(path.join("notes.txt"), "").unwrap();
+
+        let report = git_status(path).unwrap();
+
+        assert!(report.status.unstaged.contains(&".cursorrules".to_string()));
+        assert_eq!(report.managed_dirty, vec![".cursorrules"]);
+        assert!(report.status.untracked.contains(&"notes.txt".to_string()));
+        assert!(report.status.staged.is_empty());
+    }
 }
@@ -6,13 +6,56 @@ use std::fs;
 use std::path::Path;
 
 use colored::Colorize;
+use serde::Serialize;
+use similar::TextDiff;
 
+use repo_core::SyncEngine;
+use repo_meta::schema::compose_rule_markdown;
+
+use super::rule::front_matter_mode;
+use super::sync::{detect_mode, resolve_root};
 use crate::error::{CliError, Result};
+use crate::output::{self, say};
+
+/// What sync would write to one of a tool's files
+#[derive(Debug, Serialize)]
+pub struct ToolFileDiff {
+    pub tool: String,
+    pub file: String,
+    /// Unified diff from the current to the synced content
+    pub diff: String,
+    pub blocks_added: Vec<String>,
+    pub blocks_removed: Vec<String>,
+    pub blocks_changed: Vec<String>,
+}
+
+/// Rules exported as AGENTS.md
+#[derive(Debug, Serialize)]
+pub struct AgentsExport {
+    pub format: &'static str,
+    pub content: String,
+}
+
+/// Result of `repo rules-import`
+#[derive(Debug, Default, Serialize)]
+pub struct RulesImportResult {
+    /// Ids of the rules written
+    pub imported: Vec<String>,
+    pub skipped: Vec<SkippedRule>,
+}
+
+/// A rule `repo rules-import` did not write
+#[derive(Debug, Serialize)]
+pub struct SkippedRule {
+    pub id: String,
+    pub reason: String,
+}
 
 /// Run the rules-lint command
 ///
-/// Checks the configuration for consistency issues.
-pub fn run_rules_lint(path: &Path, json: bool) -> Result<()> {
+/// Checks the configuration and the rule definitions in
+/// `.repository/rules/` for consistency issues.
+pub fn run_rules_lint(path: &Path) -> Result<()> {
     let config_path = path.join(".repository").join("config.toml");
     if !config_path.exists() {
         return Err(CliError::user(
@@ -28,40 +71,89 @@ pub fn run_rules_lint(path: &Path, json: bool) -> Result<()> {
     let registry = repo_tools::ToolRegistry::with_builtins();
     let available_tools: Vec<String> = registry.list().iter().map(|s| s.to_string()).collect();
 
-    let warnings = repo_core::governance::lint_rules(&manifest, &available_tools);
+    let mut warnings = repo_core::governance::lint_rules(&manifest, &available_tools);
+    let rules = repo_meta::DefinitionLoader::new()
+        .load_rules(&repo_fs::NormalizedPath::new(path))
+        .map_err(|e| CliError::user(format!("Failed to load rules: {}", e)))?;
+    warnings.extend(repo_core::governance::lint_rule_definitions(&rules));
 
-    if json {
-        let output = serde_json::to_string_pretty(&warnings)?;
-        println!("{}", output);
+    if output::emit(&warnings)? {
         return Ok(());
     }
 
     if warnings.is_empty() {
-        println!("{} Configuration is clean.", "OK".green().bold());
+        say!("{} Configuration is clean.", "OK".green().bold());
         return Ok(());
     }
 
-    println!("{} Found {} issue(s):", "=>".blue().bold(), warnings.len());
+    say!("{} Found {} issue(s):", "=>".blue().bold(), warnings.len());
     for w in &warnings {
         let prefix = match w.level {
+            repo_core::WarnLevel::Hint => "hint".dimmed(),
             repo_core::WarnLevel::Info => "info".cyan(),
             repo_core::WarnLevel::Warning => "warn".yellow(),
             repo_core::WarnLevel::Error => "error".red(),
         };
         if let Some(ref tool) = w.tool {
-            println!("  [{}] {}: {}", prefix, tool.bold(), w.message);
+            say!("  [{}] {}: {}", prefix, tool.bold(), w.message);
         } else {
-            println!("  [{}] {}", prefix, w.message);
+            say!("  [{}] {}", prefix, w.message);
         }
     }
 
     Ok(())
 }
 
+/// Run the rules-validate command
+///
+/// Reports structural problems in `.repository/rules/` and fails if there
+/// are any, so the command can gate commits.
+pub fn run_rules_validate(path: &Path) -> Result<()> {
+    let problems = repo_core::validate_rules(path)?;
+
+    if output::emit(&problems)? {
+        // The problems are the result
+    } else if problems.is_empty() {
+        say!("{} Rules are valid.", "OK".green().bold());
+    } else {
+        for problem in &problems {
+            match &problem.rule {
+                Some(rule) => say!(
+                    "  {} {} ({}): {}",
+                    "error".red(),
+                    problem.file.display(),
+                    rule.bold(),
+                    problem.reason
+                ),
+                None => say!(
+                    "  {} {}: {}",
+                    "error".red(),
+                    problem.file.display(),
+                    problem.reason
+                ),
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::user(format!(
+            "Found {} rule problem(s)",
+            problems.len()
+        )))
+    }
+}
+
 /// Run the rules-diff command
 ///
-/// Shows drift between expected and actual config state.
-pub fn run_rules_diff(path: &Path, json: bool) -> Result<()> {
+/// Shows drift between expected and actual config state, or with `tool`
+/// what sync would write to that tool's files.
+pub fn run_rules_diff(path: &Path, tool: Option<&str>) -> Result<()> {
+    if let Some(tool) = tool {
+        return run_tool_diff(path, tool);
+    }
+
     let config_path = path.join(".repository").join("config.toml");
     if !config_path.exists() {
         return Err(CliError::user(
@@ -76,25 +168,23 @@ pub fn run_rules_diff(path: &Path, json: bool) -> Result<()> {
     let drifts = repo_core::governance::diff_configs(path, &manifest)
         .map_err(|e| CliError::user(format!("Failed to compute diff: {}", e)))?;
 
-    if json {
-        let output = serde_json::to_string_pretty(&drifts)?;
-        println!("{}", output);
+    if output::emit(&drifts)? {
         return Ok(());
     }
 
     if drifts.is_empty() {
-        println!("{} No configuration drift detected.", "OK".green().bold());
+        say!("{} No configuration drift detected.", "OK".green().bold());
         return Ok(());
     }
 
-    println!("{} Found {} drift(s):", "=>".blue().bold(), drifts.len());
+    say!("{} Found {} drift(s):", "=>".blue().bold(), drifts.len());
     for d in &drifts {
         let prefix = match d.drift_type {
             repo_core::DriftType::Modified => "modified".yellow(),
             repo_core::DriftType::Missing => "missing".red(),
             repo_core::DriftType::Extra => "extra".cyan(),
         };
-        println!(
+        say!(
             "  [{}] {} - {} ({})",
             prefix,
             d.tool.bold(),
@@ -106,28 +196,103 @@ pub fn run_rules_diff(path: &Path, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// Show a unified diff of what sync would write for `tool`
+fn run_tool_diff(path: &Path, tool: &str) -> Result<()> {
+    let root = resolve_root(path)?;
+    let mode = detect_mode(&root)?;
+    let engine = SyncEngine::new(root, mode)?;
+    let previews = engine.preview_tool(tool)?;
+
+    let diff = |preview: &repo_core::FilePreview| {
+        let current = preview.current.as_deref().unwrap_or("");
+        let synced = preview.synced.as_deref().unwrap_or("");
+        TextDiff::from_lines(current, synced)
+            .unified_diff()
+            .header(
+                &format!("a/{}", preview.file),
+                &format!("b/{}", preview.file),
+            )
+            .to_string()
+    };
+
+    if output::is_structured() {
+        let diffs: Vec<_> = previews
+            .iter()
+            .map(|preview| {
+                let blocks = preview.block_changes();
+                ToolFileDiff {
+                    tool: preview.tool.clone(),
+                    file: preview.file.clone(),
+                    diff: diff(preview),
+                    blocks_added: blocks.added,
+                    blocks_removed: blocks.removed,
+                    blocks_changed: blocks.changed,
+                }
+            })
+            .collect();
+        output::emit(&diffs)?;
+        return Ok(());
+    }
+
+    if previews.is_empty() {
+        say!("{} {} is in sync.", "OK".green().bold(), tool.bold());
+        return Ok(());
+    }
+
+    for preview in &previews {
+        for line in diff(preview).lines() {
+            let line = match line.chars().next() {
+                Some('+') => line.green(),
+                Some('-') => line.red(),
+                Some('@') => line.cyan(),
+                _ => line.normal(),
+            };
+            say!("{}", line);
+        }
+    }
+
+    Ok(())
+}
+
 /// Run the rules-export command
 ///
-/// Exports rules to AGENTS.md format.
+/// Exports rules to AGENTS.md format, or with `catalog-json` the rule
+/// definitions as a JSON catalog. Under structured output the catalog is
+/// printed in the selected format.
 pub fn run_rules_export(path: &Path, format: &str) -> Result<()> {
+    if format == "catalog-json" {
+        let catalog = repo_core::governance::export_rule_catalog(path)
+            .map_err(|e| CliError::user(format!("Failed to export: {}", e)))?;
+        if !output::emit(&catalog)? {
+            say!("{}", serde_json::to_string_pretty(&catalog)?);
+        }
+        return Ok(());
+    }
     if format != "agents" {
         return Err(CliError::user(format!(
-            "Unsupported export format '{}'. Supported: agents",
+            "Unsupported export format '{}'. Supported: agents, catalog-json",
             format
         )));
     }
 
-    let output = repo_core::governance::export_agents_md(path)
+    let content = repo_core::governance::export_agents_md(path)
         .map_err(|e| CliError::user(format!("Failed to export: {}", e)))?;
 
-    print!("{}", output);
+    let export = AgentsExport {
+        format: "agents",
+        content,
+    };
+    if !output::emit(&export)? {
+        print!("{}", export.content);
+    }
     Ok(())
 }
 
 /// Run the rules-import command
 ///
-/// Imports rules from an AGENTS.md file.
-pub fn run_rules_import(path: &Path, file: &str) -> Result<()> {
+/// Imports rules from an AGENTS.md file. A rule whose front-matter doesn't
+/// parse strictly is skipped, unless `lenient` is set.
+pub fn run_rules_import(path: &Path, file: &str, lenient: bool) -> Result<()> {
     let file_path = Path::new(file);
     if !file_path.exists() {
         return Err(CliError::user(format!("File not found: {}", file)));
@@ -136,15 +301,17 @@ pub fn run_rules_import(path: &Path, file: &str) -> Result<()> {
     let content = fs::read_to_string(file_path)?;
     let rules = repo_core::governance::import_agents_md(&content);
 
+    let mut result = RulesImportResult::default();
     if rules.is_empty() {
-        println!("{} No rules found in file.", "WARN".yellow().bold());
+        output::emit(&result)?;
+        say!("{} No rules found in file.", "WARN".yellow().bold());
         return Ok(());
     }
 
     let rules_dir = path.join(".repository").join("rules");
     fs::create_dir_all(&rules_dir)?;
 
-    println!(
+    say!(
         "{} Importing {} rule(s)...",
         "=>".blue().bold(),
         rules.len()
@@ -152,17 +319,35 @@ pub fn run_rules_import(path: &Path, file: &str) -> Result<()> {
 
     for (id, rule_content) in &rules {
         // Validate rule ID to prevent path traversal
-        if let Err(e) = repo_core::validate_rule_id(id) {
-            println!("   {} {} (skipped: {})", "!".red(), id, e);
-            continue;
-        }
+        let composed = repo_core::validate_rule_id(id)
+            .map_err(|e| e.to_string())
+            .and_then(|()| {
+                compose_rule_markdown(rule_content, &[], front_matter_mode(lenient))
+                    .map_err(|e| e.to_string())
+            });
+        let (rule_content, warnings) = match composed {
+            Ok(composed) => composed,
+            Err(reason) => {
+                say!("   {} {} (skipped: {})", "!".red(), id, reason);
+                result.skipped.push(SkippedRule {
+                    id: id.clone(),
+                    reason,
+                });
+                continue;
+            }
+        };
 
         let rule_path = rules_dir.join(format!("{}.md", id));
-        fs::write(&rule_path, rule_content)?;
-        println!("   {} {}", "+".green(), id);
+        repo_fs::io::write_text(&repo_fs::NormalizedPath::new(&rule_path), &rule_content)?;
+        say!("   {} {}", "+".green(), id);
+        for warning in &warnings {
+            say!("     {} {}", "WARN".yellow().bold(), warning);
+        }
+        result.imported.push(id.clone());
     }
 
-    println!("{} Import complete.", "OK".green().bold());
+    output::emit(&result)?;
+    say!("{} Import complete.", "OK".green().bold());
     Ok(())
 }
 
@@ -184,7 +369,7 @@ mod tests {
     #[test]
     fn test_rules_lint_no_repo() {
         let temp = TempDir::new().unwrap();
-        let result = run_rules_lint(temp.path(), false);
+        let result = run_rules_lint(temp.path());
         assert!(result.is_err());
     }
 
@@ -192,22 +377,58 @@ mod tests {
     fn test_rules_lint_basic() {
         let temp = TempDir::new().unwrap();
         create_test_repo(temp.path());
-        let result = run_rules_lint(temp.path(), false);
+        let result = run_rules_lint(temp.path());
         assert!(result.is_ok());
     }
 
+    fn write_rule(dir: &Path, file: &str, content: &str) {
+        let rules_dir = dir.join(".repository/rules");
+        fs::create_dir_all(&rules_dir).unwrap();
+        fs::write(rules_dir.join(file), content).unwrap();
+    }
+
     #[test]
-    fn test_rules_lint_json() {
+    fn test_rules_validate_clean() {
         let temp = TempDir::new().unwrap();
         create_test_repo(temp.path());
-        let result = run_rules_lint(temp.path(), true);
-        assert!(result.is_ok());
+        write_rule(temp.path(), "style.md", "Be consistent.\n");
+        write_rule(
+            temp.path(),
+            "naming.toml",
+            "[meta]\nid = \"naming\"\nrequires = [\"style\"]\n\n[content]\ninstruction = \"Use clear names.\"\n",
+        );
+        assert!(run_rules_validate(temp.path()).is_ok());
+    }
+
+    #[test]
+    fn test_rules_validate_invalid_id() {
+        let temp = TempDir::new().unwrap();
+        create_test_repo(temp.path());
+        write_rule(
+            temp.path(),
+            "bad.toml",
+            "[meta]\nid = \"bad id!\"\n\n[content]\ninstruction = \"Do things.\"\n",
+        );
+        assert!(run_rules_validate(temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_rules_validate_unresolved_requires() {
+        let temp = TempDir::new().unwrap();
+        create_test_repo(temp.path());
+        write_rule(
+            temp.path(),
+            "naming.toml",
+            "[meta]\nid = \"naming\"\nrequires = [\"missing\"]\n\n[content]\ninstruction = \"Use clear names.\"\n",
+        );
+        let err = run_rules_validate(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("1 rule problem"));
     }
 
     #[test]
     fn test_rules_diff_no_repo() {
         let temp = TempDir::new().unwrap();
-        let result = run_rules_diff(temp.path(), false);
+        let result = run_rules_diff(temp.path(), None);
         assert!(result.is_err());
     }
 
@@ -215,10 +436,18 @@ mod tests {
     fn test_rules_diff_basic() {
         let temp = TempDir::new().unwrap();
         create_test_repo(temp.path());
-        let result = run_rules_diff(temp.path(), false);
+        let result = run_rules_diff(temp.path(), None);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_rules_diff_tool() {
+        let temp = TempDir::new().unwrap();
+        create_test_repo(temp.path());
+        repo_test_utils::git::fake_git_dir(temp.path());
+        assert!(run_rules_diff(temp.path(), Some("claude")).is_ok());
+    }
+
     #[test]
     fn test_rules_export_empty() {
         let temp = TempDir::new().unwrap();
@@ -227,6 +456,18 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_rules_export_catalog_json() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join(".repository/rules")).unwrap();
+        fs::write(
+            temp.path().join(".repository/rules/style.toml"),
+            "[meta]\nid = \"style\"\n\n[content]\ninstruction = \"Be consistent.\"\n",
+        )
+        .unwrap();
+        assert!(run_rules_export(temp.path(), "catalog-json").is_ok());
+    }
+
     #[test]
     fn test_rules_export_unsupported_format() {
         let temp = TempDir::new().unwrap();
@@ -237,7 +478,7 @@ mod tests {
     #[test]
     fn test_rules_import_missing_file() {
         let temp = TempDir::new().unwrap();
-        let result = run_rules_import(temp.path(), "/nonexistent/AGENTS.md");
+        let result = run_rules_import(temp.path(), "/nonexistent/AGENTS.md", false);
         assert!(result.is_err());
     }
 
@@ -261,7 +502,7 @@ mod tests {
         // Import into new location
         let temp2 = TempDir::new().unwrap();
         fs::create_dir_all(temp2.path().join(".repository")).unwrap();
-        let result = run_rules_import(temp2.path(), agents_file.to_str().unwrap());
+        let result = run_rules_import(temp2.path(), agents_file.to_str().unwrap(), false);
         assert!(result.is_ok());
 
         // Verify imported rules exist
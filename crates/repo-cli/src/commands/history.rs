@@ -0,0 +1,121 @@
+//! History command implementation
+//!
+//! Shows the operation history recorded in `.repository/history.toml`.
+
+use std::path::Path;
+
+use colored::Colorize;
+
+use repo_core::History;
+use repo_core::history::{HistoryEntry, Operation};
+use repo_fs::NormalizedPath;
+
+use super::sync::resolve_root;
+use crate::error::Result;
+use crate::output::{self, say};
+
+/// Run the history command
+///
+/// Shows the most recent `limit` operations, newest first.
+pub fn run_history(path: &Path, limit: usize) -> Result<()> {
+    let root = resolve_root(path)?;
+    let history = History::load(&root.join(".repository"))?;
+    let entries = history.recent(limit);
+
+    if output::emit(&entries)? {
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        say!("No operations recorded yet.");
+        return Ok(());
+    }
+
+    for entry in entries {
+        print_entry(entry);
+    }
+    Ok(())
+}
+
+/// Record a configuration change made by a CLI command
+///
+/// `path` is the repository root and `changed_file` is relative to it.
+pub(crate) fn record_config_change(
+    path: &Path,
+    operation: Operation,
+    summary: String,
+    changed_file: &str,
+) {
+    History::record(
+        &NormalizedPath::new(path.join(".repository")),
+        HistoryEntry::new(operation, summary).with_changed_files(vec![changed_file.to_string()]),
+    );
+}
+
+/// Print one history entry with its changed files
+fn print_entry(entry: &HistoryEntry) {
+    let user = entry
+        .user
+        .as_deref()
+        .map(|u| format!(" by {}", u))
+        .unwrap_or_default();
+    let actions = entry
+        .actions
+        .map(|n| format!(" ({} action(s))", n))
+        .unwrap_or_default();
+    say!(
+        "{} {}{}: {}{}",
+        entry
+            .timestamp
+            .format("%Y-%m-%d %H:%M:%S UTC")
+            .to_string()
+            .dimmed(),
+        entry.operation.to_string().cyan().bold(),
+        user.dimmed(),
+        entry.summary,
+        actions.dimmed()
+    );
+    for file in &entry.changed_files {
+        say!("   {} {}", "~".yellow(), file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_minimal_repo(dir: &Path) {
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        let repo_dir = dir.join(".repository");
+        fs::create_dir_all(&repo_dir).unwrap();
+        fs::write(
+            repo_dir.join("config.toml"),
+            "tools = []\n\n[core]\nmode = \"standard\"\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_history_lists_recorded_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        create_minimal_repo(temp_dir.path());
+
+        History::append(
+            &NormalizedPath::new(temp_dir.path().join(".repository")),
+            HistoryEntry::new(Operation::ToolAdd, "Added tool cursor"),
+        )
+        .unwrap();
+
+        assert!(run_history(temp_dir.path(), 10).is_ok());
+    }
+
+    #[test]
+    fn test_history_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        create_minimal_repo(temp_dir.path());
+
+        assert!(run_history(temp_dir.path(), 10).is_ok());
+    }
+}
@@ -5,17 +5,28 @@
 use std::path::Path;
 
 use colored::Colorize;
+use serde::Serialize;
 
-use repo_core::config::Manifest;
-use repo_core::hooks::{HookConfig, HookEvent};
+use repo_core::config::{Manifest, ManifestEditor};
+use repo_core::hooks::{HookConfig, HookEvent, HookRunner};
+use repo_fs::{ConfigStore, NormalizedPath};
 
 use crate::error::Result;
+use crate::output::{self, say};
+
+/// Result of `repo hooks remove`
+#[derive(Debug, Serialize)]
+pub struct HooksRemoveResult {
+    pub event: String,
+    /// Number of hooks removed
+    pub removed: usize,
+}
 
-/// List all configured hooks
+/// List all configured hooks, from config.toml and `.repository/hooks.toml`
 pub fn run_hooks_list(path: &Path) -> Result<()> {
     let config_path = path.join(".repository").join("config.toml");
     if !config_path.exists() {
-        println!(
+        say!(
             "{} No .repository/config.toml found. Run {} first.",
             "note:".yellow().bold(),
             "repo init".cyan()
@@ -23,35 +34,40 @@ pub fn run_hooks_list(path: &Path) -> Result<()> {
         return Ok(());
     }
 
-    let content = std::fs::read_to_string(&config_path)?;
-    let manifest = Manifest::parse(&content)?;
+    // Surface config errors rather than listing no hooks
+    Manifest::parse(&std::fs::read_to_string(&config_path)?)?;
+    let runner = HookRunner::from_repository(&NormalizedPath::new(path));
+    let hooks = runner.hooks();
+    if output::emit(hooks)? {
+        return Ok(());
+    }
 
-    if manifest.hooks.is_empty() {
-        println!("{} No hooks configured.", "note:".yellow().bold());
-        println!(
+    if hooks.is_empty() {
+        say!("{} No hooks configured.", "note:".yellow().bold());
+        say!(
             "\n{} Add a hook with: {}",
             "hint:".cyan().bold(),
             "repo hooks add <event> <command> [args...]".cyan()
         );
-        println!("  Events: {}", HookEvent::all_names().join(", ").dimmed());
+        say!("  Events: {}", HookEvent::all_names().join(", ").dimmed());
         return Ok(());
     }
 
-    println!(
+    say!(
         "{} {} hook(s) configured:\n",
         "=>".blue().bold(),
-        manifest.hooks.len()
+        hooks.len()
     );
-    println!(
+    say!(
         "  {:<25} {:<15} {}",
         "EVENT".bold(),
         "COMMAND".bold(),
         "ARGS".bold()
     );
-    println!("  {}", "\u{2500}".repeat(55).dimmed());
+    say!("  {}", "\u{2500}".repeat(55).dimmed());
 
-    for hook in &manifest.hooks {
-        println!(
+    for hook in hooks {
+        say!(
             "  {:<25} {:<15} {}",
             hook.event.to_string().cyan(),
             hook.command.clone(),
@@ -67,13 +83,13 @@ pub fn run_hooks_add(path: &Path, event_str: &str, command: &str, args: Vec<Stri
     let event = match HookEvent::parse(event_str) {
         Some(e) => e,
         None => {
-            println!(
+            say!(
                 "{} Unknown event '{}'. Valid events:",
                 "error:".red().bold(),
                 event_str
             );
             for name in HookEvent::all_names() {
-                println!("  - {}", name.cyan());
+                say!("  - {}", name.cyan());
             }
             return Ok(());
         }
@@ -81,7 +97,7 @@ pub fn run_hooks_add(path: &Path, event_str: &str, command: &str, args: Vec<Stri
 
     let config_path = path.join(".repository").join("config.toml");
     if !config_path.exists() {
-        println!(
+        say!(
             "{} No .repository/config.toml found. Run {} first.",
             "note:".yellow().bold(),
             "repo init".cyan()
@@ -89,22 +105,22 @@ pub fn run_hooks_add(path: &Path, event_str: &str, command: &str, args: Vec<Stri
         return Ok(());
     }
 
-    let content = std::fs::read_to_string(&config_path)?;
-    let mut manifest = Manifest::parse(&content)?;
-
     let hook = HookConfig {
         event,
         command: command.to_string(),
         args,
         working_dir: None,
+        timeout: None,
     };
 
-    manifest.hooks.push(hook);
+    output::emit(&hook)?;
 
-    let toml_content = manifest.to_toml();
-    std::fs::write(&config_path, toml_content)?;
+    let lock = ConfigStore::new().lock(&NormalizedPath::new(&config_path))?;
+    let mut editor = ManifestEditor::parse(&lock.read()?)?;
+    editor.add_hook(&hook)?;
+    lock.write(&editor.to_string())?;
 
-    println!(
+    say!(
         "{} Hook added: {} -> {}",
         "\u{2713}".green().bold(),
         event.to_string().cyan(),
@@ -119,13 +135,13 @@ pub fn run_hooks_remove(path: &Path, event_str: &str) -> Result<()> {
     let event = match HookEvent::parse(event_str) {
         Some(e) => e,
         None => {
-            println!(
+            say!(
                 "{} Unknown event '{}'. Valid events:",
                 "error:".red().bold(),
                 event_str
             );
             for name in HookEvent::all_names() {
-                println!("  - {}", name.cyan());
+                say!("  - {}", name.cyan());
             }
             return Ok(());
         }
@@ -133,7 +149,7 @@ pub fn run_hooks_remove(path: &Path, event_str: &str) -> Result<()> {
 
     let config_path = path.join(".repository").join("config.toml");
     if !config_path.exists() {
-        println!(
+        say!(
             "{} No .repository/config.toml found. Run {} first.",
             "note:".yellow().bold(),
             "repo init".cyan()
@@ -141,15 +157,16 @@ pub fn run_hooks_remove(path: &Path, event_str: &str) -> Result<()> {
         return Ok(());
     }
 
-    let content = std::fs::read_to_string(&config_path)?;
-    let mut manifest = Manifest::parse(&content)?;
-
-    let before = manifest.hooks.len();
-    manifest.hooks.retain(|h| h.event != event);
-    let removed = before - manifest.hooks.len();
+    let lock = ConfigStore::new().lock(&NormalizedPath::new(&config_path))?;
+    let mut editor = ManifestEditor::parse(&lock.read()?)?;
+    let removed = editor.remove_hooks(event);
+    output::emit(&HooksRemoveResult {
+        event: event.to_string(),
+        removed,
+    })?;
 
     if removed == 0 {
-        println!(
+        say!(
             "{} No hooks found for event '{}'.",
             "note:".yellow().bold(),
             event
@@ -157,10 +174,9 @@ pub fn run_hooks_remove(path: &Path, event_str: &str) -> Result<()> {
         return Ok(());
     }
 
-    let toml_content = manifest.to_toml();
-    std::fs::write(&config_path, toml_content)?;
+    lock.write(&editor.to_string())?;
 
-    println!(
+    say!(
         "{} Removed {} hook(s) for event '{}'.",
         "\u{2713}".green().bold(),
         removed,
@@ -273,4 +289,23 @@ mod tests {
         assert_eq!(manifest.hooks[0].event, HookEvent::PostBranchCreate);
         assert_eq!(manifest.hooks[1].event, HookEvent::PreSync);
     }
+
+    #[test]
+    fn test_hooks_add_remove_keeps_comments() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join(".repository/config.toml");
+        fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        let original =
+            "# Team setup\ntools = [\"cursor\"] # editors\n\n[core]\nmode = \"standard\"\n";
+        fs::write(&config_path, original).unwrap();
+
+        run_hooks_add(temp.path(), "pre-sync", "cargo", vec!["check".to_string()]).unwrap();
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(content.starts_with(original), "{content}");
+        assert!(content.contains("[[hooks]]\n"), "{content}");
+        assert!(content.contains("event = \"pre-sync\"\n"), "{content}");
+
+        run_hooks_remove(temp.path(), "pre-sync").unwrap();
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), original);
+    }
 }
@@ -1,13 +1,27 @@
 //! Init command implementation
 //!
 //! Initializes a new repository with Repository Manager configuration.
+//!
+//! Initializing inside an existing repository is refused unless `--nested`
+//! is given, in which case the new repository records the enclosing one as
+//! its parent (`parent` under `[core]`).
+//!
+//! With `--template`, the configuration and rules of a
+//! [`Template`](repo_core::Template) are merged into the new repository
+//! before it is set up further.
 
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use colored::Colorize;
+use repo_core::{HookContext, HookEvent, HookRunner, ImportMode, Template};
+use repo_fs::{ConfigStore, NormalizedPath, WorkspaceLayout};
+use repo_tools::ToolRegistry;
+use serde::Serialize;
 
+use crate::commands::tool::edit_manifest;
 use crate::error::{CliError, Result};
+use crate::output::{self, say};
 
 /// Configuration for init command
 pub struct InitConfig {
@@ -17,6 +31,36 @@ pub struct InitConfig {
     pub presets: Vec<String>,
     pub extensions: Vec<String>,
     pub remote: Option<String>,
+    /// Initialize as a sub-package of an enclosing repository
+    pub nested: bool,
+    /// Template whose configuration and rules the repository starts with
+    pub template: Option<Template>,
+}
+
+/// Result of the init command
+#[derive(Debug, Serialize)]
+pub struct InitResult {
+    pub path: String,
+    pub mode: String,
+    /// Configured tools, including those the template added
+    pub tools: Vec<String>,
+    pub presets: Vec<String>,
+    pub extensions: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Enclosing repository of a nested repository
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+}
+
+/// A template listed by `repo init --list-templates`
+#[derive(Debug, Serialize)]
+pub struct TemplateSummary {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 /// Run the init command
@@ -27,39 +71,51 @@ pub fn run_init(cwd: &Path, config: InitConfig) -> Result<PathBuf> {
     // Normalize mode early so all downstream usage (printing, config writing) is canonical
     let normalized_mode = normalize_mode(&config.mode)?;
 
-    // Determine target path
-    let target_path = if config.name == "." {
-        cwd.to_path_buf()
-    } else {
-        let sanitized = sanitize_project_name(&config.name);
-        let path = cwd.join(&sanitized);
-
-        // Create the folder
-        if !path.exists() {
-            std::fs::create_dir_all(&path)?;
-            println!(
-                "{} Created project folder: {}",
-                "=>".blue().bold(),
-                sanitized.cyan()
-            );
+    let target_path = project_path(cwd, &config.name);
+
+    let parent = enclosing_repository(&target_path);
+    if let Some(parent) = &parent {
+        say!(
+            "{} {} is inside the repository at {}",
+            "warn:".yellow().bold(),
+            target_path.display(),
+            parent.display()
+        );
+        if !config.nested {
+            return Err(CliError::user(
+                "Refusing to create a repository inside another one. Run `repo init --nested` \
+                 to set it up as a sub-package of the enclosing repository.",
+            ));
         }
-        path
-    };
+    }
+
+    // Create the folder
+    if !target_path.exists() {
+        std::fs::create_dir_all(&target_path)?;
+        say!(
+            "{} Created project folder: {}",
+            "=>".blue().bold(),
+            sanitize_project_name(&config.name).cyan()
+        );
+    }
 
-    println!(
+    say!(
         "{} Initializing repository in {} mode...",
         "=>".blue().bold(),
         normalized_mode.cyan()
     );
 
+    if let Some(template) = &config.template {
+        say!("   Template: {}", template.name.yellow());
+    }
     if !config.tools.is_empty() {
-        println!("   Tools: {}", config.tools.join(", ").yellow());
+        say!("   Tools: {}", config.tools.join(", ").yellow());
     }
     if !config.presets.is_empty() {
-        println!("   Presets: {}", config.presets.join(", ").yellow());
+        say!("   Presets: {}", config.presets.join(", ").yellow());
     }
     if !config.extensions.is_empty() {
-        println!("   Extensions: {}", config.extensions.join(", ").yellow());
+        say!("   Extensions: {}", config.extensions.join(", ").yellow());
     }
 
     init_repository(
@@ -70,35 +126,214 @@ pub fn run_init(cwd: &Path, config: InitConfig) -> Result<PathBuf> {
         &config.extensions,
     )?;
 
+    // Tools and presets given on the command line come first
+    let mut tools = config.tools.clone();
+    let mut presets = config.presets.clone();
+    if let Some(template) = &config.template {
+        let summary = template
+            .bundle
+            .apply(&target_path, ImportMode::Merge, &[])?;
+        for (label, items) in [
+            ("Template tools", &summary.tools_added),
+            ("Template presets", &summary.presets_added),
+            ("Template rules", &summary.rules_added),
+        ] {
+            if !items.is_empty() {
+                say!("   {}: {}", label, items.join(", ").yellow());
+            }
+        }
+        tools.extend(summary.tools_added);
+        presets.extend(summary.presets_added);
+    }
+
+    if let Some(parent) = &parent {
+        record_parent(&target_path, parent)?;
+        say!("   Parent: {}", parent.display().to_string().yellow());
+    }
+
     // Add remote if specified
     if let Some(remote_url) = &config.remote {
         add_git_remote(&target_path, remote_url)?;
-        println!("   Remote: {}", remote_url.yellow());
+        say!("   Remote: {}", remote_url.yellow());
+    }
+
+    output::emit(&InitResult {
+        path: target_path.display().to_string(),
+        mode: normalized_mode.clone(),
+        tools: tools.clone(),
+        presets,
+        extensions: config.extensions.clone(),
+        template: config.template.as_ref().map(|t| t.name.clone()),
+        parent: parent.as_ref().map(|p| p.display().to_string()),
+        remote: config.remote.clone(),
+    })?;
+    say!("{} Repository initialized!", "OK".green().bold());
+
+    // config.toml was just written, so post-init hooks come from hooks.toml
+    let hooks = HookRunner::from_repository(&NormalizedPath::new(&target_path));
+    for result in hooks.run(HookEvent::PostInit, &HookContext::default())? {
+        if !result.success {
+            say!(
+                "{} Post-init hook '{}' failed",
+                "warn:".yellow().bold(),
+                result.command
+            );
+        }
     }
 
-    println!("{} Repository initialized!", "OK".green().bold());
-
     // Post-init guidance
-    println!();
-    if !config.tools.is_empty() {
-        println!(
+    say!();
+    if !tools.is_empty() {
+        say!(
             "{} Next step: run {} to generate tool configurations",
             "=>".blue().bold(),
             "repo sync".cyan()
         );
     } else {
-        println!(
+        say!(
             "{} Next step: run {} to add a tool, then {} to generate configs",
             "=>".blue().bold(),
             "repo add-tool <name>".cyan(),
             "repo sync".cyan()
         );
     }
-    println!("   Run {} to see available tools", "repo list-tools".cyan());
+    say!("   Run {} to see available tools", "repo list-tools".cyan());
 
     Ok(target_path)
 }
 
+/// Run `repo init --list-templates`
+///
+/// Lists the templates in `dir` with their descriptions.
+pub fn run_list_templates(dir: &Path) -> Result<()> {
+    let templates = repo_core::list_templates(dir);
+    let summaries: Vec<TemplateSummary> = templates
+        .iter()
+        .map(|t| TemplateSummary {
+            name: t.name.clone(),
+            description: t.description().map(str::to_string),
+        })
+        .collect();
+    if output::emit(&summaries)? {
+        return Ok(());
+    }
+    if templates.is_empty() {
+        say!(
+            "{} No templates in {}",
+            "=>".blue().bold(),
+            dir.display().to_string().cyan()
+        );
+        return Ok(());
+    }
+
+    say!(
+        "{} Templates in {}:",
+        "=>".blue().bold(),
+        dir.display().to_string().cyan()
+    );
+    let width = templates.iter().map(|t| t.name.len()).max().unwrap_or(0);
+    for template in &templates {
+        say!(
+            "   {:<width$}  {}",
+            template.name.cyan(),
+            template.description().unwrap_or_default().dimmed(),
+            width = width
+        );
+    }
+    Ok(())
+}
+
+/// The user's templates directory
+///
+/// # Errors
+///
+/// Fails if the platform has no configuration directory.
+pub fn templates_dir() -> Result<PathBuf> {
+    repo_core::templates_dir()
+        .ok_or_else(|| CliError::user("Cannot determine the templates directory"))
+}
+
+/// Directory `repo init <name>` initializes, relative to `cwd`
+///
+/// `"."` is `cwd` itself; any other name is sanitized into a folder name.
+pub fn project_path(cwd: &Path, name: &str) -> PathBuf {
+    if name == "." {
+        cwd.to_path_buf()
+    } else {
+        cwd.join(sanitize_project_name(name))
+    }
+}
+
+/// Nearest repository strictly above `path`, if any
+pub fn enclosing_repository(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .skip(1)
+        .find(|dir| dir.join(".repository").join("config.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Warn about `tools` that are not built in, suggesting close matches
+///
+/// Unknown tools are still configured: a definition in `.repository/tools/`
+/// can provide them later.
+pub fn warn_unknown_tools(tools: &[String]) {
+    let registry = ToolRegistry::with_builtins();
+    for tool in tools {
+        if let Err(unknown) = registry.validate_name(tool) {
+            eprintln!("{} {}", "warning:".yellow().bold(), unknown);
+        }
+    }
+}
+
+/// Check that `url` is syntactically a git remote URL
+///
+/// Accepts `<scheme>://host/path` URLs (`https`, `http`, `ssh`, `git`, and
+/// `file` without a host) and the scp-like `[user@]host:path` form.
+pub fn validate_remote_url(url: &str) -> std::result::Result<(), String> {
+    let url = url.trim();
+    if url.is_empty() {
+        return Err("Remote URL is empty".to_string());
+    }
+    if url.chars().any(char::is_whitespace) {
+        return Err("Remote URL cannot contain whitespace".to_string());
+    }
+
+    if let Some((scheme, rest)) = url.split_once("://") {
+        if !matches!(scheme, "https" | "http" | "ssh" | "git" | "file") {
+            return Err(format!("Unsupported URL scheme '{}'", scheme));
+        }
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+        if (host.is_empty() && scheme != "file") || path.is_empty() {
+            return Err(format!("'{}' is missing a host or path", url));
+        }
+        return Ok(());
+    }
+
+    match url.split_once(':') {
+        Some((host, path)) if !host.is_empty() && !host.contains('/') && !path.is_empty() => Ok(()),
+        _ => Err(format!(
+            "'{}' is not a git URL (expected https://host/path or user@host:path)",
+            url
+        )),
+    }
+}
+
+/// Record `parent` as the enclosing repository of the one at `path`
+fn record_parent(path: &Path, parent: &Path) -> Result<()> {
+    let depth = path
+        .strip_prefix(parent)
+        .map_or(0, |rel| rel.components().count());
+    let parent = vec![".."; depth].join("/");
+    edit_manifest(
+        &NormalizedPath::new(path.join(".repository").join("config.toml")),
+        |editor| {
+            editor.set_key(&["core"], "parent", &serde_json::json!(parent));
+            true
+        },
+    )?;
+    Ok(())
+}
+
 /// Normalize a mode string to its canonical form.
 ///
 /// Accepts aliases like "worktree" and returns the canonical form "worktrees".
@@ -155,6 +390,7 @@ pub fn sanitize_project_name(name: &str) -> String {
 /// - Creates `config.toml` with the specified mode, tools, and presets
 /// - Initializes git if `.git` doesn't exist
 /// - For worktrees mode, creates the `main/` directory
+/// - Records the detected workspace layout in `config.toml`
 pub fn init_repository(
     path: &Path,
     mode: &str,
@@ -169,14 +405,15 @@ pub fn init_repository(
     let repo_dir = path.join(".repository");
     std::fs::create_dir_all(&repo_dir)?;
 
-    // Generate and write config.toml
+    // Generate and write config.toml, under the lock its readers take
     let config_content = generate_config(&canonical_mode, tools, presets, extensions);
     let config_path = repo_dir.join("config.toml");
-    std::fs::write(&config_path, config_content)?;
+    ConfigStore::new().save_text(&NormalizedPath::new(&config_path), &config_content)?;
 
-    // Initialize git if .git doesn't exist
+    // Initialize git if .git doesn't exist; a nested repository is tracked
+    // by its parent's
     let git_dir = path.join(".git");
-    if !git_dir.exists() {
+    if !git_dir.exists() && enclosing_repository(path).is_none() {
         init_git(path)?;
     }
 
@@ -188,6 +425,9 @@ pub fn init_repository(
         }
     }
 
+    // Record the layout so later detection can skip probing markers
+    WorkspaceLayout::detect(path)?.record()?;
+
     Ok(())
 }
 
@@ -233,6 +473,14 @@ pub fn generate_config(
 
     let mut config = String::new();
 
+    // Sorted, as `repo config fmt` writes them
+    let sorted = |items: &[String]| {
+        let mut items = items.to_vec();
+        items.sort();
+        items
+    };
+    let (tools, presets, extensions) = (sorted(tools), sorted(presets), sorted(extensions));
+
     // tools array at top level (before [core] section)
     let tools_arr: Vec<String> = tools
         .iter()
@@ -247,7 +495,7 @@ pub fn generate_config(
 
     // [presets] section with each preset as a table
     if !presets.is_empty() {
-        for preset in presets {
+        for preset in &presets {
             config.push('\n');
             config.push_str(&format!("[presets.\"{}\"]\n", escape_toml_value(preset)));
         }
@@ -256,7 +504,7 @@ pub fn generate_config(
     // [extensions] section
     if !extensions.is_empty() {
         let registry = ExtensionRegistry::with_known();
-        for ext in extensions {
+        for ext in &extensions {
             config.push('\n');
             let escaped_ext = escape_toml_value(ext);
             if let Some(entry) = registry.get(ext) {
@@ -315,8 +563,31 @@ fn add_git_remote(path: &Path, remote_url: &str) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use repo_core::Manifest;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_validate_remote_url() {
+        for url in [
+            "https://github.com/org/repo.git",
+            "ssh://git@github.com/org/repo",
+            "git@github.com:org/repo.git",
+            "file:///srv/git/repo.git",
+        ] {
+            assert!(validate_remote_url(url).is_ok(), "{url}");
+        }
+        for url in [
+            "",
+            "github.com/org/repo",
+            "https://github.com",
+            "ftp://host/repo",
+            "https://host/my repo",
+            "origin",
+        ] {
+            assert!(validate_remote_url(url).is_err(), "{url}");
+        }
+    }
+
     #[test]
     fn test_sanitize_project_name_basic() {
         assert_eq!(sanitize_project_name("my-project"), "my-project");
@@ -351,6 +622,8 @@ mod tests {
             presets: vec![],
             extensions: vec![],
             remote: None,
+            nested: false,
+            template: None,
         };
 
         let result = run_init(temp_dir.path(), config);
@@ -373,6 +646,8 @@ mod tests {
             presets: vec![],
             extensions: vec![],
             remote: None,
+            nested: false,
+            template: None,
         };
 
         let result = run_init(temp_dir.path(), config);
@@ -395,6 +670,8 @@ mod tests {
             presets: vec![],
             extensions: vec![],
             remote: None,
+            nested: false,
+            template: None,
         };
 
         let result = run_init(temp_dir.path(), config);
@@ -427,6 +704,7 @@ mod tests {
         let config_content = std::fs::read_to_string(&config_path).unwrap();
         assert!(config_content.contains("[core]"));
         assert!(config_content.contains("mode = \"standard\""));
+        assert!(config_content.contains("layout = \"classic\""));
     }
 
     #[test]
@@ -597,6 +875,8 @@ mod tests {
             presets: vec![],
             extensions: vec![],
             remote: None,
+            nested: false,
+            template: None,
         };
 
         let result = run_init(temp_dir.path(), config);
@@ -619,6 +899,8 @@ mod tests {
             presets: vec![],
             extensions: vec![],
             remote: None,
+            nested: false,
+            template: None,
         };
 
         let result = run_init(temp_dir.path(), config);
@@ -628,4 +910,108 @@ mod tests {
         assert_eq!(project_path.file_name().unwrap(), "empty-project");
         assert!(project_path.join(".repository").exists());
     }
+
+    #[test]
+    fn test_run_init_with_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let templates = temp_dir.path().join("templates");
+        std::fs::create_dir_all(&templates).unwrap();
+        std::fs::write(
+            templates.join("backend-service.toml"),
+            "version = 1\ndescription = \"Backend\"\n\n[config]\ntools = [\"claude\"]\n\n\
+             [config.presets.\"env:python\"]\nversion = \"3.12\"\n\n\
+             [rule_files]\n\"style.md\" = \"Use snake_case.\"\n",
+        )
+        .unwrap();
+
+        let config = InitConfig {
+            name: "service".to_string(),
+            mode: "standard".to_string(),
+            tools: vec!["cursor".to_string()],
+            presets: vec![],
+            extensions: vec![],
+            remote: None,
+            nested: false,
+            template: Some(repo_core::load_template(&templates, "backend-service").unwrap()),
+        };
+        let path = run_init(temp_dir.path(), config).unwrap();
+
+        let manifest = Manifest::parse(
+            &std::fs::read_to_string(path.join(".repository/config.toml")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(manifest.tools, ["cursor", "claude"]);
+        assert_eq!(manifest.core.mode, "standard");
+        assert_eq!(manifest.presets["env:python"]["version"], "3.12");
+        assert_eq!(
+            std::fs::read_to_string(path.join(".repository/rules/style.md")).unwrap(),
+            "Use snake_case."
+        );
+    }
+
+    #[test]
+    fn test_run_init_runs_post_init_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join(".repository");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        std::fs::write(
+            repo_dir.join("hooks.toml"),
+            "[[hooks]]\nevent = \"post-init\"\ncommand = \"git\"\n\
+             args = [\"config\", \"--file\", \"initialized.cfg\", \"hook.event\", \"${REPO_EVENT}\"]\n",
+        )
+        .unwrap();
+
+        let config = InitConfig {
+            name: ".".to_string(),
+            mode: "standard".to_string(),
+            tools: vec![],
+            presets: vec![],
+            extensions: vec![],
+            remote: None,
+            nested: false,
+            template: None,
+        };
+        run_init(temp_dir.path(), config).unwrap();
+
+        let written = std::fs::read_to_string(temp_dir.path().join("initialized.cfg")).unwrap();
+        assert!(written.contains("event = post-init"), "{written}");
+    }
+
+    #[test]
+    fn test_run_init_inside_repository_requires_nested() {
+        let temp_dir = TempDir::new().unwrap();
+        init_repository(temp_dir.path(), "standard", &[], &[], &[]).unwrap();
+        let packages = temp_dir.path().join("packages");
+        std::fs::create_dir_all(&packages).unwrap();
+
+        let config = |nested| InitConfig {
+            name: "api".to_string(),
+            mode: "standard".to_string(),
+            tools: vec![],
+            presets: vec![],
+            extensions: vec![],
+            remote: None,
+            nested,
+            template: None,
+        };
+
+        let err = run_init(&packages, config(false)).unwrap_err();
+        assert!(err.to_string().contains("--nested"), "{err}");
+        assert!(!packages.join("api").exists());
+
+        let path = run_init(&packages, config(true)).unwrap();
+        assert_eq!(
+            enclosing_repository(&path),
+            Some(temp_dir.path().to_path_buf())
+        );
+        assert!(!path.join(".git").exists());
+
+        let content = std::fs::read_to_string(path.join(".repository/config.toml")).unwrap();
+        let manifest = Manifest::parse(&content).unwrap();
+        assert_eq!(manifest.core.parent.as_deref(), Some("../.."));
+        assert_eq!(
+            manifest.parent_root(&NormalizedPath::new(&path)),
+            Some(NormalizedPath::new(temp_dir.path()))
+        );
+    }
 }
@@ -1,14 +1,69 @@
 //! List commands for tools and presets
 
+use std::path::Path;
+
 use colored::Colorize;
+use serde::Serialize;
+
+use repo_fs::NormalizedPath;
 use repo_meta::Registry;
-use repo_tools::{ToolCategory, ToolRegistry};
+use repo_meta::schema::ToolDefinition;
+use repo_tools::{ToolCategory, ToolDispatcher, close_matches};
 
 use crate::error::Result;
+use crate::output::{self, say};
+
+/// Values accepted by `repo list-tools --category`
+const CATEGORY_NAMES: [&str; 4] = ["ide", "cli-agent", "autonomous", "copilot"];
+
+/// Built-in tool categories, with their `--category` name and heading
+const CATEGORIES: [(ToolCategory, &str, &str); 4] = [
+    (ToolCategory::Ide, "ide", "IDE Tools"),
+    (ToolCategory::CliAgent, "cli-agent", "CLI Agents"),
+    (ToolCategory::Autonomous, "autonomous", "Autonomous Agents"),
+    (ToolCategory::Copilot, "copilot", "Copilots"),
+];
+
+/// A tool in `repo list-tools`
+#[derive(Debug, Serialize)]
+pub struct ToolEntry {
+    pub slug: String,
+    pub name: String,
+    /// `--category` name of a built-in tool; custom tools have none
+    pub category: Option<&'static str>,
+    pub config_path: String,
+    /// `builtin`, `repository` or `extension`
+    pub source: String,
+}
+
+/// A preset in `repo list-presets`
+#[derive(Debug, Serialize)]
+pub struct PresetEntry {
+    pub name: String,
+    pub provider: String,
+}
+
+/// Tools defined in `.repository/tools/` or by installed extensions that
+/// have no built-in, sorted by slug, with where each comes from
+fn custom_tools(dispatcher: &ToolDispatcher) -> Vec<(&ToolDefinition, String)> {
+    dispatcher
+        .list_available()
+        .iter()
+        .filter(|slug| dispatcher.get_registration(slug).is_none())
+        .filter_map(|slug| {
+            let source = dispatcher.resolution(slug)?.source().to_string();
+            Some((dispatcher.definition(slug)?, source))
+        })
+        .collect()
+}
 
 /// Run the list-tools command
-pub fn run_list_tools(category_filter: Option<&str>) -> Result<()> {
-    let registry = ToolRegistry::with_builtins();
+///
+/// Lists the built-in tools, then the tools defined in the repository at
+/// `path` and by its installed extensions.
+pub fn run_list_tools(path: &Path, category_filter: Option<&str>) -> Result<()> {
+    let dispatcher = ToolDispatcher::from_repository(&NormalizedPath::new(path));
+    let registry = dispatcher.registry();
 
     // Parse category filter if provided
     let filter: Option<ToolCategory> = match category_filter {
@@ -17,50 +72,100 @@ pub fn run_list_tools(category_filter: Option<&str>) -> Result<()> {
         Some("autonomous") => Some(ToolCategory::Autonomous),
         Some("copilot") => Some(ToolCategory::Copilot),
         Some(other) => {
+            let suggestions = close_matches(other, CATEGORY_NAMES);
+            let hint = match suggestions.first() {
+                Some(suggestion) => format!(", did you mean '{}'?", suggestion),
+                None => String::new(),
+            };
             eprintln!(
-                "{} Unknown category '{}'. Valid: ide, cli-agent, autonomous, copilot",
+                "{} Unknown category '{}'{}. Valid: {}",
                 "warning:".yellow().bold(),
-                other
+                other,
+                hint,
+                CATEGORY_NAMES.join(", ")
             );
             None
         }
         None => None,
     };
 
-    println!("{}", "Available Tools".bold());
-    println!();
-
-    // Group by category
-    let categories = [
-        (ToolCategory::Ide, "IDE Tools"),
-        (ToolCategory::CliAgent, "CLI Agents"),
-        (ToolCategory::Autonomous, "Autonomous Agents"),
-        (ToolCategory::Copilot, "Copilots"),
-    ];
-
-    for (cat, label) in categories {
+    let mut tools = Vec::new();
+    for (cat, category, _) in CATEGORIES {
         // Skip if filtering and this isn't the category
         if filter.is_some_and(|f| f != cat) {
             continue;
         }
+        tools.extend(registry.by_category(cat).into_iter().filter_map(|slug| {
+            let reg = registry.get(slug)?;
+            Some(ToolEntry {
+                slug: slug.to_string(),
+                name: reg.name.clone(),
+                category: Some(category),
+                config_path: reg.definition.integration.config_path.clone(),
+                source: "builtin".to_string(),
+            })
+        }));
+    }
+    // Custom tools have no category
+    if filter.is_none() {
+        tools.extend(
+            custom_tools(&dispatcher)
+                .into_iter()
+                .map(|(definition, source)| ToolEntry {
+                    slug: definition.meta.slug.clone(),
+                    name: definition.meta.name.clone(),
+                    category: None,
+                    config_path: definition.integration.config_path.clone(),
+                    source,
+                }),
+        );
+    }
+    if output::emit(&tools)? {
+        return Ok(());
+    }
+
+    say!("{}", "Available Tools".bold());
+    say!();
 
-        let tools = registry.by_category(cat);
-        if tools.is_empty() {
+    // Group by category
+    for (_, category, label) in CATEGORIES {
+        let mut entries = tools
+            .iter()
+            .filter(|t| t.category == Some(category))
+            .peekable();
+        if entries.peek().is_none() {
             continue;
         }
 
-        println!("{}:", label.cyan().bold());
-        for slug in tools {
-            if let Some(reg) = registry.get(slug) {
-                let config = &reg.definition.integration.config_path;
-                println!("  {:<14} {} ({})", slug.green(), reg.name, config.dimmed());
-            }
+        say!("{}:", label.cyan().bold());
+        for tool in entries {
+            say!(
+                "  {:<14} {} ({})",
+                tool.slug.green(),
+                tool.name,
+                tool.config_path.dimmed()
+            );
+        }
+        say!();
+    }
+
+    let mut custom = tools.iter().filter(|t| t.category.is_none()).peekable();
+    if custom.peek().is_some() {
+        say!("{}:", "Custom Tools".cyan().bold());
+        for tool in custom {
+            say!(
+                "  {:<14} {} ({}, {})",
+                tool.slug.green(),
+                tool.name,
+                tool.config_path.dimmed(),
+                tool.source.dimmed()
+            );
         }
-        println!();
+        say!();
     }
 
-    let total = registry.len();
-    println!(
+    let total = dispatcher.total_tool_count();
+    say!(
         "{} {} tools available. Use {} to add one.",
         "Total:".dimmed(),
         total,
@@ -73,18 +178,34 @@ pub fn run_list_tools(category_filter: Option<&str>) -> Result<()> {
 /// Run the list-presets command
 pub fn run_list_presets() -> Result<()> {
     let registry = Registry::with_builtins();
+    let presets: Vec<PresetEntry> = registry
+        .list_presets()
+        .into_iter()
+        .filter_map(|preset| {
+            let provider = registry.get_provider(&preset)?.to_string();
+            Some(PresetEntry {
+                name: preset,
+                provider,
+            })
+        })
+        .collect();
+    if output::emit(&presets)? {
+        return Ok(());
+    }
 
-    println!("{}", "Available Presets".bold());
-    println!();
+    say!("{}", "Available Presets".bold());
+    say!();
 
-    for preset in registry.list_presets() {
-        if let Some(provider) = registry.get_provider(&preset) {
-            println!("  {:<16} (provider: {})", preset.green(), provider.dimmed());
-        }
+    for preset in &presets {
+        say!(
+            "  {:<16} (provider: {})",
+            preset.name.green(),
+            preset.provider.dimmed()
+        );
     }
 
-    println!();
-    println!(
+    say!();
+    say!(
         "{} {} presets available. Use {} to add one.",
         "Total:".dimmed(),
         registry.len(),
@@ -97,19 +218,50 @@ pub fn run_list_presets() -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+    use tempfile::TempDir;
 
     #[test]
     fn test_list_tools_runs() {
-        let result = run_list_tools(None);
+        let temp = TempDir::new().unwrap();
+        let result = run_list_tools(temp.path(), None);
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_list_tools_with_category() {
-        let result = run_list_tools(Some("ide"));
+        let temp = TempDir::new().unwrap();
+        let result = run_list_tools(temp.path(), Some("ide"));
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_list_tools_includes_extension_tools() {
+        let temp = TempDir::new().unwrap();
+        let ext_dir = temp.path().join(".repository/extensions/kit");
+        fs::create_dir_all(ext_dir.join("tools")).unwrap();
+        fs::write(
+            ext_dir.join("repo_extension.toml"),
+            "[extension]\nname = \"kit\"\nversion = \"0.1.0\"\n\n\
+             [provides]\ntools = [\"kittool\"]\ntool_definitions = [\"tools/kittool.toml\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            ext_dir.join("tools/kittool.toml"),
+            "[meta]\nname = \"Kit Tool\"\nslug = \"kittool\"\n\n\
+             [integration]\nconfig_path = \".kittool.md\"\ntype = \"markdown\"\n",
+        )
+        .unwrap();
+
+        let dispatcher = ToolDispatcher::from_repository(&NormalizedPath::new(temp.path()));
+        let custom: Vec<(&str, String)> = custom_tools(&dispatcher)
+            .into_iter()
+            .map(|(definition, source)| (definition.meta.slug.as_str(), source))
+            .collect();
+        assert_eq!(custom, vec![("kittool", "extension".to_string())]);
+        assert!(run_list_tools(temp.path(), None).is_ok());
+    }
+
     #[test]
     fn test_list_presets_runs() {
         let result = run_list_presets();
@@ -0,0 +1,488 @@
+//! MCP command implementations
+//!
+//! `add`, `remove` and `list` work directly on a tool's MCP config through
+//! [`McpInstaller`]. `sync` pushes the servers declared under
+//! `[mcp.servers]` in config.toml to every enabled tool that supports MCP;
+//! the servers it wrote are recorded in the ledger, so servers dropped from
+//! config.toml are removed from tool configs on the next sync.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use colored::Colorize;
+use repo_core::{McpSyncer, SyncEngine};
+use repo_fs::NormalizedPath;
+use repo_meta::schema::{McpScope, McpServerConfig, McpTransportConfig, SecretRedaction};
+use repo_tools::{MCP_CAPABLE_TOOLS, McpInstaller, mcp_config_spec};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::commands::sync::{detect_mode, resolve_root};
+use crate::commands::tool::load_manifest;
+use crate::error::{CliError, Result};
+use crate::output::{self, say};
+
+/// Path to config.toml within a repository
+const CONFIG_PATH: &str = ".repository/config.toml";
+
+/// Result of `repo mcp add` and `repo mcp remove`
+#[derive(Debug, Serialize)]
+pub struct McpServerChange<'a> {
+    pub server: &'a str,
+    pub tool: &'a str,
+    pub scope: &'static str,
+    /// Whether the tool's config changed; removing a missing server doesn't
+    pub changed: bool,
+}
+
+/// What `repo mcp sync` did for one tool
+#[derive(Debug, Default, Serialize)]
+pub struct McpToolSync {
+    pub tool: String,
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    /// Why the tool could not be synced
+    pub error: Option<String>,
+}
+
+/// Options for `repo mcp add`
+#[derive(Debug, Default)]
+pub struct McpAddOptions {
+    /// Command that starts a stdio server
+    pub command: Option<String>,
+    /// Arguments to the command
+    pub args: Vec<String>,
+    /// URL of an HTTP server
+    pub url: Option<String>,
+    /// Environment variables as `KEY=VALUE`
+    pub env: Vec<String>,
+    /// Expand `${VAR}` placeholders at install time
+    pub expand_env: bool,
+}
+
+impl McpAddOptions {
+    /// Build the canonical server config
+    fn into_config(self) -> Result<McpServerConfig> {
+        let transport = match (self.command, self.url) {
+            (Some(command), None) => McpTransportConfig::Stdio {
+                command,
+                args: self.args,
+                cwd: None,
+            },
+            (None, Some(url)) => McpTransportConfig::Http { url, headers: None },
+            _ => return Err(CliError::user("Specify exactly one of --command or --url")),
+        };
+
+        let mut env = BTreeMap::new();
+        for pair in self.env {
+            let Some((key, value)) = pair.split_once('=') else {
+                return Err(CliError::user(format!(
+                    "Invalid --env '{pair}': expected KEY=VALUE"
+                )));
+            };
+            env.insert(key.to_string(), value.to_string());
+        }
+
+        Ok(McpServerConfig {
+            transport,
+            env: (!env.is_empty()).then_some(env),
+            auto_approve: false,
+            expand_env: self.expand_env,
+        })
+    }
+}
+
+/// Create an installer for `tool`, rejecting tools without MCP support
+fn installer(tool: &str, root: &NormalizedPath) -> Result<McpInstaller> {
+    if mcp_config_spec(tool).is_none() {
+        return Err(CliError::user(format!(
+            "Tool '{tool}' does not support MCP. Supported tools: {}",
+            MCP_CAPABLE_TOOLS.join(", ")
+        )));
+    }
+    Ok(McpInstaller::new(tool, root.clone()).map_err(repo_core::Error::from)?)
+}
+
+/// Key patterns redacted by `list`, including `core.secret_patterns`
+fn redaction(root: &NormalizedPath) -> SecretRedaction {
+    let extra = load_manifest(&root.join(CONFIG_PATH))
+        .map(|m| m.core.secret_patterns)
+        .unwrap_or_default();
+    SecretRedaction::default().with_patterns(extra)
+}
+
+fn scope_name(scope: McpScope) -> &'static str {
+    match scope {
+        McpScope::Project => "project",
+        McpScope::User => "user",
+    }
+}
+
+/// Handle `repo mcp add <name> --tool <tool> (--command <cmd> | --url <url>)`
+pub fn run_mcp_add(
+    path: &Path,
+    name: &str,
+    tool: &str,
+    scope: McpScope,
+    options: McpAddOptions,
+) -> Result<()> {
+    let root = resolve_root(path)?;
+    let config = options.into_config()?;
+    installer(tool, &root)?
+        .install(scope, name, &config)
+        .map_err(repo_core::Error::from)?;
+    output::emit(&McpServerChange {
+        server: name,
+        tool,
+        scope: scope_name(scope),
+        changed: true,
+    })?;
+
+    say!(
+        "{} Added MCP server {} to {} ({} scope)",
+        "\u{2713}".green().bold(),
+        name.cyan(),
+        tool.cyan(),
+        scope_name(scope)
+    );
+    Ok(())
+}
+
+/// Handle `repo mcp remove <name> --tool <tool>`
+pub fn run_mcp_remove(path: &Path, name: &str, tool: &str, scope: McpScope) -> Result<()> {
+    let root = resolve_root(path)?;
+    let removed = installer(tool, &root)?
+        .remove(scope, name)
+        .map_err(repo_core::Error::from)?;
+    output::emit(&McpServerChange {
+        server: name,
+        tool,
+        scope: scope_name(scope),
+        changed: removed,
+    })?;
+
+    if removed {
+        say!(
+            "{} Removed MCP server {} from {}",
+            "\u{2713}".green().bold(),
+            name.cyan(),
+            tool.cyan()
+        );
+    } else {
+        say!(
+            "{} MCP server {} is not installed in {}",
+            "note:".yellow().bold(),
+            name.cyan(),
+            tool.cyan()
+        );
+    }
+    Ok(())
+}
+
+/// Handle `repo mcp list [--tool <tool>]`
+///
+/// Without `--tool`, every MCP-capable tool is listed, skipping tools with
+/// no servers or no config at `scope`.
+pub fn run_mcp_list(path: &Path, tool: Option<&str>, scope: McpScope) -> Result<()> {
+    let root = resolve_root(path)?;
+    let redaction = redaction(&root);
+
+    let mut listed: BTreeMap<String, Vec<(String, Value)>> = BTreeMap::new();
+    match tool {
+        Some(tool) => {
+            let servers = installer(tool, &root)?
+                .with_redaction(redaction)
+                .list(scope)
+                .map_err(repo_core::Error::from)?;
+            listed.insert(tool.to_string(), servers);
+        }
+        None => {
+            for tool in MCP_CAPABLE_TOOLS {
+                let installer = installer(tool, &root)?.with_redaction(redaction.clone());
+                match installer.list(scope) {
+                    Ok(servers) if !servers.is_empty() => {
+                        listed.insert(tool.to_string(), servers);
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::debug!("Skipping {} MCP servers: {}", tool, e),
+                }
+            }
+        }
+    }
+
+    if output::is_structured() {
+        // Servers by name, by tool
+        let servers: BTreeMap<String, BTreeMap<String, Value>> = listed
+            .into_iter()
+            .map(|(tool, servers)| (tool, servers.into_iter().collect()))
+            .collect();
+        output::emit(&servers)?;
+        return Ok(());
+    }
+
+    if listed.values().all(Vec::is_empty) {
+        say!(
+            "{} No MCP servers installed ({} scope).",
+            "note:".yellow().bold(),
+            scope_name(scope)
+        );
+        return Ok(());
+    }
+
+    for (tool, servers) in &listed {
+        say!("{} {}", "=>".blue().bold(), tool.cyan().bold());
+        for (name, server) in servers {
+            say!("   {:<20} {}", name, server.to_string().dimmed());
+        }
+    }
+    Ok(())
+}
+
+/// Handle `repo mcp sync`
+///
+/// Tools that were synced before but are no longer enabled have their
+/// managed servers removed.
+pub fn run_mcp_sync(path: &Path) -> Result<()> {
+    let root = resolve_root(path)?;
+    let synced = sync_mcp_servers(&root)?;
+    output::emit(&synced)?;
+    if synced.is_empty() {
+        say!(
+            "{} No enabled tools support MCP. Supported tools: {}",
+            "note:".yellow().bold(),
+            MCP_CAPABLE_TOOLS.join(", ")
+        );
+    }
+    check_mcp_sync(&synced)
+}
+
+/// Push the servers under `[mcp.servers]` to every enabled MCP-capable tool
+///
+/// Prints what changed per tool and records the written servers in the
+/// ledger. A tool that cannot be synced is reported in its entry without
+/// stopping the others; see [`check_mcp_sync`]. Returns no entries when no
+/// enabled tool supports MCP and none was synced before.
+pub fn sync_mcp_servers(root: &NormalizedPath) -> Result<Vec<McpToolSync>> {
+    let manifest = load_manifest(&root.join(CONFIG_PATH))?;
+    let engine = SyncEngine::new(root.clone(), detect_mode(root)?)?;
+    let mut ledger = engine.load_ledger()?;
+    let syncer = McpSyncer::new(root.clone());
+
+    let servers = &manifest.mcp.servers;
+    let no_servers = BTreeMap::new();
+    let mut targets: Vec<(String, &BTreeMap<String, McpServerConfig>)> = manifest
+        .tools
+        .iter()
+        .filter(|tool| mcp_config_spec(tool).is_some())
+        .map(|tool| (tool.clone(), servers))
+        .collect();
+    for tool in McpSyncer::synced_tools(&ledger) {
+        if !targets.iter().any(|(t, _)| *t == tool) {
+            targets.push((tool, &no_servers));
+        }
+    }
+
+    if targets.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    say!(
+        "{} Syncing {} MCP server(s)...",
+        "=>".blue().bold(),
+        servers.len()
+    );
+
+    let mut synced = Vec::new();
+    for (tool, servers) in targets {
+        match syncer.sync_tool(&tool, servers, &mut ledger) {
+            Ok(Some(result)) if result.is_empty() => {
+                say!("   {} {} up to date", "-".dimmed(), tool.cyan());
+                synced.push(McpToolSync {
+                    tool,
+                    ..Default::default()
+                });
+            }
+            Ok(Some(result)) => {
+                say!("   {} {}", "*".green(), tool.cyan());
+                for name in &result.added {
+                    say!("      {} {}", "+".green(), name);
+                }
+                for name in &result.updated {
+                    say!("      {} {}", "~".yellow(), name);
+                }
+                for name in &result.removed {
+                    say!("      {} {}", "-".red(), name);
+                }
+                synced.push(McpToolSync {
+                    tool,
+                    added: result.added,
+                    updated: result.updated,
+                    removed: result.removed,
+                    error: None,
+                });
+            }
+            Ok(None) => {}
+            Err(e) => {
+                say!("   {} {}: {}", "x".red(), tool.cyan(), e);
+                synced.push(McpToolSync {
+                    tool: tool.clone(),
+                    error: Some(e.to_string()),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    engine.save_ledger(&ledger)?;
+    Ok(synced)
+}
+
+/// Fail if any tool in `synced` could not be synced
+pub fn check_mcp_sync(synced: &[McpToolSync]) -> Result<()> {
+    let errors: Vec<&str> = synced
+        .iter()
+        .filter(|sync| sync.error.is_some())
+        .map(|sync| sync.tool.as_str())
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::user(format!(
+            "MCP sync failed for {}",
+            errors.join(", ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use repo_core::Ledger;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Create config.toml and a .git directory (required for sync)
+    fn setup_repo(dir: &Path, config: &str) {
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        let repo_dir = dir.join(".repository");
+        fs::create_dir_all(&repo_dir).unwrap();
+        fs::write(repo_dir.join("config.toml"), config).unwrap();
+    }
+
+    fn cursor_servers(dir: &Path) -> Value {
+        let content = fs::read_to_string(dir.join(".cursor/mcp.json")).unwrap();
+        serde_json::from_str::<Value>(&content).unwrap()["mcpServers"].clone()
+    }
+
+    const SERVERS: &str = r#"
+[mcp.servers.a.transport]
+type = "stdio"
+command = "server-a"
+
+[mcp.servers.b.transport]
+type = "http"
+url = "https://example.com/mcp"
+"#;
+
+    #[test]
+    fn test_mcp_add_and_remove() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(temp.path(), "[core]\nmode = \"standard\"\n");
+
+        let options = McpAddOptions {
+            command: Some("npx".into()),
+            args: vec!["-y".into(), "server".into()],
+            env: vec!["API_TOKEN=abc".into()],
+            ..Default::default()
+        };
+        run_mcp_add(temp.path(), "s1", "cursor", McpScope::Project, options).unwrap();
+
+        let servers = cursor_servers(temp.path());
+        assert_eq!(servers["s1"]["command"], "npx");
+        assert_eq!(servers["s1"]["args"][1], "server");
+        assert_eq!(servers["s1"]["env"]["API_TOKEN"], "abc");
+
+        run_mcp_remove(temp.path(), "s1", "cursor", McpScope::Project).unwrap();
+        assert!(cursor_servers(temp.path()).get("s1").is_none());
+    }
+
+    #[test]
+    fn test_mcp_add_rejects_tool_without_mcp() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(temp.path(), "[core]\nmode = \"standard\"\n");
+
+        let options = McpAddOptions {
+            url: Some("https://example.com".into()),
+            ..Default::default()
+        };
+        let err = run_mcp_add(temp.path(), "s1", "aider", McpScope::Project, options).unwrap_err();
+        assert!(
+            err.to_string().contains("does not support MCP"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn test_mcp_add_rejects_malformed_env() {
+        let options = McpAddOptions {
+            command: Some("x".into()),
+            env: vec!["NO_EQUALS".into()],
+            ..Default::default()
+        };
+        assert!(options.into_config().is_err());
+    }
+
+    #[test]
+    fn test_mcp_sync_tracks_managed_servers_in_ledger() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(
+            temp.path(),
+            &format!("tools = [\"cursor\", \"aider\"]\n\n[core]\nmode = \"standard\"\n{SERVERS}"),
+        );
+
+        run_mcp_sync(temp.path()).unwrap();
+        let servers = cursor_servers(temp.path());
+        assert_eq!(servers["a"]["command"], "server-a");
+        assert!(servers.get("b").is_some());
+
+        let ledger = Ledger::load(&temp.path().join(".repository/ledger.toml")).unwrap();
+        assert_eq!(
+            McpSyncer::managed_servers(&ledger, "cursor"),
+            vec!["a", "b"]
+        );
+
+        // Dropping a server from config.toml removes it on the next sync
+        setup_repo(
+            temp.path(),
+            &format!(
+                "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n{}",
+                SERVERS.split("[mcp.servers.b").next().unwrap()
+            ),
+        );
+        run_mcp_sync(temp.path()).unwrap();
+        let servers = cursor_servers(temp.path());
+        assert!(servers.get("a").is_some());
+        assert!(servers.get("b").is_none());
+    }
+
+    #[test]
+    fn test_mcp_sync_cleans_up_disabled_tools() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(
+            temp.path(),
+            &format!("tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n{SERVERS}"),
+        );
+        run_mcp_sync(temp.path()).unwrap();
+
+        setup_repo(
+            temp.path(),
+            &format!("tools = []\n\n[core]\nmode = \"standard\"\n{SERVERS}"),
+        );
+        run_mcp_sync(temp.path()).unwrap();
+
+        assert_eq!(cursor_servers(temp.path()), serde_json::json!({}));
+        let ledger = Ledger::load(&temp.path().join(".repository/ledger.toml")).unwrap();
+        assert!(McpSyncer::synced_tools(&ledger).is_empty());
+    }
+}
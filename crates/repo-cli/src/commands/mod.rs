@@ -1,30 +1,44 @@
 //! Command implementations for repo-cli
 
+pub mod adopt;
 pub mod branch;
+pub mod bundle;
 pub mod config;
+pub mod context;
 pub mod diff;
+pub mod doctor;
 pub mod extension;
 pub mod git;
 pub mod governance;
+pub mod history;
 pub mod hooks;
 pub mod init;
 pub mod list;
+pub mod mcp;
 pub mod open;
+pub mod presets;
 
 pub mod rule;
 pub mod status;
 pub mod sync;
 pub mod tool;
 
+pub use adopt::{run_adopt, run_ws_adopt_plan};
 pub use branch::{
-    run_branch_add, run_branch_checkout, run_branch_list, run_branch_remove, run_branch_rename,
+    run_branch_add, run_branch_checkout, run_branch_list, run_branch_prune, run_branch_remove,
+    run_branch_rename,
 };
+pub use bundle::{run_export, run_import};
 pub use diff::run_diff;
-pub use git::{run_merge, run_pull, run_push};
-pub use governance::{run_rules_diff, run_rules_export, run_rules_import, run_rules_lint};
+pub use doctor::run_doctor;
+pub use git::{run_git_status, run_merge, run_pull, run_push};
+pub use governance::{
+    run_rules_diff, run_rules_export, run_rules_import, run_rules_lint, run_rules_validate,
+};
+pub use history::run_history;
 pub use init::run_init;
 pub use list::{run_list_presets, run_list_tools};
 pub use rule::{run_add_rule, run_list_rules, run_remove_rule};
 pub use status::run_status;
-pub use sync::{run_check, run_fix, run_sync};
+pub use sync::{check_exit_code, run_check, run_check_watch, run_fix, run_sync};
 pub use tool::{run_add_preset, run_add_tool, run_remove_preset, run_remove_tool};
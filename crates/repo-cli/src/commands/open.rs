@@ -1,28 +1,78 @@
 //! Open command implementation
 //!
 //! Launches an editor/IDE in a specified worktree directory after syncing configs.
+//!
+//! A tool can be opened when its definition sets `open_command` in `[meta]`,
+//! a template such as `"code {path}"`. Built-in editors come with one, and a
+//! definition in `.repository/tools/` can add or replace it.
 
-use std::path::Path;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
 use colored::Colorize;
+use serde::Serialize;
+
+use repo_core::Manifest;
+use repo_fs::NormalizedPath;
+use repo_tools::ToolDispatcher;
 
+use crate::commands::branch::create_backend;
+use crate::commands::sync::detect_mode;
 use crate::error::{CliError, Result};
+use crate::output::{self, say};
 
-/// Known editor definitions: (slug, binary name, display name)
-const EDITORS: &[(&str, &str, &str)] = &[
-    ("cursor", "cursor", "Cursor"),
-    ("vscode", "code", "VS Code"),
-    ("zed", "zed", "Zed"),
-];
+/// A tool in `repo open --list`
+#[derive(Debug, Serialize)]
+pub struct OpenerEntry {
+    pub slug: String,
+    /// Command template, e.g. `code {path}`
+    pub template: String,
+    /// The command that opens the repository root
+    pub command: String,
+    /// Where the program was found on PATH
+    pub program_path: Option<String>,
+    pub default: bool,
+}
 
-/// Check if a binary is available on PATH
-fn is_on_path(binary: &str) -> bool {
-    which(binary).is_some()
+/// Result of `repo open`
+#[derive(Debug, Serialize)]
+pub struct OpenResult {
+    pub worktree: String,
+    pub tool: String,
+    /// Whether the worktree's configs were synced before opening
+    pub synced: bool,
+}
+
+/// A tool with an `open_command`
+#[derive(Debug, Clone)]
+struct Opener {
+    slug: String,
+    name: String,
+    template: String,
+}
+
+impl Opener {
+    /// Program and arguments for opening `path`
+    fn command(&self, path: &Path) -> (String, Vec<String>) {
+        render_command(&self.template, path)
+    }
+
+    /// Whether the program the template runs can be found
+    fn is_installed(&self) -> bool {
+        let (program, _) = self.command(Path::new(""));
+        which(&program).is_some()
+    }
 }
 
 /// Find the full path of a binary on PATH (cross-platform)
-fn which(binary: &str) -> Option<std::path::PathBuf> {
+///
+/// A program given as a path is returned as is if it exists.
+fn which(binary: &str) -> Option<PathBuf> {
+    if Path::new(binary).components().count() > 1 {
+        let path = PathBuf::from(binary);
+        return path.is_file().then_some(path);
+    }
+
     let path_var = std::env::var_os("PATH")?;
     let exts = if cfg!(windows) {
         vec![".exe", ".cmd", ".bat", ""]
@@ -41,163 +91,347 @@ fn which(binary: &str) -> Option<std::path::PathBuf> {
     None
 }
 
-/// Detect which editors are installed
-pub fn detect_editors() -> Vec<(&'static str, &'static str, &'static str)> {
-    EDITORS
-        .iter()
-        .filter(|(_, binary, _)| is_on_path(binary))
-        .copied()
+/// Split an `open_command` template into program and arguments
+///
+/// Words are separated by whitespace and `{path}` is replaced in each word,
+/// so a path containing spaces stays a single argument. Without `{path}`
+/// the path is appended as the last argument.
+fn render_command(template: &str, path: &Path) -> (String, Vec<String>) {
+    let path = path.display().to_string();
+    let mut words = template
+        .split_whitespace()
+        .map(|word| word.replace("{path}", &path));
+    let program = words.next().unwrap_or_default();
+    let mut args: Vec<String> = words.collect();
+    if !template.contains("{path}") {
+        args.push(path);
+    }
+    (program, args)
+}
+
+/// Quote a command line word in double quotes if it holds whitespace or
+/// shell metacharacters
+///
+/// `cmd.exe` takes `&`, `|`, `^`, `<` and `>` literally only inside double
+/// quotes, where a `"` is written twice.
+fn quote_word(word: &str) -> String {
+    let plain = !word.is_empty()
+        && !word.contains(|c: char| c.is_whitespace() || "\"&|^<>()%!;$`'*?".contains(c));
+    if plain {
+        word.to_string()
+    } else {
+        format!("\"{}\"", word.replace('"', "\"\""))
+    }
+}
+
+/// `program` and `args` as a single command line
+fn command_line(program: &str, args: &[String]) -> String {
+    std::iter::once(program)
+        .chain(args.iter().map(String::as_str))
+        .map(quote_word)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Tools in the repository at `root` that can be opened, sorted by slug
+fn openers(root: &NormalizedPath) -> Vec<Opener> {
+    let dispatcher = ToolDispatcher::from_repository(root);
+    dispatcher
+        .list_available()
+        .into_iter()
+        .filter_map(|slug| {
+            let meta = &dispatcher.definition(&slug)?.meta;
+            Some(Opener {
+                template: meta.open_command.clone()?,
+                name: meta.name.clone(),
+                slug,
+            })
+        })
         .collect()
 }
 
-/// Find editor binary name from a tool slug
-fn resolve_editor(slug: &str) -> Result<&'static str> {
-    for (s, binary, _) in EDITORS {
-        if *s == slug {
-            if is_on_path(binary) {
-                return Ok(binary);
-            } else {
-                return Err(CliError::user(format!(
-                    "Editor '{}' is not installed or not on PATH.",
-                    slug
-                )));
-            }
+/// Load config.toml if it exists and parses
+fn load_manifest(root: &Path) -> Option<Manifest> {
+    let content = std::fs::read_to_string(root.join(".repository").join("config.toml")).ok()?;
+    Manifest::parse(&content).ok()
+}
+
+/// Pick the tool to open with
+///
+/// An explicit `tool` must be openable. Otherwise `[open] default_tool` is
+/// used, then the first configured tool that is installed, then the first
+/// installed tool.
+fn choose_opener(openers: &[Opener], tool: Option<&str>, root: &Path) -> Result<Opener> {
+    let find = |slug: &str| openers.iter().find(|o| o.slug == slug);
+    let manifest = load_manifest(root);
+
+    if let Some(slug) = tool.or_else(|| manifest.as_ref()?.open.default_tool.as_deref()) {
+        let opener = find(slug).ok_or_else(|| {
+            let known: Vec<&str> = openers.iter().map(|o| o.slug.as_str()).collect();
+            CliError::user(format!(
+                "Unknown editor '{}'. Tools that can be opened: {}",
+                slug,
+                known.join(", ")
+            ))
+        })?;
+        if !opener.is_installed() {
+            return Err(CliError::user(format!(
+                "Editor '{}' is not installed or not on PATH.",
+                slug
+            )));
         }
+        return Ok(opener.clone());
     }
-    Err(CliError::user(format!(
-        "Unknown editor '{}'. Supported: cursor, vscode, zed",
-        slug
-    )))
+
+    let configured = manifest
+        .iter()
+        .flat_map(|m| &m.tools)
+        .filter_map(|t| find(t));
+    configured
+        .chain(openers)
+        .find(|o| o.is_installed())
+        .cloned()
+        .ok_or_else(|| {
+            CliError::user(
+                "No supported editor found on PATH. Run `repo open --list` to see the tools that can be opened.",
+            )
+        })
 }
 
-/// Auto-detect the best editor to use based on config tools and what's installed
-fn auto_detect_editor(config_path: &Path) -> Result<&'static str> {
-    // Try to read config to prefer tools listed there
-    let config_file = config_path.join(".repository").join("config.toml");
-    if config_file.exists()
-        && let Ok(content) = std::fs::read_to_string(&config_file)
-        && let Ok(manifest) = repo_core::Manifest::parse(&content)
+/// Resolve `worktree` to a directory through the repository's ModeBackend
+///
+/// In worktrees mode any worktree can be named by branch or directory name;
+/// in standard mode only the current branch is checked out. An absolute
+/// path to a directory is used as is.
+fn resolve_worktree(root: &Path, worktree: &str) -> Result<PathBuf> {
+    let path = Path::new(worktree);
+    if path.is_absolute() && path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+
+    let root = NormalizedPath::new(root);
+    let backend = create_backend(&root, detect_mode(&root)?)?;
+    let available: Vec<(String, NormalizedPath)> = backend
+        .list_branches()?
+        .into_iter()
+        .filter_map(|branch| match branch.path {
+            Some(path) => Some((branch.name, path)),
+            None if branch.is_current => Some((branch.name, backend.working_dir().clone())),
+            None => None,
+        })
+        .collect();
+
+    if let Some((_, path)) = available
+        .iter()
+        .find(|(name, path)| name == worktree || path.file_name() == Some(worktree))
     {
-        // Check configured tools in order
-        for tool_name in &manifest.tools {
-            for (slug, binary, _) in EDITORS {
-                if tool_name == *slug && is_on_path(binary) {
-                    return Ok(binary);
-                }
-            }
+        return Ok(path.to_native());
+    }
+
+    let mut message = format!("Worktree '{}' not found.", worktree);
+    if available.is_empty() {
+        message.push_str(" No worktrees are checked out.");
+    } else {
+        message.push_str(" Available worktrees:");
+        for (name, path) in &available {
+            message.push_str(&format!("\n  - {} ({})", name, path));
         }
     }
+    Err(CliError::user(message))
+}
 
-    // Fall back to first installed editor
-    let installed = detect_editors();
-    if let Some((_, binary, _)) = installed.first() {
-        return Ok(binary);
+/// Start `program` without waiting for it or tying it to this terminal
+fn spawn_detached(program: &str, args: &[String]) -> std::io::Result<()> {
+    #[cfg(windows)]
+    let mut command = {
+        use std::os::windows::process::CommandExt;
+        // GUI apps and `.cmd` shims such as `code.cmd` only detach through
+        // `start`. cmd.exe parses the line itself, so it is quoted for cmd
+        // rather than with the quoting `Command::arg` applies.
+        let mut command = Command::new("cmd");
+        command.raw_arg(format!("/C start \"\" {}", command_line(program, args)));
+        command
+    };
+    #[cfg(not(windows))]
+    let mut command = {
+        let mut command = Command::new(program);
+        command.args(args);
+        command
+    };
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // Keep the editor running when the terminal's process group is signalled
+        command.process_group(0);
     }
+    command.spawn().map(drop)
+}
 
-    Err(CliError::user(
-        "No supported editor found on PATH. Install cursor, code (VS Code), or zed.",
-    ))
+/// Run `repo open --list`
+///
+/// Prints every tool that can be opened with the command that opens the
+/// repository root and where its program was found.
+pub fn run_open_list(root: &Path) -> Result<()> {
+    let openers = openers(&NormalizedPath::new(root));
+    let default_tool = load_manifest(root).and_then(|m| m.open.default_tool);
+    let entries: Vec<OpenerEntry> = openers
+        .iter()
+        .map(|opener| {
+            let (program, args) = opener.command(root);
+            OpenerEntry {
+                slug: opener.slug.clone(),
+                template: opener.template.clone(),
+                command: command_line(&program, &args),
+                program_path: which(&program).map(|path| path.display().to_string()),
+                default: default_tool.as_deref() == Some(opener.slug.as_str()),
+            }
+        })
+        .collect();
+    if output::emit(&entries)? {
+        return Ok(());
+    }
+
+    say!("{} Tools that can be opened:", "=>".blue().bold());
+    for entry in &entries {
+        let location = match &entry.program_path {
+            Some(path) => path.dimmed().to_string(),
+            None => "not on PATH".yellow().to_string(),
+        };
+        let marker = if entry.default {
+            " (default)".green().to_string()
+        } else {
+            String::new()
+        };
+        say!(
+            "   {:<12} {:<32} {}{}",
+            entry.slug.cyan(),
+            entry.command,
+            location,
+            marker
+        );
+    }
+    Ok(())
 }
 
 /// Run the open command
 ///
 /// Resolves the worktree path, syncs configs, then launches the editor.
 pub fn run_open(root: &Path, worktree: &str, tool: Option<&str>) -> Result<()> {
-    // Resolve worktree path - it should be a sibling directory in worktree mode
-    // or a subdirectory. Try both the worktree name directly and as a sibling.
-    let worktree_path = if Path::new(worktree).is_absolute() && Path::new(worktree).is_dir() {
-        std::path::PathBuf::from(worktree)
-    } else {
-        // Try as sibling of current root (worktree container pattern)
-        let sibling = root.parent().map(|p| p.join(worktree));
-        if let Some(ref p) = sibling {
-            if p.is_dir() {
-                p.clone()
-            } else {
-                // Try as child of root
-                let child = root.join(worktree);
-                if child.is_dir() {
-                    child
-                } else {
-                    return Err(CliError::user(format!(
-                        "Worktree '{}' not found. Tried:\n  - {}\n  - {}",
-                        worktree,
-                        sibling.unwrap().display(),
-                        root.join(worktree).display()
-                    )));
-                }
-            }
-        } else {
-            let child = root.join(worktree);
-            if child.is_dir() {
-                child
-            } else {
-                return Err(CliError::user(format!(
-                    "Worktree '{}' not found at {}",
-                    worktree,
-                    child.display()
-                )));
-            }
-        }
-    };
+    let worktree_path = resolve_worktree(root, worktree)?;
 
-    println!(
+    say!(
         "{} Opening worktree: {}",
         "=>".blue().bold(),
         worktree_path.display().to_string().cyan()
     );
 
     // Determine the editor to use
-    let editor_binary = match tool {
-        Some(slug) => resolve_editor(slug)?,
-        None => auto_detect_editor(&worktree_path)?,
-    };
-
-    // Find display name for the editor
-    let editor_name = EDITORS
-        .iter()
-        .find(|(_, b, _)| *b == editor_binary)
-        .map(|(_, _, name)| *name)
-        .unwrap_or(editor_binary);
+    let opener = choose_opener(&openers(&NormalizedPath::new(root)), tool, root)?;
 
-    println!(
+    say!(
         "{} Using editor: {}",
         "=>".blue().bold(),
-        editor_name.cyan()
+        opener.name.cyan()
     );
 
     // Try to sync configs in the worktree before opening
     let repo_config = worktree_path.join(".repository").join("config.toml");
+    let mut synced = false;
     if repo_config.exists() {
-        println!("{} Syncing configs...", "=>".blue().bold());
-        match crate::commands::run_sync(&worktree_path, false, false) {
-            Ok(()) => {}
+        say!("{} Syncing configs...", "=>".blue().bold());
+        match crate::commands::sync::sync(&worktree_path, false, false, None) {
+            Ok(result) => synced = result.success,
             Err(e) => {
                 // Don't fail the open if sync fails - just warn
-                println!("{} Sync warning: {}", "WARN".yellow().bold(), e);
+                say!("{} Sync warning: {}", "WARN".yellow().bold(), e);
             }
         }
     }
 
     // Launch the editor
-    println!("{} Launching {} ...", "=>".blue().bold(), editor_name);
+    say!("{} Launching {} ...", "=>".blue().bold(), opener.name);
 
-    Command::new(editor_binary)
-        .arg(&worktree_path)
-        .spawn()
-        .map_err(|e| CliError::user(format!("Failed to launch '{}': {}", editor_binary, e)))?;
+    let (program, args) = opener.command(&worktree_path);
+    spawn_detached(&program, &args)
+        .map_err(|e| CliError::user(format!("Failed to launch '{}': {}", program, e)))?;
 
-    println!("{} Opened in {}.", "OK".green().bold(), editor_name);
+    output::emit(&OpenResult {
+        worktree: worktree_path.display().to_string(),
+        tool: opener.slug.clone(),
+        synced,
+    })?;
+    say!("{} Opened in {}.", "OK".green().bold(), opener.name);
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use repo_test_utils::git::real_git_repo_with_commit;
+
+    fn opener(slug: &str, template: &str) -> Opener {
+        Opener {
+            slug: slug.to_string(),
+            name: slug.to_string(),
+            template: template.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_builtin_editors_are_openable() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let openers = openers(&NormalizedPath::new(temp.path()));
+        let vscode = openers.iter().find(|o| o.slug == "vscode").unwrap();
+        assert_eq!(vscode.template, "code {path}");
+        assert!(openers.iter().all(|o| o.slug != "claude"));
+    }
 
     #[test]
-    fn test_editors_list_not_empty() {
-        assert!(!EDITORS.is_empty());
+    fn test_repository_definition_adds_open_command() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let tools = temp.path().join(".repository").join("tools");
+        std::fs::create_dir_all(&tools).unwrap();
+        std::fs::write(
+            tools.join("helix.toml"),
+            "[meta]\nname = \"Helix\"\nslug = \"helix\"\nopen_command = \"hx {path}\"\n\n\
+             [integration]\nconfig_path = \".helix/rules.md\"\ntype = \"markdown\"\n",
+        )
+        .unwrap();
+
+        let openers = openers(&NormalizedPath::new(temp.path()));
+        let helix = openers.iter().find(|o| o.slug == "helix").unwrap();
+        assert_eq!(helix.name, "Helix");
+        assert_eq!(helix.template, "hx {path}");
+    }
+
+    #[test]
+    fn test_render_command_substitutes_path() {
+        let path = Path::new("/work/my repo");
+        let (program, args) = render_command("cursor --new-window {path}", path);
+        assert_eq!(program, "cursor");
+        assert_eq!(args, vec!["--new-window", "/work/my repo"]);
+
+        // Without a placeholder the path is appended
+        let (program, args) = render_command("zed", path);
+        assert_eq!(program, "zed");
+        assert_eq!(args, vec!["/work/my repo"]);
+    }
+
+    #[test]
+    fn test_command_line_quotes_metacharacters() {
+        let (program, args) = render_command("code {path}", Path::new(r"C:\a&b|c^d"));
+        assert_eq!(command_line(&program, &args), r#"code "C:\a&b|c^d""#);
+
+        let args = vec!["/work/my repo".to_string(), "say \"hi\"".to_string()];
+        assert_eq!(
+            command_line("zed", &args),
+            r#"zed "/work/my repo" "say ""hi""""#
+        );
+        assert_eq!(quote_word("--new-window"), "--new-window");
     }
 
     #[test]
@@ -214,28 +448,73 @@ mod tests {
     }
 
     #[test]
-    fn test_detect_editors_returns_vec() {
-        // Just verify it doesn't panic - actual results depend on environment
-        let editors = detect_editors();
-        // editors may be empty or non-empty depending on installed software
-        assert!(editors.len() <= EDITORS.len());
+    fn test_choose_opener_prefers_default_tool() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let config = temp.path().join(".repository");
+        std::fs::create_dir_all(&config).unwrap();
+        std::fs::write(
+            config.join("config.toml"),
+            "tools = [\"other\"]\n\n[open]\ndefault_tool = \"cargo-tool\"\n",
+        )
+        .unwrap();
+        let openers = vec![
+            opener("other", "cargo {path}"),
+            opener("cargo-tool", "cargo run -- {path}"),
+            opener(
+                "missing",
+                "nonexistent_binary_that_does_not_exist_12345 {path}",
+            ),
+        ];
+
+        let chosen = choose_opener(&openers, None, temp.path()).unwrap();
+        assert_eq!(chosen.slug, "cargo-tool");
+        let chosen = choose_opener(&openers, Some("other"), temp.path()).unwrap();
+        assert_eq!(chosen.slug, "other");
+
+        let err = choose_opener(&openers, Some("missing"), temp.path())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("not installed"));
     }
 
     #[test]
-    fn test_resolve_editor_unknown() {
-        let result = resolve_editor("emacs");
-        assert!(result.is_err());
-        let err = result.unwrap_err().to_string();
-        assert!(err.contains("Unknown editor"));
+    fn test_choose_opener_skips_missing_tools() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let openers = vec![
+            opener("missing", "nonexistent_binary_that_does_not_exist_12345"),
+            opener("present", "cargo {path}"),
+        ];
+        let chosen = choose_opener(&openers, None, temp.path()).unwrap();
+        assert_eq!(chosen.slug, "present");
     }
 
     #[test]
-    fn test_open_nonexistent_worktree() {
+    fn test_open_nonexistent_worktree_lists_available() {
         let temp = tempfile::TempDir::new().unwrap();
-        let result = run_open(temp.path(), "nonexistent-worktree", None);
-        assert!(result.is_err());
-        let err = result.unwrap_err().to_string();
-        assert!(err.contains("not found"));
+        real_git_repo_with_commit(temp.path());
+
+        let err = run_open(temp.path(), "nonexistent-worktree", None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Worktree 'nonexistent-worktree' not found"));
+        assert!(err.contains("Available worktrees:"));
+        assert!(err.contains(&temp.path().display().to_string()));
+    }
+
+    #[test]
+    fn test_resolve_current_branch_in_standard_mode() {
+        let temp = tempfile::TempDir::new().unwrap();
+        real_git_repo_with_commit(temp.path());
+        let branch = git2::Repository::open(temp.path())
+            .unwrap()
+            .head()
+            .unwrap()
+            .shorthand()
+            .unwrap()
+            .to_string();
+
+        let path = resolve_worktree(temp.path(), &branch).unwrap();
+        assert_eq!(NormalizedPath::new(&path), NormalizedPath::new(temp.path()));
     }
 
     #[test]
@@ -245,19 +524,9 @@ mod tests {
         let wt = temp.path().join("my-worktree");
         std::fs::create_dir_all(&wt).unwrap();
 
-        let result = run_open(temp.path(), "my-worktree", Some("emacs"));
+        let result = run_open(temp.path(), wt.to_str().unwrap(), Some("emacs"));
         assert!(result.is_err());
         let err = result.unwrap_err().to_string();
         assert!(err.contains("Unknown editor"));
     }
-
-    #[test]
-    fn test_auto_detect_no_editors_no_config() {
-        // With a temp dir that has no config and possibly no editors on PATH,
-        // this tests the fallback path
-        let temp = tempfile::TempDir::new().unwrap();
-        let result = auto_detect_editor(temp.path());
-        // May succeed or fail depending on what's installed - just verify no panic
-        let _ = result;
-    }
 }
@@ -0,0 +1,254 @@
+//! Preset command implementations
+//!
+//! `check` runs the provider `check` of every preset configured in
+//! config.toml and reports each one's status, the preset analogue of
+//! `repo check`.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use colored::Colorize;
+use repo_core::presets::{preset_context, preset_provider};
+use repo_meta::Registry;
+use repo_presets::PresetStatus;
+use serde::Serialize;
+
+use crate::commands::sync::resolve_root;
+use crate::commands::tool::load_manifest;
+use crate::error::{CliError, Result};
+use crate::output::{self, say};
+
+/// Path to config.toml within a repository
+const CONFIG_PATH: &str = ".repository/config.toml";
+
+/// Outcome of checking one preset
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetCheckResult {
+    /// Preset ID, e.g. `env:python`
+    pub preset: String,
+    /// Provider registered for the preset, if any
+    pub provider: Option<String>,
+    /// `healthy`, `degraded`, `missing`, `drifted`, `broken`, or
+    /// `unsupported` when no provider is registered
+    pub status: &'static str,
+    /// Details from the provider explaining the status
+    pub details: Vec<String>,
+    #[serde(skip)]
+    level: Option<PresetStatus>,
+}
+
+impl PresetCheckResult {
+    /// Whether the preset's status is at least as severe as `threshold`
+    fn fails(&self, threshold: &PresetStatus) -> bool {
+        self.level
+            .as_ref()
+            .is_some_and(|level| severity(level) >= severity(threshold))
+    }
+}
+
+fn severity(status: &PresetStatus) -> u8 {
+    match status {
+        PresetStatus::Healthy => 0,
+        PresetStatus::Degraded { .. } => 1,
+        PresetStatus::Missing => 2,
+        PresetStatus::Drifted => 3,
+        PresetStatus::Broken => 4,
+    }
+}
+
+fn status_label(status: &PresetStatus) -> &'static str {
+    match status {
+        PresetStatus::Healthy => "healthy",
+        PresetStatus::Degraded { .. } => "degraded",
+        PresetStatus::Missing => "missing",
+        PresetStatus::Drifted => "drifted",
+        PresetStatus::Broken => "broken",
+    }
+}
+
+/// Check every preset configured in the repository at `path`
+///
+/// Provider checks run concurrently; a check that takes longer than
+/// `timeout` is reported as broken. Results are sorted by preset ID.
+pub fn check_presets(path: &Path, timeout: Duration) -> Result<Vec<PresetCheckResult>> {
+    let root = resolve_root(path)?;
+    let manifest = load_manifest(&root.join(CONFIG_PATH))?;
+    let registry = Registry::with_builtins();
+
+    let mut presets: Vec<_> = manifest.presets.into_iter().collect();
+    presets.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    let mut results = runtime.block_on(async {
+        let mut checks = tokio::task::JoinSet::new();
+        for (index, (preset, config)) in presets.iter().enumerate() {
+            let Some(provider) = preset_provider(preset, config) else {
+                continue;
+            };
+            let context = preset_context(root.as_ref(), config);
+            checks.spawn(async move {
+                let report = tokio::time::timeout(timeout, provider.check(&context)).await;
+                (index, report)
+            });
+        }
+
+        let mut reports = HashMap::new();
+        while let Some(joined) = checks.join_next().await {
+            if let Ok((index, report)) = joined {
+                reports.insert(index, report);
+            }
+        }
+        reports
+    });
+
+    Ok(presets
+        .into_iter()
+        .enumerate()
+        .map(|(index, (preset, config))| {
+            let requested = config.get("provider").and_then(|v| v.as_str());
+            let provider = registry.resolve_provider(&preset, requested).cloned();
+            let (level, details) = match results.remove(&index) {
+                Some(Ok(Ok(report))) => (Some(report.status), report.details),
+                Some(Ok(Err(e))) => (Some(PresetStatus::Broken), vec![e.to_string()]),
+                Some(Err(_)) => (
+                    Some(PresetStatus::Broken),
+                    vec![format!("Check timed out after {}s", timeout.as_secs())],
+                ),
+                None if provider.is_some() => (
+                    Some(PresetStatus::Broken),
+                    vec!["Check did not complete".to_string()],
+                ),
+                None => (None, vec!["No provider registered".to_string()]),
+            };
+            PresetCheckResult {
+                preset,
+                provider,
+                status: level.as_ref().map_or("unsupported", status_label),
+                details,
+                level,
+            }
+        })
+        .collect())
+}
+
+/// Run `repo presets check`
+///
+/// Prints a status table of every configured preset with the details of the
+/// provider check. Returns an error if any preset's status is at least as
+/// severe as `fail_on`; `None` never fails. Statuses from least to most
+/// severe are healthy, degraded, missing, drifted and broken.
+pub fn run_presets_check(
+    path: &Path,
+    fail_on: Option<PresetStatus>,
+    timeout: Duration,
+) -> Result<()> {
+    let results = check_presets(path, timeout)?;
+
+    if output::emit(&results)? {
+        // The results are the report
+    } else if results.is_empty() {
+        say!("{} No presets configured.", "=>".blue().bold());
+    } else {
+        say!("{} Checking presets:", "=>".blue().bold());
+        for result in &results {
+            let label = format!("{:<11}", result.status.to_uppercase());
+            let label = match result.level {
+                Some(PresetStatus::Healthy) => label.green().bold(),
+                Some(PresetStatus::Degraded { .. } | PresetStatus::Missing) => {
+                    label.yellow().bold()
+                }
+                Some(_) => label.red().bold(),
+                None => label.dimmed(),
+            };
+            let provider = result
+                .provider
+                .as_deref()
+                .map(|p| format!("(provider: {p})"))
+                .unwrap_or_default();
+            say!(
+                "   {} {:<16} {}",
+                label,
+                result.preset.cyan(),
+                provider.dimmed()
+            );
+            for detail in &result.details {
+                say!("      {} {}", "-".dimmed(), detail);
+            }
+        }
+    }
+
+    if let Some(threshold) = fail_on {
+        let failed = results.iter().filter(|r| r.fails(&threshold)).count();
+        if failed > 0 {
+            return Err(CliError::user(format!(
+                "{failed} preset(s) are not healthy"
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_repo(dir: &Path, config: &str) {
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::create_dir_all(dir.join(".repository")).unwrap();
+        fs::write(dir.join(CONFIG_PATH), config).unwrap();
+    }
+
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn test_presets_check_reports_missing_venv() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(
+            temp.path(),
+            "tools = []\n\n[presets.\"env:python\"]\nversion = \"3.12\"\n\n[presets.\"env:custom\"]\n",
+        );
+
+        let results = check_presets(temp.path(), TIMEOUT).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].preset, "env:custom");
+        assert_eq!(results[0].status, "unsupported");
+        assert_eq!(results[1].preset, "env:python");
+        assert_eq!(results[1].provider.as_deref(), Some("uv"));
+        assert_ne!(results[1].status, "healthy");
+        assert!(!results[1].details.is_empty());
+
+        let err = run_presets_check(temp.path(), Some(PresetStatus::Missing), TIMEOUT).unwrap_err();
+        assert!(err.to_string().contains("1 preset(s)"));
+        assert!(run_presets_check(temp.path(), None, TIMEOUT).is_ok());
+    }
+
+    #[test]
+    fn test_presets_check_uses_selected_provider() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(
+            temp.path(),
+            "tools = []\n\n[presets.\"env:python\"]\nprovider = \"poetry\"\n",
+        );
+
+        let results = check_presets(temp.path(), TIMEOUT).unwrap();
+
+        assert_eq!(results[0].provider.as_deref(), Some("poetry"));
+        // No pyproject.toml with [tool.poetry]
+        assert_eq!(results[0].status, "broken");
+    }
+
+    #[test]
+    fn test_presets_check_without_presets() {
+        let temp = TempDir::new().unwrap();
+        setup_repo(temp.path(), "tools = []\n");
+
+        assert!(check_presets(temp.path(), TIMEOUT).unwrap().is_empty());
+        assert!(run_presets_check(temp.path(), Some(PresetStatus::Missing), TIMEOUT).is_ok());
+    }
+}
@@ -6,8 +6,31 @@ use std::fs;
 use std::path::Path;
 
 use colored::Colorize;
+use serde::Serialize;
 
+use repo_core::Operation;
+use repo_fs::NormalizedPath;
+use repo_meta::schema::{FrontMatterMode, compose_rule_markdown};
+
+use crate::commands::history::record_config_change;
 use crate::error::{CliError, Result};
+use crate::output::{self, say};
+
+/// A rule in `repo list-rules`, or the rule `repo add-rule` wrote
+#[derive(Debug, Serialize)]
+pub struct RuleEntry {
+    pub id: String,
+    /// Path of the rule file, relative to the repository root
+    pub path: String,
+}
+
+/// Result of `repo remove-rule`
+#[derive(Debug, Serialize)]
+pub struct RuleRemoval {
+    pub id: String,
+    /// Whether the rule existed
+    pub removed: bool,
+}
 
 /// Validate a rule ID to prevent path traversal and invalid filenames
 fn validate_rule_id(id: &str) -> Result<()> {
@@ -17,30 +40,53 @@ fn validate_rule_id(id: &str) -> Result<()> {
 /// Path to rules directory within a repository
 const RULES_DIR: &str = ".repository/rules";
 
+/// The front-matter mode selected by a `--lenient` flag
+pub(crate) fn front_matter_mode(lenient: bool) -> FrontMatterMode {
+    if lenient {
+        FrontMatterMode::Lenient
+    } else {
+        FrontMatterMode::Strict
+    }
+}
+
 /// Run the add-rule command
 ///
 /// Adds a rule to the repository's rules directory as a markdown file.
-pub fn run_add_rule(path: &Path, id: &str, instruction: &str, tags: Vec<String>) -> Result<()> {
+/// Front-matter in the instruction is parsed strictly unless `lenient` is
+/// set, in which case problems are reported as warnings and coerced.
+pub fn run_add_rule(
+    path: &Path,
+    id: &str,
+    instruction: &str,
+    tags: Vec<String>,
+    lenient: bool,
+) -> Result<()> {
     // Validate rule ID to prevent path traversal
     validate_rule_id(id)?;
 
-    println!("{} Adding rule: {}", "=>".blue().bold(), id.cyan());
-
-    let rules_dir = path.join(RULES_DIR);
-    fs::create_dir_all(&rules_dir)?;
-
-    let rule_path = rules_dir.join(format!("{}.md", id));
+    say!("{} Adding rule: {}", "=>".blue().bold(), id.cyan());
 
     // Generate rule content
-    let mut content = String::new();
-    if !tags.is_empty() {
-        content.push_str(&format!("tags: {}\n\n", tags.join(", ")));
+    let (content, warnings) = compose_rule_markdown(instruction, &tags, front_matter_mode(lenient))
+        .map_err(|e| CliError::user(e.to_string()))?;
+    for warning in &warnings {
+        say!("{} {}", "WARN".yellow().bold(), warning);
     }
-    content.push_str(instruction);
 
-    fs::write(&rule_path, &content)?;
-
-    println!("{} Rule '{}' added.", "OK".green().bold(), id);
+    let rule_path = path.join(RULES_DIR).join(format!("{}.md", id));
+    repo_fs::io::write_text(&NormalizedPath::new(&rule_path), &content)?;
+
+    output::emit(&RuleEntry {
+        id: id.to_string(),
+        path: format!("{}/{}.md", RULES_DIR, id),
+    })?;
+    say!("{} Rule '{}' added.", "OK".green().bold(), id);
+    record_config_change(
+        path,
+        Operation::RuleAdd,
+        format!("Added rule {}", id),
+        &format!("{}/{}.md", RULES_DIR, id),
+    );
     Ok(())
 }
 
@@ -51,17 +97,28 @@ pub fn run_remove_rule(path: &Path, id: &str) -> Result<()> {
     // Validate rule ID to prevent path traversal
     validate_rule_id(id)?;
 
-    println!("{} Removing rule: {}", "=>".blue().bold(), id.cyan());
+    say!("{} Removing rule: {}", "=>".blue().bold(), id.cyan());
 
     let rule_path = path.join(RULES_DIR).join(format!("{}.md", id));
 
-    if !rule_path.exists() {
-        println!("{} Rule '{}' not found.", "WARN".yellow().bold(), id);
+    let removed = rule_path.exists();
+    output::emit(&RuleRemoval {
+        id: id.to_string(),
+        removed,
+    })?;
+    if !removed {
+        say!("{} Rule '{}' not found.", "WARN".yellow().bold(), id);
         return Ok(());
     }
 
     fs::remove_file(&rule_path)?;
-    println!("{} Rule '{}' removed.", "OK".green().bold(), id);
+    say!("{} Rule '{}' removed.", "OK".green().bold(), id);
+    record_config_change(
+        path,
+        Operation::RuleRemove,
+        format!("Removed rule {}", id),
+        &format!("{}/{}.md", RULES_DIR, id),
+    );
     Ok(())
 }
 
@@ -71,26 +128,35 @@ pub fn run_remove_rule(path: &Path, id: &str) -> Result<()> {
 pub fn run_list_rules(path: &Path) -> Result<()> {
     let rules_dir = path.join(RULES_DIR);
 
-    if !rules_dir.exists() {
-        println!("No rules defined.");
+    let mut rules = Vec::new();
+    if rules_dir.exists() {
+        for entry in fs::read_dir(&rules_dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "md") {
+                let id = path.file_stem().unwrap_or_default().to_string_lossy();
+                rules.push(RuleEntry {
+                    path: format!("{}/{}.md", RULES_DIR, id),
+                    id: id.into_owned(),
+                });
+            }
+        }
+    }
+    rules.sort_by(|a, b| a.id.cmp(&b.id));
+    if output::emit(&rules)? {
         return Ok(());
     }
 
-    println!("{} Active rules:", "=>".blue().bold());
-
-    let mut found = false;
-    for entry in fs::read_dir(&rules_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.extension().is_some_and(|e| e == "md") {
-            let id = path.file_stem().unwrap_or_default().to_string_lossy();
-            println!("   {} {}", "-".cyan(), id);
-            found = true;
-        }
+    if !rules_dir.exists() {
+        say!("No rules defined.");
+        return Ok(());
     }
 
-    if !found {
-        println!("   (none)");
+    say!("{} Active rules:", "=>".blue().bold());
+    for rule in &rules {
+        say!("   {} {}", "-".cyan(), rule.id);
+    }
+    if rules.is_empty() {
+        say!("   (none)");
     }
 
     Ok(())
@@ -123,6 +189,7 @@ mod tests {
             "python-style",
             "Use snake_case for variables.",
             vec![],
+            false,
         );
         assert!(result.is_ok());
 
@@ -145,6 +212,7 @@ mod tests {
             "naming-conventions",
             "Follow consistent naming.",
             vec!["style".to_string(), "python".to_string()],
+            false,
         );
         assert!(result.is_ok());
 
@@ -157,6 +225,28 @@ mod tests {
         assert!(content.contains("Follow consistent naming."));
     }
 
+    #[test]
+    fn test_add_rule_front_matter_modes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        create_test_repo(path);
+        let instruction = "---\ntags: style\n---\nFollow consistent naming.";
+        let rule_path = path.join(".repository/rules/naming.md");
+
+        // Strict by default: scalar tags are rejected with their line
+        let err = run_add_rule(path, "naming", instruction, vec![], false).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        assert!(!rule_path.exists());
+
+        // Lenient coerces them to a list
+        run_add_rule(path, "naming", instruction, vec![], true).unwrap();
+        let content = fs::read_to_string(&rule_path).unwrap();
+        assert_eq!(
+            content,
+            "---\ntags: [\"style\"]\n---\nFollow consistent naming."
+        );
+    }
+
     #[test]
     fn test_remove_rule() {
         let temp_dir = TempDir::new().unwrap();
@@ -164,7 +254,7 @@ mod tests {
         create_test_repo(path);
 
         // First add a rule
-        run_add_rule(path, "test-rule", "Test instruction.", vec![]).unwrap();
+        run_add_rule(path, "test-rule", "Test instruction.", vec![], false).unwrap();
 
         // Verify it exists
         let rule_path = path.join(".repository/rules/test-rule.md");
@@ -207,8 +297,8 @@ mod tests {
         create_test_repo(path);
 
         // Add some rules
-        run_add_rule(path, "rule-one", "First rule.", vec![]).unwrap();
-        run_add_rule(path, "rule-two", "Second rule.", vec![]).unwrap();
+        run_add_rule(path, "rule-one", "First rule.", vec![], false).unwrap();
+        run_add_rule(path, "rule-two", "Second rule.", vec![], false).unwrap();
 
         // List rules
         let result = run_list_rules(path);
@@ -222,7 +312,7 @@ mod tests {
         // Don't create the repository structure
 
         // Add a rule - should create the rules directory
-        let result = run_add_rule(path, "new-rule", "A new rule.", vec![]);
+        let result = run_add_rule(path, "new-rule", "A new rule.", vec![], false);
         assert!(result.is_ok());
 
         // Verify directory and file were created
@@ -238,10 +328,10 @@ mod tests {
         create_test_repo(path);
 
         // Add a rule
-        run_add_rule(path, "my-rule", "Original content.", vec![]).unwrap();
+        run_add_rule(path, "my-rule", "Original content.", vec![], false).unwrap();
 
         // Overwrite the rule
-        let result = run_add_rule(path, "my-rule", "Updated content.", vec![]);
+        let result = run_add_rule(path, "my-rule", "Updated content.", vec![], false);
         assert!(result.is_ok());
 
         // Verify content was overwritten
@@ -254,21 +344,33 @@ mod tests {
     #[test]
     fn test_rule_id_validation_empty() {
         let temp_dir = TempDir::new().unwrap();
-        let result = run_add_rule(temp_dir.path(), "", "content", vec![]);
+        let result = run_add_rule(temp_dir.path(), "", "content", vec![], false);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_rule_id_validation_path_traversal() {
         let temp_dir = TempDir::new().unwrap();
-        let result = run_add_rule(temp_dir.path(), "../../../etc/passwd", "content", vec![]);
+        let result = run_add_rule(
+            temp_dir.path(),
+            "../../../etc/passwd",
+            "content",
+            vec![],
+            false,
+        );
         assert!(result.is_err());
     }
 
     #[test]
     fn test_rule_id_validation_special_chars() {
         let temp_dir = TempDir::new().unwrap();
-        let result = run_add_rule(temp_dir.path(), "rule with spaces", "content", vec![]);
+        let result = run_add_rule(
+            temp_dir.path(),
+            "rule with spaces",
+            "content",
+            vec![],
+            false,
+        );
         assert!(result.is_err());
     }
 
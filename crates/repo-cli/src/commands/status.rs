@@ -5,16 +5,17 @@
 use std::path::Path;
 
 use colored::Colorize;
-use serde_json::json;
+use serde::Serialize;
 
 use repo_core::{CheckStatus, ConfigResolver, Mode, RuleRegistry, SyncEngine};
 use repo_fs::NormalizedPath;
 
 use super::sync::{detect_mode, resolve_root};
 use crate::error::Result;
+use crate::output::{self, say};
 
-/// Status information for JSON output
-#[derive(Debug)]
+/// Status information for structured output
+#[derive(Debug, Serialize)]
 pub struct StatusInfo {
     /// Repository mode (standard or worktrees)
     pub mode: String,
@@ -33,7 +34,16 @@ pub struct StatusInfo {
 /// Run the status command
 ///
 /// Shows repository status overview including mode, root, tools, rules count, and sync status.
-pub fn run_status(path: &Path, json: bool) -> Result<()> {
+pub fn run_status(path: &Path) -> Result<()> {
+    let status_info = status_info(path)?;
+    if !output::emit(&status_info)? {
+        print_human_status(&status_info);
+    }
+    Ok(())
+}
+
+/// Gather the repository status
+pub fn status_info(path: &Path) -> Result<StatusInfo> {
     let root = resolve_root(path)?;
     let mode = detect_mode(&root)?;
     let engine = SyncEngine::new(root.clone(), mode)?;
@@ -55,32 +65,14 @@ pub fn run_status(path: &Path, json: bool) -> Result<()> {
     let rules_dir = root.join(".repository/rules");
     let rules_count = count_rules(&rules_dir);
 
-    let status_info = StatusInfo {
+    Ok(StatusInfo {
         mode: mode.to_string(),
         root: root.as_str().to_string(),
         tools: config.tools.clone(),
         rules_count,
         sync_status: sync_status.to_string(),
         has_local_overrides: resolver.has_local_overrides(),
-    };
-
-    if json {
-        // JSON output for scripting
-        let json_output = json!({
-            "mode": status_info.mode,
-            "root": status_info.root,
-            "tools": status_info.tools,
-            "rules_count": status_info.rules_count,
-            "sync_status": status_info.sync_status,
-            "has_local_overrides": status_info.has_local_overrides,
-        });
-        println!("{}", serde_json::to_string_pretty(&json_output)?);
-    } else {
-        // Human-readable colored output
-        print_human_status(&status_info, &mode);
-    }
-
-    Ok(())
+    })
 }
 
 /// Count the number of rule files in the rules directory
@@ -105,32 +97,32 @@ fn count_rules(rules_dir: &NormalizedPath) -> usize {
 }
 
 /// Print human-readable status output
-fn print_human_status(status: &StatusInfo, mode: &Mode) {
-    println!("{}", "Repository Status".bold().underline());
-    println!();
+fn print_human_status(status: &StatusInfo) {
+    say!("{}", "Repository Status".bold().underline());
+    say!();
 
     // Mode
-    let mode_display = match mode {
-        Mode::Standard => "standard".cyan(),
-        Mode::Worktrees => "worktrees".magenta(),
+    let mode_display = match status.mode.parse() {
+        Ok(Mode::Worktrees) => "worktrees".magenta(),
+        _ => "standard".cyan(),
     };
-    println!("  {}: {}", "Mode".bold(), mode_display);
+    say!("  {}: {}", "Mode".bold(), mode_display);
 
     // Root
-    println!("  {}: {}", "Root".bold(), status.root.yellow());
+    say!("  {}: {}", "Root".bold(), status.root.yellow());
 
     // Tools
     if status.tools.is_empty() {
-        println!("  {}: {}", "Tools".bold(), "none".dimmed());
+        say!("  {}: {}", "Tools".bold(), "none".dimmed());
     } else {
-        println!("  {}: {}", "Tools".bold(), status.tools.join(", ").green());
+        say!("  {}: {}", "Tools".bold(), status.tools.join(", ").green());
     }
 
     // Rules
     if status.rules_count == 0 {
-        println!("  {}: {}", "Rules".bold(), "none".dimmed());
+        say!("  {}: {}", "Rules".bold(), "none".dimmed());
     } else {
-        println!(
+        say!(
             "  {}: {} active",
             "Rules".bold(),
             status.rules_count.to_string().green()
@@ -145,18 +137,18 @@ fn print_human_status(status: &StatusInfo, mode: &Mode) {
         "broken" => "broken".red().bold(),
         _ => status.sync_status.as_str().normal(),
     };
-    println!("  {}: {}", "Sync".bold(), sync_display);
+    say!("  {}: {}", "Sync".bold(), sync_display);
 
     // Local overrides
     if status.has_local_overrides {
-        println!(
+        say!(
             "  {}: {}",
             "Overrides".bold(),
             "local overrides active".cyan()
         );
     }
 
-    println!();
+    say!();
 }
 
 #[cfg(test)]
@@ -189,7 +181,7 @@ mode = "{}"
         let temp_dir = TempDir::new().unwrap();
         create_minimal_repo(temp_dir.path(), "standard");
 
-        let result = run_status(temp_dir.path(), false);
+        let result = run_status(temp_dir.path());
         if let Err(e) = &result {
             eprintln!("Error: {:?}", e);
         }
@@ -201,11 +193,13 @@ mode = "{}"
         let temp_dir = TempDir::new().unwrap();
         create_minimal_repo(temp_dir.path(), "standard");
 
-        let result = run_status(temp_dir.path(), true);
-        if let Err(e) = &result {
-            eprintln!("Error: {:?}", e);
-        }
-        assert!(result.is_ok(), "run_status json failed: {:?}", result.err());
+        let info = status_info(temp_dir.path()).unwrap();
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["mode"], "standard");
+        assert_eq!(json["tools"], serde_json::json!(["cursor"]));
+        assert_eq!(json["rules_count"], 0);
+        assert!(json["sync_status"].is_string());
+        assert_eq!(json["has_local_overrides"], false);
     }
 
     #[test]
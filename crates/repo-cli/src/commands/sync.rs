@@ -2,33 +2,78 @@
 //!
 //! These commands manage synchronization state between the ledger and filesystem.
 
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use colored::Colorize;
-use serde_json::json;
+use serde::Serialize;
 
 use repo_core::config::Manifest;
-use repo_core::hooks::{HookContext, HookEvent, run_hooks};
-use repo_core::{CheckStatus, Mode, SyncEngine, SyncOptions};
+use repo_core::{CheckReport, CheckStatus, FileChange, Mode, SyncEngine, SyncOptions, SyncReport};
 use repo_fs::NormalizedPath;
 
-use crate::context::{RepoContext, detect_context};
 use crate::error::{CliError, Result};
+use crate::output::{self, say};
+
+/// Result of `repo sync`, for structured output
+#[derive(Debug, Serialize)]
+pub struct SyncResult {
+    pub dry_run: bool,
+    pub success: bool,
+    pub has_changes: bool,
+    pub changes: Vec<SyncChange>,
+    /// Files written, with the rules or settings that caused each write
+    pub attribution: Vec<FileChange>,
+    pub errors: Vec<String>,
+    /// Tool definitions that were ignored or failed to load
+    pub warnings: Vec<String>,
+    /// Id of the commit made with `--commit`
+    pub commit: Option<String>,
+    pub root: String,
+    pub mode: String,
+}
+
+/// One action taken by sync
+#[derive(Debug, Serialize)]
+pub struct SyncChange {
+    /// The action, without the dry-run prefix
+    pub action: String,
+    /// `create`, `update`, `delete` or `other`
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+}
+
+/// Result of `repo fix`, for structured output
+#[derive(Debug, Serialize)]
+pub struct FixResult {
+    pub dry_run: bool,
+    /// Status of the repository before the fix
+    pub status: CheckStatus,
+    pub success: bool,
+    pub actions: Vec<String>,
+    pub errors: Vec<String>,
+}
 
 /// Resolve the repository root from any path within the repo
 ///
-/// Uses context detection to find the correct root:
+/// Uses [`repo_core::context::detect`] to find the correct root:
 /// - In worktrees mode: returns container root
 /// - In standard mode: returns repo root
-/// - Not in a repo: returns error
+/// - Not in a repo, or partially initialized: returns error
 pub fn resolve_root(path: &Path) -> Result<NormalizedPath> {
-    let context = detect_context(path);
-
-    match context {
-        RepoContext::ContainerRoot { path } => Ok(NormalizedPath::new(&path)),
-        RepoContext::Worktree { container, .. } => Ok(NormalizedPath::new(&container)),
-        RepoContext::StandardRepo { path } => Ok(NormalizedPath::new(&path)),
-        RepoContext::NotARepo => Err(CliError::user(
+    let context = repo_core::context::detect(path);
+
+    match context.root {
+        Some(root) if context.config_path.is_some() => Ok(root),
+        Some(root) => Err(CliError::user(format!(
+            "Repository at {} is partially initialized: .repository/config.toml is missing. \
+             Run 'repo init' to complete setup, or 'repo context --explain' for details.",
+            root
+        ))),
+        None => Err(CliError::user(
             "Not in a repository. Run 'repo init' to create one.",
         )),
     }
@@ -43,27 +88,60 @@ pub fn detect_mode(root: &NormalizedPath) -> Result<Mode> {
     Ok(repo_core::detect_mode(root)?)
 }
 
-/// Load hooks from config.toml if it exists
-fn load_hooks(path: &Path) -> Vec<repo_core::hooks::HookConfig> {
+/// Load config.toml if it exists and parses
+fn load_manifest(path: &Path) -> Option<Manifest> {
     let config_path = path.join(".repository").join("config.toml");
-    if !config_path.exists() {
-        return Vec::new();
-    }
-    let content = match std::fs::read_to_string(&config_path) {
-        Ok(c) => c,
-        Err(_) => return Vec::new(),
+    let content = std::fs::read_to_string(config_path).ok()?;
+    Manifest::parse(&content).ok()
+}
+
+/// Commit the files `report` says sync wrote or removed
+///
+/// Other changes in the working directory are left alone. The ledger is
+/// committed too if `core.commit_ledger` is set. Returns `None` when there
+/// was nothing to commit.
+fn commit_sync_changes(
+    engine: &SyncEngine,
+    report: &SyncReport,
+    message: &str,
+) -> Result<Option<git2::Oid>> {
+    let repo = git2::Repository::discover(engine.working_dir().to_native())
+        .map_err(repo_git::Error::from)?;
+    let Some(workdir) = repo.workdir() else {
+        return Err(CliError::user(
+            "Cannot commit sync changes in a bare repository",
+        ));
     };
-    match Manifest::parse(&content) {
-        Ok(m) => m.hooks,
-        Err(_) => Vec::new(),
+    // The same directory can be spelled differently (symlinked temp
+    // directories, `..`), so paths are compared in canonical form
+    let workdir = std::fs::canonicalize(workdir)?;
+    let relative = |path: &Path| -> Option<PathBuf> {
+        let path = std::fs::canonicalize(path).ok()?;
+        path.strip_prefix(&workdir).ok().map(Path::to_path_buf)
+    };
+
+    let mut paths = BTreeSet::new();
+    // Changed files are relative to the working directory, and may be gone
+    if let Some(base) = relative(engine.working_dir().as_ref()) {
+        paths.extend(report.changes.iter().map(|change| base.join(&change.file)));
+    }
+    let commit_ledger = load_manifest(engine.root().as_ref()).is_some_and(|m| m.core.commit_ledger);
+    if commit_ledger {
+        paths.extend(relative(engine.ledger_path().as_ref()));
     }
+    if paths.is_empty() {
+        return Ok(None);
+    }
+
+    let paths: Vec<&Path> = paths.iter().map(PathBuf::as_path).collect();
+    Ok(Some(repo_git::commit_paths(&repo, message, None, &paths)?))
 }
 
 /// Run the check command
 ///
 /// Validates that the filesystem matches the ledger state.
 pub fn run_check(path: &Path) -> Result<()> {
-    println!(
+    say!(
         "{} Checking repository configuration...",
         "=>".blue().bold()
     );
@@ -73,18 +151,109 @@ pub fn run_check(path: &Path) -> Result<()> {
     let engine = SyncEngine::new(root, mode)?;
 
     let report = engine.check()?;
+    if !output::emit(&report)? {
+        print_check_report(&report);
+    }
+    Ok(())
+}
+
+/// How long a changed status must hold before `repo check --watch`
+/// reports it, so a file caught mid-write does not flap the status
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Exit code of `repo check --watch` for the last status it reported
+pub fn check_exit_code(status: CheckStatus) -> i32 {
+    match status {
+        CheckStatus::Healthy => 0,
+        CheckStatus::Missing => 1,
+        CheckStatus::Drifted => 2,
+        CheckStatus::Broken => 3,
+    }
+}
 
+/// Run the check command continuously
+///
+/// Re-checks every `interval` and prints the report whenever the status
+/// changes, until interrupted with Ctrl-C. Returns the last status
+/// reported.
+pub fn run_check_watch(path: &Path, interval: Duration) -> Result<CheckStatus> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let handler_stop = Arc::clone(&stop);
+    ctrlc::set_handler(move || handler_stop.store(true, Ordering::SeqCst))
+        .map_err(|e| CliError::user(format!("Cannot watch for Ctrl-C: {}", e)))?;
+
+    say!(
+        "{} Watching for drift every {:?}. Press Ctrl-C to stop.",
+        "=>".blue().bold(),
+        interval
+    );
+    watch_check(path, interval, &stop, |report| {
+        // Structured output is one document per reported status
+        if !output::emit(report)? {
+            say!();
+            print_check_report(report);
+        }
+        Ok(())
+    })
+}
+
+/// Check every `interval` until `stop` is set
+///
+/// `on_change` receives the first report and every report whose status
+/// differs from the last one reported, once a re-check after
+/// [`WATCH_DEBOUNCE`] confirms it; an error from it stops the watch. A
+/// check that fails, e.g. because config.toml is being edited, counts as
+/// broken.
+fn watch_check(
+    path: &Path,
+    interval: Duration,
+    stop: &AtomicBool,
+    mut on_change: impl FnMut(&CheckReport) -> Result<()>,
+) -> Result<CheckStatus> {
+    let root = resolve_root(path)?;
+    let mut reported: Option<CheckStatus> = None;
+    let mut pending: Option<CheckStatus> = None;
+
+    while !stop.load(Ordering::SeqCst) {
+        // The configuration may change while watching, so start afresh
+        let report = detect_mode(&root)
+            .and_then(|mode| Ok(SyncEngine::new(root.clone(), mode)?.check()?))
+            .unwrap_or_else(|e| CheckReport::broken(e.to_string()));
+
+        let wait = if reported == Some(report.status) {
+            pending = None;
+            interval
+        } else if reported.is_none() || pending == Some(report.status) {
+            on_change(&report)?;
+            reported = Some(report.status);
+            pending = None;
+            interval
+        } else {
+            pending = Some(report.status);
+            WATCH_DEBOUNCE.min(interval)
+        };
+
+        let deadline = Instant::now() + wait;
+        while !stop.load(Ordering::SeqCst) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(50).min(wait));
+        }
+    }
+    Ok(reported.unwrap_or(CheckStatus::Healthy))
+}
+
+/// Print a check report for the terminal
+fn print_check_report(report: &CheckReport) {
     match report.status {
         CheckStatus::Healthy => {
-            println!(
+            say!(
                 "{} Repository is healthy. No drift detected.",
                 "OK".green().bold()
             );
         }
         CheckStatus::Missing => {
-            println!("{} Some files are missing:", "MISSING".yellow().bold());
+            say!("{} Some files are missing:", "MISSING".yellow().bold());
             for item in &report.missing {
-                println!(
+                say!(
                     "   {} {} ({}): {}",
                     "-".yellow(),
                     item.file.cyan(),
@@ -92,13 +261,13 @@ pub fn run_check(path: &Path) -> Result<()> {
                     item.description
                 );
             }
-            println!();
-            println!("Run {} to repair.", "repo fix".cyan());
+            say!();
+            say!("Run {} to repair.", "repo fix".cyan());
         }
         CheckStatus::Drifted => {
-            println!("{} Configuration has drifted:", "DRIFTED".red().bold());
+            say!("{} Configuration has drifted:", "DRIFTED".red().bold());
             for item in &report.drifted {
-                println!(
+                say!(
                     "   {} {} ({}): {}",
                     "!".red(),
                     item.file.cyan(),
@@ -106,11 +275,20 @@ pub fn run_check(path: &Path) -> Result<()> {
                     item.description
                 );
             }
+            for item in &report.corrupt {
+                say!(
+                    "   {} {} ({}): {}",
+                    "x".red(),
+                    item.file.cyan(),
+                    item.tool.dimmed(),
+                    item.description
+                );
+            }
             if !report.missing.is_empty() {
-                println!();
-                println!("{} Also missing:", "MISSING".yellow().bold());
+                say!();
+                say!("{} Also missing:", "MISSING".yellow().bold());
                 for item in &report.missing {
-                    println!(
+                    say!(
                         "   {} {} ({}): {}",
                         "-".yellow(),
                         item.file.cyan(),
@@ -119,110 +297,170 @@ pub fn run_check(path: &Path) -> Result<()> {
                     );
                 }
             }
-            println!();
-            println!("Run {} to repair.", "repo fix".cyan());
+            say!();
+            say!("Run {} to repair.", "repo fix".cyan());
         }
         CheckStatus::Broken => {
-            println!("{} Repository is in a broken state:", "BROKEN".red().bold());
+            say!("{} Repository is in a broken state:", "BROKEN".red().bold());
             for msg in &report.messages {
-                println!("   {} {}", "!".red(), msg);
+                say!("   {} {}", "!".red(), msg);
             }
-            println!();
-            println!("Manual intervention may be required.");
+            say!();
+            say!("Manual intervention may be required.");
         }
     }
 
-    Ok(())
+    // Definitions in .repository/ that failed to load
+    if report.status != CheckStatus::Broken {
+        for msg in &report.messages {
+            say!("{} {}", "warn:".yellow().bold(), msg);
+        }
+    }
 }
 
 /// Run the sync command
 ///
-/// Synchronizes configuration from the ledger to the filesystem.
-pub fn run_sync(path: &Path, dry_run: bool, json_output: bool) -> Result<()> {
+/// Synchronizes configuration from the ledger to the filesystem. With
+/// `by_rule`, changes are listed under the rule (or settings) that caused
+/// them instead of as a flat action list. With `commit`, a successful sync
+/// that changed files is committed with that message.
+pub fn run_sync(path: &Path, dry_run: bool, by_rule: bool, commit: Option<&str>) -> Result<()> {
+    let result = sync(path, dry_run, by_rule, commit)?;
+    output::emit(&result)?;
+    Ok(())
+}
+
+/// Sync the repository containing `path`
+///
+/// Prints the changes in text mode, and fails if the sync did; under
+/// structured output the caller reports the result.
+pub(crate) fn sync(
+    path: &Path,
+    dry_run: bool,
+    by_rule: bool,
+    commit: Option<&str>,
+) -> Result<SyncResult> {
     let root = resolve_root(path)?;
     let mode = detect_mode(&root)?;
-    let hooks = load_hooks(root.as_ref());
     let engine = SyncEngine::new(root.clone(), mode)?;
 
-    // Pre-sync hooks
-    let hook_context = HookContext::for_sync();
-    if let Err(e) = run_hooks(&hooks, HookEvent::PreSync, &hook_context, root.as_ref()) {
-        println!("{} Pre-sync hook failed: {}", "warn:".yellow().bold(), e);
-    }
-
+    // The engine runs the sync hooks
     let options = SyncOptions { dry_run };
     let report = engine.sync_with_options(options)?;
 
-    if json_output {
-        // JSON output for CI/CD integration
-        let output = json!({
-            "dry_run": dry_run,
-            "success": report.success,
-            "has_changes": !report.actions.is_empty(),
-            "changes": report.actions.iter()
-                .map(|a| {
-                    let clean = a.strip_prefix("[dry-run] Would ").unwrap_or(a);
-                    json!({
-                        "action": clean,
-                        "type": categorize_action(clean),
-                    })
-                })
-                .collect::<Vec<_>>(),
-            "errors": report.errors,
-            "root": root.as_str(),
-            "mode": mode.to_string(),
-        });
-        println!("{}", serde_json::to_string_pretty(&output)?);
-    } else {
+    let commit_id = match commit {
+        Some(message) if report.success && !dry_run && !report.actions.is_empty() => {
+            commit_sync_changes(&engine, &report, message)?
+        }
+        _ => None,
+    };
+
+    let result = SyncResult {
+        dry_run,
+        success: report.success,
+        has_changes: !report.actions.is_empty(),
+        changes: report
+            .actions
+            .iter()
+            .map(|a| {
+                let clean = a.strip_prefix("[dry-run] Would ").unwrap_or(a);
+                SyncChange {
+                    action: clean.to_string(),
+                    kind: categorize_action(clean),
+                }
+            })
+            .collect(),
+        attribution: report.changes.clone(),
+        errors: report.errors.clone(),
+        warnings: report.warnings.clone(),
+        commit: commit_id.map(|id| id.to_string()),
+        root: root.as_str().to_string(),
+        mode: mode.to_string(),
+    };
+
+    if !output::is_structured() {
         // Human-readable colored output
         if dry_run {
-            println!("{} Previewing sync (dry-run)...", "=>".blue().bold());
+            say!("{} Previewing sync (dry-run)...", "=>".blue().bold());
         } else {
-            println!(
+            say!(
                 "{} Synchronizing tool configurations...",
                 "=>".blue().bold()
             );
         }
+        for warning in &report.warnings {
+            say!("{} {}", "warn:".yellow().bold(), warning);
+        }
 
         if report.success {
             if report.actions.is_empty() {
-                println!(
+                say!(
                     "{} Already synchronized. No changes needed.",
                     "OK".green().bold()
                 );
+            } else if by_rule {
+                print_changes_by_rule(&report, dry_run);
             } else {
                 let prefix = if dry_run {
                     "Would take actions"
                 } else {
                     "Synchronization complete"
                 };
-                println!("{} {}:", "OK".green().bold(), prefix);
+                say!("{} {}:", "OK".green().bold(), prefix);
                 for action in &report.actions {
                     let clean = action.strip_prefix("[dry-run] Would ").unwrap_or(action);
                     let (prefix_char, colored_action) = format_action(clean);
-                    println!("   {} {}", prefix_char, colored_action);
+                    say!("   {} {}", prefix_char, colored_action);
                 }
             }
+            if let Some(id) = commit_id {
+                say!(
+                    "{} Committed {}",
+                    "OK".green().bold(),
+                    id.to_string().cyan()
+                );
+            }
         } else {
-            println!("{} Synchronization failed:", "ERROR".red().bold());
+            say!("{} Synchronization failed:", "ERROR".red().bold());
             for error in &report.errors {
-                println!("   {} {}", "!".red(), error);
+                say!("   {} {}", "!".red(), error);
             }
             return Err(CliError::user("Synchronization failed"));
         }
     }
 
-    // Post-sync hooks (only after successful sync)
-    if report.success
-        && let Err(e) = run_hooks(&hooks, HookEvent::PostSync, &hook_context, root.as_ref())
-    {
-        println!("{} Post-sync hook failed: {}", "warn:".yellow().bold(), e);
+    Ok(result)
+}
+
+/// Print the report's file changes grouped by the rule that caused them
+fn print_changes_by_rule(report: &SyncReport, dry_run: bool) {
+    let groups = report.changes_by_source();
+    if groups.is_empty() {
+        say!("{} No files changed.", "OK".green().bold());
+        return;
     }
 
-    Ok(())
+    let prefix = if dry_run { "Would change" } else { "Changed" };
+    say!("{} {} by rule:", "OK".green().bold(), prefix);
+    for (source, changes) in groups {
+        say!("   {}", source.to_string().bold());
+        for change in changes {
+            let clean = change
+                .action
+                .strip_prefix("[dry-run] Would ")
+                .unwrap_or(&change.action);
+            let (prefix_char, _) = format_action(clean);
+            say!(
+                "     {} {} ({})",
+                prefix_char,
+                change.file.cyan(),
+                change.tool.dimmed()
+            );
+        }
+    }
 }
 
-/// Categorize an action for JSON output
+/// Categorize an action for structured output
 fn categorize_action(action: &str) -> &'static str {
     let lower = action.to_lowercase();
     if lower.starts_with("create") || lower.contains("created") {
@@ -267,9 +505,9 @@ fn format_action(action: &str) -> (colored::ColoredString, colored::ColoredStrin
 /// Repairs configuration drift by re-synchronizing.
 pub fn run_fix(path: &Path, dry_run: bool) -> Result<()> {
     if dry_run {
-        println!("{} Previewing fix (dry-run)...", "=>".blue().bold());
+        say!("{} Previewing fix (dry-run)...", "=>".blue().bold());
     } else {
-        println!("{} Fixing configuration drift...", "=>".blue().bold());
+        say!("{} Fixing configuration drift...", "=>".blue().bold());
     }
 
     let root = resolve_root(path)?;
@@ -280,7 +518,14 @@ pub fn run_fix(path: &Path, dry_run: bool) -> Result<()> {
     let check_report = engine.check()?;
 
     if check_report.status == CheckStatus::Healthy {
-        println!(
+        output::emit(&FixResult {
+            dry_run,
+            status: check_report.status,
+            success: true,
+            actions: Vec::new(),
+            errors: Vec::new(),
+        })?;
+        say!(
             "{} Repository is already healthy. Nothing to fix.",
             "OK".green().bold()
         );
@@ -290,6 +535,13 @@ pub fn run_fix(path: &Path, dry_run: bool) -> Result<()> {
     // Now fix it (or simulate)
     let options = SyncOptions { dry_run };
     let report = engine.fix_with_options(options)?;
+    output::emit(&FixResult {
+        dry_run,
+        status: check_report.status,
+        success: report.success,
+        actions: report.actions.clone(),
+        errors: report.errors.clone(),
+    })?;
 
     if report.success {
         if report.actions.is_empty() {
@@ -298,22 +550,22 @@ pub fn run_fix(path: &Path, dry_run: bool) -> Result<()> {
             } else {
                 "Configuration fixed."
             };
-            println!("{} {}", "OK".green().bold(), msg);
+            say!("{} {}", "OK".green().bold(), msg);
         } else {
             let prefix = if dry_run {
                 "Would take actions"
             } else {
                 "Configuration fixed"
             };
-            println!("{} {}:", "OK".green().bold(), prefix);
+            say!("{} {}:", "OK".green().bold(), prefix);
             for action in &report.actions {
-                println!("   {} {}", "+".green(), action);
+                say!("   {} {}", "+".green(), action);
             }
         }
     } else {
-        println!("{} Fix operation failed:", "ERROR".red().bold());
+        say!("{} Fix operation failed:", "ERROR".red().bold());
         for error in &report.errors {
-            println!("   {} {}", "!".red(), error);
+            say!("   {} {}", "!".red(), error);
         }
         return Err(CliError::user("Fix operation failed"));
     }
@@ -366,6 +618,57 @@ mode = "{}"
         assert!(result.is_ok(), "run_check failed: {:?}", result.err());
     }
 
+    #[test]
+    fn test_check_watch_reports_drift() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_path_buf();
+        create_minimal_repo(&path, "standard");
+        fs::write(
+            path.join(".repository").join("config.toml"),
+            "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n",
+        )
+        .unwrap();
+        run_sync(&path, false, false, None).unwrap();
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = std::sync::mpsc::channel();
+        let watcher = {
+            let path = path.clone();
+            let stop = Arc::clone(&stop);
+            std::thread::spawn(move || {
+                watch_check(&path, Duration::from_millis(20), &stop, |report| {
+                    tx.send(report.status).unwrap();
+                    Ok(())
+                })
+            })
+        };
+
+        let timeout = Duration::from_secs(10);
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), CheckStatus::Healthy);
+        fs::write(path.join(".cursorrules"), "corrupted").unwrap();
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), CheckStatus::Drifted);
+
+        stop.store(true, Ordering::SeqCst);
+        let last = watcher.join().unwrap().unwrap();
+        assert_eq!(last, CheckStatus::Drifted);
+        assert_eq!(check_exit_code(last), 2);
+        // Only status changes are reported
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_check_watch_stops_on_report_error() {
+        let temp_dir = TempDir::new().unwrap();
+        create_minimal_repo(temp_dir.path(), "standard");
+
+        let stop = AtomicBool::new(false);
+        let err = watch_check(temp_dir.path(), Duration::from_millis(20), &stop, |_| {
+            Err(CliError::user("stdout closed"))
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("stdout closed"), "got: {err}");
+    }
+
     #[test]
     fn test_sync_creates_ledger() {
         let temp_dir = TempDir::new().unwrap();
@@ -379,13 +682,65 @@ mode = "{}"
         assert!(!ledger_path.exists());
 
         // Run sync
-        let result = run_sync(path, false, false);
+        let result = run_sync(path, false, false, None);
         assert!(result.is_ok());
 
         // Ledger should now exist
         assert!(ledger_path.exists());
     }
 
+    /// A git repository with a user identity and a config syncing Cursor
+    fn init_git_repo(path: &Path, core: &str) -> git2::Repository {
+        let repo = git2::Repository::init(path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+
+        fs::create_dir_all(path.join(".repository")).unwrap();
+        fs::write(
+            path.join(".repository").join("config.toml"),
+            format!("tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n{core}"),
+        )
+        .unwrap();
+        repo
+    }
+
+    #[test]
+    fn test_sync_commit_only_commits_synced_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        let repo = init_git_repo(path, "");
+        fs::write(path.join("notes.txt"), "work in progress").unwrap();
+
+        run_sync(path, false, false, Some("Sync tool configs")).unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("Sync tool configs"));
+        let tree = head.tree().unwrap();
+        assert!(tree.get_name(".cursorrules").is_some());
+        // Neither unrelated files nor the ledger are committed
+        assert!(tree.get_name("notes.txt").is_none());
+        assert!(tree.get_path(Path::new(".repository/config.toml")).is_err());
+        assert!(tree.get_path(Path::new(".repository/ledger.toml")).is_err());
+        assert!(path.join(".repository").join("ledger.toml").exists());
+    }
+
+    #[test]
+    fn test_sync_commit_ledger_through_indirect_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        let repo = init_git_repo(path, "commit_ledger = true\n");
+
+        // The ledger is found however the repository path is spelled
+        let indirect = path.join(".repository").join("..");
+        run_sync(&indirect, false, false, Some("Sync tool configs")).unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let tree = head.tree().unwrap();
+        assert!(tree.get_name(".cursorrules").is_some());
+        assert!(tree.get_path(Path::new(".repository/ledger.toml")).is_ok());
+    }
+
     #[test]
     fn test_detect_mode_standard() {
         let temp_dir = TempDir::new().unwrap();
@@ -445,7 +800,7 @@ mode = "{}"
         create_minimal_repo(path, "standard");
 
         // Run sync in dry-run mode
-        let result = run_sync(path, true, false);
+        let result = run_sync(path, true, false, None);
         assert!(result.is_ok());
     }
 
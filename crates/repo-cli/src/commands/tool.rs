@@ -1,54 +1,107 @@
 //! Tool and preset management command implementations
 //!
-//! Provides add/remove operations for tools and presets in config.toml.
+//! Provides add/remove operations for tools and presets in config.toml, and
+//! scaffolding of tool definitions in `.repository/tools/`.
 
 use std::path::Path;
 
 use colored::Colorize;
+use serde::Serialize;
 use serde_json;
 
-use repo_core::{Manifest, SyncEngine};
-use repo_fs::NormalizedPath;
-use repo_meta::{KnownToolSlugs, Registry};
+use repo_core::{Manifest, ManifestEditor, Operation, SyncEngine};
+use repo_fs::{ConfigStore, NormalizedPath};
+use repo_meta::Registry;
+use repo_meta::schema::{
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+};
+use repo_tools::{ToolDispatcher, ToolRegistry};
 
+use crate::commands::history::record_config_change;
 use crate::commands::sync::detect_mode;
 use crate::error::{CliError, Result};
+use crate::output::{self, say};
 
 /// Path to config.toml within a repository
 const CONFIG_PATH: &str = ".repository/config.toml";
 
+/// Result of adding or removing a tool or preset
+#[derive(Debug, Serialize)]
+pub struct ConfigChange<'a> {
+    /// `tool` or `preset`
+    pub kind: &'static str,
+    pub name: &'a str,
+    /// `add` or `remove`
+    pub action: &'static str,
+    /// Whether config.toml changed, or in a dry run would change
+    pub changed: bool,
+    pub dry_run: bool,
+    /// Actions of the sync that followed the change
+    pub sync_actions: Vec<String>,
+}
+
+impl<'a> ConfigChange<'a> {
+    fn new(kind: &'static str, action: &'static str, name: &'a str, dry_run: bool) -> Self {
+        Self {
+            kind,
+            name,
+            action,
+            changed: false,
+            dry_run,
+            sync_actions: Vec::new(),
+        }
+    }
+
+    /// Report the change as the command's result
+    fn emit(self, changed: bool, sync_actions: Vec<String>) -> Result<()> {
+        output::emit(&Self {
+            changed,
+            sync_actions,
+            ..self
+        })?;
+        Ok(())
+    }
+}
+
 /// Run the add-tool command
 ///
 /// Adds a tool to the repository's config.toml.
 /// When `dry_run` is true, shows what would happen without modifying files.
-pub fn run_add_tool(path: &Path, name: &str, dry_run: bool) -> Result<()> {
+///
+/// Tools that are neither built in nor defined in `.repository/tools/` are
+/// refused, with the closest known names suggested, unless `allow_unknown`
+/// is set.
+pub fn run_add_tool(path: &Path, name: &str, allow_unknown: bool, dry_run: bool) -> Result<()> {
     let prefix = if dry_run { "[dry run] " } else { "" };
-    println!(
+    say!(
         "{}{} Adding tool: {}",
         prefix,
         "=>".blue().bold(),
         name.cyan()
     );
 
-    // Validate tool name
-    let tool_registry = KnownToolSlugs::with_builtins();
-    if !tool_registry.is_known(name) {
-        eprintln!(
-            "{} Unknown tool '{}'. Known tools: {}",
-            "warning:".yellow().bold(),
-            name,
-            tool_registry.list_known().join(", ")
-        );
+    // Validate tool name, including tools defined in .repository/tools/
+    let dispatcher = ToolDispatcher::from_repository(&NormalizedPath::new(path));
+    if let Err(unknown) = dispatcher.validate_name(name) {
+        if !allow_unknown {
+            return Err(CliError::user(format!(
+                "{}. Use --allow-unknown to add it anyway.",
+                unknown
+            )));
+        }
+        eprintln!("{} {}", "warning:".yellow().bold(), unknown);
     }
 
     let config_path = NormalizedPath::new(path.join(CONFIG_PATH));
+    let change = ConfigChange::new("tool", "add", name, dry_run);
 
     // Load existing manifest or create empty one
     let manifest = load_manifest(&config_path)?;
 
     // Check if tool already exists
     if manifest.tools.contains(&name.to_string()) {
-        println!(
+        change.emit(false, Vec::new())?;
+        say!(
             "{}{} Tool {} is already configured.",
             prefix,
             "OK".green().bold(),
@@ -58,27 +111,28 @@ pub fn run_add_tool(path: &Path, name: &str, dry_run: bool) -> Result<()> {
     }
 
     if dry_run {
-        println!("{}Would add tool '{}' to config.toml", prefix, name);
-        println!(
+        change.emit(true, Vec::new())?;
+        say!("{}Would add tool '{}' to config.toml", prefix, name);
+        say!(
             "{}Would trigger sync to generate tool configurations",
             prefix
         );
         return Ok(());
     }
 
-    // Add the tool
-    let mut manifest = manifest;
-    manifest.tools.push(name.to_string());
+    edit_manifest(&config_path, |editor| editor.add_tool(name))?;
 
-    // Save the manifest
-    save_manifest(&config_path, &manifest)?;
-
-    println!("{} Tool {} added.", "OK".green().bold(), name.cyan());
+    say!("{} Tool {} added.", "OK".green().bold(), name.cyan());
+    record_config_change(
+        path,
+        Operation::ToolAdd,
+        format!("Added tool {}", name),
+        CONFIG_PATH,
+    );
 
     // Trigger sync to apply tool configuration
-    trigger_sync_and_report(path)?;
-
-    Ok(())
+    let actions = trigger_sync_and_report(path)?;
+    change.emit(true, actions)
 }
 
 /// Run the remove-tool command
@@ -87,7 +141,7 @@ pub fn run_add_tool(path: &Path, name: &str, dry_run: bool) -> Result<()> {
 /// When `dry_run` is true, shows what would happen without modifying files.
 pub fn run_remove_tool(path: &Path, name: &str, dry_run: bool) -> Result<()> {
     let prefix = if dry_run { "[dry run] " } else { "" };
-    println!(
+    say!(
         "{}{} Removing tool: {}",
         prefix,
         "=>".blue().bold(),
@@ -95,27 +149,35 @@ pub fn run_remove_tool(path: &Path, name: &str, dry_run: bool) -> Result<()> {
     );
 
     let config_path = NormalizedPath::new(path.join(CONFIG_PATH));
+    let change = ConfigChange::new("tool", "remove", name, dry_run);
 
     // Load existing manifest
     let manifest = load_manifest(&config_path)?;
 
     // Check if tool exists
-    if let Some(pos) = manifest.tools.iter().position(|t| t == name) {
+    if manifest.tools.iter().any(|t| t == name) {
         if dry_run {
-            println!("{}Would remove tool '{}' from config.toml", prefix, name);
-            println!("{}Would trigger sync to update tool configurations", prefix);
+            change.emit(true, Vec::new())?;
+            say!("{}Would remove tool '{}' from config.toml", prefix, name);
+            say!("{}Would trigger sync to update tool configurations", prefix);
             return Ok(());
         }
 
-        let mut manifest = manifest;
-        manifest.tools.remove(pos);
-        save_manifest(&config_path, &manifest)?;
-        println!("{} Tool {} removed.", "OK".green().bold(), name.cyan());
+        edit_manifest(&config_path, |editor| editor.remove_tool(name))?;
+        say!("{} Tool {} removed.", "OK".green().bold(), name.cyan());
+        record_config_change(
+            path,
+            Operation::ToolRemove,
+            format!("Removed tool {}", name),
+            CONFIG_PATH,
+        );
 
         // Trigger sync to apply configuration changes
-        trigger_sync_and_report(path)?;
+        let actions = trigger_sync_and_report(path)?;
+        change.emit(true, actions)?;
     } else {
-        println!(
+        change.emit(false, Vec::new())?;
+        say!(
             "{}{} Tool {} not found in configuration.",
             prefix,
             "WARN".yellow().bold(),
@@ -132,7 +194,7 @@ pub fn run_remove_tool(path: &Path, name: &str, dry_run: bool) -> Result<()> {
 /// When `dry_run` is true, shows what would happen without modifying files.
 pub fn run_add_preset(path: &Path, name: &str, dry_run: bool) -> Result<()> {
     let prefix = if dry_run { "[dry run] " } else { "" };
-    println!(
+    say!(
         "{}{} Adding preset: {}",
         prefix,
         "=>".blue().bold(),
@@ -151,13 +213,15 @@ pub fn run_add_preset(path: &Path, name: &str, dry_run: bool) -> Result<()> {
     }
 
     let config_path = NormalizedPath::new(path.join(CONFIG_PATH));
+    let change = ConfigChange::new("preset", "add", name, dry_run);
 
     // Load existing manifest or create empty one
     let manifest = load_manifest(&config_path)?;
 
     // Check if preset already exists
     if manifest.presets.contains_key(name) {
-        println!(
+        change.emit(false, Vec::new())?;
+        say!(
             "{}{} Preset {} is already configured.",
             prefix,
             "OK".green().bold(),
@@ -167,25 +231,27 @@ pub fn run_add_preset(path: &Path, name: &str, dry_run: bool) -> Result<()> {
     }
 
     if dry_run {
-        println!("{}Would add preset '{}' to config.toml", prefix, name);
+        change.emit(true, Vec::new())?;
+        say!("{}Would add preset '{}' to config.toml", prefix, name);
         return Ok(());
     }
 
     // Add the preset with an empty object
-    let mut manifest = manifest;
-    manifest
-        .presets
-        .insert(name.to_string(), serde_json::json!({}));
-
-    // Save the manifest
-    save_manifest(&config_path, &manifest)?;
-
-    println!("{} Preset {} added.", "OK".green().bold(), name.cyan());
+    edit_manifest(&config_path, |editor| {
+        editor.add_preset(name, &serde_json::json!({}))
+    })?;
+
+    say!("{} Preset {} added.", "OK".green().bold(), name.cyan());
+    record_config_change(
+        path,
+        Operation::PresetAdd,
+        format!("Added preset {}", name),
+        CONFIG_PATH,
+    );
 
     // Trigger sync to apply preset configuration
-    trigger_sync_and_report(path)?;
-
-    Ok(())
+    let actions = trigger_sync_and_report(path)?;
+    change.emit(true, actions)
 }
 
 /// Run the remove-preset command
@@ -194,7 +260,7 @@ pub fn run_add_preset(path: &Path, name: &str, dry_run: bool) -> Result<()> {
 /// When `dry_run` is true, shows what would happen without modifying files.
 pub fn run_remove_preset(path: &Path, name: &str, dry_run: bool) -> Result<()> {
     let prefix = if dry_run { "[dry run] " } else { "" };
-    println!(
+    say!(
         "{}{} Removing preset: {}",
         prefix,
         "=>".blue().bold(),
@@ -202,6 +268,7 @@ pub fn run_remove_preset(path: &Path, name: &str, dry_run: bool) -> Result<()> {
     );
 
     let config_path = NormalizedPath::new(path.join(CONFIG_PATH));
+    let change = ConfigChange::new("preset", "remove", name, dry_run);
 
     // Load existing manifest
     let manifest = load_manifest(&config_path)?;
@@ -209,19 +276,26 @@ pub fn run_remove_preset(path: &Path, name: &str, dry_run: bool) -> Result<()> {
     // Check if preset exists
     if manifest.presets.contains_key(name) {
         if dry_run {
-            println!("{}Would remove preset '{}' from config.toml", prefix, name);
+            change.emit(true, Vec::new())?;
+            say!("{}Would remove preset '{}' from config.toml", prefix, name);
             return Ok(());
         }
 
-        let mut manifest = manifest;
-        manifest.presets.remove(name);
-        save_manifest(&config_path, &manifest)?;
-        println!("{} Preset {} removed.", "OK".green().bold(), name.cyan());
+        edit_manifest(&config_path, |editor| editor.remove_preset(name))?;
+        say!("{} Preset {} removed.", "OK".green().bold(), name.cyan());
+        record_config_change(
+            path,
+            Operation::PresetRemove,
+            format!("Removed preset {}", name),
+            CONFIG_PATH,
+        );
 
         // Trigger sync to apply configuration changes
-        trigger_sync_and_report(path)?;
+        let actions = trigger_sync_and_report(path)?;
+        change.emit(true, actions)?;
     } else {
-        println!(
+        change.emit(false, Vec::new())?;
+        say!(
             "{}{} Preset {} not found in configuration.",
             prefix,
             "WARN".yellow().bold(),
@@ -234,7 +308,8 @@ pub fn run_remove_preset(path: &Path, name: &str, dry_run: bool) -> Result<()> {
 
 /// Load a manifest from the config file
 ///
-/// If the file doesn't exist, returns an error.
+/// If the file doesn't exist, returns an error. The file is read under a
+/// shared lock, so a concurrent sync or MCP write is never seen half-done.
 pub fn load_manifest(path: &NormalizedPath) -> Result<Manifest> {
     let native_path = path.to_native();
 
@@ -245,34 +320,34 @@ pub fn load_manifest(path: &NormalizedPath) -> Result<Manifest> {
         )));
     }
 
-    let content = std::fs::read_to_string(&native_path)?;
+    let content = ConfigStore::new().load_text(path)?;
     let manifest = Manifest::parse(&content)?;
     Ok(manifest)
 }
 
-/// Save a manifest to the config file
+/// Apply `edit` to the config file under an exclusive lock
 ///
-/// Writes the manifest back to config.toml in a clean format.
-pub fn save_manifest(path: &NormalizedPath, manifest: &Manifest) -> Result<()> {
-    let native_path = path.to_native();
-
-    // Ensure parent directory exists
-    if let Some(parent) = native_path.parent() {
-        std::fs::create_dir_all(parent)?;
+/// Only the keys `edit` touches change, so the user's comments and layout
+/// are kept. The file is written only if `edit` returns `true`.
+pub fn edit_manifest(
+    path: &NormalizedPath,
+    edit: impl FnOnce(&mut ManifestEditor) -> bool,
+) -> Result<bool> {
+    let lock = ConfigStore::new().lock(path)?;
+    let mut editor = ManifestEditor::parse(&lock.read()?)?;
+    if !edit(&mut editor) {
+        return Ok(false);
     }
-
-    // Generate TOML content
-    let content = generate_manifest_toml(manifest);
-
-    std::fs::write(&native_path, content)?;
-    Ok(())
+    lock.write(&editor.to_string())?;
+    Ok(true)
 }
 
 /// Trigger sync after tool/preset changes and print the results
 ///
 /// This function runs the sync engine to apply any configuration changes
-/// resulting from adding or removing tools/presets.
-fn trigger_sync_and_report(path: &Path) -> Result<()> {
+/// resulting from adding or removing tools/presets, and returns the actions
+/// it took.
+fn trigger_sync_and_report(path: &Path) -> Result<Vec<String>> {
     let root = NormalizedPath::new(path);
     let mode = detect_mode(&root)?;
     let engine = SyncEngine::new(root, mode)?;
@@ -281,7 +356,7 @@ fn trigger_sync_and_report(path: &Path) -> Result<()> {
         Ok(report) => {
             if !report.actions.is_empty() {
                 for action in &report.actions {
-                    println!("   {} {}", "+".green(), action);
+                    say!("   {} {}", "+".green(), action);
                 }
             }
             if !report.success {
@@ -289,21 +364,179 @@ fn trigger_sync_and_report(path: &Path) -> Result<()> {
                     eprintln!("   {} {}", "!".red(), error);
                 }
             }
-            Ok(())
+            Ok(report.actions)
         }
         Err(e) => {
             eprintln!("{} Sync failed: {}", "warning:".yellow().bold(), e);
             // Don't fail the overall operation - the config change succeeded
-            Ok(())
+            Ok(Vec::new())
         }
     }
 }
 
-/// Generate TOML content from a manifest
+/// Options for [`run_tool_scaffold`]
+#[derive(Debug, Default)]
+pub struct ScaffoldOptions {
+    /// Primary config file; required unless `from` is set
+    pub config_path: Option<String>,
+    /// File format; inferred from `config_path` if unset
+    pub config_type: Option<ConfigType>,
+    /// Builtin tool whose definition to start from
+    pub from: Option<String>,
+    /// Print the definition instead of writing it
+    pub stdout: bool,
+    /// Overwrite an existing definition file
+    pub force: bool,
+}
+
+/// Result of the tool scaffold command
+#[derive(Debug, Serialize)]
+pub struct ScaffoldResult<'a> {
+    pub tool: &'a str,
+    /// Where the definition was written; absent with `--stdout`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// The definition's TOML
+    pub definition: &'a str,
+}
+
+/// Run the tool scaffold command
 ///
-/// Delegates to `Manifest::to_toml()` for the shared serialization logic.
-fn generate_manifest_toml(manifest: &Manifest) -> String {
-    manifest.to_toml()
+/// Writes a commented definition for tool `name` to
+/// `.repository/tools/<name>.toml`, or prints it with `stdout`.
+pub fn run_tool_scaffold(path: &Path, name: &str, options: ScaffoldOptions) -> Result<()> {
+    repo_core::validate_rule_id(name)
+        .map_err(|e| CliError::user(format!("Invalid tool name '{}': {}", name, e)))?;
+
+    let builtins = ToolRegistry::with_builtins();
+    let definition = match &options.from {
+        Some(from) => {
+            let reg = builtins.get(from).ok_or_else(|| {
+                CliError::user(format!(
+                    "Unknown builtin tool '{}'. Use 'repo list-tools' to see available tools.",
+                    from
+                ))
+            })?;
+            let mut definition = reg.to_definition();
+            if name != from {
+                definition.meta.slug = name.to_string();
+                definition.meta.name = name.to_string();
+            }
+            if let Some(config_path) = &options.config_path {
+                definition.integration.config_path = config_path.clone();
+            }
+            if let Some(config_type) = &options.config_type {
+                definition.integration.config_type = config_type.clone();
+            }
+            // Copying a builtin under its own slug is an explicit override
+            definition.meta.override_builtin = builtins.contains(name);
+            definition
+        }
+        None => {
+            let config_path = options
+                .config_path
+                .clone()
+                .ok_or_else(|| CliError::user("--config-path is required without --from"))?;
+            let config_type = options
+                .config_type
+                .clone()
+                .unwrap_or_else(|| infer_config_type(&config_path));
+            scaffold_definition(name, config_path, config_type)
+        }
+    };
+
+    let content = definition.to_commented_toml();
+
+    if options.stdout {
+        if !output::emit(&ScaffoldResult {
+            tool: name,
+            path: None,
+            definition: &content,
+        })? {
+            print!("{}", content);
+        }
+        return Ok(());
+    }
+
+    let file = NormalizedPath::new(
+        path.join(".repository/tools")
+            .join(format!("{}.toml", name)),
+    );
+    if file.exists() && !options.force {
+        return Err(CliError::user(format!(
+            "{} already exists. Use --force to overwrite it.",
+            file.as_str()
+        )));
+    }
+    std::fs::create_dir_all(path.join(".repository/tools"))?;
+    repo_fs::io::write_text(&file, &content)?;
+
+    output::emit(&ScaffoldResult {
+        tool: name,
+        path: Some(format!(".repository/tools/{}.toml", name)),
+        definition: &content,
+    })?;
+    say!(
+        "{} Wrote .repository/tools/{}.toml",
+        "OK".green().bold(),
+        name
+    );
+    if builtins.contains(name) && !definition.meta.override_builtin {
+        eprintln!(
+            "{} '{}' is a builtin tool; set `override = true` in [meta] to replace it.",
+            "warning:".yellow().bold(),
+            name
+        );
+    }
+    Ok(())
+}
+
+/// A new tool definition with default capabilities
+fn scaffold_definition(name: &str, config_path: String, config_type: ConfigType) -> ToolDefinition {
+    let is_directory = matches!(config_type, ConfigType::Directory { .. });
+    ToolDefinition {
+        meta: ToolMeta {
+            name: name.to_string(),
+            slug: name.to_string(),
+            description: None,
+            override_builtin: false,
+            open_command: None,
+        },
+        integration: ToolIntegrationConfig {
+            config_path,
+            config_type,
+            additional_paths: vec![],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
+        },
+        capabilities: ToolCapabilities {
+            supports_custom_instructions: true,
+            supports_mcp: false,
+            supports_rules_directory: is_directory,
+        },
+        schema_keys: None,
+    }
+}
+
+/// Guess a config file's format from its path
+fn infer_config_type(config_path: &str) -> ConfigType {
+    if config_path.ends_with('/') {
+        return ConfigType::Directory {
+            pattern: repo_meta::schema::DEFAULT_DIRECTORY_PATTERN.to_string(),
+        };
+    }
+    match Path::new(config_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+    {
+        "json" => ConfigType::Json,
+        "toml" => ConfigType::Toml,
+        "yaml" | "yml" => ConfigType::Yaml,
+        "md" | "markdown" => ConfigType::Markdown,
+        _ => ConfigType::Text,
+    }
 }
 
 #[cfg(test)]
@@ -341,7 +574,7 @@ mode = "standard"
         );
 
         // Add a tool
-        let result = run_add_tool(path, "eslint", false);
+        let result = run_add_tool(path, "eslint", true, false);
         assert!(result.is_ok());
 
         // Verify tool was added
@@ -365,7 +598,7 @@ mode = "standard"
         );
 
         // Add another tool
-        let result = run_add_tool(path, "eslint", false);
+        let result = run_add_tool(path, "eslint", true, false);
         assert!(result.is_ok());
 
         // Verify both tools exist
@@ -390,7 +623,7 @@ mode = "standard"
         );
 
         // Add duplicate tool - should succeed without duplicating
-        let result = run_add_tool(path, "eslint", false);
+        let result = run_add_tool(path, "eslint", true, false);
         assert!(result.is_ok());
 
         // Parse and verify only one instance
@@ -424,6 +657,30 @@ mode = "standard"
         assert!(content.contains("\"prettier\""));
     }
 
+    #[test]
+    fn test_add_remove_tool_keeps_comments() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        let annotated = r#"# Agents this team uses
+tools = ["claude"] # see onboarding doc
+
+[core]
+# standard until we adopt worktrees
+mode = "standard"
+"#;
+        create_test_config(path, annotated);
+
+        run_add_tool(path, "cursor", false, false).unwrap();
+        assert_eq!(
+            read_config(path),
+            annotated.replace(r#"["claude"]"#, r#"["claude", "cursor"]"#)
+        );
+
+        run_remove_tool(path, "cursor", false).unwrap();
+        assert_eq!(read_config(path), annotated);
+    }
+
     #[test]
     fn test_remove_nonexistent_tool() {
         let temp_dir = TempDir::new().unwrap();
@@ -554,7 +811,7 @@ mode = "standard"
         let path = temp_dir.path();
 
         // No config.toml exists
-        let result = run_add_tool(path, "eslint", false);
+        let result = run_add_tool(path, "eslint", true, false);
         assert!(result.is_err());
 
         let err = result.unwrap_err();
@@ -562,41 +819,6 @@ mode = "standard"
         assert!(err_msg.contains("Config file not found"));
     }
 
-    #[test]
-    fn test_save_manifest_creates_directory() {
-        let temp_dir = TempDir::new().unwrap();
-        let path = temp_dir.path();
-
-        let config_path = NormalizedPath::new(path.join(".repository/config.toml"));
-        let manifest = Manifest::empty();
-
-        let result = save_manifest(&config_path, &manifest);
-        assert!(result.is_ok());
-
-        // Verify file was created
-        assert!(path.join(".repository/config.toml").exists());
-    }
-
-    #[test]
-    fn test_generate_manifest_toml() {
-        let mut manifest = Manifest::empty();
-        manifest.core.mode = "worktrees".to_string();
-        manifest.tools = vec!["eslint".to_string(), "prettier".to_string()];
-        manifest
-            .presets
-            .insert("typescript".to_string(), serde_json::json!({}));
-
-        let content = generate_manifest_toml(&manifest);
-
-        assert!(content.contains("[core]"));
-        assert!(content.contains("mode = \"worktrees\""));
-        // toml::to_string_pretty formats arrays multi-line
-        assert!(content.contains("\"eslint\""));
-        assert!(content.contains("\"prettier\""));
-        // toml::to_string_pretty formats empty map values as sub-table headers
-        assert!(content.contains("typescript"));
-    }
-
     #[test]
     fn test_add_tool_dry_run_does_not_modify_config() {
         let temp_dir = TempDir::new().unwrap();
@@ -605,7 +827,7 @@ mode = "standard"
         let initial_config = "[core]\nmode = \"standard\"\n";
         create_test_config(path, initial_config);
 
-        let result = run_add_tool(path, "eslint", true);
+        let result = run_add_tool(path, "eslint", true, true);
         assert!(result.is_ok());
 
         // Config should be unchanged
@@ -665,4 +887,93 @@ mode = "standard"
         assert!(content.contains("typescript"));
         assert!(content.contains("react"));
     }
+
+    #[test]
+    fn test_tool_scaffold_writes_loadable_definition() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        run_tool_scaffold(
+            path,
+            "mytool",
+            ScaffoldOptions {
+                config_path: Some(".mytool/rules.md".into()),
+                config_type: Some(ConfigType::Markdown),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(path.join(".repository/tools/mytool.toml")).unwrap();
+        assert!(content.contains("# Primary config file"));
+
+        let loaded = repo_meta::DefinitionLoader::new()
+            .load_tools(&NormalizedPath::new(path))
+            .unwrap();
+        assert!(loaded.warnings.is_empty());
+        let def = &loaded.definitions["mytool"];
+        assert_eq!(def.integration.config_path, ".mytool/rules.md");
+        assert_eq!(def.integration.config_type, ConfigType::Markdown);
+        assert!(def.capabilities.supports_custom_instructions);
+        assert!(!def.meta.override_builtin);
+
+        // Refuses to overwrite without --force
+        let again = ScaffoldOptions {
+            config_path: Some(".mytool/rules.md".into()),
+            ..Default::default()
+        };
+        assert!(run_tool_scaffold(path, "mytool", again).is_err());
+    }
+
+    #[test]
+    fn test_tool_scaffold_infers_format() {
+        assert_eq!(infer_config_type(".tool/settings.json"), ConfigType::Json);
+        assert_eq!(infer_config_type(".tool.yml"), ConfigType::Yaml);
+        assert_eq!(infer_config_type("TOOL.md"), ConfigType::Markdown);
+        assert_eq!(infer_config_type(".toolrules"), ConfigType::Text);
+        assert!(matches!(
+            infer_config_type(".tool/rules/"),
+            ConfigType::Directory { .. }
+        ));
+    }
+
+    #[test]
+    fn test_tool_scaffold_from_builtin() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        run_tool_scaffold(
+            path,
+            "cursor",
+            ScaffoldOptions {
+                from: Some("cursor".into()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let loaded = repo_meta::DefinitionLoader::new()
+            .load_tools(&NormalizedPath::new(path))
+            .unwrap();
+        let def = &loaded.definitions["cursor"];
+        assert_eq!(def.meta.name, "Cursor");
+        assert_eq!(def.integration.config_path, ".cursorrules");
+        assert!(def.meta.override_builtin);
+
+        let dispatcher = repo_tools::ToolDispatcher::from_repository(&NormalizedPath::new(path));
+        assert_eq!(
+            dispatcher.resolution("cursor"),
+            Some(repo_tools::ToolResolution::Override)
+        );
+    }
+
+    #[test]
+    fn test_tool_scaffold_unknown_builtin() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = ScaffoldOptions {
+            from: Some("nonexistent".into()),
+            ..Default::default()
+        };
+        assert!(run_tool_scaffold(temp_dir.path(), "mytool", options).is_err());
+    }
 }
@@ -30,6 +30,14 @@ pub enum CliError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    /// YAML serialization error
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    /// Error from repo-extensions
+    #[error(transparent)]
+    Extensions(#[from] repo_extensions::Error),
+
     /// Error from repo-presets
     #[error(transparent)]
     Presets(#[from] repo_presets::Error),
@@ -2,23 +2,44 @@
 //!
 //! Uses dialoguer for terminal-based interactive selection.
 
+use std::path::Path;
+
 use colored::Colorize;
 use dialoguer::{Confirm, Input, MultiSelect, Select};
+use repo_core::Template;
 use repo_extensions::ExtensionRegistry;
 use repo_meta::Registry;
-use repo_tools::ToolRegistry;
+use repo_tools::{ToolCategory, ToolRegistry, close_matches};
 
-use crate::commands::init::InitConfig;
+use crate::commands::init::{InitConfig, enclosing_repository, project_path, validate_remote_url};
 use crate::error::Result;
+use crate::output::say;
 
 /// Available repository modes
 const MODES: &[&str] = &["worktrees", "standard"];
 
+/// Last entry of the tool and preset lists, for names not in the registry
+const OTHER: &str = "Other... (enter names)";
+
 /// Run interactive init prompts
 ///
 /// Prompts the user for project configuration and returns an InitConfig.
-pub fn interactive_init(default_name: &str) -> Result<InitConfig> {
-    println!();
+/// Inside an existing repository, asks before initializing a nested one
+/// unless `nested` is already set. Offers the user's templates first,
+/// unless `template` is already chosen.
+pub fn interactive_init(
+    cwd: &Path,
+    default_name: &str,
+    nested: bool,
+    template: Option<Template>,
+) -> Result<InitConfig> {
+    say!();
+
+    // Template selection
+    let template = match template {
+        Some(template) => Some(template),
+        None => select_template()?,
+    };
 
     // Project name
     let name: String = Input::new()
@@ -30,6 +51,25 @@ pub fn interactive_init(default_name: &str) -> Result<InitConfig> {
         })
         .interact_text()?;
 
+    // Nested repository
+    let parent = enclosing_repository(&project_path(cwd, &name));
+    let nested = match &parent {
+        Some(parent) if !nested => {
+            let nested = Confirm::new()
+                .with_prompt(format!(
+                    "This is inside the repository at {}. Set it up as a sub-package of it?",
+                    parent.display()
+                ))
+                .default(false)
+                .interact()?;
+            if !nested {
+                return Err(crate::error::CliError::user("Init cancelled by user."));
+            }
+            nested
+        }
+        _ => nested,
+    };
+
     // Mode selection
     let mode_idx = Select::new()
         .with_prompt("Repository mode")
@@ -38,29 +78,61 @@ pub fn interactive_init(default_name: &str) -> Result<InitConfig> {
         .interact()?;
     let mode = MODES[mode_idx].to_string();
 
-    // Tool selection (multi-select) - dynamically from ToolRegistry
+    // Tool selection (multi-select) - from ToolRegistry, grouped by category
     let tool_registry = ToolRegistry::with_builtins();
-    let available_tools = tool_registry.list();
+    let available_tools = tool_items(&tool_registry);
+    let mut tool_labels: Vec<&str> = available_tools.iter().map(|(_, l)| l.as_str()).collect();
+    tool_labels.push(OTHER);
     let tool_indices = MultiSelect::new()
         .with_prompt("Select tools (space to toggle, enter to confirm)")
-        .items(&available_tools)
+        .items(&tool_labels)
         .interact()?;
-    let tools: Vec<String> = tool_indices
-        .iter()
-        .map(|&i| available_tools[i].to_string())
-        .collect();
+    let mut tools: Vec<String> = Vec::new();
+    for &i in &tool_indices {
+        if i < available_tools.len() {
+            tools.push(available_tools[i].0.to_string());
+            continue;
+        }
+        warn(
+            "Tools that are not built in are written to config.toml as given, \
+             and are only synced once a definition in .repository/tools/ provides them.",
+        );
+        for name in prompt_names("Other tools (comma-separated)")? {
+            if let Err(unknown) = tool_registry.validate_name(&name) {
+                warn(&unknown.to_string());
+            }
+            tools.push(name);
+        }
+    }
 
     // Preset selection (multi-select) - dynamically from Registry
     let preset_registry = Registry::with_builtins();
     let available_presets = preset_registry.list_presets();
+    let mut preset_labels: Vec<&str> = available_presets.iter().map(String::as_str).collect();
+    preset_labels.push(OTHER);
     let preset_indices = MultiSelect::new()
         .with_prompt("Select presets (space to toggle, enter to confirm)")
-        .items(&available_presets)
+        .items(&preset_labels)
         .interact()?;
-    let presets: Vec<String> = preset_indices
-        .iter()
-        .map(|&i| available_presets[i].clone())
-        .collect();
+    let mut presets: Vec<String> = Vec::new();
+    for &i in &preset_indices {
+        if i < available_presets.len() {
+            presets.push(available_presets[i].clone());
+            continue;
+        }
+        warn("Presets that are not built in have no provider, so sync cannot apply them.");
+        for name in prompt_names("Other presets (comma-separated)")? {
+            let suggestions = close_matches(&name, available_presets.iter().map(String::as_str));
+            if !suggestions.is_empty() {
+                warn(&format!(
+                    "Unknown preset '{}'; did you mean '{}'?",
+                    name,
+                    suggestions.join("' or '")
+                ));
+            }
+            presets.push(name);
+        }
+    }
 
     // Extension selection (multi-select) - from ExtensionRegistry + custom option
     let ext_registry = ExtensionRegistry::with_known();
@@ -92,45 +164,54 @@ pub fn interactive_init(default_name: &str) -> Result<InitConfig> {
         .interact()?;
 
     let remote = if add_remote {
-        let url: String = Input::new().with_prompt("Remote URL").interact_text()?;
-        if url.trim().is_empty() {
-            None
-        } else {
-            Some(url)
-        }
+        let url: String = Input::new()
+            .with_prompt("Remote URL")
+            .validate_with(|url: &String| validate_remote_url(url))
+            .interact_text()?;
+        Some(url.trim().to_string())
     } else {
         None
     };
 
     // Show summary and confirm
-    println!();
-    println!("{}", "Summary:".bold());
-    println!("  {}: {}", "Project".dimmed(), name.cyan());
-    println!("  {}: {}", "Mode".dimmed(), mode.cyan());
+    say!();
+    say!("{}", "Summary:".bold());
+    say!("  {}: {}", "Project".dimmed(), name.cyan());
+    say!("  {}: {}", "Mode".dimmed(), mode.cyan());
     if tools.is_empty() {
-        println!("  {}: {}", "Tools".dimmed(), "(none)".dimmed());
+        say!("  {}: {}", "Tools".dimmed(), "(none)".dimmed());
     } else {
-        println!("  {}: {}", "Tools".dimmed(), tools.join(", ").cyan());
+        say!("  {}: {}", "Tools".dimmed(), tools.join(", ").cyan());
     }
     if presets.is_empty() {
-        println!("  {}: {}", "Presets".dimmed(), "(none)".dimmed());
+        say!("  {}: {}", "Presets".dimmed(), "(none)".dimmed());
     } else {
-        println!("  {}: {}", "Presets".dimmed(), presets.join(", ").cyan());
+        say!("  {}: {}", "Presets".dimmed(), presets.join(", ").cyan());
     }
     if extensions.is_empty() {
-        println!("  {}: {}", "Extensions".dimmed(), "(none)".dimmed());
+        say!("  {}: {}", "Extensions".dimmed(), "(none)".dimmed());
     } else {
-        println!(
+        say!(
             "  {}: {}",
             "Extensions".dimmed(),
             extensions.join(", ").cyan()
         );
     }
     match &remote {
-        Some(url) => println!("  {}: {}", "Remote".dimmed(), url.cyan()),
-        None => println!("  {}: {}", "Remote".dimmed(), "(none)".dimmed()),
+        Some(url) => say!("  {}: {}", "Remote".dimmed(), url.cyan()),
+        None => say!("  {}: {}", "Remote".dimmed(), "(none)".dimmed()),
     }
-    println!();
+    if let Some(template) = &template {
+        say!("  {}: {}", "Template".dimmed(), template.name.cyan());
+    }
+    if let Some(parent) = &parent {
+        say!(
+            "  {}: {}",
+            "Parent".dimmed(),
+            parent.display().to_string().cyan()
+        );
+    }
+    say!();
 
     let proceed = Confirm::new()
         .with_prompt("Proceed?")
@@ -148,9 +229,65 @@ pub fn interactive_init(default_name: &str) -> Result<InitConfig> {
         presets,
         extensions,
         remote,
+        nested,
+        template,
     })
 }
 
+/// Built-in tools as `(slug, label)`, grouped by category
+fn tool_items(registry: &ToolRegistry) -> Vec<(&str, String)> {
+    ToolCategory::ALL
+        .iter()
+        .flat_map(|category| {
+            registry
+                .by_category(*category)
+                .into_iter()
+                .map(move |slug| (slug, format!("{:<16} {}", category.label(), slug)))
+        })
+        .collect()
+}
+
+/// Prompt for a comma-separated list of names
+fn prompt_names(prompt: &str) -> Result<Vec<String>> {
+    let input: String = Input::new()
+        .with_prompt(prompt)
+        .allow_empty(true)
+        .interact_text()?;
+    Ok(input
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+fn warn(message: &str) {
+    eprintln!("{} {}", "warning:".yellow().bold(), message);
+}
+
+/// Ask which of the user's templates to start from, if they have any
+fn select_template() -> Result<Option<Template>> {
+    let Some(dir) = repo_core::templates_dir() else {
+        return Ok(None);
+    };
+    let mut templates = repo_core::list_templates(&dir);
+    if templates.is_empty() {
+        return Ok(None);
+    }
+
+    let mut items = vec!["(none)".to_string()];
+    items.extend(templates.iter().map(|t| match t.description() {
+        Some(description) => format!("{} - {}", t.name, description),
+        None => t.name.clone(),
+    }));
+    let index = Select::new()
+        .with_prompt("Start from a template")
+        .items(&items)
+        .default(0)
+        .interact()?;
+    Ok((index > 0).then(|| templates.swap_remove(index - 1)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,6 +308,25 @@ mod tests {
         assert!(tools.len() >= 10, "Should have at least 10 tools");
     }
 
+    #[test]
+    fn test_tool_items_grouped_by_category() {
+        let registry = ToolRegistry::with_builtins();
+        let items = tool_items(&registry);
+        assert_eq!(items.len(), registry.len());
+
+        let categories: Vec<ToolCategory> = items
+            .iter()
+            .map(|(slug, _)| registry.get(slug).unwrap().category)
+            .collect();
+        let mut grouped = categories.clone();
+        grouped.sort_by_key(|c| ToolCategory::ALL.iter().position(|a| a == c));
+        assert_eq!(categories, grouped);
+
+        let (slug, label) = items.iter().find(|(slug, _)| *slug == "cursor").unwrap();
+        assert_eq!(*slug, "cursor");
+        assert!(label.starts_with("IDE") && label.ends_with("cursor"));
+    }
+
     #[test]
     fn test_preset_registry_has_presets() {
         let registry = Registry::with_builtins();
@@ -4,11 +4,13 @@
 
 mod cli;
 mod commands;
-mod context;
 mod error;
 mod interactive;
+mod output;
 
 use std::io;
+use std::path::Path;
+use std::time::Duration;
 
 use clap::{CommandFactory, Parser};
 use clap_complete::{Shell, generate};
@@ -16,8 +18,16 @@ use colored::Colorize;
 use tracing::Level;
 use tracing_subscriber::FmtSubscriber;
 
-use cli::{BranchAction, Cli, Commands, ConfigAction, ExtensionAction, HooksAction};
+use cli::{
+    BranchAction, Cli, Commands, ConfigAction, ExtensionAction, GitAction, HooksAction, McpAction,
+    McpScopeArg, PresetFailOn, PresetsAction, ReportBy, ToolAction, ToolFormat, WsAction,
+};
 use error::Result;
+use output::{OutputFormat, say};
+use repo_core::ImportMode;
+use repo_fs::RobustnessConfig;
+use repo_meta::schema::{ConfigType, DEFAULT_DIRECTORY_PATTERN, McpScope};
+use repo_presets::PresetStatus;
 
 fn main() {
     if let Err(e) = run() {
@@ -41,23 +51,49 @@ fn run() -> Result<()> {
         tracing::debug!("Verbose mode enabled");
     }
 
+    // Write verification applies to every write site for this run
+    let verify_writes = cli.verify_writes || config_verify_writes();
+    let _robustness = RobustnessConfig::current()
+        .with_verify_writes(verify_writes)
+        .enter();
+    if verify_writes {
+        tracing::debug!("Write verification enabled");
+    }
+
+    // A command's --json flag is shorthand for --output-format json
+    let json = cli.command.as_ref().is_some_and(Commands::json_flag);
+    output::set_format(if json { OutputFormat::Json } else { cli.output });
+
     // Execute command
     match cli.command {
-        Some(cmd) => execute_command(cmd),
+        Some(cmd) => execute_command(cmd)?,
         None => {
             // No command provided - show help hint
-            println!("{} Repository Manager CLI", "repo".green().bold());
-            println!();
-            println!("Run {} for available commands.", "repo --help".cyan());
-            Ok(())
+            say!("{} Repository Manager CLI", "repo".green().bold());
+            say!();
+            say!("Run {} for available commands.", "repo --help".cyan());
         }
     }
+    output::finish()
+}
+
+/// Whether the current repository's config enables `core.verify_writes`.
+fn config_verify_writes() -> bool {
+    std::env::current_dir()
+        .ok()
+        .and_then(|cwd| repo_core::context::detect(&cwd).config_path)
+        .and_then(|path| std::fs::read_to_string(path.to_native()).ok())
+        .and_then(|content| repo_core::Manifest::parse(&content).ok())
+        .is_some_and(|manifest| manifest.core.verify_writes)
 }
 
 fn execute_command(cmd: Commands) -> Result<()> {
     match cmd {
-        Commands::Status { json } => cmd_status(json),
-        Commands::Diff { json } => cmd_diff(json),
+        Commands::Status { json: _ } => cmd_status(),
+        Commands::Doctor { json: _ } => cmd_doctor(),
+        Commands::Context { explain } => cmd_context(explain),
+        Commands::Diff { json: _ } => cmd_diff(),
+        Commands::History { limit, json: _ } => cmd_history(limit),
         Commands::Init {
             name,
             mode,
@@ -66,11 +102,46 @@ fn execute_command(cmd: Commands) -> Result<()> {
             extensions,
             remote,
             interactive,
-        } => cmd_init(name, mode, tools, presets, extensions, remote, interactive),
-        Commands::Check => cmd_check(),
-        Commands::Sync { dry_run, json } => cmd_sync(dry_run, json),
+            nested,
+            template,
+            list_templates,
+        } => {
+            if list_templates {
+                return commands::init::run_list_templates(&commands::init::templates_dir()?);
+            }
+            let template = template
+                .map(|source| {
+                    repo_core::load_template(&commands::init::templates_dir()?, &source)
+                        .map_err(error::CliError::from)
+                })
+                .transpose()?;
+            cmd_init(
+                commands::init::InitConfig {
+                    name,
+                    mode,
+                    tools,
+                    presets,
+                    extensions,
+                    remote,
+                    nested,
+                    template,
+                },
+                interactive,
+            )
+        }
+        Commands::Check { watch } => cmd_check(watch),
+        Commands::Sync {
+            dry_run,
+            json: _,
+            report_by,
+            commit,
+        } => cmd_sync(dry_run, report_by, commit.as_deref()),
         Commands::Fix { dry_run } => cmd_fix(dry_run),
-        Commands::AddTool { name, dry_run } => cmd_add_tool(&name, dry_run),
+        Commands::AddTool {
+            name,
+            allow_unknown,
+            dry_run,
+        } => cmd_add_tool(&name, allow_unknown, dry_run),
         Commands::RemoveTool { name, dry_run } => cmd_remove_tool(&name, dry_run),
         Commands::AddPreset { name, dry_run } => cmd_add_preset(&name, dry_run),
         Commands::RemovePreset { name, dry_run } => cmd_remove_preset(&name, dry_run),
@@ -78,13 +149,28 @@ fn execute_command(cmd: Commands) -> Result<()> {
             id,
             instruction,
             tags,
-        } => cmd_add_rule(&id, &instruction, tags),
+            lenient,
+        } => cmd_add_rule(&id, &instruction, tags, lenient),
         Commands::RemoveRule { id } => cmd_remove_rule(&id),
         Commands::ListRules => cmd_list_rules(),
-        Commands::RulesLint { json } => cmd_rules_lint(json),
-        Commands::RulesDiff { json } => cmd_rules_diff(json),
+        Commands::RulesLint { json: _ } => cmd_rules_lint(),
+        Commands::RulesValidate { json: _ } => cmd_rules_validate(),
+        Commands::RulesDiff { json: _, tool } => cmd_rules_diff(tool.as_deref()),
         Commands::RulesExport { format } => cmd_rules_export(&format),
-        Commands::RulesImport { file } => cmd_rules_import(&file),
+        Commands::RulesImport { file, lenient } => cmd_rules_import(&file, lenient),
+        Commands::Export { output, archive } => cmd_export(output.as_deref(), archive),
+        Commands::Import {
+            file,
+            merge: _,
+            replace,
+            no_input,
+        } => cmd_import(&file, replace, no_input),
+        Commands::Adopt {
+            analyze_only,
+            json: _,
+            bundle,
+        } => cmd_adopt(analyze_only, bundle.as_deref()),
+        Commands::Ws { action } => cmd_ws(action),
         Commands::ListTools { category } => cmd_list_tools(category.as_deref()),
         Commands::ListPresets => cmd_list_presets(),
         Commands::Completions { shell } => cmd_completions(shell),
@@ -92,11 +178,19 @@ fn execute_command(cmd: Commands) -> Result<()> {
         Commands::Push { remote, branch } => cmd_push(remote, branch),
         Commands::Pull { remote, branch } => cmd_pull(remote, branch),
         Commands::Merge { source } => cmd_merge(&source),
+        Commands::Git { action } => cmd_git(action),
         Commands::Config { action } => cmd_config(action),
         Commands::ToolInfo { name } => cmd_tool_info(&name),
+        Commands::Tool { action } => cmd_tool(action),
         Commands::Hooks { action } => cmd_hooks(action),
         Commands::Extension { action } => cmd_extension(action),
-        Commands::Open { worktree, tool } => cmd_open(&worktree, tool.as_deref()),
+        Commands::Mcp { action } => cmd_mcp(action),
+        Commands::Presets { action } => cmd_presets(action),
+        Commands::Open {
+            worktree,
+            tool,
+            list,
+        } => cmd_open(worktree.as_deref(), tool.as_deref(), list),
     }
 }
 
@@ -109,53 +203,63 @@ fn cmd_completions(shell: Shell) -> Result<()> {
     Ok(())
 }
 
-fn cmd_status(json: bool) -> Result<()> {
+fn cmd_status() -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    commands::run_status(&cwd)
+}
+
+fn cmd_doctor() -> Result<()> {
     let cwd = std::env::current_dir()?;
-    commands::run_status(&cwd, json)
+    commands::run_doctor(&cwd)
 }
 
-fn cmd_diff(json: bool) -> Result<()> {
+fn cmd_context(explain: bool) -> Result<()> {
     let cwd = std::env::current_dir()?;
-    commands::run_diff(&cwd, json)
+    commands::context::run_context(&cwd, explain)
 }
 
-fn cmd_init(
-    name: String,
-    mode: String,
-    tools: Vec<String>,
-    presets: Vec<String>,
-    extensions: Vec<String>,
-    remote: Option<String>,
-    interactive_flag: bool,
-) -> Result<()> {
+fn cmd_diff() -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    commands::run_diff(&cwd)
+}
+
+fn cmd_history(limit: usize) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    commands::run_history(&cwd, limit)
+}
+
+fn cmd_init(config: commands::init::InitConfig, interactive_flag: bool) -> Result<()> {
     let cwd = std::env::current_dir()?;
 
-    // Use interactive mode if requested
+    // Use interactive mode if requested, starting from the given name
     let config = if interactive_flag {
-        interactive::interactive_init(&name)?
+        interactive::interactive_init(&cwd, &config.name, config.nested, config.template)?
     } else {
-        commands::init::InitConfig {
-            name,
-            mode,
-            tools,
-            presets,
-            extensions,
-            remote,
-        }
+        commands::init::warn_unknown_tools(&config.tools);
+        config
     };
 
     commands::run_init(&cwd, config)?;
     Ok(())
 }
 
-fn cmd_check() -> Result<()> {
+fn cmd_check(watch: Option<u64>) -> Result<()> {
     let cwd = std::env::current_dir()?;
-    commands::run_check(&cwd)
+    let Some(interval) = watch else {
+        return commands::run_check(&cwd);
+    };
+    let status = commands::run_check_watch(&cwd, Duration::from_secs(interval))?;
+    let code = commands::check_exit_code(status);
+    if code != 0 {
+        std::process::exit(code);
+    }
+    Ok(())
 }
 
-fn cmd_sync(dry_run: bool, json: bool) -> Result<()> {
+fn cmd_sync(dry_run: bool, report_by: Option<ReportBy>, commit: Option<&str>) -> Result<()> {
     let cwd = std::env::current_dir()?;
-    commands::run_sync(&cwd, dry_run, json)
+    let by_rule = report_by == Some(ReportBy::Rule);
+    commands::run_sync(&cwd, dry_run, by_rule, commit)
 }
 
 fn cmd_fix(dry_run: bool) -> Result<()> {
@@ -163,9 +267,9 @@ fn cmd_fix(dry_run: bool) -> Result<()> {
     commands::run_fix(&cwd, dry_run)
 }
 
-fn cmd_add_tool(name: &str, dry_run: bool) -> Result<()> {
+fn cmd_add_tool(name: &str, allow_unknown: bool, dry_run: bool) -> Result<()> {
     let cwd = std::env::current_dir()?;
-    commands::run_add_tool(&cwd, name, dry_run)
+    commands::run_add_tool(&cwd, name, allow_unknown, dry_run)
 }
 
 fn cmd_remove_tool(name: &str, dry_run: bool) -> Result<()> {
@@ -183,9 +287,9 @@ fn cmd_remove_preset(name: &str, dry_run: bool) -> Result<()> {
     commands::run_remove_preset(&cwd, name, dry_run)
 }
 
-fn cmd_add_rule(id: &str, instruction: &str, tags: Vec<String>) -> Result<()> {
+fn cmd_add_rule(id: &str, instruction: &str, tags: Vec<String>, lenient: bool) -> Result<()> {
     let cwd = std::env::current_dir()?;
-    commands::run_add_rule(&cwd, id, instruction, tags)
+    commands::run_add_rule(&cwd, id, instruction, tags, lenient)
 }
 
 fn cmd_remove_rule(id: &str) -> Result<()> {
@@ -198,14 +302,19 @@ fn cmd_list_rules() -> Result<()> {
     commands::run_list_rules(&cwd)
 }
 
-fn cmd_rules_lint(json: bool) -> Result<()> {
+fn cmd_rules_lint() -> Result<()> {
     let cwd = std::env::current_dir()?;
-    commands::run_rules_lint(&cwd, json)
+    commands::run_rules_lint(&cwd)
 }
 
-fn cmd_rules_diff(json: bool) -> Result<()> {
+fn cmd_rules_validate() -> Result<()> {
     let cwd = std::env::current_dir()?;
-    commands::run_rules_diff(&cwd, json)
+    commands::run_rules_validate(&cwd)
+}
+
+fn cmd_rules_diff(tool: Option<&str>) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    commands::run_rules_diff(&cwd, tool)
 }
 
 fn cmd_rules_export(format: &str) -> Result<()> {
@@ -213,13 +322,44 @@ fn cmd_rules_export(format: &str) -> Result<()> {
     commands::run_rules_export(&cwd, format)
 }
 
-fn cmd_rules_import(file: &str) -> Result<()> {
+fn cmd_rules_import(file: &str, lenient: bool) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    commands::run_rules_import(&cwd, file, lenient)
+}
+
+fn cmd_export(output: Option<&Path>, archive: bool) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    commands::run_export(&cwd, output, archive)
+}
+
+fn cmd_import(file: &Path, replace: bool, no_input: bool) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let mode = if replace {
+        ImportMode::Replace
+    } else {
+        ImportMode::Merge
+    };
+    commands::run_import(&cwd, file, mode, no_input)
+}
+
+fn cmd_adopt(analyze_only: bool, bundle: Option<&Path>) -> Result<()> {
     let cwd = std::env::current_dir()?;
-    commands::run_rules_import(&cwd, file)
+    commands::run_adopt(&cwd, analyze_only, bundle)
+}
+
+fn cmd_ws(action: WsAction) -> Result<()> {
+    match action {
+        WsAction::AdoptPlan {
+            repos,
+            output,
+            json: _,
+        } => commands::run_ws_adopt_plan(&repos, output.as_deref()),
+    }
 }
 
 fn cmd_list_tools(category: Option<&str>) -> Result<()> {
-    commands::run_list_tools(category)
+    let cwd = std::env::current_dir()?;
+    commands::run_list_tools(&cwd, category)
 }
 
 fn cmd_list_presets() -> Result<()> {
@@ -231,9 +371,10 @@ fn cmd_branch(action: BranchAction) -> Result<()> {
     match action {
         BranchAction::Add { name, base } => commands::run_branch_add(&cwd, &name, Some(&base)),
         BranchAction::Remove { name } => commands::run_branch_remove(&cwd, &name),
-        BranchAction::List => commands::run_branch_list(&cwd),
-        BranchAction::Checkout { name } => commands::run_branch_checkout(&cwd, &name),
+        BranchAction::List { fast } => commands::run_branch_list(&cwd, fast),
+        BranchAction::Checkout { name, sync } => commands::run_branch_checkout(&cwd, &name, sync),
         BranchAction::Rename { old, new } => commands::run_branch_rename(&cwd, &old, &new),
+        BranchAction::Prune { dry_run, force } => commands::run_branch_prune(&cwd, dry_run, force),
     }
 }
 
@@ -252,10 +393,25 @@ fn cmd_merge(source: &str) -> Result<()> {
     commands::run_merge(&cwd, source)
 }
 
+fn cmd_git(action: GitAction) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    match action {
+        GitAction::Status { json: _ } => commands::run_git_status(&cwd),
+    }
+}
+
 fn cmd_config(action: ConfigAction) -> Result<()> {
     let cwd = std::env::current_dir()?;
     match action {
-        ConfigAction::Show { json } => commands::config::run_config_show(&cwd, json),
+        ConfigAction::Show {
+            json: _,
+            effective,
+            diff,
+        } => commands::config::run_config_show(&cwd, effective, diff),
+        ConfigAction::SetMode { mode, dry_run } => {
+            commands::config::run_config_set_mode(&cwd, &mode, dry_run)
+        }
+        ConfigAction::Fmt { check } => commands::config::run_config_fmt(&cwd, check),
     }
 }
 
@@ -264,6 +420,42 @@ fn cmd_tool_info(name: &str) -> Result<()> {
     commands::config::run_tool_info(&cwd, name)
 }
 
+fn cmd_tool(action: ToolAction) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    match action {
+        ToolAction::Scaffold {
+            name,
+            config_path,
+            format,
+            from,
+            stdout,
+            force,
+        } => {
+            let config_type = format.map(|f| match f {
+                ToolFormat::Text => ConfigType::Text,
+                ToolFormat::Json => ConfigType::Json,
+                ToolFormat::Toml => ConfigType::Toml,
+                ToolFormat::Yaml => ConfigType::Yaml,
+                ToolFormat::Markdown => ConfigType::Markdown,
+                ToolFormat::Directory => ConfigType::Directory {
+                    pattern: DEFAULT_DIRECTORY_PATTERN.to_string(),
+                },
+            });
+            commands::tool::run_tool_scaffold(
+                &cwd,
+                &name,
+                commands::tool::ScaffoldOptions {
+                    config_path,
+                    config_type,
+                    from,
+                    stdout,
+                    force,
+                },
+            )
+        }
+    }
+}
+
 fn cmd_hooks(action: HooksAction) -> Result<()> {
     let cwd = std::env::current_dir()?;
     match action {
@@ -282,17 +474,93 @@ fn cmd_extension(action: ExtensionAction) -> Result<()> {
         ExtensionAction::Install {
             source,
             no_activate,
-        } => commands::extension::handle_extension_install(&source, no_activate),
+            no_deps,
+        } => {
+            let cwd = std::env::current_dir()?;
+            commands::extension::handle_extension_install(&cwd, &source, no_activate, no_deps)
+        }
         ExtensionAction::Add { name } => commands::extension::handle_extension_add(&name),
         ExtensionAction::Init { name } => commands::extension::handle_extension_init(&name),
         ExtensionAction::Remove { name } => commands::extension::handle_extension_remove(&name),
-        ExtensionAction::List { json } => commands::extension::handle_extension_list(json),
+        ExtensionAction::List { json: _ } => commands::extension::handle_extension_list(),
+        ExtensionAction::Verify { json: _ } => {
+            let cwd = std::env::current_dir()?;
+            commands::extension::handle_extension_verify(&cwd)
+        }
     }
 }
 
-fn cmd_open(worktree: &str, tool: Option<&str>) -> Result<()> {
+fn cmd_presets(action: PresetsAction) -> Result<()> {
     let cwd = std::env::current_dir()?;
-    commands::open::run_open(&cwd, worktree, tool)
+    match action {
+        PresetsAction::Check {
+            json: _,
+            fail_on,
+            timeout,
+        } => {
+            let fail_on = match fail_on {
+                PresetFailOn::Degraded => Some(PresetStatus::Degraded {
+                    reason: String::new(),
+                }),
+                PresetFailOn::Missing => Some(PresetStatus::Missing),
+                PresetFailOn::Drifted => Some(PresetStatus::Drifted),
+                PresetFailOn::Broken => Some(PresetStatus::Broken),
+                PresetFailOn::Never => None,
+            };
+            commands::presets::run_presets_check(&cwd, fail_on, Duration::from_secs(timeout))
+        }
+    }
+}
+
+fn cmd_mcp(action: McpAction) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    let scope = |scope: McpScopeArg| match scope {
+        McpScopeArg::Project => McpScope::Project,
+        McpScopeArg::User => McpScope::User,
+    };
+    match action {
+        McpAction::Add {
+            name,
+            tool,
+            command,
+            args,
+            url,
+            env,
+            expand_env,
+            scope: s,
+        } => commands::mcp::run_mcp_add(
+            &cwd,
+            &name,
+            &tool,
+            scope(s),
+            commands::mcp::McpAddOptions {
+                command,
+                args,
+                url,
+                env,
+                expand_env,
+            },
+        ),
+        McpAction::Remove {
+            name,
+            tool,
+            scope: s,
+        } => commands::mcp::run_mcp_remove(&cwd, &name, &tool, scope(s)),
+        McpAction::List {
+            tool,
+            scope: s,
+            json: _,
+        } => commands::mcp::run_mcp_list(&cwd, tool.as_deref(), scope(s)),
+        McpAction::Sync => commands::mcp::run_mcp_sync(&cwd),
+    }
+}
+
+fn cmd_open(worktree: Option<&str>, tool: Option<&str>, list: bool) -> Result<()> {
+    let cwd = std::env::current_dir()?;
+    match worktree {
+        Some(worktree) if !list => commands::open::run_open(&cwd, worktree, tool),
+        _ => commands::open::run_open_list(&cwd),
+    }
 }
 
 #[cfg(test)]
@@ -317,7 +585,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         create_minimal_repo(temp_dir.path(), "standard");
 
-        let result = commands::run_add_tool(temp_dir.path(), "eslint", false);
+        let result = commands::run_add_tool(temp_dir.path(), "eslint", true, false);
         assert!(result.is_ok());
 
         // Verify the tool was added to config.toml
@@ -335,7 +603,7 @@ mod tests {
         create_minimal_repo(temp_dir.path(), "standard");
 
         // First add the tool
-        commands::run_add_tool(temp_dir.path(), "eslint", false).unwrap();
+        commands::run_add_tool(temp_dir.path(), "eslint", true, false).unwrap();
         // Then remove it
         let result = commands::run_remove_tool(temp_dir.path(), "eslint", false);
         assert!(result.is_ok());
@@ -380,6 +648,7 @@ mod tests {
             "python-style",
             "Use snake_case for variables.",
             vec![],
+            false,
         );
         assert!(result.is_ok());
 
@@ -399,7 +668,14 @@ mod tests {
         create_minimal_repo(temp_dir.path(), "standard");
 
         // First add the rule
-        commands::run_add_rule(temp_dir.path(), "test-rule", "Test instruction.", vec![]).unwrap();
+        commands::run_add_rule(
+            temp_dir.path(),
+            "test-rule",
+            "Test instruction.",
+            vec![],
+            false,
+        )
+        .unwrap();
         // Then remove it
         let result = commands::run_remove_rule(temp_dir.path(), "test-rule");
         assert!(result.is_ok());
@@ -419,7 +695,7 @@ mod tests {
         assert!(result.is_ok());
 
         // Add a rule
-        commands::run_add_rule(temp_dir.path(), "my-rule", "A rule.", vec![]).unwrap();
+        commands::run_add_rule(temp_dir.path(), "my-rule", "A rule.", vec![], false).unwrap();
 
         // List rules again
         let result = commands::run_list_rules(temp_dir.path());
@@ -452,7 +728,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         create_minimal_repo(temp_dir.path(), "standard");
 
-        let result = commands::run_sync(temp_dir.path(), false, false);
+        let result = commands::run_sync(temp_dir.path(), false, false, None);
         assert!(result.is_ok());
     }
 
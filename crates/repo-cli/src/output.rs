@@ -0,0 +1,130 @@
+//! Output format for command results
+//!
+//! The global `--output-format` flag selects how commands report. In `text` mode
+//! they print for people. In `json` and `yaml` mode each command prints a
+//! single document serialized from its typed result, and the progress
+//! messages it prints along the way go to stderr so stdout stays parseable.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::error::Result;
+
+/// How command results are printed
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// A JSON document
+    Json,
+    /// A YAML document
+    Yaml,
+}
+
+static FORMAT: AtomicU8 = AtomicU8::new(OutputFormat::Text as u8);
+/// Whether a result document has been printed
+static EMITTED: AtomicBool = AtomicBool::new(false);
+
+/// Select the output format for this run
+pub fn set_format(format: OutputFormat) {
+    FORMAT.store(format as u8, Ordering::Relaxed);
+}
+
+/// The output format selected for this run
+pub fn format() -> OutputFormat {
+    match FORMAT.load(Ordering::Relaxed) {
+        x if x == OutputFormat::Json as u8 => OutputFormat::Json,
+        x if x == OutputFormat::Yaml as u8 => OutputFormat::Yaml,
+        _ => OutputFormat::Text,
+    }
+}
+
+/// Whether results are printed as JSON or YAML
+pub fn is_structured() -> bool {
+    format() != OutputFormat::Text
+}
+
+/// Print `value` as the command's result in the structured format
+///
+/// Returns `false` without printing in text mode, where the caller prints
+/// the result for people instead.
+pub fn emit<T: Serialize + ?Sized>(value: &T) -> Result<bool> {
+    match format() {
+        OutputFormat::Text => return Ok(false),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(value)?),
+    }
+    EMITTED.store(true, Ordering::Relaxed);
+    Ok(true)
+}
+
+/// Finish a successful run
+///
+/// A command with no result to report prints `null`, so structured output
+/// always holds exactly one document.
+pub fn finish() -> Result<()> {
+    if is_structured() && !EMITTED.load(Ordering::Relaxed) {
+        emit(&())?;
+    }
+    Ok(())
+}
+
+/// Print a message line, to stdout in text mode and to stderr otherwise
+pub fn say_line(args: fmt::Arguments) {
+    if is_structured() {
+        eprintln!("{}", args);
+    } else {
+        println!("{}", args);
+    }
+}
+
+/// Print a human-readable message, like `println!`
+///
+/// Messages go to stderr under structured output; see [`say_line`].
+macro_rules! say {
+    () => {
+        $crate::output::say_line(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::output::say_line(format_args!($($arg)*))
+    };
+}
+pub(crate) use say;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Report {
+        name: &'static str,
+        count: usize,
+    }
+
+    #[test]
+    fn test_default_format_is_text() {
+        assert_eq!(format(), OutputFormat::Text);
+        assert!(!is_structured());
+        assert!(
+            !emit(&Report {
+                name: "x",
+                count: 1
+            })
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_yaml_serializes_reports() {
+        let yaml = serde_yaml::to_string(&Report {
+            name: "x",
+            count: 1,
+        })
+        .unwrap();
+        assert_eq!(yaml, "name: x\ncount: 1\n");
+    }
+}
@@ -61,6 +61,8 @@ pub enum BlockLocation {
     /// Append to end of document
     #[default]
     End,
+    /// Insert at start of document, after any byte order mark
+    Start,
     /// After specific section/key
     After(String),
     /// Before specific section/key
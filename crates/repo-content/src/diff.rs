@@ -1,12 +1,24 @@
 //! Semantic diff types and computation
-
-use serde_json::Value;
+//!
+//! Structured documents are compared as value trees: every change carries
+//! the path it happened at (`tools.eslint.enabled`, `servers[2].args[0]`)
+//! with the old and new values. Arrays are aligned by their longest common
+//! subsequence, so inserting or removing an element reports just that
+//! element rather than a modification of everything after it; elements
+//! left unmatched between two aligned ones are compared position by
+//! position. Text documents are compared line by line.
+
+use serde_json::{Value, json};
 use similar::TextDiff;
 use uuid::Uuid;
 
 /// Maximum recursion depth for diff operations
 const MAX_DIFF_DEPTH: usize = 128;
 
+/// Largest array pair (old length × new length) aligned by longest common
+/// subsequence; larger arrays are compared position by position
+const MAX_LCS_CELLS: usize = 1 << 20;
+
 /// Result of comparing two documents semantically
 #[derive(Debug, Clone, PartialEq)]
 pub struct SemanticDiff {
@@ -37,10 +49,31 @@ impl SemanticDiff {
         }
     }
 
+    /// The changes, in document order
+    pub fn changes(&self) -> &[SemanticChange] {
+        &self.changes
+    }
+
+    /// Render the diff as JSON
+    ///
+    /// ```json
+    /// {"equivalent": false, "similarity": 0.9, "changes": [
+    ///   {"kind": "modified", "path": "tools.eslint.enabled", "old": false, "new": true}
+    /// ]}
+    /// ```
+    pub fn to_json(&self) -> Value {
+        json!({
+            "equivalent": self.is_equivalent,
+            "similarity": self.similarity,
+            "changes": self.changes.iter().map(SemanticChange::to_json).collect::<Vec<_>>(),
+        })
+    }
+
     /// Compute a semantic diff between two JSON values
     ///
     /// This recursively compares two JSON values and tracks all changes
-    /// with their paths (e.g., "config.host" for nested keys).
+    /// with their paths (e.g., "config.host" for nested keys, "items[2]"
+    /// for array elements).
     pub fn compute(old: &Value, new: &Value) -> Self {
         let mut changes = Vec::new();
         diff_values(old, new, String::new(), &mut changes);
@@ -132,6 +165,71 @@ pub enum SemanticChange {
     },
 }
 
+/// Whether a change adds, removes or modifies content
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl ChangeKind {
+    /// Lowercase name, as used in [`SemanticDiff::to_json`]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Modified => "modified",
+        }
+    }
+}
+
+impl SemanticChange {
+    /// Whether this change adds, removes or modifies content
+    pub fn kind(&self) -> ChangeKind {
+        match self {
+            Self::Added { .. } | Self::BlockAdded { .. } => ChangeKind::Added,
+            Self::Removed { .. } | Self::BlockRemoved { .. } => ChangeKind::Removed,
+            Self::Modified { .. } | Self::BlockModified { .. } => ChangeKind::Modified,
+        }
+    }
+
+    /// Path of the changed value, `None` for text blocks
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            Self::Added { path, .. } | Self::Removed { path, .. } | Self::Modified { path, .. } => {
+                Some(path)
+            }
+            _ => None,
+        }
+    }
+
+    /// Render the change as JSON: its `kind`, then `path` for values or
+    /// `uuid` for blocks, and the `old` and `new` content that apply
+    pub fn to_json(&self) -> Value {
+        let kind = self.kind().as_str();
+        match self {
+            Self::Added { path, value } => json!({"kind": kind, "path": path, "new": value}),
+            Self::Removed { path, value } => json!({"kind": kind, "path": path, "old": value}),
+            Self::Modified { path, old, new } => {
+                json!({"kind": kind, "path": path, "old": old, "new": new})
+            }
+            Self::BlockAdded { uuid, content } => {
+                json!({"kind": kind, "uuid": uuid.map(|u| u.to_string()), "new": content})
+            }
+            Self::BlockRemoved { uuid, content } => {
+                json!({"kind": kind, "uuid": uuid.map(|u| u.to_string()), "old": content})
+            }
+            Self::BlockModified { uuid, old, new } => json!({
+                "kind": kind,
+                "uuid": uuid.map(|u| u.to_string()),
+                "old": old,
+                "new": new,
+            }),
+        }
+    }
+}
+
 /// Recursively diff two JSON values, collecting changes with path tracking
 fn diff_values(old: &Value, new: &Value, path: String, changes: &mut Vec<SemanticChange>) {
     diff_values_with_depth(old, new, path, changes, 0);
@@ -205,35 +303,9 @@ fn diff_values_with_depth(
             }
         }
 
-        // Both are arrays - compare element by element
+        // Both are arrays - align elements, then compare
         (Value::Array(old_arr), Value::Array(new_arr)) => {
-            let max_len = old_arr.len().max(new_arr.len());
-            for i in 0..max_len {
-                let child_path = if path.is_empty() {
-                    format!("[{}]", i)
-                } else {
-                    format!("{}[{}]", path, i)
-                };
-
-                match (old_arr.get(i), new_arr.get(i)) {
-                    (Some(old_val), Some(new_val)) => {
-                        diff_values_with_depth(old_val, new_val, child_path, changes, depth + 1);
-                    }
-                    (Some(old_val), None) => {
-                        changes.push(SemanticChange::Removed {
-                            path: child_path,
-                            value: old_val.clone(),
-                        });
-                    }
-                    (None, Some(new_val)) => {
-                        changes.push(SemanticChange::Added {
-                            path: child_path,
-                            value: new_val.clone(),
-                        });
-                    }
-                    (None, None) => unreachable!(),
-                }
-            }
+            diff_arrays(old_arr, new_arr, &path, changes, depth);
         }
 
         // Different types or scalar values - compare directly
@@ -249,6 +321,91 @@ fn diff_values_with_depth(
     }
 }
 
+/// Diff two arrays at `path`
+///
+/// Elements in their longest common subsequence are unchanged. Between two
+/// such elements, the remaining old and new elements are paired up in
+/// order and compared; any left over were removed or added. Removed and
+/// modified elements are reported at their old index, added ones at their
+/// new index.
+fn diff_arrays(
+    old: &[Value],
+    new: &[Value],
+    path: &str,
+    changes: &mut Vec<SemanticChange>,
+    depth: usize,
+) {
+    let anchors = if old.len().saturating_mul(new.len()) <= MAX_LCS_CELLS {
+        longest_common_subsequence(old, new)
+    } else {
+        Vec::new()
+    };
+
+    let (mut old_start, mut new_start) = (0, 0);
+    for (old_end, new_end) in anchors.into_iter().chain([(old.len(), new.len())]) {
+        let removed = &old[old_start..old_end];
+        let added = &new[new_start..new_end];
+        for offset in 0..removed.len().max(added.len()) {
+            match (removed.get(offset), added.get(offset)) {
+                (Some(old_val), Some(new_val)) => diff_values_with_depth(
+                    old_val,
+                    new_val,
+                    index_path(path, old_start + offset),
+                    changes,
+                    depth + 1,
+                ),
+                (Some(old_val), None) => changes.push(SemanticChange::Removed {
+                    path: index_path(path, old_start + offset),
+                    value: old_val.clone(),
+                }),
+                (None, Some(new_val)) => changes.push(SemanticChange::Added {
+                    path: index_path(path, new_start + offset),
+                    value: new_val.clone(),
+                }),
+                (None, None) => unreachable!(),
+            }
+        }
+        old_start = old_end + 1;
+        new_start = new_end + 1;
+    }
+}
+
+/// Index pairs of equal elements in a longest common subsequence of `old`
+/// and `new`, in order
+fn longest_common_subsequence(old: &[Value], new: &[Value]) -> Vec<(usize, usize)> {
+    // lengths[i][j]: LCS length of old[i..] and new[j..]
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::with_capacity(lengths[0][0]);
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// Path of element `index` of the array at `path`
+fn index_path(path: &str, index: usize) -> String {
+    format!("{}[{}]", path, index)
+}
+
 /// Compute similarity ratio between two JSON values
 ///
 /// This uses a simple approach: serialize both to strings and use
@@ -349,6 +506,70 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn test_compute_array_insertion_reports_one_change() {
+        let old = json!({"tools": ["claude", "cursor", "zed"]});
+        let new = json!({"tools": ["aider", "claude", "cursor", "zed"]});
+        let diff = SemanticDiff::compute(&old, &new);
+
+        assert_eq!(
+            diff.changes(),
+            [SemanticChange::Added {
+                path: "tools[0]".to_string(),
+                value: json!("aider"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_compute_array_of_tables() {
+        let old = json!({"servers": [
+            {"name": "a", "port": 1},
+            {"name": "b", "port": 2},
+        ]});
+        let new = json!({"servers": [
+            {"name": "b", "port": 3},
+        ]});
+        let diff = SemanticDiff::compute(&old, &new);
+
+        // Neither element is unchanged, so they pair up by position
+        assert_eq!(
+            diff.changes(),
+            [
+                SemanticChange::Modified {
+                    path: "servers[0].name".to_string(),
+                    old: json!("a"),
+                    new: json!("b"),
+                },
+                SemanticChange::Modified {
+                    path: "servers[0].port".to_string(),
+                    old: json!(1),
+                    new: json!(3),
+                },
+                SemanticChange::Removed {
+                    path: "servers[1]".to_string(),
+                    value: json!({"name": "b", "port": 2}),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let old = json!({"tools": {"eslint": {"enabled": false}}, "old": 1});
+        let new = json!({"tools": {"eslint": {"enabled": true}}});
+        let rendered = SemanticDiff::compute(&old, &new).to_json();
+
+        assert_eq!(rendered["equivalent"], false);
+        assert_eq!(
+            rendered["changes"],
+            json!([
+                {"kind": "removed", "path": "old", "old": 1},
+                {"kind": "modified", "path": "tools.eslint.enabled", "old": false, "new": true},
+            ])
+        );
+    }
+
     #[test]
     fn test_compute_text_equivalent() {
         let diff = SemanticDiff::compute_text("hello\nworld", "hello\nworld");
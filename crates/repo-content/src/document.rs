@@ -2,11 +2,12 @@
 
 use crate::block::{BlockLocation, ManagedBlock};
 use crate::diff::SemanticDiff;
-use crate::edit::Edit;
+use crate::edit::{Edit, EditKind};
 use crate::error::{Error, Result};
-use crate::format::{Format, FormatHandler};
+use crate::format::{BOM, Format, FormatHandler, split_bom};
 use crate::handlers::{JsonHandler, MarkdownHandler, PlainTextHandler, TomlHandler, YamlHandler};
-use crate::path::{get_at_path, parse_path, remove_at_path, set_at_path, PathSegment};
+use crate::path::{PathSegment, get_at_path, parse_path, remove_at_path, set_at_path};
+use crate::splice;
 use serde_json::Value;
 use uuid::Uuid;
 
@@ -14,8 +15,11 @@ use uuid::Uuid;
 pub struct Document {
     /// Original source as provided to parse/parse_as (for is_modified tracking)
     original_source: String,
-    /// Current source (may differ from original after edits)
+    /// Current source (may differ from original after edits), without any
+    /// leading byte order mark
     source: String,
+    /// Whether rendered output starts with a byte order mark
+    bom: bool,
     format: Format,
     handler: Box<dyn FormatHandler>,
 }
@@ -37,12 +41,16 @@ impl Document {
             Format::Yaml => Box::new(YamlHandler::new()),
         };
 
+        // Blocks and paths work on the content; the mark is put back on render
+        let (bom, content) = split_bom(source);
+
         // Verify it parses
-        let _ = handler.parse(source)?;
+        let _ = handler.parse(content)?;
 
         Ok(Self {
             original_source: source.to_string(),
-            source: source.to_string(),
+            source: content.to_string(),
+            bom: !bom.is_empty(),
             format,
             handler,
         })
@@ -53,11 +61,25 @@ impl Document {
         self.format
     }
 
-    /// Get current source
+    /// Get current source, without any leading byte order mark
     pub fn source(&self) -> &str {
         &self.source
     }
 
+    /// Whether [`render`](Self::render) starts the output with a byte order
+    /// mark
+    ///
+    /// True after parsing a source that had one.
+    pub fn has_bom(&self) -> bool {
+        self.bom
+    }
+
+    /// Set whether [`render`](Self::render) starts the output with a byte
+    /// order mark, for tools that require or reject one
+    pub fn set_bom(&mut self, bom: bool) {
+        self.bom = bom;
+    }
+
     /// Find all managed blocks
     pub fn find_blocks(&self) -> Vec<ManagedBlock> {
         self.handler.find_blocks(&self.source)
@@ -156,9 +178,10 @@ impl Document {
     ///
     /// For text formats (PlainText, Markdown), returns the source as-is.
     /// For structured formats (TOML, JSON, YAML), re-parses and re-renders
-    /// to produce canonical output.
+    /// to produce canonical output. Either way the output starts with a byte
+    /// order mark if [`has_bom`](Self::has_bom).
     pub fn render(&self) -> String {
-        match self.format {
+        let rendered = match self.format {
             Format::PlainText | Format::Markdown => self.source.clone(),
             _ => {
                 if let Ok(parsed) = self.handler.parse(&self.source) {
@@ -169,12 +192,18 @@ impl Document {
                     self.source.clone()
                 }
             }
+        };
+        if self.bom {
+            format!("{BOM}{rendered}")
+        } else {
+            rendered
         }
     }
 
     /// Check if document has been modified from its original source.
     pub fn is_modified(&self) -> bool {
-        self.source != self.original_source
+        let (bom, content) = split_bom(&self.original_source);
+        self.source != content || self.bom == bom.is_empty()
     }
 
     /// Get normalized representation for semantic comparison
@@ -329,6 +358,120 @@ impl Document {
         Ok(Edit::path_remove(path, 0..old_source.len(), old_source))
     }
 
+    /// Set the value at `path`, leaving the rest of the document untouched.
+    ///
+    /// Unlike [`set_path`](Self::set_path), nothing outside the changed value
+    /// is re-rendered, and a missing last key is added to its parent object
+    /// or table. JSON is edited by splicing the new value into the source, so
+    /// key order, indentation and other values survive byte for byte; the
+    /// new value itself is pretty-printed with the document's indent. TOML is
+    /// edited with toml_edit, which also keeps comments.
+    ///
+    /// Returns the applied `Edit`, spanning only the bytes that changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathNotFound` if the parent of `path` doesn't exist.
+    /// Returns `PathSetFailed` for formats other than JSON and TOML.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use repo_content::{Document, Format};
+    /// use serde_json::json;
+    ///
+    /// let source = "{\n    \"b\": 1,\n    \"a\": 2\n}";
+    /// let mut doc = Document::parse_as(source, Format::Json).unwrap();
+    /// let edit = doc.set_value("c", json!(true)).unwrap();
+    /// assert_eq!(doc.source(), "{\n    \"b\": 1,\n    \"a\": 2,\n    \"c\": true\n}");
+    /// assert_eq!(edit.apply(source), doc.source());
+    /// ```
+    pub fn set_value(&mut self, path: &str, value: Value) -> Result<Edit> {
+        let segments = parse_path(path);
+        let new_source = match self.format {
+            Format::Json => splice::set(&self.source, &segments, &value),
+            Format::Toml => self.toml_upsert(&segments, &value),
+            _ => Err(self.unsupported_edit(path)),
+        }
+        .map_err(|e| with_path(e, path))?;
+
+        let (span, new_content) = changed_span(&self.source, &new_source);
+        let edit = Edit::path_set(path, span.clone(), &self.source[span], new_content);
+        self.source = new_source;
+        Ok(edit)
+    }
+
+    /// Remove the value at `path`, leaving the rest of the document untouched.
+    ///
+    /// The format-preserving counterpart of [`remove_path`](Self::remove_path);
+    /// see [`set_value`](Self::set_value). A JSON entry is removed together
+    /// with its separating comma.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathNotFound` if the path doesn't exist.
+    /// Returns `PathSetFailed` for formats other than JSON and TOML.
+    pub fn remove_value(&mut self, path: &str) -> Result<Edit> {
+        let segments = parse_path(path);
+        let new_source = match self.format {
+            Format::Json => splice::remove(&self.source, &segments),
+            Format::Toml => self.toml_remove_path(&segments),
+            _ => Err(self.unsupported_edit(path)),
+        }
+        .map_err(|e| with_path(e, path))?;
+
+        let (span, new_content) = changed_span(&self.source, &new_source);
+        let mut edit = Edit::path_remove(path, span.clone(), &self.source[span]);
+        if !new_content.is_empty() {
+            edit.kind = EditKind::PathSet {
+                path: path.to_string(),
+            };
+            edit.new_content = new_content.to_string();
+        }
+        self.source = new_source;
+        Ok(edit)
+    }
+
+    fn unsupported_edit(&self, path: &str) -> Error {
+        Error::PathSetFailed {
+            format: format!("{:?}", self.format),
+            path: path.to_string(),
+            reason: "Format-preserving edits support JSON and TOML only".to_string(),
+        }
+    }
+
+    /// Set a value in a TOML document using toml_edit, adding a missing last
+    /// key to its parent table.
+    fn toml_upsert(&self, segments: &[PathSegment], value: &Value) -> Result<String> {
+        let mut doc: toml_edit::DocumentMut = self
+            .source
+            .parse()
+            .map_err(|e: toml_edit::TomlError| Error::parse("TOML", e.to_string()))?;
+        let new_value = json_value_to_toml_edit(value)?;
+
+        if let Some(existing) =
+            toml_edit_get(doc.as_item_mut(), segments).and_then(toml_edit::Item::as_value_mut)
+        {
+            // Keep the comment and spacing around the old value
+            let decor = existing.decor().clone();
+            *existing = new_value;
+            *existing.decor_mut() = decor;
+            return Ok(doc.to_string());
+        }
+
+        let not_found = || Error::PathNotFound {
+            path: String::new(),
+        };
+        let Some((PathSegment::Key(key), parents)) = segments.split_last() else {
+            return Err(not_found());
+        };
+        let parent = toml_edit_get(doc.as_item_mut(), parents)
+            .and_then(toml_edit::Item::as_table_like_mut)
+            .ok_or_else(not_found)?;
+        parent.insert(key, toml_edit::Item::Value(new_value));
+        Ok(doc.to_string())
+    }
+
     /// Set a value at the given path in a TOML document using toml_edit,
     /// preserving comments, key ordering, and formatting.
     fn toml_set_path(&self, segments: &[PathSegment], value: &Value) -> Result<String> {
@@ -406,6 +549,46 @@ impl Document {
     }
 }
 
+/// The `toml_edit::Item` at `segments`, if there is one
+fn toml_edit_get<'a>(
+    item: &'a mut toml_edit::Item,
+    segments: &[PathSegment],
+) -> Option<&'a mut toml_edit::Item> {
+    segments
+        .iter()
+        .try_fold(item, |item, segment| match segment {
+            PathSegment::Key(key) => item.get_mut(key.as_str()),
+            PathSegment::Index(idx) => item.get_mut(*idx),
+        })
+}
+
+/// Report `path` in full on a path error raised for one of its segments
+fn with_path(error: Error, path: &str) -> Error {
+    match error {
+        Error::PathNotFound { .. } => Error::PathNotFound {
+            path: path.to_string(),
+        },
+        other => other,
+    }
+}
+
+/// The byte range of `old` that differs from `new`, and its replacement
+fn changed_span<'a>(old: &str, new: &'a str) -> (std::ops::Range<usize>, &'a str) {
+    let prefix = old
+        .char_indices()
+        .zip(new.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(old.len().min(new.len()), |((i, _), _)| i);
+    let suffix: usize = old[prefix..]
+        .chars()
+        .rev()
+        .zip(new[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+    (prefix..old.len() - suffix, &new[prefix..new.len() - suffix])
+}
+
 /// Convert a serde_json::Value to a toml::Value
 fn json_to_toml(json: &Value) -> Result<toml::Value> {
     match json {
@@ -528,15 +711,15 @@ fn toml_edit_set(
         match first {
             PathSegment::Key(key) => match item {
                 toml_edit::Item::Table(t) => {
-                    let child = t.get_mut(key).ok_or_else(|| Error::PathNotFound {
-                        path: key.clone(),
-                    })?;
+                    let child = t
+                        .get_mut(key)
+                        .ok_or_else(|| Error::PathNotFound { path: key.clone() })?;
                     toml_edit_set(child, rest, value)
                 }
                 toml_edit::Item::Value(toml_edit::Value::InlineTable(t)) => {
-                    let child = t.get_mut(key).ok_or_else(|| Error::PathNotFound {
-                        path: key.clone(),
-                    })?;
+                    let child = t
+                        .get_mut(key)
+                        .ok_or_else(|| Error::PathNotFound { path: key.clone() })?;
                     toml_edit_set_value(child, rest, value)
                 }
                 _ => Err(Error::PathSetFailed {
@@ -621,9 +804,9 @@ fn toml_edit_set_value(
         match first {
             PathSegment::Key(key) => {
                 if let toml_edit::Value::InlineTable(t) = val {
-                    let child = t.get_mut(key).ok_or_else(|| Error::PathNotFound {
-                        path: key.clone(),
-                    })?;
+                    let child = t
+                        .get_mut(key)
+                        .ok_or_else(|| Error::PathNotFound { path: key.clone() })?;
                     toml_edit_set_value(child, rest, new_value)
                 } else {
                     Err(Error::PathSetFailed {
@@ -685,9 +868,9 @@ fn toml_edit_set_in_table(
         let (first, rest) = segments.split_first().unwrap();
         match first {
             PathSegment::Key(key) => {
-                let child = table.get_mut(key).ok_or_else(|| Error::PathNotFound {
-                    path: key.clone(),
-                })?;
+                let child = table
+                    .get_mut(key)
+                    .ok_or_else(|| Error::PathNotFound { path: key.clone() })?;
                 toml_edit_set(child, rest, value)
             }
             PathSegment::Index(idx) => Err(Error::PathSetFailed {
@@ -728,9 +911,7 @@ fn toml_edit_remove(item: &mut toml_edit::Item, segments: &[PathSegment]) -> Res
                         Err(Error::PathNotFound { path: key.clone() })
                     }
                 }
-                _ => Err(Error::PathNotFound {
-                    path: key.clone(),
-                }),
+                _ => Err(Error::PathNotFound { path: key.clone() }),
             },
             PathSegment::Index(idx) => match item {
                 toml_edit::Item::Value(toml_edit::Value::Array(arr)) => {
@@ -763,20 +944,18 @@ fn toml_edit_remove(item: &mut toml_edit::Item, segments: &[PathSegment]) -> Res
         match first {
             PathSegment::Key(key) => match item {
                 toml_edit::Item::Table(t) => {
-                    let child = t.get_mut(key).ok_or_else(|| Error::PathNotFound {
-                        path: key.clone(),
-                    })?;
+                    let child = t
+                        .get_mut(key)
+                        .ok_or_else(|| Error::PathNotFound { path: key.clone() })?;
                     toml_edit_remove(child, rest)
                 }
                 toml_edit::Item::Value(toml_edit::Value::InlineTable(t)) => {
-                    let child = t.get_mut(key).ok_or_else(|| Error::PathNotFound {
-                        path: key.clone(),
-                    })?;
+                    let child = t
+                        .get_mut(key)
+                        .ok_or_else(|| Error::PathNotFound { path: key.clone() })?;
                     toml_edit_remove_value(child, rest)
                 }
-                _ => Err(Error::PathNotFound {
-                    path: key.clone(),
-                }),
+                _ => Err(Error::PathNotFound { path: key.clone() }),
             },
             PathSegment::Index(idx) => match item {
                 toml_edit::Item::ArrayOfTables(arr) => {
@@ -843,9 +1022,9 @@ fn toml_edit_remove_value(val: &mut toml_edit::Value, segments: &[PathSegment])
         match first {
             PathSegment::Key(key) => {
                 if let toml_edit::Value::InlineTable(t) = val {
-                    let child = t.get_mut(key).ok_or_else(|| Error::PathNotFound {
-                        path: key.clone(),
-                    })?;
+                    let child = t
+                        .get_mut(key)
+                        .ok_or_else(|| Error::PathNotFound { path: key.clone() })?;
                     toml_edit_remove_value(child, rest)
                 } else {
                     Err(Error::PathNotFound { path: key.clone() })
@@ -893,9 +1072,9 @@ fn toml_edit_remove_in_table(table: &mut toml_edit::Table, segments: &[PathSegme
         let (first, rest) = segments.split_first().unwrap();
         match first {
             PathSegment::Key(key) => {
-                let child = table.get_mut(key).ok_or_else(|| Error::PathNotFound {
-                    path: key.clone(),
-                })?;
+                let child = table
+                    .get_mut(key)
+                    .ok_or_else(|| Error::PathNotFound { path: key.clone() })?;
                 toml_edit_remove(child, rest)
             }
             PathSegment::Index(idx) => Err(Error::PathNotFound {
@@ -6,6 +6,21 @@ use crate::error::Result;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// The byte order mark some editors, mostly on Windows, put at the start of
+/// UTF-8 files
+pub const BOM: &str = "\u{FEFF}";
+
+/// Split a leading byte order mark off `source`
+///
+/// Returns the mark, empty if there is none, and the text after it. Handlers
+/// parse the text and put the mark back in front of anything they re-render.
+pub fn split_bom(source: &str) -> (&str, &str) {
+    match source.strip_prefix(BOM) {
+        Some(rest) => (BOM, rest),
+        None => ("", source),
+    }
+}
+
 /// Supported document formats
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Format {
@@ -31,7 +46,7 @@ impl Format {
 
     /// Detect format from content heuristics
     pub fn from_content(content: &str) -> Self {
-        let trimmed = content.trim_start();
+        let trimmed = split_bom(content).1.trim_start();
 
         // JSON starts with { or [
         if trimmed.starts_with('{') || trimmed.starts_with('[') {
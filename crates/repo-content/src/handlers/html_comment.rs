@@ -7,7 +7,7 @@ use uuid::Uuid;
 use crate::block::{BlockLocation, ManagedBlock};
 use crate::edit::{Edit, EditKind};
 use crate::error::{Error, Result};
-use crate::format::CommentStyle;
+use crate::format::{CommentStyle, split_bom};
 
 /// Pattern to match block start markers and capture the UUID
 pub static BLOCK_START_PATTERN: LazyLock<Regex> =
@@ -71,9 +71,12 @@ pub fn insert_block(
         style.format_end(uuid)
     );
 
+    // Nothing goes in front of a byte order mark
+    let bom = split_bom(source).0.len();
     let position = match location {
         BlockLocation::End => source.len(),
-        BlockLocation::Offset(pos) => pos.min(source.len()),
+        BlockLocation::Start => bom,
+        BlockLocation::Offset(pos) => pos.clamp(bom, source.len()),
         BlockLocation::After(ref marker) => source
             .find(marker)
             .map(|p| p + marker.len())
@@ -83,7 +86,7 @@ pub fn insert_block(
 
     let mut result = String::with_capacity(source.len() + block_text.len());
     result.push_str(&source[..position]);
-    if position > 0 && !source[..position].ends_with('\n') {
+    if position > bom && !source[..position].ends_with('\n') {
         result.push('\n');
     }
     result.push_str(&block_text);
@@ -1,4 +1,7 @@
 //! JSON format handler
+//!
+//! Sources are read as JSONC, so comments and trailing commas are accepted,
+//! and managed blocks are spliced in without disturbing them.
 
 use serde_json::{Map, Value};
 use uuid::Uuid;
@@ -7,6 +10,8 @@ use crate::block::{BlockLocation, ManagedBlock};
 use crate::edit::{Edit, EditKind};
 use crate::error::{Error, Result};
 use crate::format::{Format, FormatHandler};
+use crate::jsonc;
+use crate::path::PathSegment;
 
 const MANAGED_KEY: &str = "_repo_managed";
 
@@ -38,18 +43,25 @@ impl JsonHandler {
     }
 }
 
+/// Path of a managed block's value
+fn block_path(uuid: Uuid) -> [PathSegment; 2] {
+    [
+        PathSegment::Key(MANAGED_KEY.into()),
+        PathSegment::Key(uuid.to_string()),
+    ]
+}
+
 impl FormatHandler for JsonHandler {
     fn format(&self) -> Format {
         Format::Json
     }
 
     fn parse(&self, source: &str) -> Result<Box<dyn std::any::Any + Send + Sync>> {
-        let value: Value = serde_json::from_str(source)?;
-        Ok(Box::new(value))
+        Ok(Box::new(jsonc::parse(source)?))
     }
 
     fn find_blocks(&self, source: &str) -> Vec<ManagedBlock> {
-        let Ok(value) = serde_json::from_str::<Value>(source) else {
+        let Ok(value) = jsonc::parse(source) else {
             return Vec::new();
         };
 
@@ -75,22 +87,25 @@ impl FormatHandler for JsonHandler {
         content: &str,
         _location: BlockLocation,
     ) -> Result<(String, Edit)> {
-        let mut value: Value = serde_json::from_str(source)?;
+        let value = jsonc::parse(source)?;
+        let root = value
+            .as_object()
+            .ok_or_else(|| Error::parse("JSON", "root must be object"))?;
 
         let content_value: Value =
             serde_json::from_str(content).unwrap_or_else(|_| Value::String(content.to_string()));
 
-        let managed = value
-            .as_object_mut()
-            .ok_or_else(|| Error::parse("JSON", "root must be object"))?
-            .entry(MANAGED_KEY)
-            .or_insert_with(|| Value::Object(Map::new()));
-
-        if let Some(obj) = managed.as_object_mut() {
-            obj.insert(uuid.to_string(), content_value);
-        }
-
-        let new_source = serde_json::to_string_pretty(&value)?;
+        let new_source = if root.get(MANAGED_KEY).is_some_and(Value::is_object) {
+            jsonc::set(source, &block_path(uuid), &content_value)?
+        } else {
+            let mut managed = Map::new();
+            managed.insert(uuid.to_string(), content_value);
+            jsonc::set(
+                source,
+                &[PathSegment::Key(MANAGED_KEY.into())],
+                &Value::Object(managed),
+            )?
+        };
 
         let edit = Edit {
             kind: EditKind::BlockInsert { uuid },
@@ -103,23 +118,21 @@ impl FormatHandler for JsonHandler {
     }
 
     fn update_block(&self, source: &str, uuid: Uuid, content: &str) -> Result<(String, Edit)> {
-        let mut value: Value = serde_json::from_str(source)?;
+        let value = jsonc::parse(source)?;
 
         let content_value: Value =
             serde_json::from_str(content).unwrap_or_else(|_| Value::String(content.to_string()));
 
         let managed = value
-            .get_mut(MANAGED_KEY)
-            .and_then(Value::as_object_mut)
+            .get(MANAGED_KEY)
+            .and_then(Value::as_object)
             .ok_or(Error::BlockNotFound { uuid })?;
 
         if !managed.contains_key(&uuid.to_string()) {
             return Err(Error::BlockNotFound { uuid });
         }
 
-        managed.insert(uuid.to_string(), content_value);
-
-        let new_source = serde_json::to_string_pretty(&value)?;
+        let new_source = jsonc::set(source, &block_path(uuid), &content_value)?;
 
         let edit = Edit {
             kind: EditKind::BlockUpdate { uuid },
@@ -132,23 +145,23 @@ impl FormatHandler for JsonHandler {
     }
 
     fn remove_block(&self, source: &str, uuid: Uuid) -> Result<(String, Edit)> {
-        let mut value: Value = serde_json::from_str(source)?;
+        let value = jsonc::parse(source)?;
 
         let managed = value
-            .get_mut(MANAGED_KEY)
-            .and_then(Value::as_object_mut)
+            .get(MANAGED_KEY)
+            .and_then(Value::as_object)
             .ok_or(Error::BlockNotFound { uuid })?;
 
-        if managed.remove(&uuid.to_string()).is_none() {
+        if !managed.contains_key(&uuid.to_string()) {
             return Err(Error::BlockNotFound { uuid });
         }
 
-        // Remove _repo_managed if empty
-        if managed.is_empty() {
-            value.as_object_mut().unwrap().remove(MANAGED_KEY);
-        }
-
-        let new_source = serde_json::to_string_pretty(&value)?;
+        // Remove _repo_managed if this was its last block
+        let new_source = if managed.len() == 1 {
+            jsonc::remove(source, &[PathSegment::Key(MANAGED_KEY.into())])?
+        } else {
+            jsonc::remove(source, &block_path(uuid))?
+        };
 
         let edit = Edit {
             kind: EditKind::BlockRemove { uuid },
@@ -161,7 +174,7 @@ impl FormatHandler for JsonHandler {
     }
 
     fn normalize(&self, source: &str) -> Result<serde_json::Value> {
-        let mut value: Value = serde_json::from_str(source)?;
+        let mut value = jsonc::parse(source)?;
 
         // Remove _repo_managed for comparison
         if let Some(obj) = value.as_object_mut() {
@@ -296,6 +309,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_json_blocks_keep_comments() {
+        let handler = JsonHandler::new();
+        let uuid = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
+        let source = "{\n  // Font\n  \"editor.fontSize\": 14, /* px */\n}\n";
+        assert_eq!(
+            handler.normalize(source).unwrap(),
+            json!({"editor.fontSize": 14})
+        );
+
+        let (inserted, _) = handler
+            .insert_block(source, uuid, r#"{"key": "value"}"#, BlockLocation::End)
+            .unwrap();
+        assert!(inserted.contains("// Font") && inserted.contains("/* px */"));
+        assert_eq!(handler.find_blocks(&inserted).len(), 1);
+
+        let (updated, _) = handler
+            .update_block(&inserted, uuid, r#"{"key": "new"}"#)
+            .unwrap();
+        assert!(updated.contains("\"new\"") && updated.contains("// Font"));
+
+        let (removed, _) = handler.remove_block(&updated, uuid).unwrap();
+        assert_eq!(removed, source);
+    }
+
     #[test]
     fn test_json_multiple_blocks() {
         let handler = JsonHandler::new();
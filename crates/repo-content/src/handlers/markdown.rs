@@ -12,7 +12,7 @@ use super::html_comment;
 use crate::block::{BlockLocation, ManagedBlock};
 use crate::edit::Edit;
 use crate::error::{Error, Result};
-use crate::format::{Format, FormatHandler};
+use crate::format::{Format, FormatHandler, split_bom};
 
 /// Pattern to match multiple consecutive blank lines (markdown-specific normalization)
 static MULTIPLE_BLANK_LINES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\n{3,}").unwrap());
@@ -34,7 +34,7 @@ impl FormatHandler for MarkdownHandler {
     }
 
     fn parse(&self, source: &str) -> Result<Box<dyn std::any::Any + Send + Sync>> {
-        Ok(Box::new(source.to_string()))
+        Ok(Box::new(split_bom(source).1.to_string()))
     }
 
     fn find_blocks(&self, source: &str) -> Vec<ManagedBlock> {
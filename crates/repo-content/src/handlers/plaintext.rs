@@ -6,7 +6,7 @@ use super::html_comment;
 use crate::block::{BlockLocation, ManagedBlock};
 use crate::edit::Edit;
 use crate::error::{Error, Result};
-use crate::format::{Format, FormatHandler};
+use crate::format::{Format, FormatHandler, split_bom};
 
 /// Handler for plain text files with HTML comment markers
 #[derive(Debug, Default)]
@@ -24,7 +24,7 @@ impl FormatHandler for PlainTextHandler {
     }
 
     fn parse(&self, source: &str) -> Result<Box<dyn std::any::Any + Send + Sync>> {
-        Ok(Box::new(source.to_string()))
+        Ok(Box::new(split_bom(source).1.to_string()))
     }
 
     fn find_blocks(&self, source: &str) -> Vec<ManagedBlock> {
@@ -7,7 +7,7 @@ use super::hash_comment;
 use crate::block::{BlockLocation, ManagedBlock};
 use crate::edit::Edit;
 use crate::error::{Error, Result};
-use crate::format::{Format, FormatHandler};
+use crate::format::{Format, FormatHandler, split_bom};
 
 /// Handler for TOML files using toml_edit for format preservation
 #[derive(Debug, Default)]
@@ -25,7 +25,8 @@ impl FormatHandler for TomlHandler {
     }
 
     fn parse(&self, source: &str) -> Result<Box<dyn std::any::Any + Send + Sync>> {
-        let doc: DocumentMut = source
+        let doc: DocumentMut = split_bom(source)
+            .1
             .parse()
             .map_err(|e: toml_edit::TomlError| Error::parse("TOML", e.to_string()))?;
         Ok(Box::new(doc))
@@ -54,7 +55,8 @@ impl FormatHandler for TomlHandler {
     }
 
     fn normalize(&self, source: &str) -> Result<serde_json::Value> {
-        let doc: DocumentMut = source
+        let doc: DocumentMut = split_bom(source)
+            .1
             .parse()
             .map_err(|e: toml_edit::TomlError| Error::parse("TOML", e.to_string()))?;
 
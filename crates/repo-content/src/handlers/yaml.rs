@@ -7,7 +7,7 @@ use super::hash_comment;
 use crate::block::{BlockLocation, ManagedBlock};
 use crate::edit::Edit;
 use crate::error::{Error, Result};
-use crate::format::{Format, FormatHandler};
+use crate::format::{Format, FormatHandler, split_bom};
 
 /// Handler for YAML files using serde_yaml
 #[derive(Debug, Default)]
@@ -25,8 +25,8 @@ impl FormatHandler for YamlHandler {
     }
 
     fn parse(&self, source: &str) -> Result<Box<dyn std::any::Any + Send + Sync>> {
-        let value: YamlValue =
-            serde_yaml::from_str(source).map_err(|e| Error::parse("YAML", e.to_string()))?;
+        let value: YamlValue = serde_yaml::from_str(split_bom(source).1)
+            .map_err(|e| Error::parse("YAML", e.to_string()))?;
         Ok(Box::new(value))
     }
 
@@ -53,8 +53,8 @@ impl FormatHandler for YamlHandler {
     }
 
     fn normalize(&self, source: &str) -> Result<serde_json::Value> {
-        let yaml_value: YamlValue =
-            serde_yaml::from_str(source).map_err(|e| Error::parse("YAML", e.to_string()))?;
+        let yaml_value: YamlValue = serde_yaml::from_str(split_bom(source).1)
+            .map_err(|e| Error::parse("YAML", e.to_string()))?;
 
         fn yaml_to_json_sorted(value: &YamlValue) -> serde_json::Value {
             match value {
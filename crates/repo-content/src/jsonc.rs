@@ -0,0 +1,134 @@
+//! JSON with comments
+//!
+//! Editor settings files such as VSCode's `settings.json` are JSONC: JSON
+//! that may contain `//` and `/* */` comments and trailing commas. Reading
+//! blanks those out with spaces, so byte offsets into the blanked text are
+//! offsets into the original and edits can be spliced back into it with
+//! every comment intact.
+
+use serde_json::Value;
+
+use crate::error::Result;
+use crate::format::split_bom;
+use crate::path::PathSegment;
+use crate::splice;
+
+/// Blank out comments and trailing commas, leaving plain JSON
+///
+/// The result has the same length as `source` and keeps its line breaks.
+pub fn strip(source: &str) -> String {
+    blank(source, true)
+}
+
+/// Parse JSONC source into a value
+///
+/// A leading byte order mark is ignored.
+pub fn parse(source: &str) -> Result<Value> {
+    Ok(serde_json::from_str(&strip(split_bom(source).1))?)
+}
+
+/// Set the value at `path`, leaving the rest of the source untouched
+///
+/// A missing last key is added after the last member of its object.
+pub fn set(source: &str, path: &[PathSegment], value: &Value) -> Result<String> {
+    let (bom, body) = split_bom(source);
+    Ok(format!("{bom}{}", splice::set(body, path, value)?))
+}
+
+/// Remove the value at `path`, leaving the rest of the source untouched
+pub fn remove(source: &str, path: &[PathSegment]) -> Result<String> {
+    let (bom, body) = split_bom(source);
+    Ok(format!("{bom}{}", splice::remove(body, path)?))
+}
+
+/// Replace comments, and optionally trailing commas, with spaces
+pub(crate) fn blank(source: &str, trailing_commas: bool) -> String {
+    let bytes = source.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut pos = 0;
+    // The last comma, while only whitespace and comments follow it
+    let mut comma = None;
+
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'"' => {
+                comma = None;
+                pos += 1;
+                while pos < bytes.len() && bytes[pos] != b'"' {
+                    pos += if bytes[pos] == b'\\' { 2 } else { 1 };
+                }
+                pos += 1;
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'/') => {
+                let end = source[pos..].find('\n').map_or(bytes.len(), |i| pos + i);
+                clear(&mut out, pos..end);
+                pos = end;
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+                let end = source[pos + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |i| pos + i + 4);
+                clear(&mut out, pos..end);
+                pos = end;
+            }
+            b',' => {
+                comma = Some(pos);
+                pos += 1;
+            }
+            b'}' | b']' => {
+                if let (true, Some(at)) = (trailing_commas, comma.take()) {
+                    out[at] = b' ';
+                }
+                pos += 1;
+            }
+            byte => {
+                if !byte.is_ascii_whitespace() {
+                    comma = None;
+                }
+                pos += 1;
+            }
+        }
+    }
+
+    String::from_utf8(out).expect("only whole characters are blanked")
+}
+
+fn clear(out: &mut [u8], span: std::ops::Range<usize>) {
+    for byte in &mut out[span] {
+        if *byte != b'\n' && *byte != b'\r' {
+            *byte = b' ';
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SETTINGS: &str = "{\n    // Editor\n    \"editor.fontSize\": 14, /* px */\n    \"url\": \"http://x\",\n    \"tabs\": [1, 2,],\n}\n";
+
+    #[test]
+    fn test_strip_keeps_offsets() {
+        let stripped = strip(SETTINGS);
+        assert_eq!(stripped.len(), SETTINGS.len());
+        assert_eq!(stripped.lines().count(), SETTINGS.lines().count());
+        assert!(!stripped.contains("Editor") && !stripped.contains("px"));
+        assert!(stripped.contains("\"http://x\""));
+    }
+
+    #[test]
+    fn test_parse_comments_and_trailing_commas() {
+        assert_eq!(
+            parse(SETTINGS).unwrap(),
+            json!({"editor.fontSize": 14, "url": "http://x", "tabs": [1, 2]})
+        );
+        assert_eq!(parse("\u{FEFF}{} // empty").unwrap(), json!({}));
+        assert!(parse("{\"a\": }").is_err());
+    }
+
+    #[test]
+    fn test_blank_non_ascii_comment() {
+        assert_eq!(parse("{\"a\": 1 /* größe */}").unwrap(), json!({"a": 1}));
+    }
+}
@@ -56,10 +56,12 @@ pub mod edit;
 pub mod error;
 pub mod format;
 pub mod handlers;
+pub mod jsonc;
 pub mod path;
+mod splice;
 
 pub use block::{BlockLocation, ManagedBlock};
-pub use diff::{SemanticChange, SemanticDiff};
+pub use diff::{ChangeKind, SemanticChange, SemanticDiff};
 pub use document::Document;
 pub use edit::{Edit, EditKind};
 pub use error::{Error, Result};
@@ -47,6 +47,7 @@ pub enum PathSegment {
 /// - Dot-separated keys: `config.database.host`
 /// - Array indexing: `items[0].name`
 /// - Combined: `config.servers[0].host`
+/// - Quoted keys, for keys containing dots: `["editor.fontSize"]`
 ///
 /// # Examples
 ///
@@ -94,7 +95,12 @@ pub fn parse_path(path: &str) -> Vec<PathSegment> {
                     }
                     index_str.push(ch);
                 }
-                if let Ok(index) = index_str.parse::<usize>() {
+                let quoted = ['"', '\'']
+                    .into_iter()
+                    .find_map(|quote| index_str.strip_prefix(quote)?.strip_suffix(quote));
+                if let Some(key) = quoted {
+                    segments.push(PathSegment::Key(key.to_string()));
+                } else if let Ok(index) = index_str.parse::<usize>() {
                     segments.push(PathSegment::Index(index));
                 }
             }
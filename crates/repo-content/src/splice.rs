@@ -0,0 +1,490 @@
+//! In-place value edits to JSON source
+//!
+//! [`Document::set_value`](crate::Document::set_value) and
+//! [`Document::remove_value`](crate::Document::remove_value) change one value
+//! without re-serializing the document. The source is scanned for the byte
+//! spans of its values, and the change is spliced in, so key order,
+//! indentation, comments and every untouched value stay exactly as they were.
+
+use std::ops::Range;
+
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::ser::{PrettyFormatter, Serializer};
+
+use crate::error::{Error, Result};
+use crate::jsonc;
+use crate::path::PathSegment;
+
+/// A JSON value and the bytes it spans in the source
+#[derive(Debug)]
+struct Node {
+    span: Range<usize>,
+    kind: NodeKind,
+}
+
+#[derive(Debug)]
+enum NodeKind {
+    Object(Vec<Member>),
+    Array(Vec<Node>),
+    Scalar,
+}
+
+/// An object member; `start` is where its key begins
+#[derive(Debug)]
+struct Member {
+    key: String,
+    start: usize,
+    value: Node,
+}
+
+impl Node {
+    fn child(&self, segment: &PathSegment) -> Option<&Node> {
+        match (&self.kind, segment) {
+            (NodeKind::Object(members), PathSegment::Key(key)) => members
+                .iter()
+                .rev()
+                .find(|m| &m.key == key)
+                .map(|m| &m.value),
+            (NodeKind::Array(items), PathSegment::Index(index)) => items.get(*index),
+            _ => None,
+        }
+    }
+
+    /// Byte ranges of the entries of an object or array, keys included
+    fn entries(&self) -> Vec<Range<usize>> {
+        match &self.kind {
+            NodeKind::Object(members) => {
+                members.iter().map(|m| m.start..m.value.span.end).collect()
+            }
+            NodeKind::Array(items) => items.iter().map(|i| i.span.clone()).collect(),
+            NodeKind::Scalar => Vec::new(),
+        }
+    }
+}
+
+/// Set the value at `segments` in `source`, returning the new source
+///
+/// A missing last key is added after the last member of its object.
+pub(crate) fn set(source: &str, segments: &[PathSegment], value: &Value) -> Result<String> {
+    let code = jsonc::blank(source, false);
+    let root = Scanner::new(&code).document()?;
+    let (last, parents) = segments.split_last().ok_or_else(not_found)?;
+    let parent = find(&root, parents)?;
+    let unit = indent_unit(source);
+
+    if let Some(node) = parent.child(last) {
+        let indent = line_indent(source, node.span.start);
+        return Ok(splice(
+            source,
+            node.span.clone(),
+            &render(value, indent, unit)?,
+        ));
+    }
+
+    let (PathSegment::Key(key), NodeKind::Object(members)) = (last, &parent.kind) else {
+        return Err(not_found());
+    };
+    let key = serde_json::to_string(key)?;
+    let end = match members.last() {
+        // One-line objects stay on one line
+        Some(last) if !source[parent.span.start..last.start].contains('\n') => {
+            let at = last.value.span.end;
+            let entry = format!(", {}: {}", key, serde_json::to_string(value)?);
+            return Ok(splice(source, at..at, &entry));
+        }
+        Some(last) => last.value.span.end,
+        None => {
+            let outer = line_indent(source, parent.span.start);
+            let indent = format!("{outer}{unit}");
+            let entry = format!("\n{indent}{key}: {}", render(value, &indent, unit)?);
+            let inside = parent.span.start + 1..parent.span.end - 1;
+            if code[inside.clone()] != source[inside.clone()] {
+                // Keep the comments of an otherwise empty object
+                return Ok(splice(source, inside.start..inside.start, &entry));
+            }
+            return Ok(splice(source, inside, &format!("{entry}\n{outer}")));
+        }
+    };
+
+    // The new entry goes on its own line after the last one, past any
+    // trailing comma or comment on that line
+    let indent = line_indent(source, members.last().map_or(0, |m| m.start));
+    let entry = format!("\n{indent}{key}: {}", render(value, indent, unit)?);
+    let rest = skip_whitespace(&code, end);
+    let trailing_comma = code.as_bytes().get(rest) == Some(&b',');
+    let after = if trailing_comma { rest + 1 } else { end };
+    let line_end = code[after..].find('\n').map_or(code.len(), |i| after + i);
+    let at = if code[after..line_end].trim().is_empty() {
+        line_end
+    } else {
+        after
+    };
+    Ok(if trailing_comma {
+        splice(source, at..at, &format!("{entry},"))
+    } else {
+        let source = splice(source, at..at, &entry);
+        splice(&source, end..end, ",")
+    })
+}
+
+/// Remove the value at `segments` from `source`, returning the new source
+///
+/// The entry is removed with its separating comma; the last entry of an
+/// object or array leaves it empty.
+pub(crate) fn remove(source: &str, segments: &[PathSegment]) -> Result<String> {
+    let code = jsonc::blank(source, false);
+    let root = Scanner::new(&code).document()?;
+    let (last, parents) = segments.split_last().ok_or_else(not_found)?;
+    let parent = find(&root, parents)?;
+    let target = parent.child(last).ok_or_else(not_found)?;
+
+    let entries = parent.entries();
+    let index = entries
+        .iter()
+        .position(|entry| entry.end == target.span.end)
+        .ok_or_else(not_found)?;
+    let inside = parent.span.start + 1..parent.span.end - 1;
+    let span = if code[inside.clone()] != source[inside.clone()] {
+        // Comments around the entry stay; take only the entry, its own
+        // comma, and its line if nothing else is left on it
+        let rest = skip_whitespace(&code, target.span.end);
+        let end = if code.as_bytes().get(rest) == Some(&b',') {
+            rest + 1
+        } else {
+            target.span.end
+        };
+        whole_lines(source, entries[index].start..end)
+    } else if entries.len() == 1 {
+        inside
+    } else if index + 1 < entries.len() {
+        entries[index].start..entries[index + 1].start
+    } else {
+        entries[index - 1].end..entries[index].end
+    };
+    Ok(splice(source, span, ""))
+}
+
+/// Widen `span` to the lines it is on if nothing else shares them
+///
+/// A span that starts its line but is followed by a comment also takes the
+/// spaces before the comment, so the comment keeps the line's indent.
+fn whole_lines(source: &str, span: Range<usize>) -> Range<usize> {
+    let line_start = source[..span.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[span.end..]
+        .find('\n')
+        .map_or(source.len(), |i| span.end + i + 1);
+    if !source[line_start..span.start].trim().is_empty() {
+        span
+    } else if source[span.end..line_end].trim().is_empty() {
+        line_start..line_end
+    } else {
+        let rest = &source[span.end..];
+        span.start..span.end + rest.len() - rest.trim_start_matches([' ', '\t']).len()
+    }
+}
+
+fn skip_whitespace(code: &str, position: usize) -> usize {
+    code[position..]
+        .find(|c: char| !c.is_ascii_whitespace())
+        .map_or(code.len(), |i| position + i)
+}
+
+fn find<'n>(root: &'n Node, segments: &[PathSegment]) -> Result<&'n Node> {
+    segments.iter().try_fold(root, |node, segment| {
+        node.child(segment).ok_or_else(not_found)
+    })
+}
+
+/// Callers report the full path; the scanner only knows a segment is missing
+fn not_found() -> Error {
+    Error::PathNotFound {
+        path: String::new(),
+    }
+}
+
+fn splice(source: &str, span: Range<usize>, text: &str) -> String {
+    let mut result = String::with_capacity(source.len() + text.len());
+    result.push_str(&source[..span.start]);
+    result.push_str(text);
+    result.push_str(&source[span.end..]);
+    result
+}
+
+/// Pretty-print `value` to sit on a line indented by `indent`
+fn render(value: &Value, indent: &str, unit: &str) -> Result<String> {
+    let mut out = Vec::new();
+    let formatter = PrettyFormatter::with_indent(unit.as_bytes());
+    value.serialize(&mut Serializer::with_formatter(&mut out, formatter))?;
+    let text = String::from_utf8(out).expect("serde_json writes UTF-8");
+    Ok(text.replace('\n', &format!("\n{indent}")))
+}
+
+/// Leading whitespace of the line containing `position`
+fn line_indent(source: &str, position: usize) -> &str {
+    let start = source[..position].rfind('\n').map_or(0, |i| i + 1);
+    let line = &source[start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// The document's indentation step: the indent of its first indented line
+fn indent_unit(source: &str) -> &str {
+    source
+        .lines()
+        .skip(1)
+        .map(|line| &line[..line.len() - line.trim_start_matches([' ', '\t']).len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ")
+}
+
+/// Scans JSON for value spans
+///
+/// The source has already been validated by `serde_json`, so this only has
+/// to find where values start and end. Comments must be blanked out first;
+/// trailing commas are accepted.
+struct Scanner<'a> {
+    source: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            bytes: source.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn document(mut self) -> Result<Node> {
+        let node = self.value()?;
+        self.skip_whitespace();
+        if self.pos != self.bytes.len() {
+            return Err(self.error("trailing characters"));
+        }
+        Ok(node)
+    }
+
+    fn value(&mut self) -> Result<Node> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let kind = match self.bytes.get(self.pos) {
+            Some(b'{') => {
+                self.pos += 1;
+                let mut members = Vec::new();
+                while !self.close(b'}', members.is_empty())? {
+                    let start = self.pos;
+                    let key = self.string()?;
+                    self.skip_whitespace();
+                    self.expect(b':')?;
+                    let value = self.value()?;
+                    members.push(Member { key, start, value });
+                }
+                NodeKind::Object(members)
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                while !self.close(b']', items.is_empty())? {
+                    items.push(self.value()?);
+                }
+                NodeKind::Array(items)
+            }
+            Some(b'"') => {
+                self.string()?;
+                NodeKind::Scalar
+            }
+            Some(_) => {
+                while self.pos < self.bytes.len()
+                    && !matches!(self.bytes[self.pos], b',' | b']' | b'}')
+                    && !self.bytes[self.pos].is_ascii_whitespace()
+                {
+                    self.pos += 1;
+                }
+                NodeKind::Scalar
+            }
+            None => return Err(self.error("unexpected end of input")),
+        };
+        Ok(Node {
+            span: start..self.pos,
+            kind,
+        })
+    }
+
+    /// Step to the start of the next entry, returning `true` instead at the
+    /// closing bracket
+    fn close(&mut self, bracket: u8, first: bool) -> Result<bool> {
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&bracket) {
+            self.pos += 1;
+            return Ok(true);
+        }
+        if !first {
+            self.expect(b',')?;
+            self.skip_whitespace();
+            if self.bytes.get(self.pos) == Some(&bracket) {
+                self.pos += 1;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let start = self.pos;
+        self.expect(b'"')?;
+        loop {
+            match self.bytes.get(self.pos) {
+                Some(b'\\') => self.pos += 2,
+                Some(b'"') => break,
+                Some(_) => self.pos += 1,
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+        self.pos += 1;
+        Ok(serde_json::from_str(&self.source[start..self.pos])?)
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        if self.bytes.get(self.pos) != Some(&byte) {
+            return Err(self.error(format!("expected '{}'", byte as char)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::parse("JSON", format!("{} at byte {}", message.into(), self.pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::parse_path;
+    use serde_json::json;
+
+    const SETTINGS: &str = "{\n    \"editor.fontSize\": 14,\n    \"files.exclude\": {\n        \"**/.git\": true\n    },\n    \"tabs\": [1, 2, 3]\n}\n";
+    const FONT_SIZE: &str = r#"["editor.fontSize"]"#;
+
+    fn set_at(source: &str, path: &str, value: Value) -> String {
+        set(source, &parse_path(path), &value).unwrap()
+    }
+
+    fn remove_at(source: &str, path: &str) -> String {
+        remove(source, &parse_path(path)).unwrap()
+    }
+
+    #[test]
+    fn test_set_replaces_only_the_value() {
+        assert_eq!(
+            set_at(SETTINGS, FONT_SIZE, json!(16)),
+            SETTINGS.replace("14", "16")
+        );
+        assert_eq!(
+            set_at(SETTINGS, "tabs[1]", json!("two")),
+            SETTINGS.replace("[1, 2, 3]", "[1, \"two\", 3]")
+        );
+    }
+
+    #[test]
+    fn test_set_adds_key_with_document_indent() {
+        assert_eq!(
+            set_at(SETTINGS, r#"["files.exclude"].dist"#, json!({"a": 1})),
+            "{\n    \"editor.fontSize\": 14,\n    \"files.exclude\": {\n        \"**/.git\": true,\n        \"dist\": {\n            \"a\": 1\n        }\n    },\n    \"tabs\": [1, 2, 3]\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_set_into_empty_and_inline_objects() {
+        assert_eq!(
+            set_at("{\n  \"a\": {}\n}", "a.b", json!(1)),
+            "{\n  \"a\": {\n    \"b\": 1\n  }\n}"
+        );
+        assert_eq!(
+            set_at("{\"a\": 1}", "b", json!([true])),
+            "{\"a\": 1, \"b\": [true]}"
+        );
+    }
+
+    #[test]
+    fn test_set_missing_parent_fails() {
+        assert!(set(SETTINGS, &parse_path("missing.key"), &json!(1)).is_err());
+        assert!(set(SETTINGS, &parse_path("tabs[5]"), &json!(1)).is_err());
+    }
+
+    #[test]
+    fn test_remove_entries() {
+        assert_eq!(
+            remove_at(SETTINGS, FONT_SIZE),
+            SETTINGS.replace("\"editor.fontSize\": 14,\n    ", "")
+        );
+        assert_eq!(
+            remove_at(SETTINGS, "tabs"),
+            SETTINGS.replace(",\n    \"tabs\": [1, 2, 3]", "")
+        );
+        assert_eq!(
+            remove_at(SETTINGS, r#"["files.exclude"]["**/.git"]"#),
+            SETTINGS.replace("{\n        \"**/.git\": true\n    }", "{}")
+        );
+        assert_eq!(
+            remove_at(SETTINGS, "tabs[0]"),
+            SETTINGS.replace("[1, 2, 3]", "[2, 3]")
+        );
+    }
+
+    const COMMENTED: &str = "{\n    // Editor\n    \"editor.fontSize\": 14, // px\n    /* Tabs */\n    \"tabs\": [1, 2, 3] // last\n}\n";
+
+    #[test]
+    fn test_set_keeps_comments() {
+        assert_eq!(
+            set_at(COMMENTED, FONT_SIZE, json!(16)),
+            COMMENTED.replace("14", "16")
+        );
+        assert_eq!(
+            set_at(COMMENTED, "added", json!(true)),
+            COMMENTED.replace("] // last\n", "], // last\n    \"added\": true\n")
+        );
+        let trailing = "{\n  \"a\": 1, // one\n}";
+        assert_eq!(
+            set_at(trailing, "b", json!(2)),
+            "{\n  \"a\": 1, // one\n  \"b\": 2,\n}"
+        );
+        assert_eq!(
+            set_at("{\n  // nothing yet\n}", "a", json!(1)),
+            "{\n  \"a\": 1\n  // nothing yet\n}"
+        );
+    }
+
+    #[test]
+    fn test_remove_keeps_comments() {
+        assert_eq!(
+            remove_at(COMMENTED, "tabs"),
+            "{\n    // Editor\n    \"editor.fontSize\": 14, // px\n    /* Tabs */\n    // last\n}\n"
+        );
+        assert_eq!(
+            remove_at(COMMENTED, FONT_SIZE),
+            "{\n    // Editor\n    // px\n    /* Tabs */\n    \"tabs\": [1, 2, 3] // last\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_keys_with_escapes() {
+        let source = r#"{"say \"hi\"": 1, "b": 2}"#;
+        assert_eq!(
+            set_at(source, "b", json!(3)),
+            r#"{"say \"hi\"": 1, "b": 3}"#
+        );
+    }
+}
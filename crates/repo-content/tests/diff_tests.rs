@@ -2,7 +2,7 @@
 //!
 //! Category: component
 
-use repo_content::{Document, SemanticChange};
+use repo_content::{ChangeKind, Document, Format, SemanticChange};
 use serde_json::json;
 
 #[test]
@@ -138,3 +138,37 @@ fn test_diff_nested_changes() {
         SemanticChange::Modified { path, .. } if path == "config.host"
     )));
 }
+
+#[test]
+fn test_diff_toml_nested_tables() {
+    let doc1 = Document::parse_as(
+        "[tools.eslint]\nenabled = false\nargs = [\"--fix\"]\n",
+        Format::Toml,
+    )
+    .unwrap();
+    let doc2 = Document::parse_as(
+        "[tools.eslint]\nenabled = true\nargs = [\"--cache\", \"--fix\"]\n\n[tools.prettier]\nenabled = true\n",
+        Format::Toml,
+    )
+    .unwrap();
+
+    let diff = doc1.diff(&doc2);
+
+    let changes: Vec<_> = diff
+        .changes()
+        .iter()
+        .map(|c| (c.kind(), c.path().unwrap()))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            (ChangeKind::Added, "tools.eslint.args[0]"),
+            (ChangeKind::Modified, "tools.eslint.enabled"),
+            (ChangeKind::Added, "tools.prettier"),
+        ]
+    );
+    assert_eq!(
+        diff.to_json()["changes"][1],
+        json!({"kind": "modified", "path": "tools.eslint.enabled", "old": false, "new": true})
+    );
+}
@@ -140,3 +140,40 @@ fn test_document_is_modified() {
         .unwrap();
     assert!(doc.is_modified());
 }
+
+#[test]
+fn test_document_parse_json_with_bom() {
+    let doc = Document::parse("\u{FEFF}{\"name\": \"test\"}").unwrap();
+    assert_eq!(doc.format(), Format::Json);
+    assert!(doc.has_bom());
+    assert_eq!(doc.source(), "{\"name\": \"test\"}");
+    assert_eq!(doc.get_path("name"), Some(serde_json::json!("test")));
+    assert!(!doc.is_modified());
+}
+
+#[test]
+fn test_document_insert_at_start_after_bom() {
+    let mut doc = Document::parse_as("\u{FEFF}# Rules\n", Format::Markdown).unwrap();
+    let uuid = Uuid::new_v4();
+    doc.insert_block(uuid, "managed", BlockLocation::Start)
+        .unwrap();
+
+    let rendered = doc.render();
+    assert!(rendered.starts_with(&format!("\u{FEFF}<!-- repo:block:{uuid} -->\nmanaged\n")));
+    assert!(rendered.ends_with("# Rules\n"));
+    assert_eq!(rendered.matches('\u{FEFF}').count(), 1);
+}
+
+#[test]
+fn test_document_render_bom_policy() {
+    let mut doc = Document::parse_as("\u{FEFF}{\"a\": 1}", Format::Json).unwrap();
+    assert!(doc.render().starts_with('\u{FEFF}'));
+
+    doc.set_bom(false);
+    assert!(doc.is_modified());
+    assert_eq!(doc.render(), "{\n  \"a\": 1\n}");
+
+    let mut doc = Document::parse_as("[a]\nx = 1\n", Format::Toml).unwrap();
+    doc.set_bom(true);
+    assert_eq!(doc.render(), "\u{FEFF}[a]\nx = 1\n");
+}
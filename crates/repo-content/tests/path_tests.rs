@@ -241,3 +241,91 @@ cc = "1.0"
     );
     assert_eq!(doc.get_path("build-dependencies.cc"), Some(json!("1.0")));
 }
+
+const SETTINGS_JSON: &str = r#"{
+    "workbench.colorTheme": "Default Dark+",
+    "editor.tabSize": 4,
+    "files.exclude": {
+        "**/.git": true,
+        "**/node_modules": true
+    },
+    "python.analysis.extraPaths": ["./src"]
+}
+"#;
+
+#[test]
+fn test_set_value_keeps_untouched_json_layout() {
+    let mut doc = Document::parse_as(SETTINGS_JSON, Format::Json).unwrap();
+
+    let edit = doc.set_value(r#"["editor.tabSize"]"#, json!(2)).unwrap();
+    assert_eq!(doc.source(), SETTINGS_JSON.replace(": 4,", ": 2,"));
+    assert_eq!(edit.old_content, "4");
+    assert_eq!(edit.new_content, "2");
+    assert_eq!(&SETTINGS_JSON[edit.span.clone()], "4");
+
+    doc.set_value(r#"["files.exclude"].dist"#, json!(true))
+        .unwrap();
+    doc.set_value(r#"["editor.formatOnSave"]"#, json!(true))
+        .unwrap();
+    assert_eq!(
+        doc.source(),
+        r#"{
+    "workbench.colorTheme": "Default Dark+",
+    "editor.tabSize": 2,
+    "files.exclude": {
+        "**/.git": true,
+        "**/node_modules": true,
+        "dist": true
+    },
+    "python.analysis.extraPaths": ["./src"],
+    "editor.formatOnSave": true
+}
+"#
+    );
+}
+
+#[test]
+fn test_remove_value_keeps_untouched_json_layout() {
+    let mut doc = Document::parse_as(SETTINGS_JSON, Format::Json).unwrap();
+    let edit = doc.remove_value(r#"["editor.tabSize"]"#).unwrap();
+    assert_eq!(
+        doc.source(),
+        SETTINGS_JSON.replace("    \"editor.tabSize\": 4,\n", "")
+    );
+    assert_eq!(edit.apply(SETTINGS_JSON), doc.source());
+    assert_eq!(edit.inverse().apply(doc.source()), SETTINGS_JSON);
+
+    assert!(matches!(
+        doc.remove_value("missing"),
+        Err(repo_content::Error::PathNotFound { path }) if path == "missing"
+    ));
+}
+
+#[test]
+fn test_set_value_toml_keeps_comments() {
+    let source = "# Project settings\n[package]\nname = \"test\" # the crate name\n\n[features]\n";
+    let mut doc = Document::parse_as(source, Format::Toml).unwrap();
+
+    doc.set_value("package.version", json!("1.0")).unwrap();
+    doc.set_value("package.name", json!("renamed")).unwrap();
+    let edit = doc.remove_value("package.version").unwrap();
+    assert_eq!(edit.new_content, "");
+    assert_eq!(
+        doc.source(),
+        "# Project settings\n[package]\nname = \"renamed\" # the crate name\n\n[features]\n"
+    );
+}
+
+#[test]
+fn test_set_value_rejects_text_formats() {
+    let mut doc = Document::parse_as("# Notes\n", Format::Markdown).unwrap();
+    assert!(doc.set_value("title", json!("x")).is_err());
+}
+
+#[test]
+fn test_quoted_path_keys() {
+    let doc = Document::parse(r#"{"files.exclude": {"dist": true}}"#).unwrap();
+    assert_eq!(doc.get_path(r#"["files.exclude"].dist"#), Some(json!(true)));
+    assert_eq!(doc.get_path("['files.exclude']['dist']"), Some(json!(true)));
+    assert_eq!(doc.get_path("files.exclude"), None);
+}
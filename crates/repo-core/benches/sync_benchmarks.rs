@@ -0,0 +1,146 @@
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use repo_core::{Mode, SyncEngine};
+use repo_fs::NormalizedPath;
+use repo_meta::schema::{RuleContent, RuleDefinition, RuleMeta, RuleTargets, Severity};
+use repo_test_utils::fixture::{GeneratedRule, generate_rules, large_repo};
+use repo_test_utils::repo::TestRepo;
+use repo_tools::{RuleTranslator, ToolRegistry};
+
+/// Rule counts each benchmark runs with
+const RULE_COUNTS: [usize; 3] = [10, 100, 500];
+
+/// Tool sets each benchmark runs with: a few common tools, and every
+/// builtin tool but Cline, whose config file collides with its rules
+/// directory
+fn tool_sets() -> Vec<(&'static str, Vec<String>)> {
+    let registry = ToolRegistry::with_builtins();
+    let all = registry
+        .list()
+        .into_iter()
+        .filter(|t| *t != "cline")
+        .map(String::from)
+        .collect();
+    let common = ["claude", "cursor", "copilot", "windsurf"];
+    vec![
+        ("4_tools", common.map(String::from).to_vec()),
+        ("all_tools", all),
+    ]
+}
+
+/// A synced fixture with `rules` rules and `tools`
+fn synced_repo(rules: usize, tools: &[String]) -> (TestRepo, SyncEngine) {
+    let repo = fixture(rules, tools);
+    let engine = SyncEngine::new(NormalizedPath::new(repo.root()), Mode::Standard).unwrap();
+    engine.sync().unwrap();
+    (repo, engine)
+}
+
+fn fixture(rules: usize, tools: &[String]) -> TestRepo {
+    let tools: Vec<&str> = tools.iter().map(String::as_str).collect();
+    large_repo(rules, &tools)
+}
+
+fn to_definition(rule: &GeneratedRule) -> RuleDefinition {
+    RuleDefinition {
+        meta: RuleMeta {
+            id: rule.id.clone(),
+            severity: Severity::Mandatory,
+            tags: rule.tags.clone(),
+            requires: vec![],
+        },
+        content: RuleContent {
+            instruction: rule.content.clone(),
+        },
+        examples: None,
+        targets: (!rule.paths.is_empty()).then(|| RuleTargets {
+            file_patterns: rule.paths.clone(),
+        }),
+    }
+}
+
+fn benchmark_sync(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sync");
+    group.sample_size(10);
+
+    for (tools_name, tools) in tool_sets() {
+        for rules in RULE_COUNTS {
+            group.bench_with_input(BenchmarkId::new(tools_name, rules), &rules, |b, &rules| {
+                b.iter_with_setup(
+                    || fixture(rules, &tools),
+                    |repo| {
+                        let engine =
+                            SyncEngine::new(NormalizedPath::new(repo.root()), Mode::Standard)
+                                .unwrap();
+                        engine.sync().unwrap();
+                        repo
+                    },
+                );
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn benchmark_resync(c: &mut Criterion) {
+    let mut group = c.benchmark_group("resync");
+    group.sample_size(10);
+
+    for (tools_name, tools) in tool_sets() {
+        for rules in RULE_COUNTS {
+            let (_repo, engine) = synced_repo(rules, &tools);
+
+            group.bench_with_input(BenchmarkId::new(tools_name, rules), &rules, |b, _| {
+                b.iter(|| engine.sync().unwrap());
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn benchmark_check(c: &mut Criterion) {
+    let mut group = c.benchmark_group("check");
+
+    for (tools_name, tools) in tool_sets() {
+        for rules in RULE_COUNTS {
+            let (_repo, engine) = synced_repo(rules, &tools);
+
+            group.bench_with_input(BenchmarkId::new(tools_name, rules), &rules, |b, _| {
+                b.iter(|| engine.check().unwrap());
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn benchmark_translate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("translate");
+    let registry = ToolRegistry::with_builtins();
+    let definitions: Vec<_> = registry.iter().map(|r| r.to_definition()).collect();
+
+    for rules in RULE_COUNTS {
+        let rule_definitions: Vec<RuleDefinition> =
+            generate_rules(rules).iter().map(to_definition).collect();
+
+        group.bench_with_input(BenchmarkId::new("all_tools", rules), &rules, |b, _| {
+            b.iter(|| {
+                for tool in &definitions {
+                    RuleTranslator::translate(tool, &rule_definitions);
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_sync,
+    benchmark_resync,
+    benchmark_check,
+    benchmark_translate
+);
+criterion_main!(benches);
@@ -0,0 +1,459 @@
+//! Adoption of hand-written AI config files
+//!
+//! Repositories often start with hand-maintained `CLAUDE.md`, `.cursorrules`
+//! and similar files. Adoption splits those files into candidate rules (one
+//! per `## ` section) and moves them into the rule registry, after which
+//! `repo sync` regenerates the files from the registry.
+//!
+//! For many repositories at once, [`plan`] clusters the candidates of each
+//! repository by content hash. Rules present in more than one repository
+//! form the shared rule set; the rest are per-repository residuals. Both are
+//! rendered as AGENTS.md bundles (see [`render_bundle`]) that [`adopt`] can
+//! import before adopting whatever the bundle did not cover.
+//!
+//! Similarity is exact: two candidates cluster only if their trimmed bodies
+//! have the same checksum.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::Result;
+use crate::governance::{import_agents_md, validate_rule_id};
+use crate::rules::RuleRegistry;
+
+/// Hand-written files inspected by [`analyze`], relative to the repository root
+pub const HAND_WRITTEN_FILES: &[&str] = &[
+    "CLAUDE.md",
+    "AGENTS.md",
+    "GEMINI.md",
+    ".cursorrules",
+    ".windsurfrules",
+    ".clinerules",
+    ".github/copilot-instructions.md",
+];
+
+/// Marker identifying files already generated from the registry
+const MANAGED_MARKER: &str = "<!-- repo:block:";
+
+/// Maximum rule ID length accepted by [`validate_rule_id`]
+const MAX_ID_LEN: usize = 64;
+
+/// A section of a hand-written file proposed as a rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CandidateRule {
+    /// Proposed rule ID, derived from the heading
+    pub id: String,
+    /// Section heading, or `None` for text before the first heading
+    pub heading: Option<String>,
+    /// File the section was found in, relative to the repository root
+    pub source: String,
+    /// Trimmed section body
+    pub content: String,
+    /// Checksum of `content`, used to cluster identical rules
+    pub content_hash: String,
+    /// Size of `content` in bytes
+    pub size: usize,
+}
+
+/// Candidate rules found in one repository
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AdoptAnalysis {
+    /// Hand-written files that were analyzed
+    pub files: Vec<String>,
+    /// Files skipped because they are already generated from the registry
+    pub managed_files: Vec<String>,
+    /// Candidate rules, in file order
+    pub candidates: Vec<CandidateRule>,
+}
+
+impl AdoptAnalysis {
+    /// Candidates with distinct content, keeping the first of each
+    pub fn unique_candidates(&self) -> Vec<&CandidateRule> {
+        let mut seen = BTreeSet::new();
+        self.candidates
+            .iter()
+            .filter(|c| seen.insert(c.content_hash.as_str()))
+            .collect()
+    }
+}
+
+/// Split the hand-written AI config files of a repository into candidate rules
+///
+/// Each `## ` heading starts a candidate; text before the first heading
+/// (other than `# ` titles) becomes an `overview` candidate. Files that
+/// already contain managed blocks are listed in
+/// [`AdoptAnalysis::managed_files`] and not split.
+pub fn analyze(root: &Path) -> Result<AdoptAnalysis> {
+    let mut analysis = AdoptAnalysis::default();
+    let mut ids = BTreeSet::new();
+
+    for file in HAND_WRITTEN_FILES {
+        let path = root.join(file);
+        if !path.is_file() {
+            continue;
+        }
+        let text = std::fs::read_to_string(&path)?;
+        if text.contains(MANAGED_MARKER) {
+            analysis.managed_files.push(file.to_string());
+            continue;
+        }
+
+        analysis.files.push(file.to_string());
+        for (heading, body) in split_sections(&text) {
+            let base = heading.as_deref().map_or("overview".to_string(), slugify);
+            let content_hash = repo_fs::checksum::compute_content_checksum(&body);
+
+            // Identical sections repeated across files share an ID
+            let existing = analysis
+                .candidates
+                .iter()
+                .find(|c| c.content_hash == content_hash)
+                .map(|c| c.id.clone());
+            let id = existing.unwrap_or_else(|| unique_id(&base, &mut ids));
+
+            analysis.candidates.push(CandidateRule {
+                id,
+                heading,
+                source: file.to_string(),
+                size: body.len(),
+                content: body,
+                content_hash,
+            });
+        }
+    }
+
+    Ok(analysis)
+}
+
+/// Split markdown into `(heading, body)` sections at `## ` headings
+fn split_sections(text: &str) -> Vec<(Option<String>, String)> {
+    let mut sections = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut body = String::new();
+
+    let mut flush = |heading: Option<String>, body: &str| {
+        let body = body.trim();
+        if !body.is_empty() {
+            sections.push((heading, body.to_string()));
+        }
+    };
+
+    for line in text.lines() {
+        if let Some(next) = line.strip_prefix("## ") {
+            flush(heading.take(), &body);
+            heading = Some(next.trim().to_string());
+            body.clear();
+        } else if heading.is_none() && line.starts_with("# ") {
+            // Document title
+        } else {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    flush(heading, &body);
+
+    sections
+}
+
+/// Turn a heading into a valid rule ID
+fn slugify(heading: &str) -> String {
+    let mut slug = String::new();
+    for c in heading.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug: String = slug
+        .trim_matches('-')
+        .chars()
+        .take(MAX_ID_LEN - 4)
+        .collect();
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "rule".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// `base`, or `base-N` for the first N that is not yet in `used`
+fn unique_id(base: &str, used: &mut BTreeSet<String>) -> String {
+    let mut id = base.to_string();
+    let mut n = 2;
+    while used.contains(&id) {
+        id = format!("{}-{}", base, n);
+        n += 1;
+    }
+    used.insert(id.clone());
+    id
+}
+
+/// A rule in an adoption plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedRule {
+    /// Rule ID
+    pub id: String,
+    /// Rule content
+    pub content: String,
+    /// Checksum of `content`
+    pub content_hash: String,
+}
+
+/// A candidate rule's place in a cluster
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClusterMember {
+    /// Repository name
+    pub repo: String,
+    /// Candidate rule ID within that repository
+    pub rule_id: String,
+}
+
+/// Candidate rules with identical content across repositories
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleCluster {
+    /// ID of the planned rule for this cluster
+    pub id: String,
+    /// Shared content checksum
+    pub content_hash: String,
+    /// Whether the cluster spans more than one repository
+    pub shared: bool,
+    /// Candidates in the cluster, one per repository
+    pub members: Vec<ClusterMember>,
+}
+
+/// A cross-repository adoption plan produced by [`plan`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AdoptPlan {
+    /// Rules found in more than one repository
+    pub shared: Vec<PlannedRule>,
+    /// Rules found in one repository only, by repository name
+    pub residual: BTreeMap<String, Vec<PlannedRule>>,
+    /// All clusters, shared ones first
+    pub clusters: Vec<RuleCluster>,
+}
+
+impl AdoptPlan {
+    /// The shared rule set as an AGENTS.md bundle
+    pub fn shared_bundle(&self) -> String {
+        render_bundle(&self.shared)
+    }
+
+    /// A repository's residual rules as an AGENTS.md bundle
+    pub fn residual_bundle(&self, repo: &str) -> Option<String> {
+        self.residual.get(repo).map(|rules| render_bundle(rules))
+    }
+}
+
+/// Cluster the candidates of several repositories into an adoption plan
+///
+/// `repos` pairs a repository name with its analysis. Candidates cluster by
+/// content hash; clusters spanning two or more repositories become shared
+/// rules, named after their first member. Residual rule IDs never collide
+/// with shared ones.
+pub fn plan(repos: &[(String, AdoptAnalysis)]) -> AdoptPlan {
+    // content hash -> (first candidate, members)
+    let mut clusters: BTreeMap<&str, (&CandidateRule, Vec<ClusterMember>)> = BTreeMap::new();
+    let mut order: Vec<&str> = Vec::new();
+    for (repo, analysis) in repos {
+        for candidate in analysis.unique_candidates() {
+            let entry = clusters
+                .entry(candidate.content_hash.as_str())
+                .or_insert_with(|| {
+                    order.push(candidate.content_hash.as_str());
+                    (candidate, Vec::new())
+                });
+            entry.1.push(ClusterMember {
+                repo: repo.clone(),
+                rule_id: candidate.id.clone(),
+            });
+        }
+    }
+
+    let mut plan = AdoptPlan::default();
+    let mut shared_ids = BTreeSet::new();
+    let mut residual_ids: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let (shared, residual): (Vec<&str>, Vec<&str>) = order
+        .into_iter()
+        .partition(|hash| clusters[hash].1.len() > 1);
+
+    for hash in shared {
+        let (first, members) = &clusters[hash];
+        let id = unique_id(&first.id, &mut shared_ids);
+        plan.shared.push(planned(&id, first));
+        plan.clusters.push(RuleCluster {
+            id,
+            content_hash: hash.to_string(),
+            shared: true,
+            members: members.clone(),
+        });
+    }
+
+    for hash in residual {
+        let (first, members) = &clusters[hash];
+        let repo = &members[0].repo;
+        let used = residual_ids
+            .entry(repo.clone())
+            .or_insert_with(|| shared_ids.clone());
+        let id = unique_id(&first.id, used);
+        plan.residual
+            .entry(repo.clone())
+            .or_default()
+            .push(planned(&id, first));
+        plan.clusters.push(RuleCluster {
+            id,
+            content_hash: hash.to_string(),
+            shared: false,
+            members: members.clone(),
+        });
+    }
+
+    plan
+}
+
+fn planned(id: &str, candidate: &CandidateRule) -> PlannedRule {
+    PlannedRule {
+        id: id.to_string(),
+        content: candidate.content.clone(),
+        content_hash: candidate.content_hash.clone(),
+    }
+}
+
+/// Render rules as an AGENTS.md bundle
+///
+/// Uses the format of `repo rules export`, so bundles round-trip through
+/// [`import_agents_md`].
+pub fn render_bundle(rules: &[PlannedRule]) -> String {
+    let mut output = String::from("# AGENTS.md\n\n<!-- Generated by repo ws adopt-plan -->\n\n");
+    for rule in rules {
+        output.push_str(&format!("## {}\n\n{}\n\n", rule.id, rule.content));
+    }
+    output
+}
+
+/// Outcome of [`adopt`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AdoptReport {
+    /// Rule IDs imported from the bundle
+    pub imported: Vec<String>,
+    /// Rule IDs adopted from the repository's own files
+    pub adopted: Vec<String>,
+    /// Candidate IDs skipped because the registry already had their content
+    pub skipped: Vec<String>,
+}
+
+/// Move a repository's hand-written rules into its rule registry
+///
+/// Rules from `bundle` (an AGENTS.md bundle such as a shared rule set) are
+/// imported first, replacing registry rules with the same ID. Candidates
+/// found by [`analyze`] are then added unless the registry already holds a
+/// rule with the same content. Run `repo sync` afterwards to regenerate the
+/// tool files from the registry.
+pub fn adopt(root: &Path, bundle: Option<&str>) -> Result<AdoptReport> {
+    let analysis = analyze(root)?;
+    let rules_dir = root.join(".repository").join("rules");
+    std::fs::create_dir_all(&rules_dir)?;
+    let mut registry = RuleRegistry::load_or_create(rules_dir.join("registry.toml"))?;
+    let mut report = AdoptReport::default();
+
+    for (id, content) in bundle.map(import_agents_md).unwrap_or_default() {
+        validate_rule_id(&id).map_err(|e| crate::Error::SyncError {
+            message: format!("Invalid rule ID '{}' in bundle: {}", id, e),
+        })?;
+        match registry.get_rule_by_id(&id).map(|r| r.uuid) {
+            Some(uuid) => registry.update_rule(uuid, &content)?,
+            None => {
+                registry.add_rule(&id, &content, vec![])?;
+            }
+        }
+        report.imported.push(id);
+    }
+
+    let mut known: BTreeSet<String> = registry
+        .all_rules()
+        .iter()
+        .map(|r| r.content_hash.clone())
+        .collect();
+    let mut ids: BTreeSet<String> = registry.all_rules().iter().map(|r| r.id.clone()).collect();
+
+    for candidate in analysis.unique_candidates() {
+        if !known.insert(candidate.content_hash.clone()) {
+            report.skipped.push(candidate.id.clone());
+            continue;
+        }
+        let id = unique_id(&candidate.id, &mut ids);
+        registry.add_rule(&id, &candidate.content, vec![])?;
+        report.adopted.push(id);
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_split_sections() {
+        let text = "# Project\n\nIntro text.\n\n## Code Style\n\nUse 4 spaces.\n\n## Testing\nRun tests.\n";
+        let sections = split_sections(text);
+        assert_eq!(
+            sections,
+            vec![
+                (None, "Intro text.".to_string()),
+                (Some("Code Style".to_string()), "Use 4 spaces.".to_string()),
+                (Some("Testing".to_string()), "Run tests.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Code Style"), "code-style");
+        assert_eq!(slugify("  C++ / Rust!  "), "c-rust");
+        assert_eq!(slugify("***"), "rule");
+        assert!(validate_rule_id(&slugify(&"x".repeat(100))).is_ok());
+    }
+
+    #[test]
+    fn test_analyze_shares_ids_across_files_and_skips_managed() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("CLAUDE.md"), "## Style\n\nUse tabs.\n").unwrap();
+        std::fs::write(
+            temp.path().join(".cursorrules"),
+            "## Style\n\nUse tabs.\n\n## Style\n\nWrap at 80.\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("GEMINI.md"),
+            "<!-- repo:block:x -->\nmanaged\n<!-- /repo:block:x -->\n",
+        )
+        .unwrap();
+
+        let analysis = analyze(temp.path()).unwrap();
+        assert_eq!(analysis.files, vec!["CLAUDE.md", ".cursorrules"]);
+        assert_eq!(analysis.managed_files, vec!["GEMINI.md"]);
+        let ids: Vec<&str> = analysis.candidates.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["style", "style", "style-2"]);
+        assert_eq!(analysis.unique_candidates().len(), 2);
+        assert_eq!(analysis.candidates[0].size, "Use tabs.".len());
+    }
+
+    #[test]
+    fn test_bundle_round_trips_through_import() {
+        let rules = vec![PlannedRule {
+            id: "style".into(),
+            content: "Use tabs.\n\n### Details\nAlways.".into(),
+            content_hash: String::new(),
+        }];
+        let imported = import_agents_md(&render_bundle(&rules));
+        assert_eq!(
+            imported,
+            vec![("style".to_string(), rules[0].content.clone())]
+        );
+    }
+}
@@ -26,6 +26,57 @@ pub struct BranchInfo {
 
     /// Whether this is the main/primary branch
     pub is_main: bool,
+
+    /// Whether the branch's worktree is locked against pruning
+    pub locked: bool,
+
+    /// Commits the branch has that its upstream does not (None without an upstream)
+    pub ahead: Option<usize>,
+
+    /// Commits the upstream has that the branch does not (None without an upstream)
+    pub behind: Option<usize>,
+
+    /// Whether the branch's working tree has uncommitted or untracked changes
+    /// (None if it is not checked out or was not checked)
+    pub dirty: Option<bool>,
+}
+
+/// What [`ModeBackend::list_branches_with`] computes for each branch
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BranchListOptions {
+    /// Skip the dirty check, which reads every checked-out working tree
+    pub fast: bool,
+}
+
+/// What [`ModeBackend::prune_merged_branches`] may remove
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BranchPruneOptions {
+    /// Only report what would be removed
+    pub dry_run: bool,
+
+    /// Also remove worktrees with uncommitted or untracked changes
+    pub force: bool,
+}
+
+/// A branch merged into the main branch, found by
+/// [`ModeBackend::prune_merged_branches`]
+#[derive(Debug, Clone)]
+pub struct MergedBranch {
+    /// Branch name
+    pub name: String,
+
+    /// Filesystem path to the branch's worktree, if it has one
+    pub path: Option<NormalizedPath>,
+
+    /// Why the branch was kept, or None if it was (or would be) removed
+    pub skipped: Option<String>,
+}
+
+impl MergedBranch {
+    /// Whether the branch was (or, in a dry run, would be) removed
+    pub fn is_pruned(&self) -> bool {
+        self.skipped.is_none()
+    }
 }
 
 impl BranchInfo {
@@ -36,6 +87,10 @@ impl BranchInfo {
             path: None,
             is_current,
             is_main,
+            locked: false,
+            ahead: None,
+            behind: None,
+            dirty: None,
         }
     }
 
@@ -51,7 +106,27 @@ impl BranchInfo {
             path: Some(path),
             is_current,
             is_main,
+            locked: false,
+            ahead: None,
+            behind: None,
+            dirty: None,
+        }
+    }
+
+    /// Fill in upstream and working tree status from the checkout at `workdir`.
+    ///
+    /// The dirty check only runs when `check_dirty` is set, and status that
+    /// cannot be read is left unset rather than failing the listing.
+    pub(crate) fn with_status(mut self, workdir: &NormalizedPath, check_dirty: bool) -> Self {
+        match repo_git::branch_status(&workdir.to_native(), &self.name, check_dirty) {
+            Ok(status) => {
+                self.ahead = status.ahead;
+                self.behind = status.behind;
+                self.dirty = status.dirty;
+            }
+            Err(e) => tracing::debug!("No status for branch {}: {}", self.name, e),
         }
+        self
     }
 }
 
@@ -90,8 +165,14 @@ pub trait ModeBackend: Send + Sync {
 
     /// List all branches in the repository.
     ///
-    /// Returns branch information including paths for worktree mode.
-    fn list_branches(&self) -> Result<Vec<BranchInfo>>;
+    /// Returns branch information including paths for worktree mode, and
+    /// each branch's upstream and working tree status.
+    fn list_branches(&self) -> Result<Vec<BranchInfo>> {
+        self.list_branches_with(BranchListOptions::default())
+    }
+
+    /// List all branches, computing only the status `options` asks for.
+    fn list_branches_with(&self, options: BranchListOptions) -> Result<Vec<BranchInfo>>;
 
     /// Switch to a branch and return the working directory path.
     ///
@@ -104,4 +185,15 @@ pub trait ModeBackend: Send + Sync {
     /// In Standard mode, this renames the git branch.
     /// In Worktrees mode, this renames both the branch and moves the worktree directory.
     fn rename_branch(&self, old_name: &str, new_name: &str) -> Result<()>;
+
+    /// Remove branches whose tips are ancestors of the main branch.
+    ///
+    /// The main and current branches are never removed, nor are branches
+    /// whose tip is the main branch's tip, which have no commits of their
+    /// own yet. In Standard mode, this deletes the merged local branches
+    /// with `git branch -d`, keeping any it refuses. In Worktrees mode, this also
+    /// removes their worktrees, skipping locked worktrees and, unless
+    /// `options.force` is set, worktrees with uncommitted or untracked
+    /// changes.
+    fn prune_merged_branches(&self, options: BranchPruneOptions) -> Result<Vec<MergedBranch>>;
 }
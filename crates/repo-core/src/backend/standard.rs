@@ -2,10 +2,12 @@
 
 use std::process::Command;
 
+use crate::cancel;
+use crate::hooks::{HookContext, HookEvent, HookRunner};
 use crate::{Error, Result};
 use repo_fs::NormalizedPath;
 
-use super::{BranchInfo, ModeBackend};
+use super::{BranchInfo, BranchListOptions, BranchPruneOptions, MergedBranch, ModeBackend};
 
 /// Backend for traditional single-directory Git repositories.
 ///
@@ -40,11 +42,11 @@ impl StandardBackend {
 
     /// Run a git command and return the output.
     fn git_command(&self, args: &[&str]) -> Result<String> {
-        let output = Command::new("git")
-            .args(args)
-            .current_dir(self.root.to_native())
-            .output()
-            .map_err(Error::Io)?;
+        let output = cancel::output(
+            Command::new("git")
+                .args(args)
+                .current_dir(self.root.to_native()),
+        )?;
 
         if output.status.success() {
             Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
@@ -93,6 +95,10 @@ impl ModeBackend for StandardBackend {
     }
 
     fn create_branch(&self, name: &str, base: Option<&str>) -> Result<()> {
+        let hooks = HookRunner::from_repository(&self.root);
+        let context = HookContext::for_branch(name, None);
+        hooks.run(HookEvent::PreBranchCreate, &context)?;
+
         // Use "--" to separate branch names from git flags (defense-in-depth)
         let args = match base {
             Some(base_branch) => vec!["branch", "--", name, base_branch],
@@ -100,6 +106,7 @@ impl ModeBackend for StandardBackend {
         };
 
         self.git_command(&args)?;
+        hooks.run(HookEvent::PostBranchCreate, &context)?;
         Ok(())
     }
 
@@ -112,12 +119,17 @@ impl ModeBackend for StandardBackend {
             });
         }
 
+        let hooks = HookRunner::from_repository(&self.root);
+        let context = HookContext::for_branch(name, None);
+        hooks.run(HookEvent::PreBranchDelete, &context)?;
+
         // Use "--" to separate branch names from git flags (defense-in-depth)
         self.git_command(&["branch", "-d", "--", name])?;
+        hooks.run(HookEvent::PostBranchDelete, &context)?;
         Ok(())
     }
 
-    fn list_branches(&self) -> Result<Vec<BranchInfo>> {
+    fn list_branches_with(&self, options: BranchListOptions) -> Result<Vec<BranchInfo>> {
         let output =
             self.git_command(&["for-each-ref", "--format=%(refname:short)", "refs/heads/"])?;
 
@@ -130,7 +142,9 @@ impl ModeBackend for StandardBackend {
             .map(|name| {
                 let is_current = name == current;
                 let is_main = name == main_branch;
+                // Only the current branch is checked out
                 BranchInfo::standard(name, is_current, is_main)
+                    .with_status(&self.root, is_current && !options.fast)
             })
             .collect();
 
@@ -159,6 +173,54 @@ impl ModeBackend for StandardBackend {
         self.git_command(&["branch", "-m", "--", old_name, new_name])?;
         Ok(())
     }
+
+    fn prune_merged_branches(&self, options: BranchPruneOptions) -> Result<Vec<MergedBranch>> {
+        let main_branch = self.main_branch_name();
+        let current = self.current_branch()?;
+        let output = self.git_command(&[
+            "for-each-ref",
+            "--format=%(objectname) %(refname:short)",
+            &format!("--merged=refs/heads/{}", main_branch),
+            "refs/heads/",
+        ])?;
+        let refs: Vec<(&str, &str)> = output
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .collect();
+        let main_tip = refs
+            .iter()
+            .find(|(_, name)| *name == main_branch)
+            .map(|(tip, _)| *tip);
+
+        let hooks = HookRunner::from_repository(&self.root);
+        let mut pruned = Vec::new();
+        for &(tip, name) in &refs {
+            // A branch at main's tip has no commits yet, rather than merged ones
+            if name == main_branch || name == current || Some(tip) == main_tip {
+                continue;
+            }
+            let mut skipped = None;
+            if !options.dry_run {
+                let context = HookContext::for_branch(name, None);
+                match hooks.run(HookEvent::PreBranchDelete, &context) {
+                    // `-d` refuses branches not merged into HEAD, which are kept
+                    Ok(_) => match self.git_command(&["branch", "-d", "--", name]) {
+                        Ok(_) => {
+                            hooks.run(HookEvent::PostBranchDelete, &context)?;
+                        }
+                        Err(e) => skipped = Some(e.to_string()),
+                    },
+                    Err(e) => skipped = Some(e.to_string()),
+                }
+            }
+            pruned.push(MergedBranch {
+                name: name.to_string(),
+                path: None,
+                skipped,
+            });
+        }
+        Ok(pruned)
+    }
 }
 
 #[cfg(test)]
@@ -2,10 +2,13 @@
 
 use std::process::Command;
 
+use crate::cancel;
+use crate::hooks::{HookContext, HookEvent, HookRunner};
+use crate::sync::SyncEngine;
 use crate::{Error, Result};
 use repo_fs::NormalizedPath;
 
-use super::{BranchInfo, ModeBackend};
+use super::{BranchInfo, BranchListOptions, BranchPruneOptions, MergedBranch, ModeBackend};
 
 /// Backend for container-based Git repositories with multiple worktrees.
 ///
@@ -22,6 +25,9 @@ pub struct WorktreeBackend {
 
     /// Path to the git database (.gt directory)
     git_dir: NormalizedPath,
+
+    /// Sync tool configurations into worktrees on checkout
+    auto_sync: bool,
 }
 
 impl WorktreeBackend {
@@ -45,6 +51,7 @@ impl WorktreeBackend {
             container,
             current_worktree: main_worktree,
             git_dir,
+            auto_sync: false,
         })
     }
 
@@ -68,9 +75,20 @@ impl WorktreeBackend {
             container,
             current_worktree: worktree,
             git_dir,
+            auto_sync: false,
         })
     }
 
+    /// Sync tool configurations into a worktree after `switch_branch`
+    /// checks it out or `create_branch` creates it.
+    ///
+    /// A failed sync is logged as a warning and does not fail the branch
+    /// operation.
+    pub fn with_auto_sync(mut self, enabled: bool) -> Self {
+        self.auto_sync = enabled;
+        self
+    }
+
     /// Get the container directory.
     pub fn container(&self) -> &NormalizedPath {
         &self.container
@@ -83,11 +101,11 @@ impl WorktreeBackend {
 
     /// Run a git command from a specific worktree.
     fn git_command_in_worktree(&self, worktree: &NormalizedPath, args: &[&str]) -> Result<String> {
-        let output = Command::new("git")
-            .args(args)
-            .current_dir(worktree.to_native())
-            .output()
-            .map_err(Error::Io)?;
+        let output = cancel::output(
+            Command::new("git")
+                .args(args)
+                .current_dir(worktree.to_native()),
+        )?;
 
         if output.status.success() {
             Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
@@ -99,6 +117,24 @@ impl WorktreeBackend {
         }
     }
 
+    /// Sync tool configurations into `worktree` if auto-sync is enabled.
+    fn auto_sync_worktree(&self, worktree: &NormalizedPath) {
+        if !self.auto_sync {
+            return;
+        }
+        let result = SyncEngine::for_worktree(self.container.clone(), worktree.clone())
+            .and_then(|engine| engine.sync());
+        match result {
+            Ok(report) if report.success => {}
+            Ok(report) => tracing::warn!(
+                "Sync of worktree {} reported errors: {}",
+                worktree,
+                report.errors.join("; ")
+            ),
+            Err(e) => tracing::warn!("Failed to sync worktree {}: {}", worktree, e),
+        }
+    }
+
     /// Get the main branch name.
     fn main_branch_name(&self) -> String {
         // In container mode, main is typically the default
@@ -116,6 +152,8 @@ impl WorktreeBackend {
     }
 
     /// Parse git worktree list output.
+    ///
+    /// Returns `(path, branch, locked)` for each non-bare worktree.
     fn parse_worktree_list(&self) -> Result<Vec<(NormalizedPath, String, bool)>> {
         // Use porcelain format for reliable parsing
         let output = self.git_command_in_worktree(
@@ -127,6 +165,7 @@ impl WorktreeBackend {
         let mut current_path: Option<NormalizedPath> = None;
         let mut current_branch: Option<String> = None;
         let mut is_bare = false;
+        let mut is_locked = false;
 
         for line in output.lines() {
             if let Some(path_str) = line.strip_prefix("worktree ") {
@@ -134,11 +173,12 @@ impl WorktreeBackend {
                 if let (Some(path), Some(branch)) = (current_path.take(), current_branch.take())
                     && !is_bare
                 {
-                    worktrees.push((path, branch, false));
+                    worktrees.push((path, branch, is_locked));
                 }
                 current_path = Some(NormalizedPath::new(path_str));
                 current_branch = None;
                 is_bare = false;
+                is_locked = false;
             } else if let Some(branch_str) = line.strip_prefix("branch refs/heads/") {
                 current_branch = Some(branch_str.to_string());
             } else if line.starts_with("HEAD ") {
@@ -148,6 +188,8 @@ impl WorktreeBackend {
                 }
             } else if line == "bare" {
                 is_bare = true;
+            } else if line == "locked" || line.starts_with("locked ") {
+                is_locked = true;
             }
         }
 
@@ -155,11 +197,37 @@ impl WorktreeBackend {
         if let (Some(path), Some(branch)) = (current_path, current_branch)
             && !is_bare
         {
-            worktrees.push((path, branch, false));
+            worktrees.push((path, branch, is_locked));
         }
 
         Ok(worktrees)
     }
+
+    /// Remove the worktree at `path` and delete branch `name`.
+    fn remove_merged_worktree(&self, name: &str, path: &NormalizedPath) -> Result<()> {
+        let repo =
+            git2::Repository::open(self.git_dir.to_native()).map_err(repo_git::Error::from)?;
+        // The worktree's administrative name is not necessarily the branch name
+        let admin_name = repo
+            .worktrees()
+            .map_err(repo_git::Error::from)?
+            .iter()
+            .flatten()
+            .find(|admin| {
+                repo.find_worktree(admin)
+                    .is_ok_and(|wt| NormalizedPath::new(wt.path()).as_str() == path.as_str())
+            })
+            .map(str::to_string)
+            .ok_or_else(|| repo_git::Error::WorktreeNotFound {
+                name: name.to_string(),
+            })?;
+        repo_git::remove_worktree_and_branch(&repo, &admin_name)?;
+
+        if let Ok(mut branch) = repo.find_branch(name, git2::BranchType::Local) {
+            branch.delete().map_err(repo_git::Error::from)?;
+        }
+        Ok(())
+    }
 }
 
 impl ModeBackend for WorktreeBackend {
@@ -182,6 +250,9 @@ impl ModeBackend for WorktreeBackend {
         }
 
         let worktree_path = self.worktree_path(name);
+        let hooks = HookRunner::from_repository(&self.container);
+        let context = HookContext::for_branch(name, Some(worktree_path.to_native().as_path()));
+        hooks.run(HookEvent::PreBranchCreate, &context)?;
 
         // Create worktree with new branch
         // Use "--" to separate flags from branch/path names (defense-in-depth)
@@ -199,6 +270,8 @@ impl ModeBackend for WorktreeBackend {
         };
 
         self.git_command_in_worktree(&self.current_worktree, &args)?;
+        self.auto_sync_worktree(&worktree_path);
+        hooks.run(HookEvent::PostBranchCreate, &context)?;
         Ok(())
     }
 
@@ -219,6 +292,10 @@ impl ModeBackend for WorktreeBackend {
             }));
         }
 
+        let hooks = HookRunner::from_repository(&self.container);
+        let context = HookContext::for_branch(name, Some(worktree_path.to_native().as_path()));
+        hooks.run(HookEvent::PreBranchDelete, &context)?;
+
         // Remove the worktree
         self.git_command_in_worktree(
             &self.current_worktree,
@@ -238,19 +315,24 @@ impl ModeBackend for WorktreeBackend {
             );
         }
 
+        hooks.run(HookEvent::PostBranchDelete, &context)?;
         Ok(())
     }
 
-    fn list_branches(&self) -> Result<Vec<BranchInfo>> {
+    fn list_branches_with(&self, options: BranchListOptions) -> Result<Vec<BranchInfo>> {
         let worktrees = self.parse_worktree_list()?;
         let main_branch = self.main_branch_name();
 
         let branches: Vec<BranchInfo> = worktrees
             .into_iter()
-            .map(|(path, branch, _)| {
+            .map(|(path, branch, locked)| {
                 let is_main = branch == main_branch;
                 let is_current = path.as_str() == self.current_worktree.as_str();
-                BranchInfo::worktree(&branch, path, is_current, is_main)
+                BranchInfo {
+                    locked,
+                    ..BranchInfo::worktree(&branch, path.clone(), is_current, is_main)
+                }
+                .with_status(&path, !options.fast)
             })
             .collect();
 
@@ -262,9 +344,10 @@ impl ModeBackend for WorktreeBackend {
 
         if worktree_path.exists() {
             // Worktree exists, just return its path
+            self.auto_sync_worktree(&worktree_path);
             Ok(worktree_path)
         } else {
-            // Need to create the worktree first
+            // Need to create the worktree first, which also syncs it
             self.create_branch(name, None)?;
             Ok(worktree_path)
         }
@@ -306,6 +389,86 @@ impl ModeBackend for WorktreeBackend {
 
         Ok(())
     }
+
+    fn prune_merged_branches(&self, options: BranchPruneOptions) -> Result<Vec<MergedBranch>> {
+        let main_branch = self.main_branch_name();
+        let worktrees = self.parse_worktree_list()?;
+        let output = self.git_command_in_worktree(
+            &self.current_worktree,
+            &[
+                "for-each-ref",
+                "--format=%(objectname) %(refname:short)",
+                &format!("--merged=refs/heads/{}", main_branch),
+                "refs/heads/",
+            ],
+        )?;
+        let refs: Vec<(&str, &str)> = output
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .collect();
+        let main_tip = refs
+            .iter()
+            .find(|(_, name)| *name == main_branch)
+            .map(|(tip, _)| *tip);
+
+        let hooks = HookRunner::from_repository(&self.container);
+        let mut merged = Vec::new();
+        for &(tip, name) in &refs {
+            // A branch at main's tip has no commits yet, rather than merged ones
+            if name == main_branch || Some(tip) == main_tip {
+                continue;
+            }
+            let worktree = worktrees.iter().find(|(_, branch, _)| branch == name);
+            if worktree.is_some_and(|(path, ..)| path.as_str() == self.current_worktree.as_str()) {
+                continue;
+            }
+
+            let mut skipped = match worktree {
+                Some((_, _, true)) => Some("worktree is locked".to_string()),
+                Some((path, ..)) if !options.force => {
+                    // A worktree whose status cannot be read is treated as dirty
+                    let dirty = repo_git::branch_status(&path.to_native(), name, true)
+                        .map_or(true, |status| status.dirty == Some(true));
+                    dirty.then(|| "worktree has uncommitted or untracked changes".to_string())
+                }
+                _ => None,
+            };
+            if skipped.is_none() && !options.dry_run {
+                let path = worktree.map(|(path, ..)| path.to_native());
+                let context = HookContext::for_branch(name, path.as_deref());
+                match hooks.run(HookEvent::PreBranchDelete, &context) {
+                    Ok(_) => {
+                        let removed = match worktree {
+                            Some((path, ..)) => {
+                                self.remove_merged_worktree(name, path)?;
+                                Ok(())
+                            }
+                            // `-d` refuses branches not merged into HEAD, which are kept
+                            None => self
+                                .git_command_in_worktree(
+                                    &self.current_worktree,
+                                    &["branch", "-d", "--", name],
+                                )
+                                .map(drop),
+                        };
+                        match removed {
+                            Ok(()) => {
+                                hooks.run(HookEvent::PostBranchDelete, &context)?;
+                            }
+                            Err(e) => skipped = Some(e.to_string()),
+                        }
+                    }
+                    Err(e) => skipped = Some(e.to_string()),
+                }
+            }
+            merged.push(MergedBranch {
+                name: name.to_string(),
+                path: worktree.map(|(path, ..)| path.clone()),
+                skipped,
+            });
+        }
+        Ok(merged)
+    }
 }
 
 #[cfg(test)]
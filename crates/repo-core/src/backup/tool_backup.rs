@@ -118,7 +118,7 @@ impl BackupManager {
         // Create and save metadata
         let metadata = BackupMetadata::new(tool, backed_up_files);
         let metadata_content = toml::to_string_pretty(&metadata)?;
-        fs::write(self.metadata_path(tool).as_ref(), metadata_content)?;
+        repo_fs::io::write_text(&self.metadata_path(tool), &metadata_content)?;
 
         Ok(ToolBackup {
             tool: tool.to_string(),
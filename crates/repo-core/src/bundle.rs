@@ -0,0 +1,668 @@
+//! Configuration bundles for sharing a repository's setup
+//!
+//! A bundle carries what one repository's configuration can share with
+//! another: the config.toml sections other than `[core]` (tools, presets,
+//! rules, extensions, tool settings, hooks, MCP servers, ...), the rule
+//! files in `.repository/rules/` and the rules of the rule registry.
+//! `[core]` holds per-checkout settings such as the mode and layout, so it
+//! is never exported.
+//!
+//! A bundle is written as a single TOML document, or as a gzipped tar
+//! archive holding `bundle.toml` (the version and registry rules),
+//! `config.toml` and `rules/<file>` for each rule file.
+//!
+//! [`ConfigBundle::apply`] imports a bundle into a repository in one of two
+//! modes:
+//!
+//! - [`ImportMode::Merge`] adds the tools, presets, rules and other entries
+//!   the repository lacks and keeps the ones it has. Rules both define
+//!   differently are only overwritten when listed in `overwrite`;
+//!   [`ConfigBundle::conflicts`] finds them beforehand.
+//! - [`ImportMode::Replace`] takes every config.toml section but `[core]`
+//!   from the bundle and overwrites every rule it defines. Rules the bundle
+//!   does not define are left alone.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use repo_fs::{ConfigStore, NormalizedPath};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Manifest, ManifestEditor};
+use crate::governance::validate_rule_id;
+use crate::rules::RuleRegistry;
+use crate::{Error, Result};
+
+/// Bundle format version written by [`ConfigBundle::export`]
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Rule directory within a repository
+const RULES_DIR: &str = ".repository/rules";
+
+/// Rule registry file within [`RULES_DIR`]
+const REGISTRY_FILE: &str = "registry.toml";
+
+/// Bundle metadata entry of an archive
+const ARCHIVE_BUNDLE: &str = "bundle.toml";
+
+/// Config entry of an archive
+const ARCHIVE_CONFIG: &str = "config.toml";
+
+/// Prefix of the rule file entries of an archive
+const ARCHIVE_RULES: &str = "rules/";
+
+/// A rule from the rule registry, without its repository-specific UUID
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundledRule {
+    /// Human-readable identifier
+    pub id: String,
+    /// The rule content (Markdown)
+    pub content: String,
+    /// Tags for categorization
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Tools the rule is projected to; empty means every tool
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// Glob patterns of the files the rule targets
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+}
+
+/// How [`ConfigBundle::apply`] combines a bundle with the repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportMode {
+    /// Add what the repository lacks, keep what it has
+    #[default]
+    Merge,
+    /// Let the bundle replace the repository's configuration and rules
+    Replace,
+}
+
+/// What [`ConfigBundle::apply`] changed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ImportSummary {
+    /// Tools that were not enabled before
+    pub tools_added: Vec<String>,
+    /// Presets that were not configured before
+    pub presets_added: Vec<String>,
+    /// IDs of rules that did not exist before
+    pub rules_added: Vec<String>,
+    /// IDs of rules overwritten with the bundle's version
+    pub rules_replaced: Vec<String>,
+    /// IDs of rules that differ from the bundle's but were kept
+    pub rules_kept: Vec<String>,
+}
+
+/// A repository's shareable configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    /// Bundle format version
+    pub version: u32,
+    /// What the bundle sets up, shown when it is used as a template
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Rules of the rule registry
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<BundledRule>,
+    /// config.toml without `[core]`
+    #[serde(default)]
+    pub config: toml::Table,
+    /// Rule files from `.repository/rules/`, keyed by file name
+    #[serde(default)]
+    pub rule_files: BTreeMap<String, String>,
+}
+
+/// Bundle metadata stored as `bundle.toml` in an archive
+#[derive(Serialize, Deserialize)]
+struct ArchiveMeta {
+    version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default)]
+    rules: Vec<BundledRule>,
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidBundle {
+        message: message.into(),
+    }
+}
+
+/// ID of the rule stored in the rule file `name`
+fn rule_file_id(name: &str) -> &str {
+    Path::new(name)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(name)
+}
+
+impl ConfigBundle {
+    /// Export the configuration of the repository at `root`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ConfigNotFound`] if the repository has no
+    /// config.toml, or an error if it or the rules cannot be read.
+    pub fn export(root: &Path) -> Result<Self> {
+        let config_path = root.join(".repository").join("config.toml");
+        if !config_path.is_file() {
+            return Err(Error::ConfigNotFound { path: config_path });
+        }
+        let content = fs::read_to_string(&config_path)?;
+        Manifest::parse(&content)?;
+        let mut config: toml::Table = content.parse()?;
+        config.remove("core");
+
+        let rules_dir = root.join(RULES_DIR);
+        let mut rule_files = BTreeMap::new();
+        if rules_dir.is_dir() {
+            for entry in fs::read_dir(&rules_dir)? {
+                let path = entry?.path();
+                let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                if !path.is_file() || name == REGISTRY_FILE {
+                    continue;
+                }
+                rule_files.insert(name.to_string(), fs::read_to_string(&path)?);
+            }
+        }
+
+        let registry_path = rules_dir.join(REGISTRY_FILE);
+        let rules = if registry_path.is_file() {
+            RuleRegistry::load(registry_path)?
+                .all_rules()
+                .iter()
+                .map(|rule| BundledRule {
+                    id: rule.id.clone(),
+                    content: rule.content.clone(),
+                    tags: rule.tags.clone(),
+                    tools: rule.tools.clone(),
+                    paths: rule.paths.clone(),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            description: None,
+            rules,
+            config,
+            rule_files,
+        })
+    }
+
+    /// Render the bundle as a TOML document
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    /// Parse a bundle from a TOML document
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidBundle`] if the bundle is from a newer
+    /// version, its config would not load, or a rule file name is not a
+    /// valid rule ID.
+    pub fn parse(content: &str) -> Result<Self> {
+        let bundle: Self = toml::from_str(content)?;
+        bundle.validate()?;
+        Ok(bundle)
+    }
+
+    /// Read a bundle written by [`Self::to_toml`] or [`Self::to_archive`]
+    pub fn read(bytes: &[u8]) -> Result<Self> {
+        // gzip magic number
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            return Self::from_archive(bytes);
+        }
+        let content =
+            std::str::from_utf8(bytes).map_err(|_| invalid("not a TOML document or archive"))?;
+        Self::parse(content)
+    }
+
+    /// Render the bundle as a gzipped tar archive
+    pub fn to_archive(&self) -> Result<Vec<u8>> {
+        let meta = ArchiveMeta {
+            version: self.version,
+            description: self.description.clone(),
+            rules: self.rules.clone(),
+        };
+        let mut entries = vec![
+            (ARCHIVE_BUNDLE.to_string(), toml::to_string_pretty(&meta)?),
+            (
+                ARCHIVE_CONFIG.to_string(),
+                toml::to_string_pretty(&self.config)?,
+            ),
+        ];
+        entries.extend(
+            self.rule_files
+                .iter()
+                .map(|(name, content)| (format!("{}{}", ARCHIVE_RULES, name), content.clone())),
+        );
+
+        let mut archive = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        for (path, content) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            archive.append_data(&mut header, path, content.as_bytes())?;
+        }
+        let mut encoder = archive.into_inner()?;
+        encoder.flush()?;
+        Ok(encoder.finish()?)
+    }
+
+    /// Read a bundle from a gzipped tar archive
+    fn from_archive(bytes: &[u8]) -> Result<Self> {
+        let mut archive = tar::Archive::new(GzDecoder::new(bytes));
+        let mut meta = None;
+        let mut bundle = Self::default();
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let path = entry.path()?.to_string_lossy().replace('\\', "/");
+            let mut content = String::new();
+            entry.read_to_string(&mut content)?;
+
+            if path == ARCHIVE_BUNDLE {
+                meta = Some(toml::from_str::<ArchiveMeta>(&content)?);
+            } else if path == ARCHIVE_CONFIG {
+                bundle.config = content.parse()?;
+            } else if let Some(name) = path.strip_prefix(ARCHIVE_RULES) {
+                bundle.rule_files.insert(name.to_string(), content);
+            }
+        }
+
+        let meta = meta.ok_or_else(|| invalid(format!("archive has no {}", ARCHIVE_BUNDLE)))?;
+        bundle.version = meta.version;
+        bundle.description = meta.description;
+        bundle.rules = meta.rules;
+        bundle.validate()?;
+        Ok(bundle)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.version > BUNDLE_VERSION {
+            return Err(invalid(format!(
+                "bundle version {} is newer than the supported version {}",
+                self.version, BUNDLE_VERSION
+            )));
+        }
+        self.manifest()?;
+        for name in self.rule_files.keys() {
+            if name.contains(['/', '\\']) || name == REGISTRY_FILE {
+                return Err(invalid(format!("invalid rule file name '{}'", name)));
+            }
+            validate_rule_id(rule_file_id(name))
+                .map_err(|message| invalid(format!("rule file '{}': {}", name, message)))?;
+        }
+        Ok(())
+    }
+
+    /// The bundled config as a manifest
+    fn manifest(&self) -> Result<Manifest> {
+        toml::Value::Table(self.config.clone())
+            .try_into()
+            .map_err(|e: toml::de::Error| invalid(format!("invalid config: {}", e.message())))
+    }
+
+    /// IDs of the rules the repository at `root` defines differently from
+    /// the bundle, sorted
+    pub fn conflicts(&self, root: &Path) -> Result<Vec<String>> {
+        let rules_dir = root.join(RULES_DIR);
+        let mut conflicts = BTreeSet::new();
+        for (name, content) in &self.rule_files {
+            if let Ok(existing) = fs::read_to_string(rules_dir.join(name))
+                && existing != *content
+            {
+                conflicts.insert(rule_file_id(name).to_string());
+            }
+        }
+
+        let registry_path = rules_dir.join(REGISTRY_FILE);
+        if registry_path.is_file() {
+            let registry = RuleRegistry::load(registry_path)?;
+            for rule in &self.rules {
+                if let Some(existing) = registry.get_rule_by_id(&rule.id)
+                    && !same_rule(existing, rule)
+                {
+                    conflicts.insert(rule.id.clone());
+                }
+            }
+        }
+        Ok(conflicts.into_iter().collect())
+    }
+
+    /// Apply the bundle to the repository at `root`
+    ///
+    /// In [`ImportMode::Merge`], conflicting rules are overwritten only if
+    /// their ID is in `overwrite`. Does not sync tool configurations.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ConfigNotFound`] if the repository has no
+    /// config.toml, or an error if a file cannot be written.
+    pub fn apply(
+        &self,
+        root: &Path,
+        mode: ImportMode,
+        overwrite: &[String],
+    ) -> Result<ImportSummary> {
+        let config_path = root.join(".repository").join("config.toml");
+        if !config_path.is_file() {
+            return Err(Error::ConfigNotFound { path: config_path });
+        }
+        let incoming = self.manifest()?;
+        let lock = ConfigStore::new().lock(&NormalizedPath::new(&config_path))?;
+        let mut editor = ManifestEditor::parse(&lock.read()?)?;
+        let manifest = editor.manifest()?;
+
+        let mut summary = ImportSummary {
+            tools_added: incoming
+                .tools
+                .iter()
+                .filter(|tool| !manifest.tools.contains(tool))
+                .cloned()
+                .collect(),
+            presets_added: incoming
+                .presets
+                .keys()
+                .filter(|preset| !manifest.presets.contains_key(*preset))
+                .cloned()
+                .collect(),
+            ..ImportSummary::default()
+        };
+        summary.presets_added.sort();
+
+        match mode {
+            ImportMode::Merge => merge_manifest(&mut editor, &manifest, incoming)?,
+            ImportMode::Replace => {
+                let config = ManifestEditor::parse(&toml::to_string(&self.config)?)?;
+                editor.replace_settings(&config);
+            }
+        }
+        lock.write(&editor.to_string())?;
+
+        let replace = |id: &str| mode == ImportMode::Replace || overwrite.iter().any(|o| o == id);
+        let mut added = BTreeSet::new();
+        let mut replaced = BTreeSet::new();
+        let mut kept = BTreeSet::new();
+
+        let rules_dir = root.join(RULES_DIR);
+        if !self.rule_files.is_empty() {
+            fs::create_dir_all(&rules_dir)?;
+        }
+        for (name, content) in &self.rule_files {
+            let id = rule_file_id(name).to_string();
+            let path = rules_dir.join(name);
+            match fs::read_to_string(&path) {
+                Ok(existing) if existing == *content => {}
+                Ok(_) if !replace(&id) => {
+                    kept.insert(id);
+                }
+                Ok(_) => {
+                    repo_fs::io::write_text(&NormalizedPath::new(&path), content)?;
+                    replaced.insert(id);
+                }
+                Err(_) => {
+                    repo_fs::io::write_text(&NormalizedPath::new(&path), content)?;
+                    added.insert(id);
+                }
+            }
+        }
+
+        if !self.rules.is_empty() {
+            let mut registry = RuleRegistry::load_or_create(rules_dir.join(REGISTRY_FILE))?;
+            for rule in &self.rules {
+                let existing = registry
+                    .get_rule_by_id(&rule.id)
+                    .map(|r| (r.uuid, same_rule(r, rule)));
+                let uuid = match existing {
+                    Some((_, true)) => continue,
+                    Some((_, false)) if !replace(&rule.id) => {
+                        kept.insert(rule.id.clone());
+                        continue;
+                    }
+                    Some((uuid, false)) => {
+                        replaced.insert(rule.id.clone());
+                        uuid
+                    }
+                    None => {
+                        added.insert(rule.id.clone());
+                        registry.add_rule(&rule.id, &rule.content, Vec::new())?.uuid
+                    }
+                };
+                if let Some(existing) = registry.get_rule_mut(uuid) {
+                    if existing.content != rule.content {
+                        existing.update_content(rule.content.clone());
+                    }
+                    existing.tags = rule.tags.clone();
+                    existing.tools = rule.tools.clone();
+                    existing.paths = rule.paths.clone();
+                }
+            }
+            registry.save()?;
+        }
+
+        summary.rules_added = added.into_iter().collect();
+        summary.rules_replaced = replaced.into_iter().collect();
+        summary.rules_kept = kept.into_iter().collect();
+        Ok(summary)
+    }
+}
+
+/// Whether a registry rule matches a bundled one
+fn same_rule(existing: &crate::Rule, rule: &BundledRule) -> bool {
+    existing.content == rule.content
+        && existing.tags == rule.tags
+        && existing.tools == rule.tools
+        && existing.paths == rule.paths
+}
+
+/// Add the entries of `incoming` that `manifest`, the config `editor`
+/// holds, lacks
+fn merge_manifest(
+    editor: &mut ManifestEditor,
+    manifest: &Manifest,
+    incoming: Manifest,
+) -> Result<()> {
+    for tool in &incoming.tools {
+        editor.add_tool(tool);
+    }
+    for rule in &incoming.rules {
+        editor.add_rule(rule);
+    }
+    for (key, value) in &incoming.presets {
+        editor.add_preset(key, value);
+    }
+    for (key, value) in &incoming.extensions {
+        editor.add_entry(&["extensions"], key, value);
+    }
+    for (key, value) in &incoming.tool_settings {
+        editor.add_entry(&["tool_settings"], key, value);
+    }
+    let mut hooks = manifest.hooks.clone();
+    for hook in incoming.hooks {
+        let exists = hooks.iter().any(|existing| {
+            existing.event == hook.event
+                && existing.command == hook.command
+                && existing.args == hook.args
+        });
+        if !exists {
+            editor.add_hook(&hook)?;
+            hooks.push(hook);
+        }
+    }
+    for (name, server) in &incoming.mcp.servers {
+        editor.add_entry(&["mcp", "servers"], name, &serde_json::to_value(server)?);
+    }
+    if manifest.mcp.on_conflict.is_none()
+        && let Some(policy) = incoming.mcp.on_conflict
+    {
+        editor.set_key(&["mcp"], "on_conflict", &serde_json::to_value(policy)?);
+    }
+    if manifest.sync.is_empty() && !incoming.sync.is_empty() {
+        editor.set_entry(&[], "sync", &serde_json::to_value(&incoming.sync)?);
+    }
+    if manifest.open.is_empty() && !incoming.open.is_empty() {
+        editor.set_entry(&[], "open", &serde_json::to_value(&incoming.open)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_repo(dir: &Path, config: &str) {
+        fs::create_dir_all(dir.join(RULES_DIR)).unwrap();
+        fs::write(dir.join(".repository/config.toml"), config).unwrap();
+    }
+
+    fn source_repo() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        create_repo(
+            temp.path(),
+            "tools = [\"claude\", \"cursor\"]\n\n[core]\nmode = \"worktrees\"\n\n\
+             [presets.\"env:python\"]\nversion = \"3.12\"\n\n\
+             [mcp.servers.github.transport]\ntype = \"stdio\"\ncommand = \"npx\"\n",
+        );
+        fs::write(
+            temp.path().join(RULES_DIR).join("style.md"),
+            "Use snake_case.",
+        )
+        .unwrap();
+        let mut registry =
+            RuleRegistry::load_or_create(temp.path().join(RULES_DIR).join(REGISTRY_FILE)).unwrap();
+        registry
+            .add_rule("testing", "Write tests.", vec!["quality".to_string()])
+            .unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_export_leaves_out_core() {
+        let source = source_repo();
+        let bundle = ConfigBundle::export(source.path()).unwrap();
+
+        assert_eq!(bundle.version, BUNDLE_VERSION);
+        assert!(!bundle.config.contains_key("core"));
+        assert!(bundle.config.contains_key("mcp"));
+        assert_eq!(bundle.rule_files["style.md"], "Use snake_case.");
+        assert_eq!(bundle.rules[0].id, "testing");
+        assert_eq!(bundle.rules[0].tags, vec!["quality"]);
+
+        assert_eq!(
+            ConfigBundle::parse(&bundle.to_toml().unwrap()).unwrap(),
+            bundle
+        );
+        assert_eq!(
+            ConfigBundle::read(&bundle.to_archive().unwrap()).unwrap(),
+            bundle
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_bundles() {
+        assert!(ConfigBundle::parse("version = 99\n").is_err());
+        assert!(ConfigBundle::parse("version = 1\n\n[config]\ntools = \"claude\"\n").is_err());
+        assert!(
+            ConfigBundle::parse("version = 1\n\n[rule_files]\n\"../escape.md\" = \"x\"\n").is_err()
+        );
+        assert!(ConfigBundle::read(&[0xff, 0xfe]).is_err());
+    }
+
+    #[test]
+    fn test_merge_keeps_existing_entries() {
+        let bundle = ConfigBundle::export(source_repo().path()).unwrap();
+        let target = TempDir::new().unwrap();
+        create_repo(
+            target.path(),
+            "# Editors we use\ntools = [\"vscode\"]\n\n[core]\nmode = \"standard\"\n\n\
+             [presets.\"env:python\"]\nversion = \"3.11\" # pinned\n",
+        );
+        fs::write(
+            target.path().join(RULES_DIR).join("style.md"),
+            "Use camelCase.",
+        )
+        .unwrap();
+
+        assert_eq!(bundle.conflicts(target.path()).unwrap(), vec!["style"]);
+        let summary = bundle.apply(target.path(), ImportMode::Merge, &[]).unwrap();
+
+        assert_eq!(summary.tools_added, vec!["claude", "cursor"]);
+        assert!(summary.presets_added.is_empty());
+        assert_eq!(summary.rules_added, vec!["testing"]);
+        assert_eq!(summary.rules_kept, vec!["style"]);
+
+        let config = fs::read_to_string(target.path().join(".repository/config.toml")).unwrap();
+        assert!(config.starts_with("# Editors we use\n"), "{config}");
+        assert!(config.contains("version = \"3.11\" # pinned\n"), "{config}");
+        let manifest = Manifest::parse(&config).unwrap();
+        assert_eq!(manifest.core.mode, "standard");
+        assert_eq!(manifest.tools, vec!["vscode", "claude", "cursor"]);
+        assert_eq!(manifest.presets["env:python"]["version"], "3.11");
+        assert!(manifest.mcp.servers.contains_key("github"));
+        assert_eq!(
+            fs::read_to_string(target.path().join(RULES_DIR).join("style.md")).unwrap(),
+            "Use camelCase."
+        );
+
+        // A conflicting rule chosen for overwriting is replaced
+        let summary = bundle
+            .apply(target.path(), ImportMode::Merge, &["style".to_string()])
+            .unwrap();
+        assert_eq!(summary.rules_replaced, vec!["style"]);
+        assert!(bundle.conflicts(target.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_replace_overwrites_rules_and_arrays() {
+        let bundle = ConfigBundle::export(source_repo().path()).unwrap();
+        let target = TempDir::new().unwrap();
+        create_repo(
+            target.path(),
+            "tools = [\"vscode\"]\n\n[core]\nmode = \"standard\"\n",
+        );
+        fs::write(
+            target.path().join(RULES_DIR).join("style.md"),
+            "Use camelCase.",
+        )
+        .unwrap();
+        let mut registry =
+            RuleRegistry::load_or_create(target.path().join(RULES_DIR).join(REGISTRY_FILE))
+                .unwrap();
+        registry.add_rule("testing", "Skip tests.", vec![]).unwrap();
+
+        let summary = bundle
+            .apply(target.path(), ImportMode::Replace, &[])
+            .unwrap();
+        assert_eq!(summary.rules_replaced, vec!["style", "testing"]);
+
+        let manifest = Manifest::parse(
+            &fs::read_to_string(target.path().join(".repository/config.toml")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(manifest.core.mode, "standard");
+        assert_eq!(manifest.tools, vec!["claude", "cursor"]);
+
+        let registry =
+            RuleRegistry::load(target.path().join(RULES_DIR).join(REGISTRY_FILE)).unwrap();
+        assert_eq!(registry.all_rules().len(), 1);
+        assert_eq!(registry.all_rules()[0].content, "Write tests.");
+        assert_eq!(registry.all_rules()[0].tags, vec!["quality"]);
+    }
+}
@@ -0,0 +1,111 @@
+//! Cancellation of in-flight operations
+//!
+//! A caller that stops waiting for an operation (such as the MCP server
+//! when a tool call times out) cancels its [`CancelToken`]. Work running
+//! under the token, on the thread that [entered](CancelToken::enter) it,
+//! stops at its next check with [`Error::Cancelled`], and subprocesses
+//! started through [`output`] or the hook runner are killed.
+//!
+//! The token itself lives in [`repo_fs::cancel`], so that the git and
+//! preset crates can honour it as well.
+
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::thread::{self, JoinHandle};
+
+pub use repo_fs::cancel::{CancelScope, CancelToken, POLL_INTERVAL, current, is_cancelled};
+
+use crate::error::{Error, Result};
+
+/// Fail with [`Error::Cancelled`] if the current token has been cancelled
+pub fn check() -> Result<()> {
+    if is_cancelled() {
+        return Err(Error::Cancelled);
+    }
+    Ok(())
+}
+
+/// Read a pipe to the end on its own thread, so a child writing more than
+/// the pipe buffer holds cannot block
+pub(crate) fn drain(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+/// Run `command` to completion and collect its output, like
+/// [`Command::output`], killing the child if the current token is cancelled
+pub fn output(command: &mut Command) -> Result<Output> {
+    check()?;
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = child.stdout.take().map(drain);
+    let stderr = child.stderr.take().map(drain);
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(Error::Cancelled);
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    let collect = |pipe: Option<JoinHandle<Vec<u8>>>| {
+        pipe.and_then(|handle| handle.join().ok())
+            .unwrap_or_default()
+    };
+
+    Ok(Output {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_check_fails_once_cancelled() {
+        let token = CancelToken::new();
+        let _scope = token.enter();
+        assert!(check().is_ok());
+        token.cancel();
+        assert!(matches!(check(), Err(Error::Cancelled)));
+    }
+
+    #[test]
+    fn test_output_without_token_runs_command() {
+        let output = output(Command::new("git").arg("--version")).unwrap();
+        assert!(output.status.success());
+        assert!(String::from_utf8_lossy(&output.stdout).starts_with("git version"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cancel_kills_running_child() {
+        let token = CancelToken::new();
+        let canceller = token.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            canceller.cancel();
+        });
+
+        let started = Instant::now();
+        let _scope = token.enter();
+        let result = output(Command::new("sleep").arg("30"));
+
+        assert!(matches!(result, Err(Error::Cancelled)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
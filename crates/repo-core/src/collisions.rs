@@ -0,0 +1,285 @@
+//! Tool name collisions across built-ins, repository definitions and extensions
+//!
+//! A tool slug can be supplied by the built-in registry, by a definition in
+//! `.repository/tools/`, and by any installed extension that lists it under
+//! `[provides] tools`. Two suppliers of one slug would write the same config
+//! files, so only one is used, in this order of precedence:
+//!
+//! 1. a repository definition that sets `override = true` in `[meta]`
+//! 2. the built-in tool
+//! 3. a repository definition without `override`
+//! 4. extensions, in order of name
+//!
+//! Extensions therefore never replace a built-in or repository tool. Each
+//! collision is reported by `check` and when installing an extension.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use repo_extensions::installer::EXTENSIONS_DIR;
+use repo_extensions::{ExtensionManifest, MANIFEST_FILENAME};
+use repo_fs::NormalizedPath;
+use repo_meta::DefinitionLoader;
+
+use crate::Result;
+
+/// Where a tool with a given slug comes from
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ToolOrigin {
+    /// `.repository/tools/` definition with `override = true`
+    Override,
+    /// Built-in tool registry
+    Builtin,
+    /// `.repository/tools/` definition
+    Repository,
+    /// Installed extension, by name
+    Extension(String),
+}
+
+impl fmt::Display for ToolOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Override => write!(f, ".repository/tools (override)"),
+            Self::Builtin => write!(f, "built-in"),
+            Self::Repository => write!(f, ".repository/tools"),
+            Self::Extension(name) => write!(f, "extension '{}'", name),
+        }
+    }
+}
+
+/// A tool slug supplied by more than one source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolNameCollision {
+    /// The colliding slug
+    pub name: String,
+    /// Every source supplying it, in order of precedence
+    pub sources: Vec<ToolOrigin>,
+}
+
+impl ToolNameCollision {
+    /// The source that is used
+    pub fn winner(&self) -> &ToolOrigin {
+        &self.sources[0]
+    }
+}
+
+impl fmt::Display for ToolNameCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sources: Vec<String> = self.sources.iter().map(ToString::to_string).collect();
+        write!(
+            f,
+            "Tool '{}' is provided by {}; {} is used",
+            self.name,
+            sources.join(", "),
+            self.winner()
+        )
+    }
+}
+
+/// Tool slugs by source: built-ins, `.repository/tools/` and installed
+/// extensions
+fn tool_origins(root: &NormalizedPath) -> Result<BTreeMap<String, Vec<ToolOrigin>>> {
+    let mut origins: BTreeMap<String, Vec<ToolOrigin>> = BTreeMap::new();
+    for registration in repo_tools::builtin_registrations() {
+        origins
+            .entry(registration.slug)
+            .or_default()
+            .push(ToolOrigin::Builtin);
+    }
+
+    let loaded = DefinitionLoader::new().load_tools(root)?;
+    for (slug, definition) in loaded.definitions {
+        let origin = if definition.meta.override_builtin {
+            ToolOrigin::Override
+        } else {
+            ToolOrigin::Repository
+        };
+        origins.entry(slug).or_default().push(origin);
+    }
+
+    for manifest in installed_extensions(root) {
+        add_extension(&mut origins, &manifest);
+    }
+    Ok(origins)
+}
+
+/// Manifests of the extensions installed under `root`
+///
+/// Extensions whose manifest cannot be read are skipped; `repo extension
+/// verify` reports them.
+fn installed_extensions(root: &NormalizedPath) -> Vec<ExtensionManifest> {
+    let Ok(entries) = std::fs::read_dir(root.join(EXTENSIONS_DIR).to_native()) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            ExtensionManifest::from_path(&entry.path().join(MANIFEST_FILENAME)).ok()
+        })
+        .collect()
+}
+
+fn add_extension(origins: &mut BTreeMap<String, Vec<ToolOrigin>>, manifest: &ExtensionManifest) {
+    let name = &manifest.extension.name;
+    for slug in manifest.provides.iter().flat_map(|p| &p.tools) {
+        let sources = origins.entry(slug.clone()).or_default();
+        let origin = ToolOrigin::Extension(name.clone());
+        // Reinstalling an extension must not collide with itself
+        if !sources.contains(&origin) {
+            sources.push(origin);
+        }
+    }
+}
+
+fn collisions(origins: BTreeMap<String, Vec<ToolOrigin>>) -> Vec<ToolNameCollision> {
+    origins
+        .into_iter()
+        .filter(|(_, sources)| sources.len() > 1)
+        .map(|(name, mut sources)| {
+            sources.sort();
+            ToolNameCollision { name, sources }
+        })
+        .collect()
+}
+
+/// Tool slugs supplied by more than one source in the repository at `root`,
+/// sorted by slug
+///
+/// # Errors
+///
+/// Returns an error if `.repository/tools/` cannot be read.
+pub fn find_tool_collisions(root: &NormalizedPath) -> Result<Vec<ToolNameCollision>> {
+    Ok(collisions(tool_origins(root)?))
+}
+
+/// Collisions the extension described by `manifest` would cause if
+/// installed into the repository at `root`
+///
+/// Only collisions involving one of the extension's own tools are returned.
+pub fn extension_tool_collisions(
+    root: &NormalizedPath,
+    manifest: &ExtensionManifest,
+) -> Result<Vec<ToolNameCollision>> {
+    let mut origins = tool_origins(root)?;
+    add_extension(&mut origins, manifest);
+    let origin = ToolOrigin::Extension(manifest.extension.name.clone());
+    Ok(collisions(origins)
+        .into_iter()
+        .filter(|collision| collision.sources.contains(&origin))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn extension_manifest(name: &str, tools: &str) -> String {
+        format!(
+            "[extension]\nname = \"{name}\"\nversion = \"0.1.0\"\n\n[provides]\ntools = [{tools}]\n"
+        )
+    }
+
+    fn install_extension(root: &NormalizedPath, name: &str, tools: &str) {
+        let dir = root.join(EXTENSIONS_DIR).join(name).to_native();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(MANIFEST_FILENAME), extension_manifest(name, tools)).unwrap();
+    }
+
+    fn write_tool(root: &NormalizedPath, slug: &str, meta: &str) {
+        let dir = root.join(".repository/tools").to_native();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(format!("{slug}.toml")),
+            format!(
+                "[meta]\nname = \"{slug}\"\nslug = \"{slug}\"\n{meta}\n\n\
+                 [integration]\nconfig_path = \".{slug}rc\"\ntype = \"text\"\n"
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_extension_tool_collides_with_builtin() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        install_extension(&root, "editor-kit", "\"vscode\", \"editor-kit\"");
+
+        let collisions = find_tool_collisions(&root).unwrap();
+        assert_eq!(
+            collisions,
+            vec![ToolNameCollision {
+                name: "vscode".to_string(),
+                sources: vec![
+                    ToolOrigin::Builtin,
+                    ToolOrigin::Extension("editor-kit".to_string())
+                ],
+            }]
+        );
+        assert_eq!(collisions[0].winner(), &ToolOrigin::Builtin);
+        assert_eq!(
+            collisions[0].to_string(),
+            "Tool 'vscode' is provided by built-in, extension 'editor-kit'; built-in is used"
+        );
+    }
+
+    #[test]
+    fn test_precedence_of_repository_definitions() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        write_tool(&root, "cursor", "override = true");
+        write_tool(&root, "zed", "");
+        install_extension(&root, "b-ext", "\"cursor\", \"zed\"");
+        install_extension(&root, "a-ext", "\"zed\"");
+
+        let collisions = find_tool_collisions(&root).unwrap();
+        assert_eq!(collisions.len(), 2);
+        assert_eq!(
+            collisions[0].sources,
+            vec![
+                ToolOrigin::Override,
+                ToolOrigin::Builtin,
+                ToolOrigin::Extension("b-ext".to_string())
+            ]
+        );
+        assert_eq!(
+            collisions[1].sources,
+            vec![
+                ToolOrigin::Builtin,
+                ToolOrigin::Repository,
+                ToolOrigin::Extension("a-ext".to_string()),
+                ToolOrigin::Extension("b-ext".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extension_tool_collisions_before_install() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        write_tool(&root, "zed", "");
+        install_extension(&root, "other", "\"mytool\"");
+        let manifest =
+            ExtensionManifest::from_toml(&extension_manifest("new", "\"mytool\", \"aider\""))
+                .unwrap();
+
+        // The repository's own zed collision is not the extension's concern
+        let names: Vec<String> = extension_tool_collisions(&root, &manifest)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, vec!["aider", "mytool"]);
+
+        // Reinstalling does not collide with the installed copy
+        install_extension(&root, "new", "\"unique\"");
+        let manifest =
+            ExtensionManifest::from_toml(&extension_manifest("new", "\"unique\"")).unwrap();
+        assert!(
+            extension_tool_collisions(&root, &manifest)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,535 @@
+//! Format-preserving edits to config.toml
+//!
+//! [`Manifest::to_toml`](super::Manifest::to_toml) rewrites the whole file,
+//! losing comments, key order and blank lines. [`ManifestEditor`] changes
+//! only the keys it is asked to, so everything else in a hand-edited config
+//! is written back verbatim.
+//!
+//! ```
+//! use repo_core::ManifestEditor;
+//!
+//! let mut editor = ManifestEditor::parse("# Agents we use\ntools = [\"claude\"]\n").unwrap();
+//! editor.add_tool("cursor");
+//! assert_eq!(editor.to_string(), "# Agents we use\ntools = [\"claude\", \"cursor\"]\n");
+//! ```
+
+use std::fmt;
+
+use serde_json::Value as JsonValue;
+use toml_edit::{Array, ArrayOfTables, DocumentMut, InlineTable, Item, Table, TableLike, Value};
+
+use super::Manifest;
+use crate::Result;
+use crate::hooks::{HookConfig, HookEvent};
+
+/// A config.toml document with typed, format-preserving mutations
+#[derive(Debug, Clone)]
+pub struct ManifestEditor {
+    doc: DocumentMut,
+}
+
+impl ManifestEditor {
+    /// Parse config.toml `content` for editing
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `content` is not a valid config.toml.
+    pub fn parse(content: &str) -> Result<Self> {
+        // Reject anything that would not load, not just invalid TOML
+        Manifest::parse(content)?;
+        Ok(Self {
+            doc: content.parse()?,
+        })
+    }
+
+    /// The edited document as a [`Manifest`]
+    pub fn manifest(&self) -> Result<Manifest> {
+        Manifest::parse(&self.doc.to_string())
+    }
+
+    /// Add `name` to the `tools` array
+    ///
+    /// Returns `false` if the tool was already listed.
+    pub fn add_tool(&mut self, name: &str) -> bool {
+        self.push_unique("tools", name)
+    }
+
+    /// Add `name` to the `rules` array
+    ///
+    /// Returns `false` if the rule was already listed.
+    pub fn add_rule(&mut self, name: &str) -> bool {
+        self.push_unique("rules", name)
+    }
+
+    /// Append `name` to the string array `key`, unless it is already there
+    fn push_unique(&mut self, key: &str, name: &str) -> bool {
+        let array = self.array_mut(key);
+        if array.iter().any(|v| v.as_str() == Some(name)) {
+            return false;
+        }
+
+        // Indent the new entry like the last one, so multi-line arrays stay
+        // multi-line; comments before the last entry stay with it
+        let mut value = Value::from(name);
+        let prefix = match array.iter().last() {
+            Some(last) => {
+                let raw = last.decor().prefix().and_then(|p| p.as_str()).unwrap_or("");
+                match raw.rfind('\n') {
+                    Some(newline) => raw[newline..].to_string(),
+                    None => " ".to_string(),
+                }
+            }
+            None => String::new(),
+        };
+        value.decor_mut().set_prefix(prefix);
+        array.push_formatted(value);
+        true
+    }
+
+    /// Remove `name` from the `tools` array
+    ///
+    /// Returns `false` if the tool was not listed.
+    pub fn remove_tool(&mut self, name: &str) -> bool {
+        let Some(tools) = self.doc.get_mut("tools").and_then(Item::as_array_mut) else {
+            return false;
+        };
+        let Some(index) = tools.iter().position(|t| t.as_str() == Some(name)) else {
+            return false;
+        };
+
+        let removed = tools.remove(index);
+        // The first entry has no space after `[`; keep it that way
+        if index == 0
+            && let Some(first) = tools.get_mut(0)
+        {
+            first
+                .decor_mut()
+                .set_prefix(removed.decor().prefix().cloned().unwrap_or_default());
+        }
+        true
+    }
+
+    /// Set `core.mode`, keeping any comment on the line
+    pub fn set_mode(&mut self, mode: &str) {
+        self.set_key(&["core"], "mode", &JsonValue::from(mode));
+    }
+
+    /// Set `key` in the table at `section` to `value`, keeping any comment
+    /// on the line
+    ///
+    /// Missing tables along `section` are created, replacing any value that
+    /// is not a table. A null `value` removes the key.
+    pub fn set_key(&mut self, section: &[&str], key: &str, value: &JsonValue) {
+        let table = self.table_mut(section);
+        let Some(value) = to_toml(value) else {
+            table.remove(key);
+            return;
+        };
+        match table.get_mut(key).and_then(Item::as_value_mut) {
+            Some(existing) => {
+                let decor = existing.decor().clone();
+                *existing = value;
+                *existing.decor_mut() = decor;
+            }
+            None => {
+                table.insert(key, Item::Value(value));
+            }
+        }
+    }
+
+    /// Add a `[presets."<name>"]` table holding `config`
+    ///
+    /// Returns `false` if the preset was already configured. Null values in
+    /// `config`, which TOML cannot represent, are left out.
+    pub fn add_preset(&mut self, name: &str, config: &JsonValue) -> bool {
+        self.add_entry(&["presets"], name, config)
+    }
+
+    /// Add a `name` table holding `config` to the table at `section`, such
+    /// as `["mcp", "servers"]`
+    ///
+    /// Returns `false` if `name` was already there. Missing tables along
+    /// `section` are created; an empty `section` adds a top-level table.
+    pub fn add_entry(&mut self, section: &[&str], name: &str, config: &JsonValue) -> bool {
+        let table = self.table_mut(section);
+        if table.contains_key(name) {
+            return false;
+        }
+        table.insert(name, Item::Table(to_table(config)));
+        true
+    }
+
+    /// Set the `name` table in `section` to `config`, replacing any table
+    /// already there
+    pub fn set_entry(&mut self, section: &[&str], name: &str, config: &JsonValue) {
+        self.table_mut(section)
+            .insert(name, Item::Table(to_table(config)));
+    }
+
+    /// Append `hook` to the `[[hooks]]` array
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hook's working directory is not valid UTF-8.
+    pub fn add_hook(&mut self, hook: &HookConfig) -> Result<()> {
+        let config = serde_json::to_value(hook)?;
+        let hooks = self
+            .doc
+            .entry("hooks")
+            .or_insert(Item::ArrayOfTables(ArrayOfTables::new()));
+        match hooks {
+            Item::ArrayOfTables(tables) => tables.push(to_table(&config)),
+            // A hand-written `hooks = [...]` stays inline
+            Item::Value(Value::Array(array)) => {
+                if let Some(value) = to_toml(&config) {
+                    array.push(value);
+                }
+            }
+            _ => unreachable!("hooks is an array in a parsed manifest"),
+        }
+        Ok(())
+    }
+
+    /// Remove every hook for `event`
+    ///
+    /// Returns the number of hooks removed.
+    pub fn remove_hooks(&mut self, event: HookEvent) -> usize {
+        let event = serde_json::to_value(event).expect("hook events serialize to strings");
+        let matches =
+            |table: &dyn TableLike| table.get("event").and_then(Item::as_str) == event.as_str();
+        match self.doc.get_mut("hooks") {
+            Some(Item::ArrayOfTables(tables)) => {
+                let before = tables.len();
+                tables.retain(|table| !matches(table));
+                before - tables.len()
+            }
+            Some(Item::Value(Value::Array(array))) => {
+                let before = array.len();
+                array.retain(|value| !value.as_inline_table().is_some_and(|t| matches(t)));
+                before - array.len()
+            }
+            _ => 0,
+        }
+    }
+
+    /// Replace everything but `[core]` with the settings of `other`
+    ///
+    /// The `[core]` table is kept as it is.
+    pub fn replace_settings(&mut self, other: &ManifestEditor) {
+        let keys: Vec<String> = self
+            .doc
+            .iter()
+            .map(|(key, _)| key.to_string())
+            .filter(|key| key != "core")
+            .collect();
+        for key in keys {
+            self.doc.remove(&key);
+        }
+        for (key, item) in other.doc.iter().filter(|(key, _)| *key != "core") {
+            self.doc.insert(key, item.clone());
+        }
+    }
+
+    /// Remove the preset `name`
+    ///
+    /// Returns `false` if the preset was not configured. An implicit
+    /// `[presets]` table left empty is removed with it.
+    pub fn remove_preset(&mut self, name: &str) -> bool {
+        let Some(presets) = self
+            .doc
+            .get_mut("presets")
+            .and_then(Item::as_table_like_mut)
+        else {
+            return false;
+        };
+        if presets.remove(name).is_none() {
+            return false;
+        }
+
+        if presets.is_empty()
+            && self
+                .doc
+                .get("presets")
+                .and_then(Item::as_table)
+                .is_some_and(Table::is_implicit)
+        {
+            self.doc.remove("presets");
+        }
+        true
+    }
+
+    /// The string array `key`, created at the top level if missing
+    fn array_mut(&mut self, key: &str) -> &mut Array {
+        self.doc
+            .entry(key)
+            .or_insert(toml_edit::value(Array::new()))
+            .as_array_mut()
+            .expect("tools and rules are arrays in a parsed manifest")
+    }
+
+    /// The table at `section`
+    ///
+    /// Missing tables, and values that are not tables, along `section` are
+    /// replaced by new implicit tables.
+    fn table_mut(&mut self, section: &[&str]) -> &mut dyn TableLike {
+        let mut table: &mut dyn TableLike = self.doc.as_table_mut();
+        for key in section {
+            let item = table.entry(key).or_insert(Item::None);
+            if !item.is_table_like() {
+                let mut new = Table::new();
+                new.set_implicit(true);
+                *item = Item::Table(new);
+            }
+            table = item
+                .as_table_like_mut()
+                .expect("non-table items were just replaced");
+        }
+        table
+    }
+}
+
+impl fmt::Display for ManifestEditor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.doc)
+    }
+}
+
+/// Convert a JSON object to a TOML table, leaving out null values
+///
+/// Anything but an object gives an empty table.
+fn to_table(config: &JsonValue) -> Table {
+    let mut table = Table::new();
+    if let JsonValue::Object(fields) = config {
+        for (key, value) in fields {
+            if let Some(value) = to_toml(value) {
+                table.insert(key, Item::Value(value));
+            }
+        }
+    }
+    table
+}
+
+/// Convert a JSON value to TOML, or `None` for null
+fn to_toml(json: &JsonValue) -> Option<Value> {
+    Some(match json {
+        JsonValue::Null => return None,
+        JsonValue::Bool(b) => Value::from(*b),
+        JsonValue::Number(n) => match n.as_i64() {
+            Some(i) => Value::from(i),
+            None => Value::from(n.as_f64()?),
+        },
+        JsonValue::String(s) => Value::from(s.as_str()),
+        JsonValue::Array(items) => Value::Array(items.iter().filter_map(to_toml).collect()),
+        JsonValue::Object(fields) => {
+            let mut table = InlineTable::new();
+            for (key, value) in fields {
+                if let Some(value) = to_toml(value) {
+                    table.insert(key, value);
+                }
+            }
+            Value::InlineTable(table)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ANNOTATED: &str = r#"# Repository Manager configuration
+
+# Agents this team uses.
+# Keep in sync with the onboarding doc.
+tools = [
+    "claude", # primary
+    "cursor",
+]
+
+[core]
+mode = "standard" # no worktrees here
+
+# Python tooling
+[presets."env:python"]
+version = "3.12"
+"#;
+
+    #[test]
+    fn test_add_remove_tool_keeps_comments() {
+        let mut editor = ManifestEditor::parse(ANNOTATED).unwrap();
+        assert!(editor.add_tool("zed"));
+        assert!(!editor.add_tool("zed"));
+
+        let added = editor.to_string();
+        assert!(added.contains(
+            "# Agents this team uses.\n# Keep in sync with the onboarding doc.\ntools = ["
+        ));
+        assert!(added.contains("    \"cursor\",\n    \"zed\",\n]"));
+        assert_eq!(
+            editor.manifest().unwrap().tools,
+            ["claude", "cursor", "zed"]
+        );
+
+        assert!(editor.remove_tool("zed"));
+        assert!(!editor.remove_tool("zed"));
+        assert_eq!(editor.to_string(), ANNOTATED);
+    }
+
+    #[test]
+    fn test_remove_first_tool_of_inline_array() {
+        let mut editor = ManifestEditor::parse("tools = [\"claude\", \"cursor\"]\n").unwrap();
+        assert!(editor.remove_tool("claude"));
+        assert_eq!(editor.to_string(), "tools = [\"cursor\"]\n");
+    }
+
+    #[test]
+    fn test_add_tool_creates_array() {
+        let mut editor = ManifestEditor::parse("[core]\nmode = \"standard\"\n").unwrap();
+        editor.add_tool("claude");
+        assert_eq!(
+            editor.to_string(),
+            "tools = [\"claude\"]\n[core]\nmode = \"standard\"\n"
+        );
+    }
+
+    #[test]
+    fn test_set_mode_keeps_comment() {
+        let mut editor = ManifestEditor::parse(ANNOTATED).unwrap();
+        editor.set_mode("worktrees");
+        assert!(
+            editor
+                .to_string()
+                .contains("mode = \"worktrees\" # no worktrees here\n")
+        );
+        assert!(
+            editor
+                .to_string()
+                .starts_with("# Repository Manager configuration\n")
+        );
+    }
+
+    #[test]
+    fn test_add_remove_preset() {
+        let mut editor = ManifestEditor::parse(ANNOTATED).unwrap();
+        assert!(!editor.add_preset("env:python", &json!({})));
+        assert!(editor.add_preset(
+            "env:node",
+            &json!({"version": "20", "lts": true, "unset": null})
+        ));
+
+        let manifest = editor.manifest().unwrap();
+        assert_eq!(
+            manifest.presets["env:node"],
+            json!({"version": "20", "lts": true})
+        );
+        assert_eq!(manifest.presets["env:python"], json!({"version": "3.12"}));
+
+        assert!(editor.remove_preset("env:node"));
+        assert!(!editor.remove_preset("env:node"));
+        assert_eq!(editor.to_string(), ANNOTATED);
+    }
+
+    #[test]
+    fn test_remove_last_preset_drops_table() {
+        let mut editor = ManifestEditor::parse("tools = []\n").unwrap();
+        editor.add_preset("env:python", &json!({}));
+        assert!(editor.to_string().contains("[presets.\"env:python\"]"));
+        editor.remove_preset("env:python");
+        assert_eq!(editor.to_string(), "tools = []\n");
+    }
+
+    #[test]
+    fn test_add_remove_hooks_keeps_comments() {
+        let mut editor = ManifestEditor::parse(ANNOTATED).unwrap();
+        let hook = HookConfig {
+            event: HookEvent::PreSync,
+            command: "cargo".to_string(),
+            args: vec!["check".to_string()],
+            working_dir: None,
+            timeout: None,
+        };
+        editor.add_hook(&hook).unwrap();
+        editor.add_hook(&hook).unwrap();
+
+        let hooks = editor.manifest().unwrap().hooks;
+        assert_eq!(hooks.len(), 2);
+        assert_eq!(hooks[0].args, ["check"]);
+
+        assert_eq!(editor.remove_hooks(HookEvent::PostSync), 0);
+        assert_eq!(editor.remove_hooks(HookEvent::PreSync), 2);
+        assert_eq!(editor.to_string(), ANNOTATED);
+    }
+
+    #[test]
+    fn test_remove_inline_hooks() {
+        let mut editor = ManifestEditor::parse(
+            "hooks = [{ event = \"pre-sync\", command = \"a\" }, { event = \"post-sync\", command = \"b\" }]\n",
+        )
+        .unwrap();
+        assert_eq!(editor.remove_hooks(HookEvent::PreSync), 1);
+        let hooks = editor.manifest().unwrap().hooks;
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].command, "b");
+    }
+
+    #[test]
+    fn test_add_entry_creates_nested_tables() {
+        let mut editor = ManifestEditor::parse("tools = []\n").unwrap();
+        let server = json!({"transport": {"type": "stdio", "command": "npx"}});
+        assert!(editor.add_entry(&["mcp", "servers"], "github", &server));
+        assert!(!editor.add_entry(&["mcp", "servers"], "github", &json!({})));
+
+        assert!(editor.to_string().contains("[mcp.servers.github]\n"));
+        assert!(
+            editor
+                .manifest()
+                .unwrap()
+                .mcp
+                .servers
+                .contains_key("github")
+        );
+    }
+
+    #[test]
+    fn test_set_key_keeps_rest_of_table() {
+        let mut editor = ManifestEditor::parse(
+            "[extensions.\"vaultspec\"]\nref = \"v0.1.0\" # pinned\nsource = \"old\"\n",
+        )
+        .unwrap();
+        editor.set_key(&["extensions", "vaultspec"], "source", &json!("new"));
+        assert_eq!(
+            editor.to_string(),
+            "[extensions.\"vaultspec\"]\nref = \"v0.1.0\" # pinned\nsource = \"new\"\n"
+        );
+
+        // A value that is not a table is replaced by one
+        let mut editor = ManifestEditor::parse("[extensions]\nvaultspec = \"old\"\n").unwrap();
+        editor.set_key(&["extensions", "vaultspec"], "source", &json!("new"));
+        assert_eq!(
+            editor.manifest().unwrap().extensions["vaultspec"],
+            json!({"source": "new"})
+        );
+    }
+
+    #[test]
+    fn test_replace_settings_keeps_core() {
+        let mut editor = ManifestEditor::parse(ANNOTATED).unwrap();
+        let other =
+            ManifestEditor::parse("tools = [\"zed\"]\n[core]\nmode = \"worktrees\"\n").unwrap();
+        editor.replace_settings(&other);
+
+        let manifest = editor.manifest().unwrap();
+        assert_eq!(manifest.tools, ["zed"]);
+        assert!(manifest.presets.is_empty());
+        assert!(
+            editor
+                .to_string()
+                .contains("mode = \"standard\" # no worktrees here\n")
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_config() {
+        assert!(ManifestEditor::parse("tools = \"claude\"\n").is_err());
+    }
+}
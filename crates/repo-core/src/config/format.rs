@@ -0,0 +1,375 @@
+//! Canonical formatting of config.toml
+//!
+//! [`format_config`] rewrites a hand-edited config into the layout
+//! [`Manifest::to_toml`](super::Manifest::to_toml) produces, while keeping
+//! its comments:
+//!
+//! - `tools` is sorted
+//! - `[core]` comes first, then the other known sections in a fixed order,
+//!   then unknown tables in their original order; preset, extension and
+//!   tool settings tables are ordered by key
+//! - one space around `=`, after commas and before trailing comments
+//! - one blank line before each table and at most one between values
+//!
+//! Comments before a value or table stay with it, and a trailing comment
+//! stays on its line. Arrays containing comments keep their layout and
+//! order.
+
+use toml_edit::{Array, Decor, DocumentMut, Item, Table, Value};
+
+use super::Manifest;
+use crate::Result;
+
+/// Top-level tables, in the order [`Manifest::to_toml`] writes them
+const TABLE_ORDER: &[&str] = &[
+    "core",
+    "presets",
+    "extensions",
+    "tool_settings",
+    "hooks",
+    "mcp",
+    "sync",
+    "open",
+];
+
+/// Top-level tables whose sub-tables are ordered by key
+const KEYED_TABLES: &[&str] = &["presets", "extensions", "tool_settings"];
+
+/// Indentation of the values of a multi-line array
+const ARRAY_INDENT: &str = "    ";
+
+/// Rewrite config.toml `content` into canonical form
+///
+/// Formatting canonical content returns it unchanged.
+///
+/// # Errors
+///
+/// Returns an error if `content` is not a valid config.toml.
+pub fn format_config(content: &str) -> Result<String> {
+    // Reject anything that would not load, not just invalid TOML
+    Manifest::parse(content)?;
+    let mut doc: DocumentMut = content.parse()?;
+
+    if let Some(Item::Value(Value::Array(tools))) = doc.get_mut("tools")
+        && !has_comments(tools)
+    {
+        tools.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+    }
+    order_tables(doc.as_table_mut());
+    format_table(doc.as_table_mut());
+
+    let comments = comment_lines(doc.trailing().as_str().unwrap_or_default());
+    let trailing = if comments.is_empty() {
+        String::new()
+    } else {
+        format!("\n{}", comments.concat())
+    };
+    doc.set_trailing(trailing);
+
+    // Tables always get a blank line before them, even the first
+    let formatted = doc.to_string();
+    let formatted = formatted.trim_start_matches('\n').trim_end();
+    if formatted.is_empty() {
+        Ok(String::new())
+    } else {
+        Ok(format!("{}\n", formatted))
+    }
+}
+
+/// Whether `item` is written as a `[table]` or `[[array]]` header
+fn is_section(item: &Item) -> bool {
+    match item {
+        Item::Table(table) => !table.is_dotted(),
+        Item::ArrayOfTables(_) => true,
+        _ => false,
+    }
+}
+
+/// Document position of a section, for keeping unknown tables in order
+fn position(item: &Item) -> isize {
+    let position = match item {
+        Item::Table(table) => table.position(),
+        Item::ArrayOfTables(array) => array.iter().next().and_then(Table::position),
+        _ => None,
+    };
+    position.unwrap_or(isize::MAX)
+}
+
+/// Keys of the sections in `table`, in original document order
+fn section_keys(table: &Table) -> Vec<String> {
+    let mut keys: Vec<(isize, String)> = table
+        .iter()
+        .filter(|(_, item)| is_section(item))
+        .map(|(key, item)| (position(item), key.to_string()))
+        .collect();
+    keys.sort();
+    keys.into_iter().map(|(_, key)| key).collect()
+}
+
+/// Renumber the sections of the document so they are written in
+/// canonical order
+fn order_tables(root: &mut Table) {
+    let mut keys = section_keys(root);
+    keys.sort_by_key(|key| {
+        TABLE_ORDER
+            .iter()
+            .position(|known| known == key)
+            .unwrap_or(TABLE_ORDER.len())
+    });
+
+    let mut next = 0;
+    for key in keys {
+        let by_key = KEYED_TABLES.contains(&key.as_str());
+        if let Some(item) = root.get_mut(&key) {
+            assign_positions(item, by_key, &mut next);
+        }
+    }
+}
+
+/// Number `item` and the sections nested in it from `next` on
+fn assign_positions(item: &mut Item, by_key: bool, next: &mut isize) {
+    let tables: Vec<&mut Table> = match item {
+        Item::Table(table) => vec![table],
+        Item::ArrayOfTables(array) => array.iter_mut().collect(),
+        _ => return,
+    };
+    for table in tables {
+        table.set_position(*next);
+        *next += 1;
+
+        let mut keys = section_keys(table);
+        if by_key {
+            keys.sort();
+        }
+        for key in keys {
+            if let Some(child) = table.get_mut(&key) {
+                assign_positions(child, false, next);
+            }
+        }
+    }
+}
+
+/// Comment lines of a decor string, each ending in a newline
+fn comment_lines(raw: &str) -> Vec<String> {
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('#'))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// A trailing comment, with the space that separates it from its value
+fn trailing_comment(raw: &str) -> String {
+    match raw.trim() {
+        comment if comment.starts_with('#') => format!(" {}", comment),
+        _ => String::new(),
+    }
+}
+
+/// Whether a decor string contains an empty line, not just indentation
+fn has_blank_line(raw: &str) -> bool {
+    raw.split_inclusive('\n')
+        .any(|line| line.ends_with('\n') && line.trim().is_empty())
+}
+
+fn prefix(decor: &Decor) -> &str {
+    decor.prefix().and_then(|p| p.as_str()).unwrap_or_default()
+}
+
+fn suffix(decor: &Decor) -> &str {
+    decor.suffix().and_then(|s| s.as_str()).unwrap_or_default()
+}
+
+/// Whether any value of `array` has a comment before or after it
+fn has_comments(array: &Array) -> bool {
+    array
+        .iter()
+        .any(|value| prefix(value.decor()).contains('#') || suffix(value.decor()).contains('#'))
+        || array.trailing().as_str().unwrap_or_default().contains('#')
+}
+
+/// Normalize the spacing of a table's header, values and sub-tables
+fn format_table(table: &mut Table) {
+    let header = table.decor_mut();
+    let comments = comment_lines(prefix(header)).concat();
+    let comment = trailing_comment(suffix(header));
+    *header = Decor::new(format!("\n{}", comments), comment);
+
+    let mut first = true;
+    for (mut key, item) in table.iter_mut() {
+        match item {
+            Item::Value(value) => {
+                let decor = key.leaf_decor();
+                // Keep a blank line between values, but not after the header
+                let blank = !first && has_blank_line(prefix(decor));
+                let comments = comment_lines(prefix(decor)).concat();
+                key.fmt();
+                *key.leaf_decor_mut() = Decor::new(
+                    format!("{}{}", if blank { "\n" } else { "" }, comments),
+                    " ",
+                );
+                format_value(value);
+                first = false;
+            }
+            Item::Table(child) if !child.is_dotted() => {
+                key.fmt();
+                format_table(child);
+            }
+            Item::ArrayOfTables(array) => {
+                key.fmt();
+                for child in array.iter_mut() {
+                    format_table(child);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Normalize the spacing of a value and the values nested in it
+fn format_value(value: &mut Value) {
+    let comment = trailing_comment(suffix(value.decor()));
+    match value {
+        Value::Array(array) => format_array(array),
+        Value::InlineTable(table) => table.fmt(),
+        _ => {}
+    }
+    *value.decor_mut() = Decor::new(" ", comment);
+}
+
+/// Lay out an array on one line, or one value per line if it already
+/// spans several
+fn format_array(array: &mut Array) {
+    if has_comments(array) {
+        return;
+    }
+    let multiline = array
+        .iter()
+        .any(|value| prefix(value.decor()).contains('\n'))
+        || array.trailing().as_str().unwrap_or_default().contains('\n');
+
+    for value in array.iter_mut() {
+        match value {
+            Value::Array(nested) => format_array(nested),
+            Value::InlineTable(table) => table.fmt(),
+            _ => {}
+        }
+    }
+    if multiline {
+        for value in array.iter_mut() {
+            *value.decor_mut() = Decor::new(format!("\n{}", ARRAY_INDENT), "");
+        }
+        array.set_trailing_comma(true);
+        array.set_trailing("\n");
+    } else {
+        // Only resets the spacing between values, not inside nested ones
+        array.fmt();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_sorts_tools_and_orders_tables() {
+        let content = r#"tools = ["vscode","claude",   "cursor"]
+[sync]
+auto_sync_on_switch=true
+
+[presets."env:rust"]
+[presets."env:python"]
+version =   "3.12"
+[core]
+mode="worktrees"
+"#;
+
+        assert_eq!(
+            format_config(content).unwrap(),
+            r#"tools = ["claude", "cursor", "vscode"]
+
+[core]
+mode = "worktrees"
+
+[presets."env:python"]
+version = "3.12"
+
+[presets."env:rust"]
+
+[sync]
+auto_sync_on_switch = true
+"#
+        );
+    }
+
+    #[test]
+    fn test_format_keeps_comments() {
+        let content = r#"# Repository configuration
+tools=["b","a"]  # enabled tools
+
+
+# Rules applied everywhere
+rules = [
+  "one",
+      "two"
+]
+
+# Core settings
+[core]   # the basics
+mode = "standard"
+"#;
+        assert_eq!(
+            format_config(content).unwrap(),
+            r#"# Repository configuration
+tools = ["a", "b"] # enabled tools
+
+# Rules applied everywhere
+rules = [
+    "one",
+    "two",
+]
+
+# Core settings
+[core] # the basics
+mode = "standard"
+"#
+        );
+    }
+
+    #[test]
+    fn test_format_removes_indentation() {
+        assert_eq!(
+            format_config("[core]\n  mode = \"standard\"\n    verify_writes = true\n").unwrap(),
+            "[core]\nmode = \"standard\"\nverify_writes = true\n"
+        );
+    }
+
+    #[test]
+    fn test_format_leaves_commented_arrays_alone() {
+        let content =
+            "tools = [\n  \"zed\", # editor\n  \"aider\",\n]\n\n[core]\nmode = \"standard\"\n";
+        assert_eq!(format_config(content).unwrap(), content);
+    }
+
+    #[test]
+    fn test_format_is_idempotent() {
+        let manifest = Manifest::parse(
+            "tools = [\"cursor\", \"claude\"]\n\n[core]\nmode = \"standard\"\n\n\
+             [[hooks]]\nevent = \"post-sync\"\ncommand = \"echo\"\nargs = [\"done\"]\n\n\
+             [extensions.vaultspec]\nsource = \"https://example.com/vaultspec\"\n",
+        )
+        .unwrap();
+        let canonical = manifest.to_toml();
+        assert_eq!(format_config(&canonical).unwrap(), canonical);
+
+        let formatted =
+            format_config("[core]\nmode='standard'\n[open]\ndefault_tool='zed'\n").unwrap();
+        assert_eq!(format_config(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn test_format_rejects_invalid_config() {
+        assert!(format_config("tools = [").is_err());
+        assert!(format_config("tools = \"cursor\"").is_err());
+    }
+}
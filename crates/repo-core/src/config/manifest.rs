@@ -5,30 +5,143 @@
 
 use crate::Result;
 use crate::hooks::HookConfig;
-use serde::{Deserialize, Serialize};
+use repo_extensions::McpMergePolicy;
+use repo_fs::{LayoutMode, NormalizedPath};
+use repo_meta::schema::McpServerConfig;
+use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 fn default_mode() -> String {
     "worktrees".to_string()
 }
 
+/// Serialize a list in sorted order
+fn sorted_list<S: Serializer>(
+    list: &[String],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    let mut sorted: Vec<&String> = list.iter().collect();
+    sorted.sort();
+    sorted.serialize(serializer)
+}
+
+/// Serialize a map with its keys in sorted order
+fn sorted_map<S: Serializer>(
+    map: &HashMap<String, Value>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
 /// Core configuration section
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoreSection {
     /// Repository mode: "standard" or "worktree"
     #[serde(default = "default_mode")]
     pub mode: String,
+
+    /// Workspace layout recorded at init, preferred over probing the
+    /// filesystem when it still matches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<LayoutMode>,
+
+    /// Read back and checksum every managed file and ledger write
+    /// (for unreliable filesystems such as flaky network mounts)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub verify_writes: bool,
+
+    /// Include the ledger in commits made by `repo sync --commit`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub commit_ledger: bool,
+
+    /// Extra key patterns (e.g. `*_PASSWORD`) whose values are hidden when
+    /// configs are shown to agents, on top of `*_TOKEN`, `*_KEY` and
+    /// `*_SECRET`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secret_patterns: Vec<String>,
+
+    /// Path of the enclosing repository, relative to this one, when this
+    /// repository was initialized as a sub-package with `repo init --nested`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
 }
 
 impl Default for CoreSection {
     fn default() -> Self {
         Self {
             mode: default_mode(),
+            layout: None,
+            verify_writes: false,
+            commit_ledger: false,
+            secret_patterns: Vec::new(),
+            parent: None,
         }
     }
 }
 
+/// MCP server configuration section
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct McpSection {
+    /// Servers pushed to every enabled MCP-capable tool by `repo mcp sync`,
+    /// keyed by server name
+    ///
+    /// ```toml
+    /// [mcp.servers.github]
+    /// env = { GITHUB_TOKEN = "${GITHUB_TOKEN}" }
+    ///
+    /// [mcp.servers.github.transport]
+    /// type = "stdio"
+    /// command = "npx"
+    /// args = ["-y", "@modelcontextprotocol/server-github"]
+    /// ```
+    #[serde(default)]
+    pub servers: BTreeMap<String, McpServerConfig>,
+
+    /// How sync resolves a server name defined differently by two
+    /// extensions: `last_wins` (the default), `error`, or `rename`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_conflict: Option<McpMergePolicy>,
+}
+
+impl McpSection {
+    /// Whether no servers are declared and no settings are changed
+    pub fn is_empty(&self) -> bool {
+        self.servers.is_empty() && self.on_conflict.is_none()
+    }
+}
+
+/// Sync behaviour outside of `repo sync` itself
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncSection {
+    /// Sync tool configurations into a worktree when a branch checkout or
+    /// branch creation materializes it (worktrees mode)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_sync_on_switch: bool,
+}
+
+impl SyncSection {
+    /// Whether every setting is at its default
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Settings for `repo open`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenSection {
+    /// Tool slug to open worktrees with when none is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_tool: Option<String>,
+}
+
+impl OpenSection {
+    /// Whether every setting is at its default
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Repository configuration manifest parsed from config.toml
 ///
 /// This struct represents a single configuration file. Multiple manifests
@@ -46,11 +159,11 @@ pub struct Manifest {
     /// - "env:python" - Python environment configuration
     /// - "tool:linter" - Linter tool configuration
     /// - "config:editor" - Editor configuration
-    #[serde(default)]
+    #[serde(default, serialize_with = "sorted_map")]
     pub presets: HashMap<String, Value>,
 
     /// List of tools to configure
-    #[serde(default)]
+    #[serde(default, serialize_with = "sorted_list")]
     pub tools: Vec<String>,
 
     /// List of rules to apply
@@ -68,12 +181,37 @@ pub struct Manifest {
     /// source = "https://github.com/org/vaultspec"
     /// ref = "v0.1.0"
     /// ```
-    #[serde(default)]
+    #[serde(default, serialize_with = "sorted_map")]
     pub extensions: HashMap<String, Value>,
 
+    /// Per-tool settings keyed by tool slug
+    ///
+    /// ```toml
+    /// [tool_settings.cursor]
+    /// format = "mdc"
+    /// ```
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "sorted_map"
+    )]
+    pub tool_settings: HashMap<String, Value>,
+
     /// Lifecycle hooks
     #[serde(default)]
     pub hooks: Vec<HookConfig>,
+
+    /// MCP servers managed across tools
+    #[serde(default, skip_serializing_if = "McpSection::is_empty")]
+    pub mcp: McpSection,
+
+    /// Sync settings
+    #[serde(default, skip_serializing_if = "SyncSection::is_empty")]
+    pub sync: SyncSection,
+
+    /// `repo open` settings
+    #[serde(default, skip_serializing_if = "OpenSection::is_empty")]
+    pub open: OpenSection,
 }
 
 impl Manifest {
@@ -116,18 +254,31 @@ impl Manifest {
         Self {
             core: CoreSection {
                 mode: default_mode(),
+                layout: None,
+                verify_writes: false,
+                commit_ledger: false,
+                secret_patterns: Vec::new(),
+                parent: None,
             },
             presets: HashMap::new(),
             tools: Vec::new(),
             rules: Vec::new(),
             extensions: HashMap::new(),
+            tool_settings: HashMap::new(),
             hooks: Vec::new(),
+            mcp: McpSection::default(),
+            sync: SyncSection::default(),
+            open: OpenSection::default(),
         }
     }
 
     /// Serialize this manifest to a clean TOML string
     ///
-    /// Uses serde serialization with proper escaping for all values.
+    /// Uses serde serialization with proper escaping for all values. The
+    /// output is deterministic: top-level values come first, then `[core]`,
+    /// then the other sections in a fixed order; tools are sorted and preset,
+    /// extension and tool settings tables are ordered by key. Rewriting a
+    /// config therefore only changes the lines that were edited.
     pub fn to_toml(&self) -> String {
         match toml::to_string_pretty(self) {
             Ok(s) => s,
@@ -142,6 +293,14 @@ impl Manifest {
         }
     }
 
+    /// Root of the enclosing repository of a nested repository at `root`
+    ///
+    /// Returns `None` unless the repository was initialized with
+    /// `repo init --nested`.
+    pub fn parent_root(&self, root: &NormalizedPath) -> Option<NormalizedPath> {
+        self.core.parent.as_deref().map(|parent| root.join(parent))
+    }
+
     /// Merge another manifest into this one
     ///
     /// The `other` manifest takes precedence for scalar values.
@@ -158,6 +317,30 @@ impl Manifest {
         // (even if set to the default value, it may be an explicit choice)
         self.core.mode = other.core.mode.clone();
 
+        // Layout: recorded per checkout, so only an explicit value overrides
+        if other.core.layout.is_some() {
+            self.core.layout = other.core.layout;
+        }
+
+        // Parent repository: recorded per repository, so only an explicit
+        // value overrides
+        if other.core.parent.is_some() {
+            self.core.parent = other.core.parent.clone();
+        }
+
+        // Write verification: enabled if any layer opts in
+        self.core.verify_writes |= other.core.verify_writes;
+
+        // Ledger commits: enabled if any layer opts in
+        self.core.commit_ledger |= other.core.commit_ledger;
+
+        // Secret patterns: extend with unique values
+        for pattern in &other.core.secret_patterns {
+            if !self.core.secret_patterns.contains(pattern) {
+                self.core.secret_patterns.push(pattern.clone());
+            }
+        }
+
         // Presets: deep merge
         for (key, other_value) in &other.presets {
             if let Some(base_value) = self.presets.get_mut(key) {
@@ -192,8 +375,33 @@ impl Manifest {
             }
         }
 
+        // Tool settings: deep merge (same strategy as presets)
+        for (key, other_value) in &other.tool_settings {
+            if let Some(base_value) = self.tool_settings.get_mut(key) {
+                deep_merge_value(base_value, other_value);
+            } else {
+                self.tool_settings.insert(key.clone(), other_value.clone());
+            }
+        }
+
         // Hooks: extend (append all from other)
         self.hooks.extend(other.hooks.iter().cloned());
+
+        // MCP servers: other replaces same-named servers whole
+        for (name, server) in &other.mcp.servers {
+            self.mcp.servers.insert(name.clone(), server.clone());
+        }
+        if other.mcp.on_conflict.is_some() {
+            self.mcp.on_conflict = other.mcp.on_conflict;
+        }
+
+        // Auto-sync: enabled if any layer opts in
+        self.sync.auto_sync_on_switch |= other.sync.auto_sync_on_switch;
+
+        // Default tool: other overrides if set
+        if other.open.default_tool.is_some() {
+            self.open.default_tool = other.open.default_tool.clone();
+        }
     }
 }
 
@@ -246,6 +454,7 @@ fn deep_merge_value(base: &mut Value, other: &Value) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use repo_meta::schema::McpTransportConfig;
 
     #[test]
     fn test_default_mode() {
@@ -258,6 +467,38 @@ mod tests {
         assert_eq!(manifest.core.mode, "worktrees");
     }
 
+    #[test]
+    fn test_parse_recorded_layout() {
+        let manifest =
+            Manifest::parse("[core]\nmode = \"standard\"\nlayout = \"in-repo-worktrees\"\n")
+                .unwrap();
+        assert_eq!(manifest.core.layout, Some(LayoutMode::InRepoWorktrees));
+        assert!(
+            manifest
+                .to_toml()
+                .contains("layout = \"in-repo-worktrees\"")
+        );
+
+        let manifest = Manifest::parse("[core]\nmode = \"standard\"\n").unwrap();
+        assert_eq!(manifest.core.layout, None);
+        assert!(!manifest.to_toml().contains("layout"));
+    }
+
+    #[test]
+    fn test_parent_root() {
+        let manifest =
+            Manifest::parse("[core]\nmode = \"standard\"\nparent = \"../..\"\n").unwrap();
+        assert_eq!(
+            manifest.parent_root(&NormalizedPath::new("/work/mono/packages/api")),
+            Some(NormalizedPath::new("/work/mono"))
+        );
+        assert!(manifest.to_toml().contains("parent = \"../..\""));
+
+        let manifest = Manifest::parse("[core]\nmode = \"standard\"\n").unwrap();
+        assert_eq!(manifest.parent_root(&NormalizedPath::new("/work")), None);
+        assert!(!manifest.to_toml().contains("parent"));
+    }
+
     #[test]
     fn test_parse_tools_and_rules() {
         // Note: tools and rules must be BEFORE [core] section to be top-level
@@ -363,6 +604,29 @@ source = "https://example.com/new.git"
         assert!(base.extensions.contains_key("new-ext"));
     }
 
+    #[test]
+    fn test_merge_tool_settings() {
+        let mut base = Manifest::parse(
+            r#"
+[tool_settings.cursor]
+format = "legacy"
+"#,
+        )
+        .unwrap();
+        let local = Manifest::parse(
+            r#"
+[tool_settings.cursor]
+format = "mdc"
+"#,
+        )
+        .unwrap();
+
+        base.merge(&local);
+        assert_eq!(base.tool_settings["cursor"]["format"], "mdc");
+        assert!(base.to_toml().contains("[tool_settings.cursor]"));
+        assert!(!Manifest::empty().to_toml().contains("tool_settings"));
+    }
+
     #[test]
     fn test_extensions_toml_round_trip() {
         let toml_content = r#"
@@ -386,4 +650,159 @@ ref = "v0.1.0"
             reparsed.extensions["vaultspec"]["ref"]
         );
     }
+
+    #[test]
+    fn test_mcp_servers_round_trip() {
+        let toml_content = r#"
+tools = ["cursor"]
+
+[mcp.servers.github]
+env = { GITHUB_TOKEN = "${GITHUB_TOKEN}" }
+
+[mcp.servers.github.transport]
+type = "stdio"
+command = "npx"
+args = ["-y", "@modelcontextprotocol/server-github"]
+
+[mcp.servers.docs.transport]
+type = "http"
+url = "https://example.com/mcp"
+"#;
+        let manifest = Manifest::parse(toml_content).unwrap();
+        let reparsed = Manifest::parse(&manifest.to_toml()).unwrap();
+
+        assert_eq!(
+            reparsed.mcp.servers.keys().collect::<Vec<_>>(),
+            ["docs", "github"]
+        );
+        let github = &reparsed.mcp.servers["github"];
+        assert!(matches!(
+            &github.transport,
+            McpTransportConfig::Stdio { command, args, .. }
+                if command == "npx" && args.len() == 2
+        ));
+        assert_eq!(
+            github.env.as_ref().unwrap()["GITHUB_TOKEN"],
+            "${GITHUB_TOKEN}"
+        );
+    }
+
+    #[test]
+    fn test_merge_mcp_servers_replaces_by_name() {
+        let mut base = Manifest::parse(
+            "[mcp.servers.a.transport]\ntype = \"stdio\"\ncommand = \"old\"\n\n\
+             [mcp.servers.b.transport]\ntype = \"stdio\"\ncommand = \"b\"\n",
+        )
+        .unwrap();
+        let local =
+            Manifest::parse("[mcp.servers.a.transport]\ntype = \"http\"\nurl = \"http://x\"\n")
+                .unwrap();
+
+        base.merge(&local);
+
+        assert_eq!(base.mcp.servers.len(), 2);
+        assert!(matches!(
+            base.mcp.servers["a"].transport,
+            McpTransportConfig::Http { .. }
+        ));
+    }
+
+    #[test]
+    fn test_mcp_on_conflict_policy() {
+        let mut base = Manifest::parse(
+            "[mcp]
+on_conflict = \"rename\"\n",
+        )
+        .unwrap();
+        assert_eq!(base.mcp.on_conflict, Some(McpMergePolicy::Rename));
+
+        // A layer that leaves the policy unset keeps the inherited one
+        base.merge(&Manifest::parse("tools = [\"cursor\"]\n").unwrap());
+        assert_eq!(base.mcp.on_conflict, Some(McpMergePolicy::Rename));
+
+        base.merge(&Manifest::parse("[mcp]\non_conflict = \"error\"\n").unwrap());
+        assert_eq!(base.mcp.on_conflict, Some(McpMergePolicy::Error));
+    }
+
+    #[test]
+    fn test_sync_auto_sync_on_switch() {
+        let mut base = Manifest::parse("tools = [\"cursor\"]\n").unwrap();
+        assert!(!base.sync.auto_sync_on_switch);
+        assert!(!base.to_toml().contains("[sync]"));
+
+        base.merge(&Manifest::parse("[sync]\nauto_sync_on_switch = true\n").unwrap());
+        assert!(base.sync.auto_sync_on_switch);
+        let reparsed = Manifest::parse(&base.to_toml()).unwrap();
+        assert!(reparsed.sync.auto_sync_on_switch);
+    }
+
+    #[test]
+    fn test_to_toml_is_sorted() {
+        let mut manifest = Manifest::parse(
+            r#"tools = ["zed", "cursor"]
+
+[core]
+mode = "standard"
+
+[presets."env:python"]
+version = "3.12"
+
+[presets."env:node"]
+version = "20"
+
+[extensions.zeta]
+source = "./zeta"
+
+[extensions.alpha]
+source = "./alpha"
+"#,
+        )
+        .unwrap();
+        let text = manifest.to_toml();
+
+        assert!(text.starts_with("tools = [\n    \"cursor\",\n    \"zed\",\n]"));
+        let position = |needle: &str| text.find(needle).unwrap();
+        assert!(position("[core]") < position("[presets.\"env:node\"]"));
+        assert!(position("[presets.\"env:node\"]") < position("[presets.\"env:python\"]"));
+        assert!(position("[extensions.alpha]") < position("[extensions.zeta]"));
+
+        // A map built with a different hasher state serializes identically
+        manifest.presets = manifest.presets.clone().into_iter().collect();
+        assert_eq!(manifest.to_toml(), text);
+        assert_eq!(Manifest::parse(&text).unwrap().to_toml(), text);
+    }
+
+    #[test]
+    fn test_add_then_remove_tool_is_byte_identical() {
+        let original = Manifest::parse(
+            "tools = [\"zed\"]\n\n[core]\nmode = \"standard\"\n\n\
+             [presets.\"tool:b\"]\n\n[presets.\"tool:a\"]\n\n\
+             [tool_settings.zed]\nx = 1\n\n[tool_settings.cursor]\nx = 2\n",
+        )
+        .unwrap()
+        .to_toml();
+
+        let mut manifest = Manifest::parse(&original).unwrap();
+        manifest.tools.push("aider".to_string());
+        let added = manifest.to_toml();
+        assert_ne!(added, original);
+
+        let mut manifest = Manifest::parse(&added).unwrap();
+        manifest.tools.retain(|t| t != "aider");
+        assert_eq!(manifest.to_toml(), original);
+    }
+
+    #[test]
+    fn test_open_default_tool() {
+        let mut base = Manifest::parse("[open]\ndefault_tool = \"cursor\"\n").unwrap();
+        assert_eq!(base.open.default_tool.as_deref(), Some("cursor"));
+
+        // A layer without the setting keeps the inherited tool
+        base.merge(&Manifest::parse("tools = [\"zed\"]\n").unwrap());
+        assert_eq!(base.open.default_tool.as_deref(), Some("cursor"));
+
+        base.merge(&Manifest::parse("[open]\ndefault_tool = \"zed\"\n").unwrap());
+        assert_eq!(base.open.default_tool.as_deref(), Some("zed"));
+        assert!(!Manifest::empty().to_toml().contains("[open]"));
+    }
 }
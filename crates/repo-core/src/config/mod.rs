@@ -35,10 +35,14 @@
 //! let json = context.to_json();
 //! ```
 
+mod editor;
+mod format;
 mod manifest;
 mod resolver;
 mod runtime;
 
-pub use manifest::{Manifest, json_to_toml_value};
-pub use resolver::{ConfigResolver, ResolvedConfig};
+pub use editor::ManifestEditor;
+pub use format::format_config;
+pub use manifest::{Manifest, McpSection, OpenSection, SyncSection, json_to_toml_value};
+pub use resolver::{ConfigDifference, ConfigLayer, ConfigResolver, ResolvedConfig};
 pub use runtime::RuntimeContext;
@@ -4,14 +4,16 @@
 //! in a defined hierarchy, with later sources overriding earlier ones.
 
 use crate::Result;
-use repo_fs::NormalizedPath;
+use crate::hooks::HookConfig;
+use repo_fs::{LayoutMode, NormalizedPath};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
 use std::fs;
 use std::path::PathBuf;
 
-use super::manifest::Manifest;
+use super::manifest::{CoreSection, Manifest, McpSection, OpenSection, SyncSection};
 
 /// The final resolved configuration after merging all sources
 ///
@@ -22,6 +24,26 @@ pub struct ResolvedConfig {
     /// Repository mode: "standard" or "worktree"
     pub mode: String,
 
+    /// Workspace layout recorded at init
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<LayoutMode>,
+
+    /// Path of the enclosing repository of a nested repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+
+    /// Whether managed file and ledger writes are read back and checksummed
+    #[serde(default)]
+    pub verify_writes: bool,
+
+    /// Whether `repo sync --commit` includes the ledger
+    #[serde(default)]
+    pub commit_ledger: bool,
+
+    /// Combined extra secret key patterns (unique)
+    #[serde(default)]
+    pub secret_patterns: Vec<String>,
+
     /// Merged preset configurations
     pub presets: HashMap<String, Value>,
 
@@ -33,16 +55,46 @@ pub struct ResolvedConfig {
 
     /// Merged extension configurations
     pub extensions: HashMap<String, Value>,
+
+    /// Merged per-tool settings
+    #[serde(default)]
+    pub tool_settings: HashMap<String, Value>,
+
+    /// Lifecycle hooks from every layer
+    #[serde(default)]
+    pub hooks: Vec<HookConfig>,
+
+    /// Merged MCP servers and settings
+    #[serde(default)]
+    pub mcp: McpSection,
+
+    /// Merged sync settings
+    #[serde(default)]
+    pub sync: SyncSection,
+
+    /// Merged `repo open` settings
+    #[serde(default)]
+    pub open: OpenSection,
 }
 
 impl Default for ResolvedConfig {
     fn default() -> Self {
         Self {
             mode: "standard".to_string(),
+            layout: None,
+            parent: None,
+            verify_writes: false,
+            commit_ledger: false,
+            secret_patterns: Vec::new(),
             presets: HashMap::new(),
             tools: Vec::new(),
             rules: Vec::new(),
             extensions: HashMap::new(),
+            tool_settings: HashMap::new(),
+            hooks: Vec::new(),
+            mcp: McpSection::default(),
+            sync: SyncSection::default(),
+            open: OpenSection::default(),
         }
     }
 }
@@ -51,14 +103,182 @@ impl From<Manifest> for ResolvedConfig {
     fn from(manifest: Manifest) -> Self {
         Self {
             mode: manifest.core.mode,
+            layout: manifest.core.layout,
+            parent: manifest.core.parent,
+            verify_writes: manifest.core.verify_writes,
+            commit_ledger: manifest.core.commit_ledger,
+            secret_patterns: manifest.core.secret_patterns,
             presets: manifest.presets,
             tools: manifest.tools,
             rules: manifest.rules,
             extensions: manifest.extensions,
+            tool_settings: manifest.tool_settings,
+            hooks: manifest.hooks,
+            mcp: manifest.mcp,
+            sync: manifest.sync,
+            open: manifest.open,
+        }
+    }
+}
+
+impl ResolvedConfig {
+    /// Render as a canonical `config.toml` document
+    ///
+    /// Tables and keys are sorted, so the output only changes when the
+    /// configuration does. Using the output as a repository's `config.toml`
+    /// resolves to the same configuration, provided no global, org, or
+    /// local layers add to it.
+    pub fn to_toml(&self) -> Result<String> {
+        let manifest = Manifest {
+            core: self.core(),
+            presets: self.presets.clone(),
+            tools: self.tools.clone(),
+            rules: self.rules.clone(),
+            extensions: self.extensions.clone(),
+            tool_settings: self.tool_settings.clone(),
+            hooks: self.hooks.clone(),
+            mcp: self.mcp.clone(),
+            sync: self.sync.clone(),
+            open: self.open.clone(),
+        };
+        let mut value = toml::Value::try_from(&manifest)?;
+        if self.hooks.is_empty()
+            && let Some(table) = value.as_table_mut()
+        {
+            table.remove("hooks");
+        }
+        Ok(toml::to_string_pretty(&value)?)
+    }
+
+    /// The resolved `[core]` section
+    pub fn core(&self) -> CoreSection {
+        CoreSection {
+            mode: self.mode.clone(),
+            layout: self.layout,
+            verify_writes: self.verify_writes,
+            commit_ledger: self.commit_ledger,
+            secret_patterns: self.secret_patterns.clone(),
+            parent: self.parent.clone(),
         }
     }
 }
 
+/// A configuration source in the resolution hierarchy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigLayer {
+    /// Global defaults (`<config_dir>/repo-manager/config.toml`)
+    Global,
+    /// Organization config (`<config_dir>/repo-manager/org/config.toml`)
+    Org,
+    /// Repository config (`.repository/config.toml`)
+    Repository,
+    /// Local overrides (`.repository/config.local.toml`)
+    Local,
+}
+
+impl fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Global => "global",
+            Self::Org => "org",
+            Self::Repository => "repository",
+            Self::Local => "local",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A key whose effective value differs from the committed `config.toml`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConfigDifference {
+    /// Dotted key path, e.g. `tools` or `presets."env:python".version`
+    pub key: String,
+
+    /// Value in `.repository/config.toml`, if the key is set there
+    pub committed: Option<Value>,
+
+    /// Value after merging all layers, if the key is set at all
+    pub effective: Option<Value>,
+
+    /// Layers other than the repository layer that changed the key
+    pub layers: Vec<ConfigLayer>,
+}
+
+/// Flatten a resolved configuration into dotted key paths
+///
+/// The `[core]`, `[mcp]`, `[sync]` and `[open]` sections, preset, extension
+/// and tool settings tables are flattened down to their leaf values; arrays
+/// are kept whole.
+fn flatten_config(config: &ResolvedConfig) -> BTreeMap<String, Value> {
+    let mut keys = BTreeMap::new();
+    if let Ok(core) = serde_json::to_value(config.core()) {
+        flatten_value("core", &core, &mut keys);
+    }
+    keys.insert("tools".to_string(), Value::from(config.tools.clone()));
+    keys.insert("rules".to_string(), Value::from(config.rules.clone()));
+    if !config.hooks.is_empty()
+        && let Ok(hooks) = serde_json::to_value(&config.hooks)
+    {
+        keys.insert("hooks".to_string(), hooks);
+    }
+    if !config.mcp.is_empty()
+        && let Ok(mcp) = serde_json::to_value(&config.mcp)
+    {
+        flatten_value("mcp", &mcp, &mut keys);
+    }
+    if !config.sync.is_empty()
+        && let Ok(sync) = serde_json::to_value(&config.sync)
+    {
+        flatten_value("sync", &sync, &mut keys);
+    }
+    if !config.open.is_empty()
+        && let Ok(open) = serde_json::to_value(&config.open)
+    {
+        flatten_value("open", &open, &mut keys);
+    }
+    for (section, entries) in [
+        ("presets", &config.presets),
+        ("extensions", &config.extensions),
+        ("tool_settings", &config.tool_settings),
+    ] {
+        for (name, value) in entries {
+            flatten_value(
+                &format!("{section}.{}", key_segment(name)),
+                value,
+                &mut keys,
+            );
+        }
+    }
+    keys
+}
+
+fn flatten_value(prefix: &str, value: &Value, keys: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (name, child) in map {
+                flatten_value(&format!("{prefix}.{}", key_segment(name)), child, keys);
+            }
+        }
+        _ => {
+            keys.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+/// Quote a key segment unless it is a valid bare TOML key
+fn key_segment(name: &str) -> String {
+    let bare = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
 /// Resolves configuration by merging multiple sources
 ///
 /// Configuration is loaded from a hierarchy of sources:
@@ -149,6 +369,67 @@ impl ConfigResolver {
     /// ```
     pub fn resolve(&self) -> Result<ResolvedConfig> {
         let mut manifest = Manifest::empty();
+        for (_, layer_manifest) in self.load_layers()? {
+            manifest.merge(&layer_manifest);
+        }
+        Ok(ResolvedConfig::from(manifest))
+    }
+
+    /// Compare the effective configuration with the committed `config.toml`
+    ///
+    /// Returns one entry per key whose effective value differs from the
+    /// value in `.repository/config.toml` alone, sorted by key. Each entry
+    /// names the layers other than the repository layer that changed the
+    /// key while the layers were merged, so users can see why the
+    /// repository behaves differently than the committed file suggests.
+    pub fn diff_committed(&self) -> Result<Vec<ConfigDifference>> {
+        let layers = self.load_layers()?;
+
+        let mut committed = Manifest::empty();
+        for (layer, layer_manifest) in &layers {
+            if *layer == ConfigLayer::Repository {
+                committed.merge(layer_manifest);
+            }
+        }
+        let committed = flatten_config(&ResolvedConfig::from(committed));
+
+        // Replay the merge, recording which layers changed each key
+        let mut manifest = Manifest::empty();
+        let mut effective = flatten_config(&ResolvedConfig::from(manifest.clone()));
+        let mut changed_by: BTreeMap<String, Vec<ConfigLayer>> = BTreeMap::new();
+        for (layer, layer_manifest) in &layers {
+            manifest.merge(layer_manifest);
+            let merged = flatten_config(&ResolvedConfig::from(manifest.clone()));
+            for key in effective.keys().chain(merged.keys()) {
+                if effective.get(key) != merged.get(key) {
+                    let entry = changed_by.entry(key.clone()).or_default();
+                    if *layer != ConfigLayer::Repository && !entry.contains(layer) {
+                        entry.push(*layer);
+                    }
+                }
+            }
+            effective = merged;
+        }
+
+        let keys: BTreeSet<&String> = committed.keys().chain(effective.keys()).collect();
+        Ok(keys
+            .into_iter()
+            .filter(|key| committed.get(*key) != effective.get(*key))
+            .map(|key| ConfigDifference {
+                key: key.clone(),
+                committed: committed.get(key).cloned(),
+                effective: effective.get(key).cloned(),
+                layers: changed_by.remove(key).unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Load the manifests of all present layers, in merge order
+    ///
+    /// Missing layers are skipped. Invalid TOML in any layer produces an
+    /// error.
+    fn load_layers(&self) -> Result<Vec<(ConfigLayer, Manifest)>> {
+        let mut layers = Vec::new();
 
         // Layer 1 - Global defaults (~/.config/repo-manager/config.toml)
         if let Some(global_dir) = self.global_config_dir() {
@@ -156,8 +437,7 @@ impl ConfigResolver {
             if global_config_path.is_file() {
                 tracing::debug!(?global_config_path, "Loading global config (layer 1)");
                 let content = fs::read_to_string(&global_config_path)?;
-                let global_manifest = Manifest::parse(&content)?;
-                manifest.merge(&global_manifest);
+                layers.push((ConfigLayer::Global, Manifest::parse(&content)?));
             } else {
                 tracing::debug!(
                     ?global_config_path,
@@ -183,13 +463,9 @@ impl ConfigResolver {
             if org_config_path.is_file() {
                 tracing::debug!(?org_config_path, "Loading org config (layer 2)");
                 let content = fs::read_to_string(&org_config_path)?;
-                let org_manifest = Manifest::parse(&content)?;
-                manifest.merge(&org_manifest);
+                layers.push((ConfigLayer::Org, Manifest::parse(&content)?));
             } else {
-                tracing::debug!(
-                    ?org_config_path,
-                    "No org config found (layer 2) — skipping"
-                );
+                tracing::debug!(?org_config_path, "No org config found (layer 2) — skipping");
             }
         }
 
@@ -198,8 +474,7 @@ impl ConfigResolver {
         if repo_config_path.is_file() {
             tracing::debug!(?repo_config_path, "Loading repo config (layer 3)");
             let content = fs::read_to_string(repo_config_path.to_native())?;
-            let repo_manifest = Manifest::parse(&content)?;
-            manifest.merge(&repo_manifest);
+            layers.push((ConfigLayer::Repository, Manifest::parse(&content)?));
         }
 
         // Layer 4 - Local overrides (.repository/config.local.toml)
@@ -207,11 +482,10 @@ impl ConfigResolver {
         if local_config_path.is_file() {
             tracing::debug!(?local_config_path, "Loading local config (layer 4)");
             let content = fs::read_to_string(local_config_path.to_native())?;
-            let local_manifest = Manifest::parse(&content)?;
-            manifest.merge(&local_manifest);
+            layers.push((ConfigLayer::Local, Manifest::parse(&content)?));
         }
 
-        Ok(ResolvedConfig::from(manifest))
+        Ok(layers)
     }
 
     /// Get the repository root path
@@ -325,4 +599,334 @@ version = "3.12"
         assert!(config.tools.contains(&"cursor".to_string()));
         assert!(config.tools.contains(&"vscode".to_string()));
     }
+
+    #[test]
+    fn effective_toml_round_trips_with_local_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let global_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join(".repository");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        std::fs::write(
+            repo_dir.join("config.toml"),
+            r#"
+tools = ["cursor"]
+
+[core]
+mode = "standard"
+
+[presets."env:python"]
+version = "3.11"
+debug = false
+
+[extensions.vaultspec]
+source = "https://example.com/vaultspec.git"
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            repo_dir.join("config.local.toml"),
+            r#"
+tools = ["vscode"]
+
+[core]
+mode = "worktrees"
+
+[presets."env:python"]
+version = "3.12"
+"#,
+        )
+        .unwrap();
+
+        let resolver = ConfigResolver::with_global_config_dir(
+            NormalizedPath::new(temp_dir.path()),
+            global_dir.path().to_path_buf(),
+        );
+        let config = resolver.resolve().unwrap();
+        let effective = config.to_toml().unwrap();
+
+        // Parses as a manifest reflecting the local overrides
+        let manifest = Manifest::parse(&effective).unwrap();
+        assert_eq!(manifest.core.mode, "worktrees");
+        assert_eq!(manifest.presets["env:python"]["version"], "3.12");
+        assert_eq!(manifest.presets["env:python"]["debug"], false);
+        assert_eq!(manifest.tools, vec!["cursor", "vscode"]);
+        assert!(!effective.contains("hooks"));
+
+        // Used as the only layer of a fresh repository, it resolves the same
+        let fresh = TempDir::new().unwrap();
+        std::fs::create_dir_all(fresh.path().join(".repository")).unwrap();
+        std::fs::write(fresh.path().join(".repository/config.toml"), &effective).unwrap();
+        let reproduced = ConfigResolver::with_global_config_dir(
+            NormalizedPath::new(fresh.path()),
+            global_dir.path().to_path_buf(),
+        )
+        .resolve()
+        .unwrap();
+        assert_eq!(reproduced.to_toml().unwrap(), effective);
+        assert_eq!(reproduced.tools, config.tools);
+        assert_eq!(reproduced.presets, config.presets);
+        assert_eq!(reproduced.extensions, config.extensions);
+    }
+
+    #[test]
+    fn effective_toml_round_trips_core_settings_and_hooks() {
+        let temp_dir = TempDir::new().unwrap();
+        let global_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join(".repository");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        std::fs::write(
+            repo_dir.join("config.toml"),
+            r#"
+tools = ["cursor"]
+
+[core]
+mode = "worktrees"
+layout = "in-repo-worktrees"
+verify_writes = true
+commit_ledger = true
+secret_patterns = ["*_PASSWORD"]
+parent = ".."
+
+[[hooks]]
+event = "post-sync"
+command = "echo"
+args = ["synced"]
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            repo_dir.join("config.local.toml"),
+            r#"
+[core]
+mode = "worktrees"
+secret_patterns = ["*_PASSPHRASE"]
+
+[[hooks]]
+event = "pre-sync"
+command = "true"
+"#,
+        )
+        .unwrap();
+
+        let resolver = ConfigResolver::with_global_config_dir(
+            NormalizedPath::new(temp_dir.path()),
+            global_dir.path().to_path_buf(),
+        );
+        let config = resolver.resolve().unwrap();
+        let effective = config.to_toml().unwrap();
+
+        let manifest = Manifest::parse(&effective).unwrap();
+        assert_eq!(manifest.core.layout, Some(LayoutMode::InRepoWorktrees));
+        assert!(manifest.core.verify_writes);
+        assert!(manifest.core.commit_ledger);
+        assert_eq!(
+            manifest.core.secret_patterns,
+            vec!["*_PASSWORD", "*_PASSPHRASE"]
+        );
+        assert_eq!(manifest.core.parent.as_deref(), Some(".."));
+        assert_eq!(manifest.hooks.len(), 2);
+        assert_eq!(manifest.hooks[0].command, "echo");
+        assert_eq!(manifest.hooks[0].args, vec!["synced"]);
+        assert_eq!(manifest.hooks[1].command, "true");
+
+        let fresh = TempDir::new().unwrap();
+        std::fs::create_dir_all(fresh.path().join(".repository")).unwrap();
+        std::fs::write(fresh.path().join(".repository/config.toml"), &effective).unwrap();
+        let reproduced = ConfigResolver::with_global_config_dir(
+            NormalizedPath::new(fresh.path()),
+            global_dir.path().to_path_buf(),
+        )
+        .resolve()
+        .unwrap();
+        assert_eq!(reproduced.to_toml().unwrap(), effective);
+    }
+
+    #[test]
+    fn effective_toml_keeps_mcp_servers_from_every_layer() {
+        let temp_dir = TempDir::new().unwrap();
+        let global_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join(".repository");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        std::fs::write(
+            global_dir.path().join("config.toml"),
+            r#"
+[mcp.servers.github.transport]
+type = "stdio"
+command = "npx"
+args = ["-y", "@modelcontextprotocol/server-github"]
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            repo_dir.join("config.toml"),
+            r#"
+[mcp]
+on_conflict = "error"
+
+[mcp.servers.docs.transport]
+type = "stdio"
+command = "docs-server"
+"#,
+        )
+        .unwrap();
+
+        let resolver = ConfigResolver::with_global_config_dir(
+            NormalizedPath::new(temp_dir.path()),
+            global_dir.path().to_path_buf(),
+        );
+        let config = resolver.resolve().unwrap();
+        let effective = config.to_toml().unwrap();
+
+        let manifest = Manifest::parse(&effective).unwrap();
+        let servers: Vec<&String> = manifest.mcp.servers.keys().collect();
+        assert_eq!(servers, vec!["docs", "github"]);
+        assert_eq!(
+            manifest.mcp.on_conflict,
+            Some(repo_extensions::McpMergePolicy::Error)
+        );
+
+        let fresh = TempDir::new().unwrap();
+        let fresh_global = TempDir::new().unwrap();
+        std::fs::create_dir_all(fresh.path().join(".repository")).unwrap();
+        std::fs::write(fresh.path().join(".repository/config.toml"), &effective).unwrap();
+        let reproduced = ConfigResolver::with_global_config_dir(
+            NormalizedPath::new(fresh.path()),
+            fresh_global.path().to_path_buf(),
+        )
+        .resolve()
+        .unwrap();
+        assert_eq!(reproduced.to_toml().unwrap(), effective);
+    }
+
+    #[test]
+    fn effective_toml_keeps_sync_settings_from_local_layer() {
+        let temp_dir = TempDir::new().unwrap();
+        let global_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join(".repository");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        std::fs::write(repo_dir.join("config.toml"), "tools = [\"cursor\"]\n").unwrap();
+        std::fs::write(
+            repo_dir.join("config.local.toml"),
+            "[sync]\nauto_sync_on_switch = true\n",
+        )
+        .unwrap();
+
+        let resolver = ConfigResolver::with_global_config_dir(
+            NormalizedPath::new(temp_dir.path()),
+            global_dir.path().to_path_buf(),
+        );
+        let config = resolver.resolve().unwrap();
+        assert!(config.sync.auto_sync_on_switch);
+
+        let manifest = Manifest::parse(&config.to_toml().unwrap()).unwrap();
+        assert!(manifest.sync.auto_sync_on_switch);
+
+        let diff = resolver.diff_committed().unwrap();
+        assert_eq!(diff.len(), 1, "{diff:?}");
+        assert_eq!(diff[0].key, "sync.auto_sync_on_switch");
+        assert_eq!(diff[0].layers, vec![ConfigLayer::Local]);
+    }
+
+    #[test]
+    fn effective_toml_keeps_open_default_tool_override() {
+        let temp_dir = TempDir::new().unwrap();
+        let global_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join(".repository");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        std::fs::write(
+            repo_dir.join("config.toml"),
+            "[open]\ndefault_tool = \"cursor\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            repo_dir.join("config.local.toml"),
+            "[open]\ndefault_tool = \"vscode\"\n",
+        )
+        .unwrap();
+
+        let resolver = ConfigResolver::with_global_config_dir(
+            NormalizedPath::new(temp_dir.path()),
+            global_dir.path().to_path_buf(),
+        );
+        let config = resolver.resolve().unwrap();
+
+        let manifest = Manifest::parse(&config.to_toml().unwrap()).unwrap();
+        assert_eq!(manifest.open.default_tool.as_deref(), Some("vscode"));
+
+        let diff = resolver.diff_committed().unwrap();
+        assert_eq!(diff.len(), 1, "{diff:?}");
+        assert_eq!(diff[0].key, "open.default_tool");
+        assert_eq!(diff[0].committed, Some(serde_json::json!("cursor")));
+        assert_eq!(diff[0].effective, Some(serde_json::json!("vscode")));
+    }
+
+    #[test]
+    fn diff_committed_attributes_tools_change_to_local_layer() {
+        let temp_dir = TempDir::new().unwrap();
+        let global_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join(".repository");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        std::fs::write(
+            repo_dir.join("config.toml"),
+            "tools = [\"cursor\"]\n\n[presets.\"env:python\"]\nversion = \"3.12\"\n",
+        )
+        .unwrap();
+        std::fs::write(repo_dir.join("config.local.toml"), "tools = [\"vscode\"]\n").unwrap();
+
+        let resolver = ConfigResolver::with_global_config_dir(
+            NormalizedPath::new(temp_dir.path()),
+            global_dir.path().to_path_buf(),
+        );
+        let diff = resolver.diff_committed().unwrap();
+
+        assert_eq!(diff.len(), 1, "only tools should differ: {diff:?}");
+        assert_eq!(diff[0].key, "tools");
+        assert_eq!(diff[0].committed, Some(serde_json::json!(["cursor"])));
+        assert_eq!(
+            diff[0].effective,
+            Some(serde_json::json!(["cursor", "vscode"]))
+        );
+        assert_eq!(diff[0].layers, vec![ConfigLayer::Local]);
+    }
+
+    #[test]
+    fn diff_committed_reports_keys_added_by_global_layer() {
+        let temp_dir = TempDir::new().unwrap();
+        let global_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join(".repository");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        std::fs::write(repo_dir.join("config.toml"), "tools = [\"cursor\"]\n").unwrap();
+        std::fs::write(
+            global_dir.path().join("config.toml"),
+            "[presets.\"env:python\"]\nversion = \"3.11\"\n",
+        )
+        .unwrap();
+
+        let resolver = ConfigResolver::with_global_config_dir(
+            NormalizedPath::new(temp_dir.path()),
+            global_dir.path().to_path_buf(),
+        );
+        let diff = resolver.diff_committed().unwrap();
+
+        assert_eq!(diff.len(), 1, "{diff:?}");
+        assert_eq!(diff[0].key, "presets.\"env:python\".version");
+        assert_eq!(diff[0].committed, None);
+        assert_eq!(diff[0].effective, Some(serde_json::json!("3.11")));
+        assert_eq!(diff[0].layers, vec![ConfigLayer::Global]);
+    }
+
+    #[test]
+    fn diff_committed_is_empty_without_other_layers() {
+        let temp_dir = TempDir::new().unwrap();
+        let global_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join(".repository");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        std::fs::write(repo_dir.join("config.toml"), "tools = [\"cursor\"]\n").unwrap();
+
+        let resolver = ConfigResolver::with_global_config_dir(
+            NormalizedPath::new(temp_dir.path()),
+            global_dir.path().to_path_buf(),
+        );
+        assert!(resolver.diff_committed().unwrap().is_empty());
+    }
 }
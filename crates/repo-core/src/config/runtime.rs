@@ -79,6 +79,8 @@ impl RuntimeContext {
     ///     tools: vec![],
     ///     rules: vec![],
     ///     extensions: HashMap::new(),
+    ///     tool_settings: HashMap::new(),
+    ///     ..Default::default()
     /// };
     ///
     /// let context = RuntimeContext::from_resolved(&config);
@@ -171,6 +173,8 @@ mod tests {
             tools: vec![],
             rules: vec![],
             extensions: HashMap::new(),
+            tool_settings: HashMap::new(),
+            ..Default::default()
         };
 
         let ctx = RuntimeContext::from_resolved(&config);
@@ -199,6 +203,8 @@ mod tests {
             tools: vec![],
             rules: vec![],
             extensions: HashMap::new(),
+            tool_settings: HashMap::new(),
+            ..Default::default()
         };
 
         let ctx = RuntimeContext::from_resolved(&config);
@@ -224,6 +230,8 @@ mod tests {
             tools: vec![],
             rules: vec![],
             extensions: HashMap::new(),
+            tool_settings: HashMap::new(),
+            ..Default::default()
         };
 
         let ctx = RuntimeContext::from_resolved(&config);
@@ -0,0 +1,290 @@
+//! Repository context detection
+//!
+//! Resolves "where am I?" for every entry point (CLI, MCP server, core):
+//! the repository root, its mode, the worktree container and active worktree
+//! (if any), and the config path. Detection records a step-by-step
+//! explanation trail so users can see why a particular root or mode was
+//! chosen.
+//!
+//! # Detection rules
+//!
+//! Starting at the given directory and walking up:
+//!
+//! 1. The first directory containing `.repository/` is the candidate root.
+//!    If it has no `config.toml`, the repository is partially initialized.
+//! 2. If that directory has no `.gt/` but its parent does, it is a worktree
+//!    checkout inside a container; the container becomes the root.
+//! 3. The mode is resolved at the root with [`detect_mode`](crate::detect_mode)
+//!    precedence: `.gt/` marker, `.git` marker, then the merged config.
+//! 4. In worktrees mode, the first non-hidden path component below the
+//!    container is the active worktree.
+
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+use repo_fs::NormalizedPath;
+
+use crate::config::ConfigResolver;
+use crate::error::{Error, Result};
+use crate::mode::Mode;
+
+/// Directory holding repository-manager configuration.
+const REPOSITORY_DIR: &str = ".repository";
+
+/// The kind of location the start directory resolved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextKind {
+    /// A standard (single checkout) repository.
+    StandardRepo,
+    /// The root of a worktrees container (or a hidden directory inside it).
+    ContainerRoot,
+    /// Inside a worktree of a container.
+    Worktree,
+    /// Not inside any recognized repository.
+    NotARepo,
+}
+
+impl fmt::Display for ContextKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StandardRepo => write!(f, "standard repository"),
+            Self::ContainerRoot => write!(f, "container root"),
+            Self::Worktree => write!(f, "worktree"),
+            Self::NotARepo => write!(f, "not a repository"),
+        }
+    }
+}
+
+/// Result of repository context detection.
+#[derive(Debug, Clone)]
+pub struct RepoContextInfo {
+    /// The directory detection started from.
+    pub start: PathBuf,
+    /// What the start directory resolved to.
+    pub kind: ContextKind,
+    /// Repository root (where `.repository/` lives).
+    pub root: Option<NormalizedPath>,
+    /// Resolved repository mode.
+    pub mode: Option<Mode>,
+    /// Worktrees container, in worktrees mode.
+    pub container: Option<NormalizedPath>,
+    /// Name of the active worktree, when inside one.
+    pub worktree: Option<String>,
+    /// Path to `.repository/config.toml`, if it exists.
+    pub config_path: Option<NormalizedPath>,
+    /// Step-by-step explanation of what was checked.
+    pub trail: Vec<String>,
+}
+
+impl RepoContextInfo {
+    /// Whether a repository root was found.
+    pub fn is_repo(&self) -> bool {
+        self.kind != ContextKind::NotARepo
+    }
+
+    /// Whether `.repository/` exists but `config.toml` is missing.
+    pub fn is_partial(&self) -> bool {
+        self.is_repo() && self.config_path.is_none()
+    }
+
+    /// Path to the `.repository/` directory, if a root was found.
+    pub fn repository_dir(&self) -> Option<NormalizedPath> {
+        self.root.as_ref().map(|r| r.join(REPOSITORY_DIR))
+    }
+
+    /// Render the explanation trail as numbered lines.
+    pub fn explain(&self) -> String {
+        self.trail
+            .iter()
+            .enumerate()
+            .map(|(i, step)| format!("{:>3}. {}", i + 1, step))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn not_a_repo(start: PathBuf, trail: Vec<String>) -> Self {
+        Self {
+            start,
+            kind: ContextKind::NotARepo,
+            root: None,
+            mode: None,
+            container: None,
+            worktree: None,
+            config_path: None,
+            trail,
+        }
+    }
+}
+
+/// Detect the repository context for a directory.
+///
+/// Never fails: problems encountered along the way (unreadable or invalid
+/// config) are recorded in the trail and detection falls back to defaults.
+pub fn detect(start: &Path) -> RepoContextInfo {
+    let start = std::path::absolute(start).unwrap_or_else(|_| start.to_path_buf());
+    let mut trail = Vec::new();
+
+    let mut current = Some(start.as_path());
+    let found = loop {
+        let Some(dir) = current else {
+            break None;
+        };
+
+        if dir.join(REPOSITORY_DIR).is_dir() {
+            trail.push(format!("{}: found {}/", dir.display(), REPOSITORY_DIR));
+            break Some(dir.to_path_buf());
+        }
+
+        if dir.join(".gt").is_dir() || dir.join(".git").exists() {
+            trail.push(format!(
+                "{}: git marker present but no {}/, continuing upward",
+                dir.display(),
+                REPOSITORY_DIR
+            ));
+        } else {
+            trail.push(format!("{}: no {}/", dir.display(), REPOSITORY_DIR));
+        }
+        current = dir.parent();
+    };
+
+    let Some(mut root_dir) = found else {
+        trail.push(format!(
+            "reached filesystem root without finding {}/; run 'repo init' to create one",
+            REPOSITORY_DIR
+        ));
+        return RepoContextInfo::not_a_repo(start, trail);
+    };
+
+    // A worktree checkout carries its own tracked .repository/; the container
+    // above it (marked by .gt/) is the real root.
+    if !root_dir.join(".gt").is_dir()
+        && let Some(parent) = root_dir.parent()
+        && parent.join(".gt").is_dir()
+    {
+        trail.push(format!(
+            "{}: parent {} has .gt/, treating it as a worktree of that container",
+            root_dir.display(),
+            parent.display()
+        ));
+        root_dir = parent.to_path_buf();
+    }
+
+    let root = NormalizedPath::new(&root_dir);
+    let config = root.join(".repository/config.toml");
+    let config_path = if config.is_file() {
+        trail.push(format!("using config {}", config));
+        Some(config)
+    } else {
+        trail.push(format!(
+            "partial init: {} is missing; run 'repo init' to complete setup",
+            config
+        ));
+        None
+    };
+
+    let mode = match resolve_mode(&root, &mut trail) {
+        Ok(mode) => mode,
+        Err(e) => {
+            trail.push(format!("could not resolve mode ({}), assuming standard", e));
+            Mode::Standard
+        }
+    };
+
+    let (kind, container, worktree) = match mode {
+        Mode::Standard => (ContextKind::StandardRepo, None, None),
+        Mode::Worktrees => match first_component_below(&start, &root_dir) {
+            Some(name) if !name.starts_with('.') => {
+                trail.push(format!("inside worktree '{}'", name));
+                (ContextKind::Worktree, Some(root.clone()), Some(name))
+            }
+            _ => {
+                trail.push("at container root".to_string());
+                (ContextKind::ContainerRoot, Some(root.clone()), None)
+            }
+        },
+    };
+
+    RepoContextInfo {
+        start,
+        kind,
+        root: Some(root),
+        mode: Some(mode),
+        container,
+        worktree,
+        config_path,
+        trail,
+    }
+}
+
+/// Resolve the mode of a repository root, recording each check in `trail`.
+///
+/// Precedence: `.gt/` in root, `.git` in root, `.gt/` in parent, then the
+/// merged configuration. Defaults to [`Mode::Standard`] when no config exists.
+pub(crate) fn resolve_mode(root: &NormalizedPath, trail: &mut Vec<String>) -> Result<Mode> {
+    if root.join(".gt").exists() {
+        trail.push("mode: worktrees (.gt/ marker in root)".to_string());
+        return Ok(Mode::Worktrees);
+    }
+
+    if root.join(".git").exists() {
+        trail.push("mode: standard (.git marker in root)".to_string());
+        return Ok(Mode::Standard);
+    }
+
+    if let Some(parent) = root.as_ref().parent()
+        && NormalizedPath::new(parent).join(".gt").exists()
+    {
+        trail.push("mode: worktrees (.gt/ marker in parent)".to_string());
+        return Ok(Mode::Worktrees);
+    }
+
+    let resolver = ConfigResolver::new(root.clone());
+    if !resolver.has_config() {
+        trail.push("mode: standard (no markers and no config)".to_string());
+        return Ok(Mode::Standard);
+    }
+
+    let config = resolver.resolve()?;
+    let mode: Mode = config
+        .mode
+        .parse()
+        .map_err(|e: repo_meta::Error| Error::Meta(e))?;
+    trail.push(format!("mode: {} (from config)", mode));
+    Ok(mode)
+}
+
+/// First normal path component of `path` below `base`, if `path` is inside it.
+fn first_component_below(path: &Path, base: &Path) -> Option<String> {
+    let relative = path.strip_prefix(base).ok()?;
+    relative.components().find_map(|c| match c {
+        Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_component_below() {
+        let base = Path::new("/c");
+        assert_eq!(
+            first_component_below(Path::new("/c/feature/src"), base),
+            Some("feature".to_string())
+        );
+        assert_eq!(first_component_below(Path::new("/c"), base), None);
+        assert_eq!(first_component_below(Path::new("/other"), base), None);
+    }
+
+    #[test]
+    fn test_explain_numbers_steps() {
+        let info = RepoContextInfo::not_a_repo(
+            PathBuf::from("/x"),
+            vec!["first".to_string(), "second".to_string()],
+        );
+        assert_eq!(info.explain(), "  1. first\n  2. second");
+        assert!(!info.is_repo());
+        assert!(info.repository_dir().is_none());
+    }
+}
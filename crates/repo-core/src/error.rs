@@ -12,6 +12,18 @@ pub enum Error {
     #[error("Configuration not found at {path}")]
     ConfigNotFound { path: PathBuf },
 
+    /// No `.repository/` directory at or above the path
+    #[error("Not in a repository: no .repository/ found at or above {path}")]
+    NotARepository { path: PathBuf },
+
+    /// Rule ID that cannot be used as a file name
+    #[error("Invalid rule ID '{id}': {message}")]
+    InvalidRuleId { id: String, message: String },
+
+    /// Repository cannot be converted to another mode
+    #[error("Cannot change mode: {message}")]
+    ModeMigration { message: String },
+
     /// Error in ledger operations
     #[error("Ledger error: {message}")]
     LedgerError { message: String },
@@ -20,6 +32,10 @@ pub enum Error {
     #[error("Intent not found: {id}")]
     IntentNotFound { id: String },
 
+    /// Intent whose arguments do not match the schema for its kind
+    #[error("Malformed intent {uuid}: {reason}")]
+    MalformedIntent { uuid: uuid::Uuid, reason: String },
+
     /// Projection failed for a tool
     #[error("Projection failed for {tool}: {reason}")]
     ProjectionFailed { tool: String, reason: String },
@@ -36,6 +52,30 @@ pub enum Error {
         message: String,
     },
 
+    /// Configuration bundle that cannot be imported
+    #[error("Invalid configuration bundle: {message}")]
+    InvalidBundle { message: String },
+
+    /// Init template that cannot be found or fetched
+    #[error("Cannot load template '{name}': {message}")]
+    Template { name: String, message: String },
+
+    /// Extension source is neither a git URL nor a local extension directory
+    #[error(
+        "Extension source not found: {path} \
+         (expected a git URL or a local directory containing {manifest})",
+        manifest = repo_extensions::MANIFEST_FILENAME
+    )]
+    ExtensionNotFound { path: String },
+
+    /// Presets an extension requires do not meet its version constraints
+    #[error("Cannot install extension '{name}': unsatisfied preset requirements:\n{requirements}")]
+    UnsatisfiedPresets { name: String, requirements: String },
+
+    /// Operation stopped because its caller cancelled it
+    #[error("Operation cancelled")]
+    Cancelled,
+
     /// Resource not found
     #[error("Not found: {0}")]
     NotFound(String),
@@ -65,10 +105,18 @@ pub enum Error {
     #[error(transparent)]
     Presets(#[from] repo_presets::Error),
 
+    /// Extension error from repo-extensions
+    #[error(transparent)]
+    Extensions(#[from] repo_extensions::Error),
+
     /// Content error from repo-content
     #[error(transparent)]
     Content(#[from] repo_content::Error),
 
+    /// Managed block error from repo-blocks
+    #[error(transparent)]
+    Blocks(#[from] repo_blocks::Error),
+
     /// Standard I/O error
     #[error(transparent)]
     Io(#[from] std::io::Error),
@@ -84,4 +132,8 @@ pub enum Error {
     /// TOML serialization error
     #[error(transparent)]
     TomlSer(#[from] toml::ser::Error),
+
+    /// TOML document parsing error
+    #[error(transparent)]
+    TomlEdit(#[from] toml_edit::TomlError),
 }
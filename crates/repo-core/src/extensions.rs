@@ -0,0 +1,342 @@
+//! Installing extensions into a repository
+//!
+//! [`Repository::install_extension`] is the one install flow behind both
+//! `repo extension install` and the MCP `extension_install` tool: clone or
+//! copy the source into `.repository/extensions/<name>`, check its tools for
+//! collisions and its preset requirements against the repository, record it
+//! in the extension lock file and config.toml, then apply the presets it
+//! added.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use repo_extensions::deps::parse_version;
+use repo_extensions::installer::extension_dir;
+use repo_extensions::{
+    DependencyGraph, ExtensionConfig, ExtensionManifest, LockFile, LockedExtension,
+    MANIFEST_FILENAME, PresetRequirement, clone_extension, is_git_url,
+};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::collisions::{ToolNameCollision, extension_tool_collisions};
+use crate::config::Manifest;
+use crate::error::{Error, Result};
+use crate::presets::{preset_context, preset_provider};
+use crate::repository::Repository;
+
+/// An extension installed by [`Repository::install_extension`]
+#[derive(Debug, Clone)]
+pub struct ExtensionInstall {
+    /// The entry recorded in the extension lock file
+    pub locked: LockedExtension,
+    /// Where the extension was installed
+    pub path: PathBuf,
+    /// Tool slugs the extension shares with a built-in or repository tool,
+    /// which take precedence over the extension's
+    pub collisions: Vec<ToolNameCollision>,
+    /// Presets the extension requires
+    pub required: Vec<PresetRequirement>,
+    /// Presets added to config.toml, with their initial configuration
+    pub presets_added: Vec<(String, Value)>,
+    /// What applying each added preset did, in the same order
+    pub presets_applied: Vec<PresetApply>,
+}
+
+/// Outcome of applying a preset added by [`Repository::install_extension`]
+///
+/// Provider failures are recorded here rather than failing the install: the
+/// preset is already saved to config.toml and can be re-applied later.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PresetApply {
+    /// Preset ID, e.g. `env:python`
+    pub preset: String,
+    /// Whether a provider is registered for the preset
+    pub supported: bool,
+    /// Actions the provider reported taking
+    pub actions: Vec<String>,
+    /// Errors from the provider or from rolling back its changes
+    pub errors: Vec<String>,
+    /// Whether the partial changes of a failed apply were rolled back
+    pub rolled_back: bool,
+}
+
+/// Install the extension at `source` into `repo`
+///
+/// See [`Repository::install_extension`].
+pub(crate) fn install(repo: &Repository, source: &str, no_deps: bool) -> Result<ExtensionInstall> {
+    let root = repo.root().to_native();
+    let manifest = repo.manifest()?;
+
+    let clone = if is_git_url(source) {
+        let git_ref = configured_ref(&manifest, source);
+        Some(clone_extension(&root, source, git_ref.as_deref())?)
+    } else {
+        None
+    };
+
+    let source_dir = clone.as_ref().map_or(Path::new(source), |c| c.path());
+    let manifest_path = source_dir.join(MANIFEST_FILENAME);
+    if !manifest_path.is_file() {
+        return Err(Error::ExtensionNotFound {
+            path: source.to_string(),
+        });
+    }
+
+    let ext_manifest = ExtensionManifest::from_path(&manifest_path)?;
+    let name = ext_manifest.extension.name.clone();
+    let collisions = extension_tool_collisions(repo.root(), &ext_manifest)?;
+
+    let mut graph = DependencyGraph::new();
+    graph.add_extension(&ext_manifest)?;
+    let required = graph.required_presets();
+    let presets_added = if no_deps {
+        Vec::new()
+    } else {
+        resolve_preset_dependencies(&name, &required, &manifest)?
+    };
+
+    // Move or copy the extension source into .repository/extensions/<name>
+    let dest = extension_dir(&root, &name);
+    if let Some(clone) = clone {
+        clone.install(&dest)?;
+    } else if std::fs::canonicalize(source).ok() != std::fs::canonicalize(&dest).ok() {
+        if dest.exists() {
+            std::fs::remove_dir_all(&dest)?;
+        }
+        copy_dir_all(Path::new(source), &dest)?;
+    }
+
+    let locked = LockedExtension::new(&ext_manifest, source, &dest);
+    let mut lock = LockFile::load(&root)?;
+    lock.upsert(locked.clone());
+    lock.save(&root)?;
+
+    repo.update_manifest(|editor| {
+        // Keep the rest of the extension's configuration, such as its ref
+        editor.set_key(&["extensions", &name], "source", &json!(source));
+        for (preset, config) in &presets_added {
+            editor.set_entry(&["presets"], preset, config);
+        }
+        true
+    })?;
+
+    let presets_applied = apply_presets(&root, &presets_added)?;
+
+    Ok(ExtensionInstall {
+        locked,
+        path: dest,
+        collisions,
+        required,
+        presets_added,
+        presets_applied,
+    })
+}
+
+/// Run the provider registered for each preset.
+///
+/// Providers are async, so they run on a thread of their own with its own
+/// runtime; that works whether or not the caller is already inside one, as
+/// the MCP server is. Whatever a failed apply created is rolled back so the
+/// next attempt starts clean.
+fn apply_presets(root: &Path, presets: &[(String, Value)]) -> Result<Vec<PresetApply>> {
+    if presets.is_empty() {
+        return Ok(Vec::new());
+    }
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?;
+                Ok(presets
+                    .iter()
+                    .map(|(preset, config)| runtime.block_on(apply_preset(root, preset, config)))
+                    .collect())
+            })
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+async fn apply_preset(root: &Path, preset: &str, config: &Value) -> PresetApply {
+    let mut applied = PresetApply {
+        preset: preset.to_string(),
+        ..Default::default()
+    };
+    let Some(provider) = preset_provider(preset, config) else {
+        return applied;
+    };
+    applied.supported = true;
+    let context = preset_context(root, config);
+
+    let report = match provider.apply(&context).await {
+        Ok(report) => report,
+        Err(e) => {
+            applied.errors.push(e.to_string());
+            return applied;
+        }
+    };
+    applied.actions = report.actions_taken.clone();
+    applied.errors = report.errors.clone();
+    if report.is_failure() && !report.performed.is_empty() {
+        match provider.rollback(&context, &report).await {
+            Ok(()) => applied.rolled_back = true,
+            Err(e) => applied.errors.push(e.to_string()),
+        }
+    }
+    applied
+}
+
+/// The ref configured in config.toml for the extension installed from
+/// `source`, if any.
+fn configured_ref(manifest: &Manifest, source: &str) -> Option<String> {
+    manifest
+        .extensions
+        .values()
+        .filter_map(|v| serde_json::from_value::<ExtensionConfig>(v.clone()).ok())
+        .find(|config| config.source == source)
+        .and_then(|config| config.ref_pin)
+}
+
+/// Check every required preset against the manifest and installed runtimes.
+///
+/// Returns the presets that must be added, with their initial configuration.
+/// Fails without side effects if any version constraint cannot be satisfied.
+fn resolve_preset_dependencies(
+    extension: &str,
+    required: &[PresetRequirement],
+    manifest: &Manifest,
+) -> Result<Vec<(String, Value)>> {
+    let mut to_apply = Vec::new();
+    let mut unsatisfied = Vec::new();
+
+    for req in required {
+        if let Some(existing) = manifest.presets.get(&req.preset) {
+            // A configured preset pins its own version; check that instead of
+            // whatever happens to be on PATH.
+            let pinned = existing.get("version").and_then(|v| v.as_str());
+            let version = pinned
+                .map(String::from)
+                .or_else(|| detect_runtime_version(&req.preset));
+            check_requirement(req, version.as_deref(), &mut unsatisfied);
+            continue;
+        }
+
+        let mut config = json!({});
+        if req.is_constrained() {
+            let version = detect_runtime_version(&req.preset);
+            if !check_requirement(req, version.as_deref(), &mut unsatisfied) {
+                continue;
+            }
+            // Pin the Python version so the provider creates a matching venv
+            if req.preset == "env:python"
+                && let Some(v) = version.as_deref().and_then(parse_version)
+            {
+                let pin = v.iter().take(2).map(u64::to_string).collect::<Vec<_>>();
+                config["version"] = json!(pin.join("."));
+            }
+        }
+        to_apply.push((req.preset.clone(), config));
+    }
+
+    if !unsatisfied.is_empty() {
+        return Err(Error::UnsatisfiedPresets {
+            name: extension.to_string(),
+            requirements: unsatisfied
+                .iter()
+                .map(|line| format!("  - {line}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        });
+    }
+
+    Ok(to_apply)
+}
+
+/// Record a requirement as unsatisfied if `version` does not meet it.
+///
+/// Returns `true` if the requirement is satisfied.
+fn check_requirement(
+    req: &PresetRequirement,
+    version: Option<&str>,
+    unsatisfied: &mut Vec<String>,
+) -> bool {
+    if !req.is_constrained() {
+        return true;
+    }
+    match version {
+        Some(v) if req.is_satisfied_by(v) => true,
+        Some(v) => {
+            unsatisfied.push(format!(
+                "{} {} (found {})",
+                req.preset,
+                format_constraints(req),
+                v
+            ));
+            false
+        }
+        None => {
+            unsatisfied.push(format!(
+                "{} {} (not found)",
+                req.preset,
+                format_constraints(req)
+            ));
+            false
+        }
+    }
+}
+
+/// The version constraints of `req`, comma-separated
+pub fn format_constraints(req: &PresetRequirement) -> String {
+    req.constraints
+        .iter()
+        .map(|c| c.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Detect the version of the runtime backing a preset from its CLI.
+fn detect_runtime_version(preset: &str) -> Option<String> {
+    let candidates: &[&str] = match preset {
+        "env:python" => &["python3", "python"],
+        "env:node" => &["node"],
+        "env:rust" => &["rustc"],
+        _ => &[],
+    };
+
+    candidates.iter().find_map(|program| {
+        let output = Command::new(program).arg("--version").output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        // Python 2 prints its version to stderr
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        text.split_whitespace()
+            .find(|token| parse_version(token).is_some())
+            .map(|token| token.trim_start_matches(['v', 'V']).to_string())
+    })
+}
+
+/// Recursively copy a directory, skipping any `.git` directory.
+fn copy_dir_all(src: &Path, dest: &Path) -> Result<()> {
+    std::fs::create_dir_all(dest)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let target = dest.join(entry.file_name());
+        if file_type.is_dir() {
+            if entry.file_name() == ".git" {
+                continue;
+            }
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
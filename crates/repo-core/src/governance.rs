@@ -1,38 +1,28 @@
 //! Config governance: lint, diff, export/import
 //!
 //! Provides rule validation, drift detection against synced state,
-//! and AGENTS.md export/import capabilities.
+//! AGENTS.md export/import capabilities, and a rule catalog for
+//! documentation.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+use repo_fs::NormalizedPath;
+use repo_meta::schema::{FrontMatterMode, RuleDefinition, Severity, parse_rule_markdown};
+use repo_meta::{DefinitionLoader, LoadResult};
 use serde::{Deserialize, Serialize};
 
 use crate::config::Manifest;
 use crate::error::Result;
 use crate::ledger::{Ledger, ProjectionKind};
+use crate::rules::RuleRegistry;
 
 /// Severity level for lint warnings
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum WarnLevel {
-    /// Informational notice
-    Info,
-    /// Potential problem
-    Warning,
-    /// Configuration error
-    Error,
-}
-
-impl std::fmt::Display for WarnLevel {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Info => write!(f, "info"),
-            Self::Warning => write!(f, "warning"),
-            Self::Error => write!(f, "error"),
-        }
-    }
-}
+///
+/// This is the diagnostic scale shared with rule definitions: a rule's
+/// [`Severity`](repo_meta::schema::Severity) converts to and from it, so lint
+/// output and rule enforcement agree on what counts as an error.
+pub type WarnLevel = repo_meta::DiagnosticLevel;
 
 /// A lint warning about the configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -164,6 +154,208 @@ pub fn lint_rules(manifest: &Manifest, available_tools: &[String]) -> Vec<LintWa
     warnings
 }
 
+/// Lint the rule definitions loaded from `.repository/rules/`
+///
+/// Checks for:
+/// - Rule files that failed to load (error)
+/// - Rules with an empty instruction, at the level of the rule's severity
+/// - Mandatory rules without examples (hint)
+pub fn lint_rule_definitions(rules: &LoadResult<RuleDefinition>) -> Vec<LintWarning> {
+    let mut warnings: Vec<LintWarning> = rules
+        .warnings
+        .iter()
+        .map(|warning| LintWarning {
+            level: WarnLevel::Error,
+            message: warning.clone(),
+            tool: None,
+        })
+        .collect();
+
+    let mut ids: Vec<&String> = rules.definitions.keys().collect();
+    ids.sort();
+    for id in ids {
+        let rule = &rules.definitions[id];
+        let level = WarnLevel::from(rule.meta.severity);
+        if rule.content.instruction.trim().is_empty() {
+            warnings.push(LintWarning {
+                level,
+                message: format!("Rule '{}' has an empty instruction.", id),
+                tool: None,
+            });
+        }
+        let has_examples = rule
+            .examples
+            .as_ref()
+            .is_some_and(|e| !e.positive.is_empty() || !e.negative.is_empty());
+        if level == WarnLevel::Error && !has_examples {
+            warnings.push(LintWarning {
+                level: WarnLevel::Hint,
+                message: format!(
+                    "Mandatory rule '{}' has no examples. Examples help agents apply it.",
+                    id
+                ),
+                tool: None,
+            });
+        }
+    }
+
+    warnings
+}
+
+/// A structural problem with a rule file, found by [`validate_rules`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleProblem {
+    /// Rule file, relative to the repository root
+    pub file: PathBuf,
+    /// ID of the affected rule, if the file got far enough to name one
+    pub rule: Option<String>,
+    /// Human-readable description
+    pub reason: String,
+}
+
+/// Check the rules in `.repository/rules/` for structural problems
+///
+/// Unlike [`lint_rule_definitions`] this only reports hard errors, so an
+/// empty result means the rule set is safe to sync:
+/// - rule definitions (`*.toml`) and the registry parse
+/// - every rule ID is valid
+/// - no rule has an empty body
+/// - no two rule files define the same ID
+/// - every ID in a definition's `requires` names an existing rule
+///
+/// A registry rule may share its ID with a Markdown rule file, since the
+/// registry is where such rules are recorded for sync. Problems are sorted
+/// by file.
+pub fn validate_rules(root: &Path) -> Result<Vec<RuleProblem>> {
+    let rules_dir = root.join(".repository").join("rules");
+    let mut problems = Vec::new();
+    if !rules_dir.is_dir() {
+        return Ok(problems);
+    }
+
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(&rules_dir)?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .collect();
+    entries.sort();
+
+    // Rule ID -> file that defines it, for rule files (not the registry)
+    let mut defined: HashMap<String, PathBuf> = HashMap::new();
+    let mut registered: HashSet<String> = HashSet::new();
+    let mut requires: Vec<(PathBuf, String, String)> = Vec::new();
+
+    for path in entries {
+        let file = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+        let problem = |rule: Option<&str>, reason: String| RuleProblem {
+            file: file.clone(),
+            rule: rule.map(String::from),
+            reason,
+        };
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let extension = path.extension().and_then(|e| e.to_str());
+
+        let (id, empty) = if name == "registry.toml" {
+            let registry = match RuleRegistry::load(path.clone()) {
+                Ok(registry) => registry,
+                Err(e) => {
+                    problems.push(problem(None, format!("Cannot parse registry: {}", e)));
+                    continue;
+                }
+            };
+            for rule in registry.all_rules() {
+                if let Err(e) = validate_rule_id(&rule.id) {
+                    problems.push(problem(Some(&rule.id), e));
+                }
+                if rule.content.trim().is_empty() {
+                    problems.push(problem(Some(&rule.id), "Rule body is empty".into()));
+                }
+                if !registered.insert(rule.id.clone()) {
+                    problems.push(problem(
+                        Some(&rule.id),
+                        format!("Duplicate rule ID '{}' in registry", rule.id),
+                    ));
+                }
+            }
+            continue;
+        } else if extension == Some("toml") {
+            let text = std::fs::read_to_string(&path)?;
+            let rule: RuleDefinition = match toml::from_str(&text) {
+                Ok(rule) => rule,
+                Err(e) => {
+                    problems.push(problem(
+                        None,
+                        format!("Cannot parse rule definition: {}", e),
+                    ));
+                    continue;
+                }
+            };
+            for required in &rule.meta.requires {
+                requires.push((file.clone(), rule.meta.id.clone(), required.clone()));
+            }
+            let empty = rule.content.instruction.trim().is_empty();
+            (rule.meta.id, empty)
+        } else if extension == Some("md") {
+            let text = std::fs::read_to_string(&path)?;
+            let id = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+            let rule = match parse_rule_markdown(&text, FrontMatterMode::Strict) {
+                Ok(rule) => rule,
+                Err(e) => {
+                    problems.push(problem(Some(id), e.to_string()));
+                    continue;
+                }
+            };
+            if let Some(declared) = rule.front_matter.id.as_deref().filter(|d| *d != id) {
+                problems.push(problem(
+                    Some(id),
+                    format!(
+                        "Front-matter ID '{}' does not match the file name",
+                        declared
+                    ),
+                ));
+            }
+            for required in rule.front_matter.requires {
+                requires.push((file.clone(), id.to_string(), required));
+            }
+            (id.to_string(), rule.body.trim().is_empty())
+        } else {
+            continue;
+        };
+
+        if let Err(e) = validate_rule_id(&id) {
+            problems.push(problem(Some(&id), e));
+        }
+        if empty {
+            problems.push(problem(Some(&id), "Rule body is empty".into()));
+        }
+        if let Some(other) = defined.get(&id) {
+            problems.push(problem(
+                Some(&id),
+                format!(
+                    "Duplicate rule ID '{}', also defined in {}",
+                    id,
+                    other.display()
+                ),
+            ));
+        } else {
+            defined.insert(id, file.clone());
+        }
+    }
+
+    for (file, id, required) in requires {
+        if !defined.contains_key(&required) && !registered.contains(&required) {
+            problems.push(RuleProblem {
+                file,
+                rule: Some(id),
+                reason: format!("Required rule '{}' does not exist", required),
+            });
+        }
+    }
+
+    problems.sort_by(|a, b| a.file.cmp(&b.file));
+    Ok(problems)
+}
+
 /// Compare current config file state against the last-synced state in the ledger
 ///
 /// For each tool in the config, checks if its generated config files:
@@ -307,6 +499,77 @@ pub fn export_agents_md(root: &Path) -> Result<String> {
     Ok(output)
 }
 
+/// One rule in the documentation catalog
+///
+/// A flat view of a [`RuleDefinition`] for generating coding-standards
+/// documentation; unlike the AGENTS.md bundle it is not meant for
+/// re-import. Serialized as:
+///
+/// ```json
+/// {
+///   "id": "python-snake-case",
+///   "severity": "mandatory",
+///   "tags": ["python", "style"],
+///   "instruction": "Use snake_case for all Python variables.",
+///   "targets": ["**/*.py"],
+///   "positive_examples": ["my_variable = 1"],
+///   "negative_examples": ["myVariable = 1"]
+/// }
+/// ```
+///
+/// Every field is always present; `targets` is empty for rules that apply
+/// to all files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogRule {
+    /// Rule identifier
+    pub id: String,
+    /// How strictly the rule should be enforced
+    pub severity: Severity,
+    /// Tags for categorization
+    pub tags: Vec<String>,
+    /// The instruction text
+    pub instruction: String,
+    /// Glob patterns of the files the rule applies to
+    pub targets: Vec<String>,
+    /// Examples that follow the rule
+    pub positive_examples: Vec<String>,
+    /// Examples that violate the rule
+    pub negative_examples: Vec<String>,
+}
+
+impl From<RuleDefinition> for CatalogRule {
+    fn from(rule: RuleDefinition) -> Self {
+        let examples = rule.examples.unwrap_or_default();
+        Self {
+            id: rule.meta.id,
+            severity: rule.meta.severity,
+            tags: rule.meta.tags,
+            instruction: rule.content.instruction,
+            targets: rule.targets.unwrap_or_default().file_patterns,
+            positive_examples: examples.positive,
+            negative_examples: examples.negative,
+        }
+    }
+}
+
+/// Export the rule definitions in `.repository/rules/` as a catalog
+///
+/// Rules are sorted by ID. Fails if any rule definition cannot be loaded,
+/// so the catalog never silently misses a rule.
+pub fn export_rule_catalog(root: &Path) -> Result<Vec<CatalogRule>> {
+    let loaded = DefinitionLoader::new().load_rules(&NormalizedPath::new(root))?;
+    if let Some(error) = loaded.errors.into_iter().next() {
+        return Err(error.into());
+    }
+    let mut catalog: Vec<CatalogRule> = loaded
+        .definitions
+        .into_values()
+        .map(CatalogRule::from)
+        .collect();
+    catalog.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(catalog)
+}
+
 /// Import rules from AGENTS.md format
 ///
 /// Parses markdown content with `## rule-id` headers and returns
@@ -347,6 +610,8 @@ pub fn import_agents_md(content: &str) -> Vec<(String, String)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use repo_meta::schema::{RuleContent, RuleMeta};
+
     fn make_manifest(tools: &[&str], rules: &[&str]) -> Manifest {
         Manifest {
             tools: tools.iter().map(|s| s.to_string()).collect(),
@@ -500,11 +765,153 @@ mod tests {
         assert!(imported[1].1.contains("Beta rule content."));
     }
 
+    #[test]
+    fn test_export_rule_catalog() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let rules_dir = temp.path().join(".repository/rules");
+        std::fs::create_dir_all(&rules_dir).unwrap();
+        std::fs::write(
+            rules_dir.join("snake.toml"),
+            "[meta]\nid = \"python-snake-case\"\nseverity = \"mandatory\"\n\
+             tags = [\"python\", \"style\"]\n\n[content]\ninstruction = \"Use snake_case.\"\n\n\
+             [examples]\npositive = [\"my_var = 1\"]\n\n[targets]\nfiles = [\"**/*.py\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            rules_dir.join("docs.toml"),
+            "[meta]\nid = \"docs\"\n\n[content]\ninstruction = \"Document public APIs.\"\n",
+        )
+        .unwrap();
+        // Markdown rules are not definitions and stay out of the catalog
+        std::fs::write(rules_dir.join("notes.md"), "Be nice.").unwrap();
+
+        let catalog = export_rule_catalog(temp.path()).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string_pretty(&catalog).unwrap()).unwrap();
+        let rules = json.as_array().unwrap();
+        let ids: Vec<&str> = rules.iter().map(|r| r["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["docs", "python-snake-case"]);
+
+        let snake = &rules[1];
+        assert_eq!(snake["severity"], "mandatory");
+        assert_eq!(snake["tags"], serde_json::json!(["python", "style"]));
+        assert_eq!(snake["targets"], serde_json::json!(["**/*.py"]));
+        assert_eq!(
+            snake["positive_examples"],
+            serde_json::json!(["my_var = 1"])
+        );
+        assert_eq!(snake["negative_examples"], serde_json::json!([]));
+        assert_eq!(rules[0]["severity"], "suggestion");
+        assert_eq!(rules[0]["targets"], serde_json::json!([]));
+
+        // A broken definition fails the export instead of being skipped
+        std::fs::write(rules_dir.join("broken.toml"), "[meta\n").unwrap();
+        assert!(export_rule_catalog(temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_validate_rules() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let rules_dir = temp.path().join(".repository/rules");
+        std::fs::create_dir_all(&rules_dir).unwrap();
+        std::fs::write(rules_dir.join("style.md"), "tags: style\nBe consistent.\n").unwrap();
+        std::fs::write(
+            rules_dir.join("naming.toml"),
+            "[meta]\nid = \"naming\"\nrequires = [\"style\"]\n\n\
+             [content]\ninstruction = \"Use clear names.\"\n",
+        )
+        .unwrap();
+        assert!(validate_rules(temp.path()).unwrap().is_empty());
+
+        std::fs::write(
+            rules_dir.join("naming2.toml"),
+            "[meta]\nid = \"naming\"\n\n[content]\ninstruction = \"  \"\n",
+        )
+        .unwrap();
+        std::fs::write(rules_dir.join("broken.toml"), "[meta\n").unwrap();
+        std::fs::write(rules_dir.join("loose.md"), "---\ntags: style\n---\nBody\n").unwrap();
+        let problems = validate_rules(temp.path()).unwrap();
+        let reasons: Vec<(&str, &str)> = problems
+            .iter()
+            .map(|p| {
+                (
+                    p.file.file_name().unwrap().to_str().unwrap(),
+                    p.reason.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(reasons.len(), 4, "{:?}", reasons);
+        assert!(reasons[0].0 == "broken.toml" && reasons[0].1.starts_with("Cannot parse"));
+        assert!(reasons[1].0 == "loose.md" && reasons[1].1.contains("at line 2"));
+        assert_eq!(reasons[2], ("naming2.toml", "Rule body is empty"));
+        assert!(reasons[3].1.starts_with("Duplicate rule ID 'naming'"));
+    }
+
     #[test]
     fn test_warn_level_display() {
         assert_eq!(WarnLevel::Info.to_string(), "info");
         assert_eq!(WarnLevel::Warning.to_string(), "warning");
         assert_eq!(WarnLevel::Error.to_string(), "error");
+        assert_eq!(WarnLevel::Hint.to_string(), "hint");
+    }
+
+    fn make_rule(id: &str, severity: Severity, instruction: &str) -> RuleDefinition {
+        RuleDefinition {
+            meta: RuleMeta {
+                id: id.to_string(),
+                severity,
+                tags: vec![],
+                requires: vec![],
+            },
+            content: RuleContent {
+                instruction: instruction.to_string(),
+            },
+            examples: None,
+            targets: None,
+        }
+    }
+
+    #[test]
+    fn test_lint_rule_definitions_uses_rule_severity() {
+        let rules = LoadResult {
+            definitions: [
+                make_rule("required", Severity::Mandatory, " "),
+                make_rule("suggested", Severity::Suggestion, ""),
+                make_rule("fine", Severity::Suggestion, "Use snake_case."),
+            ]
+            .into_iter()
+            .map(|rule| (rule.meta.id.clone(), rule))
+            .collect(),
+            warnings: vec!["broken.toml: invalid TOML".to_string()],
+            errors: vec![],
+        };
+
+        let warnings = lint_rule_definitions(&rules);
+        let levels: Vec<(WarnLevel, &str)> = warnings
+            .iter()
+            .map(|w| (w.level, w.message.as_str()))
+            .collect();
+        assert_eq!(
+            levels,
+            vec![
+                (WarnLevel::Error, "broken.toml: invalid TOML"),
+                (
+                    WarnLevel::Error,
+                    "Rule 'required' has an empty instruction."
+                ),
+                (
+                    WarnLevel::Hint,
+                    "Mandatory rule 'required' has no examples. Examples help agents apply it."
+                ),
+                (
+                    WarnLevel::Warning,
+                    "Rule 'suggested' has an empty instruction."
+                ),
+            ]
+        );
+        // Report levels convert back to the rule severities they came from
+        assert_eq!(Severity::from(warnings[1].level), Severity::Mandatory);
+        assert_eq!(Severity::from(warnings[3].level), Severity::Suggestion);
     }
 
     #[test]
@@ -1,18 +1,46 @@
 //! Lifecycle hooks for repository events
 //!
-//! Provides pre/post hooks for branch creation, deletion, and sync
-//! events. Hooks are configured in config.toml as `[[hooks]]`
-//! entries and executed as subprocesses.
+//! Provides pre/post hooks for branch creation, deletion, sync and
+//! initialization. Hooks are configured as `[[hooks]]` entries in
+//! config.toml or in [`HOOKS_FILE`], and executed as subprocesses by a
+//! [`HookRunner`]:
+//!
+//! ```toml
+//! [[hooks]]
+//! event = "post-branch-create"
+//! command = "npm"
+//! args = ["install"]
+//! timeout = 300
+//! ```
+//!
+//! Hooks receive `REPO_ROOT`, `REPO_EVENT` and, for branch events,
+//! `REPO_BRANCH` and `REPO_WORKTREE` as environment variables. A failing
+//! `pre-*` hook aborts the operation; a failing `post-*` hook is logged.
 
 use std::collections::HashMap;
 use std::fmt;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
+use repo_fs::NormalizedPath;
 use serde::{Deserialize, Serialize};
 
+use crate::cancel::{self, POLL_INTERVAL, drain};
+use crate::config::Manifest;
 use crate::error::{Error, Result};
 
+/// File holding hooks alongside those in config.toml, relative to the
+/// repository root
+///
+/// Unlike config.toml it is not rewritten by `repo init`, so it can carry
+/// `post-init` hooks.
+pub const HOOKS_FILE: &str = ".repository/hooks.toml";
+
+/// How long a hook may run before it is killed, unless it sets `timeout`
+pub const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// Events that can trigger hooks
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -29,6 +57,8 @@ pub enum HookEvent {
     PreSync,
     /// After sync runs
     PostSync,
+    /// After a repository is initialized
+    PostInit,
 }
 
 impl fmt::Display for HookEvent {
@@ -40,6 +70,7 @@ impl fmt::Display for HookEvent {
             Self::PostBranchDelete => write!(f, "post-branch-delete"),
             Self::PreSync => write!(f, "pre-sync"),
             Self::PostSync => write!(f, "post-sync"),
+            Self::PostInit => write!(f, "post-init"),
         }
     }
 }
@@ -54,10 +85,20 @@ impl HookEvent {
             "post-branch-delete" => Some(Self::PostBranchDelete),
             "pre-sync" => Some(Self::PreSync),
             "post-sync" => Some(Self::PostSync),
+            "post-init" => Some(Self::PostInit),
             _ => None,
         }
     }
 
+    /// Whether the event fires before its operation, so a failing hook
+    /// aborts it
+    pub fn is_pre(&self) -> bool {
+        matches!(
+            self,
+            Self::PreBranchCreate | Self::PreBranchDelete | Self::PreSync
+        )
+    }
+
     /// List all valid event names
     pub fn all_names() -> &'static [&'static str] {
         &[
@@ -67,6 +108,7 @@ impl HookEvent {
             "post-branch-delete",
             "pre-sync",
             "post-sync",
+            "post-init",
         ]
     }
 }
@@ -83,6 +125,9 @@ pub struct HookConfig {
     pub args: Vec<String>,
     /// Working directory override (defaults to repository root)
     pub working_dir: Option<PathBuf>,
+    /// Seconds the hook may run before it is killed (defaults to 60)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
 }
 
 /// Context variables available to hooks during execution
@@ -97,8 +142,10 @@ impl HookContext {
     pub fn for_branch(branch_name: &str, worktree_path: Option<&Path>) -> Self {
         let mut vars = HashMap::new();
         vars.insert("BRANCH_NAME".to_string(), branch_name.to_string());
+        vars.insert("REPO_BRANCH".to_string(), branch_name.to_string());
         if let Some(path) = worktree_path {
             vars.insert("WORKTREE_PATH".to_string(), path.display().to_string());
+            vars.insert("REPO_WORKTREE".to_string(), path.display().to_string());
         }
         Self { vars }
     }
@@ -126,59 +173,172 @@ pub struct HookResult {
     pub stderr: String,
     /// Exit code
     pub exit_code: Option<i32>,
+    /// Whether the hook was killed for running past its timeout
+    pub timed_out: bool,
 }
 
-/// Run all hooks matching the given event
+impl HookResult {
+    /// Why the hook failed, with its stderr for actionable diagnostics
+    fn failure_message(&self, timeout: Duration) -> String {
+        if self.timed_out {
+            return format!("Hook timed out after {}s", timeout.as_secs());
+        }
+        let stderr_snippet = self.stderr.trim();
+        if stderr_snippet.is_empty() {
+            format!(
+                "Hook exited with non-zero status (exit code: {:?})",
+                self.exit_code
+            )
+        } else {
+            format!(
+                "Hook exited with non-zero status (exit code: {:?}): {}",
+                self.exit_code, stderr_snippet
+            )
+        }
+    }
+}
+
+/// The `[[hooks]]` entries of [`HOOKS_FILE`]
+#[derive(Debug, Default, Deserialize)]
+struct HooksFile {
+    #[serde(default)]
+    hooks: Vec<HookConfig>,
+}
+
+/// Runs the hooks configured for a repository
+#[derive(Debug, Clone)]
+pub struct HookRunner {
+    /// Repository root: the default working directory and `REPO_ROOT`
+    root: PathBuf,
+    hooks: Vec<HookConfig>,
+    /// Timeout for hooks that do not set their own
+    timeout: Duration,
+}
+
+impl HookRunner {
+    /// Create a runner for `hooks` in the repository at `root`
+    pub fn new(root: impl Into<PathBuf>, hooks: Vec<HookConfig>) -> Self {
+        Self {
+            root: root.into(),
+            hooks,
+            timeout: DEFAULT_HOOK_TIMEOUT,
+        }
+    }
+
+    /// Create a runner for the hooks in config.toml followed by those in
+    /// [`HOOKS_FILE`]
+    ///
+    /// Missing files contribute no hooks. Files that cannot be read or
+    /// parsed are logged and skipped; `repo check` reports invalid config.
+    pub fn from_repository(root: &NormalizedPath) -> Self {
+        let mut hooks = Vec::new();
+        let config = root.join(".repository").join("config.toml");
+        if let Some(content) = read_hook_source(&config) {
+            match Manifest::parse(&content) {
+                Ok(manifest) => hooks.extend(manifest.hooks),
+                Err(e) => tracing::warn!("Hooks in {} are ignored: {}", config, e),
+            }
+        }
+        let file = root.join(HOOKS_FILE);
+        if let Some(content) = read_hook_source(&file) {
+            match toml::from_str::<HooksFile>(&content) {
+                Ok(parsed) => hooks.extend(parsed.hooks),
+                Err(e) => tracing::warn!("Hooks in {} are ignored: {}", file, e),
+            }
+        }
+        Self::new(root.to_native(), hooks)
+    }
+
+    /// Set the timeout for hooks that do not set their own
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The configured hooks, for all events
+    pub fn hooks(&self) -> &[HookConfig] {
+        &self.hooks
+    }
+
+    /// Run the hooks for `event`, in order
+    ///
+    /// `REPO_ROOT` and `REPO_EVENT` are added to `context`. For `pre-*`
+    /// events the first hook that fails, cannot be started or times out
+    /// stops execution and is returned as [`Error::HookFailed`]; for
+    /// `post-*` events failures are logged and the remaining hooks run.
+    pub fn run(&self, event: HookEvent, context: &HookContext) -> Result<Vec<HookResult>> {
+        let mut context = context.clone();
+        context
+            .vars
+            .insert("REPO_ROOT".to_string(), self.root.display().to_string());
+        context
+            .vars
+            .insert("REPO_EVENT".to_string(), event.to_string());
+
+        let mut results = Vec::new();
+        for hook in self.hooks.iter().filter(|h| h.event == event) {
+            let timeout = hook
+                .timeout
+                .map(Duration::from_secs)
+                .unwrap_or(self.timeout);
+            let error = match execute_hook(hook, &context, &self.root, timeout) {
+                Ok(result) if result.success => {
+                    results.push(result);
+                    continue;
+                }
+                Ok(result) => {
+                    let error = Error::HookFailed {
+                        event: event.to_string(),
+                        command: hook.command.clone(),
+                        message: result.failure_message(timeout),
+                    };
+                    results.push(result);
+                    error
+                }
+                Err(Error::Cancelled) => return Err(Error::Cancelled),
+                Err(e) => e,
+            };
+            if event.is_pre() {
+                return Err(error);
+            }
+            tracing::warn!("{}", error);
+        }
+        Ok(results)
+    }
+}
+
+/// Content of a hook source file, or None if it is missing or unreadable
+fn read_hook_source(path: &NormalizedPath) -> Option<String> {
+    if !path.exists() {
+        return None;
+    }
+    std::fs::read_to_string(path.to_native())
+        .inspect_err(|e| tracing::warn!("Hooks in {} are ignored: {}", path, e))
+        .ok()
+}
+
+/// Run all hooks matching the given event, with `default_dir` as the
+/// repository root
 ///
-/// Hooks are executed in order. If a hook fails (non-zero exit), execution
-/// stops and an error is returned (fail-fast behavior).
+/// Hooks are executed in order, as by [`HookRunner::run`]: a failing
+/// `pre-*` hook stops execution and returns an error, a failing `post-*`
+/// hook is logged.
 pub fn run_hooks(
     hooks: &[HookConfig],
     event: HookEvent,
     context: &HookContext,
     default_dir: &Path,
 ) -> Result<Vec<HookResult>> {
-    let matching: Vec<&HookConfig> = hooks.iter().filter(|h| h.event == event).collect();
-
-    let mut results = Vec::new();
-
-    for hook in matching {
-        let result = execute_hook(hook, context, default_dir)?;
-        let failed = !result.success;
-
-        if failed {
-            // Include stderr in the error message for actionable diagnostics
-            let stderr_snippet = result.stderr.trim();
-            let message = if stderr_snippet.is_empty() {
-                format!(
-                    "Hook exited with non-zero status (exit code: {:?})",
-                    result.exit_code
-                )
-            } else {
-                format!(
-                    "Hook exited with non-zero status (exit code: {:?}): {}",
-                    result.exit_code, stderr_snippet
-                )
-            };
-            results.push(result);
-            return Err(Error::HookFailed {
-                event: event.to_string(),
-                command: hook.command.clone(),
-                message,
-            });
-        }
-
-        results.push(result);
-    }
-
-    Ok(results)
+    HookRunner::new(default_dir, hooks.to_vec()).run(event, context)
 }
 
-/// Execute a single hook as a subprocess
+/// Execute a single hook as a subprocess, killing it after `timeout` or
+/// once the current [`CancelToken`](crate::CancelToken) is cancelled
 fn execute_hook(
     hook: &HookConfig,
     context: &HookContext,
     default_dir: &Path,
+    timeout: Duration,
 ) -> Result<HookResult> {
     let work_dir = hook.working_dir.as_deref().unwrap_or(default_dir);
 
@@ -207,23 +367,70 @@ fn execute_hook(
         .map(|arg| substitute_vars(arg, &context.vars))
         .collect();
 
-    let output = Command::new(&hook.command)
+    cancel::check()?;
+    // Arguments are passed as-is, never through a shell, so they need no
+    // quoting on any platform
+    let mut child = Command::new(&hook.command)
         .args(&args)
         .current_dir(work_dir)
         .envs(&context.vars)
-        .output()
-        .map_err(Error::Io)?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| Error::HookFailed {
+            event: hook.event.to_string(),
+            command: hook.command.clone(),
+            message: format!("Hook could not be started: {}", e),
+        })?;
+    let stdout = child.stdout.take().map(drain);
+    let stderr = child.stderr.take().map(drain);
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(Error::Io)? {
+            break Some(status);
+        }
+        if cancel::is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(Error::Cancelled);
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let Some(status) = status else {
+        // Processes the hook started may still hold its output open, so
+        // the output is abandoned rather than waited for
+        return Ok(HookResult {
+            event: hook.event,
+            command: hook.command.clone(),
+            success: false,
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: None,
+            timed_out: true,
+        });
+    };
+    let collect = |pipe: Option<JoinHandle<Vec<u8>>>| {
+        pipe.and_then(|handle| handle.join().ok())
+            .map(|buf| String::from_utf8_lossy(&buf).to_string())
+            .unwrap_or_default()
+    };
 
     Ok(HookResult {
         event: hook.event,
         command: hook.command.clone(),
-        success: output.status.success(),
-        stdout,
-        stderr,
-        exit_code: output.status.code(),
+        success: status.success(),
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+        exit_code: status.code(),
+        timed_out: false,
     })
 }
 
@@ -278,6 +485,7 @@ mod tests {
             command: "npm".to_string(),
             args: vec!["install".to_string()],
             working_dir: None,
+            timeout: None,
         };
 
         let json = serde_json::to_string(&hook).unwrap();
@@ -320,6 +528,7 @@ mod tests {
             command: "echo".to_string(),
             args: vec!["sync".to_string()],
             working_dir: None,
+            timeout: None,
         }];
 
         let ctx = HookContext::default();
@@ -343,6 +552,7 @@ mod tests {
                 vec!["hello".to_string()]
             },
             working_dir: None,
+            timeout: None,
         }];
 
         let ctx = HookContext::default();
@@ -368,6 +578,7 @@ mod tests {
                 vec![]
             },
             working_dir: None,
+            timeout: None,
         }];
 
         let ctx = HookContext::default();
@@ -398,17 +609,19 @@ args = ["install"]
         assert_eq!(hook.args, vec!["install"]);
     }
 
-    /// Verify HookEvent has exactly 6 variants (pre/post for branch-create,
-    /// branch-delete, sync). This catches unwired events being added without
-    /// updating all_names() and the rest of the matching infrastructure.
+    /// Verify HookEvent has exactly 7 variants (pre/post for branch-create,
+    /// branch-delete, sync, plus post-init). This catches unwired events being
+    /// added without updating all_names() and the rest of the matching
+    /// infrastructure.
     #[test]
     fn test_hook_event_enum_has_no_agent_events() {
         let names = HookEvent::all_names();
         assert_eq!(
             names.len(),
-            6,
-            "Expected exactly 6 hook events (pre/post for branch-create, branch-delete, sync), \
-             found {}. If you added a new event, make sure it is wired to a call site.",
+            7,
+            "Expected exactly 7 hook events (pre/post for branch-create, branch-delete, sync, \
+             and post-init), found {}. If you added a new event, make sure it is wired to a \
+             call site.",
             names.len()
         );
 
@@ -420,6 +633,7 @@ args = ["install"]
             "post-branch-delete",
             "pre-sync",
             "post-sync",
+            "post-init",
         ];
         for name in &expected {
             assert!(
@@ -468,6 +682,7 @@ args = ["install"]
                 format!("echo 'hook ran' > '{}'", marker_path.display()),
             ],
             working_dir: None,
+            timeout: None,
         }];
 
         let ctx = HookContext::for_sync();
@@ -507,6 +722,7 @@ args = ["install"]
                 format!("echo 'oops' > '{}'", marker_path.display()),
             ],
             working_dir: None,
+            timeout: None,
         }];
 
         let ctx = HookContext::for_sync();
@@ -538,6 +754,7 @@ args = ["install"]
                 "echo 'failing on purpose' >&2; exit 1".to_string(),
             ],
             working_dir: None,
+            timeout: None,
         }];
 
         let ctx = HookContext::for_sync();
@@ -561,4 +778,189 @@ args = ["install"]
             err_msg
         );
     }
+
+    fn sh_hook(event: HookEvent, script: &str) -> HookConfig {
+        HookConfig {
+            event,
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            working_dir: None,
+            timeout: None,
+        }
+    }
+
+    #[test]
+    fn test_runner_sets_environment() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let runner = HookRunner::new(
+            temp.path(),
+            vec![sh_hook(
+                HookEvent::PostBranchCreate,
+                "echo \"$REPO_ROOT|$REPO_EVENT|$REPO_BRANCH|$REPO_WORKTREE\"",
+            )],
+        );
+
+        let ctx = HookContext::for_branch("feature-x", Some(Path::new("/repo/feature-x")));
+        let results = runner.run(HookEvent::PostBranchCreate, &ctx).unwrap();
+        assert_eq!(
+            results[0].stdout.trim(),
+            format!(
+                "{}|post-branch-create|feature-x|/repo/feature-x",
+                temp.path().display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_post_hook_failure_is_not_an_error() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let runner = HookRunner::new(
+            temp.path(),
+            vec![
+                sh_hook(HookEvent::PostSync, "exit 3"),
+                sh_hook(HookEvent::PostSync, "echo second"),
+            ],
+        );
+
+        let results = runner
+            .run(HookEvent::PostSync, &HookContext::for_sync())
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(!results[0].success);
+        assert_eq!(results[0].exit_code, Some(3));
+        assert_eq!(results[1].stdout.trim(), "second");
+
+        // A pre hook that cannot be started aborts
+        let missing = HookConfig {
+            command: "repo-no-such-hook-command".to_string(),
+            ..sh_hook(HookEvent::PreSync, "")
+        };
+        let err = HookRunner::new(temp.path(), vec![missing])
+            .run(HookEvent::PreSync, &HookContext::for_sync())
+            .unwrap_err();
+        assert!(err.to_string().contains("could not be started"), "{err}");
+    }
+
+    #[test]
+    fn test_hook_timeout() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let started = Instant::now();
+        let runner = HookRunner::new(temp.path(), vec![sh_hook(HookEvent::PreSync, "sleep 10")])
+            .with_timeout(Duration::from_millis(200));
+
+        let err = runner
+            .run(HookEvent::PreSync, &HookContext::for_sync())
+            .unwrap_err();
+        assert!(err.to_string().contains("timed out"), "{err}");
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // A hook's own timeout wins over the runner's
+        let hook = HookConfig {
+            timeout: Some(5),
+            ..sh_hook(HookEvent::PreSync, "sleep 0.5")
+        };
+        let results = HookRunner::new(temp.path(), vec![hook])
+            .with_timeout(Duration::from_millis(100))
+            .run(HookEvent::PreSync, &HookContext::for_sync())
+            .unwrap();
+        assert!(results[0].success);
+    }
+
+    #[test]
+    fn test_cancel_kills_running_post_hook() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let token = crate::CancelToken::new();
+        let canceller = token.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            canceller.cancel();
+        });
+
+        let started = Instant::now();
+        let _scope = token.enter();
+        let runner = HookRunner::new(
+            temp.path(),
+            vec![
+                sh_hook(HookEvent::PostSync, "sleep 10"),
+                sh_hook(HookEvent::PostSync, "echo second"),
+            ],
+        );
+
+        // Unlike a post hook failure, cancellation stops the run
+        let err = runner
+            .run(HookEvent::PostSync, &HookContext::for_sync())
+            .unwrap_err();
+        assert!(matches!(err, Error::Cancelled), "{err}");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    /// Arguments with spaces and quotes reach the hook verbatim; Windows
+    /// has no argv, so this exercises std's quoting there. git behaves the
+    /// same on every platform.
+    #[test]
+    fn test_hook_args_are_passed_verbatim() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let value = r#"a b "quoted" \ c\"#;
+        let hook = HookConfig {
+            event: HookEvent::PostInit,
+            command: "git".to_string(),
+            args: vec![
+                "config".to_string(),
+                "--file".to_string(),
+                "with space.cfg".to_string(),
+                "hook.value".to_string(),
+                value.to_string(),
+            ],
+            working_dir: None,
+            timeout: None,
+        };
+        let results = HookRunner::new(temp.path(), vec![hook])
+            .run(HookEvent::PostInit, &HookContext::default())
+            .unwrap();
+        assert!(results[0].success, "{}", results[0].stderr);
+
+        let config = git2::Config::open(&temp.path().join("with space.cfg")).unwrap();
+        assert_eq!(config.get_string("hook.value").unwrap(), value);
+    }
+
+    #[test]
+    fn test_runner_from_repository() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo_dir = temp.path().join(".repository");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        std::fs::write(
+            repo_dir.join("config.toml"),
+            "[core]\nmode = \"standard\"\n\n\
+             [[hooks]]\nevent = \"pre-sync\"\ncommand = \"cargo\"\nargs = [\"check\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            repo_dir.join("hooks.toml"),
+            "[[hooks]]\nevent = \"post-init\"\ncommand = \"npm\"\nargs = [\"install\"]\ntimeout = 300\n",
+        )
+        .unwrap();
+
+        let runner = HookRunner::from_repository(&NormalizedPath::new(temp.path()));
+        let hooks = runner.hooks();
+        assert_eq!(hooks.len(), 2);
+        assert_eq!(hooks[0].event, HookEvent::PreSync);
+        assert_eq!(hooks[1].event, HookEvent::PostInit);
+        assert_eq!(hooks[1].timeout, Some(300));
+
+        // Without either file there is nothing to run
+        let empty = tempfile::TempDir::new().unwrap();
+        assert!(
+            HookRunner::from_repository(&NormalizedPath::new(empty.path()))
+                .hooks()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_is_pre() {
+        assert!(HookEvent::PreSync.is_pre());
+        assert!(HookEvent::PreBranchDelete.is_pre());
+        assert!(!HookEvent::PostInit.is_pre());
+        assert!(!HookEvent::PostBranchCreate.is_pre());
+    }
 }
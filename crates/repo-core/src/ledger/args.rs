@@ -0,0 +1,52 @@
+//! Typed intent arguments
+//!
+//! Intents store their arguments as an untyped JSON value. Each kind of
+//! intent, told apart by the prefix of its ID, has a schema here that the
+//! arguments are checked against when a ledger is loaded, and that
+//! consumers read them through with [`Intent::typed_args`].
+//!
+//! [`Intent::typed_args`]: super::Intent::typed_args
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Arguments of a `tool:<name>` intent
+///
+/// Tool intents record everything in their projections, so the arguments
+/// are an empty table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolArgs {}
+
+/// Arguments of a `rules:<tool>` intent
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RulesArgs {
+    /// Rule blocks written for the tool, keyed by rule UUID
+    ///
+    /// Absent in intents written before per-rule checksums were recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<BTreeMap<String, RuleBlock>>,
+}
+
+/// A rule block recorded in a [`RulesArgs`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleBlock {
+    /// The rule's ID
+    #[serde(default)]
+    pub id: String,
+    /// Checksum of the rendered block
+    pub checksum: String,
+    /// File the rule was written to
+    ///
+    /// Absent in intents written before rules were tracked per file, whose
+    /// rules all went to the tool's rules file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+/// Arguments of an `mcp:<tool>` intent
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpArgs {
+    /// Names of the MCP servers synced to the tool
+    pub servers: Vec<String>,
+}
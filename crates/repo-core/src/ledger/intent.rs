@@ -4,8 +4,11 @@
 //! or more tools. Each intent has projections that track how the rule is
 //! rendered in each tool's configuration format.
 
+use super::args::{McpArgs, RulesArgs, ToolArgs};
 use super::projection::Projection;
+use crate::{Error, Result};
 use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::path::Path;
@@ -64,11 +67,42 @@ impl Intent {
         }
     }
 
+    /// Deserialize the arguments into `T`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::MalformedIntent`] if the arguments don't fit `T`.
+    pub fn typed_args<T: DeserializeOwned>(&self) -> Result<T> {
+        T::deserialize(&self.args).map_err(|e| Error::MalformedIntent {
+            uuid: self.uuid,
+            reason: format!("invalid args for '{}': {}", self.id, e),
+        })
+    }
+
+    /// Check the arguments against the schema for this intent's kind
+    ///
+    /// `tool:`, `rules:` and `mcp:` intents have a schema; the arguments of
+    /// other intents are not checked.
+    pub fn validate(&self) -> Result<()> {
+        let (kind, _) = self.id.split_once(':').unwrap_or_default();
+        match kind {
+            "tool" => self.typed_args::<ToolArgs>().map(drop),
+            "rules" => self.typed_args::<RulesArgs>().map(drop),
+            "mcp" => self.typed_args::<McpArgs>().map(drop),
+            _ => Ok(()),
+        }
+    }
+
     /// Get all projections for this intent
     pub fn projections(&self) -> &[Projection] {
         &self.projections
     }
 
+    /// Get all projections for this intent mutably
+    pub fn projections_mut(&mut self) -> &mut [Projection] {
+        &mut self.projections
+    }
+
     /// Add a projection to this intent
     pub fn add_projection(&mut self, projection: Projection) {
         self.projections.push(projection);
@@ -117,4 +151,30 @@ mod tests {
         let serialized = toml::to_string(&intent).unwrap();
         assert!(serialized.contains("rule:python/style"));
     }
+
+    #[test]
+    fn intent_validate_checks_args_by_kind() {
+        let rules = Intent::new(
+            "rules:claude".to_string(),
+            json!({"rules": {"550e8400-e29b-41d4-a716-446655440000": {"id": "style", "checksum": "sha256:ab"}}}),
+        );
+        assert!(rules.validate().is_ok());
+        let args: RulesArgs = rules.typed_args().unwrap();
+        let block = &args.rules.unwrap()["550e8400-e29b-41d4-a716-446655440000"];
+        assert_eq!(block.checksum, "sha256:ab");
+        assert_eq!(block.file, None);
+
+        let mcp = Intent::new("mcp:cursor".to_string(), json!({"servers": "github"}));
+        assert!(matches!(
+            mcp.validate(),
+            Err(Error::MalformedIntent { uuid, .. }) if uuid == mcp.uuid
+        ));
+
+        // Other intents are not checked
+        assert!(
+            Intent::new("rule:test".to_string(), json!(3))
+                .validate()
+                .is_ok()
+        );
+    }
 }
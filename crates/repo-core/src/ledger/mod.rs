@@ -4,17 +4,20 @@
 //! projections. It provides persistence via TOML serialization and
 //! query methods for finding intents and projections.
 
+mod args;
 mod intent;
 mod projection;
 
+pub use args::{McpArgs, RuleBlock, RulesArgs, ToolArgs};
 pub use intent::Intent;
-pub use projection::{Projection, ProjectionKind};
+pub use projection::{Projection, ProjectionBackend, ProjectionKind};
 
 use crate::Result;
 use fs2::FileExt;
 use serde::{Deserialize, Serialize};
-use std::fs::{self, File, OpenOptions};
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// The ledger tracks all active intents and their projections
@@ -46,7 +49,9 @@ impl Ledger {
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be read, locked, or parsed.
+    /// Returns an error if the file cannot be read, locked, or parsed, and
+    /// [`Error::MalformedIntent`](crate::Error::MalformedIntent) if an
+    /// intent's arguments do not match its kind.
     pub fn load(path: &Path) -> Result<Self> {
         let file = File::open(path)?;
         file.lock_shared()?;
@@ -56,6 +61,7 @@ impl Ledger {
         use std::io::Read;
         (&file).read_to_string(&mut content)?;
         let ledger: Ledger = toml::from_str(&content)?;
+        ledger.validate()?;
 
         // Lock released when file is dropped
         Ok(ledger)
@@ -86,12 +92,12 @@ impl Ledger {
         // Acquire exclusive lock (blocks if another process holds lock)
         lock_file.lock_exclusive()?;
 
-        // Write to temporary file first
-        let temp_path = path.with_extension("toml.tmp");
-        fs::write(&temp_path, &content)?;
-
-        // Atomically rename to target
-        fs::rename(&temp_path, path)?;
+        // Write to a temp file, fsync it and rename it over the target
+        repo_fs::io::write_atomic_unlocked(
+            path,
+            content.as_bytes(),
+            &repo_fs::io::RobustnessConfig::current(),
+        )?;
 
         // Lock released when lock_file is dropped
         Ok(())
@@ -111,7 +117,8 @@ impl Ledger {
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be locked, read, parsed, or written.
+    /// Returns an error if the file cannot be locked, read, parsed, or written,
+    /// or holds a malformed intent.
     pub fn modify<F, T>(path: &Path, f: F) -> Result<T>
     where
         F: FnOnce(&mut Ledger) -> T,
@@ -140,6 +147,7 @@ impl Ledger {
         } else {
             toml::from_str(&content)?
         };
+        ledger.validate()?;
 
         // Apply caller's mutation
         let result = f(&mut ledger);
@@ -153,10 +161,48 @@ impl Ledger {
         lock_file.write_all(serialized.as_bytes())?;
         lock_file.sync_all()?;
 
+        if repo_fs::io::RobustnessConfig::current().verify_writes {
+            Self::sync_and_verify(path, serialized.as_bytes(), || {
+                lock_file.set_len(0)?;
+                lock_file.seek(std::io::SeekFrom::Start(0))?;
+                lock_file.write_all(serialized.as_bytes())
+            })?;
+        }
+
         // Lock released when lock_file is dropped
         Ok(result)
     }
 
+    /// Fsync `path` and its directory, then read it back against `expected`,
+    /// rewriting once via `rewrite` on mismatch.
+    fn sync_and_verify(
+        path: &Path,
+        expected: &[u8],
+        mut rewrite: impl FnMut() -> std::io::Result<()>,
+    ) -> Result<()> {
+        let sync = |path: &Path| -> repo_fs::Result<()> {
+            File::open(path)
+                .and_then(|f| f.sync_all())
+                .map_err(|e| repo_fs::Error::io(path, e))?;
+            match path.parent() {
+                Some(parent) => repo_fs::io::sync_dir(parent),
+                None => Ok(()),
+            }
+        };
+
+        sync(path)?;
+        repo_fs::io::verify_file(path, expected, || {
+            rewrite().map_err(|e| repo_fs::Error::io(path, e))?;
+            sync(path)
+        })?;
+        Ok(())
+    }
+
+    /// Check every intent's arguments against the schema for its kind
+    fn validate(&self) -> Result<()> {
+        self.intents.iter().try_for_each(Intent::validate)
+    }
+
     /// Get all intents in the ledger
     pub fn intents(&self) -> &[Intent] {
         &self.intents
@@ -195,15 +241,40 @@ impl Ledger {
     /// Returns tuples of (intent, projection) for all projections
     /// that write to the specified file.
     pub fn projections_for_file(&self, file: &Path) -> Vec<(&Intent, &Projection)> {
-        let mut results = Vec::new();
-        for intent in &self.intents {
-            for projection in intent.projections() {
-                if projection.file == file {
-                    results.push((intent, projection));
-                }
-            }
-        }
-        results
+        self.projections_where(|projection| projection.file == file)
+    }
+
+    /// Find all projections that target a specific tool
+    ///
+    /// Returns tuples of (intent, projection), in ledger order.
+    pub fn projections_for_tool(&self, tool: &str) -> Vec<(&Intent, &Projection)> {
+        self.projections_where(|projection| projection.tool == tool)
+    }
+
+    /// Find all projections of one kind
+    pub fn projections_by_kind(&self, backend: ProjectionBackend) -> Vec<(&Intent, &Projection)> {
+        self.projections_where(|projection| projection.kind.backend() == backend)
+    }
+
+    /// All files managed by the ledger, relative to the config root
+    pub fn files(&self) -> BTreeSet<PathBuf> {
+        self.intents
+            .iter()
+            .flat_map(|intent| intent.projections())
+            .map(|projection| projection.file.clone())
+            .collect()
+    }
+
+    /// Find all projections matching `predicate`, with their intents
+    fn projections_where(
+        &self,
+        predicate: impl Fn(&Projection) -> bool,
+    ) -> Vec<(&Intent, &Projection)> {
+        self.intents
+            .iter()
+            .flat_map(|intent| intent.projections().iter().map(move |p| (intent, p)))
+            .filter(|(_, projection)| predicate(projection))
+            .collect()
     }
 }
 
@@ -321,4 +392,115 @@ mod tests {
         assert_eq!(deserialized.intents.len(), 1);
         assert_eq!(deserialized.intents[0].id, "rule:test");
     }
+
+    #[test]
+    fn ledger_load_detects_malformed_intent() {
+        use crate::Error;
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("ledger.toml");
+
+        let mut ledger = Ledger::new();
+        ledger.add_intent(Intent::new("tool:cursor".to_string(), json!({})));
+        ledger.add_intent(Intent::new(
+            "mcp:cursor".to_string(),
+            json!({"servers": ["github"]}),
+        ));
+        ledger.save(&path).unwrap();
+
+        let loaded = Ledger::load(&path).unwrap();
+        assert_eq!(
+            loaded.intents()[0].typed_args::<ToolArgs>().unwrap(),
+            ToolArgs {}
+        );
+        assert_eq!(
+            loaded.intents()[1].typed_args::<McpArgs>().unwrap().servers,
+            vec!["github"]
+        );
+
+        let malformed = Intent::new("tool:claude".to_string(), json!("corrupted"));
+        let uuid = malformed.uuid;
+        ledger.add_intent(malformed);
+        ledger.save(&path).unwrap();
+
+        match Ledger::load(&path) {
+            Err(Error::MalformedIntent { uuid: got, reason }) => {
+                assert_eq!(got, uuid);
+                assert!(reason.contains("tool:claude"), "{}", reason);
+            }
+            other => panic!("expected a malformed intent error, got {:?}", other),
+        }
+        assert!(matches!(
+            Ledger::modify(&path, |_| ()),
+            Err(Error::MalformedIntent { .. })
+        ));
+    }
+
+    #[test]
+    fn ledger_queries_projections_by_tool_and_kind() {
+        let mut cursor = Intent::new("tool:cursor".to_string(), json!({}));
+        cursor.add_projection(Projection::file_managed(
+            "cursor".to_string(),
+            PathBuf::from(".cursorrules"),
+            "sha256:a".to_string(),
+        ));
+        cursor.add_projection(Projection::json_key(
+            "cursor".to_string(),
+            PathBuf::from(".vscode/settings.json"),
+            "editor.fontSize".to_string(),
+            json!(14),
+        ));
+        let mut claude = Intent::new("rules:claude".to_string(), json!({}));
+        claude.add_projection(Projection::text_block(
+            "claude".to_string(),
+            PathBuf::from("CLAUDE.md"),
+            Uuid::new_v4(),
+            "sha256:b".to_string(),
+        ));
+        claude.add_projection(Projection::file_managed(
+            "claude".to_string(),
+            PathBuf::from(".cursorrules"),
+            "sha256:c".to_string(),
+        ));
+        let mut ledger = Ledger::new();
+        ledger.add_intent(cursor);
+        ledger.add_intent(claude);
+
+        let for_cursor = ledger.projections_for_tool("cursor");
+        assert_eq!(for_cursor.len(), 2);
+        assert!(
+            for_cursor
+                .iter()
+                .all(|(intent, p)| intent.id == "tool:cursor" && p.tool == "cursor")
+        );
+        let files: Vec<_> = ledger
+            .projections_for_tool("claude")
+            .iter()
+            .map(|(_, p)| p.file.clone())
+            .collect();
+        assert_eq!(
+            files,
+            vec![PathBuf::from("CLAUDE.md"), PathBuf::from(".cursorrules")]
+        );
+        assert!(ledger.projections_for_tool("vscode").is_empty());
+
+        let managed = ledger.projections_by_kind(ProjectionBackend::FileManaged);
+        assert_eq!(managed.len(), 2);
+        assert_eq!(
+            ledger.projections_by_kind(ProjectionBackend::JsonKey)[0]
+                .1
+                .tool,
+            "cursor"
+        );
+
+        assert_eq!(
+            ledger.files(),
+            BTreeSet::from([
+                PathBuf::from(".cursorrules"),
+                PathBuf::from(".vscode/settings.json"),
+                PathBuf::from("CLAUDE.md"),
+            ])
+        );
+    }
 }
@@ -60,6 +60,25 @@ pub enum ProjectionKind {
     },
 }
 
+/// The backend of a [`ProjectionKind`], without its data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProjectionBackend {
+    TextBlock,
+    JsonKey,
+    FileManaged,
+}
+
+impl ProjectionKind {
+    /// The backend of this projection
+    pub fn backend(&self) -> ProjectionBackend {
+        match self {
+            Self::TextBlock { .. } => ProjectionBackend::TextBlock,
+            Self::JsonKey { .. } => ProjectionBackend::JsonKey,
+            Self::FileManaged { .. } => ProjectionBackend::FileManaged,
+        }
+    }
+}
+
 impl Projection {
     /// Create a new text block projection
     ///
@@ -4,9 +4,18 @@
 //! implementing:
 //!
 //! - **Mode abstraction**: Unified interface for Standard and Worktree repository layouts
+//! - **Context detection**: Root, mode, and worktree resolution shared by all entry points
 //! - **Ledger system**: Intent and projection tracking for configuration management
+//! - **Mode migration**: Converting a repository between Standard and Worktree layouts
 //! - **Configuration resolution**: Hierarchical merge of workspace, repository, and user configs
 //! - **SyncEngine**: Check, sync, and fix operations for tool configurations
+//! - **Tool collisions**: Which source wins when several supply the same tool slug
+//! - **Repository**: Programmatic facade over discovery, config and sync for embedding
+//! - **Adoption**: Splitting hand-written AI config files into registry rules
+//! - **History**: Append-only log of operations that changed the configuration
+//! - **Bundles**: Exporting a repository's configuration and rules for import elsewhere
+//! - **Templates**: Named bundles that `repo init` starts a repository from
+//! - **Cancellation**: Stopping work, and killing its subprocesses, once its caller gives up
 //!
 //! # Architecture
 //!
@@ -33,31 +42,60 @@
 //! }
 //! ```
 
+pub mod adopt;
 pub mod backend;
 pub mod backup;
+pub mod bundle;
+pub mod cancel;
+pub mod collisions;
 pub mod config;
+pub mod context;
 pub mod error;
+pub mod extensions;
 pub mod governance;
+pub mod history;
 pub mod hooks;
 pub mod ledger;
+pub mod migrate;
 pub mod mode;
+pub mod presets;
 pub mod projection;
+pub mod repository;
 pub mod rules;
 pub mod sync;
+pub mod template;
 
-pub use backend::{BranchInfo, ModeBackend, StandardBackend, WorktreeBackend};
+pub use backend::{
+    BranchInfo, BranchListOptions, BranchPruneOptions, MergedBranch, ModeBackend, StandardBackend,
+    WorktreeBackend,
+};
 pub use backup::{BackupManager, BackupMetadata, ToolBackup};
-pub use config::{ConfigResolver, Manifest, ResolvedConfig, RuntimeContext, json_to_toml_value};
+pub use bundle::{BundledRule, ConfigBundle, ImportMode, ImportSummary};
+pub use cancel::CancelToken;
+pub use collisions::{ToolNameCollision, ToolOrigin, find_tool_collisions};
+pub use config::{
+    ConfigDifference, ConfigLayer, ConfigResolver, Manifest, ManifestEditor, McpSection,
+    ResolvedConfig, RuntimeContext, format_config, json_to_toml_value,
+};
+pub use context::{ContextKind, RepoContextInfo};
 pub use error::{Error, Result};
-pub use governance::{ConfigDrift, DriftType, LintWarning, WarnLevel, validate_rule_id};
-pub use hooks::{HookConfig, HookContext, HookEvent, run_hooks};
-pub use ledger::{Intent, Ledger, Projection, ProjectionKind};
+pub use extensions::{ExtensionInstall, PresetApply};
+pub use governance::{
+    ConfigDrift, DriftType, LintWarning, RuleProblem, WarnLevel, validate_rule_id, validate_rules,
+};
+pub use history::{History, HistoryEntry, Operation};
+pub use hooks::{HookConfig, HookContext, HookEvent, HookRunner, run_hooks};
+pub use ledger::{Intent, Ledger, Projection, ProjectionBackend, ProjectionKind};
+pub use migrate::{MigrationStep, ModeMigration};
 pub use mode::{Mode, detect_mode};
 pub use projection::{ProjectionWriter, compute_checksum};
+pub use repository::Repository;
 pub use rules::{Rule, RuleRegistry};
 pub use sync::{
-    CheckReport, CheckStatus, DriftItem, RuleFile, RuleSyncer, SyncEngine, SyncOptions, SyncReport,
+    BlockChanges, ChangeSource, CheckReport, CheckStatus, DriftItem, FileChange, FilePreview,
+    McpSyncer, RuleFile, RuleSyncer, SyncEngine, SyncOptions, SyncReport,
 };
+pub use template::{Template, list_templates, load_template, templates_dir};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,396 @@
+//! Converting a repository between standard and worktrees mode
+//!
+//! Standard to worktrees moves the checkout into `main/`, renames `.git/` to
+//! a bare `.gt/` database with `main/` as its worktree, and copies
+//! `.repository/` back up to the container, where the worktrees backend
+//! reads shared configuration. Worktrees to standard reverses this and is
+//! only possible while `main/` is the only worktree; the container's
+//! `.repository/` replaces the checkout's copy.
+//!
+//! Either way the ledger's projection paths are rebased onto the new
+//! location of the files they track, so `check` stays healthy, and
+//! `core.mode` is rewritten in config.toml.
+//!
+//! [`ModeMigration::plan`] works out the steps without changing anything,
+//! which is what a dry run prints; [`ModeMigration::apply`] performs them.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use repo_fs::{ConfigStore, NormalizedPath};
+use repo_git::conversion;
+
+use crate::config::ManifestEditor;
+use crate::ledger::Ledger;
+use crate::mode::{Mode, detect_mode};
+use crate::{Error, Result};
+
+const GIT_DIR: &str = ".git";
+const CONTAINER_GIT_DIR: &str = ".gt";
+const MAIN_WORKTREE: &str = "main";
+const REPOSITORY_DIR: &str = ".repository";
+
+/// One step of a mode migration, with paths relative to the repository root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationStep {
+    /// Rename a file or directory
+    Move { from: PathBuf, to: PathBuf },
+    /// Copy a directory tree
+    Copy { from: PathBuf, to: PathBuf },
+    /// Delete a file or directory tree
+    Remove { path: PathBuf },
+    /// Make `git_dir` a bare database with `worktree` as its main worktree
+    AttachWorktree { git_dir: PathBuf, worktree: PathBuf },
+    /// Make `git_dir` the classic database of the checkout in `worktree`
+    DetachWorktree { git_dir: PathBuf, worktree: PathBuf },
+    /// Rebase ledger projection paths under `from` onto `to`
+    RebaseLedger {
+        from: PathBuf,
+        to: PathBuf,
+        projections: usize,
+    },
+    /// Set `core.mode` in config.toml
+    SetMode(Mode),
+}
+
+impl fmt::Display for MigrationStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |path: &Path| {
+            if path.as_os_str().is_empty() {
+                ".".to_string()
+            } else {
+                path.display().to_string()
+            }
+        };
+        match self {
+            Self::Move { from, to } => write!(f, "move {} -> {}", show(from), show(to)),
+            Self::Copy { from, to } => write!(f, "copy {} -> {}", show(from), show(to)),
+            Self::Remove { path } => write!(f, "remove {}", show(path)),
+            Self::AttachWorktree { git_dir, worktree } => write!(
+                f,
+                "register {}/ as the main worktree of {}/",
+                show(worktree),
+                show(git_dir)
+            ),
+            Self::DetachWorktree { git_dir, worktree } => write!(
+                f,
+                "make {}/ the git database of {}/",
+                show(git_dir),
+                show(worktree)
+            ),
+            Self::RebaseLedger {
+                from,
+                to,
+                projections,
+            } => write!(
+                f,
+                "rebase {} ledger projection path(s) from {} to {}",
+                projections,
+                show(from),
+                show(to)
+            ),
+            Self::SetMode(mode) => write!(f, "set core.mode = \"{}\"", mode),
+        }
+    }
+}
+
+/// The steps converting the repository at `root` from one mode to another
+#[derive(Debug, Clone)]
+pub struct ModeMigration {
+    /// Repository root: the checkout in standard mode, the container in
+    /// worktrees mode
+    pub root: NormalizedPath,
+    /// Mode the repository is in
+    pub from: Mode,
+    /// Mode to convert to
+    pub to: Mode,
+    /// Steps in the order they are applied; empty if already in `to`
+    pub steps: Vec<MigrationStep>,
+}
+
+impl ModeMigration {
+    /// Plan converting the repository at `root` to `to`
+    ///
+    /// Nothing is changed on disk. Fails with
+    /// [`Error::ModeMigration`](crate::Error::ModeMigration) if the
+    /// repository cannot be converted as it is, e.g. because feature
+    /// worktrees exist or a file would be overwritten.
+    pub fn plan(root: &NormalizedPath, to: Mode) -> Result<Self> {
+        let from = detect_mode(root)?;
+        let steps = match (from, to) {
+            (Mode::Standard, Mode::Worktrees) => plan_to_worktrees(root)?,
+            (Mode::Worktrees, Mode::Standard) => plan_to_standard(root)?,
+            _ => Vec::new(),
+        };
+        Ok(Self {
+            root: root.clone(),
+            from,
+            to,
+            steps,
+        })
+    }
+
+    /// Perform the planned steps
+    ///
+    /// Steps are not rolled back if one fails; the error names the step.
+    pub fn apply(&self) -> Result<()> {
+        for step in &self.steps {
+            self.apply_step(step).map_err(|e| Error::ModeMigration {
+                message: format!("failed to {}: {}", step, e),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn path(&self, relative: &Path) -> PathBuf {
+        self.root.to_native().join(relative)
+    }
+
+    fn apply_step(&self, step: &MigrationStep) -> Result<()> {
+        match step {
+            MigrationStep::Move { from, to } => {
+                let to = self.path(to);
+                if let Some(parent) = to.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::rename(self.path(from), to)?;
+            }
+            MigrationStep::Copy { from, to } => copy_tree(&self.path(from), &self.path(to))?,
+            MigrationStep::Remove { path } => {
+                let path = self.path(path);
+                if path.is_dir() {
+                    fs::remove_dir_all(path)?;
+                } else {
+                    fs::remove_file(path)?;
+                }
+            }
+            MigrationStep::AttachWorktree { git_dir, worktree } => {
+                conversion::attach_main_worktree(
+                    &NormalizedPath::new(self.path(git_dir)),
+                    &NormalizedPath::new(self.path(worktree)),
+                )?;
+            }
+            MigrationStep::DetachWorktree { git_dir, worktree } => {
+                conversion::detach_main_worktree(
+                    &NormalizedPath::new(self.path(git_dir)),
+                    &NormalizedPath::new(self.path(worktree)),
+                )?;
+            }
+            MigrationStep::RebaseLedger { from, to, .. } => {
+                Ledger::modify(&ledger_path(&self.root), |ledger| {
+                    rebase_ledger(ledger, from, to)
+                })?;
+            }
+            MigrationStep::SetMode(mode) => {
+                let path = self.root.join(REPOSITORY_DIR).join("config.toml");
+                let lock = ConfigStore::new().lock(&path)?;
+                let mut editor = ManifestEditor::parse(&lock.read()?)?;
+                editor.set_mode(&mode.to_string());
+                lock.write(&editor.to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn blocked(message: impl Into<String>) -> Error {
+    Error::ModeMigration {
+        message: message.into(),
+    }
+}
+
+fn ledger_path(root: &NormalizedPath) -> PathBuf {
+    root.join(REPOSITORY_DIR).join("ledger.toml").to_native()
+}
+
+/// Names of the entries in `dir`, sorted
+fn entries(dir: &Path) -> Result<Vec<String>> {
+    let mut names: Vec<String> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// `file` rebased from under `from` onto `to`
+///
+/// Files in `.repository/` are not rebased: a copy of it stays at the root
+/// in both modes.
+fn rebased(file: &Path, from: &Path, to: &Path) -> Option<PathBuf> {
+    if file.starts_with(REPOSITORY_DIR) {
+        return None;
+    }
+    file.strip_prefix(from).ok().map(|rest| to.join(rest))
+}
+
+fn rebase_ledger(ledger: &mut Ledger, from: &Path, to: &Path) -> usize {
+    let uuids: Vec<_> = ledger.intents().iter().map(|i| i.uuid).collect();
+    let mut count = 0;
+    for uuid in uuids {
+        let Some(intent) = ledger.get_intent_mut(uuid) else {
+            continue;
+        };
+        for projection in intent.projections_mut() {
+            if let Some(file) = rebased(&projection.file, from, to) {
+                projection.file = file;
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+/// Ledger rebase step, if the repository at `root` has a ledger
+fn rebase_ledger_step(
+    root: &NormalizedPath,
+    from: &str,
+    to: &str,
+) -> Result<Option<MigrationStep>> {
+    let path = ledger_path(root);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let (from, to) = (PathBuf::from(from), PathBuf::from(to));
+    let projections = rebase_ledger(&mut Ledger::load(&path)?, &from, &to);
+    Ok(Some(MigrationStep::RebaseLedger {
+        from,
+        to,
+        projections,
+    }))
+}
+
+fn plan_to_worktrees(root: &NormalizedPath) -> Result<Vec<MigrationStep>> {
+    let git_dir = root.join(GIT_DIR);
+    if !git_dir.is_dir() {
+        return Err(blocked(format!(
+            "{} is not a directory; only a primary checkout can be converted",
+            git_dir
+        )));
+    }
+    for name in [CONTAINER_GIT_DIR, MAIN_WORKTREE] {
+        if root.join(name).exists() {
+            return Err(blocked(format!("{} already exists", root.join(name))));
+        }
+    }
+    let worktrees = conversion::linked_worktrees(&git_dir)?;
+    if !worktrees.is_empty() {
+        return Err(blocked(format!(
+            "worktrees still exist: {}. Remove them first.",
+            worktrees.join(", ")
+        )));
+    }
+    conversion::ensure_convertible(root)?;
+
+    let main = Path::new(MAIN_WORKTREE);
+    let mut steps = vec![MigrationStep::Move {
+        from: GIT_DIR.into(),
+        to: CONTAINER_GIT_DIR.into(),
+    }];
+    for name in entries(&root.to_native())? {
+        if name != GIT_DIR {
+            steps.push(MigrationStep::Move {
+                from: name.as_str().into(),
+                to: main.join(&name),
+            });
+        }
+    }
+    if root.join(REPOSITORY_DIR).is_dir() {
+        steps.push(MigrationStep::Copy {
+            from: main.join(REPOSITORY_DIR),
+            to: REPOSITORY_DIR.into(),
+        });
+    }
+    steps.push(MigrationStep::AttachWorktree {
+        git_dir: CONTAINER_GIT_DIR.into(),
+        worktree: main.into(),
+    });
+    steps.extend(rebase_ledger_step(root, "", MAIN_WORKTREE)?);
+    steps.push(MigrationStep::SetMode(Mode::Worktrees));
+    Ok(steps)
+}
+
+fn plan_to_standard(root: &NormalizedPath) -> Result<Vec<MigrationStep>> {
+    let git_dir = root.join(CONTAINER_GIT_DIR);
+    let main_dir = root.join(MAIN_WORKTREE);
+    if !git_dir.is_dir() || !main_dir.join(GIT_DIR).is_file() {
+        return Err(blocked(format!(
+            "{} is not a worktree container with a {}/ worktree",
+            root, MAIN_WORKTREE
+        )));
+    }
+    if root.join(GIT_DIR).exists() {
+        return Err(blocked(format!("{} already exists", root.join(GIT_DIR))));
+    }
+    let features: Vec<String> = conversion::linked_worktrees(&git_dir)?
+        .into_iter()
+        .filter(|name| name != MAIN_WORKTREE)
+        .collect();
+    if !features.is_empty() {
+        return Err(blocked(format!(
+            "feature worktrees still exist: {}. Remove them first.",
+            features.join(", ")
+        )));
+    }
+    conversion::ensure_convertible(&main_dir)?;
+
+    let checkout = entries(&main_dir.to_native())?;
+    let clashes: Vec<&String> = checkout
+        .iter()
+        .filter(|name| *name != GIT_DIR && *name != REPOSITORY_DIR)
+        .filter(|name| root.join(name.as_str()).exists())
+        .collect();
+    if !clashes.is_empty() {
+        return Err(blocked(format!(
+            "{} would be overwritten by the {}/ checkout",
+            clashes
+                .iter()
+                .map(|name| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            MAIN_WORKTREE
+        )));
+    }
+
+    let main = Path::new(MAIN_WORKTREE);
+    let mut steps = vec![MigrationStep::DetachWorktree {
+        git_dir: CONTAINER_GIT_DIR.into(),
+        worktree: main.into(),
+    }];
+    for name in checkout {
+        match name.as_str() {
+            GIT_DIR => {}
+            REPOSITORY_DIR => steps.push(MigrationStep::Remove {
+                path: main.join(&name),
+            }),
+            _ => steps.push(MigrationStep::Move {
+                from: main.join(&name),
+                to: name.as_str().into(),
+            }),
+        }
+    }
+    steps.push(MigrationStep::Remove { path: main.into() });
+    steps.push(MigrationStep::Move {
+        from: CONTAINER_GIT_DIR.into(),
+        to: GIT_DIR.into(),
+    });
+    steps.extend(rebase_ledger_step(root, MAIN_WORKTREE, "")?);
+    steps.push(MigrationStep::SetMode(Mode::Standard));
+    Ok(steps)
+}
+
+/// Recursively copy the directory `from` to `to`
+pub(crate) fn copy_tree(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_tree(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
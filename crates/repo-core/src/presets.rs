@@ -0,0 +1,57 @@
+//! Running preset providers
+//!
+//! The provider lookup and context construction shared by `repo preset check`
+//! and the extension install flow, which applies the presets it adds.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use repo_fs::{LayoutMode, NormalizedPath, WorkspaceLayout};
+use repo_meta::Registry;
+use repo_presets::{
+    Context, NodeProvider, PoetryProvider, PresetProvider, RustProvider, UvProvider, VenvProvider,
+};
+
+/// Provider for `preset`, chosen by the registry and the preset's config
+pub fn preset_provider(
+    preset: &str,
+    config: &serde_json::Value,
+) -> Option<Box<dyn PresetProvider>> {
+    let registry = Registry::with_builtins();
+    let requested = config.get("provider").and_then(|v| v.as_str());
+    let provider: Box<dyn PresetProvider> = match registry
+        .resolve_provider(preset, requested)
+        .map(String::as_str)
+    {
+        Some("uv") => Box::new(UvProvider::new()),
+        Some("venv") => Box::new(VenvProvider::new()),
+        Some("poetry") => Box::new(PoetryProvider::new()),
+        Some("node") => Box::new(NodeProvider::new()),
+        Some("rust") => Box::new(RustProvider::new()),
+        _ => return None,
+    };
+    Some(provider)
+}
+
+/// Provider context for a preset configured in the repository at `path`
+pub fn preset_context(path: &Path, config: &serde_json::Value) -> Context {
+    let layout = WorkspaceLayout::detect(path).unwrap_or_else(|_| WorkspaceLayout {
+        root: NormalizedPath::new(path),
+        active_context: NormalizedPath::new(path),
+        mode: LayoutMode::Classic,
+    });
+    let preset_config: HashMap<String, toml::Value> = config
+        .as_object()
+        .map(|obj| {
+            obj.iter()
+                .filter_map(|(k, v)| toml::Value::try_from(v).ok().map(|v| (k.clone(), v)))
+                .collect()
+        })
+        .unwrap_or_default();
+    // Run from a sub-package so providers pick its environment over the root's
+    let mut context = Context::new(layout, preset_config);
+    if let Ok(cwd) = std::env::current_dir() {
+        context = context.with_working_dir(NormalizedPath::new(cwd));
+    }
+    context
+}
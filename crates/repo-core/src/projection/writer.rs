@@ -5,7 +5,9 @@
 use crate::ledger::{Projection, ProjectionKind};
 use crate::{Error, Result};
 use repo_fs::NormalizedPath;
+use repo_fs::io::ReadOptions;
 use std::fs;
+use std::path::Path;
 use uuid::Uuid;
 
 /// Write content to a file safely (with symlink protection)
@@ -13,6 +15,18 @@ fn safe_write(path: &NormalizedPath, content: &str) -> Result<()> {
     repo_fs::io::write_text(path, content).map_err(Error::Fs)
 }
 
+/// Read a file that managed blocks are written into
+///
+/// Invalid UTF-8, e.g. from a tool that wrote Latin-1, is replaced rather
+/// than failing the sync, so writing the blocks back repairs the file.
+fn read_lossy(path: &NormalizedPath) -> Result<String> {
+    let file = repo_fs::io::read_text_with(path, ReadOptions { lossy: true })?;
+    if let Some(offset) = file.invalid_utf8_at {
+        tracing::warn!("Replacing invalid UTF-8 in {} from byte {}", path, offset);
+    }
+    Ok(file.text)
+}
+
 /// Writes projections to filesystem
 pub struct ProjectionWriter {
     root: NormalizedPath,
@@ -48,6 +62,43 @@ impl ProjectionWriter {
         }
     }
 
+    /// Remove the managed blocks `markers` from `file`, deleting the file
+    /// if nothing else is left in it
+    ///
+    /// Used when a tool's output moves elsewhere, so content added by hand
+    /// around the blocks is kept.
+    pub fn remove_blocks(&self, file: &Path, markers: &[String]) -> Result<String> {
+        let path = self.root.join(file.to_string_lossy().as_ref());
+        if !path.exists() {
+            return Ok(format!("File already missing: {}", path));
+        }
+
+        let existing = read_lossy(&path)?;
+        let mut content = existing;
+        let mut removed_any = false;
+        for marker in markers {
+            let (remaining, removed) = repo_blocks::remove_block_reported(&content, marker);
+            content = remaining;
+            removed_any |= removed;
+        }
+
+        if content.trim().is_empty() {
+            return self.remove_managed_file(&path);
+        }
+        if !removed_any {
+            return Ok(format!("No managed blocks left in {}", path));
+        }
+        if self.dry_run {
+            return Ok(format!(
+                "[dry-run] Would remove managed blocks from {}",
+                path
+            ));
+        }
+
+        safe_write(&path, &content)?;
+        Ok(format!("Removed managed blocks from {}", path))
+    }
+
     fn write_managed_file(&self, path: &NormalizedPath, content: &str) -> Result<String> {
         if self.dry_run {
             return Ok(format!("[dry-run] Would create {}", path));
@@ -64,7 +115,7 @@ impl ProjectionWriter {
         content: &str,
     ) -> Result<String> {
         let existing = if path.exists() {
-            fs::read_to_string(path.as_ref())?
+            read_lossy(path)?
         } else {
             String::new()
         };
@@ -121,7 +172,7 @@ impl ProjectionWriter {
 
     fn write_json_key(&self, path: &NormalizedPath, key_path: &str, value: &str) -> Result<String> {
         let existing = if path.exists() {
-            fs::read_to_string(path.as_ref())?
+            repo_fs::io::read_text(path)?
         } else {
             "{}".to_string()
         };
@@ -159,7 +210,7 @@ impl ProjectionWriter {
             return Ok(format!("File already missing: {}", path));
         }
 
-        let existing = fs::read_to_string(path.as_ref())?;
+        let existing = read_lossy(path)?;
         let marker_start = format!("<!-- repo:block:{} -->", marker);
         let marker_end = format!("<!-- /repo:block:{} -->", marker);
 
@@ -208,7 +259,7 @@ impl ProjectionWriter {
             return Ok(format!("File already missing: {}", path));
         }
 
-        let existing = fs::read_to_string(path.as_ref())?;
+        let existing = repo_fs::io::read_text(path)?;
         let mut json: serde_json::Value = serde_json::from_str(&existing)?;
 
         remove_json_path(&mut json, key_path);
@@ -0,0 +1,478 @@
+//! Programmatic facade over a managed repository
+//!
+//! [`Repository`] bundles the orchestration every frontend needs: finding
+//! the root and mode, loading config.toml, and building the sync engine,
+//! mode backend and git layout. The MCP server drives it directly, and Rust
+//! tools can embed it instead of shelling out to the CLI.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use repo_core::{Repository, SyncOptions};
+//!
+//! # fn main() -> repo_core::Result<()> {
+//! let repo = Repository::discover(std::path::Path::new("."))?;
+//! repo.add_tool("cursor")?;
+//! let report = repo.sync(SyncOptions::default())?;
+//! assert!(report.success);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+use repo_fs::{ConfigStore, NormalizedPath};
+use repo_git::{ClassicLayout, ContainerLayout, InRepoWorktreesLayout, LayoutProvider};
+use repo_meta::schema::{FrontMatterMode, compose_rule_markdown, parse_rule_markdown};
+
+use crate::backend::{
+    BranchInfo, BranchPruneOptions, MergedBranch, ModeBackend, StandardBackend, WorktreeBackend,
+};
+use crate::config::{Manifest, ManifestEditor};
+use crate::context::detect;
+use crate::error::{Error, Result};
+use crate::extensions::{self, ExtensionInstall};
+use crate::governance::validate_rule_id;
+use crate::mode::{Mode, detect_mode};
+use crate::rules::RuleRegistry;
+use crate::sync::{CheckReport, SyncEngine, SyncOptions, SyncReport};
+
+/// A repository managed by repository-manager
+#[derive(Debug, Clone)]
+pub struct Repository {
+    root: NormalizedPath,
+    mode: Mode,
+    container: Option<NormalizedPath>,
+}
+
+impl Repository {
+    /// Find the repository containing `path`
+    ///
+    /// Walks up from `path` to the nearest `.repository/`, resolving a
+    /// worktree checkout to its container, as the CLI does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotARepository`] if no `.repository/` is found and
+    /// [`Error::ConfigNotFound`] if it has no config.toml.
+    pub fn discover(path: &Path) -> Result<Self> {
+        let info = detect(path);
+        let root = info.root.ok_or_else(|| Error::NotARepository {
+            path: path.to_path_buf(),
+        })?;
+        if info.config_path.is_none() {
+            return Err(Error::ConfigNotFound {
+                path: root.join(".repository/config.toml").to_native(),
+            });
+        }
+        Ok(Self {
+            mode: info.mode.unwrap_or_default(),
+            container: info.container,
+            root,
+        })
+    }
+
+    /// Open the repository rooted at `root` without searching upward
+    ///
+    /// The mode is detected from the git markers in or above `root`, then
+    /// config.toml. config.toml itself is only required by the operations
+    /// that read it.
+    pub fn open(root: impl AsRef<Path>) -> Result<Self> {
+        let root = NormalizedPath::new(root);
+        let mode = detect_mode(&root)?;
+        let container = match mode {
+            Mode::Standard => None,
+            Mode::Worktrees if root.join(".gt").exists() => Some(root.clone()),
+            Mode::Worktrees => root
+                .parent()
+                .filter(|parent| parent.join(".gt").exists())
+                .or_else(|| detect(root.as_ref()).container),
+        };
+        Ok(Self {
+            root,
+            mode,
+            container,
+        })
+    }
+
+    /// Repository root, where `.repository/` lives
+    pub fn root(&self) -> &NormalizedPath {
+        &self.root
+    }
+
+    /// Repository mode
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Worktrees container, in worktrees mode
+    pub fn container(&self) -> Option<&NormalizedPath> {
+        self.container.as_ref()
+    }
+
+    /// Path to `.repository/config.toml`
+    pub fn config_path(&self) -> NormalizedPath {
+        self.root.join(".repository/config.toml")
+    }
+
+    /// Directory holding rule files
+    pub fn rules_dir(&self) -> NormalizedPath {
+        self.root.join(".repository/rules")
+    }
+
+    /// Load config.toml
+    pub fn manifest(&self) -> Result<Manifest> {
+        let path = self.config_path();
+        if !path.exists() {
+            return Err(Error::ConfigNotFound {
+                path: path.to_native(),
+            });
+        }
+        Manifest::parse(&ConfigStore::new().load_text(&path)?)
+    }
+
+    /// Apply `edit` to config.toml under an exclusive lock
+    ///
+    /// Only the keys `edit` touches change; comments and layout are kept.
+    /// The file is saved only if `edit` returns `true`, and the return value
+    /// is passed through.
+    pub(crate) fn update_manifest(
+        &self,
+        edit: impl FnOnce(&mut ManifestEditor) -> bool,
+    ) -> Result<bool> {
+        let path = self.config_path();
+        if !path.exists() {
+            return Err(Error::ConfigNotFound {
+                path: path.to_native(),
+            });
+        }
+        let lock = ConfigStore::new().lock(&path)?;
+        let mut editor = ManifestEditor::parse(&lock.read()?)?;
+        if !edit(&mut editor) {
+            return Ok(false);
+        }
+        lock.write(&editor.to_string())?;
+        Ok(true)
+    }
+
+    /// Sync engine for this repository
+    pub fn sync_engine(&self) -> Result<SyncEngine> {
+        SyncEngine::new(self.root.clone(), self.mode)
+    }
+
+    /// Branch backend for this repository's mode
+    pub fn backend(&self) -> Result<Box<dyn ModeBackend>> {
+        match self.mode {
+            Mode::Standard => Ok(Box::new(StandardBackend::new(self.root.clone())?)),
+            Mode::Worktrees => {
+                let container = self.container.clone().ok_or_else(|| {
+                    Error::Fs(repo_fs::Error::LayoutValidation {
+                        message: format!(
+                            "Not a worktree container: .gt not found at {}",
+                            self.root
+                        ),
+                    })
+                })?;
+                Ok(Box::new(WorktreeBackend::new(container)?))
+            }
+        }
+    }
+
+    /// Git layout for push, pull and merge
+    ///
+    /// A standard repository with a `.worktrees/` directory uses the in-repo
+    /// worktrees layout.
+    pub fn git_layout(&self) -> Result<Box<dyn LayoutProvider>> {
+        Ok(match self.mode {
+            Mode::Standard if self.root.join(".worktrees").is_dir() => Box::new(
+                InRepoWorktreesLayout::new(self.root.clone(), Default::default())?,
+            ),
+            Mode::Standard => Box::new(ClassicLayout::new(self.root.clone())?),
+            Mode::Worktrees => Box::new(ContainerLayout::new(
+                self.container.clone().unwrap_or_else(|| self.root.clone()),
+                Default::default(),
+            )?),
+        })
+    }
+
+    /// Check tool configurations for drift
+    pub fn check(&self) -> Result<CheckReport> {
+        self.sync_engine()?.check()
+    }
+
+    /// Regenerate tool configurations from config.toml and rules
+    pub fn sync(&self, options: SyncOptions) -> Result<SyncReport> {
+        self.sync_engine()?.sync_with_options(options)
+    }
+
+    /// Repair drifted or missing tool configurations
+    pub fn fix(&self, options: SyncOptions) -> Result<SyncReport> {
+        self.sync_engine()?.fix_with_options(options)
+    }
+
+    /// Enable a tool in config.toml
+    ///
+    /// Returns `false` if the tool was already enabled. Run [`sync`](Self::sync)
+    /// to write its configuration.
+    pub fn add_tool(&self, name: &str) -> Result<bool> {
+        self.update_manifest(|editor| editor.add_tool(name))
+    }
+
+    /// Disable a tool in config.toml
+    ///
+    /// Returns `false` if the tool was not enabled.
+    pub fn remove_tool(&self, name: &str) -> Result<bool> {
+        self.update_manifest(|editor| editor.remove_tool(name))
+    }
+
+    /// Configure a preset in config.toml
+    ///
+    /// Returns `false` if the preset was already configured.
+    pub fn add_preset(&self, name: &str, config: serde_json::Value) -> Result<bool> {
+        self.update_manifest(|editor| editor.add_preset(name, &config))
+    }
+
+    /// Remove a preset from config.toml
+    ///
+    /// Returns `false` if the preset was not configured.
+    pub fn remove_preset(&self, name: &str) -> Result<bool> {
+        self.update_manifest(|editor| editor.remove_preset(name))
+    }
+
+    /// Record `source` as the source of extension `name` in config.toml
+    ///
+    /// The rest of the extension's configuration, such as its ref, is kept.
+    pub fn set_extension_source(&self, name: &str, source: &str) -> Result<()> {
+        self.update_manifest(|editor| {
+            editor.set_key(&["extensions", name], "source", &source.into());
+            true
+        })?;
+        Ok(())
+    }
+
+    /// Install an extension from a git URL or a local directory containing
+    /// a `repo_extension.toml`
+    ///
+    /// Git sources are cloned at the `ref` configured for that source in
+    /// config.toml, if any. Presets the extension requires are checked
+    /// against the configured presets and installed runtimes, and missing
+    /// ones are added to config.toml and applied, unless `no_deps` is set,
+    /// in which case they are only reported in [`ExtensionInstall::required`].
+    /// Provider failures while applying them are reported in
+    /// [`ExtensionInstall::presets_applied`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ExtensionNotFound`] if `source` holds no extension
+    /// and [`Error::UnsatisfiedPresets`] if a required preset's version
+    /// constraint cannot be met. Nothing is installed in either case.
+    pub fn install_extension(&self, source: &str, no_deps: bool) -> Result<ExtensionInstall> {
+        extensions::install(self, source, no_deps)
+    }
+
+    /// Path of the rule file for `id`
+    pub fn rule_path(&self, id: &str) -> Result<NormalizedPath> {
+        validate_rule_id(id).map_err(|message| Error::InvalidRuleId {
+            id: id.to_string(),
+            message,
+        })?;
+        Ok(self.rules_dir().join(&format!("{}.md", id)))
+    }
+
+    /// Write the rule `id`, replacing any existing rule with that ID
+    ///
+    /// Front-matter in `content` is parsed strictly, so malformed metadata is
+    /// rejected rather than written. Tags are merged into it, or recorded in
+    /// a `tags:` line above content without front-matter. Returns the path
+    /// of the rule file.
+    pub fn add_rule(&self, id: &str, content: &str, tags: &[String]) -> Result<NormalizedPath> {
+        let path = self.rule_path(id)?;
+        let (text, _) = compose_rule_markdown(content, tags, FrontMatterMode::Strict)?;
+        repo_fs::io::write_text(&path, &text)?;
+        Ok(path)
+    }
+
+    /// Read the rule `id`, returning its tags and content
+    ///
+    /// The front-matter or `tags:` line written by [`add_rule`](Self::add_rule)
+    /// is split off the content. Front-matter is read leniently, so a rule
+    /// edited by hand still loads; what could not be read is logged.
+    pub fn read_rule(&self, id: &str) -> Result<(Vec<String>, String)> {
+        let text = fs::read_to_string(self.rule_path(id)?.as_ref())?;
+        let rule = parse_rule_markdown(&text, FrontMatterMode::Lenient)?;
+        for warning in &rule.warnings {
+            tracing::warn!(rule = id, "{}", warning);
+        }
+        Ok((rule.front_matter.tags, rule.body))
+    }
+
+    /// Load the rule registry, empty if `rules/registry.toml` does not exist
+    pub fn rule_registry(&self) -> Result<RuleRegistry> {
+        RuleRegistry::load_or_create(self.rules_dir().join("registry.toml").to_native())
+    }
+
+    /// Delete the rule `id`
+    ///
+    /// Returns `false` if no such rule exists.
+    pub fn remove_rule(&self, id: &str) -> Result<bool> {
+        let path = self.rule_path(id)?;
+        if !path.exists() {
+            return Ok(false);
+        }
+        fs::remove_file(path.as_ref())?;
+        Ok(true)
+    }
+
+    /// IDs of the rule files, sorted
+    pub fn list_rules(&self) -> Result<Vec<String>> {
+        let dir = self.rules_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut ids: Vec<String> = fs::read_dir(dir.as_ref())?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+            .filter_map(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// Branches, with their worktrees in worktrees mode
+    pub fn list_branches(&self) -> Result<Vec<BranchInfo>> {
+        self.backend()?.list_branches()
+    }
+
+    /// Create a branch (and its worktree in worktrees mode)
+    pub fn create_branch(&self, name: &str, base: Option<&str>) -> Result<()> {
+        self.backend()?.create_branch(name, base)
+    }
+
+    /// Delete a branch (and its worktree in worktrees mode)
+    pub fn delete_branch(&self, name: &str) -> Result<()> {
+        self.backend()?.delete_branch(name)
+    }
+
+    /// Switch to a branch, returning the directory to work in
+    ///
+    /// Checks the branch out in standard mode; in worktrees mode returns its
+    /// worktree, creating it if needed.
+    pub fn switch_branch(&self, name: &str) -> Result<NormalizedPath> {
+        self.backend()?.switch_branch(name)
+    }
+
+    /// Rename a branch (and move its worktree in worktrees mode)
+    pub fn rename_branch(&self, old_name: &str, new_name: &str) -> Result<()> {
+        self.backend()?.rename_branch(old_name, new_name)
+    }
+
+    /// Remove branches merged into the main branch (and their worktrees in
+    /// worktrees mode)
+    pub fn prune_branches(&self, options: BranchPruneOptions) -> Result<Vec<MergedBranch>> {
+        self.backend()?.prune_merged_branches(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::CheckStatus;
+    use tempfile::TempDir;
+
+    fn init(dir: &Path) {
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::create_dir_all(dir.join(".repository")).unwrap();
+        fs::write(
+            dir.join(".repository/config.toml"),
+            "tools = []\n\n[core]\nmode = \"standard\"\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_discover_walks_up() {
+        let temp = TempDir::new().unwrap();
+        init(temp.path());
+        let nested = temp.path().join("src/module");
+        fs::create_dir_all(&nested).unwrap();
+
+        let repo = Repository::discover(&nested).unwrap();
+        assert_eq!(repo.root(), &NormalizedPath::new(temp.path()));
+        assert_eq!(repo.mode(), Mode::Standard);
+        assert!(repo.container().is_none());
+    }
+
+    #[test]
+    fn test_discover_errors_outside_repository() {
+        let temp = TempDir::new().unwrap();
+        assert!(matches!(
+            Repository::discover(temp.path()),
+            Err(Error::NotARepository { .. })
+        ));
+
+        fs::create_dir_all(temp.path().join(".repository")).unwrap();
+        assert!(matches!(
+            Repository::discover(temp.path()),
+            Err(Error::ConfigNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_tools_and_rules_round_trip() {
+        let temp = TempDir::new().unwrap();
+        init(temp.path());
+        let repo = Repository::open(temp.path()).unwrap();
+
+        assert!(repo.add_tool("cursor").unwrap());
+        assert!(!repo.add_tool("cursor").unwrap());
+        assert_eq!(repo.manifest().unwrap().tools, vec!["cursor"]);
+
+        let path = repo
+            .add_rule("style", "Use 4 spaces", &["python".into()])
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(path.as_ref()).unwrap(),
+            "tags: python\n\nUse 4 spaces"
+        );
+        assert_eq!(repo.list_rules().unwrap(), vec!["style"]);
+        assert_eq!(
+            repo.read_rule("style").unwrap(),
+            (vec!["python".to_string()], "Use 4 spaces".to_string())
+        );
+        assert!(matches!(
+            repo.add_rule("../escape", "", &[]),
+            Err(Error::InvalidRuleId { .. })
+        ));
+
+        let report = repo.sync(SyncOptions::default()).unwrap();
+        assert!(report.success, "{:?}", report.errors);
+        assert_eq!(repo.check().unwrap().status, CheckStatus::Healthy);
+
+        assert!(repo.remove_rule("style").unwrap());
+        assert!(!repo.remove_rule("style").unwrap());
+        assert!(repo.remove_tool("cursor").unwrap());
+        assert!(!repo.remove_tool("cursor").unwrap());
+    }
+
+    #[test]
+    fn test_presets() {
+        let temp = TempDir::new().unwrap();
+        init(temp.path());
+        let repo = Repository::open(temp.path()).unwrap();
+
+        assert!(
+            repo.add_preset("env:python", serde_json::json!({}))
+                .unwrap()
+        );
+        assert!(
+            !repo
+                .add_preset("env:python", serde_json::json!({}))
+                .unwrap()
+        );
+        assert!(repo.manifest().unwrap().presets.contains_key("env:python"));
+        assert!(repo.remove_preset("env:python").unwrap());
+        assert!(!repo.remove_preset("env:python").unwrap());
+    }
+}
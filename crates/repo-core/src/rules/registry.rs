@@ -5,6 +5,7 @@
 
 use super::rule::Rule;
 use crate::Result;
+use repo_fs::NormalizedPath;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use uuid::Uuid;
@@ -67,19 +68,8 @@ impl RuleRegistry {
     /// Uses write-to-temp-then-rename to prevent partial writes from
     /// corrupting the registry on disk.
     pub fn save(&self) -> Result<()> {
-        // Ensure parent directory exists
-        if let Some(parent) = self.path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
         let content = toml::to_string_pretty(self)?;
-
-        // Atomic write: temp file then rename
-        let temp_path = self.path.with_extension("toml.tmp");
-        std::fs::write(&temp_path, &content)?;
-        std::fs::rename(&temp_path, &self.path).inspect_err(|_e| {
-            // Best-effort cleanup of temp file on rename failure
-            let _ = std::fs::remove_file(&temp_path);
-        })?;
+        repo_fs::io::write_text(&NormalizedPath::new(&self.path), &content)?;
         Ok(())
     }
 
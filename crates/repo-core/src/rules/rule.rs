@@ -26,6 +26,12 @@ pub struct Rule {
     /// Tags for categorization
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Tools this rule is projected to; empty means every tool
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// Glob patterns of the files this rule targets; empty means every file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
     /// SHA-256 hash of content for drift detection
     pub content_hash: String,
 }
@@ -44,6 +50,8 @@ impl Rule {
             created: now,
             updated: now,
             tags,
+            tools: Vec::new(),
+            paths: Vec::new(),
             content_hash,
         }
     }
@@ -66,10 +74,29 @@ impl Rule {
             created: now,
             updated: now,
             tags,
+            tools: Vec::new(),
+            paths: Vec::new(),
             content_hash,
         }
     }
 
+    /// Restrict the rule to the given tools
+    pub fn with_tools(mut self, tools: Vec<String>) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    /// Restrict the rule to files matching the given glob patterns
+    pub fn with_paths(mut self, paths: Vec<String>) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// Check whether this rule is projected to `tool`
+    pub fn applies_to(&self, tool: &str) -> bool {
+        self.tools.is_empty() || self.tools.iter().any(|t| t == tool)
+    }
+
     /// Compute SHA-256 hash for content
     fn compute_hash_for(content: &str) -> String {
         repo_fs::checksum::compute_content_checksum(content)
@@ -137,4 +164,14 @@ mod tests {
         assert!(!rule.has_drifted("original content"));
         assert!(rule.has_drifted("drifted content"));
     }
+
+    #[test]
+    fn test_applies_to() {
+        let rule = Rule::new("test", "content", vec![]);
+        assert!(rule.applies_to("cursor"));
+
+        let rule = rule.with_tools(vec!["claude".to_string()]);
+        assert!(rule.applies_to("claude"));
+        assert!(!rule.applies_to("cursor"));
+    }
 }
@@ -12,7 +12,8 @@ pub enum CheckStatus {
     Healthy,
     /// Some projections are missing from the filesystem
     Missing,
-    /// Some projections have drifted from expected values
+    /// Some projections have drifted from expected values, or their files
+    /// are corrupt
     Drifted,
     /// The ledger is corrupted or unreadable
     Broken,
@@ -40,6 +41,9 @@ pub struct CheckReport {
     pub drifted: Vec<DriftItem>,
     /// Items that are missing from the filesystem
     pub missing: Vec<DriftItem>,
+    /// Items whose file is not valid UTF-8 and must be regenerated
+    #[serde(default)]
+    pub corrupt: Vec<DriftItem>,
     /// Additional messages about the check
     pub messages: Vec<String>,
 }
@@ -51,6 +55,7 @@ impl CheckReport {
             status: CheckStatus::Healthy,
             drifted: Vec::new(),
             missing: Vec::new(),
+            corrupt: Vec::new(),
             messages: Vec::new(),
         }
     }
@@ -61,6 +66,7 @@ impl CheckReport {
             status: CheckStatus::Missing,
             drifted: Vec::new(),
             missing,
+            corrupt: Vec::new(),
             messages: Vec::new(),
         }
     }
@@ -71,6 +77,7 @@ impl CheckReport {
             status: CheckStatus::Drifted,
             drifted,
             missing: Vec::new(),
+            corrupt: Vec::new(),
             messages: Vec::new(),
         }
     }
@@ -81,6 +88,7 @@ impl CheckReport {
             status: CheckStatus::Broken,
             drifted: Vec::new(),
             missing: Vec::new(),
+            corrupt: Vec::new(),
             messages: vec![message],
         }
     }
@@ -92,6 +100,7 @@ impl CheckReport {
     pub fn merge(mut self, other: CheckReport) -> Self {
         self.drifted.extend(other.drifted);
         self.missing.extend(other.missing);
+        self.corrupt.extend(other.corrupt);
         self.messages.extend(other.messages);
 
         // Determine the worst status
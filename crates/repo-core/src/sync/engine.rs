@@ -3,20 +3,31 @@
 //! The SyncEngine coordinates state between the ledger (configuration intents)
 //! and the filesystem (actual tool configurations).
 
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
 use std::fs;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use uuid::Uuid;
 
-use crate::Result;
 use crate::backend::{ModeBackend, StandardBackend, WorktreeBackend};
+use crate::collisions::find_tool_collisions;
 use crate::config::Manifest;
+use crate::history::{History, HistoryEntry, Operation};
+use crate::hooks::{HookContext, HookEvent, HookRunner};
 use crate::ledger::{Ledger, ProjectionKind};
+use crate::migrate::copy_tree;
 use crate::mode::Mode;
+use crate::{Error, Result};
 use repo_extensions::{ExtensionManifest, ResolveContext, merge_mcp_configs, resolve_mcp_config};
-use repo_fs::NormalizedPath;
+use repo_fs::{ConfigStore, NormalizedPath};
+use repo_meta::DefinitionLoader;
+use repo_tools::{CursorMode, ToolDispatcher, WindsurfMode};
 
 use super::check::{CheckReport, CheckStatus, DriftItem};
+use super::preview::FilePreview;
 use super::rule_syncer::RuleSyncer;
 use super::tool_syncer::ToolSyncer;
 
@@ -29,6 +40,50 @@ pub struct SyncReport {
     pub actions: Vec<String>,
     /// Errors encountered during the operation
     pub errors: Vec<String>,
+    /// Problems that did not stop the operation, such as tool definitions
+    /// that were ignored
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Files written (or that would be written), with what caused each write
+    #[serde(default)]
+    pub changes: Vec<FileChange>,
+}
+
+/// A file written during sync, attributed to its sources
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChange {
+    /// Tool the file belongs to
+    pub tool: String,
+    /// Path of the file, relative to the repository root
+    pub file: String,
+    /// Action description, as recorded in [`SyncReport::actions`]
+    pub action: String,
+    /// What caused the write
+    pub sources: Vec<ChangeSource>,
+}
+
+/// The origin of a write in a [`FileChange`]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChangeSource {
+    /// A rule block from the registry that was added, edited or removed
+    Rule {
+        /// Rule UUID (the block marker)
+        uuid: Uuid,
+        /// Human-readable rule ID
+        id: String,
+    },
+    /// Keys derived from tool settings and capabilities
+    Settings,
+}
+
+impl fmt::Display for ChangeSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rule { id, .. } => write!(f, "rule {}", id),
+            Self::Settings => write!(f, "settings"),
+        }
+    }
 }
 
 impl SyncReport {
@@ -38,6 +93,8 @@ impl SyncReport {
             success: true,
             actions: Vec::new(),
             errors: Vec::new(),
+            warnings: Vec::new(),
+            changes: Vec::new(),
         }
     }
 
@@ -47,6 +104,8 @@ impl SyncReport {
             success: false,
             actions: Vec::new(),
             errors,
+            warnings: Vec::new(),
+            changes: Vec::new(),
         }
     }
 
@@ -55,6 +114,20 @@ impl SyncReport {
         self.actions.push(action);
         self
     }
+
+    /// Group the file changes by source
+    ///
+    /// Each source is listed once, in order (rules first, then settings),
+    /// with every change it contributed to.
+    pub fn changes_by_source(&self) -> Vec<(&ChangeSource, Vec<&FileChange>)> {
+        let mut groups: BTreeMap<&ChangeSource, Vec<&FileChange>> = BTreeMap::new();
+        for change in &self.changes {
+            for source in &change.sources {
+                groups.entry(source).or_default().push(change);
+            }
+        }
+        groups.into_iter().collect()
+    }
 }
 
 /// Options for sync and fix operations
@@ -78,6 +151,8 @@ pub struct SyncEngine {
     mode: Mode,
     /// Backend for mode-specific operations
     backend: Box<dyn ModeBackend>,
+    /// Worktree the sync is scoped to, if not the whole repository
+    worktree: Option<NormalizedPath>,
 }
 
 impl SyncEngine {
@@ -101,12 +176,43 @@ impl SyncEngine {
             root,
             mode,
             backend,
+            worktree: None,
+        })
+    }
+
+    /// Create a SyncEngine scoped to one worktree of a container
+    ///
+    /// Configuration and definitions are read from the container's shared
+    /// `.repository/`, while tool configurations are written into
+    /// `worktree`. Each worktree keeps its own ledger under
+    /// `.repository/worktrees/{name}/`, tracking files relative to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `container` is not a worktree container.
+    pub fn for_worktree(container: NormalizedPath, worktree: NormalizedPath) -> Result<Self> {
+        let backend = WorktreeBackend::with_worktree(container.clone(), worktree.clone())?;
+        Ok(Self {
+            root: container,
+            mode: Mode::Worktrees,
+            backend: Box::new(backend),
+            worktree: Some(worktree),
         })
     }
 
     /// Get the path to the ledger file
     pub fn ledger_path(&self) -> NormalizedPath {
-        self.backend.config_root().join("ledger.toml")
+        let config_root = self.backend.config_root();
+        match self.worktree.as_ref().and_then(|w| w.file_name()) {
+            Some(name) => config_root.join("worktrees").join(name).join("ledger.toml"),
+            None => config_root.join("ledger.toml"),
+        }
+    }
+
+    /// Directory tool configurations are written into and ledger paths
+    /// are relative to
+    fn target(&self) -> &NormalizedPath {
+        self.worktree.as_ref().unwrap_or(&self.root)
     }
 
     /// Load the ledger from disk, or create an empty one if it doesn't exist
@@ -146,12 +252,41 @@ impl SyncEngine {
     /// Check the synchronization state
     ///
     /// Validates that all projections in the ledger are correctly reflected
-    /// in the filesystem.
+    /// in the filesystem. Tool, rule, and preset definitions in
+    /// `.repository/` that fail to load are listed in the report's messages.
     ///
     /// # Returns
     ///
     /// A `CheckReport` containing the status and any issues found.
     pub fn check(&self) -> Result<CheckReport> {
+        let mut report = self.check_projections()?;
+        report.messages.extend(self.definition_problems());
+        Ok(report)
+    }
+
+    /// Problems loading the definitions in `.repository/` and registering
+    /// its tools, and tool slugs supplied by more than one source
+    fn definition_problems(&self) -> Vec<String> {
+        let loader = DefinitionLoader::new();
+        let results = [
+            Ok(ToolDispatcher::from_repository(&self.root)
+                .warnings()
+                .to_vec()),
+            loader.load_rules(&self.root).map(|r| r.warnings),
+            loader.load_presets(&self.root).map(|r| r.warnings),
+        ];
+        let mut problems: Vec<String> = results
+            .into_iter()
+            .flat_map(|result| result.unwrap_or_else(|e| vec![e.to_string()]))
+            .collect();
+        if let Ok(collisions) = find_tool_collisions(&self.root) {
+            problems.extend(collisions.iter().map(ToString::to_string));
+        }
+        problems
+    }
+
+    /// Check every ledger projection against the filesystem
+    fn check_projections(&self) -> Result<CheckReport> {
         let ledger = match self.load_ledger() {
             Ok(l) => l,
             Err(e) => {
@@ -166,10 +301,27 @@ impl SyncEngine {
 
         let mut drifted = Vec::new();
         let mut missing = Vec::new();
+        let mut corrupt = Vec::new();
+        let mut files = CheckedFiles::default();
 
         for intent in ledger.intents() {
             for projection in intent.projections() {
-                let file_path = self.root.join(projection.file.to_string_lossy().as_ref());
+                let file_path = self
+                    .target()
+                    .join(projection.file.to_string_lossy().as_ref());
+
+                // Blocks and keys can't be found in a file that isn't text
+                if !matches!(projection.kind, ProjectionKind::FileManaged { .. })
+                    && let Some(offset) = files.invalid_utf8_at(file_path.as_ref())
+                {
+                    corrupt.push(DriftItem {
+                        intent_id: intent.id.clone(),
+                        tool: projection.tool.clone(),
+                        file: projection.file.to_string_lossy().to_string(),
+                        description: format!("Invalid UTF-8 at byte {}", offset),
+                    });
+                    continue;
+                }
 
                 match &projection.kind {
                     ProjectionKind::FileManaged { checksum } => {
@@ -182,9 +334,22 @@ impl SyncEngine {
                             });
                         } else {
                             // Check checksum
-                            match repo_fs::checksum::compute_file_checksum(file_path.as_ref()) {
+                            match files.checksum(file_path.as_ref()).map(str::to_owned) {
                                 Ok(actual_checksum) => {
-                                    if &actual_checksum != checksum {
+                                    if actual_checksum != *checksum
+                                        && let Some(offset) =
+                                            files.invalid_utf8_at(file_path.as_ref())
+                                    {
+                                        corrupt.push(DriftItem {
+                                            intent_id: intent.id.clone(),
+                                            tool: projection.tool.clone(),
+                                            file: projection.file.to_string_lossy().to_string(),
+                                            description: format!(
+                                                "Invalid UTF-8 at byte {}",
+                                                offset
+                                            ),
+                                        });
+                                    } else if actual_checksum != *checksum {
                                         drifted.push(DriftItem {
                                             intent_id: intent.id.clone(),
                                             tool: projection.tool.clone(),
@@ -218,7 +383,7 @@ impl SyncEngine {
                             });
                         } else {
                             // Check if the file contains the marker UUID
-                            match fs::read_to_string(file_path.as_ref()) {
+                            match files.text(file_path.as_ref()) {
                                 Ok(content) => {
                                     let marker_str = marker.to_string();
                                     if !content.contains(&marker_str) {
@@ -234,9 +399,11 @@ impl SyncEngine {
                                     } else {
                                         // Extract only the managed block for checksum, not the full file
                                         let block_content =
-                                            extract_managed_block(&content, &marker_str);
+                                            extract_managed_block(content, &marker_str);
                                         let actual_checksum =
-                                            repo_fs::checksum::compute_content_checksum(&block_content);
+                                            repo_fs::checksum::compute_content_checksum(
+                                                &block_content,
+                                            );
                                         if actual_checksum != *checksum {
                                             drifted.push(DriftItem {
                                                 intent_id: intent.id.clone(),
@@ -272,10 +439,10 @@ impl SyncEngine {
                             });
                         } else {
                             // Parse JSON and check the key
-                            match fs::read_to_string(file_path.as_ref()) {
-                                Ok(content) => match serde_json::from_str::<Value>(&content) {
+                            match files.json(file_path.as_ref()) {
+                                Ok(parsed) => match parsed {
                                     Ok(json) => {
-                                        let actual_value = get_json_path(&json, path);
+                                        let actual_value = get_json_path(json, path);
                                         match actual_value {
                                             Some(actual) => {
                                                 if actual != value {
@@ -334,11 +501,12 @@ impl SyncEngine {
         }
 
         // Determine overall status
-        if !drifted.is_empty() {
+        if !drifted.is_empty() || !corrupt.is_empty() {
             Ok(CheckReport {
                 status: CheckStatus::Drifted,
                 drifted,
                 missing,
+                corrupt,
                 messages: Vec::new(),
             })
         } else if !missing.is_empty() {
@@ -346,6 +514,7 @@ impl SyncEngine {
                 status: CheckStatus::Missing,
                 drifted,
                 missing,
+                corrupt,
                 messages: Vec::new(),
             })
         } else {
@@ -356,7 +525,35 @@ impl SyncEngine {
     /// Synchronize configuration to the filesystem with options
     ///
     /// When `options.dry_run` is true, simulates changes without writing.
+    /// Otherwise the sync is recorded in the operation history, and the
+    /// repository's hooks run around it: a failing `pre-sync` hook aborts
+    /// the sync with [`Error::HookFailed`](crate::Error::HookFailed), and
+    /// `post-sync` hooks run once it succeeds.
     pub fn sync_with_options(&self, options: SyncOptions) -> Result<SyncReport> {
+        if options.dry_run {
+            return self.sync_impl(&options);
+        }
+
+        let hooks = HookRunner::from_repository(&self.root);
+        let mut context = HookContext::for_sync();
+        if let Some(worktree) = &self.worktree {
+            context.vars.insert(
+                "REPO_WORKTREE".to_string(),
+                worktree.to_native().display().to_string(),
+            );
+        }
+        hooks.run(HookEvent::PreSync, &context)?;
+
+        let report = self.sync_impl(&options)?;
+        self.record_history(Operation::Sync, &report);
+        if report.success {
+            hooks.run(HookEvent::PostSync, &context)?;
+        }
+        Ok(report)
+    }
+
+    /// Synchronize without recording history
+    fn sync_impl(&self, options: &SyncOptions) -> Result<SyncReport> {
         let mut ledger = self.load_ledger()?;
         let mut report = SyncReport::success();
 
@@ -378,7 +575,7 @@ impl SyncEngine {
         }
 
         // Read config and sync tools using typed Manifest parsing
-        let config_content = std::fs::read_to_string(config_path.as_ref())?;
+        let config_content = ConfigStore::new().load_text(&config_path)?;
         let manifest = match Manifest::parse(&config_content) {
             Ok(m) => m,
             Err(e) => {
@@ -390,24 +587,75 @@ impl SyncEngine {
                 return Ok(report);
             }
         };
-        let tool_names = &manifest.tools;
+        self.sync_tools(
+            self.target(),
+            &manifest,
+            &manifest.tools,
+            &mut ledger,
+            options.dry_run,
+            &mut report,
+        );
+
+        // Save ledger
+        if !options.dry_run {
+            self.save_ledger(&ledger)?;
+        }
+
+        report.success = report.errors.is_empty();
+        Ok(report)
+    }
+
+    /// Sync `tool_names` and their rules into `target`, recording actions,
+    /// changes and errors in `report`
+    fn sync_tools(
+        &self,
+        target: &NormalizedPath,
+        manifest: &Manifest,
+        tool_names: &[String],
+        ledger: &mut Ledger,
+        dry_run: bool,
+        report: &mut SyncReport,
+    ) {
+        let modes = tool_format::<CursorMode>(manifest, "cursor", "\"legacy\" or \"mdc\"")
+            .and_then(|cursor| {
+                let windsurf = tool_format::<WindsurfMode>(
+                    manifest,
+                    "windsurf",
+                    "\"legacy\" or \"directory\"",
+                )?;
+                Ok((cursor, windsurf))
+            });
+        let (cursor_mode, windsurf_mode) = match modes {
+            Ok(modes) => modes,
+            Err(e) => {
+                report.success = false;
+                report.errors.push(e);
+                return;
+            }
+        };
 
         // Resolve MCP server configs from extensions
-        let mcp_servers = self.resolve_extension_mcp_configs(&manifest, &mut report);
+        let mcp_servers = self.resolve_extension_mcp_configs(manifest, report);
 
+        let tool_syncer =
+            ToolSyncer::new(target.clone(), dry_run).with_definitions_from(&self.root);
         let tool_syncer = if let Some(servers) = mcp_servers {
-            ToolSyncer::new(self.root.clone(), options.dry_run).with_mcp_servers(servers)
+            tool_syncer.with_mcp_servers(servers)
         } else {
-            ToolSyncer::new(self.root.clone(), options.dry_run)
-        };
+            tool_syncer
+        }
+        .with_cursor_mode(cursor_mode)
+        .with_windsurf_mode(windsurf_mode);
+        report
+            .warnings
+            .extend(tool_syncer.warnings().iter().cloned());
 
         // Sync tool configurations
         for tool_name in tool_names {
-            match tool_syncer.sync_tool(tool_name, &mut ledger) {
-                Ok(actions) => {
-                    for action in actions {
-                        report = report.with_action(action);
-                    }
+            match tool_syncer.sync_tool_with_changes(tool_name, ledger) {
+                Ok((actions, changes)) => {
+                    report.actions.extend(actions);
+                    report.changes.extend(changes);
                 }
                 Err(e) => {
                     report
@@ -418,25 +666,19 @@ impl SyncEngine {
         }
 
         // Sync rules to tool configurations
-        let rule_syncer = RuleSyncer::new(self.root.clone(), options.dry_run);
-        match rule_syncer.sync_rules(tool_names, &mut ledger) {
-            Ok(actions) => {
-                for action in actions {
-                    report = report.with_action(action);
-                }
+        let rule_syncer = RuleSyncer::new(target.clone(), dry_run)
+            .with_definitions_from(&self.root)
+            .with_cursor_mode(cursor_mode)
+            .with_windsurf_mode(windsurf_mode);
+        match rule_syncer.sync_rules_with_changes(tool_names, ledger) {
+            Ok((actions, changes)) => {
+                report.actions.extend(actions);
+                report.changes.extend(changes);
             }
             Err(e) => {
                 report.errors.push(format!("Failed to sync rules: {}", e));
             }
         }
-
-        // Save ledger
-        if !options.dry_run {
-            self.save_ledger(&ledger)?;
-        }
-
-        report.success = report.errors.is_empty();
-        Ok(report)
     }
 
     /// Synchronize configuration to the filesystem
@@ -466,7 +708,7 @@ impl SyncEngine {
         }
 
         // Re-sync will fix drift and recreate missing files
-        let mut sync_report = self.sync_with_options(options)?;
+        let mut sync_report = self.sync_impl(&options)?;
 
         // Re-check after sync to report actual fix counts instead of stale pre-sync counts
         let post_check = self.check()?;
@@ -481,18 +723,45 @@ impl SyncEngine {
             .saturating_sub(post_check.missing.len());
 
         if fixed_drift > 0 {
-            sync_report = sync_report
-                .with_action(format!("Fixed {} drifted projections", fixed_drift));
+            sync_report =
+                sync_report.with_action(format!("Fixed {} drifted projections", fixed_drift));
         }
 
         if fixed_missing > 0 {
-            sync_report = sync_report
-                .with_action(format!("Recreated {} missing projections", fixed_missing));
+            sync_report =
+                sync_report.with_action(format!("Recreated {} missing projections", fixed_missing));
+        }
+
+        if !options.dry_run {
+            self.record_history(Operation::Fix, &sync_report);
         }
 
         Ok(sync_report)
     }
 
+    /// Record a sync or fix in the operation history
+    fn record_history(&self, operation: Operation, report: &SyncReport) {
+        let mut files: Vec<String> = report.changes.iter().map(|c| c.file.clone()).collect();
+        files.sort();
+        files.dedup();
+
+        let mut summary = match files.len() {
+            0 => "No files changed".to_string(),
+            1 => "1 file changed".to_string(),
+            n => format!("{} files changed", n),
+        };
+        if !report.errors.is_empty() {
+            summary.push_str(&format!(", {} error(s)", report.errors.len()));
+        }
+
+        History::record(
+            &self.backend.config_root(),
+            HistoryEntry::new(operation, summary)
+                .with_actions(report.actions.len())
+                .with_changed_files(files),
+        );
+    }
+
     /// Fix synchronization issues
     ///
     /// Re-synchronizes to repair any drift or missing files.
@@ -504,11 +773,126 @@ impl SyncEngine {
         self.fix_with_options(SyncOptions::default())
     }
 
+    /// Preview what a sync would write for `tool`
+    ///
+    /// Runs the tool and rule syncers against a scratch copy of the tool's
+    /// files and returns every file whose content would differ, with its
+    /// content before and after. Nothing in the repository is modified.
+    pub fn preview_tool(&self, tool: &str) -> Result<Vec<FilePreview>> {
+        let manifest = self.load_manifest()?;
+        let ledger = self.load_ledger()?;
+        let target = self.target();
+
+        // Every file the tool owns now or may write: its config locations,
+        // and whatever the ledger says earlier syncs wrote for it
+        let mut files: BTreeSet<String> = ledger
+            .projections_for_tool(tool)
+            .into_iter()
+            .map(|(_, p)| p.file.to_string_lossy().replace('\\', "/"))
+            .collect();
+        let mut dirs = Vec::new();
+        if let Some(integration) = ToolDispatcher::from_repository(&self.root).get_integration(tool)
+        {
+            for location in integration.config_locations() {
+                if location.is_directory {
+                    dirs.push(location.path);
+                } else {
+                    files.insert(location.path);
+                }
+            }
+            files.extend(integration.shared_files());
+        }
+
+        let scratch = tempfile::tempdir()?;
+        let scratch_root = NormalizedPath::new(scratch.path());
+        for dir in &dirs {
+            let from = target.join(dir).to_native();
+            if from.is_dir() {
+                copy_tree(&from, &scratch_root.join(dir).to_native())?;
+            }
+        }
+        for file in &files {
+            let from = target.join(file).to_native();
+            if from.is_file() {
+                let to = scratch_root.join(file).to_native();
+                if let Some(parent) = to.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(&from, &to)?;
+            }
+        }
+
+        let mut synced_ledger = ledger.clone();
+        let mut report = SyncReport::success();
+        self.sync_tools(
+            &scratch_root,
+            &manifest,
+            &[tool.to_string()],
+            &mut synced_ledger,
+            false,
+            &mut report,
+        );
+        if !report.errors.is_empty() {
+            return Err(Error::SyncError {
+                message: report.errors.join("; "),
+            });
+        }
+
+        files.extend(
+            synced_ledger
+                .projections_for_tool(tool)
+                .into_iter()
+                .map(|(_, p)| p.file.to_string_lossy().replace('\\', "/")),
+        );
+        files.extend(report.changes.iter().map(|c| c.file.replace('\\', "/")));
+
+        let read = |path: PathBuf| fs::read_to_string(path).ok();
+        Ok(files
+            .into_iter()
+            .map(|file| FilePreview {
+                tool: tool.to_string(),
+                current: read(target.join(&file).to_native()),
+                synced: read(scratch_root.join(&file).to_native()),
+                file,
+            })
+            .filter(FilePreview::is_changed)
+            .collect())
+    }
+
+    /// Preview what a sync would write for every configured tool
+    ///
+    /// See [`preview_tool`](Self::preview_tool).
+    pub fn preview(&self) -> Result<Vec<FilePreview>> {
+        let mut previews = Vec::new();
+        for tool in &self.load_manifest()?.tools {
+            previews.extend(self.preview_tool(tool)?);
+        }
+        Ok(previews)
+    }
+
+    /// Read config.toml, or an empty manifest if there is none
+    fn load_manifest(&self) -> Result<Manifest> {
+        let config_path = self.backend.config_root().join("config.toml");
+        if config_path.exists() {
+            Manifest::parse(&ConfigStore::new().load_text(&config_path)?)
+        } else {
+            Ok(Manifest::empty())
+        }
+    }
+
     /// Get the repository root path
     pub fn root(&self) -> &NormalizedPath {
         &self.root
     }
 
+    /// Get the directory sync writes tool configurations into
+    ///
+    /// The repository root in Standard mode, the active worktree in
+    /// Worktrees mode.
+    pub fn working_dir(&self) -> &NormalizedPath {
+        self.backend.working_dir()
+    }
+
     /// Get the repository mode
     pub fn mode(&self) -> Mode {
         self.mode
@@ -533,7 +917,7 @@ impl SyncEngine {
         }
 
         let extensions_dir = self.root.join(".repository/extensions");
-        let mut mcp_configs: Vec<Value> = Vec::new();
+        let mut mcp_configs: Vec<(String, Value)> = Vec::new();
 
         for ext_name in manifest.extensions.keys() {
             let ext_source_dir = extensions_dir.join(ext_name);
@@ -582,7 +966,7 @@ impl SyncEngine {
                         "Resolved {} MCP server(s) from extension '{}'",
                         server_count, ext_name
                     ));
-                    mcp_configs.push(config);
+                    mcp_configs.push((ext_name.clone(), config));
                 }
                 Ok(None) => {
                     // Extension doesn't provide MCP config - that's fine
@@ -602,9 +986,35 @@ impl SyncEngine {
         }
 
         if mcp_configs.is_empty() {
-            None
-        } else {
-            Some(merge_mcp_configs(&mcp_configs))
+            return None;
+        }
+
+        let policy = manifest.mcp.on_conflict.unwrap_or_default();
+        match merge_mcp_configs(&mcp_configs, policy) {
+            Ok((merged, merge_report)) => {
+                for conflict in &merge_report.conflicts {
+                    let resolution = match &conflict.renamed_to {
+                        Some(renamed) => {
+                            format!("kept '{}' as '{}'", conflict.second_source, renamed)
+                        }
+                        None => format!("using '{}'", conflict.second_source),
+                    };
+                    report.actions.push(format!(
+                        "MCP server '{}' defined by both '{}' and '{}', {}",
+                        conflict.server_name,
+                        conflict.first_source,
+                        conflict.second_source,
+                        resolution
+                    ));
+                }
+                Some(merged)
+            }
+            Err(e) => {
+                report
+                    .errors
+                    .push(format!("Failed to merge extension MCP configs: {}", e));
+                None
+            }
         }
     }
 
@@ -626,6 +1036,89 @@ impl SyncEngine {
     }
 }
 
+/// Files read during a check
+///
+/// Several projections usually point at the same file, e.g. a tool's
+/// config file and the rules written into it, or every MCP server in one
+/// JSON file. Each file is read, hashed, and parsed as JSON at most once;
+/// files that are only hashed are streamed rather than read into memory.
+#[derive(Default)]
+struct CheckedFiles {
+    contents: HashMap<PathBuf, std::result::Result<Vec<u8>, String>>,
+    checksums: HashMap<PathBuf, std::result::Result<String, String>>,
+    json: HashMap<PathBuf, std::result::Result<Value, String>>,
+}
+
+impl CheckedFiles {
+    fn checksum(&mut self, path: &Path) -> std::result::Result<&str, String> {
+        if !self.checksums.contains_key(path) {
+            let checksum = match self.contents.get(path) {
+                Some(content) => content
+                    .as_deref()
+                    .map(repo_fs::checksum::compute_bytes_checksum)
+                    .map_err(Clone::clone),
+                None => repo_fs::checksum::compute_file_checksum(path).map_err(|e| e.to_string()),
+            };
+            self.checksums.insert(path.to_path_buf(), checksum);
+        }
+        self.checksums[path].as_deref().map_err(Clone::clone)
+    }
+
+    fn read(&mut self, path: &Path) -> std::result::Result<&[u8], String> {
+        self.contents
+            .entry(path.to_path_buf())
+            .or_insert_with(|| fs::read(path).map_err(|e| e.to_string()))
+            .as_deref()
+            .map_err(Clone::clone)
+    }
+
+    /// Offset of the first invalid UTF-8 byte, `None` for valid or
+    /// unreadable files
+    fn invalid_utf8_at(&mut self, path: &Path) -> Option<usize> {
+        let content = self.read(path).ok()?;
+        std::str::from_utf8(content).err().map(|e| e.valid_up_to())
+    }
+
+    fn text(&mut self, path: &Path) -> std::result::Result<&str, String> {
+        self.read(path)
+            .and_then(|content| std::str::from_utf8(content).map_err(|e| e.to_string()))
+    }
+
+    /// The file parsed as JSON; the outer error is a read failure
+    fn json(
+        &mut self,
+        path: &Path,
+    ) -> std::result::Result<&std::result::Result<Value, String>, String> {
+        if !self.json.contains_key(path) {
+            let parsed = serde_json::from_slice(self.read(path)?).map_err(|e| e.to_string());
+            self.json.insert(path.to_path_buf(), parsed);
+        }
+        Ok(&self.json[path])
+    }
+}
+
+/// Read where a tool's rules are written from `[tool_settings.<tool>] format`
+///
+/// `expected` lists the accepted values for the error message.
+fn tool_format<T: serde::de::DeserializeOwned + Default>(
+    manifest: &Manifest,
+    tool: &str,
+    expected: &str,
+) -> std::result::Result<T, String> {
+    match manifest
+        .tool_settings
+        .get(tool)
+        .and_then(|settings| settings.get("format"))
+    {
+        Some(format) => serde_json::from_value(format.clone()).map_err(|_| {
+            format!(
+                "Invalid [tool_settings.{}] format {}: expected {}",
+                tool, format, expected
+            )
+        }),
+        None => Ok(T::default()),
+    }
+}
 
 /// Extract managed block content from a file by marker UUID
 ///
@@ -0,0 +1,193 @@
+//! MCP server synchronization
+//!
+//! This module provides the `McpSyncer` for pushing the servers declared in
+//! `[mcp.servers]` of config.toml into each tool's MCP config.
+//!
+//! The names of the servers written for a tool are recorded in the ledger
+//! as an `mcp:<tool>` intent. On the next sync, a recorded server that is no
+//! longer declared is removed, while servers the user added by hand are left
+//! alone.
+
+use crate::Result;
+use crate::ledger::{Intent, Ledger, McpArgs};
+use repo_fs::NormalizedPath;
+use repo_meta::schema::{McpScope, McpServerConfig, McpSyncResult};
+use repo_tools::{McpInstaller, mcp_config_spec};
+use std::collections::BTreeMap;
+
+/// Synchronizes declared MCP servers to tool configurations
+pub struct McpSyncer {
+    /// Root path for the repository
+    root: NormalizedPath,
+}
+
+impl McpSyncer {
+    /// Create a new `McpSyncer`
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root path of the repository
+    pub fn new(root: NormalizedPath) -> Self {
+        Self { root }
+    }
+
+    /// Ledger intent ID recording the servers synced to `tool`
+    fn intent_id(tool: &str) -> String {
+        format!("mcp:{}", tool)
+    }
+
+    /// Names of the servers last synced to `tool`, as recorded in the ledger
+    pub fn managed_servers(ledger: &Ledger, tool: &str) -> Vec<String> {
+        ledger
+            .find_by_rule(&Self::intent_id(tool))
+            .iter()
+            .filter_map(|intent| intent.typed_args::<McpArgs>().ok())
+            .flat_map(|args| args.servers)
+            .collect()
+    }
+
+    /// Tools with servers recorded in the ledger, in ledger order
+    pub fn synced_tools(ledger: &Ledger) -> Vec<String> {
+        ledger
+            .intents()
+            .iter()
+            .filter_map(|intent| intent.id.strip_prefix("mcp:"))
+            .map(String::from)
+            .collect()
+    }
+
+    /// Sync `servers` to the project-scope MCP config of `tool`
+    ///
+    /// Returns `Ok(None)` if the tool does not support MCP. On success the
+    /// ledger records `servers` as the set managed for `tool`.
+    pub fn sync_tool(
+        &self,
+        tool: &str,
+        servers: &BTreeMap<String, McpServerConfig>,
+        ledger: &mut Ledger,
+    ) -> Result<Option<McpSyncResult>> {
+        if mcp_config_spec(tool).is_none() {
+            return Ok(None);
+        }
+
+        let previously_managed = Self::managed_servers(ledger, tool);
+        let installer = McpInstaller::new(tool, self.root.clone())?;
+        let result = installer.sync(McpScope::Project, servers, &previously_managed)?;
+
+        let intent_id = Self::intent_id(tool);
+        let stale: Vec<_> = ledger
+            .find_by_rule(&intent_id)
+            .iter()
+            .map(|intent| intent.uuid)
+            .collect();
+        for uuid in stale {
+            ledger.remove_intent(uuid);
+        }
+        if !servers.is_empty() {
+            let args = McpArgs {
+                servers: servers.keys().cloned().collect(),
+            };
+            ledger.add_intent(Intent::new(intent_id, serde_json::to_value(args)?));
+        }
+
+        Ok(Some(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use repo_meta::schema::McpTransportConfig;
+    use serde_json::Value;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn server(command: &str) -> McpServerConfig {
+        McpServerConfig {
+            transport: McpTransportConfig::Stdio {
+                command: command.into(),
+                args: vec![],
+                cwd: None,
+            },
+            env: None,
+            auto_approve: false,
+            expand_env: false,
+        }
+    }
+
+    fn cursor_servers(temp: &TempDir) -> Value {
+        let content = fs::read_to_string(temp.path().join(".cursor/mcp.json")).unwrap();
+        serde_json::from_str::<Value>(&content).unwrap()["mcpServers"].clone()
+    }
+
+    #[test]
+    fn test_sync_records_and_removes_managed_servers() {
+        let temp = TempDir::new().unwrap();
+        let syncer = McpSyncer::new(NormalizedPath::new(temp.path()));
+        let mut ledger = Ledger::new();
+
+        let servers = BTreeMap::from([
+            ("a".to_string(), server("a")),
+            ("b".to_string(), server("b")),
+        ]);
+        let result = syncer
+            .sync_tool("cursor", &servers, &mut ledger)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.added, vec!["a", "b"]);
+        assert_eq!(
+            McpSyncer::managed_servers(&ledger, "cursor"),
+            vec!["a", "b"]
+        );
+
+        // A server the user added by hand survives the next sync
+        McpInstaller::new("cursor", NormalizedPath::new(temp.path()))
+            .unwrap()
+            .install(McpScope::Project, "mine", &server("mine"))
+            .unwrap();
+
+        let servers = BTreeMap::from([("a".to_string(), server("a"))]);
+        let result = syncer
+            .sync_tool("cursor", &servers, &mut ledger)
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.removed, vec!["b"]);
+        assert_eq!(McpSyncer::managed_servers(&ledger, "cursor"), vec!["a"]);
+        assert_eq!(ledger.find_by_rule("mcp:cursor").len(), 1);
+        assert_eq!(McpSyncer::synced_tools(&ledger), vec!["cursor"]);
+
+        let installed = cursor_servers(&temp);
+        assert!(installed.get("a").is_some());
+        assert!(installed.get("b").is_none());
+        assert!(installed.get("mine").is_some());
+    }
+
+    #[test]
+    fn test_sync_empty_set_clears_intent() {
+        let temp = TempDir::new().unwrap();
+        let syncer = McpSyncer::new(NormalizedPath::new(temp.path()));
+        let mut ledger = Ledger::new();
+
+        let servers = BTreeMap::from([("a".to_string(), server("a"))]);
+        syncer.sync_tool("cursor", &servers, &mut ledger).unwrap();
+        syncer
+            .sync_tool("cursor", &BTreeMap::new(), &mut ledger)
+            .unwrap();
+
+        assert!(ledger.intents().is_empty());
+        assert!(cursor_servers(&temp).get("a").is_none());
+    }
+
+    #[test]
+    fn test_sync_skips_tools_without_mcp() {
+        let temp = TempDir::new().unwrap();
+        let syncer = McpSyncer::new(NormalizedPath::new(temp.path()));
+        let mut ledger = Ledger::new();
+
+        let servers = BTreeMap::from([("a".to_string(), server("a"))]);
+        let result = syncer.sync_tool("aider", &servers, &mut ledger).unwrap();
+
+        assert!(result.is_none());
+        assert!(ledger.intents().is_empty());
+    }
+}
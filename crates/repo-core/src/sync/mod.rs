@@ -6,13 +6,19 @@
 //! - **fix**: Re-synchronize to repair drift or missing files
 //! - **tool_syncer**: Coordinate syncing of tool configurations
 //! - **rule_syncer**: Synchronize rules from `.repository/rules/` to tool configurations
+//! - **mcp_syncer**: Synchronize MCP servers declared in config.toml to tool configurations
+//! - **preview**: Compare what a sync would write for one tool with what is on disk
 
 mod check;
 mod engine;
+mod mcp_syncer;
+mod preview;
 mod rule_syncer;
 mod tool_syncer;
 
 pub use check::{CheckReport, CheckStatus, DriftItem};
-pub use engine::{SyncEngine, SyncOptions, SyncReport, get_json_path};
+pub use engine::{ChangeSource, FileChange, SyncEngine, SyncOptions, SyncReport, get_json_path};
+pub use mcp_syncer::McpSyncer;
+pub use preview::{BlockChanges, FilePreview};
 pub use rule_syncer::{RuleFile, RuleSyncer};
 pub use tool_syncer::ToolSyncer;
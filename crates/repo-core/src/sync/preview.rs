@@ -0,0 +1,121 @@
+//! Per-tool sync previews
+//!
+//! [`SyncEngine::preview_tool`](super::SyncEngine::preview_tool) runs the
+//! tool and rule syncers for one tool against a scratch copy of its files,
+//! so the exact content sync would write can be compared with what is on
+//! disk without touching the repository.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// One file a tool's sync would write, before and after
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePreview {
+    /// The tool the file belongs to
+    pub tool: String,
+    /// Path relative to the sync root, with forward slashes
+    pub file: String,
+    /// Content on disk, `None` if the file does not exist
+    pub current: Option<String>,
+    /// Content after a sync, `None` if sync would delete the file
+    pub synced: Option<String>,
+}
+
+/// Managed blocks that differ between two versions of a file, by marker
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BlockChanges {
+    /// Blocks only in the synced version
+    pub added: Vec<String>,
+    /// Blocks only in the current version
+    pub removed: Vec<String>,
+    /// Blocks in both whose content differs
+    pub changed: Vec<String>,
+}
+
+impl FilePreview {
+    /// Whether sync would change the file
+    pub fn is_changed(&self) -> bool {
+        self.current != self.synced
+    }
+
+    /// Compare the managed blocks of the current and synced content
+    pub fn block_changes(&self) -> BlockChanges {
+        let blocks = |content: &Option<String>| {
+            content
+                .as_deref()
+                .map(repo_blocks::parse_blocks)
+                .unwrap_or_default()
+        };
+        let current = blocks(&self.current);
+        let synced = blocks(&self.synced);
+        let before: HashMap<&str, &str> = current
+            .iter()
+            .map(|b| (b.uuid.as_str(), b.content.as_str()))
+            .collect();
+        let after: HashMap<&str, &str> = synced
+            .iter()
+            .map(|b| (b.uuid.as_str(), b.content.as_str()))
+            .collect();
+
+        let mut changes = BlockChanges::default();
+        for block in &synced {
+            match before.get(block.uuid.as_str()) {
+                None => changes.added.push(block.uuid.clone()),
+                Some(content) if *content != block.content => {
+                    changes.changed.push(block.uuid.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        changes.removed = current
+            .iter()
+            .filter(|b| !after.contains_key(b.uuid.as_str()))
+            .map(|b| b.uuid.clone())
+            .collect();
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(uuid: &str, content: &str) -> String {
+        format!(
+            "<!-- repo:block:{0} -->\n{1}\n<!-- /repo:block:{0} -->\n",
+            uuid, content
+        )
+    }
+
+    #[test]
+    fn test_block_changes() {
+        let preview = FilePreview {
+            tool: "claude".into(),
+            file: "CLAUDE.md".into(),
+            current: Some(block("kept", "same") + &block("edited", "old") + &block("gone", "x")),
+            synced: Some(block("kept", "same") + &block("edited", "new") + &block("fresh", "y")),
+        };
+
+        assert!(preview.is_changed());
+        assert_eq!(
+            preview.block_changes(),
+            BlockChanges {
+                added: vec!["fresh".into()],
+                removed: vec!["gone".into()],
+                changed: vec!["edited".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_block_changes_new_file() {
+        let preview = FilePreview {
+            tool: "claude".into(),
+            file: "CLAUDE.md".into(),
+            current: None,
+            synced: Some(block("fresh", "y")),
+        };
+        assert_eq!(preview.block_changes().added, ["fresh"]);
+    }
+}
@@ -7,12 +7,22 @@
 //! The rule UUID becomes the managed block marker in tool config files,
 //! enabling bidirectional traceability between registry and projections.
 
+use super::engine::{ChangeSource, FileChange};
 use crate::Result;
-use crate::ledger::{Intent, Ledger, Projection, ProjectionKind};
+use crate::ledger::{Intent, Ledger, Projection, ProjectionKind, RuleBlock, RulesArgs};
 use crate::projection::{ProjectionWriter, compute_checksum};
 use crate::rules::RuleRegistry;
 use repo_fs::NormalizedPath;
+use repo_tools::{
+    CursorMode, MDC_EXTENSION, WINDSURF_RULE_CHAR_LIMIT, WINDSURF_RULES_DIR, WindsurfMode,
+    instructions_file_name, mdc_file_name, render_instructions, render_mdc, render_windsurf_rule,
+    windsurf_rule_file_name, windsurf_trigger,
+};
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::rc::Rc;
+#[cfg(test)]
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// A rule loaded from the registry with UUID for block markers
 #[derive(Debug, Clone)]
@@ -23,6 +33,19 @@ pub struct RuleFile {
     pub id: String,
     /// The rule content
     pub content: String,
+    /// Tools the rule is projected to; empty means every tool
+    pub tools: Vec<String>,
+    /// Glob patterns of the files the rule targets; empty means every file
+    pub paths: Vec<String>,
+    /// Tags of the rule; `trigger:<mode>` sets its Windsurf activation mode
+    pub tags: Vec<String>,
+}
+
+impl RuleFile {
+    /// Check whether this rule is projected to `tool`
+    pub fn applies_to(&self, tool: &str) -> bool {
+        self.tools.is_empty() || self.tools.iter().any(|t| t == tool)
+    }
 }
 
 /// Synchronizes rules to tool configurations
@@ -32,10 +55,57 @@ pub struct RuleFile {
 pub struct RuleSyncer {
     /// Root path for the repository
     root: NormalizedPath,
+    /// Repository whose `.repository/` holds the rule registry
+    config_root: NormalizedPath,
     /// Whether to run in dry-run mode (simulate changes without writing)
     dry_run: bool,
+    /// Where Cursor rules are written
+    cursor_mode: CursorMode,
+    /// Where Windsurf rules are written
+    windsurf_mode: WindsurfMode,
+    /// Rules rendered so far, for measuring the render cache
+    #[cfg(test)]
+    renders: AtomicUsize,
 }
 
+/// How a rule is rendered: as a block in a tool's rules file, or as a file
+/// of its own in one of the per-rule formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RuleFormat {
+    Block,
+    Mdc,
+    Windsurf,
+    Instructions,
+}
+
+impl RuleFormat {
+    /// Format of a rule written to `path` by a tool whose rules file is
+    /// `rules_file`
+    fn of(path: &str, rules_file: &str) -> Self {
+        if path == rules_file {
+            Self::Block
+        } else if path.ends_with(MDC_EXTENSION) {
+            Self::Mdc
+        } else if path.starts_with(WINDSURF_RULES_DIR) {
+            Self::Windsurf
+        } else {
+            Self::Instructions
+        }
+    }
+}
+
+/// A rule rendered in one format, with its checksum
+struct RenderedRule {
+    content: Rc<str>,
+    checksum: String,
+}
+
+/// Rules rendered during one sync, by rule and format
+///
+/// A rule renders the same for every tool using the same format, so each
+/// one is rendered and hashed once and shared by all of those tools.
+type RenderCache = HashMap<(uuid::Uuid, RuleFormat), Rc<RenderedRule>>;
+
 impl RuleSyncer {
     /// Create a new `RuleSyncer`
     ///
@@ -44,7 +114,34 @@ impl RuleSyncer {
     /// * `root` - The root path of the repository
     /// * `dry_run` - If true, simulate changes without modifying the filesystem
     pub fn new(root: NormalizedPath, dry_run: bool) -> Self {
-        Self { root, dry_run }
+        Self {
+            config_root: root.clone(),
+            root,
+            dry_run,
+            cursor_mode: CursorMode::default(),
+            windsurf_mode: WindsurfMode::default(),
+            #[cfg(test)]
+            renders: AtomicUsize::new(0),
+        }
+    }
+
+    /// Read the rule registry from `root`'s `.repository/` instead of the
+    /// sync root's, e.g. from a container when syncing one of its worktrees
+    pub fn with_definitions_from(mut self, root: &NormalizedPath) -> Self {
+        self.config_root = root.clone();
+        self
+    }
+
+    /// Set where Cursor rules are written
+    pub fn with_cursor_mode(mut self, mode: CursorMode) -> Self {
+        self.cursor_mode = mode;
+        self
+    }
+
+    /// Set where Windsurf rules are written
+    pub fn with_windsurf_mode(mut self, mode: WindsurfMode) -> Self {
+        self.windsurf_mode = mode;
+        self
     }
 
     /// Load all rules from the rule registry
@@ -56,7 +153,7 @@ impl RuleSyncer {
     ///
     /// A vector of `RuleFile` structs, empty if the registry doesn't exist.
     pub fn load_rules(&self) -> Result<Vec<RuleFile>> {
-        let registry_path = self.root.join(".repository/rules/registry.toml");
+        let registry_path = self.config_root.join(".repository/rules/registry.toml");
         let native_path = registry_path.to_native();
 
         if !native_path.exists() {
@@ -71,6 +168,9 @@ impl RuleSyncer {
                 uuid: r.uuid,
                 id: r.id.clone(),
                 content: r.content.clone(),
+                tools: r.tools.clone(),
+                paths: r.paths.clone(),
+                tags: r.tags.clone(),
             })
             .collect();
 
@@ -84,7 +184,7 @@ impl RuleSyncer {
     ///
     /// This method:
     /// 1. Loads all rules from the rule registry (`.repository/rules/registry.toml`)
-    /// 2. Combines rules into content with UUID-based block markers
+    /// 2. Combines the rules that apply to each tool into content with UUID-based block markers
     /// 3. Writes to each tool's rules file (e.g., `.cursorrules`)
     /// 4. Updates the ledger with the projection
     ///
@@ -97,16 +197,32 @@ impl RuleSyncer {
     ///
     /// A list of action descriptions taken during the sync.
     pub fn sync_rules(&self, tools: &[String], ledger: &mut Ledger) -> Result<Vec<String>> {
+        self.sync_rules_with_changes(tools, ledger)
+            .map(|(actions, _)| actions)
+    }
+
+    /// Sync rules, also returning the files written
+    ///
+    /// Each rules intent records a checksum per rule block, so a write can
+    /// be attributed to exactly the rules that were added, edited or
+    /// removed since the previous sync. Intents written before per-rule
+    /// checksums were recorded attribute the write to every rule in the file.
+    pub fn sync_rules_with_changes(
+        &self,
+        tools: &[String],
+        ledger: &mut Ledger,
+    ) -> Result<(Vec<String>, Vec<FileChange>)> {
         let mut actions = Vec::new();
+        let mut changes = Vec::new();
 
         let rules = self.load_rules()?;
         if rules.is_empty() {
             actions.push("No rules found in registry".to_string());
-            return Ok(actions);
+            return Ok((actions, changes));
         }
 
-        let combined_rules = self.combine_rules(&rules);
         let writer = ProjectionWriter::new(self.root.clone(), self.dry_run);
+        let mut cache = RenderCache::default();
 
         // Apply rules to each applicable tool
         for tool in tools {
@@ -114,48 +230,183 @@ impl RuleSyncer {
 
             if let Some(file) = rules_file {
                 let intent_id = format!("rules:{}", tool);
-
-                // Check if already synced with same checksum
                 let existing = ledger.find_by_rule(&intent_id);
-                let new_checksum = compute_checksum(&combined_rules);
-
-                // Check if content has changed
-                let needs_update = if let Some(existing_intent) = existing.first() {
-                    // Check if checksum differs
-                    existing_intent.projections().iter().any(|p| {
-                        if let ProjectionKind::FileManaged { checksum } = &p.kind {
-                            checksum != &new_checksum
-                        } else {
-                            true
+
+                let tool_rules: Vec<RuleFile> = rules
+                    .iter()
+                    .filter(|r| r.applies_to(tool))
+                    .cloned()
+                    .collect();
+                if tool_rules.is_empty() && existing.is_empty() {
+                    continue;
+                }
+
+                // Files to write: the rules file, plus one file per rule with
+                // target paths for tools that support path-scoped rules. Tools
+                // with a rules directory get one file per rule instead.
+                let rules_dir = self.get_rules_dir_for_tool(tool);
+                let scoped_dir = self.get_scoped_rules_dir_for_tool(tool);
+                let rule_file = |rule: &RuleFile| match (rules_dir, scoped_dir) {
+                    (Some(WINDSURF_RULES_DIR), _) => {
+                        format!(
+                            "{}{}",
+                            WINDSURF_RULES_DIR,
+                            windsurf_rule_file_name(&rule.id)
+                        )
+                    }
+                    (Some(dir), _) => format!("{}{}", dir, mdc_file_name(&rule.id)),
+                    (None, Some(dir)) if !rule.paths.is_empty() => {
+                        format!("{}{}", dir, instructions_file_name(&rule.id))
+                    }
+                    _ => file.clone(),
+                };
+                let mut files: Vec<(String, Rc<str>, String)> = Vec::new();
+                if rules_dir.is_none() {
+                    let blocks: Vec<Rc<RenderedRule>> = tool_rules
+                        .iter()
+                        .filter(|r| rule_file(r) == file)
+                        .map(|r| self.rendered(&mut cache, r, RuleFormat::Block))
+                        .collect();
+                    let content = Self::combine_blocks(blocks.iter().map(|b| &*b.content));
+                    let checksum = compute_checksum(&content);
+                    files.push((file.clone(), content.into(), checksum));
+                }
+                for rule in &tool_rules {
+                    let path = rule_file(rule);
+                    if path != file {
+                        let rendered =
+                            self.rendered(&mut cache, rule, RuleFormat::of(&path, &file));
+                        files.push((path, rendered.content.clone(), rendered.checksum.clone()));
+                    }
+                }
+
+                // Windsurf ignores content past its per-file limit
+                if tool == "windsurf" {
+                    for (path, content, _) in &files {
+                        let length = content.chars().count();
+                        if length > WINDSURF_RULE_CHAR_LIMIT {
+                            tracing::warn!(
+                                "{} is {} characters, over Windsurf's limit of {}",
+                                path,
+                                length,
+                                WINDSURF_RULE_CHAR_LIMIT
+                            );
+                            actions.push(format!(
+                                "Warning: {} is {} characters; Windsurf reads only the first {}",
+                                path, length, WINDSURF_RULE_CHAR_LIMIT
+                            ));
                         }
+                    }
+                }
+
+                // Check if any file has changed since the last sync
+                let recorded: BTreeMap<String, String> = existing
+                    .first()
+                    .map(|intent| {
+                        intent
+                            .projections()
+                            .iter()
+                            .filter_map(|p| match &p.kind {
+                                ProjectionKind::FileManaged { checksum } => {
+                                    Some((p.file.to_string_lossy().into_owned(), checksum.clone()))
+                                }
+                                _ => None,
+                            })
+                            .collect()
                     })
-                } else {
-                    true
-                };
+                    .unwrap_or_default();
+                let desired: BTreeMap<String, String> = files
+                    .iter()
+                    .map(|(path, _, checksum)| (path.clone(), checksum.clone()))
+                    .collect();
+
+                // Files whose content changed, or that went missing
+                let stale: Vec<&(String, Rc<str>, String)> = files
+                    .iter()
+                    .filter(|(path, _, _)| {
+                        existing.is_empty()
+                            || recorded.get(path) != desired.get(path)
+                            || !self.root.join(path).exists()
+                    })
+                    .collect();
 
-                if !needs_update {
+                if stale.is_empty() && recorded == desired {
                     actions.push(format!("Rules for {} unchanged", tool));
                     continue;
                 }
 
-                // Create projection for writing
-                let projection = Projection::file_managed(
-                    tool.clone(),
-                    PathBuf::from(&file),
-                    String::new(), // Checksum will be updated after
-                );
+                let block_checksums =
+                    self.block_checksums(&mut cache, &tool_rules, &file, rule_file);
+                let previous_args = existing
+                    .first()
+                    .map(|intent| intent.typed_args::<RulesArgs>())
+                    .transpose()?
+                    .unwrap_or_default();
+                let sources = Self::changed_rules(&previous_args, &block_checksums, &tool_rules);
+
+                // Write the files whose content changed or went missing
+                for (path, content, _) in stale {
+                    let projection = Projection::file_managed(
+                        tool.clone(),
+                        PathBuf::from(path),
+                        String::new(), // Checksum is recorded in the intent below
+                    );
+                    let action = writer.apply(&projection, content)?;
+                    changes.push(FileChange {
+                        tool: tool.clone(),
+                        file: path.clone(),
+                        action: action.clone(),
+                        sources: Self::sources_in_file(
+                            path,
+                            &file,
+                            &sources,
+                            &previous_args,
+                            &block_checksums,
+                        ),
+                    });
+                    actions.push(action);
+                }
 
-                // Write the file
-                let action = writer.apply(&projection, &combined_rules)?;
-                actions.push(action);
+                // Retire files no longer written: per-rule files of rules that
+                // were removed or lost their target paths, and the rules file
+                // of a tool that switched to a rules directory
+                for (path, checksum) in recorded.iter().filter(|(p, _)| !desired.contains_key(*p)) {
+                    let action = self.retire_file(
+                        &writer,
+                        tool,
+                        path,
+                        checksum,
+                        &Self::markers_in_file(path, &file, &previous_args),
+                    )?;
+                    changes.push(FileChange {
+                        tool: tool.clone(),
+                        file: path.clone(),
+                        action: action.clone(),
+                        sources: Self::sources_in_file(
+                            path,
+                            &file,
+                            &sources,
+                            &previous_args,
+                            &block_checksums,
+                        ),
+                    });
+                    actions.push(action);
+                }
 
-                // Create intent with updated checksum
-                let mut intent = Intent::new(intent_id.clone(), serde_json::json!({}));
-                intent.add_projection(Projection::file_managed(
-                    tool.clone(),
-                    PathBuf::from(&file),
-                    new_checksum,
-                ));
+                // Create intent with updated checksums
+                let mut intent = Intent::new(
+                    intent_id.clone(),
+                    serde_json::to_value(RulesArgs {
+                        rules: Some(block_checksums),
+                    })?,
+                );
+                for (path, checksum) in desired {
+                    intent.add_projection(Projection::file_managed(
+                        tool.clone(),
+                        PathBuf::from(path),
+                        checksum,
+                    ));
+                }
 
                 if !self.dry_run {
                     // Remove old intent if exists
@@ -168,7 +419,155 @@ impl RuleSyncer {
             }
         }
 
-        Ok(actions)
+        Ok((actions, changes))
+    }
+
+    /// Remove a file this tool's rules are no longer written to
+    ///
+    /// A file left as it was written is deleted outright. Otherwise only the
+    /// rule blocks `markers` are taken out, and the file is deleted if
+    /// nothing else is left in it.
+    fn retire_file(
+        &self,
+        writer: &ProjectionWriter,
+        tool: &str,
+        path: &str,
+        checksum: &str,
+        markers: &[String],
+    ) -> Result<String> {
+        let full_path = self.root.join(path);
+        let unchanged = repo_fs::checksum::compute_file_checksum(full_path.as_ref())
+            .is_ok_and(|actual| actual == checksum);
+        if unchanged {
+            let projection =
+                Projection::file_managed(tool.to_string(), PathBuf::from(path), String::new());
+            writer.remove(&projection)
+        } else {
+            writer.remove_blocks(std::path::Path::new(path), markers)
+        }
+    }
+
+    /// Block markers of the rules recorded in `previous_args` as written to
+    /// `path`
+    ///
+    /// Entries recorded before the file was tracked per rule belong to
+    /// `rules_file`.
+    fn markers_in_file(path: &str, rules_file: &str, previous_args: &RulesArgs) -> Vec<String> {
+        previous_args
+            .rules
+            .iter()
+            .flatten()
+            .filter(|(_, entry)| entry.file.as_deref().unwrap_or(rules_file) == path)
+            .map(|(uuid, _)| uuid.clone())
+            .collect()
+    }
+
+    /// Per-rule block checksums, as recorded in the rules intent args
+    ///
+    /// Each entry also records the file the rule was written to, as given
+    /// by `rule_file`. Rules written to their own file are checksummed as
+    /// the whole file, so a change to their target paths counts as an edit.
+    fn block_checksums(
+        &self,
+        cache: &mut RenderCache,
+        rules: &[RuleFile],
+        rules_file: &str,
+        rule_file: impl Fn(&RuleFile) -> String,
+    ) -> BTreeMap<String, RuleBlock> {
+        rules
+            .iter()
+            .map(|r| {
+                let file = rule_file(r);
+                let checksum = self
+                    .rendered(cache, r, RuleFormat::of(&file, rules_file))
+                    .checksum
+                    .clone();
+                (
+                    r.uuid.to_string(),
+                    RuleBlock {
+                        id: r.id.clone(),
+                        checksum,
+                        file: Some(file),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// The changed rules that were or are now written to `path`
+    ///
+    /// Entries recorded before the file was tracked per rule belong to
+    /// `rules_file`.
+    fn sources_in_file(
+        path: &str,
+        rules_file: &str,
+        sources: &[ChangeSource],
+        previous_args: &RulesArgs,
+        block_checksums: &BTreeMap<String, RuleBlock>,
+    ) -> Vec<ChangeSource> {
+        let in_file = |entry: Option<&RuleBlock>| {
+            entry.is_some_and(|e| e.file.as_deref().unwrap_or(rules_file) == path)
+        };
+        let previous = previous_args.rules.as_ref();
+        sources
+            .iter()
+            .filter(|source| match source {
+                ChangeSource::Rule { uuid, .. } => {
+                    let key = uuid.to_string();
+                    in_file(block_checksums.get(&key))
+                        || in_file(previous.and_then(|p| p.get(&key)))
+                }
+                ChangeSource::Settings => true,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Rules whose blocks differ from those recorded in `previous_args`
+    ///
+    /// Includes rules that were added or edited, and rules recorded
+    /// previously that are no longer in the file.
+    fn changed_rules(
+        previous_args: &RulesArgs,
+        block_checksums: &BTreeMap<String, RuleBlock>,
+        rules: &[RuleFile],
+    ) -> Vec<ChangeSource> {
+        let Some(previous) = &previous_args.rules else {
+            return rules
+                .iter()
+                .map(|r| ChangeSource::Rule {
+                    uuid: r.uuid,
+                    id: r.id.clone(),
+                })
+                .collect();
+        };
+
+        let mut sources: Vec<ChangeSource> = rules
+            .iter()
+            .filter(|r| {
+                let key = r.uuid.to_string();
+                previous.get(&key).map(|p| &p.checksum)
+                    != block_checksums.get(&key).map(|c| &c.checksum)
+            })
+            .map(|r| ChangeSource::Rule {
+                uuid: r.uuid,
+                id: r.id.clone(),
+            })
+            .collect();
+
+        for (key, entry) in previous {
+            if block_checksums.contains_key(key) {
+                continue;
+            }
+            if let Ok(uuid) = key.parse() {
+                sources.push(ChangeSource::Rule {
+                    uuid,
+                    id: entry.id.clone(),
+                });
+            }
+        }
+
+        sources
     }
 
     /// Get the rules file path for a specific tool
@@ -181,14 +580,39 @@ impl RuleSyncer {
             "windsurf" => Some(".windsurfrules".to_string()),
             "claude" | "claude-desktop" => Some("CLAUDE.md".to_string()),
             "gemini" => Some("GEMINI.md".to_string()),
+            "codex" => Some("AGENTS.md".to_string()),
             "copilot" => Some(".github/copilot-instructions.md".to_string()),
             "cline" => Some(".clinerules".to_string()),
             "roo" => Some(".roorules".to_string()),
             "antigravity" => Some(".antigravityrules".to_string()),
+            // Aider reads the conventions file listed in .aider.conf.yml
+            "aider" => Some("CONVENTIONS.md".to_string()),
             // VSCode uses settings.json, not a rules file
             "vscode" => None,
-            // JetBrains, Zed, Aider, AmazonQ don't have standard rules files
-            "jetbrains" | "zed" | "aider" | "amazonq" => None,
+            // JetBrains, Zed, AmazonQ don't have standard rules files
+            "jetbrains" | "zed" | "amazonq" => None,
+            _ => None,
+        }
+    }
+
+    /// Get the directory receiving one file per rule, in place of the
+    /// rules file
+    ///
+    /// Returns None if the tool reads rules from its rules file.
+    pub fn get_rules_dir_for_tool(&self, tool: &str) -> Option<&'static str> {
+        match tool {
+            "cursor" if self.cursor_mode == CursorMode::Mdc => Some(".cursor/rules/"),
+            "windsurf" if self.windsurf_mode == WindsurfMode::Directory => Some(WINDSURF_RULES_DIR),
+            _ => None,
+        }
+    }
+
+    /// Get the directory receiving one file per rule with target paths
+    ///
+    /// Returns None if the tool reads every rule from its rules file.
+    pub fn get_scoped_rules_dir_for_tool(&self, tool: &str) -> Option<&'static str> {
+        match tool {
+            "copilot" => Some(".github/instructions/"),
             _ => None,
         }
     }
@@ -206,26 +630,81 @@ impl RuleSyncer {
     /// <!-- /repo:block:UUID -->
     /// ```
     pub fn combine_rules(&self, rules: &[RuleFile]) -> String {
+        let blocks: Vec<String> = rules.iter().map(Self::render_block).collect();
+        Self::combine_blocks(blocks.iter().map(String::as_str))
+    }
+
+    /// Join rendered rule blocks into a rules file
+    fn combine_blocks<'a>(blocks: impl Iterator<Item = &'a str>) -> String {
         let header = "# Repository Rules\n\n\
             # This file is auto-generated by repository-manager.\n\
             # Do not edit directly - modify rules in .repository/rules/registry.toml instead.\n";
 
-        let rule_content = rules
-            .iter()
-            .map(|r| {
-                format!(
-                    "<!-- repo:block:{} -->\n## {}\n\n{}\n<!-- /repo:block:{} -->",
-                    r.uuid,
-                    r.id,
-                    r.content.trim(),
-                    r.uuid
-                )
-            })
-            .collect::<Vec<_>>()
-            .join("\n\n---\n\n");
+        let rule_content = blocks.collect::<Vec<_>>().join("\n\n---\n\n");
 
         format!("{}\n\n{}", header, rule_content)
     }
+
+    /// `rule` rendered in `format`, from `cache` if it was rendered before
+    fn rendered(
+        &self,
+        cache: &mut RenderCache,
+        rule: &RuleFile,
+        format: RuleFormat,
+    ) -> Rc<RenderedRule> {
+        if let Some(rendered) = cache.get(&(rule.uuid, format)) {
+            return rendered.clone();
+        }
+
+        #[cfg(test)]
+        self.renders.fetch_add(1, Ordering::Relaxed);
+        let content = match format {
+            RuleFormat::Block => Self::render_block(rule),
+            _ => {
+                let block = self.rendered(cache, rule, RuleFormat::Block);
+                Self::render_rule_file(rule, format, &block.content)
+            }
+        };
+        let rendered = Rc::new(RenderedRule {
+            checksum: compute_checksum(&content),
+            content: content.into(),
+        });
+        cache.insert((rule.uuid, format), rendered.clone());
+        rendered
+    }
+
+    /// Render a rule written to its own file, around its rendered `block`
+    ///
+    /// MDC and Windsurf rule files attach the rule to its target paths, or
+    /// always apply it; other files are path-scoped instructions files.
+    fn render_rule_file(rule: &RuleFile, format: RuleFormat, block: &str) -> String {
+        match format {
+            RuleFormat::Mdc => render_mdc(&rule.id, &rule.paths, block),
+            RuleFormat::Windsurf => {
+                render_windsurf_rule(windsurf_trigger(&rule.tags), &rule.id, &rule.paths, block)
+            }
+            RuleFormat::Instructions => render_instructions(&rule.paths, block),
+            RuleFormat::Block => block.to_string(),
+        }
+    }
+
+    /// Number of times a rule was rendered, rather than taken from the
+    /// render cache
+    #[cfg(test)]
+    fn render_count(&self) -> usize {
+        self.renders.load(Ordering::Relaxed)
+    }
+
+    /// Render a single rule as a managed block
+    fn render_block(rule: &RuleFile) -> String {
+        format!(
+            "<!-- repo:block:{} -->\n## {}\n\n{}\n<!-- /repo:block:{} -->",
+            rule.uuid,
+            rule.id,
+            rule.content.trim(),
+            rule.uuid
+        )
+    }
 }
 
 #[cfg(test)]
@@ -308,11 +787,17 @@ mod tests {
                 uuid: uuid1,
                 id: "style".to_string(),
                 content: "Use consistent formatting".to_string(),
+                tools: vec![],
+                paths: vec![],
+                tags: vec![],
             },
             RuleFile {
                 uuid: uuid2,
                 id: "naming".to_string(),
                 content: "Use descriptive names".to_string(),
+                tools: vec![],
+                paths: vec![],
+                tags: vec![],
             },
         ];
 
@@ -354,6 +839,10 @@ mod tests {
             syncer.get_rules_file_for_tool("copilot"),
             Some(".github/copilot-instructions.md".to_string())
         );
+        assert_eq!(
+            syncer.get_rules_file_for_tool("aider"),
+            Some("CONVENTIONS.md".to_string())
+        );
         assert_eq!(syncer.get_rules_file_for_tool("vscode"), None);
         assert_eq!(syncer.get_rules_file_for_tool("unknown"), None);
     }
@@ -405,6 +894,54 @@ mod tests {
         assert!(content.contains(&rule_uuid.to_string()));
     }
 
+    #[test]
+    fn test_sync_rules_renders_shared_format_once() {
+        let dir = tempdir().unwrap();
+        let root = NormalizedPath::new(dir.path());
+        let mut registry = setup_registry(dir.path());
+        let rule = registry
+            .add_rule("code-style", "Use 4 spaces", vec![])
+            .unwrap()
+            .clone();
+
+        let syncer = RuleSyncer::new(root.clone(), false);
+        let mut ledger = Ledger::new();
+        let tools = ["claude", "gemini", "codex"].map(String::from);
+        syncer.sync_rules(&tools, &mut ledger).unwrap();
+
+        // One rule, one format: rendered once for all three tools
+        assert_eq!(syncer.render_count(), 1);
+
+        // Output and checksums are those of rendering per file
+        let rule_file = RuleFile {
+            uuid: rule.uuid,
+            id: rule.id,
+            content: rule.content,
+            tools: Vec::new(),
+            paths: Vec::new(),
+            tags: Vec::new(),
+        };
+        let expected = syncer.combine_rules(std::slice::from_ref(&rule_file));
+        for (tool, file) in [
+            ("claude", "CLAUDE.md"),
+            ("gemini", "GEMINI.md"),
+            ("codex", "AGENTS.md"),
+        ] {
+            let content = fs::read_to_string(root.join(file).as_ref()).unwrap();
+            assert_eq!(content, expected);
+
+            let intent = ledger.find_by_rule(&format!("rules:{}", tool))[0];
+            assert!(matches!(
+                &intent.projections()[0].kind,
+                ProjectionKind::FileManaged { checksum } if *checksum == compute_checksum(&expected)
+            ));
+            assert_eq!(
+                intent.args["rules"][rule_file.uuid.to_string()]["checksum"],
+                compute_checksum(&RuleSyncer::render_block(&rule_file))
+            );
+        }
+    }
+
     #[test]
     fn test_sync_rules_dry_run() {
         let dir = tempdir().unwrap();
@@ -10,12 +10,16 @@
 //!
 //! Includes backup/restore functionality for tool configurations.
 
+use super::engine::{ChangeSource, FileChange};
 use crate::backup::BackupManager;
 use crate::ledger::{Intent, Ledger, Projection, ProjectionKind};
-use crate::projection::compute_checksum;
+use crate::projection::{ProjectionWriter, compute_checksum};
 use crate::{Error, Result};
 use repo_fs::NormalizedPath;
-use repo_tools::{Rule, SyncContext, ToolDispatcher};
+use repo_tools::{
+    CursorMode, Rule, SyncContext, ToolDispatcher, ToolIntegration, WindsurfMode,
+    validate_synced_output,
+};
 use serde_json::Value;
 use std::path::PathBuf;
 use uuid::Uuid;
@@ -59,7 +63,7 @@ impl ToolSyncer {
     /// * `dry_run` - If true, simulate changes without modifying the filesystem
     pub fn new(root: NormalizedPath, dry_run: bool) -> Self {
         let backup_manager = BackupManager::new(root.clone());
-        let dispatcher = ToolDispatcher::new();
+        let dispatcher = ToolDispatcher::from_repository(&root);
         Self {
             root,
             dry_run,
@@ -69,12 +73,38 @@ impl ToolSyncer {
         }
     }
 
+    /// Read tool definitions from `root`'s `.repository/` instead of the
+    /// sync root's, e.g. from a container when syncing one of its worktrees.
+    ///
+    /// Replaces the Cursor and Windsurf modes, so set those afterwards.
+    pub fn with_definitions_from(mut self, root: &NormalizedPath) -> Self {
+        self.dispatcher = ToolDispatcher::from_repository(root);
+        self
+    }
+
     /// Set the resolved MCP server configuration from extensions.
     pub fn with_mcp_servers(mut self, servers: Value) -> Self {
         self.mcp_servers = Some(servers);
         self
     }
 
+    /// Set where Cursor rules are written.
+    pub fn with_cursor_mode(mut self, mode: CursorMode) -> Self {
+        self.dispatcher = self.dispatcher.with_cursor_mode(mode);
+        self
+    }
+
+    /// Set where Windsurf rules are written.
+    pub fn with_windsurf_mode(mut self, mode: WindsurfMode) -> Self {
+        self.dispatcher = self.dispatcher.with_windsurf_mode(mode);
+        self
+    }
+
+    /// Problems found while loading and registering tool definitions
+    pub fn warnings(&self) -> &[String] {
+        self.dispatcher.warnings()
+    }
+
     /// Check if a backup exists for a tool
     pub fn has_backup(&self, tool_name: &str) -> bool {
         self.backup_manager.has_backup(tool_name)
@@ -98,14 +128,63 @@ impl ToolSyncer {
     ///
     /// A list of action descriptions taken during the sync.
     pub fn sync_tool(&self, tool_name: &str, ledger: &mut Ledger) -> Result<Vec<String>> {
+        self.sync_tool_with_changes(tool_name, ledger)
+            .map(|(actions, _)| actions)
+    }
+
+    /// Sync a tool, also returning the files written
+    ///
+    /// Tool config files are generated from the tool's settings, so every
+    /// change is attributed to [`ChangeSource::Settings`].
+    pub fn sync_tool_with_changes(
+        &self,
+        tool_name: &str,
+        ledger: &mut Ledger,
+    ) -> Result<(Vec<String>, Vec<FileChange>)> {
         let mut actions = Vec::new();
+        let mut changes = Vec::new();
         let intent_id = format!("tool:{}", tool_name);
 
         // Check if intent already exists
         let existing = self.get_intents_by_id(ledger, &intent_id);
         if !existing.is_empty() {
-            actions.push(format!("Tool {} already synced", tool_name));
-            return Ok(actions);
+            let current = self
+                .dispatcher
+                .get_integration(tool_name)
+                .map(|integration| Self::tool_files(integration.as_ref(), tool_name))
+                .unwrap_or_default();
+            let moved: Vec<PathBuf> = existing
+                .iter()
+                .flat_map(|intent| intent.projections())
+                .map(|p| p.file.clone())
+                .filter(|file| !current.contains(&file.to_string_lossy().into_owned()))
+                .collect();
+            if moved.is_empty() {
+                actions.push(format!("Tool {} already synced", tool_name));
+                return Ok((actions, changes));
+            }
+
+            // The tool writes elsewhere now (e.g. Cursor switched to
+            // `.cursor/rules/*.mdc`): take our content out of the old files
+            // and sync afresh
+            let uuids: Vec<Uuid> = existing.iter().map(|i| i.uuid).collect();
+            let writer = ProjectionWriter::new(self.root.clone(), self.dry_run);
+            let markers = [Self::initial_rule(tool_name).id];
+            for file in moved {
+                let action = writer.remove_blocks(&file, &markers)?;
+                changes.push(FileChange {
+                    tool: tool_name.to_string(),
+                    file: file.to_string_lossy().into_owned(),
+                    action: action.clone(),
+                    sources: vec![ChangeSource::Settings],
+                });
+                actions.push(action);
+            }
+            if !self.dry_run {
+                for uuid in uuids {
+                    ledger.remove_intent(uuid);
+                }
+            }
         }
 
         // Ensure tool config files exist (creates them if needed)
@@ -113,7 +192,7 @@ impl ToolSyncer {
 
         if config_files.is_empty() {
             actions.push(format!("No config files for tool {}", tool_name));
-            return Ok(actions);
+            return Ok((actions, changes));
         }
 
         // Create projections for each config file
@@ -126,14 +205,21 @@ impl ToolSyncer {
                 kind: ProjectionKind::FileManaged { checksum },
             });
 
-            if self.dry_run {
-                actions.push(format!("[dry-run] Would create {}", file_path));
+            let action = if self.dry_run {
+                format!("[dry-run] Would create {}", file_path)
             } else {
                 // Write the file using symlink-safe write
                 let full_path = self.root.join(file_path);
                 safe_write(&full_path, content)?;
-                actions.push(format!("Created {}", file_path));
-            }
+                format!("Created {}", file_path)
+            };
+            changes.push(FileChange {
+                tool: tool_name.to_string(),
+                file: file_path.clone(),
+                action: action.clone(),
+                sources: vec![ChangeSource::Settings],
+            });
+            actions.push(action);
         }
 
         // Create intent with projections
@@ -147,7 +233,7 @@ impl ToolSyncer {
             actions.push(format!("Added intent {}", intent_id));
         }
 
-        Ok(actions)
+        Ok((actions, changes))
     }
 
     /// Remove a tool, deleting its projections
@@ -230,13 +316,25 @@ impl ToolSyncer {
             }
         }
 
+        // Files shared with the user only lose our managed content
+        let integration = self.dispatcher.get_integration(tool_name);
+        let shared = integration
+            .as_ref()
+            .map(|i| i.shared_files())
+            .unwrap_or_default();
+
         // Delete the files and remove intents
         for uuid in intents {
             if let Some(intent) = ledger.get_intent(uuid) {
                 for projection in intent.projections() {
-                    let file_path = self.root.join(projection.file.to_string_lossy().as_ref());
+                    let file = projection.file.to_string_lossy();
+                    let file_path = self.root.join(file.as_ref());
 
-                    if self.dry_run {
+                    if shared.iter().any(|s| *s == file) {
+                        if self.dry_run {
+                            actions.push(format!("[dry-run] Would update {}", file_path));
+                        }
+                    } else if self.dry_run {
                         actions.push(format!("[dry-run] Would delete {}", file_path));
                     } else if file_path.exists() {
                         std::fs::remove_file(file_path.as_ref())?;
@@ -251,6 +349,16 @@ impl ToolSyncer {
             }
         }
 
+        if let Some(integration) = integration.filter(|_| !shared.is_empty() && !self.dry_run) {
+            integration.remove_managed(&self.root)?;
+            for file in &shared {
+                actions.push(format!(
+                    "Removed managed content from {}",
+                    self.root.join(file)
+                ));
+            }
+        }
+
         Ok(actions)
     }
 
@@ -325,39 +433,32 @@ impl ToolSyncer {
     fn ensure_tool_config_files(&self, tool_name: &str) -> Vec<(String, String)> {
         if let Some(integration) = self.dispatcher.get_integration(tool_name) {
             let context = self.make_sync_context();
-            let initial_rule = Rule {
-                id: format!("{}-init", tool_name),
-                content: format!(
-                    "# {} Configuration\n\nManaged by Repository Manager.\n",
-                    tool_name
-                ),
-            };
 
             if !self.dry_run
-                && let Err(e) = integration.sync(&context, &[initial_rule])
+                && let Err(e) = integration
+                    .sync(&context, &[Self::initial_rule(tool_name)])
+                    .and_then(|()| validate_synced_output(integration.as_ref(), &self.root))
             {
                 tracing::warn!("Failed to sync tool {}: {}", tool_name, e);
                 return vec![];
             }
 
-            integration
-                .config_locations()
+            Self::tool_files(integration.as_ref(), tool_name)
                 .into_iter()
-                .filter(|loc| !loc.is_directory)
-                .map(|loc| {
-                    let full_path = self.root.join(&loc.path);
+                .map(|path| {
+                    let full_path = self.root.join(&path);
                     let content = if full_path.exists() {
                         match std::fs::read_to_string(full_path.as_ref()) {
                             Ok(c) => c,
                             Err(e) => {
-                                tracing::warn!("Failed to read {}: {}", loc.path, e);
+                                tracing::warn!("Failed to read {}: {}", path, e);
                                 String::new()
                             }
                         }
                     } else {
                         String::new()
                     };
-                    (loc.path, content)
+                    (path, content)
                 })
                 .collect()
         } else {
@@ -365,6 +466,29 @@ impl ToolSyncer {
         }
     }
 
+    /// The rule written to a tool's config files on its first sync
+    fn initial_rule(tool_name: &str) -> Rule {
+        Rule {
+            id: format!("{}-init", tool_name),
+            content: format!(
+                "# {} Configuration\n\nManaged by Repository Manager.\n",
+                tool_name
+            ),
+            paths: vec![],
+        }
+    }
+
+    /// Files written by syncing the initial rule, relative to the root
+    fn tool_files(integration: &dyn ToolIntegration, tool_name: &str) -> Vec<String> {
+        integration
+            .config_locations()
+            .into_iter()
+            .filter(|loc| !loc.is_directory)
+            .map(|loc| loc.path)
+            .chain(integration.rule_files(&[Self::initial_rule(tool_name)]))
+            .collect()
+    }
+
     /// Sync a tool with specific rules
     ///
     /// This method uses repo-tools integrations to sync rules to a tool's
@@ -399,42 +523,54 @@ impl ToolSyncer {
             return Ok(actions);
         }
 
-        // Sync rules using the integration
-        integration.sync(&context, rules).map_err(|e| {
-            Error::SyncError {
+        // Sync rules using the integration, then check what it wrote
+        integration
+            .sync(&context, rules)
+            .and_then(|()| validate_synced_output(integration.as_ref(), &self.root))
+            .map_err(|e| Error::SyncError {
                 message: format!("Tool sync failed for {}: {}", tool_name, e),
-            }
-        })?;
-
-        // Create projections for ledger
+            })?;
+
+        // Create projections for ledger, including files written per rule
+        // inside directory locations
+        let files = integration
+            .config_locations()
+            .into_iter()
+            .filter(|loc| !loc.is_directory)
+            .map(|loc| loc.path)
+            .chain(integration.rule_files(rules));
         let mut projections = Vec::new();
-        for loc in integration.config_locations() {
-            if loc.is_directory {
-                continue;
-            }
-            let full_path = self.root.join(&loc.path);
+        for file in files {
+            let full_path = self.root.join(&file);
             if full_path.exists() {
                 let content = std::fs::read_to_string(full_path.as_ref())?;
                 let checksum = compute_checksum(&content);
                 projections.push(Projection {
                     tool: tool_name.to_string(),
-                    file: PathBuf::from(&loc.path),
+                    file: PathBuf::from(&file),
                     kind: ProjectionKind::FileManaged { checksum },
                 });
-                actions.push(format!("Synced {}", loc.path));
+                actions.push(format!("Synced {}", file));
             }
         }
 
-        // Update or create intent
-        let existing = self.get_intents_by_id(ledger, &intent_id);
+        // Replace any existing intent so the projections match this sync
+        let existing: Vec<Uuid> = self
+            .get_intents_by_id(ledger, &intent_id)
+            .iter()
+            .map(|i| i.uuid)
+            .collect();
+        let mut intent = Intent::new(intent_id.clone(), serde_json::json!({}));
+        for projection in projections {
+            intent.add_projection(projection);
+        }
+        ledger.add_intent(intent);
         if existing.is_empty() {
-            let mut intent = Intent::new(intent_id.clone(), serde_json::json!({}));
-            for projection in projections {
-                intent.add_projection(projection);
-            }
-            ledger.add_intent(intent);
             actions.push(format!("Added intent {}", intent_id));
         } else {
+            for uuid in existing {
+                ledger.remove_intent(uuid);
+            }
             actions.push(format!("Updated {}", tool_name));
         }
 
@@ -656,6 +792,17 @@ mod tests {
         // Read-only: only verify path, not content
     }
 
+    #[test]
+    fn test_get_tool_config_files_codex() {
+        let dir = tempdir().unwrap();
+        let root = NormalizedPath::new(dir.path());
+        let syncer = ToolSyncer::new(root, false);
+
+        let files = syncer.get_tool_config_files("codex");
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].0, "AGENTS.md");
+    }
+
     #[test]
     fn test_has_tool() {
         let dir = tempdir().unwrap();
@@ -737,6 +884,51 @@ mod tests {
         assert!(file_path.exists());
     }
 
+    #[test]
+    fn test_sync_tool_with_rules_records_rule_files() {
+        let dir = tempdir().unwrap();
+        let root = NormalizedPath::new(dir.path());
+        let syncer = ToolSyncer::new(root.clone(), false);
+        let mut ledger = Ledger::new();
+
+        let rule = |paths: Vec<String>| Rule {
+            id: "python".to_string(),
+            content: "Use type hints".to_string(),
+            paths,
+        };
+        syncer
+            .sync_tool_with_rules("copilot", &[rule(vec!["**/*.py".into()])], &mut ledger)
+            .unwrap();
+
+        let files = |ledger: &Ledger| -> Vec<String> {
+            let intents = ledger.find_by_rule("tool:copilot");
+            assert_eq!(intents.len(), 1);
+            intents[0]
+                .projections()
+                .iter()
+                .map(|p| p.file.to_string_lossy().into_owned())
+                .collect()
+        };
+        assert_eq!(
+            files(&ledger),
+            vec![
+                ".github/copilot-instructions.md",
+                ".github/instructions/python.instructions.md"
+            ]
+        );
+
+        // Re-syncing replaces the intent, dropping the removed file
+        syncer
+            .sync_tool_with_rules("copilot", &[rule(vec![])], &mut ledger)
+            .unwrap();
+        assert_eq!(files(&ledger), vec![".github/copilot-instructions.md"]);
+        assert!(
+            !root
+                .join(".github/instructions/python.instructions.md")
+                .exists()
+        );
+    }
+
     #[test]
     fn test_sync_tool_already_synced() {
         let dir = tempdir().unwrap();
@@ -791,6 +983,28 @@ mod tests {
         assert!(!file_path.exists());
     }
 
+    #[test]
+    fn test_remove_tool_keeps_user_aider_config() {
+        let dir = tempdir().unwrap();
+        let root = NormalizedPath::new(dir.path());
+        let syncer = ToolSyncer::new(root.clone(), false);
+        let mut ledger = Ledger::new();
+        let user_config = "model: sonnet # preferred\nread:\n  - NOTES.md\n";
+        std::fs::write(dir.path().join(".aider.conf.yml"), user_config).unwrap();
+
+        syncer.sync_tool("aider", &mut ledger).unwrap();
+        let actions = syncer.remove_tool("aider", &mut ledger).unwrap();
+
+        assert!(
+            actions
+                .iter()
+                .any(|a| a.contains("Removed managed content"))
+        );
+        assert!(ledger.intents().is_empty());
+        let config = std::fs::read_to_string(dir.path().join(".aider.conf.yml")).unwrap();
+        assert_eq!(config, user_config);
+    }
+
     #[test]
     fn test_remove_tool_not_found() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,204 @@
+//! Init templates
+//!
+//! A template is a [`ConfigBundle`] that a new repository starts from: the
+//! tools, presets, rules and MCP servers an organization uses by default.
+//! Any bundle written by `repo export` works as a template; a hand-written
+//! one usually sets a `description` and a `[config]` table:
+//!
+//! ```toml
+//! version = 1
+//! description = "Rust service with the team's review rules"
+//!
+//! [config]
+//! tools = ["claude", "cursor"]
+//!
+//! [config.presets."env:rust"]
+//!
+//! [rule_files]
+//! "review.md" = "Every public function needs a doc comment."
+//! ```
+//!
+//! Templates are stored as `<name>.toml` in the templates directory
+//! (`~/.config/repo-manager/templates/` on Linux), or fetched from a git
+//! repository with `<url>#<name>`, which reads `<name>.toml` from the root
+//! of the clone (`template.toml` without a name).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::bundle::ConfigBundle;
+use crate::{Error, Result};
+
+/// File read from the root of a template repository fetched without a name
+const DEFAULT_TEMPLATE: &str = "template";
+
+/// A named configuration bundle for `repo init`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    /// Name the template was loaded under
+    pub name: String,
+    /// The configuration it applies
+    pub bundle: ConfigBundle,
+}
+
+impl Template {
+    /// One-line description, if the template has one
+    pub fn description(&self) -> Option<&str> {
+        self.bundle.description.as_deref()
+    }
+}
+
+fn template_error(name: &str, message: impl Into<String>) -> Error {
+    Error::Template {
+        name: name.to_string(),
+        message: message.into(),
+    }
+}
+
+/// The user's templates directory, `<config_dir>/repo-manager/templates`
+pub fn templates_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("repo-manager").join("templates"))
+}
+
+/// Templates in `dir`, sorted by name
+///
+/// Files that are not valid templates are skipped with a warning.
+pub fn list_templates(dir: &Path) -> Vec<Template> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut templates: Vec<Template> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_string();
+            match read_template(&name, &path) {
+                Ok(template) => Some(template),
+                Err(e) => {
+                    tracing::warn!("Skipping template {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .collect();
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
+/// Load the template `source` names
+///
+/// `source` is either the name of a template in `dir` or a git URL,
+/// optionally followed by `#<name>`.
+///
+/// # Errors
+///
+/// Returns [`Error::Template`] if the template does not exist or cannot be
+/// fetched, or [`Error::InvalidBundle`] if it is not a valid bundle.
+pub fn load_template(dir: &Path, source: &str) -> Result<Template> {
+    if repo_extensions::installer::is_git_url(source) {
+        return fetch_template(source);
+    }
+    if source.is_empty() || source.contains(['/', '\\']) || source.starts_with('.') {
+        return Err(template_error(source, "not a template name or git URL"));
+    }
+    let path = dir.join(format!("{}.toml", source));
+    if !path.is_file() {
+        return Err(template_error(
+            source,
+            format!("no {}.toml in {}", source, dir.display()),
+        ));
+    }
+    read_template(source, &path)
+}
+
+fn read_template(name: &str, path: &Path) -> Result<Template> {
+    let content = fs::read_to_string(path)?;
+    Ok(Template {
+        name: name.to_string(),
+        bundle: ConfigBundle::parse(&content)?,
+    })
+}
+
+/// Clone the repository at `source` (`<url>[#<name>]`) and read the
+/// template from it
+fn fetch_template(source: &str) -> Result<Template> {
+    let (url, name) = match source.rsplit_once('#') {
+        Some((url, name)) if !name.is_empty() => (url, name),
+        _ => (source.trim_end_matches('#'), DEFAULT_TEMPLATE),
+    };
+
+    let checkout = std::env::temp_dir().join(format!("repo-template-{}", std::process::id()));
+    if checkout.exists() {
+        fs::remove_dir_all(&checkout)?;
+    }
+    let result = git2::Repository::clone(url, &checkout)
+        .map_err(|e| template_error(name, format!("cannot clone {}: {}", url, e.message())))
+        .and_then(|_| load_template(&checkout, name));
+    if let Err(e) = fs::remove_dir_all(&checkout) {
+        tracing::warn!("Failed to remove {}: {}", checkout.display(), e);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const BACKEND: &str = r#"version = 1
+description = "Backend service"
+
+[config]
+tools = ["claude"]
+
+[rule_files]
+"style.md" = "Use snake_case."
+"#;
+
+    #[test]
+    fn test_list_and_load_templates() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("backend-service.toml"), BACKEND).unwrap();
+        fs::write(dir.path().join("empty.toml"), "version = 1\n").unwrap();
+        fs::write(dir.path().join("broken.toml"), "version = [").unwrap();
+        fs::write(dir.path().join("notes.md"), "not a template").unwrap();
+
+        let templates = list_templates(dir.path());
+        let names: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["backend-service", "empty"]);
+        assert_eq!(templates[0].description(), Some("Backend service"));
+        assert_eq!(templates[1].description(), None);
+
+        let template = load_template(dir.path(), "backend-service").unwrap();
+        assert_eq!(template.bundle.rule_files["style.md"], "Use snake_case.");
+
+        let err = load_template(dir.path(), "frontend").unwrap_err();
+        assert!(matches!(err, Error::Template { .. }), "{err}");
+        assert!(load_template(dir.path(), "../backend-service").is_err());
+    }
+
+    #[test]
+    fn test_fetch_template_from_git() {
+        let source = TempDir::new().unwrap();
+        let repo = git2::Repository::init(source.path()).unwrap();
+        fs::write(source.path().join("backend.toml"), BACKEND).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("backend.toml")).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "Add", &tree, &[])
+            .unwrap();
+
+        let url = format!("file://{}", source.path().display());
+        let template = load_template(Path::new("unused"), &format!("{}#backend", url)).unwrap();
+        assert_eq!(template.name, "backend");
+        assert_eq!(template.description(), Some("Backend service"));
+
+        // Without a name, template.toml is read
+        assert!(matches!(
+            load_template(Path::new("unused"), &url),
+            Err(Error::Template { .. })
+        ));
+    }
+}
@@ -0,0 +1,203 @@
+//! Tests for adopting hand-written rules across several repositories
+
+use pretty_assertions::assert_eq;
+use repo_core::adopt::{self, AdoptAnalysis};
+use repo_core::{Mode, RuleRegistry, SyncEngine};
+use repo_fs::NormalizedPath;
+use repo_test_utils::git::fake_git_dir;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const STYLE: &str = "## Code Style\n\nUse rustfmt with default settings.\n\n";
+const COMMITS: &str = "## Commits\n\nWrite conventional commit messages.\n\n";
+
+/// Three repositories sharing a style rule, two sharing a commit rule, and
+/// one with a different rule under the same `Commits` heading
+fn fixture_repos(dir: &Path) -> Vec<(String, PathBuf)> {
+    let files: [(&str, &[(&str, String)]); 3] = [
+        (
+            "api",
+            &[(
+                "CLAUDE.md",
+                format!("# API\n\n{STYLE}{COMMITS}## Database\n\nAlways add a migration.\n"),
+            )],
+        ),
+        (
+            "web",
+            &[
+                ("CLAUDE.md", STYLE.to_string()),
+                (
+                    ".cursorrules",
+                    format!("{STYLE}{COMMITS}## Frontend\n\nPrefer function components.\n"),
+                ),
+            ],
+        ),
+        (
+            "cli",
+            &[(
+                "CLAUDE.md",
+                format!("{STYLE}## Commits\n\nSign off every commit.\n"),
+            )],
+        ),
+    ];
+
+    files
+        .iter()
+        .map(|(name, files)| {
+            let root = dir.join(name);
+            fs::create_dir_all(root.join(".repository")).unwrap();
+            fake_git_dir(&root);
+            fs::write(
+                root.join(".repository/config.toml"),
+                "tools = [\"claude\", \"cursor\"]\n\n[core]\nmode = \"standard\"\n",
+            )
+            .unwrap();
+            for (file, content) in *files {
+                fs::write(root.join(file), content).unwrap();
+            }
+            (name.to_string(), root)
+        })
+        .collect()
+}
+
+fn analyze_all(repos: &[(String, PathBuf)]) -> Vec<(String, AdoptAnalysis)> {
+    repos
+        .iter()
+        .map(|(name, root)| (name.clone(), adopt::analyze(root).unwrap()))
+        .collect()
+}
+
+#[test]
+fn test_analysis_reports_candidates() {
+    let temp = TempDir::new().unwrap();
+    let repos = fixture_repos(temp.path());
+
+    let analysis = adopt::analyze(&repos[1].1).unwrap();
+    assert_eq!(analysis.files, vec!["CLAUDE.md", ".cursorrules"]);
+    let summary: Vec<(&str, Option<&str>, &str)> = analysis
+        .candidates
+        .iter()
+        .map(|c| (c.id.as_str(), c.heading.as_deref(), c.source.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("code-style", Some("Code Style"), "CLAUDE.md"),
+            ("code-style", Some("Code Style"), ".cursorrules"),
+            ("commits", Some("Commits"), ".cursorrules"),
+            ("frontend", Some("Frontend"), ".cursorrules"),
+        ]
+    );
+    assert_eq!(
+        analysis.candidates[0].content_hash,
+        analysis.candidates[1].content_hash
+    );
+    assert_eq!(
+        analysis.candidates[3].size,
+        "Prefer function components.".len()
+    );
+}
+
+#[test]
+fn test_plan_clusters_rules_across_repos() {
+    let temp = TempDir::new().unwrap();
+    let repos = fixture_repos(temp.path());
+    let plan = adopt::plan(&analyze_all(&repos));
+
+    let clusters: Vec<(&str, bool, Vec<&str>)> = plan
+        .clusters
+        .iter()
+        .map(|c| {
+            (
+                c.id.as_str(),
+                c.shared,
+                c.members.iter().map(|m| m.repo.as_str()).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        clusters,
+        vec![
+            ("code-style", true, vec!["api", "web", "cli"]),
+            ("commits", true, vec!["api", "web"]),
+            ("database", false, vec!["api"]),
+            ("frontend", false, vec!["web"]),
+            // Same heading as the shared rule, different content
+            ("commits-2", false, vec!["cli"]),
+        ]
+    );
+
+    let shared: Vec<&str> = plan.shared.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(shared, vec!["code-style", "commits"]);
+    let residual: Vec<(&str, Vec<&str>)> = plan
+        .residual
+        .iter()
+        .map(|(repo, rules)| (repo.as_str(), rules.iter().map(|r| r.id.as_str()).collect()))
+        .collect();
+    assert_eq!(
+        residual,
+        vec![
+            ("api", vec!["database"]),
+            ("cli", vec!["commits-2"]),
+            ("web", vec!["frontend"]),
+        ]
+    );
+}
+
+#[test]
+fn test_applying_plan_preserves_effective_content() {
+    let temp = TempDir::new().unwrap();
+    let repos = fixture_repos(temp.path());
+    let analyses = analyze_all(&repos);
+    let plan = adopt::plan(&analyses);
+    let shared = plan.shared_bundle();
+
+    for ((name, root), (_, before)) in repos.iter().zip(&analyses) {
+        let report = adopt::adopt(root, Some(&shared)).unwrap();
+        assert_eq!(report.imported, vec!["code-style", "commits"], "{}", name);
+
+        // Shared rules are not duplicated; residuals are adopted
+        let registry = RuleRegistry::load(root.join(".repository/rules/registry.toml")).unwrap();
+        assert_eq!(
+            registry.all_rules().len(),
+            2 + plan.residual[name].len(),
+            "{}",
+            name
+        );
+
+        SyncEngine::new(NormalizedPath::new(root), Mode::Standard)
+            .unwrap()
+            .sync()
+            .unwrap();
+
+        // Every section of every original file survives in that file
+        for candidate in &before.candidates {
+            let synced = fs::read_to_string(root.join(&candidate.source)).unwrap();
+            assert!(
+                synced.contains(&candidate.content),
+                "{}: {} lost '{}'",
+                name,
+                candidate.source,
+                candidate.id
+            );
+        }
+
+        // Other repositories' residual rules don't leak in
+        let claude = fs::read_to_string(root.join("CLAUDE.md")).unwrap();
+        assert!(claude.contains("<!-- repo:block:"), "{}: not managed", name);
+        for rule in plan
+            .residual
+            .iter()
+            .filter(|(r, _)| *r != name)
+            .flat_map(|(_, r)| r)
+        {
+            assert!(
+                !claude.contains(&rule.content),
+                "{}: leaked {}",
+                name,
+                rule.id
+            );
+        }
+    }
+}
@@ -70,6 +70,24 @@ config = "strict"
         assert!(manifest.rules.is_empty());
     }
 
+    #[test]
+    fn test_manifest_parse_verify_writes() {
+        let manifest = Manifest::parse("[core]\nverify_writes = true\n").unwrap();
+        assert!(manifest.core.verify_writes);
+
+        // Off by default and omitted when serialized
+        let manifest = Manifest::parse("").unwrap();
+        assert!(!manifest.core.verify_writes);
+        assert!(!manifest.to_toml().contains("verify_writes"));
+    }
+
+    #[test]
+    fn test_manifest_merge_verify_writes_any_layer_enables() {
+        let mut base = Manifest::parse("[core]\nverify_writes = true\n").unwrap();
+        base.merge(&Manifest::parse("").unwrap());
+        assert!(base.core.verify_writes);
+    }
+
     #[test]
     fn test_manifest_empty() {
         let manifest = Manifest::empty();
@@ -827,6 +845,8 @@ mod runtime_context_tests {
             tools: vec!["cargo".to_string(), "python".to_string()],
             rules: vec!["no-unsafe".to_string()],
             extensions: HashMap::new(),
+            tool_settings: HashMap::new(),
+            ..Default::default()
         }
     }
 
@@ -879,6 +899,8 @@ mod runtime_context_tests {
             tools: vec![],
             rules: vec![],
             extensions: HashMap::new(),
+            tool_settings: HashMap::new(),
+            ..Default::default()
         };
         let context = RuntimeContext::from_resolved(&config);
 
@@ -0,0 +1,251 @@
+//! Tests for repository context detection
+
+use repo_core::context::detect;
+use repo_core::{ContextKind, Mode};
+use repo_fs::NormalizedPath;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Filesystem entries to create for a detection case.
+enum Entry {
+    Dir(&'static str),
+    File(&'static str, &'static str),
+}
+
+use Entry::{Dir, File};
+
+const STANDARD_CONFIG: &str = "[core]\nmode = \"standard\"\n";
+const WORKTREES_CONFIG: &str = "[core]\nmode = \"worktrees\"\n";
+
+struct Case {
+    name: &'static str,
+    layout: &'static [Entry],
+    start: &'static str,
+    kind: ContextKind,
+    root: Option<&'static str>,
+    mode: Option<Mode>,
+    worktree: Option<&'static str>,
+    has_config: bool,
+    trail_contains: &'static str,
+}
+
+fn build(root: &Path, layout: &[Entry]) {
+    for entry in layout {
+        match entry {
+            Dir(path) => fs::create_dir_all(root.join(path)).unwrap(),
+            File(path, content) => {
+                let path = root.join(path);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(path, content).unwrap();
+            }
+        }
+    }
+}
+
+fn cases() -> Vec<Case> {
+    vec![
+        Case {
+            name: "standard repo",
+            layout: &[
+                Dir(".git"),
+                File(".repository/config.toml", STANDARD_CONFIG),
+                Dir("src"),
+            ],
+            start: "",
+            kind: ContextKind::StandardRepo,
+            root: Some(""),
+            mode: Some(Mode::Standard),
+            worktree: None,
+            has_config: true,
+            trail_contains: ".git marker in root",
+        },
+        Case {
+            name: "subdirectory of standard repo",
+            layout: &[
+                Dir(".git"),
+                File(".repository/config.toml", STANDARD_CONFIG),
+                Dir("src/deep"),
+            ],
+            start: "src/deep",
+            kind: ContextKind::StandardRepo,
+            root: Some(""),
+            mode: Some(Mode::Standard),
+            worktree: None,
+            has_config: true,
+            trail_contains: "no .repository/",
+        },
+        Case {
+            name: "container root",
+            layout: &[
+                Dir(".gt"),
+                File(".repository/config.toml", WORKTREES_CONFIG),
+            ],
+            start: "",
+            kind: ContextKind::ContainerRoot,
+            root: Some(""),
+            mode: Some(Mode::Worktrees),
+            worktree: None,
+            has_config: true,
+            trail_contains: "at container root",
+        },
+        Case {
+            name: "inside feature worktree",
+            layout: &[
+                Dir(".gt"),
+                File(".repository/config.toml", WORKTREES_CONFIG),
+                File("feature/.git", "gitdir: ../.gt/worktrees/feature\n"),
+                File("feature/.repository/config.toml", WORKTREES_CONFIG),
+                Dir("feature/src"),
+            ],
+            start: "feature/src",
+            kind: ContextKind::Worktree,
+            root: Some(""),
+            mode: Some(Mode::Worktrees),
+            worktree: Some("feature"),
+            has_config: true,
+            trail_contains: "treating it as a worktree",
+        },
+        Case {
+            name: "nested repo resolves to innermost",
+            layout: &[
+                Dir(".git"),
+                File(".repository/config.toml", STANDARD_CONFIG),
+                Dir("inner/.git"),
+                File("inner/.repository/config.toml", STANDARD_CONFIG),
+                Dir("inner/src"),
+            ],
+            start: "inner/src",
+            kind: ContextKind::StandardRepo,
+            root: Some("inner"),
+            mode: Some(Mode::Standard),
+            worktree: None,
+            has_config: true,
+            trail_contains: "found .repository/",
+        },
+        Case {
+            name: "nested git checkout without .repository",
+            layout: &[
+                Dir(".git"),
+                File(".repository/config.toml", STANDARD_CONFIG),
+                Dir("vendor/lib/.git"),
+            ],
+            start: "vendor/lib",
+            kind: ContextKind::StandardRepo,
+            root: Some(""),
+            mode: Some(Mode::Standard),
+            worktree: None,
+            has_config: true,
+            trail_contains: "git marker present but no .repository/",
+        },
+        Case {
+            name: "no repo",
+            layout: &[Dir("plain")],
+            start: "plain",
+            kind: ContextKind::NotARepo,
+            root: None,
+            mode: None,
+            worktree: None,
+            has_config: false,
+            trail_contains: "run 'repo init'",
+        },
+        Case {
+            name: "partial init",
+            layout: &[Dir(".repository")],
+            start: "",
+            kind: ContextKind::StandardRepo,
+            root: Some(""),
+            mode: Some(Mode::Standard),
+            worktree: None,
+            has_config: false,
+            trail_contains: "partial init",
+        },
+        Case {
+            name: "worktree alias in config",
+            layout: &[File(
+                ".repository/config.toml",
+                "[core]\nmode = \"worktree\"\n",
+            )],
+            start: "",
+            kind: ContextKind::ContainerRoot,
+            root: Some(""),
+            mode: Some(Mode::Worktrees),
+            worktree: None,
+            has_config: true,
+            trail_contains: "from config",
+        },
+    ]
+}
+
+#[test]
+fn test_detect_table() {
+    for case in cases() {
+        let temp = TempDir::new().unwrap();
+        build(temp.path(), case.layout);
+
+        let info = detect(&temp.path().join(case.start));
+        let ctx = format!("case '{}':\n{}", case.name, info.explain());
+
+        assert_eq!(info.kind, case.kind, "{}", ctx);
+        assert_eq!(
+            info.root,
+            case.root.map(|r| NormalizedPath::new(temp.path().join(r))),
+            "{}",
+            ctx
+        );
+        assert_eq!(info.mode, case.mode, "{}", ctx);
+        assert_eq!(info.worktree.as_deref(), case.worktree, "{}", ctx);
+        assert_eq!(info.config_path.is_some(), case.has_config, "{}", ctx);
+        assert_eq!(
+            info.is_partial(),
+            info.is_repo() && !case.has_config,
+            "{}",
+            ctx
+        );
+        assert!(
+            info.trail
+                .iter()
+                .any(|step| step.contains(case.trail_contains)),
+            "{}\nexpected trail to contain '{}'",
+            ctx,
+            case.trail_contains
+        );
+    }
+}
+
+#[test]
+fn test_detect_container_is_set_in_worktrees_mode() {
+    let temp = TempDir::new().unwrap();
+    build(
+        temp.path(),
+        &[
+            Dir(".gt"),
+            File(".repository/config.toml", WORKTREES_CONFIG),
+            Dir("main"),
+        ],
+    );
+
+    let info = detect(&temp.path().join("main"));
+    assert_eq!(info.container, Some(NormalizedPath::new(temp.path())));
+    assert_eq!(info.worktree.as_deref(), Some("main"));
+}
+
+#[test]
+fn test_detect_invalid_mode_falls_back_to_standard() {
+    let temp = TempDir::new().unwrap();
+    build(
+        temp.path(),
+        &[File(
+            ".repository/config.toml",
+            "[core]\nmode = \"bogus\"\n",
+        )],
+    );
+
+    let info = detect(temp.path());
+    assert_eq!(info.mode, Some(Mode::Standard));
+    assert!(
+        info.trail
+            .iter()
+            .any(|s| s.contains("could not resolve mode"))
+    );
+}
@@ -87,6 +87,7 @@ fn load_coding_standards_rule() -> Rule {
     Rule {
         id: "coding-standards".to_string(),
         content,
+        paths: vec![],
     }
 }
 
@@ -251,42 +252,48 @@ fn test_golden_file_aider_output_matches_expected() {
         .sync_tool_with_rules("aider", &[rule], &mut ledger)
         .unwrap();
 
-    // Read the generated .aider.conf.yml file
-    let generated_path = temp.path().join(".aider.conf.yml");
+    // Rules go to CONVENTIONS.md as Markdown managed blocks
+    let conventions = fs::read_to_string(temp.path().join("CONVENTIONS.md")).unwrap();
+    assert!(conventions.contains("<!-- repo:block:coding-standards -->"));
+    assert!(conventions.contains("<!-- /repo:block:coding-standards -->"));
     assert!(
-        generated_path.exists(),
-        ".aider.conf.yml should be created after sync"
+        conventions.contains("rustfmt"),
+        "Rule content must be present"
     );
-    let generated = fs::read_to_string(&generated_path).unwrap();
-
-    // Structural checks: YAML comment block markers
     assert!(
-        generated.contains("# repo:block:coding-standards"),
-        "Aider output must contain YAML comment open marker"
+        conventions.contains("clippy"),
+        "Rule content must be present"
     );
+
+    let expected = load_expected_output("aider", "CONVENTIONS.md");
+    assert_eq!(
+        normalize(&conventions).trim(),
+        normalize(&strip_provenance_header(&expected)).trim(),
+        "Generated CONVENTIONS.md should match the expected golden file"
+    );
+
+    // .aider.conf.yml only points Aider at the conventions file
+    let generated_path = temp.path().join(".aider.conf.yml");
     assert!(
-        generated.contains("# /repo:block:coding-standards"),
-        "Aider output must contain YAML comment close marker"
+        generated_path.exists(),
+        ".aider.conf.yml should be created after sync"
     );
+    let generated = fs::read_to_string(&generated_path).unwrap();
     assert!(
         !generated.contains("<!-- repo:block:"),
         "Aider YAML must NOT contain HTML comment markers"
     );
     assert!(
-        generated.contains("rustfmt"),
-        "Rule content must be present"
-    );
-    assert!(
-        generated.contains("clippy"),
-        "Rule content must be present"
+        !generated.contains("rustfmt"),
+        "Rule content belongs in CONVENTIONS.md"
     );
 
-    // YAML validity: the file should parse as valid YAML
-    let yaml_result: Result<serde_yaml::Value, _> = serde_yaml::from_str(&generated);
-    assert!(
-        yaml_result.is_ok(),
-        "Generated .aider.conf.yml must be valid YAML: {:?}",
-        yaml_result.err()
+    // Validate that the generated file is valid YAML reading the conventions
+    let yaml: serde_yaml::Value = serde_yaml::from_str(&generated)
+        .unwrap_or_else(|e| panic!("Generated .aider.conf.yml must be valid YAML: {}", e));
+    assert_eq!(
+        yaml["read"],
+        serde_yaml::Value::Sequence(vec!["CONVENTIONS.md".into()])
     );
 
     // Full comparison against golden file (provenance headers stripped)
@@ -430,7 +437,7 @@ fn test_expected_outputs_contain_managed_block_markers() {
     let checks = vec![
         ("claude/CLAUDE.md", "repo:block:coding-standards"),
         ("cursor/.cursorrules", "repo:block:coding-standards"),
-        ("aider/.aider.conf.yml", "repo:block:coding-standards"),
+        ("aider/CONVENTIONS.md", "repo:block:coding-standards"),
     ];
 
     for (rel_path, marker) in checks {
@@ -451,7 +458,11 @@ fn test_expected_outputs_contain_managed_block_markers() {
 fn test_expected_outputs_have_matching_open_close_markers() {
     let expected_dir = fixtures_dir().join("expected");
 
-    let files = vec!["claude/CLAUDE.md", "cursor/.cursorrules"];
+    let files = vec![
+        "claude/CLAUDE.md",
+        "cursor/.cursorrules",
+        "aider/CONVENTIONS.md",
+    ];
 
     for rel_path in files {
         let path = expected_dir.join(rel_path);
@@ -481,6 +492,7 @@ fn test_expected_outputs_have_provenance_headers() {
         "cursor/.cursorrules",
         "claude/CLAUDE.md",
         "aider/.aider.conf.yml",
+        "aider/CONVENTIONS.md",
     ];
 
     for rel_path in files {
@@ -741,6 +741,8 @@ fn test_runtime_context_edge_cases() {
         tools: vec![],
         rules: vec![],
         extensions: HashMap::new(),
+        tool_settings: HashMap::new(),
+        ..Default::default()
     };
 
     let context = RuntimeContext::from_resolved(&config);
@@ -760,6 +762,8 @@ fn test_runtime_context_edge_cases() {
         tools: vec![],
         rules: vec![],
         extensions: HashMap::new(),
+        tool_settings: HashMap::new(),
+        ..Default::default()
     };
 
     let context2 = RuntimeContext::from_resolved(&config2);
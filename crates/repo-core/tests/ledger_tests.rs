@@ -389,3 +389,26 @@ fn test_ledger_default_version() {
     let serialized = toml::to_string(&ledger).unwrap();
     assert!(serialized.contains("version = \"1.0\""));
 }
+
+#[test]
+fn test_ledger_save_and_modify_with_verify_writes() {
+    let _verify = repo_fs::RobustnessConfig::default()
+        .with_verify_writes(true)
+        .enter();
+
+    let dir = tempdir().unwrap();
+    let ledger_path = dir.path().join("ledger.toml");
+
+    let mut ledger = Ledger::new();
+    ledger.add_intent(Intent::new("rule:verified".to_string(), json!({})));
+    ledger.save(&ledger_path).unwrap();
+    assert!(!dir.path().join("ledger.toml.tmp").exists());
+
+    Ledger::modify(&ledger_path, |ledger| {
+        ledger.add_intent(Intent::new("rule:second".to_string(), json!({})));
+    })
+    .unwrap();
+
+    let loaded = Ledger::load(&ledger_path).unwrap();
+    assert_eq!(loaded.intents().len(), 2);
+}
@@ -0,0 +1,136 @@
+//! Tests for converting repositories between standard and worktrees mode
+
+use repo_core::sync::{CheckStatus, SyncEngine};
+use repo_core::{Error, MigrationStep, Mode, ModeMigration, detect_mode};
+use repo_fs::NormalizedPath;
+use repo_test_utils::git::real_git_repo_with_commit;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// A committed standard-mode repository with synced cursor config
+fn setup_synced_repo(root: &Path) {
+    real_git_repo_with_commit(root);
+    fs::create_dir_all(root.join(".repository")).unwrap();
+    fs::write(
+        root.join(".repository/config.toml"),
+        "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n",
+    )
+    .unwrap();
+    let engine = SyncEngine::new(NormalizedPath::new(root), Mode::Standard).unwrap();
+    assert!(engine.sync().unwrap().success);
+    git(root, &["add", "."]);
+    git(root, &["commit", "-m", "Configure repository"]);
+}
+
+fn check_status(root: &NormalizedPath, mode: Mode) -> CheckStatus {
+    SyncEngine::new(root.clone(), mode)
+        .unwrap()
+        .check()
+        .unwrap()
+        .status
+}
+
+#[test]
+fn test_migrate_standard_to_worktrees_and_back() {
+    let temp = TempDir::new().unwrap();
+    let root = NormalizedPath::new(temp.path());
+    setup_synced_repo(temp.path());
+    let branch = git(temp.path(), &["rev-parse", "--abbrev-ref", "HEAD"]);
+
+    // Planning changes nothing
+    let migration = ModeMigration::plan(&root, Mode::Worktrees).unwrap();
+    assert_eq!(migration.from, Mode::Standard);
+    assert_eq!(
+        migration.steps[0],
+        MigrationStep::Move {
+            from: PathBuf::from(".git"),
+            to: PathBuf::from(".gt")
+        }
+    );
+    assert!(migration.steps.contains(&MigrationStep::Move {
+        from: PathBuf::from(".cursorrules"),
+        to: PathBuf::from("main/.cursorrules")
+    }));
+    assert!(migration.steps.iter().any(|step| matches!(
+        step,
+        MigrationStep::RebaseLedger { projections, .. } if *projections > 0
+    )));
+    assert!(temp.path().join(".git").is_dir());
+
+    migration.apply().unwrap();
+    let main = temp.path().join("main");
+    assert!(temp.path().join(".gt").is_dir());
+    assert!(main.join(".git").is_file());
+    assert!(main.join(".cursorrules").is_file());
+    assert!(temp.path().join(".repository/config.toml").is_file());
+    assert_eq!(detect_mode(&root).unwrap(), Mode::Worktrees);
+    assert_eq!(check_status(&root, Mode::Worktrees), CheckStatus::Healthy);
+    assert_eq!(git(&main, &["rev-parse", "--abbrev-ref", "HEAD"]), branch);
+    assert_eq!(git(&main, &["status", "--porcelain"]), "");
+
+    // Converting back is refused while a feature worktree exists
+    let feature = temp.path().join("feature");
+    git(
+        &main,
+        &[
+            "worktree",
+            "add",
+            "-b",
+            "feature",
+            feature.to_str().unwrap(),
+        ],
+    );
+    let err = ModeMigration::plan(&root, Mode::Standard).unwrap_err();
+    assert!(matches!(err, Error::ModeMigration { ref message } if message.contains("feature")));
+    git(&main, &["worktree", "remove", feature.to_str().unwrap()]);
+
+    let migration = ModeMigration::plan(&root, Mode::Standard).unwrap();
+    migration.apply().unwrap();
+    assert!(temp.path().join(".git").is_dir());
+    assert!(!temp.path().join(".gt").exists());
+    assert!(!main.exists());
+    assert!(temp.path().join(".cursorrules").is_file());
+    assert_eq!(detect_mode(&root).unwrap(), Mode::Standard);
+    assert_eq!(check_status(&root, Mode::Standard), CheckStatus::Healthy);
+    assert_eq!(
+        git(temp.path(), &["rev-parse", "--abbrev-ref", "HEAD"]),
+        branch
+    );
+    assert_eq!(git(temp.path(), &["ls-files", "--deleted"]), "");
+
+    // Already in the requested mode
+    let migration = ModeMigration::plan(&root, Mode::Standard).unwrap();
+    assert!(migration.steps.is_empty());
+}
+
+#[test]
+fn test_migrate_to_standard_refuses_to_overwrite_container_files() {
+    let temp = TempDir::new().unwrap();
+    let root = NormalizedPath::new(temp.path());
+    setup_synced_repo(temp.path());
+    ModeMigration::plan(&root, Mode::Worktrees)
+        .unwrap()
+        .apply()
+        .unwrap();
+    fs::write(temp.path().join("README.md"), "stray").unwrap();
+
+    let err = ModeMigration::plan(&root, Mode::Standard).unwrap_err();
+    assert!(matches!(err, Error::ModeMigration { ref message } if message.contains("README.md")));
+}
@@ -1,9 +1,13 @@
 //! Tests for Mode abstraction and backends
 
-use repo_core::backend::{ModeBackend, StandardBackend, WorktreeBackend};
+use repo_core::ModeMigration;
+use repo_core::backend::{
+    BranchListOptions, BranchPruneOptions, ModeBackend, StandardBackend, WorktreeBackend,
+};
 use repo_core::mode::Mode;
+use repo_core::sync::{CheckStatus, SyncEngine};
 use repo_fs::NormalizedPath;
-use repo_test_utils::git::fake_git_dir;
+use repo_test_utils::git::{fake_git_dir, real_git_repo_with_commit};
 use std::fs;
 use tempfile::TempDir;
 
@@ -289,3 +293,352 @@ fn test_worktree_backend_config_shared_across_worktrees() {
         "Working directories must be different for different worktrees"
     );
 }
+
+// =============================================================================
+// Auto-sync on switch tests
+// =============================================================================
+
+/// A real worktree container whose shared config enables cursor
+fn setup_synced_container() -> TempDir {
+    let temp = TempDir::new().unwrap();
+    real_git_repo_with_commit(temp.path());
+    fs::create_dir_all(temp.path().join(".repository")).unwrap();
+    fs::write(
+        temp.path().join(".repository/config.toml"),
+        "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n",
+    )
+    .unwrap();
+    ModeMigration::plan(&NormalizedPath::new(temp.path()), Mode::Worktrees)
+        .unwrap()
+        .apply()
+        .unwrap();
+    temp
+}
+
+#[test]
+fn test_worktree_backend_auto_sync_on_create_and_switch() {
+    let temp = setup_synced_container();
+    let container = NormalizedPath::new(temp.path());
+    let backend = WorktreeBackend::new(container.clone())
+        .unwrap()
+        .with_auto_sync(true);
+
+    backend.create_branch("feature", None).unwrap();
+    let feature = container.join("feature");
+    assert!(feature.join(".cursorrules").exists());
+    // The ledger is per worktree; nothing is written to the container
+    assert!(
+        container
+            .join(".repository/worktrees/feature/ledger.toml")
+            .exists()
+    );
+    assert!(!container.join(".cursorrules").exists());
+    let engine = SyncEngine::for_worktree(container.clone(), feature).unwrap();
+    assert_eq!(engine.check().unwrap().status, CheckStatus::Healthy);
+
+    // Switching to a branch without a worktree creates and syncs it
+    let other = backend.switch_branch("other").unwrap();
+    assert!(other.join(".cursorrules").exists());
+}
+
+#[test]
+fn test_worktree_backend_without_auto_sync_leaves_worktree_bare() {
+    let temp = setup_synced_container();
+    let container = NormalizedPath::new(temp.path());
+    let backend = WorktreeBackend::new(container.clone()).unwrap();
+
+    let feature = backend.switch_branch("feature").unwrap();
+    assert!(feature.join("README.md").exists());
+    assert!(!feature.join(".cursorrules").exists());
+    assert!(!container.join(".repository/worktrees").exists());
+}
+
+#[test]
+fn test_worktree_backend_auto_sync_failure_does_not_fail_switch() {
+    let temp = setup_synced_container();
+    let container = NormalizedPath::new(temp.path());
+    fs::write(
+        container.join(".repository/config.toml").to_native(),
+        "tools = [",
+    )
+    .unwrap();
+    let backend = WorktreeBackend::new(container.clone())
+        .unwrap()
+        .with_auto_sync(true);
+
+    let feature = backend.switch_branch("feature").unwrap();
+    assert!(feature.join("README.md").exists());
+    assert!(!feature.join(".cursorrules").exists());
+}
+
+// =============================================================================
+// Branch status tests
+// =============================================================================
+
+fn git(dir: &std::path::Path, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Commit once in each of the worktrees of `branches`, then merge them all
+/// into the main worktree
+fn commit_and_merge(container: &NormalizedPath, branches: &[&str]) {
+    for name in branches {
+        git(
+            &container.join(name).to_native(),
+            &["commit", "--allow-empty", "-m", name],
+        );
+    }
+    let mut args = vec!["merge", "--no-ff", "-m", "Merge"];
+    args.extend(branches);
+    git(&container.join("main").to_native(), &args);
+}
+
+#[test]
+fn test_standard_backend_lists_upstream_and_dirty_status() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    real_git_repo_with_commit(root);
+    git(root, &["branch", "-M", "main"]);
+    git(root, &["branch", "other"]);
+    // Track a remote copy of main, then commit past it
+    git(
+        root,
+        &[
+            "remote",
+            "add",
+            "origin",
+            "https://example.invalid/repo.git",
+        ],
+    );
+    git(root, &["update-ref", "refs/remotes/origin/main", "HEAD"]);
+    git(root, &["branch", "--set-upstream-to=origin/main", "main"]);
+    git(root, &["commit", "--allow-empty", "-m", "Local"]);
+    fs::write(root.join("scratch.txt"), "").unwrap();
+
+    let backend = StandardBackend::new(NormalizedPath::new(root)).unwrap();
+    let branches = backend.list_branches().unwrap();
+    let main = branches.iter().find(|b| b.name == "main").unwrap();
+    assert_eq!((main.ahead, main.behind), (Some(1), Some(0)));
+    assert_eq!(main.dirty, Some(true));
+    let other = branches.iter().find(|b| b.name == "other").unwrap();
+    assert_eq!((other.ahead, other.behind), (None, None));
+    // Not checked out, so there is no working tree to check
+    assert_eq!(other.dirty, None);
+}
+
+#[test]
+fn test_worktree_backend_lists_dirty_worktrees() {
+    let temp = setup_synced_container();
+    let container = NormalizedPath::new(temp.path());
+    let backend = WorktreeBackend::new(container.clone()).unwrap();
+    backend.create_branch("feature", None).unwrap();
+    backend.create_branch("other", None).unwrap();
+    fs::write(container.join("feature/notes.txt").to_native(), "wip").unwrap();
+
+    let branches = backend.list_branches().unwrap();
+    let dirty = |name: &str| branches.iter().find(|b| b.name == name).unwrap().dirty;
+    assert_eq!(dirty("feature"), Some(true));
+    assert_eq!(dirty("other"), Some(false));
+
+    let fast = backend
+        .list_branches_with(BranchListOptions { fast: true })
+        .unwrap();
+    assert!(fast.iter().all(|b| b.dirty.is_none()));
+}
+
+#[test]
+fn test_standard_backend_prunes_merged_branches() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    real_git_repo_with_commit(root);
+    git(root, &["branch", "-M", "main"]);
+    git(root, &["checkout", "-b", "merged"]);
+    git(root, &["commit", "--allow-empty", "-m", "Merged"]);
+    git(root, &["checkout", "main"]);
+    git(root, &["merge", "--no-ff", "-m", "Merge", "merged"]);
+    // Fresh from main, with no commits of its own yet
+    git(root, &["branch", "fresh"]);
+    git(root, &["checkout", "-b", "unmerged"]);
+    git(root, &["commit", "--allow-empty", "-m", "Unmerged"]);
+    git(root, &["checkout", "main"]);
+
+    let backend = StandardBackend::new(NormalizedPath::new(root)).unwrap();
+    let names = |options| {
+        backend
+            .prune_merged_branches(options)
+            .unwrap()
+            .into_iter()
+            .map(|b| b.name)
+            .collect::<Vec<_>>()
+    };
+    let dry_run = BranchPruneOptions {
+        dry_run: true,
+        ..BranchPruneOptions::default()
+    };
+    assert_eq!(names(dry_run), vec!["merged"]);
+    assert!(git(root, &["branch", "--list", "merged"]).contains("merged"));
+
+    assert_eq!(names(BranchPruneOptions::default()), vec!["merged"]);
+    let remaining = git(root, &["branch", "--format=%(refname:short)"]);
+    assert_eq!(
+        remaining.lines().collect::<Vec<_>>(),
+        vec!["fresh", "main", "unmerged"]
+    );
+}
+
+#[test]
+fn test_worktree_backend_prunes_merged_worktrees() {
+    let temp = setup_synced_container();
+    let container = NormalizedPath::new(temp.path());
+    let backend = WorktreeBackend::new(container.clone()).unwrap();
+    for name in ["feature", "other", "ahead"] {
+        backend.create_branch(name, None).unwrap();
+    }
+    commit_and_merge(&container, &["feature", "other"]);
+    fs::write(container.join("feature/notes.txt").to_native(), "wip").unwrap();
+    git(
+        &container.join("ahead").to_native(),
+        &["commit", "--allow-empty", "-m", "Ahead"],
+    );
+    // Fresh from main, with no commits of its own yet
+    backend.create_branch("fresh", None).unwrap();
+
+    // The dirty feature worktree is kept, the unmerged one not considered
+    let merged = backend
+        .prune_merged_branches(BranchPruneOptions {
+            dry_run: true,
+            ..BranchPruneOptions::default()
+        })
+        .unwrap();
+    let summary: Vec<(&str, bool)> = merged
+        .iter()
+        .map(|b| (b.name.as_str(), b.is_pruned()))
+        .collect();
+    assert_eq!(summary, vec![("feature", false), ("other", true)]);
+    assert!(container.join("other").exists());
+
+    backend
+        .prune_merged_branches(BranchPruneOptions::default())
+        .unwrap();
+    assert!(!container.join("other").exists());
+    assert!(container.join("feature").exists());
+
+    backend
+        .prune_merged_branches(BranchPruneOptions {
+            force: true,
+            ..BranchPruneOptions::default()
+        })
+        .unwrap();
+    assert!(!container.join("feature").exists());
+    assert!(container.join("fresh").exists());
+    let refs = git(
+        &container.join("main").to_native(),
+        &["branch", "--format=%(refname:short)"],
+    );
+    assert_eq!(
+        refs.lines().collect::<Vec<_>>(),
+        vec!["ahead", "fresh", "main"]
+    );
+}
+
+// =============================================================================
+// Hook tests
+// =============================================================================
+
+/// Hooks that log each branch event to `hooks.log` in the repository root,
+/// and refuse to create or delete a branch named `blocked`
+const LOGGING_HOOKS: &str = r#"
+[[hooks]]
+event = "pre-branch-create"
+command = "sh"
+args = ["-c", "if [ \"$REPO_BRANCH\" = blocked ]; then echo 'branch is blocked' >&2; exit 1; fi"]
+
+[[hooks]]
+event = "post-branch-create"
+command = "sh"
+args = ["-c", "echo \"$REPO_EVENT $REPO_BRANCH $REPO_WORKTREE\" >> \"$REPO_ROOT/hooks.log\""]
+
+[[hooks]]
+event = "pre-branch-delete"
+command = "sh"
+args = ["-c", "if [ \"$REPO_BRANCH\" = keep ]; then echo 'branch is kept' >&2; exit 1; fi"]
+
+[[hooks]]
+event = "post-branch-delete"
+command = "sh"
+args = ["-c", "echo \"$REPO_EVENT $REPO_BRANCH\" >> \"$REPO_ROOT/hooks.log\""]
+"#;
+
+#[test]
+fn test_standard_backend_runs_branch_hooks() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    real_git_repo_with_commit(root);
+    fs::create_dir_all(root.join(".repository")).unwrap();
+    fs::write(root.join(".repository/hooks.toml"), LOGGING_HOOKS).unwrap();
+    let backend = StandardBackend::new(NormalizedPath::new(root)).unwrap();
+
+    let err = backend.create_branch("blocked", None).unwrap_err();
+    assert!(err.to_string().contains("branch is blocked"), "{err}");
+    assert!(git(root, &["branch", "--list", "blocked"]).is_empty());
+
+    backend.create_branch("feature", None).unwrap();
+    backend.create_branch("keep", None).unwrap();
+    assert!(backend.delete_branch("keep").is_err());
+    assert!(!git(root, &["branch", "--list", "keep"]).is_empty());
+    backend.delete_branch("feature").unwrap();
+
+    let log = fs::read_to_string(root.join("hooks.log")).unwrap();
+    assert_eq!(
+        log.lines().map(str::trim_end).collect::<Vec<_>>(),
+        vec![
+            "post-branch-create feature",
+            "post-branch-create keep",
+            "post-branch-delete feature"
+        ]
+    );
+}
+
+#[test]
+fn test_worktree_backend_runs_branch_hooks() {
+    let temp = setup_synced_container();
+    let container = NormalizedPath::new(temp.path());
+    fs::write(temp.path().join(".repository/hooks.toml"), LOGGING_HOOKS).unwrap();
+    let backend = WorktreeBackend::new(container.clone()).unwrap();
+
+    assert!(backend.create_branch("blocked", None).is_err());
+    assert!(!container.join("blocked").exists());
+
+    backend.create_branch("feature", None).unwrap();
+    backend.create_branch("keep", None).unwrap();
+    commit_and_merge(&container, &["feature", "keep"]);
+
+    // A failing pre-delete hook skips the branch when pruning
+    let merged = backend
+        .prune_merged_branches(BranchPruneOptions::default())
+        .unwrap();
+    let keep = merged.iter().find(|b| b.name == "keep").unwrap();
+    assert!(keep.skipped.as_deref().unwrap().contains("branch is kept"));
+    assert!(container.join("keep").exists());
+    assert!(!container.join("feature").exists());
+
+    let log = fs::read_to_string(temp.path().join("hooks.log")).unwrap();
+    let feature = container.join("feature").to_native();
+    assert_eq!(
+        log.lines().collect::<Vec<_>>(),
+        vec![
+            format!("post-branch-create feature {}", feature.display()),
+            format!(
+                "post-branch-create keep {}",
+                container.join("keep").to_native().display()
+            ),
+            "post-branch-delete feature".to_string(),
+        ]
+    );
+}
@@ -0,0 +1,71 @@
+//! Performance guard for sync on large repositories
+//!
+//! The thresholds are generous, far above the time sync takes even in a
+//! debug build, so they only trip on accidental quadratic behaviour, not
+//! on slow CI machines. Use `cargo bench -p repo-core` for measurements.
+
+use std::time::{Duration, Instant};
+
+use repo_core::{CheckStatus, Mode, SyncEngine};
+use repo_fs::NormalizedPath;
+use repo_test_utils::fixture::large_repo;
+use repo_tools::ToolRegistry;
+
+/// Rules in the guarded fixture
+const RULES: usize = 200;
+
+/// Upper bound for a first sync and for a check of the fixture
+const SYNC_LIMIT: Duration = Duration::from_secs(30);
+const CHECK_LIMIT: Duration = Duration::from_secs(10);
+
+#[test]
+fn test_sync_of_200_rules_stays_fast() {
+    let registry = ToolRegistry::with_builtins();
+    // Cline's config file and its registry rules directory share a path,
+    // so syncing registry rules to it fails
+    let tools: Vec<&str> = registry
+        .list()
+        .into_iter()
+        .filter(|t| *t != "cline")
+        .collect();
+    let repo = large_repo(RULES, &tools);
+    let engine = SyncEngine::new(NormalizedPath::new(repo.root()), Mode::Standard).unwrap();
+
+    let start = Instant::now();
+    let report = engine.sync().unwrap();
+    let sync_time = start.elapsed();
+    assert!(report.success, "sync failed: {:?}", report.errors);
+    assert!(
+        sync_time < SYNC_LIMIT,
+        "sync of {} rules took {:?}, over {:?}",
+        RULES,
+        sync_time,
+        SYNC_LIMIT
+    );
+
+    let start = Instant::now();
+    let check = engine.check().unwrap();
+    let check_time = start.elapsed();
+    // Rules written into a tool's own config file change the checksum its
+    // tool intent recorded, so the fixture reads as drifted, not broken
+    assert_ne!(check.status, CheckStatus::Broken, "{:?}", check.messages);
+    assert!(
+        check_time < CHECK_LIMIT,
+        "check of {} rules took {:?}, over {:?}",
+        RULES,
+        check_time,
+        CHECK_LIMIT
+    );
+
+    // A sync with nothing to do must not be slower than the first one
+    let start = Instant::now();
+    engine.sync().unwrap();
+    let resync_time = start.elapsed();
+    assert!(
+        resync_time < SYNC_LIMIT,
+        "resync of {} rules took {:?}, over {:?}",
+        RULES,
+        resync_time,
+        SYNC_LIMIT
+    );
+}
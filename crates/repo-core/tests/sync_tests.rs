@@ -2,8 +2,9 @@
 
 use pretty_assertions::assert_eq;
 use repo_core::Mode;
+use repo_core::history::{History, Operation};
 use repo_core::ledger::{Intent, Ledger, Projection};
-use repo_core::sync::{CheckReport, CheckStatus, DriftItem, SyncEngine};
+use repo_core::sync::{ChangeSource, CheckReport, CheckStatus, DriftItem, SyncEngine};
 use repo_fs::NormalizedPath;
 use serde_json::json;
 use std::fs;
@@ -458,6 +459,73 @@ mode = "standard"
     );
 }
 
+#[test]
+fn test_preview_tool_leaves_repository_untouched() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+    let repo_dir = temp.path().join(".repository");
+    fs::create_dir_all(repo_dir.join("rules")).unwrap();
+    fs::write(repo_dir.join("config.toml"), "tools = [\"claude\"]\n").unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    assert!(engine.sync().unwrap().success);
+    let synced = fs::read_to_string(temp.path().join("CLAUDE.md")).unwrap();
+    assert!(engine.preview_tool("claude").unwrap().is_empty());
+
+    let mut registry = repo_core::RuleRegistry::new(repo_dir.join("rules/registry.toml"));
+    let rule_uuid = registry
+        .add_rule("style", "Prefer small functions", vec![])
+        .unwrap()
+        .uuid;
+
+    let previews = engine.preview_tool("claude").unwrap();
+    assert_eq!(previews.len(), 1);
+    assert_eq!(previews[0].file, "CLAUDE.md");
+    assert_eq!(previews[0].current.as_deref(), Some(synced.as_str()));
+    assert!(
+        previews[0]
+            .synced
+            .as_deref()
+            .unwrap()
+            .contains("Prefer small functions")
+    );
+    assert_eq!(previews[0].block_changes().added, [rule_uuid.to_string()]);
+
+    // Nothing was written
+    assert_eq!(
+        fs::read_to_string(temp.path().join("CLAUDE.md")).unwrap(),
+        synced
+    );
+}
+
+#[test]
+fn test_check_reports_invalid_utf8_as_corrupt() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+    let repo_dir = temp.path().join(".repository");
+    fs::create_dir_all(&repo_dir).unwrap();
+    fs::write(repo_dir.join("config.toml"), "tools = [\"claude\"]\n").unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    assert!(engine.sync().unwrap().success);
+
+    // A tool appended Latin-1 text after the managed block
+    let claude_md = temp.path().join("CLAUDE.md");
+    let mut content = fs::read(&claude_md).unwrap();
+    let offset = content.len() + 4;
+    content.extend_from_slice(b"\nCaf\xe9\n");
+    fs::write(&claude_md, &content).unwrap();
+
+    let report = engine.check().unwrap();
+    assert_eq!(report.status, CheckStatus::Drifted);
+    assert_eq!(report.corrupt.len(), 1);
+    assert_eq!(report.corrupt[0].file, "CLAUDE.md");
+    assert_eq!(
+        report.corrupt[0].description,
+        format!("Invalid UTF-8 at byte {}", offset)
+    );
+}
+
 #[test]
 fn test_sync_reads_tools_from_config_using_manifest() {
     // GAP-021: SyncEngine should use typed Manifest parsing instead of raw toml::Value
@@ -498,3 +566,500 @@ mode = "standard"
         report.actions
     );
 }
+
+#[test]
+fn test_sync_attributes_changes_to_edited_rules() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+
+    let repo_dir = temp.path().join(".repository");
+    let rules_dir = repo_dir.join("rules");
+    fs::create_dir_all(&rules_dir).unwrap();
+    fs::write(
+        repo_dir.join("config.toml"),
+        "tools = [\"cursor\", \"claude\", \"gemini\"]\n\n[core]\nmode = \"standard\"\n",
+    )
+    .unwrap();
+
+    let mut registry = repo_core::RuleRegistry::new(rules_dir.join("registry.toml"));
+    registry.add_rule("style", "Use 4 spaces", vec![]).unwrap();
+    let python = registry
+        .add_rule("python", "Use type hints", vec![])
+        .unwrap()
+        .uuid;
+    let docs = registry
+        .add_rule("docs", "Document public APIs", vec![])
+        .unwrap()
+        .uuid;
+    registry.get_rule_mut(python).unwrap().tools = vec!["cursor".into(), "claude".into()];
+    registry.get_rule_mut(docs).unwrap().tools = vec!["gemini".into()];
+    registry.save().unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    let report = engine.sync().unwrap();
+    assert!(report.success, "Sync should succeed: {:?}", report.errors);
+    assert!(
+        report
+            .changes_by_source()
+            .iter()
+            .any(|(source, _)| **source == ChangeSource::Settings)
+    );
+
+    registry
+        .update_rule(python, "Use type hints everywhere")
+        .unwrap();
+    registry
+        .update_rule(docs, "Document all public APIs")
+        .unwrap();
+
+    let report = engine.sync().unwrap();
+    assert!(report.success, "Sync should succeed: {:?}", report.errors);
+
+    let mut grouped: Vec<(String, Vec<String>)> = report
+        .changes_by_source()
+        .into_iter()
+        .map(|(source, changes)| {
+            let mut files: Vec<String> = changes.iter().map(|c| c.file.clone()).collect();
+            files.sort();
+            (source.to_string(), files)
+        })
+        .collect();
+    grouped.sort();
+
+    // The unchanged "style" rule and the already-synced settings are absent
+    assert_eq!(
+        grouped,
+        vec![
+            ("rule docs".to_string(), vec!["GEMINI.md".to_string()]),
+            (
+                "rule python".to_string(),
+                vec![".cursorrules".to_string(), "CLAUDE.md".to_string()]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_sync_writes_copilot_path_scoped_instructions() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+
+    let repo_dir = temp.path().join(".repository");
+    let rules_dir = repo_dir.join("rules");
+    fs::create_dir_all(&rules_dir).unwrap();
+    fs::write(
+        repo_dir.join("config.toml"),
+        "tools = [\"copilot\"]\n\n[core]\nmode = \"standard\"\n",
+    )
+    .unwrap();
+
+    let mut registry = repo_core::RuleRegistry::new(rules_dir.join("registry.toml"));
+    registry.add_rule("style", "Use 4 spaces", vec![]).unwrap();
+    let python = registry
+        .add_rule("python", "Use type hints", vec![])
+        .unwrap()
+        .uuid;
+    registry.get_rule_mut(python).unwrap().paths = vec!["**/*.py".into()];
+    registry.save().unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    let report = engine.sync().unwrap();
+    assert!(report.success, "Sync should succeed: {:?}", report.errors);
+
+    let main = fs::read_to_string(temp.path().join(".github/copilot-instructions.md")).unwrap();
+    assert!(main.contains("Use 4 spaces"));
+    assert!(!main.contains("Use type hints"));
+
+    let scoped_path = temp
+        .path()
+        .join(".github/instructions/python.instructions.md");
+    let scoped = fs::read_to_string(&scoped_path).unwrap();
+    assert!(scoped.starts_with("---\napplyTo: \"**/*.py\"\n---\n"));
+    assert!(scoped.contains("Use type hints"));
+    assert!(engine.check().unwrap().missing.is_empty());
+
+    // A deleted instructions file is reported as missing
+    fs::remove_file(&scoped_path).unwrap();
+    let missing: Vec<String> = engine
+        .check()
+        .unwrap()
+        .missing
+        .into_iter()
+        .map(|m| m.file)
+        .collect();
+    assert_eq!(missing, vec![".github/instructions/python.instructions.md"]);
+    engine.fix().unwrap();
+    assert!(scoped_path.exists());
+
+    // Removing the rule removes its instructions file and ledger entry
+    registry.remove_rule(python).unwrap();
+    let report = engine.sync().unwrap();
+    assert!(report.success, "Sync should succeed: {:?}", report.errors);
+    assert!(!scoped_path.exists());
+    assert!(engine.check().unwrap().missing.is_empty());
+}
+
+#[test]
+fn test_sync_migrates_cursor_rules_to_mdc() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+
+    let repo_dir = temp.path().join(".repository");
+    let rules_dir = repo_dir.join("rules");
+    fs::create_dir_all(&rules_dir).unwrap();
+    let config = "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n";
+    fs::write(repo_dir.join("config.toml"), config).unwrap();
+
+    let mut registry = repo_core::RuleRegistry::new(rules_dir.join("registry.toml"));
+    registry.add_rule("style", "Use 4 spaces", vec![]).unwrap();
+    let python = registry
+        .add_rule("python", "Use type hints", vec![])
+        .unwrap()
+        .uuid;
+    registry.get_rule_mut(python).unwrap().paths = vec!["*.py".into()];
+    registry.save().unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    let report = engine.sync().unwrap();
+    assert!(report.success, "Sync should succeed: {:?}", report.errors);
+    let legacy = temp.path().join(".cursorrules");
+    assert!(
+        fs::read_to_string(&legacy)
+            .unwrap()
+            .contains("Use 4 spaces")
+    );
+
+    // Switching to MDC moves every rule into its own file
+    fs::write(
+        repo_dir.join("config.toml"),
+        format!("{config}\n[tool_settings.cursor]\nformat = \"mdc\"\n"),
+    )
+    .unwrap();
+    let report = engine.sync().unwrap();
+    assert!(report.success, "Sync should succeed: {:?}", report.errors);
+
+    assert!(!legacy.exists(), ".cursorrules should be removed");
+    let style = fs::read_to_string(temp.path().join(".cursor/rules/style.mdc")).unwrap();
+    assert!(style.starts_with("---\ndescription: \"style\"\nalwaysApply: true\n---\n"));
+    assert!(style.contains("Use 4 spaces"));
+    let python = fs::read_to_string(temp.path().join(".cursor/rules/python.mdc")).unwrap();
+    assert!(python.contains("globs: \"*.py\"\nalwaysApply: false\n"));
+
+    let check = engine.check().unwrap();
+    assert_eq!(check.status, CheckStatus::Healthy, "{:?}", check);
+
+    // Syncing again changes nothing
+    let report = engine.sync().unwrap();
+    assert!(report.changes.is_empty(), "{:?}", report.changes);
+}
+
+#[test]
+fn test_sync_mdc_migration_keeps_hand_written_cursorrules() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+
+    let repo_dir = temp.path().join(".repository");
+    let rules_dir = repo_dir.join("rules");
+    fs::create_dir_all(&rules_dir).unwrap();
+    let config = "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n";
+    fs::write(repo_dir.join("config.toml"), config).unwrap();
+
+    let mut registry = repo_core::RuleRegistry::new(rules_dir.join("registry.toml"));
+    registry.add_rule("style", "Use 4 spaces", vec![]).unwrap();
+    registry.save().unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    engine.sync().unwrap();
+
+    let legacy = temp.path().join(".cursorrules");
+    let mut content = fs::read_to_string(&legacy).unwrap();
+    content.push_str("\nPrefer small functions.\n");
+    fs::write(&legacy, content).unwrap();
+
+    fs::write(
+        repo_dir.join("config.toml"),
+        format!("{config}\n[tool_settings.cursor]\nformat = \"mdc\"\n"),
+    )
+    .unwrap();
+    let report = engine.sync().unwrap();
+    assert!(report.success, "Sync should succeed: {:?}", report.errors);
+
+    let content = fs::read_to_string(&legacy).unwrap();
+    assert!(content.contains("Prefer small functions."));
+    assert!(!content.contains("Use 4 spaces"));
+    assert!(temp.path().join(".cursor/rules/style.mdc").exists());
+}
+
+#[test]
+fn test_sync_migrates_windsurf_rules_to_directory() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+
+    let repo_dir = temp.path().join(".repository");
+    let rules_dir = repo_dir.join("rules");
+    fs::create_dir_all(&rules_dir).unwrap();
+    let config = "tools = [\"windsurf\"]\n\n[core]\nmode = \"standard\"\n";
+    fs::write(repo_dir.join("config.toml"), config).unwrap();
+
+    let mut registry = repo_core::RuleRegistry::new(rules_dir.join("registry.toml"));
+    registry.add_rule("style", "Use 4 spaces", vec![]).unwrap();
+    registry
+        .add_rule(
+            "review",
+            "Explain trade-offs",
+            vec!["trigger:model_decision".into()],
+        )
+        .unwrap();
+    let long = registry
+        .add_rule("glossary", &"term ".repeat(1300), vec![])
+        .unwrap()
+        .uuid;
+    registry.save().unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    let report = engine.sync().unwrap();
+    assert!(report.success, "Sync should succeed: {:?}", report.errors);
+    let legacy = temp.path().join(".windsurfrules");
+    assert!(legacy.exists());
+
+    // Switching to the directory format moves every rule into its own file
+    fs::write(
+        repo_dir.join("config.toml"),
+        format!("{config}\n[tool_settings.windsurf]\nformat = \"directory\"\n"),
+    )
+    .unwrap();
+    let report = engine.sync().unwrap();
+    assert!(report.success, "Sync should succeed: {:?}", report.errors);
+
+    assert!(!legacy.exists(), ".windsurfrules should be removed");
+    let rules = temp.path().join(".windsurf/rules");
+    let style = fs::read_to_string(rules.join("style.md")).unwrap();
+    assert!(style.starts_with("---\ntrigger: always_on\n---\n"));
+    assert!(style.contains("Use 4 spaces"));
+    let review = fs::read_to_string(rules.join("review.md")).unwrap();
+    assert!(review.starts_with("---\ntrigger: model_decision\ndescription: \"review\"\n---\n"));
+
+    // Rules over Windsurf's per-file limit are written with a warning
+    assert!(rules.join("glossary.md").exists());
+    assert!(
+        report
+            .actions
+            .iter()
+            .any(|a| a.starts_with("Warning: .windsurf/rules/glossary.md is"))
+    );
+
+    let check = engine.check().unwrap();
+    assert_eq!(check.status, CheckStatus::Healthy, "{:?}", check);
+
+    // Removing a rule removes its file
+    registry.remove_rule(long).unwrap();
+    let report = engine.sync().unwrap();
+    assert!(report.success, "Sync should succeed: {:?}", report.errors);
+    assert!(!rules.join("glossary.md").exists());
+    assert!(rules.join("style.md").exists());
+
+    let report = engine.sync().unwrap();
+    assert!(report.changes.is_empty(), "{:?}", report.changes);
+}
+
+#[test]
+fn test_sync_rejects_unknown_cursor_format() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+
+    let repo_dir = temp.path().join(".repository");
+    fs::create_dir_all(&repo_dir).unwrap();
+    fs::write(
+        repo_dir.join("config.toml"),
+        "tools = [\"cursor\"]\n\n[tool_settings.cursor]\nformat = \"rules\"\n",
+    )
+    .unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    let report = engine.sync().unwrap();
+    assert!(!report.success);
+    assert!(report.errors[0].contains("[tool_settings.cursor] format"));
+    assert!(!temp.path().join(".cursorrules").exists());
+}
+
+#[test]
+fn test_check_reports_invalid_definitions() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+
+    let tools_dir = temp.path().join(".repository/tools");
+    fs::create_dir_all(&tools_dir).unwrap();
+    fs::write(tools_dir.join("broken.toml"), "[meta]\nname = \"Broken\"\n").unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    let report = engine.check().unwrap();
+    assert_eq!(report.status, CheckStatus::Healthy);
+    assert_eq!(report.messages.len(), 1);
+    assert!(
+        report.messages[0].contains("broken.toml"),
+        "{:?}",
+        report.messages
+    );
+}
+
+#[test]
+fn test_check_reports_tool_name_collisions() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+
+    let ext_dir = temp.path().join(".repository/extensions/editor-kit");
+    fs::create_dir_all(&ext_dir).unwrap();
+    fs::write(
+        ext_dir.join("repo_extension.toml"),
+        "[extension]\nname = \"editor-kit\"\nversion = \"0.1.0\"\n\n\
+         [provides]\ntools = [\"vscode\"]\n",
+    )
+    .unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    let report = engine.check().unwrap();
+    assert_eq!(
+        report.messages,
+        vec!["Tool 'vscode' is provided by built-in, extension 'editor-kit'; built-in is used"]
+    );
+}
+
+#[test]
+fn test_sync_and_check_report_ignored_tool_definitions() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+
+    // Shares the built-in's slug without `override = true`
+    let tools_dir = temp.path().join(".repository/tools");
+    fs::create_dir_all(&tools_dir).unwrap();
+    fs::write(
+        tools_dir.join("cursor.toml"),
+        "[meta]\nname = \"Team Cursor\"\nslug = \"cursor\"\n\n\
+         [integration]\nconfig_path = \".cursor/team.md\"\ntype = \"markdown\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join(".repository/config.toml"),
+        "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n",
+    )
+    .unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    let report = engine.sync().unwrap();
+    assert!(report.success, "{:?}", report.errors);
+    assert_eq!(report.warnings.len(), 1, "{:?}", report.warnings);
+    assert!(report.warnings[0].contains("override = true"));
+    assert!(temp.path().join(".cursorrules").exists());
+
+    let check = engine.check().unwrap();
+    assert!(
+        check.messages.contains(&report.warnings[0]),
+        "{:?}",
+        check.messages
+    );
+}
+
+#[test]
+fn test_sync_writes_extension_tool_definitions() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+
+    let ext_dir = temp.path().join(".repository/extensions/kit");
+    fs::create_dir_all(&ext_dir).unwrap();
+    fs::write(
+        ext_dir.join("repo_extension.toml"),
+        "[extension]\nname = \"kit\"\nversion = \"0.1.0\"\n\n\
+         [provides]\ntools = [\"kittool\"]\ntool_definitions = [\"kittool.toml\"]\n",
+    )
+    .unwrap();
+    fs::write(
+        ext_dir.join("kittool.toml"),
+        "[meta]\nname = \"Kit Tool\"\nslug = \"kittool\"\n\n\
+         [integration]\nconfig_path = \".kittool.md\"\ntype = \"markdown\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join(".repository/config.toml"),
+        "tools = [\"kittool\"]\n\n[core]\nmode = \"standard\"\n",
+    )
+    .unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    let report = engine.sync().unwrap();
+    assert!(report.success, "{:?}", report.errors);
+    assert!(temp.path().join(".kittool.md").exists());
+    assert_eq!(engine.check().unwrap().status, CheckStatus::Healthy);
+}
+
+#[test]
+fn test_sync_records_history_entries() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+
+    let repo_dir = temp.path().join(".repository");
+    fs::create_dir_all(&repo_dir).unwrap();
+    fs::write(
+        repo_dir.join("config.toml"),
+        "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n",
+    )
+    .unwrap();
+
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+    let first = engine.sync().unwrap();
+    let second = engine.sync().unwrap();
+
+    // Dry runs are not recorded
+    engine
+        .sync_with_options(repo_core::sync::SyncOptions { dry_run: true })
+        .unwrap();
+
+    let history = History::load(&NormalizedPath::new(&repo_dir)).unwrap();
+    let entries = history.entries();
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|e| e.operation == Operation::Sync));
+    assert_eq!(entries[0].changed_files, vec![".cursorrules"]);
+    assert_eq!(entries[0].summary, "1 file changed");
+    assert_eq!(entries[0].actions, Some(first.actions.len()));
+    assert!(entries[1].changed_files.is_empty());
+    assert_eq!(entries[1].summary, "No files changed");
+    assert_eq!(entries[1].actions, Some(second.actions.len()));
+    assert!(first.actions.len() > second.actions.len());
+}
+
+#[test]
+fn test_sync_runs_hooks() {
+    let temp = setup_git_repo();
+    let root = NormalizedPath::new(temp.path());
+    let repo_dir = temp.path().join(".repository");
+    fs::create_dir_all(&repo_dir).unwrap();
+    fs::write(
+        repo_dir.join("config.toml"),
+        "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n\n\
+         [[hooks]]\nevent = \"pre-sync\"\ncommand = \"sh\"\n\
+         args = [\"-c\", \"test ! -e blocked || { echo 'sync is blocked' >&2; exit 1; }\"]\n\n\
+         [[hooks]]\nevent = \"post-sync\"\ncommand = \"sh\"\n\
+         args = [\"-c\", \"echo \\\"$REPO_EVENT\\\" >> synced.log\"]\n",
+    )
+    .unwrap();
+    let engine = SyncEngine::new(root, Mode::Standard).unwrap();
+
+    // A failing pre-sync hook aborts before anything is written
+    fs::write(temp.path().join("blocked"), "").unwrap();
+    let err = engine.sync().unwrap_err();
+    assert!(err.to_string().contains("sync is blocked"), "{err}");
+    assert!(!temp.path().join(".cursorrules").exists());
+    assert!(!temp.path().join("synced.log").exists());
+
+    // Dry runs do not run hooks
+    engine
+        .sync_with_options(repo_core::sync::SyncOptions { dry_run: true })
+        .unwrap();
+
+    fs::remove_file(temp.path().join("blocked")).unwrap();
+    assert!(engine.sync().unwrap().success);
+    assert!(temp.path().join(".cursorrules").exists());
+    assert_eq!(
+        fs::read_to_string(temp.path().join("synced.log")).unwrap(),
+        "post-sync\n"
+    );
+}
@@ -12,7 +12,9 @@ pub struct ExtensionConfig {
     /// Source URL or path for the extension (e.g., a git repository URL).
     pub source: String,
     /// Optional pinned ref (branch, tag, or commit hash).
-    #[serde(default)]
+    ///
+    /// Also accepted as `ref`.
+    #[serde(default, alias = "ref")]
     pub ref_pin: Option<String>,
     /// Arbitrary extension-specific configuration values.
     #[serde(default, flatten)]
@@ -39,6 +41,17 @@ custom_key = "custom_value"
         );
     }
 
+    #[test]
+    fn test_parse_extension_config_ref_alias() {
+        let toml_str = r#"
+source = "https://github.com/user/vaultspec.git"
+ref = "main"
+"#;
+        let config: ExtensionConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.ref_pin.as_deref(), Some("main"));
+        assert!(config.config.is_empty());
+    }
+
     #[test]
     fn test_parse_extension_config_minimal() {
         let toml_str = r#"
@@ -0,0 +1,882 @@
+//! Preset dependency resolution for extensions.
+//!
+//! Extensions declare runtime requirements (e.g., `[requires.python]`,
+//! `[runtime] type = "python"`) which imply presets that must be configured
+//! in the repository before the extension can run. This module turns those
+//! declarations into [`PresetDependency`] values and aggregates them across
+//! extensions in a [`DependencyGraph`].
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::manifest::ExtensionManifest;
+
+/// Comparison operator used in a version constraint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+/// Operator as written in a constraint; `^` and `~` expand to a pair of
+/// comparators.
+#[derive(Debug, Clone, Copy)]
+enum Operator {
+    Cmp(Op),
+    Caret,
+    Tilde,
+}
+
+/// A single `<op><version>` comparator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparator {
+    op: Op,
+    version: Version,
+}
+
+impl Comparator {
+    fn matches(&self, version: &Version) -> bool {
+        match self.op {
+            // Exact matches compare only the components given in the
+            // constraint, so "3.12" matches "3.12.4". A pre-release must
+            // match exactly.
+            Op::Eq => match &self.version.pre {
+                Some(_) => version == &self.version,
+                None => {
+                    let release = &self.version.release;
+                    version.pre.is_none()
+                        && version.release.len() >= release.len()
+                        && version.release[..release.len()] == release[..]
+                }
+            },
+            Op::Gt => version > &self.version,
+            Op::Ge => version >= &self.version,
+            Op::Lt => version < &self.version,
+            Op::Le => version <= &self.version,
+        }
+    }
+}
+
+/// Compare two dotted versions, padding the shorter one with zeros.
+fn compare(a: &[u64], b: &[u64]) -> Ordering {
+    let len = a.len().max(b.len());
+    for i in 0..len {
+        let x = a.get(i).copied().unwrap_or(0);
+        let y = b.get(i).copied().unwrap_or(0);
+        match x.cmp(&y) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+    Ordering::Equal
+}
+
+/// Compare two pre-release tags.
+///
+/// Tags are split into runs of digits and of other characters, ignoring `.`
+/// and `-` separators. Numeric runs compare numerically and sort before
+/// alphabetic ones, so `rc2 < rc10` and `beta.2 < beta.11`.
+fn compare_pre(a: &str, b: &str) -> Ordering {
+    fn tokens(tag: &str) -> Vec<&str> {
+        let mut out = Vec::new();
+        for part in tag.split(['.', '-']) {
+            let mut start = 0;
+            for (i, c) in part.char_indices().skip(1) {
+                let prev = part[..i].chars().next_back().unwrap_or(c);
+                if prev.is_ascii_digit() != c.is_ascii_digit() {
+                    out.push(&part[start..i]);
+                    start = i;
+                }
+            }
+            if start < part.len() {
+                out.push(&part[start..]);
+            }
+        }
+        out
+    }
+
+    let (a, b) = (tokens(a), tokens(b));
+    for (x, y) in a.iter().zip(&b) {
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => x.cmp(y),
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// Parse the leading dotted numeric components of a version string.
+///
+/// Tolerates common prefixes and suffixes emitted by runtime tools, e.g.
+/// `"v20.11.1"`, `"3.13.0rc1"`, `"1.95.0-nightly"`. Returns `None` if the
+/// string contains no numeric component.
+pub fn parse_version(input: &str) -> Option<Vec<u64>> {
+    Version::parse(input).map(|v| v.release)
+}
+
+/// A runtime version: dotted numeric components and an optional pre-release
+/// tag.
+///
+/// Unlike semver, any number of components is allowed, and missing trailing
+/// components compare as zero, so `3.12` equals `3.12.0`. A pre-release
+/// sorts before the release it precedes: `3.13.0rc1 < 3.13.0`.
+#[derive(Debug, Clone)]
+pub struct Version {
+    release: Vec<u64>,
+    pre: Option<String>,
+}
+
+impl Version {
+    /// Parse a version as reported by a runtime tool.
+    ///
+    /// Tolerates a leading `v` and trailing build metadata (`+...`). Anything
+    /// after the numeric components is taken as the pre-release tag, e.g.
+    /// `rc1` in `"3.13.0rc1"` or `nightly` in `"1.95.0-nightly"`. Returns
+    /// `None` if the string contains no numeric component.
+    pub fn parse(input: &str) -> Option<Self> {
+        let trimmed = input.trim().trim_start_matches(['v', 'V']);
+        let trimmed = trimmed.split('+').next().unwrap_or_default();
+        let mut release = Vec::new();
+        let mut rest = trimmed;
+
+        loop {
+            let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            if digits == 0 {
+                break;
+            }
+            release.push(rest[..digits].parse().ok()?);
+            rest = &rest[digits..];
+            match rest.strip_prefix('.') {
+                Some(next) if next.starts_with(|c: char| c.is_ascii_digit()) => rest = next,
+                _ => break,
+            }
+        }
+
+        if release.is_empty() {
+            return None;
+        }
+        let pre = rest.trim_start_matches(['-', '.']);
+        Some(Self {
+            release,
+            pre: (!pre.is_empty()).then(|| pre.to_string()),
+        })
+    }
+
+    /// Parse a version written in a constraint, rejecting anything that
+    /// isn't `<digits>(.<digits>)*` with an optional alphanumeric
+    /// pre-release tag.
+    fn parse_strict(input: &str) -> Option<Self> {
+        let tail = input.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+        let numeric = &input[..input.len() - tail.len()];
+        if numeric.split('.').any(str::is_empty) {
+            return None;
+        }
+        let pre = tail.strip_prefix('-').unwrap_or(tail);
+        if pre.is_empty() != tail.is_empty()
+            || !pre
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        {
+            return None;
+        }
+        Self::parse(input)
+    }
+
+    /// Whether this is a pre-release.
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some()
+    }
+
+    /// The release components, without any pre-release tag.
+    pub fn release(&self) -> &[u64] {
+        &self.release
+    }
+
+    /// The version one past the component at `index`, e.g. bumping index 1
+    /// of `1.2.3` gives `1.3`.
+    fn bump(&self, index: usize) -> Self {
+        let mut release: Vec<u64> = self.release[..=index].to_vec();
+        release[index] += 1;
+        Self { release, pre: None }
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare(&self.release, &other.release).then_with(|| match (&self.pre, &other.pre) {
+            (None, None) => Ordering::Equal,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(a), Some(b)) => compare_pre(a, b),
+        })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let release: Vec<String> = self.release.iter().map(u64::to_string).collect();
+        f.write_str(&release.join("."))?;
+        match &self.pre {
+            Some(pre) => write!(f, "-{pre}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A version constraint such as `">=3.12"`, `"^1.2"` or `">=18, <21"`.
+///
+/// Comma-separated comparators must all match. Supported operators are
+/// `==`, `=`, `>`, `>=`, `<` and `<=`; a bare version is treated as `==`.
+/// Versions are compared component-wise, so non-semver runtime versions
+/// like `3.13` are accepted.
+///
+/// `^` and `~` expand to ranges as in Cargo: `^1.2.3` is `>=1.2.3, <2`,
+/// `^0.2.3` is `>=0.2.3, <0.3`, and `~1.2.3` is `>=1.2.3, <1.3`.
+///
+/// A pre-release version only matches if a comparator names a pre-release
+/// of the same release: `>=3.13.0rc1` matches `3.13.0rc2`, but `>=3.12`
+/// does not match `3.13.0rc1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionConstraint {
+    raw: String,
+    comparators: Vec<Comparator>,
+}
+
+impl VersionConstraint {
+    /// Parse a constraint string.
+    pub fn parse(input: &str) -> Result<Self> {
+        let invalid = |reason: String| Error::InvalidConstraint {
+            constraint: input.to_string(),
+            reason,
+        };
+
+        let mut comparators = Vec::new();
+        for part in input.split(',') {
+            let part = part.trim();
+            if part.is_empty() {
+                return Err(invalid("empty comparator".to_string()));
+            }
+
+            let (operator, rest) = if let Some(rest) = part.strip_prefix(">=") {
+                (Operator::Cmp(Op::Ge), rest)
+            } else if let Some(rest) = part.strip_prefix("<=") {
+                (Operator::Cmp(Op::Le), rest)
+            } else if let Some(rest) = part.strip_prefix("==") {
+                (Operator::Cmp(Op::Eq), rest)
+            } else if let Some(rest) = part.strip_prefix('>') {
+                (Operator::Cmp(Op::Gt), rest)
+            } else if let Some(rest) = part.strip_prefix('<') {
+                (Operator::Cmp(Op::Lt), rest)
+            } else if let Some(rest) = part.strip_prefix('=') {
+                (Operator::Cmp(Op::Eq), rest)
+            } else if let Some(rest) = part.strip_prefix('^') {
+                (Operator::Caret, rest)
+            } else if let Some(rest) = part.strip_prefix('~') {
+                (Operator::Tilde, rest)
+            } else {
+                (Operator::Cmp(Op::Eq), part)
+            };
+
+            let rest = rest.trim();
+            if rest.is_empty() {
+                return Err(invalid(format!("missing version after '{part}'")));
+            }
+            let version = Version::parse_strict(rest).ok_or_else(|| {
+                invalid(format!(
+                    "'{rest}' is not a dotted numeric version with an optional pre-release"
+                ))
+            })?;
+
+            let op = match operator {
+                Operator::Cmp(op) => op,
+                Operator::Caret => {
+                    // Bump the first non-zero component, or the last one given
+                    let index = version
+                        .release
+                        .iter()
+                        .position(|&c| c != 0)
+                        .unwrap_or(version.release.len() - 1);
+                    comparators.push(Comparator {
+                        op: Op::Lt,
+                        version: version.bump(index),
+                    });
+                    Op::Ge
+                }
+                Operator::Tilde => {
+                    // Bump the minor, or the major if only a major is given
+                    let index = version.release.len().min(2) - 1;
+                    comparators.push(Comparator {
+                        op: Op::Lt,
+                        version: version.bump(index),
+                    });
+                    Op::Ge
+                }
+            };
+            comparators.push(Comparator { op, version });
+        }
+
+        Ok(Self {
+            raw: input.trim().to_string(),
+            comparators,
+        })
+    }
+
+    /// Check whether a version satisfies this constraint.
+    pub fn matches(&self, version: &Version) -> bool {
+        if version.is_prerelease() {
+            let named = self.comparators.iter().any(|c| {
+                c.version.is_prerelease() && compare(&c.version.release, &version.release).is_eq()
+            });
+            if !named {
+                return false;
+            }
+        }
+        self.comparators.iter().all(|c| c.matches(version))
+    }
+
+    /// Check whether a version string satisfies this constraint.
+    ///
+    /// Returns `false` if the version cannot be parsed.
+    pub fn matches_str(&self, version: &str) -> bool {
+        Version::parse(version).is_some_and(|v| self.matches(&v))
+    }
+
+    /// The original constraint string.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl fmt::Display for VersionConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl FromStr for VersionConstraint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+/// A preset that an extension requires, with an optional version constraint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetDependency {
+    /// Preset identifier (e.g., "env:python").
+    pub preset: String,
+    /// Version the preset's runtime must satisfy, if any.
+    pub constraint: Option<VersionConstraint>,
+}
+
+impl ExtensionManifest {
+    /// Presets implied by the manifest's `[requires]` and `[runtime]` tables.
+    ///
+    /// - `[requires.python]` maps to `env:python` with its version constraint.
+    /// - `[runtime] type = "python" | "node" | "rust"` maps to the matching
+    ///   `env:*` preset when not already covered by `[requires]`.
+    pub fn implicit_preset_dependencies(&self) -> Result<Vec<PresetDependency>> {
+        let mut deps: Vec<PresetDependency> = Vec::new();
+
+        if let Some(python) = self.requires.as_ref().and_then(|r| r.python.as_ref()) {
+            deps.push(PresetDependency {
+                preset: "env:python".to_string(),
+                constraint: Some(VersionConstraint::parse(&python.version)?),
+            });
+        }
+
+        if let Some(runtime) = &self.runtime {
+            let preset = match runtime.runtime_type.as_str() {
+                "python" => Some("env:python"),
+                "node" => Some("env:node"),
+                "rust" => Some("env:rust"),
+                _ => None,
+            };
+            if let Some(preset) = preset
+                && !deps.iter().any(|d| d.preset == preset)
+            {
+                deps.push(PresetDependency {
+                    preset: preset.to_string(),
+                    constraint: None,
+                });
+            }
+        }
+
+        Ok(deps)
+    }
+}
+
+/// A preset required by one or more extensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresetRequirement {
+    /// Preset identifier (e.g., "env:python").
+    pub preset: String,
+    /// All constraints declared for this preset; each must be satisfied.
+    pub constraints: Vec<VersionConstraint>,
+    /// Names of the extensions requiring this preset (sorted).
+    pub required_by: Vec<String>,
+}
+
+impl PresetRequirement {
+    /// Check whether a runtime version satisfies every constraint.
+    pub fn is_satisfied_by(&self, version: &str) -> bool {
+        self.constraints.iter().all(|c| c.matches_str(version))
+    }
+
+    /// Whether any version constraint was declared.
+    pub fn is_constrained(&self) -> bool {
+        !self.constraints.is_empty()
+    }
+}
+
+/// A node in a [`DependencyGraph`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NodeId {
+    /// An extension, by name.
+    Extension(String),
+    /// A preset, by id (e.g., "env:python").
+    Preset(String),
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Extension(name) => f.write_str(name),
+            Self::Preset(id) => write!(f, "preset {id}"),
+        }
+    }
+}
+
+/// A loop of extensions that (transitively) depend on themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyCycle {
+    /// Nodes along the loop, starting and ending with the same node, each
+    /// depending on the next: `[a, b, c, a]` for `a -> b -> c -> a`.
+    pub path: Vec<NodeId>,
+}
+
+impl fmt::Display for DependencyCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path: Vec<String> = self.path.iter().map(NodeId::to_string).collect();
+        write!(f, "dependency cycle: {}", path.join(" -> "))
+    }
+}
+
+impl std::error::Error for DependencyCycle {}
+
+/// Dependency graph from extensions to the presets and other extensions
+/// they require.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    extensions: BTreeMap<String, Vec<PresetDependency>>,
+    requires: BTreeMap<String, Vec<String>>,
+}
+
+impl DependencyGraph {
+    /// Create an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an extension, its implicit preset dependencies, and the
+    /// extensions listed in its `[requires] extensions`.
+    pub fn add_extension(&mut self, manifest: &ExtensionManifest) -> Result<()> {
+        let deps = manifest.implicit_preset_dependencies()?;
+        let name = manifest.extension.name.clone();
+        let requires = manifest
+            .requires
+            .as_ref()
+            .map(|r| r.extensions.clone())
+            .unwrap_or_default();
+        self.extensions.insert(name.clone(), deps);
+        self.requires.insert(name, requires);
+        Ok(())
+    }
+
+    /// Preset dependencies declared by an extension.
+    pub fn dependencies_of(&self, extension: &str) -> &[PresetDependency] {
+        self.extensions
+            .get(extension)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Nodes that `node` depends on, in declaration order: required
+    /// extensions first, then presets.
+    fn edges(&self, node: &NodeId) -> Vec<NodeId> {
+        let NodeId::Extension(name) = node else {
+            return Vec::new();
+        };
+        let extensions = self
+            .requires
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(|dep| NodeId::Extension(dep.clone()));
+        let presets = self
+            .dependencies_of(name)
+            .iter()
+            .map(|dep| NodeId::Preset(dep.preset.clone()));
+        extensions.chain(presets).collect()
+    }
+
+    /// All nodes in dependency order: every node comes after the nodes it
+    /// depends on.
+    ///
+    /// Extensions are visited by name, so the order is deterministic.
+    /// Required extensions that were never added still appear as nodes.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`DependencyCycle`] found, naming every extension
+    /// on the loop in dependency order.
+    pub fn topological_order(&self) -> std::result::Result<Vec<NodeId>, DependencyCycle> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            Visiting,
+            Done,
+        }
+
+        fn visit(
+            graph: &DependencyGraph,
+            node: NodeId,
+            marks: &mut BTreeMap<NodeId, Mark>,
+            stack: &mut Vec<NodeId>,
+            order: &mut Vec<NodeId>,
+        ) -> std::result::Result<(), DependencyCycle> {
+            match marks.get(&node) {
+                Some(Mark::Done) => return Ok(()),
+                Some(Mark::Visiting) => {
+                    let start = stack
+                        .iter()
+                        .position(|n| *n == node)
+                        .expect("invariant: visiting nodes are on the stack");
+                    let mut path = stack[start..].to_vec();
+                    path.push(node);
+                    return Err(DependencyCycle { path });
+                }
+                None => {}
+            }
+
+            marks.insert(node.clone(), Mark::Visiting);
+            stack.push(node.clone());
+            for dep in graph.edges(&node) {
+                visit(graph, dep, marks, stack, order)?;
+            }
+            stack.pop();
+            marks.insert(node.clone(), Mark::Done);
+            order.push(node);
+            Ok(())
+        }
+
+        let mut marks = BTreeMap::new();
+        let mut stack = Vec::new();
+        let mut order = Vec::new();
+        for name in self.extensions.keys() {
+            visit(
+                self,
+                NodeId::Extension(name.clone()),
+                &mut marks,
+                &mut stack,
+                &mut order,
+            )?;
+        }
+        Ok(order)
+    }
+
+    /// Names of all extensions in the graph (sorted).
+    pub fn extensions(&self) -> Vec<&str> {
+        self.extensions.keys().map(String::as_str).collect()
+    }
+
+    /// Aggregate the presets required across all extensions.
+    ///
+    /// Results are sorted by preset id, with constraints merged per preset.
+    pub fn required_presets(&self) -> Vec<PresetRequirement> {
+        let mut by_preset: BTreeMap<&str, PresetRequirement> = BTreeMap::new();
+
+        for (ext_name, deps) in &self.extensions {
+            for dep in deps {
+                let entry =
+                    by_preset
+                        .entry(dep.preset.as_str())
+                        .or_insert_with(|| PresetRequirement {
+                            preset: dep.preset.clone(),
+                            constraints: Vec::new(),
+                            required_by: Vec::new(),
+                        });
+                if let Some(c) = &dep.constraint
+                    && !entry.constraints.contains(c)
+                {
+                    entry.constraints.push(c.clone());
+                }
+                if !entry.required_by.contains(ext_name) {
+                    entry.required_by.push(ext_name.clone());
+                }
+            }
+        }
+
+        by_preset.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(extra: &str) -> ExtensionManifest {
+        ExtensionManifest::from_toml(&format!(
+            "[extension]\nname = \"ext\"\nversion = \"0.1.0\"\n{extra}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("3.12.1"), Some(vec![3, 12, 1]));
+        assert_eq!(parse_version("v20.11.0"), Some(vec![20, 11, 0]));
+        assert_eq!(parse_version("3.13.0rc1"), Some(vec![3, 13, 0]));
+        assert_eq!(parse_version("1.95.0-nightly"), Some(vec![1, 95, 0]));
+        assert_eq!(parse_version("unknown"), None);
+    }
+
+    #[test]
+    fn test_constraint_operators() {
+        let ge = VersionConstraint::parse(">=3.12").unwrap();
+        assert!(ge.matches_str("3.12.0"));
+        assert!(ge.matches_str("3.13"));
+        assert!(!ge.matches_str("3.11.9"));
+
+        let lt = VersionConstraint::parse("<4").unwrap();
+        assert!(lt.matches_str("3.99"));
+        assert!(!lt.matches_str("4.0.0"));
+
+        let exact = VersionConstraint::parse("3.12").unwrap();
+        assert!(exact.matches_str("3.12.4"));
+        assert!(!exact.matches_str("3.13.0"));
+    }
+
+    #[test]
+    fn test_constraint_range() {
+        let range = VersionConstraint::parse(">=18, <21").unwrap();
+        assert!(range.matches_str("18.0.0"));
+        assert!(range.matches_str("20.11.1"));
+        assert!(!range.matches_str("21.0.0"));
+        assert!(!range.matches_str("16.20.0"));
+    }
+
+    #[test]
+    fn test_constraint_invalid() {
+        assert!(VersionConstraint::parse(">=").is_err());
+        assert!(VersionConstraint::parse(">=abc").is_err());
+        assert!(VersionConstraint::parse(">=3.12,").is_err());
+        assert!(VersionConstraint::parse("^").is_err());
+        assert!(VersionConstraint::parse("~1..2").is_err());
+        assert!(VersionConstraint::parse("^1.2-").is_err());
+        assert!(VersionConstraint::parse(">=1.2.3+build").is_err());
+        assert!(VersionConstraint::parse("!=1.3").is_err());
+
+        let err = VersionConstraint::parse(">=1.0, ~")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "invalid version constraint '>=1.0, ~': missing version after '~'"
+        );
+    }
+
+    #[test]
+    fn test_constraint_ranges() {
+        let cases = [
+            // Caret: bump the first non-zero component
+            ("^1.2.3", "1.2.3", true),
+            ("^1.2.3", "1.9.0", true),
+            ("^1.2.3", "1.2.2", false),
+            ("^1.2.3", "2.0.0", false),
+            ("^1.2", "1.2.0", true),
+            ("^1", "1.99", true),
+            ("^0.2.3", "0.2.9", true),
+            ("^0.2.3", "0.3.0", false),
+            ("^0.0.3", "0.0.3", true),
+            ("^0.0.3", "0.0.4", false),
+            ("^0.0", "0.0.9", true),
+            ("^0.0", "0.1.0", false),
+            // Tilde: bump the minor, or the major if only a major is given
+            ("~1.2.3", "1.2.9", true),
+            ("~1.2.3", "1.3.0", false),
+            ("~1.2.3", "1.2.2", false),
+            ("~1.2", "1.2.0", true),
+            ("~1.2", "1.3.0", false),
+            ("~1", "1.9.9", true),
+            ("~1", "2.0.0", false),
+            // Compound ranges
+            (">=1.2, <2.0", "1.2.0", true),
+            (">=1.2, <2.0", "1.99.99", true),
+            (">=1.2, <2.0", "2.0.0", false),
+            (">1.2, <=1.4", "1.2.0", false),
+            (">1.2, <=1.4", "1.4.0", true),
+            ("^1.2, <1.5", "1.5.0", false),
+            // Pre-releases only match ranges naming the same release
+            ("^1.2.3", "1.3.0-beta.1", false),
+            ("<2.0", "2.0.0-rc.1", false),
+            (">=3.12", "3.13.0rc1", false),
+            ("3.13", "3.13.0rc1", false),
+            ("^1.2.3-beta.2", "1.2.3-beta.3", true),
+            ("^1.2.3-beta.2", "1.2.3-beta.11", true),
+            ("^1.2.3-beta.2", "1.2.3-beta.1", false),
+            ("^1.2.3-beta.2", "1.2.3", true),
+            ("^1.2.3-beta.2", "1.2.4-beta.1", false),
+            (">=3.13.0rc1", "3.13.0rc2", true),
+            (">=3.13.0rc1", "3.13.0", true),
+            ("=1.2.3-rc.1", "1.2.3-rc.1", true),
+            ("=1.2.3-rc.1", "1.2.3", false),
+        ];
+
+        for (constraint, version, expected) in cases {
+            let parsed = VersionConstraint::parse(constraint).unwrap();
+            let version = Version::parse(version).unwrap();
+            assert_eq!(
+                parsed.matches(&version),
+                expected,
+                "{constraint} matching {version}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        let v = |s: &str| Version::parse(s).unwrap();
+        assert_eq!(v("3.12"), v("3.12.0"));
+        assert!(v("3.13.0rc1") < v("3.13.0"));
+        assert!(v("3.13.0rc2") < v("3.13.0rc10"));
+        assert!(v("1.0.0-alpha") < v("1.0.0-alpha.1"));
+        assert!(v("1.0.0-alpha.beta") < v("1.0.0-beta"));
+        assert!(v("v20.11.1+build.5") == v("20.11.1"));
+        assert_eq!(v("1.95.0-nightly").to_string(), "1.95.0-nightly");
+    }
+
+    #[test]
+    fn test_constraint_display_roundtrip() {
+        let c: VersionConstraint = " >=3.12 ".parse().unwrap();
+        assert_eq!(c.to_string(), ">=3.12");
+    }
+
+    #[test]
+    fn test_implicit_dependencies_from_requires_and_runtime() {
+        let m = manifest("[requires.python]\nversion = \">=3.13\"\n[runtime]\ntype = \"python\"\n");
+        let deps = m.implicit_preset_dependencies().unwrap();
+
+        assert_eq!(deps.len(), 1, "runtime should not duplicate requires");
+        assert_eq!(deps[0].preset, "env:python");
+        assert_eq!(deps[0].constraint.as_ref().unwrap().as_str(), ">=3.13");
+    }
+
+    #[test]
+    fn test_implicit_dependencies_from_runtime_only() {
+        let m = manifest("[runtime]\ntype = \"node\"\n");
+        let deps = m.implicit_preset_dependencies().unwrap();
+
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].preset, "env:node");
+        assert!(deps[0].constraint.is_none());
+    }
+
+    #[test]
+    fn test_implicit_dependencies_none() {
+        let m = manifest("");
+        assert!(m.implicit_preset_dependencies().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_graph_merges_requirements() {
+        let mut a = manifest("[requires.python]\nversion = \">=3.12\"\n");
+        a.extension.name = "a".to_string();
+        let mut b =
+            manifest("[requires.python]\nversion = \"<3.14\"\n[runtime]\ntype = \"python\"\n");
+        b.extension.name = "b".to_string();
+
+        let mut graph = DependencyGraph::new();
+        graph.add_extension(&a).unwrap();
+        graph.add_extension(&b).unwrap();
+
+        let required = graph.required_presets();
+        assert_eq!(required.len(), 1);
+        let python = &required[0];
+        assert_eq!(python.preset, "env:python");
+        assert_eq!(python.required_by, vec!["a", "b"]);
+        assert!(python.is_satisfied_by("3.13.1"));
+        assert!(!python.is_satisfied_by("3.11.0"));
+        assert!(!python.is_satisfied_by("3.14.0"));
+    }
+
+    #[test]
+    fn test_graph_dependencies_of_unknown_is_empty() {
+        let graph = DependencyGraph::new();
+        assert!(graph.dependencies_of("missing").is_empty());
+    }
+
+    fn requiring(name: &str, extensions: &[&str]) -> ExtensionManifest {
+        let list: Vec<String> = extensions.iter().map(|e| format!("\"{e}\"")).collect();
+        let mut m = manifest(&format!("[requires]\nextensions = [{}]\n", list.join(", ")));
+        m.extension.name = name.to_string();
+        m
+    }
+
+    #[test]
+    fn test_topological_order_puts_dependencies_first() {
+        let mut graph = DependencyGraph::new();
+        graph.add_extension(&requiring("app", &["lib"])).unwrap();
+        let mut lib = manifest("[runtime]\ntype = \"python\"\n");
+        lib.extension.name = "lib".to_string();
+        graph.add_extension(&lib).unwrap();
+
+        let order = graph.topological_order().unwrap();
+        assert_eq!(
+            order,
+            vec![
+                NodeId::Preset("env:python".to_string()),
+                NodeId::Extension("lib".to_string()),
+                NodeId::Extension("app".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_topological_order_reports_cycle_path() {
+        let mut graph = DependencyGraph::new();
+        graph.add_extension(&requiring("a", &["b"])).unwrap();
+        graph.add_extension(&requiring("b", &["c"])).unwrap();
+        graph.add_extension(&requiring("c", &["a"])).unwrap();
+
+        let cycle = graph.topological_order().unwrap_err();
+        let ext = |name: &str| NodeId::Extension(name.to_string());
+        assert_eq!(cycle.path, vec![ext("a"), ext("b"), ext("c"), ext("a")]);
+        assert_eq!(cycle.to_string(), "dependency cycle: a -> b -> c -> a");
+    }
+}
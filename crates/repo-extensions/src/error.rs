@@ -18,6 +18,14 @@ pub enum Error {
         source: semver::Error,
     },
 
+    /// Invalid version constraint string.
+    #[error("invalid version constraint '{constraint}': {reason}")]
+    InvalidConstraint { constraint: String, reason: String },
+
+    /// Extensions depend on each other in a loop.
+    #[error(transparent)]
+    DependencyCycle(#[from] crate::deps::DependencyCycle),
+
     /// Invalid extension name.
     #[error("invalid extension name '{name}': {reason}")]
     InvalidName { name: String, reason: String },
@@ -41,11 +49,36 @@ pub enum Error {
         extension: String,
     },
 
+    /// Failed to parse the extension lock file.
+    #[error("failed to parse extension lock file at {path}: {reason}")]
+    LockFileParse { path: PathBuf, reason: String },
+
+    /// Failed to serialize the extension lock file.
+    #[error("failed to serialize extension lock file: {0}")]
+    LockFileSerialize(String),
+
+    /// Failed to clone an extension repository.
+    #[error("failed to clone extension from {url}: {reason}")]
+    Clone { url: String, reason: String },
+
+    /// The configured ref does not exist in the cloned repository.
+    #[error("ref '{git_ref}' not found in {url}")]
+    RefNotFound { url: String, git_ref: String },
+
+    /// Filesystem error from repo-fs.
+    #[error(transparent)]
+    Fs(#[from] repo_fs::Error),
+
     /// Failed to parse MCP config JSON.
     #[error("failed to parse MCP config at {path}: {reason}")]
-    McpConfigParse {
-        path: PathBuf,
-        reason: String,
+    McpConfigParse { path: PathBuf, reason: String },
+
+    /// Two extensions define the same MCP server differently.
+    #[error("MCP server '{server}' is defined differently by extensions '{first}' and '{second}'")]
+    McpServerConflict {
+        server: String,
+        first: String,
+        second: String,
     },
 }
 
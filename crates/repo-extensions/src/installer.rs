@@ -0,0 +1,860 @@
+//! Installed extension tracking and verification.
+//!
+//! Installed extensions live under `.repository/extensions/<name>` and are
+//! recorded in `.repository/extensions.lock`, which is committed alongside
+//! the rest of `.repository/`. [`verify`] checks that what is on disk still
+//! matches the lock.
+//!
+//! # Example lock file
+//!
+//! ```toml
+//! [[extension]]
+//! name = "vaultspec"
+//! version = "0.1.0"
+//! source = "https://github.com/example/vaultspec"
+//! commit = "4f2c9e0d5b..."
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::MANIFEST_FILENAME;
+use crate::error::{Error, Result};
+use crate::manifest::ExtensionManifest;
+
+/// Directory holding installed extensions, relative to the repository root.
+pub const EXTENSIONS_DIR: &str = ".repository/extensions";
+
+/// Lock file path, relative to the repository root.
+pub const LOCK_FILE: &str = ".repository/extensions.lock";
+
+/// Directory an extension named `name` is installed into.
+pub fn extension_dir(root: &Path, name: &str) -> PathBuf {
+    root.join(EXTENSIONS_DIR).join(name)
+}
+
+/// An installed extension as recorded in the lock file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LockedExtension {
+    /// Extension name (matches the installed directory name).
+    pub name: String,
+    /// Version from the extension manifest at install time.
+    pub version: String,
+    /// Source the extension was installed from.
+    pub source: String,
+    /// Commit SHA checked out at install time, when installed from git.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+impl LockedExtension {
+    /// Build a lock entry for an extension installed into `installed_dir`.
+    ///
+    /// The commit is taken from the checkout in `installed_dir`, if any.
+    pub fn new(manifest: &ExtensionManifest, source: &str, installed_dir: &Path) -> Self {
+        Self {
+            name: manifest.extension.name.clone(),
+            version: manifest.extension.version.clone(),
+            source: source.to_string(),
+            commit: head_commit(installed_dir),
+        }
+    }
+}
+
+/// The extension lock file (`.repository/extensions.lock`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LockFile {
+    /// Locked extensions, kept sorted by name.
+    #[serde(default, rename = "extension")]
+    pub extensions: Vec<LockedExtension>,
+}
+
+impl LockFile {
+    /// Load the lock file for a repository, or an empty lock if none exists.
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(LOCK_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        toml::from_str(&content).map_err(|e| Error::LockFileParse {
+            path,
+            reason: e.to_string(),
+        })
+    }
+
+    /// Write the lock file atomically.
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let content =
+            toml::to_string_pretty(self).map_err(|e| Error::LockFileSerialize(e.to_string()))?;
+        let path = repo_fs::NormalizedPath::new(root.join(LOCK_FILE));
+        repo_fs::io::write_text(&path, &content)?;
+        Ok(())
+    }
+
+    /// Look up a locked extension by name.
+    pub fn get(&self, name: &str) -> Option<&LockedExtension> {
+        self.extensions.iter().find(|e| e.name == name)
+    }
+
+    /// Insert or replace the entry for an extension.
+    pub fn upsert(&mut self, entry: LockedExtension) {
+        self.extensions.retain(|e| e.name != entry.name);
+        self.extensions.push(entry);
+        self.extensions.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    /// Remove the entry for an extension, returning it if present.
+    pub fn remove(&mut self, name: &str) -> Option<LockedExtension> {
+        let index = self.extensions.iter().position(|e| e.name == name)?;
+        Some(self.extensions.remove(index))
+    }
+
+    /// Compare the lock with the extensions installed under `root`.
+    ///
+    /// Reports locked extensions that are missing, or whose manifest version
+    /// or checked-out commit differs from the lock, followed by installed
+    /// extensions the lock does not list. An empty result means the install
+    /// matches the lock.
+    pub fn verify(&self, root: &Path) -> Vec<LockDrift> {
+        let mut drift: Vec<LockDrift> = self
+            .extensions
+            .iter()
+            .flat_map(|locked| locked.drift(root))
+            .collect();
+        drift.extend(
+            installed_names(root)
+                .into_iter()
+                .filter(|name| self.get(name).is_none())
+                .map(|name| LockDrift::Unlocked { name }),
+        );
+        drift
+    }
+}
+
+impl LockedExtension {
+    /// Compare this entry with the extension installed under `root`.
+    ///
+    /// Reports a missing install, or a manifest version or checked-out
+    /// commit that differs from the lock.
+    fn drift(&self, root: &Path) -> Vec<LockDrift> {
+        let name = self.name.clone();
+        let dir = extension_dir(root, &self.name);
+        if !dir.is_dir() {
+            return vec![LockDrift::Missing { name }];
+        }
+
+        let mut drift = Vec::new();
+        match ExtensionManifest::from_path(&dir.join(MANIFEST_FILENAME)) {
+            Ok(manifest) if manifest.extension.version != self.version => {
+                drift.push(LockDrift::VersionMismatch {
+                    name: name.clone(),
+                    locked: self.version.clone(),
+                    installed: manifest.extension.version,
+                });
+            }
+            Ok(_) => {}
+            Err(e) => drift.push(LockDrift::InvalidManifest {
+                name: name.clone(),
+                reason: e.to_string(),
+            }),
+        }
+
+        if let Some(expected) = &self.commit {
+            let actual = head_commit(&dir);
+            if actual.as_ref() != Some(expected) {
+                drift.push(LockDrift::CommitMismatch {
+                    name,
+                    locked: expected.clone(),
+                    installed: actual,
+                });
+            }
+        }
+        drift
+    }
+}
+
+/// A difference between the lock file and the installed extensions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LockDrift {
+    /// A locked extension is not installed.
+    Missing { name: String },
+    /// A locked extension's installed manifest cannot be read.
+    InvalidManifest { name: String, reason: String },
+    /// The installed manifest version differs from the locked version.
+    VersionMismatch {
+        name: String,
+        locked: String,
+        installed: String,
+    },
+    /// The installed checkout is at a different commit than the locked one,
+    /// or is not a git checkout (`installed` is `None`).
+    CommitMismatch {
+        name: String,
+        locked: String,
+        installed: Option<String>,
+    },
+    /// An extension is installed but not listed in the lock file.
+    Unlocked { name: String },
+}
+
+impl LockDrift {
+    /// Name of the extension that drifted.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Missing { name }
+            | Self::InvalidManifest { name, .. }
+            | Self::VersionMismatch { name, .. }
+            | Self::CommitMismatch { name, .. }
+            | Self::Unlocked { name } => name,
+        }
+    }
+
+    /// What drifted, without the extension name.
+    pub fn detail(&self) -> String {
+        match self {
+            Self::Missing { .. } => "locked but not installed".to_string(),
+            Self::InvalidManifest { reason, .. } => {
+                format!("installed manifest is invalid: {reason}")
+            }
+            Self::VersionMismatch {
+                locked, installed, ..
+            } => format!("installed {installed} but locked {locked}"),
+            Self::CommitMismatch {
+                locked,
+                installed: Some(installed),
+                ..
+            } => format!("HEAD is {installed} but locked {locked}"),
+            Self::CommitMismatch {
+                locked,
+                installed: None,
+                ..
+            } => format!("locked {locked} but not a git checkout"),
+            Self::Unlocked { .. } => "installed but not in the lock file".to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for LockDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name(), self.detail())
+    }
+}
+
+/// Names of the extensions installed under `root`, sorted.
+///
+/// Hidden directories, such as in-progress clones, are skipped.
+fn installed_names(root: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(root.join(EXTENSIONS_DIR)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Commit SHA of `HEAD` for the git checkout at `dir`, if it is one.
+pub fn head_commit(dir: &Path) -> Option<String> {
+    let repo = git2::Repository::open(dir).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.id().to_string())
+}
+
+/// Whether `source` names a git remote rather than a local directory.
+pub fn is_git_url(source: &str) -> bool {
+    source.contains("://") || source.starts_with("git@")
+}
+
+/// An extension cloned into a staging directory and validated, but not yet
+/// installed.
+///
+/// Dropping a staged clone without [`install`](StagedClone::install)ing it
+/// removes the staging directory, so an aborted install leaves nothing
+/// behind.
+#[derive(Debug)]
+pub struct StagedClone {
+    dir: PathBuf,
+    manifest: ExtensionManifest,
+}
+
+impl StagedClone {
+    /// The validated manifest at the root of the clone.
+    pub fn manifest(&self) -> &ExtensionManifest {
+        &self.manifest
+    }
+
+    /// The staging directory holding the clone.
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Move the clone to `dest`, replacing any previous install there.
+    pub fn install(self, dest: &Path) -> Result<()> {
+        if dest.exists() {
+            std::fs::remove_dir_all(dest)?;
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::rename(&self.dir, dest)?;
+        Ok(())
+    }
+}
+
+impl Drop for StagedClone {
+    fn drop(&mut self) {
+        if self.dir.exists()
+            && let Err(e) = std::fs::remove_dir_all(&self.dir)
+        {
+            tracing::warn!("Failed to remove {}: {}", self.dir.display(), e);
+        }
+    }
+}
+
+/// Clone the extension repository at `url` into a staging directory under
+/// [`EXTENSIONS_DIR`].
+///
+/// `git_ref` names a branch, tag, or commit to check out; without one the
+/// remote's default branch is used. The checkout must have a valid
+/// [`MANIFEST_FILENAME`] at its root. On failure the partial clone is
+/// removed.
+pub fn clone_extension(root: &Path, url: &str, git_ref: Option<&str>) -> Result<StagedClone> {
+    let staging = root
+        .join(EXTENSIONS_DIR)
+        .join(format!(".clone-{}", std::process::id()));
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(root.join(EXTENSIONS_DIR))?;
+
+    match clone_into(&staging, url, git_ref) {
+        Ok(manifest) => Ok(StagedClone {
+            dir: staging,
+            manifest,
+        }),
+        Err(e) => {
+            // Report the clone failure, not a failure to clean up after it
+            if staging.exists()
+                && let Err(cleanup) = std::fs::remove_dir_all(&staging)
+            {
+                tracing::warn!("Failed to remove {}: {}", staging.display(), cleanup);
+            }
+            Err(e)
+        }
+    }
+}
+
+fn clone_into(dir: &Path, url: &str, git_ref: Option<&str>) -> Result<ExtensionManifest> {
+    let clone_error = |e: git2::Error| Error::Clone {
+        url: url.to_string(),
+        reason: e.message().to_string(),
+    };
+
+    let repo = git2::Repository::clone(url, dir).map_err(clone_error)?;
+
+    if let Some(git_ref) = git_ref {
+        // Branches only exist as remote-tracking refs after a clone
+        let object = repo
+            .revparse_single(&format!("origin/{git_ref}"))
+            .or_else(|_| repo.revparse_single(git_ref))
+            .map_err(|_| Error::RefNotFound {
+                url: url.to_string(),
+                git_ref: git_ref.to_string(),
+            })?;
+        let commit = object.peel_to_commit().map_err(clone_error)?;
+        repo.checkout_tree(
+            commit.as_object(),
+            Some(git2::build::CheckoutBuilder::new().force()),
+        )
+        .map_err(clone_error)?;
+        repo.set_head_detached(commit.id()).map_err(clone_error)?;
+    }
+
+    let manifest_path = dir.join(MANIFEST_FILENAME);
+    if !manifest_path.is_file() {
+        return Err(Error::ManifestNotFound(
+            Path::new(url).join(MANIFEST_FILENAME),
+        ));
+    }
+    ExtensionManifest::from_path(&manifest_path)
+}
+
+/// Outcome of a single verification check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VerifyCheck {
+    /// Check name (`directory`, `version`, `commit`, `entry_points`).
+    pub check: &'static str,
+    /// Whether the check passed.
+    pub passed: bool,
+    /// What was found, or why the check failed.
+    pub detail: String,
+}
+
+impl VerifyCheck {
+    fn pass(check: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            check,
+            passed: true,
+            detail: detail.into(),
+        }
+    }
+
+    fn fail(check: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            check,
+            passed: false,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Verification result for one locked extension.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtensionVerification {
+    /// Extension name.
+    pub name: String,
+    /// Whether every check passed.
+    pub passed: bool,
+    /// Individual check results.
+    pub checks: Vec<VerifyCheck>,
+}
+
+/// Verification result for all locked extensions.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyReport {
+    /// Whether every locked extension passed and nothing is installed
+    /// outside the lock.
+    pub passed: bool,
+    /// Per-extension results, in lock file order.
+    pub extensions: Vec<ExtensionVerification>,
+    /// Installed extensions the lock does not list
+    /// ([`LockDrift::Unlocked`]); other drift is reported in the
+    /// per-extension checks.
+    pub drift: Vec<LockDrift>,
+}
+
+impl VerifyReport {
+    /// Extensions that failed at least one check.
+    pub fn failures(&self) -> impl Iterator<Item = &ExtensionVerification> {
+        self.extensions.iter().filter(|e| !e.passed)
+    }
+}
+
+/// Verify installed extensions against the lock file.
+///
+/// For every [`LockedExtension`], turns the differences found by
+/// [`LockFile::verify`] (directory, manifest version, checked-out commit)
+/// into checks, and checks that the resolved entry point scripts exist on
+/// disk. Installed extensions missing from the lock are reported in
+/// [`VerifyReport::drift`].
+///
+/// # Errors
+///
+/// Returns an error only if the lock file itself cannot be read; problems
+/// with individual extensions are reported as failed checks.
+pub fn verify(root: &Path) -> Result<VerifyReport> {
+    let lock = LockFile::load(root)?;
+    let (unlocked, drift): (Vec<_>, Vec<_>) = lock
+        .verify(root)
+        .into_iter()
+        .partition(|d| matches!(d, LockDrift::Unlocked { .. }));
+
+    let extensions: Vec<_> = lock
+        .extensions
+        .iter()
+        .map(|locked| {
+            let own: Vec<&LockDrift> = drift.iter().filter(|d| d.name() == locked.name).collect();
+            verify_extension(root, locked, &own)
+        })
+        .collect();
+
+    Ok(VerifyReport {
+        passed: extensions.iter().all(|e| e.passed) && unlocked.is_empty(),
+        extensions,
+        drift: unlocked,
+    })
+}
+
+/// Checks for one locked extension, given its drift from the lock.
+fn verify_extension(
+    root: &Path,
+    locked: &LockedExtension,
+    drift: &[&LockDrift],
+) -> ExtensionVerification {
+    let dir = extension_dir(root, &locked.name);
+    let mut checks = Vec::new();
+
+    if drift.iter().any(|d| matches!(d, LockDrift::Missing { .. })) {
+        checks.push(VerifyCheck::fail(
+            "directory",
+            format!("{} does not exist", dir.display()),
+        ));
+        return finish(locked, checks);
+    }
+    checks.push(VerifyCheck::pass("directory", dir.display().to_string()));
+
+    let version = drift.iter().find(|d| {
+        matches!(
+            d,
+            LockDrift::VersionMismatch { .. } | LockDrift::InvalidManifest { .. }
+        )
+    });
+    match version {
+        Some(d) => checks.push(VerifyCheck::fail("version", d.detail())),
+        None => checks.push(VerifyCheck::pass("version", locked.version.clone())),
+    }
+
+    if let Some(expected) = &locked.commit {
+        match drift
+            .iter()
+            .find(|d| matches!(d, LockDrift::CommitMismatch { .. }))
+        {
+            Some(d) => checks.push(VerifyCheck::fail("commit", d.detail())),
+            None => checks.push(VerifyCheck::pass("commit", expected.clone())),
+        }
+    }
+
+    let Ok(manifest) = ExtensionManifest::from_path(&dir.join(MANIFEST_FILENAME)) else {
+        return finish(locked, checks);
+    };
+    if let Some(entry_points) = &manifest.entry_points {
+        // Only the script paths matter here, so the interpreter is nominal
+        let resolved = entry_points.resolve(Path::new("python"), &dir);
+        let missing: Vec<String> = [resolved.cli, resolved.mcp]
+            .into_iter()
+            .flatten()
+            .filter_map(|cmd| cmd.args.into_iter().next())
+            .filter(|script| !Path::new(script).exists())
+            .collect();
+
+        if missing.is_empty() {
+            checks.push(VerifyCheck::pass("entry_points", "all present"));
+        } else {
+            checks.push(VerifyCheck::fail(
+                "entry_points",
+                format!("missing: {}", missing.join(", ")),
+            ));
+        }
+    }
+
+    finish(locked, checks)
+}
+
+fn finish(locked: &LockedExtension, checks: Vec<VerifyCheck>) -> ExtensionVerification {
+    ExtensionVerification {
+        name: locked.name.clone(),
+        passed: checks.iter().all(|c| c.passed),
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn install(root: &Path, name: &str, version: &str, extra: &str) -> PathBuf {
+        let dir = extension_dir(root, name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(MANIFEST_FILENAME),
+            format!("[extension]\nname = \"{name}\"\nversion = \"{version}\"\n{extra}"),
+        )
+        .unwrap();
+        dir
+    }
+
+    fn lock(root: &Path, entries: Vec<LockedExtension>) {
+        let mut lock = LockFile::default();
+        for entry in entries {
+            lock.upsert(entry);
+        }
+        lock.save(root).unwrap();
+    }
+
+    fn locked(name: &str, version: &str, commit: Option<&str>) -> LockedExtension {
+        LockedExtension {
+            name: name.to_string(),
+            version: version.to_string(),
+            source: format!("https://example.com/{name}"),
+            commit: commit.map(str::to_string),
+        }
+    }
+
+    fn check<'a>(report: &'a VerifyReport, ext: &str, check: &str) -> &'a VerifyCheck {
+        report
+            .extensions
+            .iter()
+            .find(|e| e.name == ext)
+            .and_then(|e| e.checks.iter().find(|c| c.check == check))
+            .unwrap_or_else(|| panic!("no {check} check for {ext}"))
+    }
+
+    fn init_git(dir: &Path) -> String {
+        let repo = git2::Repository::init(dir).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap()
+            .to_string()
+    }
+
+    /// Create a bare repository whose `HEAD` holds `files`, returning its
+    /// `file://` URL and the commit.
+    fn bare_repo(dir: &Path, files: &[(&str, &str)]) -> (String, git2::Oid) {
+        let repo = git2::Repository::init_bare(dir).unwrap();
+        let mut tree = repo.treebuilder(None).unwrap();
+        for (name, content) in files {
+            let blob = repo.blob(content.as_bytes()).unwrap();
+            tree.insert(name, blob, 0o100644).unwrap();
+        }
+        let tree = repo.find_tree(tree.write().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let commit = repo
+            .commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+        (format!("file://{}", dir.display()), commit)
+    }
+
+    #[test]
+    fn test_is_git_url() {
+        assert!(is_git_url("https://github.com/example/ext.git"));
+        assert!(is_git_url("file:///tmp/ext.git"));
+        assert!(is_git_url("git@github.com:example/ext.git"));
+        assert!(!is_git_url("./extensions/ext"));
+        assert!(!is_git_url("/abs/path"));
+    }
+
+    #[test]
+    fn test_clone_extension_installs_validated_checkout() {
+        let temp = TempDir::new().unwrap();
+        let (url, commit) = bare_repo(
+            &temp.path().join("ext.git"),
+            &[(
+                MANIFEST_FILENAME,
+                "[extension]\nname = \"ext\"\nversion = \"0.1.0\"\n",
+            )],
+        );
+        let root = temp.path().join("repo");
+
+        let staged = clone_extension(&root, &url, Some(&commit.to_string())).unwrap();
+        let manifest = staged.manifest().clone();
+        assert_eq!(manifest.extension.name, "ext");
+        let dest = extension_dir(&root, "ext");
+        staged.install(&dest).unwrap();
+        let mut lock = LockFile::load(&root).unwrap();
+        lock.upsert(LockedExtension::new(&manifest, &url, &dest));
+        lock.save(&root).unwrap();
+
+        assert!(dest.join(MANIFEST_FILENAME).is_file());
+        assert_eq!(head_commit(&dest), Some(commit.to_string()));
+        let leftovers: Vec<_> = fs::read_dir(root.join(EXTENSIONS_DIR)).unwrap().collect();
+        assert_eq!(leftovers.len(), 1, "only the installed extension remains");
+
+        let lock = LockFile::load(&root).unwrap();
+        let entry = lock.get("ext").unwrap();
+        assert_eq!(entry.source, url);
+        assert_eq!(entry.version, "0.1.0");
+        assert_eq!(entry.commit, Some(commit.to_string()));
+        assert!(lock.verify(&root).is_empty());
+    }
+
+    #[test]
+    fn test_clone_extension_cleans_up_on_failure() {
+        let temp = TempDir::new().unwrap();
+        let (url, _) = bare_repo(
+            &temp.path().join("ext.git"),
+            &[("README.md", "no manifest")],
+        );
+        let root = temp.path().join("repo");
+
+        let err = clone_extension(&root, &url, None).unwrap_err();
+        assert!(matches!(err, Error::ManifestNotFound(_)), "got: {err}");
+        let err = clone_extension(&root, &url, Some("no-such-ref")).unwrap_err();
+        assert!(matches!(err, Error::RefNotFound { .. }), "got: {err}");
+
+        let leftovers: Vec<_> = fs::read_dir(root.join(EXTENSIONS_DIR)).unwrap().collect();
+        assert!(leftovers.is_empty(), "partial clones must be removed");
+        assert!(!root.join(LOCK_FILE).exists(), "nothing is locked");
+    }
+
+    #[test]
+    fn test_lock_file_round_trip() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(LockFile::load(temp.path()).unwrap(), LockFile::default());
+
+        lock(
+            temp.path(),
+            vec![
+                locked("zeta", "1.0.0", None),
+                locked("alpha", "0.1.0", Some("abc")),
+            ],
+        );
+
+        let loaded = LockFile::load(temp.path()).unwrap();
+        let names: Vec<_> = loaded.extensions.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["alpha", "zeta"]);
+        assert_eq!(loaded.get("alpha").unwrap().commit.as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn test_lock_file_upsert_and_remove() {
+        let mut lock = LockFile::default();
+        lock.upsert(locked("ext", "0.1.0", None));
+        lock.upsert(locked("ext", "0.2.0", None));
+        assert_eq!(lock.extensions.len(), 1);
+        assert_eq!(lock.get("ext").unwrap().version, "0.2.0");
+
+        assert!(lock.remove("ext").is_some());
+        assert!(lock.remove("ext").is_none());
+    }
+
+    #[test]
+    fn test_verify_passes_for_matching_install() {
+        let temp = TempDir::new().unwrap();
+        let dir = install(
+            temp.path(),
+            "ext",
+            "0.1.0",
+            "[entry_points]\ncli = \"cli.py\"\nmcp = \"server.py serve\"\n",
+        );
+        fs::write(dir.join("cli.py"), "").unwrap();
+        fs::write(dir.join("server.py"), "").unwrap();
+        let commit = init_git(&dir);
+        lock(temp.path(), vec![locked("ext", "0.1.0", Some(&commit))]);
+
+        let report = verify(temp.path()).unwrap();
+        assert!(report.passed, "{:?}", report);
+        assert_eq!(report.failures().count(), 0);
+        assert!(check(&report, "ext", "commit").passed);
+        assert!(check(&report, "ext", "entry_points").passed);
+    }
+
+    #[test]
+    fn test_verify_reports_each_mismatch() {
+        let temp = TempDir::new().unwrap();
+        install(temp.path(), "bumped", "0.2.0", "");
+        let dir = install(
+            temp.path(),
+            "moved",
+            "1.0.0",
+            "[entry_points]\nmcp = \"server.py serve\"\n",
+        );
+        init_git(&dir);
+        lock(
+            temp.path(),
+            vec![
+                locked("bumped", "0.1.0", None),
+                locked(
+                    "moved",
+                    "1.0.0",
+                    Some("0000000000000000000000000000000000000000"),
+                ),
+                locked("gone", "1.0.0", None),
+            ],
+        );
+
+        let report = verify(temp.path()).unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.failures().count(), 3);
+
+        assert!(!check(&report, "bumped", "version").passed);
+        assert!(
+            check(&report, "bumped", "version")
+                .detail
+                .contains("locked 0.1.0")
+        );
+        assert!(!check(&report, "moved", "commit").passed);
+        assert!(!check(&report, "moved", "entry_points").passed);
+        assert!(
+            check(&report, "moved", "entry_points")
+                .detail
+                .contains("server.py")
+        );
+        assert!(!check(&report, "gone", "directory").passed);
+        // Mismatches of locked extensions are not repeated as drift
+        assert!(report.drift.is_empty(), "{:?}", report.drift);
+    }
+
+    #[test]
+    fn test_verify_locked_commit_without_checkout_fails() {
+        let temp = TempDir::new().unwrap();
+        install(temp.path(), "ext", "0.1.0", "");
+        lock(temp.path(), vec![locked("ext", "0.1.0", Some("abc"))]);
+
+        let report = verify(temp.path()).unwrap();
+        let commit = check(&report, "ext", "commit");
+        assert!(!commit.passed);
+        assert!(commit.detail.contains("not a git checkout"));
+    }
+
+    #[test]
+    fn test_verify_empty_lock_passes() {
+        let temp = TempDir::new().unwrap();
+        let report = verify(temp.path()).unwrap();
+        assert!(report.passed);
+        assert!(report.extensions.is_empty());
+    }
+
+    #[test]
+    fn test_lock_verify_reports_version_drift() {
+        let temp = TempDir::new().unwrap();
+        install(temp.path(), "ext", "0.2.0", "");
+        lock(temp.path(), vec![locked("ext", "0.1.0", None)]);
+
+        let drift = LockFile::load(temp.path()).unwrap().verify(temp.path());
+        assert_eq!(
+            drift,
+            vec![LockDrift::VersionMismatch {
+                name: "ext".to_string(),
+                locked: "0.1.0".to_string(),
+                installed: "0.2.0".to_string(),
+            }]
+        );
+        assert_eq!(
+            drift[0].to_string(),
+            "ext: installed 0.2.0 but locked 0.1.0"
+        );
+    }
+
+    #[test]
+    fn test_lock_verify_reports_missing_and_unlocked() {
+        let temp = TempDir::new().unwrap();
+        install(temp.path(), "extra", "1.0.0", "");
+        fs::create_dir_all(temp.path().join(EXTENSIONS_DIR).join(".clone-1")).unwrap();
+        lock(temp.path(), vec![locked("gone", "1.0.0", None)]);
+
+        let drift = LockFile::load(temp.path()).unwrap().verify(temp.path());
+        assert_eq!(
+            drift,
+            vec![
+                LockDrift::Missing {
+                    name: "gone".to_string()
+                },
+                LockDrift::Unlocked {
+                    name: "extra".to_string()
+                },
+            ]
+        );
+
+        let report = verify(temp.path()).unwrap();
+        assert!(!report.passed);
+        assert!(!check(&report, "gone", "directory").passed);
+        // Each problem is reported once: the missing extension as a failed
+        // check, the unlocked one as drift
+        assert_eq!(report.drift, drift[1..]);
+    }
+}
@@ -1,10 +1,13 @@
 //! Extension system for Repository Manager.
 //!
 //! This crate provides manifest parsing, configuration, MCP resolution,
-//! and a registry for repository-manager extensions.
+//! preset dependency resolution, install locking and verification, and a
+//! registry for repository-manager extensions.
 
 pub mod config;
+pub mod deps;
 pub mod error;
+pub mod installer;
 pub mod manifest;
 pub mod mcp;
 pub mod registry;
@@ -16,7 +19,19 @@ pub mod registry;
 pub const MANIFEST_FILENAME: &str = "repo_extension.toml";
 
 pub use config::ExtensionConfig;
+pub use deps::{
+    DependencyCycle, DependencyGraph, NodeId, PresetDependency, PresetRequirement,
+    VersionConstraint,
+};
 pub use error::Error;
-pub use manifest::{EntryPoints, ExtensionManifest, Provides, ResolvedCommand, ResolvedEntryPoints};
-pub use mcp::{ResolveContext, merge_mcp_configs, resolve_mcp_config};
+pub use installer::{
+    ExtensionVerification, LockDrift, LockFile, LockedExtension, StagedClone, VerifyCheck,
+    VerifyReport, clone_extension, is_git_url, verify,
+};
+pub use manifest::{
+    EntryPoints, ExtensionManifest, Provides, ResolvedCommand, ResolvedEntryPoints,
+};
+pub use mcp::{
+    McpConflict, McpMergePolicy, MergeReport, ResolveContext, merge_mcp_configs, resolve_mcp_config,
+};
 pub use registry::{ExtensionEntry, ExtensionRegistry};
@@ -13,6 +13,9 @@
 //! version = "0.1.0"
 //! description = "A governed development framework for AI agents"
 //!
+//! [requires]
+//! extensions = ["vaultspec-core"]
+//!
 //! [requires.python]
 //! version = ">=3.13"
 //!
@@ -83,6 +86,10 @@ pub struct Requirements {
     /// Python version requirement.
     #[serde(default)]
     pub python: Option<PythonRequirement>,
+
+    /// Names of other extensions this one depends on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<String>,
 }
 
 /// Python version requirement.
@@ -201,6 +208,17 @@ pub struct Provides {
     /// Content types this extension manages.
     #[serde(default)]
     pub content_types: Vec<String>,
+    /// Slugs of the tool integrations this extension writes configuration
+    /// for. A slug also supplied by a built-in or a `.repository/tools/`
+    /// definition is a collision, which the repository side wins.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// Tool definition files, relative to the extension directory, in the
+    /// format of `.repository/tools/`. Each one adds a tool that is synced
+    /// like a repository definition; its slug must also be listed in
+    /// `tools`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_definitions: Vec<String>,
 }
 
 /// Output directory/file mappings.
@@ -662,4 +680,21 @@ content_types = ["rules"]
         let provides = manifest.provides.unwrap();
         assert!(provides.mcp_config.is_none());
     }
+
+    #[test]
+    fn test_parse_provides_tool_definitions() {
+        let toml = r#"
+[extension]
+name = "tool-ext"
+version = "1.0.0"
+
+[provides]
+tools = ["mytool"]
+tool_definitions = ["tools/mytool.toml"]
+"#;
+        let manifest = ExtensionManifest::from_toml(toml).unwrap();
+        let provides = manifest.provides.unwrap();
+        assert_eq!(provides.tools, vec!["mytool"]);
+        assert_eq!(provides.tool_definitions, vec!["tools/mytool.toml"]);
+    }
 }
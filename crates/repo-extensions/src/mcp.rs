@@ -13,9 +13,17 @@
 //! | `{{runtime.python}}`   | Absolute path to the extension's Python venv      |
 //! | `{{root}}`             | Absolute path to the repository / container root   |
 //! | `{{extension.source}}` | Absolute path to the extension's source directory  |
+//!
+//! # Conflicts
+//!
+//! When two extensions define a server with the same name but a different
+//! configuration, [`merge_mcp_configs`] resolves it according to an
+//! [`McpMergePolicy`] and lists it in the returned [`MergeReport`].
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::error::{Error, Result};
@@ -33,6 +41,51 @@ pub struct ResolveContext {
     pub python_path: Option<String>,
 }
 
+/// How [`merge_mcp_configs`] resolves a server name defined differently by
+/// two extensions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpMergePolicy {
+    /// The later extension's definition replaces the earlier one.
+    #[default]
+    LastWins,
+    /// Merging fails with [`Error::McpServerConflict`].
+    Error,
+    /// The later definition is kept as `<server>-<extension>`.
+    Rename,
+}
+
+/// A server name defined differently by two extensions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpConflict {
+    /// The contested server name.
+    pub server_name: String,
+    /// Extension whose definition was merged first.
+    pub first_source: String,
+    /// Extension whose definition clashed with it.
+    pub second_source: String,
+    /// Definition from `first_source`.
+    pub first_value: Value,
+    /// Definition from `second_source`.
+    pub second_value: Value,
+    /// Name the second definition was stored under, if it was renamed.
+    pub renamed_to: Option<String>,
+}
+
+/// Outcome of [`merge_mcp_configs`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeReport {
+    /// Conflicts found, in merge order.
+    pub conflicts: Vec<McpConflict>,
+}
+
+impl MergeReport {
+    /// Whether the merge found no conflicts.
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
 /// Read an `mcp.json` from an extension and resolve template variables.
 ///
 /// # Arguments
@@ -136,27 +189,77 @@ pub fn resolve_mcp_config(
 
 /// Collect MCP configs from all extensions into a single merged object.
 ///
-/// Each extension contributes its own MCP server entries. If two extensions
-/// define the same server name, the later one wins (last-write-wins) and a
-/// warning is emitted.
-pub fn merge_mcp_configs(configs: &[Value]) -> Value {
+/// `configs` pairs each extension name with its resolved config, in merge
+/// order. A server defined identically by several extensions is not a
+/// conflict; one defined differently is resolved by `policy` and listed in
+/// the report.
+///
+/// # Errors
+///
+/// Returns [`Error::McpServerConflict`] on the first conflict when `policy`
+/// is [`McpMergePolicy::Error`].
+pub fn merge_mcp_configs(
+    configs: &[(String, Value)],
+    policy: McpMergePolicy,
+) -> Result<(Value, MergeReport)> {
     let mut merged = serde_json::Map::new();
+    let mut sources: HashMap<String, String> = HashMap::new();
+    let mut report = MergeReport::default();
 
-    for config in configs {
-        if let Some(obj) = config.as_object() {
-            for (key, value) in obj {
-                if merged.contains_key(key) {
+    for (extension, config) in configs {
+        let Some(obj) = config.as_object() else {
+            continue;
+        };
+        for (key, value) in obj {
+            let first = match merged.get(key) {
+                Some(existing) if existing != value => existing.clone(),
+                _ => {
+                    merged.insert(key.clone(), value.clone());
+                    sources.insert(key.clone(), extension.clone());
+                    continue;
+                }
+            };
+            let first_source = sources.get(key).cloned().unwrap_or_default();
+
+            let renamed_to = match policy {
+                McpMergePolicy::Error => {
+                    return Err(Error::McpServerConflict {
+                        server: key.clone(),
+                        first: first_source,
+                        second: extension.clone(),
+                    });
+                }
+                McpMergePolicy::LastWins => {
                     tracing::warn!(
-                        "MCP server '{}' defined by multiple extensions — last definition wins",
-                        key
+                        "MCP server '{}' defined by both '{}' and '{}' — last definition wins",
+                        key,
+                        first_source,
+                        extension
                     );
+                    merged.insert(key.clone(), value.clone());
+                    sources.insert(key.clone(), extension.clone());
+                    None
                 }
-                merged.insert(key.clone(), value.clone());
-            }
+                McpMergePolicy::Rename => {
+                    let renamed = format!("{}-{}", key, extension);
+                    merged.insert(renamed.clone(), value.clone());
+                    sources.insert(renamed.clone(), extension.clone());
+                    Some(renamed)
+                }
+            };
+
+            report.conflicts.push(McpConflict {
+                server_name: key.clone(),
+                first_source,
+                second_source: extension.clone(),
+                first_value: first,
+                second_value: value.clone(),
+                renamed_to,
+            });
         }
     }
 
-    Value::Object(merged)
+    Ok((Value::Object(merged), report))
 }
 
 /// Recursively resolve `{{...}}` template variables in all JSON string values.
@@ -344,23 +447,71 @@ version = "1.0.0"
         assert!(result.is_err());
     }
 
+    fn conflicting_configs() -> Vec<(String, Value)> {
+        vec![
+            ("ext-a".to_string(), json!({"server": {"command": "old"}})),
+            ("ext-b".to_string(), json!({"server": {"command": "new"}})),
+        ]
+    }
+
     #[test]
     fn test_merge_mcp_configs() {
-        let a = json!({"server-a": {"command": "a"}});
-        let b = json!({"server-b": {"command": "b"}});
-        let merged = merge_mcp_configs(&[a, b]);
+        let a = json!({"server-a": {"command": "a"}, "shared": {"command": "s"}});
+        let b = json!({"server-b": {"command": "b"}, "shared": {"command": "s"}});
+        let (merged, report) = merge_mcp_configs(
+            &[("a".to_string(), a), ("b".to_string(), b)],
+            McpMergePolicy::Error,
+        )
+        .unwrap();
 
         assert!(merged["server-a"].is_object());
         assert!(merged["server-b"].is_object());
+        // Identical definitions are not a conflict
+        assert!(report.is_clean());
     }
 
     #[test]
     fn test_merge_mcp_configs_last_wins() {
-        let a = json!({"server": {"command": "old"}});
-        let b = json!({"server": {"command": "new"}});
-        let merged = merge_mcp_configs(&[a, b]);
+        let (merged, report) =
+            merge_mcp_configs(&conflicting_configs(), McpMergePolicy::LastWins).unwrap();
 
         assert_eq!(merged["server"]["command"], "new");
+        assert_eq!(
+            report.conflicts,
+            vec![McpConflict {
+                server_name: "server".to_string(),
+                first_source: "ext-a".to_string(),
+                second_source: "ext-b".to_string(),
+                first_value: json!({"command": "old"}),
+                second_value: json!({"command": "new"}),
+                renamed_to: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_merge_mcp_configs_error_policy() {
+        let err = merge_mcp_configs(&conflicting_configs(), McpMergePolicy::Error).unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::McpServerConflict { ref server, ref first, ref second }
+                if server == "server" && first == "ext-a" && second == "ext-b"
+        ));
+    }
+
+    #[test]
+    fn test_merge_mcp_configs_rename_policy() {
+        let (merged, report) =
+            merge_mcp_configs(&conflicting_configs(), McpMergePolicy::Rename).unwrap();
+
+        assert_eq!(merged["server"]["command"], "old");
+        assert_eq!(merged["server-ext-b"]["command"], "new");
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(
+            report.conflicts[0].renamed_to.as_deref(),
+            Some("server-ext-b")
+        );
     }
 
     #[test]
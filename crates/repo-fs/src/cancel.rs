@@ -0,0 +1,104 @@
+//! Cancellation of in-flight operations
+//!
+//! A caller that stops waiting for an operation (such as the MCP server
+//! when a tool call times out) cancels its [`CancelToken`]. Work running
+//! on the thread that [entered](CancelToken::enter) the token checks
+//! [`is_cancelled`] at its own pace: subprocesses are killed, git remote
+//! operations abort at their next callback, and the work returns early.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// How often a running subprocess is checked for exit or cancellation
+pub const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+/// Shared flag that tells running work to stop
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Create a token that has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the work running under this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Run the calling thread's work under this token until the guard drops.
+    pub fn enter(&self) -> CancelScope {
+        let previous = CURRENT.with(|current| current.replace(Some(self.clone())));
+        CancelScope { previous }
+    }
+}
+
+/// Guard returned by [`CancelToken::enter`]
+#[must_use = "the token is only current while the scope is held"]
+#[derive(Debug)]
+pub struct CancelScope {
+    previous: Option<CancelToken>,
+}
+
+impl Drop for CancelScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// The calling thread's current token, if it entered one
+///
+/// Async work that may be polled from another thread captures this before
+/// its first await.
+pub fn current() -> Option<CancelToken> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Whether the calling thread's current token has been cancelled
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(CancelToken::is_cancelled)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_restores_previous_token() {
+        let outer = CancelToken::new();
+        let inner = CancelToken::new();
+        inner.cancel();
+
+        let _outer = outer.enter();
+        {
+            let _inner = inner.enter();
+            assert!(is_cancelled());
+        }
+        assert!(!is_cancelled());
+        assert!(current().is_some_and(|token| !token.is_cancelled()));
+    }
+
+    #[test]
+    fn test_no_token_is_never_cancelled() {
+        assert!(current().is_none());
+        assert!(!is_cancelled());
+    }
+}
@@ -4,31 +4,67 @@
 //! the workspace for content integrity verification and drift detection.
 
 use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
 use std::path::Path;
 
 /// Prefix for all checksums produced by this module
 const PREFIX: &str = "sha256:";
 
+/// Chunk size [`compute_file_checksum`] reads files in
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
 /// Compute the SHA-256 checksum of string content.
 ///
 /// Returns a string in the canonical format `"sha256:<hex>"`.
 pub fn compute_content_checksum(content: &str) -> String {
+    compute_bytes_checksum(content.as_bytes())
+}
+
+/// Compute the SHA-256 checksum of raw bytes.
+///
+/// Returns a string in the canonical format `"sha256:<hex>"`.
+pub fn compute_bytes_checksum(content: &[u8]) -> String {
     let mut hasher = Sha256::new();
-    hasher.update(content.as_bytes());
+    hasher.update(content);
     format!("{}{:x}", PREFIX, hasher.finalize())
 }
 
 /// Compute the SHA-256 checksum of a file's contents.
 ///
-/// Returns a string in the canonical format `"sha256:<hex>"`.
+/// The file is streamed in chunks of [`DEFAULT_CHUNK_SIZE`] bytes rather
+/// than read into memory. Returns a string in the canonical format
+/// `"sha256:<hex>"`.
 ///
 /// # Errors
 ///
 /// Returns an error if the file cannot be read.
 pub fn compute_file_checksum(path: &Path) -> std::io::Result<String> {
-    let content = std::fs::read(path)?;
+    compute_file_checksum_chunked(path, DEFAULT_CHUNK_SIZE)
+}
+
+/// Compute the SHA-256 checksum of a file's contents, reading it in chunks
+/// of `chunk_size` bytes (at least one).
+///
+/// Returns the same checksum as [`compute_file_checksum`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read.
+pub fn compute_file_checksum_chunked(path: &Path, chunk_size: usize) -> std::io::Result<String> {
+    let mut reader = BufReader::with_capacity(chunk_size.max(1), File::open(path)?);
     let mut hasher = Sha256::new();
-    hasher.update(&content);
+    loop {
+        let chunk = match reader.fill_buf() {
+            Ok([]) => break,
+            Ok(chunk) => chunk,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(chunk);
+        let len = chunk.len();
+        reader.consume(len);
+    }
     Ok(format!("{}{:x}", PREFIX, hasher.finalize()))
 }
 
@@ -75,4 +111,39 @@ mod tests {
         let content_cs = compute_content_checksum("hello world");
         assert_eq!(file_cs, content_cs);
     }
+
+    #[test]
+    fn streamed_checksum_matches_whole_file_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("large.md");
+        // 3 MiB plus a partial chunk, with content varying across chunks
+        let content: Vec<u8> = (0..3 * 1024 * 1024 + 1234)
+            .map(|i: usize| (i % 251) as u8)
+            .collect();
+        std::fs::write(&path, &content).unwrap();
+
+        let whole = compute_bytes_checksum(&std::fs::read(&path).unwrap());
+        assert_eq!(compute_file_checksum(&path).unwrap(), whole);
+        for chunk_size in [1000, 4096, DEFAULT_CHUNK_SIZE, 8 * 1024 * 1024] {
+            assert_eq!(
+                compute_file_checksum_chunked(&path, chunk_size).unwrap(),
+                whole,
+                "chunk size {}",
+                chunk_size
+            );
+        }
+    }
+
+    #[test]
+    fn streamed_checksum_of_small_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("small");
+        for content in ["", "hello world"] {
+            std::fs::write(&path, content).unwrap();
+            let expected = compute_content_checksum(content);
+            assert_eq!(compute_file_checksum(&path).unwrap(), expected);
+            // A chunk size of zero reads a byte at a time
+            assert_eq!(compute_file_checksum_chunked(&path, 0).unwrap(), expected);
+        }
+    }
 }
@@ -2,18 +2,42 @@
 
 use crate::{Error, NormalizedPath, Result, io};
 use serde::{Serialize, de::DeserializeOwned};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How long to wait between attempts to take a contended config lock.
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 /// Format-agnostic configuration store.
 ///
 /// Automatically detects format from file extension and handles
 /// serialization/deserialization transparently.
-#[derive(Debug, Default)]
+///
+/// Reads take a shared advisory lock and writes an exclusive one, both on
+/// a `<file>.store.lock` file, so a reader never sees a half-written file.
+/// A lock not acquired within [`io::RobustnessConfig::lock_timeout`] fails
+/// with [`Error::ConfigLocked`].
+///
+/// The lock file is kept after use. It is not the `<file>.lock` sidecar of
+/// [`io::write_atomic`], which is removed after every write: a lock file
+/// that can be unlinked while held no longer excludes a process that opens
+/// the path afterwards. Files read through a `ConfigStore` must therefore
+/// also be written through one.
+#[derive(Debug)]
 pub struct ConfigStore {
     robustness: io::RobustnessConfig,
 }
 
+impl Default for ConfigStore {
+    fn default() -> Self {
+        Self::with_robustness(io::RobustnessConfig::current())
+    }
+}
+
 impl ConfigStore {
-    /// Create a new ConfigStore with default robustness settings.
+    /// Create a new ConfigStore with the
+    /// [current](io::RobustnessConfig::current) robustness settings.
     pub fn new() -> Self {
         Self::default()
     }
@@ -30,7 +54,7 @@ impl ConfigStore {
     /// - `.json` -> JSON
     /// - `.yaml`, `.yml` -> YAML
     pub fn load<T: DeserializeOwned>(&self, path: &NormalizedPath) -> Result<T> {
-        let content = io::read_text(path)?;
+        let content = self.load_text(path)?;
         let extension = path.extension().unwrap_or("");
 
         match extension.to_lowercase().as_str() {
@@ -85,6 +109,99 @@ impl ConfigStore {
             }
         };
 
-        io::write_atomic(path, content.as_bytes(), self.robustness)
+        self.save_text(path, &content)
+    }
+
+    /// Read a configuration file as text under a shared lock.
+    pub fn load_text(&self, path: &NormalizedPath) -> Result<String> {
+        // Reading a missing file fails anyway; don't leave a lock file behind
+        if !path.exists() {
+            return io::read_text(path);
+        }
+        let _lock = self.acquire(path, false)?;
+        io::read_text(path)
+    }
+
+    /// Write text to a configuration file atomically under an exclusive lock.
+    pub fn save_text(&self, path: &NormalizedPath, content: &str) -> Result<()> {
+        self.lock(path)?.write(content)
+    }
+
+    /// Take an exclusive lock on a configuration file.
+    ///
+    /// Use this for read-modify-write cycles, so no other writer can change
+    /// the file between the read and the write. The lock is released when
+    /// the returned [`ConfigLock`] is dropped.
+    pub fn lock(&self, path: &NormalizedPath) -> Result<ConfigLock> {
+        if let Some(parent) = path.to_native().parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::io(parent, e))?;
+        }
+        let file = self.acquire(path, true)?;
+        Ok(ConfigLock {
+            _file: file,
+            path: path.clone(),
+            robustness: self.robustness,
+        })
+    }
+
+    /// Lock the store lock file of `path`, polling until the lock timeout.
+    fn acquire(&self, path: &NormalizedPath, exclusive: bool) -> Result<File> {
+        let lock_path = store_lock_path(&path.to_native());
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .map_err(|e| Error::io(&lock_path, e))?;
+
+        let deadline = Instant::now() + self.robustness.lock_timeout;
+        loop {
+            let attempt = if exclusive {
+                file.try_lock()
+            } else {
+                file.try_lock_shared()
+            };
+            match attempt {
+                Ok(()) => return Ok(file),
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    std::thread::sleep(LOCK_POLL_INTERVAL);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(Error::ConfigLocked {
+                        path: path.to_native(),
+                    });
+                }
+                Err(TryLockError::Error(e)) => return Err(Error::io(&lock_path, e)),
+            }
+        }
+    }
+}
+
+/// Path of the file [`ConfigStore`] locks to coordinate access to `path`.
+fn store_lock_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".store.lock");
+    PathBuf::from(name)
+}
+
+/// An exclusive lock on a configuration file, from [`ConfigStore::lock`].
+///
+/// Reads and writes through the lock do not lock again.
+#[derive(Debug)]
+pub struct ConfigLock {
+    _file: File,
+    path: NormalizedPath,
+    robustness: io::RobustnessConfig,
+}
+
+impl ConfigLock {
+    /// Read the locked file as text.
+    pub fn read(&self) -> Result<String> {
+        io::read_text(&self.path)
+    }
+
+    /// Replace the locked file's content atomically.
+    pub fn write(&self, content: &str) -> Result<()> {
+        io::write_atomic_unlocked(&self.path.to_native(), content.as_bytes(), &self.robustness)
     }
 }
@@ -47,8 +47,26 @@ pub enum Error {
     #[error("Lock acquisition failed for {path}")]
     LockFailed { path: PathBuf },
 
+    #[error("Config file {path} is locked by another process")]
+    ConfigLocked { path: PathBuf },
+
+    #[error("Invalid UTF-8 in {path} at byte {byte_offset}")]
+    InvalidUtf8 { path: PathBuf, byte_offset: usize },
+
     #[error("Refusing to write through symlink: {path}")]
     SymlinkInPath { path: PathBuf },
+
+    #[error(
+        "Write verification failed for {path}: expected {expected_len} bytes ({expected_checksum}), \
+         read back {actual_len} bytes ({actual_checksum})"
+    )]
+    WriteVerification {
+        path: PathBuf,
+        expected_len: usize,
+        actual_len: usize,
+        expected_checksum: String,
+        actual_checksum: String,
+    },
 }
 
 impl Error {
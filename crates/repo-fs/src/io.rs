@@ -1,13 +1,25 @@
 //! Atomic I/O operations with file locking
 
+use crate::checksum::compute_bytes_checksum;
 use crate::{Error, NormalizedPath, Result};
 use fs2::FileExt;
-use std::fs::{self, OpenOptions};
+use std::cell::Cell;
+use std::fs::{self, File, OpenOptions};
 use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use backoff::ExponentialBackoff;
 use std::time::Duration;
 
+/// Counter distinguishing the temp files of concurrent [`write_atomic_unlocked`] calls.
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Configuration entered with [`RobustnessConfig::enter`], if any.
+    static CURRENT: Cell<Option<RobustnessConfig>> = const { Cell::new(None) };
+}
+
 /// Configuration for filesystem robustness and performance trade-offs.
 #[derive(Debug, Clone, Copy)]
 pub struct RobustnessConfig {
@@ -19,6 +31,40 @@ pub struct RobustnessConfig {
 
     /// Maximum duration to wait for a file lock before failing.
     pub lock_timeout: Duration,
+
+    /// Read back each written file and compare length and checksum before
+    /// committing it, fsyncing the file and its parent directory.
+    ///
+    /// Intended for unreliable filesystems (e.g. flaky network mounts) where
+    /// acknowledged writes may not be durable. Off by default.
+    pub verify_writes: bool,
+}
+
+impl RobustnessConfig {
+    /// The configuration write sites without one of their own use: the one
+    /// the calling thread [entered](Self::enter), or the default.
+    pub fn current() -> Self {
+        CURRENT.with(Cell::get).unwrap_or_default()
+    }
+
+    /// Make this the calling thread's [current](Self::current)
+    /// configuration until the guard drops.
+    ///
+    /// Entered once per run (from config or a `--verify-writes` flag) so
+    /// that every write site inherits it.
+    pub fn enter(self) -> RobustnessScope {
+        RobustnessScope {
+            previous: CURRENT.with(|current| current.replace(Some(self))),
+        }
+    }
+
+    /// This configuration with [`verify_writes`](Self::verify_writes) set.
+    pub fn with_verify_writes(self, verify_writes: bool) -> Self {
+        Self {
+            verify_writes,
+            ..self
+        }
+    }
 }
 
 impl Default for RobustnessConfig {
@@ -27,16 +73,177 @@ impl Default for RobustnessConfig {
             enable_fsync: true,
             // default 10s timeout for locks
             lock_timeout: Duration::from_secs(10),
+            verify_writes: false,
+        }
+    }
+}
+
+/// Guard returned by [`RobustnessConfig::enter`]
+#[must_use = "the configuration is only current while the scope is held"]
+#[derive(Debug)]
+pub struct RobustnessScope {
+    previous: Option<RobustnessConfig>,
+}
+
+impl Drop for RobustnessScope {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.previous));
+    }
+}
+
+/// The low-level write performed by [`write_atomic_with`].
+///
+/// Production code uses [`DirectWrite`]; tests can inject a layer that
+/// simulates short or corrupted writes.
+pub trait WriteLayer {
+    /// Write `content` to the freshly truncated `file`.
+    fn write(&self, file: &mut File, content: &[u8]) -> std::io::Result<()>;
+}
+
+/// [`WriteLayer`] that writes the whole buffer with `write_all`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DirectWrite;
+
+impl WriteLayer for DirectWrite {
+    fn write(&self, file: &mut File, content: &[u8]) -> std::io::Result<()> {
+        file.write_all(content)
+    }
+}
+
+/// Re-read `path` and compare it against `expected`, reporting `target`.
+fn check_written(path: &Path, target: &Path, expected: &[u8]) -> Result<()> {
+    let actual = fs::read(path).map_err(|e| Error::io(path, e))?;
+    if actual == expected {
+        return Ok(());
+    }
+
+    Err(Error::WriteVerification {
+        path: target.to_path_buf(),
+        expected_len: expected.len(),
+        actual_len: actual.len(),
+        expected_checksum: compute_bytes_checksum(expected),
+        actual_checksum: compute_bytes_checksum(&actual),
+    })
+}
+
+/// Verify that `path` holds exactly `expected`, rewriting once on mismatch.
+///
+/// Calls `rewrite` after the first failed check and verifies again; a second
+/// mismatch is returned as [`Error::WriteVerification`].
+pub fn verify_file(
+    path: &Path,
+    expected: &[u8],
+    rewrite: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    verify_with_retry(path, path, expected, rewrite)
+}
+
+fn verify_with_retry(
+    path: &Path,
+    target: &Path,
+    expected: &[u8],
+    rewrite: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    if let Err(first) = check_written(path, target, expected) {
+        tracing::warn!("{}; retrying write", first);
+        rewrite()?;
+        check_written(path, target, expected)?;
+    }
+    Ok(())
+}
+
+/// Fsync a directory so renames and new entries inside it are durable.
+///
+/// A no-op on platforms where directories cannot be opened for syncing.
+pub fn sync_dir(dir: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        File::open(dir)
+            .and_then(|d| d.sync_all())
+            .map_err(|e| Error::io(dir, e))?;
+    }
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
+
+/// Write `content` to `path` through a sibling temp file and a rename.
+///
+/// Unlike [`write_atomic`] this takes no lock and does not retry, for
+/// callers that already hold their own lock on the target (the ledger, MCP
+/// configs). With [`RobustnessConfig::enable_fsync`], the temp file is
+/// fsynced before the rename and the parent directory after it, so a crash
+/// leaves either the old or the new content on disk. With
+/// [`RobustnessConfig::verify_writes`], the temp file is also read back
+/// before the rename and rewritten once on mismatch.
+///
+/// Each call writes its own temp file, `.<name>.<pid>.<n>.tmp`, so
+/// concurrent writers never share one; it is removed if the write fails.
+/// Paths through a symlink are refused like in [`write_atomic`].
+pub fn write_atomic_unlocked(path: &Path, content: &[u8], config: &RobustnessConfig) -> Result<()> {
+    if contains_symlink(path).unwrap_or(true) {
+        return Err(Error::SymlinkInPath {
+            path: path.to_path_buf(),
+        });
+    }
+
+    let temp_name = format!(
+        ".{}.{}.{}.tmp",
+        path.file_name()
+            .map(|n| n.to_string_lossy())
+            .unwrap_or_default(),
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    let temp_path = path.with_file_name(temp_name);
+    let fsync = config.enable_fsync || config.verify_writes;
+
+    // The first write claims the name; a verify rewrite truncates it again
+    let created = std::cell::Cell::new(false);
+    let write_temp = || -> Result<()> {
+        let mut temp_file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create_new(!created.get())
+            .open(&temp_path)
+            .map_err(|e| Error::io(&temp_path, e))?;
+        created.set(true);
+        temp_file
+            .write_all(content)
+            .map_err(|e| Error::io(&temp_path, e))?;
+        if fsync {
+            temp_file.sync_all().map_err(|e| Error::io(&temp_path, e))?;
         }
+        Ok(())
+    };
+
+    let result = write_temp()
+        .and_then(|()| {
+            if config.verify_writes {
+                verify_with_retry(&temp_path, path, content, write_temp)?;
+            }
+            fs::rename(&temp_path, path).map_err(|e| Error::io(path, e))
+        })
+        .and_then(|()| match path.parent() {
+            Some(parent) if fsync => sync_dir(parent),
+            _ => Ok(()),
+        });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
     }
+    result
 }
 
 /// Check if any component in the path (or its ancestors) is a symlink.
 ///
 /// This prevents symlink-based attacks where writes could escape intended directories.
 fn contains_symlink(path: &std::path::Path) -> std::io::Result<bool> {
-    use std::path::PathBuf;
-
     let mut current = PathBuf::from(path);
 
     // Walk up the path checking each component
@@ -59,6 +266,13 @@ fn contains_symlink(path: &std::path::Path) -> std::io::Result<bool> {
     Ok(false)
 }
 
+/// Path of the sidecar file locked to coordinate writes to `path`.
+pub(crate) fn lock_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".lock");
+    PathBuf::from(name)
+}
+
 /// Write content atomically to a file with locking and retry logic.
 ///
 /// Uses write-to-temp-then-rename strategy to prevent partial writes.
@@ -68,7 +282,26 @@ fn contains_symlink(path: &std::path::Path) -> std::io::Result<bool> {
 /// Uses exponential backoff for:
 /// - Acquiring locks (simulating timeout via try_lock loop)
 /// - Transient I/O errors (e.g. network blips)
+///
+/// # Durability
+/// With [`RobustnessConfig::enable_fsync`], the temp file is fsynced before
+/// the rename and the parent directory after it.
+///
+/// # Verification
+/// With [`RobustnessConfig::verify_writes`], the temp file is fsynced and read
+/// back before the rename, rewritten once on mismatch, and the parent
+/// directory is fsynced around the rename.
 pub fn write_atomic(path: &NormalizedPath, content: &[u8], config: RobustnessConfig) -> Result<()> {
+    write_atomic_with(path, content, config, &DirectWrite)
+}
+
+/// [`write_atomic`] with an explicit [`WriteLayer`].
+pub fn write_atomic_with(
+    path: &NormalizedPath,
+    content: &[u8],
+    config: RobustnessConfig,
+    layer: &dyn WriteLayer,
+) -> Result<()> {
     tracing::debug!(path = %path.as_str(), content_len = content.len(), "Starting atomic write");
     let native_path = path.to_native();
 
@@ -85,7 +318,7 @@ pub fn write_atomic(path: &NormalizedPath, content: &[u8], config: RobustnessCon
     }
 
     // 1. Acquire coordination lock on a separate lock file
-    let lock_path = format!("{}.lock", native_path.to_string_lossy());
+    let lock_path = lock_path(&native_path);
     let lock_file = OpenOptions::new()
         .write(true)
         .create(true)
@@ -119,37 +352,57 @@ pub fn write_atomic(path: &NormalizedPath, content: &[u8], config: RobustnessCon
         // Explicit unlock is not strictly needed if we drop the file, but good for clarity.
         // We will hold this lock until the end of the closure.
 
-        let mut temp_file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(&temp_path)
-            .map_err(|e| backoff::Error::transient(Error::io(&temp_path, e)))?;
+        // 2. Write content to the temp file, flushing to disk if enabled
+        let write_temp = || -> Result<()> {
+            let mut temp_file = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&temp_path)
+                .map_err(|e| Error::io(&temp_path, e))?;
 
-        // Write content
-        temp_file
-            .write_all(content)
-            .map_err(|e| backoff::Error::transient(Error::io(&temp_path, e)))?;
+            layer
+                .write(&mut temp_file, content)
+                .map_err(|e| Error::io(&temp_path, e))?;
 
-        // Flush to disk if enabled
-        if config.enable_fsync {
-            temp_file
-                .sync_all()
-                .map_err(|e| backoff::Error::transient(Error::io(&temp_path, e)))?;
-        }
+            if config.enable_fsync || config.verify_writes {
+                temp_file.sync_all().map_err(|e| Error::io(&temp_path, e))?;
+            }
+
+            // Close temp file explicitly before rename (improves Windows reliability)
+            drop(temp_file);
+            Ok(())
+        };
+        write_temp().map_err(backoff::Error::transient)?;
 
-        // Close temp file explicitly before rename (improves Windows reliability)
-        drop(temp_file);
+        // Read back before committing; a persistent mismatch is not retried
+        if config.verify_writes {
+            verify_with_retry(&temp_path, &native_path, content, write_temp)
+                .map_err(backoff::Error::permanent)?;
+            if let Some(parent) = native_path.parent() {
+                sync_dir(parent).map_err(backoff::Error::transient)?;
+            }
+        }
 
         // 3. Atomic rename
         // Replaces target if exists
         fs::rename(&temp_path, &native_path)
             .map_err(|e| backoff::Error::transient(Error::io(&native_path, e)))?;
 
+        if (config.enable_fsync || config.verify_writes)
+            && let Some(parent) = native_path.parent()
+        {
+            sync_dir(parent).map_err(backoff::Error::transient)?;
+        }
+
         // 4. Release lock (advisory locks are also released on fd close,
         // so an explicit unlock failure is non-critical but worth logging)
         if let Err(e) = lock_file.unlock() {
-            tracing::warn!("Failed to release lock for {}: {}", native_path.display(), e);
+            tracing::warn!(
+                "Failed to release lock for {}: {}",
+                native_path.display(),
+                e
+            );
         }
 
         Ok(())
@@ -178,13 +431,68 @@ pub fn write_atomic(path: &NormalizedPath, content: &[u8], config: RobustnessCon
     result
 }
 
+/// Options for [`read_text_with`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReadOptions {
+    /// Replace invalid UTF-8 with U+FFFD instead of failing
+    pub lossy: bool,
+}
+
+/// Text read by [`read_text_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextFile {
+    /// The file content
+    pub text: String,
+    /// Offset of the first invalid UTF-8 byte, if the file was read lossily
+    pub invalid_utf8_at: Option<usize>,
+}
+
+impl TextFile {
+    /// Whether the content was damaged and the file should be regenerated
+    pub fn is_corrupt(&self) -> bool {
+        self.invalid_utf8_at.is_some()
+    }
+}
+
 /// Read text content from a file.
+///
+/// Fails with [`Error::InvalidUtf8`] if the file is not valid UTF-8.
 pub fn read_text(path: &NormalizedPath) -> Result<String> {
+    read_text_with(path, ReadOptions::default()).map(|file| file.text)
+}
+
+/// Read text content from a file with `options`.
+pub fn read_text_with(path: &NormalizedPath, options: ReadOptions) -> Result<TextFile> {
     let native_path = path.to_native();
-    fs::read_to_string(&native_path).map_err(|e| Error::io(&native_path, e))
+    let bytes = fs::read(&native_path).map_err(|e| Error::io(&native_path, e))?;
+    decode_text(&native_path, bytes, options)
+}
+
+/// Decode the `bytes` read from `path` as UTF-8 with `options`.
+pub fn decode_text(path: &Path, bytes: Vec<u8>, options: ReadOptions) -> Result<TextFile> {
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok(TextFile {
+            text,
+            invalid_utf8_at: None,
+        }),
+        Err(e) => {
+            let byte_offset = e.utf8_error().valid_up_to();
+            if !options.lossy {
+                return Err(Error::InvalidUtf8 {
+                    path: path.to_path_buf(),
+                    byte_offset,
+                });
+            }
+            Ok(TextFile {
+                text: String::from_utf8_lossy(e.as_bytes()).into_owned(),
+                invalid_utf8_at: Some(byte_offset),
+            })
+        }
+    }
 }
 
-/// Write text content to a file atomically.
+/// Write text content to a file atomically, with the
+/// [current](RobustnessConfig::current) robustness configuration.
 pub fn write_text(path: &NormalizedPath, content: &str) -> Result<()> {
-    write_atomic(path, content.as_bytes(), RobustnessConfig::default())
+    write_atomic(path, content.as_bytes(), RobustnessConfig::current())
 }
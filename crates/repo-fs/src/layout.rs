@@ -1,10 +1,15 @@
 //! Workspace layout detection and management
 
-use crate::{Error, NormalizedPath, RepoPath, Result};
+use crate::{ConfigStore, Error, NormalizedPath, RepoPath, Result};
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 /// The detected or configured layout mode for a workspace.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Serialized in kebab-case (`in-repo-worktrees`) as the `layout` key of
+/// `[core]` in `.repository/config.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum LayoutMode {
     /// Container layout with `.gt/` database and sibling worktrees
     Container,
@@ -16,6 +21,18 @@ pub enum LayoutMode {
     LinkedWorktrees,
 }
 
+impl LayoutMode {
+    /// The name used for this mode in `config.toml`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Container => "container",
+            Self::InRepoWorktrees => "in-repo-worktrees",
+            Self::Classic => "classic",
+            Self::LinkedWorktrees => "linked-worktrees",
+        }
+    }
+}
+
 impl std::fmt::Display for LayoutMode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -45,7 +62,9 @@ pub struct WorkspaceLayout {
 impl WorkspaceLayout {
     /// Detect the workspace layout starting from the given directory.
     ///
-    /// Walks up the directory tree looking for layout signals.
+    /// Walks up the directory tree looking for layout signals. A layout
+    /// recorded in `.repository/config.toml` is preferred over probing
+    /// markers, as long as the filesystem still matches it.
     pub fn detect(start_dir: impl AsRef<Path>) -> Result<Self> {
         let start = dunce::canonicalize(start_dir.as_ref())
             .map_err(|e| Error::io(start_dir.as_ref(), e))?;
@@ -64,6 +83,101 @@ impl WorkspaceLayout {
 
     /// Attempt to detect layout at a specific directory.
     fn detect_at(dir: &Path) -> Result<Option<Self>> {
+        match Self::check_recorded(dir) {
+            Ok(Some(mode)) => return Ok(Some(Self::at(dir, mode))),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Ignoring recorded layout in {}: {}", dir.display(), e),
+        }
+        Ok(Self::probe(dir).map(|mode| Self::at(dir, mode)))
+    }
+
+    /// Read the layout recorded in `dir`'s `.repository/config.toml` and
+    /// check it against the filesystem markers.
+    ///
+    /// Returns `Ok(None)` when no layout is recorded (or the config cannot
+    /// be read), and [`Error::LayoutMismatch`] when the recorded layout no
+    /// longer matches the directory.
+    pub fn check_recorded(dir: impl AsRef<Path>) -> Result<Option<LayoutMode>> {
+        let dir = dir.as_ref();
+        let Some(declared) = Self::read_recorded(dir) else {
+            return Ok(None);
+        };
+
+        match Self::at(dir, declared).validate() {
+            Ok(()) => Ok(Some(declared)),
+            Err(e) => match Self::probe(dir) {
+                Some(detected) => Err(Error::LayoutMismatch { declared, detected }),
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Record this layout as `layout` in `[core]` of `.repository/config.toml`.
+    ///
+    /// The rest of the file, including comments and key order, is preserved.
+    /// The file is locked through [`ConfigStore`] while it is rewritten.
+    pub fn record(&self) -> Result<()> {
+        let path = self.config_dir().join("config.toml");
+        let lock = ConfigStore::new().lock(&path)?;
+        let content = if path.exists() {
+            lock.read()?
+        } else {
+            String::new()
+        };
+
+        let mut doc: toml_edit::DocumentMut =
+            content
+                .parse()
+                .map_err(|e: toml_edit::TomlError| Error::ConfigParse {
+                    path: path.to_native(),
+                    format: "TOML".into(),
+                    message: e.to_string(),
+                })?;
+        let core = doc
+            .entry("core")
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .ok_or_else(|| Error::ConfigParse {
+                path: path.to_native(),
+                format: "TOML".into(),
+                message: "[core] is not a table".into(),
+            })?;
+        core.insert("layout", toml_edit::value(self.mode.as_str()));
+
+        lock.write(&doc.to_string())
+    }
+
+    /// Read `core.layout` from `dir`'s config, if present and valid.
+    fn read_recorded(dir: &Path) -> Option<LayoutMode> {
+        let path = dir.join(RepoPath::RepositoryConfig).join("config.toml");
+        let content = std::fs::read_to_string(&path).ok()?;
+        let config: toml::Value = match toml::from_str(&content) {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::debug!("Cannot read layout from {}: {}", path.display(), e);
+                return None;
+            }
+        };
+        let layout = config.get("core")?.get("layout")?.clone();
+        match layout.try_into() {
+            Ok(mode) => Some(mode),
+            Err(e) => {
+                tracing::warn!("Unknown layout in {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    fn at(dir: &Path, mode: LayoutMode) -> Self {
+        Self {
+            root: NormalizedPath::new(dir),
+            active_context: NormalizedPath::new(dir),
+            mode,
+        }
+    }
+
+    /// Infer the layout mode from filesystem markers in `dir`.
+    fn probe(dir: &Path) -> Option<LayoutMode> {
         let has_gt = dir.join(RepoPath::GtDir).is_dir();
         let has_git = dir.join(RepoPath::GitDir).exists(); // Can be file (gitdir pointer) or dir
         let has_main = dir.join(RepoPath::MainWorktree).is_dir();
@@ -72,7 +186,7 @@ impl WorkspaceLayout {
         let main_git_dir = dir.join(RepoPath::MainWorktree).join(RepoPath::GitDir);
         let has_main_git_dir = main_git_dir.is_dir();
 
-        let mode = if has_main_git_dir && !has_gt && !has_git {
+        if has_main_git_dir && !has_gt && !has_git {
             // LinkedWorktrees: parent has main/.git/ directory but no .gt or .git of its own
             Some(LayoutMode::LinkedWorktrees)
         } else if has_gt && has_main {
@@ -86,13 +200,7 @@ impl WorkspaceLayout {
             Some(LayoutMode::Classic)
         } else {
             None
-        };
-
-        Ok(mode.map(|mode| Self {
-            root: NormalizedPath::new(dir),
-            active_context: NormalizedPath::new(dir),
-            mode,
-        }))
+        }
     }
 
     /// Get the path to the git database.
@@ -147,9 +255,7 @@ impl WorkspaceLayout {
                     .join(RepoPath::GitDir.as_str());
                 if !main_git.is_dir() {
                     return Err(Error::LayoutValidation {
-                        message: format!(
-                            "Worktree collection missing main/.git/ directory.",
-                        ),
+                        message: "Worktree collection missing main/.git/ directory.".into(),
                     });
                 }
             }
@@ -2,6 +2,7 @@
 //!
 //! Provides layout-agnostic path resolution and safe I/O operations.
 
+pub mod cancel;
 pub mod checksum;
 pub mod config;
 pub mod constants;
@@ -10,9 +11,10 @@ pub mod io;
 pub mod layout;
 pub mod path;
 
-pub use config::ConfigStore;
+pub use cancel::CancelToken;
+pub use config::{ConfigLock, ConfigStore};
 pub use constants::RepoPath;
 pub use error::{Error, Result};
-pub use io::RobustnessConfig;
+pub use io::{RobustnessConfig, RobustnessScope};
 pub use layout::{LayoutMode, WorkspaceLayout};
 pub use path::{NormalizedPath, validate_path_identifier};
@@ -23,7 +23,10 @@ pub fn validate_path_identifier(id: &str, label: &str) -> std::result::Result<()
         return Err(format!("{} must not start with '-'", label));
     }
     if id.len() > 255 {
-        return Err(format!("{} exceeds maximum length of 255 characters", label));
+        return Err(format!(
+            "{} exceeds maximum length of 255 characters",
+            label
+        ));
     }
     if !id
         .chars()
@@ -42,6 +45,10 @@ pub fn validate_path_identifier(id: &str, label: &str) -> std::result::Result<()
 /// Provides consistent path handling across platforms by normalizing
 /// all paths to forward slashes internally and converting to
 /// platform-native format only at I/O boundaries.
+///
+/// Normalization is lexical: symlinks are not resolved, so two paths that
+/// reach the same file through different links compare unequal. Compare
+/// their [`canonicalized`](Self::canonicalized) forms when that matters.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct NormalizedPath {
     /// Internal representation always uses forward slashes
@@ -126,6 +133,43 @@ impl NormalizedPath {
         Self { inner: cleaned }
     }
 
+    /// Resolve symlinks, for comparing paths that may reach the same file
+    /// through different links.
+    ///
+    /// If the path exists, it is canonicalized by the filesystem: symlinks
+    /// are resolved and a relative path becomes absolute. Otherwise its
+    /// deepest existing ancestor is canonicalized and the remaining
+    /// components are appended lexically; if no ancestor exists, the path is
+    /// returned as it is.
+    ///
+    /// The result is an ordinary `NormalizedPath`. Equality stays lexical, so
+    /// a canonical path equals the canonical form of any path to the same
+    /// location, but not the symlinked path it was made from. `join` is
+    /// lexical too: joining onto a canonical path only yields a canonical
+    /// path if the joined segment contains no symlinks, so canonicalize
+    /// again after joining when it might.
+    pub fn canonicalized(&self) -> Self {
+        let native = self.to_native();
+        let mut existing = native.as_path();
+        let mut rest: Vec<String> = Vec::new();
+        loop {
+            if let Ok(resolved) = std::fs::canonicalize(existing) {
+                let resolved = Self::new(strip_verbatim_prefix(&resolved.to_string_lossy()));
+                return rest
+                    .iter()
+                    .rev()
+                    .fold(resolved, |path, name| path.join(name));
+            }
+            match (existing.parent(), existing.file_name()) {
+                (Some(parent), Some(name)) => {
+                    rest.push(name.to_string_lossy().into_owned());
+                    existing = parent;
+                }
+                _ => return self.clone(),
+            }
+        }
+    }
+
     /// Clean the path by resolving . and .. components
     fn clean(path: &str) -> String {
         // Optimization: check if we actually need to do anything
@@ -246,6 +290,16 @@ impl NormalizedPath {
     }
 }
 
+/// Strip the `\\?\` prefix Windows puts on canonical local paths
+///
+/// Verbatim UNC paths (`\\?\UNC\...`) are left alone.
+fn strip_verbatim_prefix(path: &str) -> &str {
+    match path.strip_prefix(r"\\?\") {
+        Some(local) if !local.starts_with(r"UNC\") => local,
+        _ => path,
+    }
+}
+
 impl AsRef<Path> for NormalizedPath {
     fn as_ref(&self) -> &Path {
         Path::new(&self.inner)
@@ -286,6 +340,50 @@ impl From<&Path> for NormalizedPath {
 mod tests {
     use super::*;
 
+    #[cfg(unix)]
+    #[test]
+    fn test_canonicalized_resolves_symlinked_parent() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let worktree = temp.path().join("worktrees").join("main");
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(worktree.join("CLAUDE.md"), "").unwrap();
+        let link = temp.path().join("link");
+        std::os::unix::fs::symlink(temp.path().join("worktrees"), &link).unwrap();
+
+        let direct = NormalizedPath::new(&worktree).join("CLAUDE.md");
+        let linked = NormalizedPath::new(&link).join("main/CLAUDE.md");
+        assert_ne!(direct, linked);
+        assert_eq!(direct.canonicalized(), linked.canonicalized());
+
+        // A file that does not exist yet resolves through its parent
+        let missing = NormalizedPath::new(&link).join("main/.cursorrules");
+        assert_eq!(
+            missing.canonicalized(),
+            direct
+                .canonicalized()
+                .parent()
+                .unwrap()
+                .join(".cursorrules")
+        );
+    }
+
+    #[test]
+    fn test_canonicalized_falls_back_to_lexical() {
+        let path = NormalizedPath::new("does-not-exist/./a/../b");
+        assert_eq!(path.canonicalized(), path);
+        assert_eq!(path.canonicalized().as_str(), "does-not-exist/b");
+    }
+
+    #[test]
+    fn test_strip_verbatim_prefix() {
+        assert_eq!(strip_verbatim_prefix(r"\\?\C:\repo"), r"C:\repo");
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\UNC\server\share"),
+            r"\\?\UNC\server\share"
+        );
+        assert_eq!(strip_verbatim_prefix("/repo"), "/repo");
+    }
+
     #[test]
     fn test_normalize_forward_slashes() {
         let path = NormalizedPath::new("foo/bar/baz");
@@ -322,7 +420,10 @@ mod tests {
     fn test_unc_path_rewritten_to_local() {
         // UNC paths should be rewritten to local paths (strip leading //)
         let path = NormalizedPath::new("//server/share/path");
-        assert!(!path.is_network_path(), "UNC paths should be rejected at construction");
+        assert!(
+            !path.is_network_path(),
+            "UNC paths should be rejected at construction"
+        );
         assert_eq!(path.as_str(), "/server/share/path");
     }
 
@@ -145,6 +145,7 @@ fn test_lock_timeout_is_respected() {
     let config = RobustnessConfig {
         lock_timeout: Duration::from_millis(500),
         enable_fsync: false,
+        verify_writes: false,
     };
 
     let result = io::write_atomic(&path, b"content", config);
@@ -1,6 +1,7 @@
-use repo_fs::{ConfigStore, NormalizedPath};
+use repo_fs::{ConfigStore, NormalizedPath, RobustnessConfig};
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::time::Duration;
 use tempfile::TempDir;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -122,3 +123,50 @@ fn test_roundtrip_toml() {
 
     assert_eq!(original, loaded);
 }
+
+#[test]
+fn test_writer_times_out_while_locked() {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("config.toml");
+    let path = NormalizedPath::new(&file_path);
+    let store = ConfigStore::with_robustness(RobustnessConfig {
+        lock_timeout: Duration::from_millis(50),
+        ..RobustnessConfig::default()
+    });
+    store.save_text(&path, "name = \"held\"\n").unwrap();
+
+    let lock = store.lock(&path).unwrap();
+    let result = store.save_text(&path, "name = \"racing\"\n");
+    assert!(matches!(result, Err(repo_fs::Error::ConfigLocked { .. })));
+    assert!(matches!(
+        store.load_text(&path),
+        Err(repo_fs::Error::ConfigLocked { .. })
+    ));
+
+    lock.write("name = \"updated\"\n").unwrap();
+    drop(lock);
+    assert_eq!(store.load_text(&path).unwrap(), "name = \"updated\"\n");
+    store.save_text(&path, "name = \"after\"\n").unwrap();
+}
+
+#[test]
+fn test_lock_survives_atomic_write_to_the_same_file() {
+    let temp = TempDir::new().unwrap();
+    let path = NormalizedPath::new(temp.path().join("config.toml"));
+    let store = ConfigStore::with_robustness(RobustnessConfig {
+        lock_timeout: Duration::from_millis(50),
+        ..RobustnessConfig::default()
+    });
+    store.save_text(&path, "name = \"held\"\n").unwrap();
+
+    let lock = store.lock(&path).unwrap();
+    // A plain atomic write cleans up its own sidecar afterwards, which must
+    // not release the store's lock
+    repo_fs::io::write_text(&path, "name = \"direct\"\n").unwrap();
+    assert!(matches!(
+        store.lock(&path),
+        Err(repo_fs::Error::ConfigLocked { .. })
+    ));
+    drop(lock);
+    store.lock(&path).unwrap();
+}
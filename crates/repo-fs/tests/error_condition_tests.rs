@@ -175,6 +175,7 @@ mod windows_tests {
         let config = repo_fs::RobustnessConfig {
             lock_timeout: std::time::Duration::from_millis(500),
             enable_fsync: false,
+            verify_writes: false,
         };
         let result = io::write_atomic(&path, b"new content", config);
 
@@ -145,6 +145,27 @@ fn test_read_text_nonexistent_file() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_read_text_invalid_utf8() {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("latin1.md");
+    fs::write(&file_path, b"caf\xe9 au lait").unwrap();
+    let path = NormalizedPath::new(&file_path);
+
+    match io::read_text(&path) {
+        Err(repo_fs::Error::InvalidUtf8 { path, byte_offset }) => {
+            assert_eq!(path, file_path);
+            assert_eq!(byte_offset, 3);
+        }
+        other => panic!("expected InvalidUtf8, got {:?}", other),
+    }
+
+    let file = io::read_text_with(&path, io::ReadOptions { lossy: true }).unwrap();
+    assert!(file.is_corrupt());
+    assert_eq!(file.invalid_utf8_at, Some(3));
+    assert_eq!(file.text, "caf\u{FFFD} au lait");
+}
+
 #[test]
 fn test_write_text_creates_file() {
     let temp = TempDir::new().unwrap();
@@ -155,3 +176,224 @@ fn test_write_text_creates_file() {
     let content = fs::read_to_string(path.to_native()).unwrap();
     assert_eq!(content, "hello world");
 }
+
+/// Write layer that truncates the first `short_writes` writes to half length.
+struct ShortWrite {
+    short_writes: usize,
+    calls: std::cell::Cell<usize>,
+}
+
+impl ShortWrite {
+    fn new(short_writes: usize) -> Self {
+        Self {
+            short_writes,
+            calls: std::cell::Cell::new(0),
+        }
+    }
+}
+
+impl io::WriteLayer for ShortWrite {
+    fn write(&self, file: &mut fs::File, content: &[u8]) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let call = self.calls.get();
+        self.calls.set(call + 1);
+        if call < self.short_writes {
+            file.write_all(&content[..content.len() / 2])
+        } else {
+            file.write_all(content)
+        }
+    }
+}
+
+fn verifying_config() -> io::RobustnessConfig {
+    io::RobustnessConfig {
+        verify_writes: true,
+        ..io::RobustnessConfig::default()
+    }
+}
+
+#[test]
+fn test_verify_writes_retries_short_write_once() {
+    let temp = TempDir::new().unwrap();
+    let path = NormalizedPath::new(temp.path().join("managed.md"));
+    let layer = ShortWrite::new(1);
+
+    io::write_atomic_with(&path, b"full managed content", verifying_config(), &layer).unwrap();
+
+    assert_eq!(layer.calls.get(), 2, "short write should be retried once");
+    assert_eq!(
+        fs::read_to_string(path.to_native()).unwrap(),
+        "full managed content"
+    );
+}
+
+#[test]
+fn test_verify_writes_fails_loudly_on_persistent_short_write() {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("managed.md");
+    fs::write(&file_path, "original").unwrap();
+    let path = NormalizedPath::new(&file_path);
+    let layer = ShortWrite::new(usize::MAX);
+
+    let err = io::write_atomic_with(&path, b"0123456789", verifying_config(), &layer)
+        .expect_err("persistent short write must fail");
+
+    assert_eq!(layer.calls.get(), 2, "should retry exactly once");
+    match &err {
+        repo_fs::Error::WriteVerification {
+            path: reported,
+            expected_len,
+            actual_len,
+            ..
+        } => {
+            assert_eq!(reported, &file_path);
+            assert_eq!(*expected_len, 10);
+            assert_eq!(*actual_len, 5);
+        }
+        other => panic!("Expected WriteVerification, got {:?}", other),
+    }
+    assert!(err.to_string().contains("managed.md"));
+
+    // The original file is never replaced by the bad write
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), "original");
+    let leftovers: Vec<_> = fs::read_dir(temp.path())
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+        .collect();
+    assert!(leftovers.is_empty(), "temp file should be cleaned up");
+}
+
+#[test]
+fn test_short_write_undetected_when_verification_disabled() {
+    let temp = TempDir::new().unwrap();
+    let path = NormalizedPath::new(temp.path().join("managed.md"));
+    let layer = ShortWrite::new(1);
+    let config = io::RobustnessConfig {
+        verify_writes: false,
+        ..io::RobustnessConfig::default()
+    };
+
+    io::write_atomic_with(&path, b"0123456789", config, &layer).unwrap();
+
+    // Without verification the write path is unchanged: one write, no read-back
+    assert_eq!(layer.calls.get(), 1);
+    assert_eq!(fs::read_to_string(path.to_native()).unwrap(), "01234");
+}
+
+#[test]
+fn test_robustness_scope_restores_previous_config() {
+    assert!(!io::RobustnessConfig::current().verify_writes);
+    {
+        let _scope = io::RobustnessConfig::default()
+            .with_verify_writes(true)
+            .enter();
+        assert!(io::RobustnessConfig::current().verify_writes);
+    }
+    assert!(!io::RobustnessConfig::current().verify_writes);
+}
+
+#[test]
+fn test_verify_writes_normal_path_writes_once() {
+    let temp = TempDir::new().unwrap();
+    let path = NormalizedPath::new(temp.path().join("managed.md"));
+    let layer = ShortWrite::new(0);
+
+    io::write_atomic_with(&path, b"content", verifying_config(), &layer).unwrap();
+
+    assert_eq!(layer.calls.get(), 1);
+    assert_eq!(fs::read_to_string(path.to_native()).unwrap(), "content");
+}
+
+#[test]
+fn test_write_atomic_unlocked_leaves_no_temp_file() {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("ledger.toml");
+    fs::write(&file_path, "old").unwrap();
+    let config = io::RobustnessConfig {
+        enable_fsync: false,
+        ..io::RobustnessConfig::default()
+    };
+
+    io::write_atomic_unlocked(&file_path, b"version = \"1.0\"\n", &config).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&file_path).unwrap(),
+        "version = \"1.0\"\n"
+    );
+    let entries: Vec<_> = fs::read_dir(temp.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    assert_eq!(entries, ["ledger.toml"], "only the target should remain");
+}
+
+#[test]
+fn test_write_atomic_unlocked_with_fsync_on_tmpfs() {
+    // /dev/shm is tmpfs on Linux; fsyncing the file and directory there must
+    // still succeed
+    let shm = std::path::Path::new("/dev/shm");
+    let temp = if shm.is_dir() {
+        TempDir::new_in(shm).unwrap()
+    } else {
+        TempDir::new().unwrap()
+    };
+    let file_path = temp.path().join("mcp.json");
+    let config = io::RobustnessConfig {
+        enable_fsync: true,
+        ..io::RobustnessConfig::default()
+    };
+
+    io::write_atomic_unlocked(&file_path, b"{}\n", &config).unwrap();
+
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), "{}\n");
+    let entries: Vec<_> = fs::read_dir(temp.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    assert_eq!(entries, ["mcp.json"], "only the target should remain");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_atomic_unlocked_rejects_symlink() {
+    let temp = TempDir::new().unwrap();
+    let real_dir = temp.path().join("real");
+    fs::create_dir(&real_dir).unwrap();
+    let link = temp.path().join("link");
+    std::os::unix::fs::symlink(&real_dir, &link).unwrap();
+
+    let result = io::write_atomic_unlocked(
+        &link.join("ledger.toml"),
+        b"x",
+        &io::RobustnessConfig::default(),
+    );
+
+    assert!(matches!(result, Err(repo_fs::Error::SymlinkInPath { .. })));
+    assert_eq!(fs::read_dir(&real_dir).unwrap().count(), 0);
+}
+
+#[test]
+fn test_write_atomic_unlocked_concurrent_writers_do_not_collide() {
+    let temp = TempDir::new().unwrap();
+    let file_path = temp.path().join("mcp.json");
+    let config = io::RobustnessConfig {
+        enable_fsync: false,
+        ..io::RobustnessConfig::default()
+    };
+
+    std::thread::scope(|s| {
+        for i in 0..8 {
+            let file_path = &file_path;
+            s.spawn(move || {
+                let content = format!("{{\"writer\": {i}}}\n");
+                io::write_atomic_unlocked(file_path, content.as_bytes(), &config).unwrap();
+            });
+        }
+    });
+
+    let content = fs::read_to_string(&file_path).unwrap();
+    assert!(content.starts_with("{\"writer\": "), "got {content:?}");
+    assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 1);
+}
@@ -78,3 +78,80 @@ fn test_detect_accepts_git_file_as_gitdir_pointer() {
     );
     assert_eq!(result.unwrap().mode, LayoutMode::Classic);
 }
+
+#[test]
+fn test_recorded_layout_is_preferred_over_probing() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+
+    // Markers alone would be detected as InRepoWorktrees
+    fs::create_dir(root.join(".git")).unwrap();
+    fs::create_dir(root.join(".worktrees")).unwrap();
+    fs::create_dir(root.join(".repository")).unwrap();
+    fs::write(
+        root.join(".repository/config.toml"),
+        "# Team config\n[core]\nmode = \"standard\"\n",
+    )
+    .unwrap();
+
+    let mut layout = WorkspaceLayout::detect(root).unwrap();
+    assert_eq!(layout.mode, LayoutMode::InRepoWorktrees);
+
+    layout.mode = LayoutMode::Classic;
+    layout.record().unwrap();
+
+    let config = fs::read_to_string(root.join(".repository/config.toml")).unwrap();
+    assert_eq!(
+        config,
+        "# Team config\n[core]\nmode = \"standard\"\nlayout = \"classic\"\n"
+    );
+
+    assert_eq!(
+        WorkspaceLayout::check_recorded(root).unwrap(),
+        Some(LayoutMode::Classic)
+    );
+    let layout = WorkspaceLayout::detect(root).unwrap();
+    assert_eq!(layout.mode, LayoutMode::Classic);
+}
+
+#[test]
+fn test_recorded_layout_mismatch_falls_back_to_probing() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+
+    fs::create_dir(root.join(".git")).unwrap();
+    fs::create_dir(root.join(".repository")).unwrap();
+    fs::write(
+        root.join(".repository/config.toml"),
+        "[core]\nmode = \"worktrees\"\nlayout = \"container\"\n",
+    )
+    .unwrap();
+
+    let err = WorkspaceLayout::check_recorded(root).unwrap_err();
+    assert!(matches!(
+        err,
+        repo_fs::Error::LayoutMismatch {
+            declared: LayoutMode::Container,
+            detected: LayoutMode::Classic,
+        }
+    ));
+
+    let layout = WorkspaceLayout::detect(root).unwrap();
+    assert_eq!(layout.mode, LayoutMode::Classic);
+}
+
+#[test]
+fn test_check_recorded_without_layout_key() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+
+    fs::create_dir(root.join(".git")).unwrap();
+    fs::create_dir(root.join(".repository")).unwrap();
+    fs::write(
+        root.join(".repository/config.toml"),
+        "[core]\nmode = \"standard\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(WorkspaceLayout::check_recorded(root).unwrap(), None);
+}
@@ -60,6 +60,9 @@ impl LayoutProvider for ClassicLayout {
             path: self.root.clone(),
             branch,
             is_main: true,
+            locked: false,
+            lock_reason: None,
+            prunable: false,
         }])
     }
 
@@ -81,6 +84,29 @@ impl LayoutProvider for ClassicLayout {
         })
     }
 
+    fn lock_worktree(&self, _name: &str, _reason: Option<&str>) -> Result<()> {
+        Err(Error::LayoutUnsupported {
+            operation: "lock_worktree".into(),
+            layout: "Classic".into(),
+            hint: "Run `repo migrate --layout in-repo-worktrees` to enable parallel worktrees."
+                .into(),
+        })
+    }
+
+    fn unlock_worktree(&self, _name: &str) -> Result<()> {
+        Err(Error::LayoutUnsupported {
+            operation: "unlock_worktree".into(),
+            layout: "Classic".into(),
+            hint: "Run `repo migrate --layout in-repo-worktrees` to enable parallel worktrees."
+                .into(),
+        })
+    }
+
+    fn prune_worktrees(&self) -> Result<Vec<String>> {
+        // Classic layout has no linked worktrees to prune
+        Ok(Vec::new())
+    }
+
     fn current_branch(&self) -> Result<String> {
         let repo = Repository::open(self.root.to_native())?;
         crate::helpers::get_current_branch(&repo)
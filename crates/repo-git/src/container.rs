@@ -29,6 +29,7 @@ pub struct ContainerLayout {
 impl ContainerLayout {
     /// Create a new ContainerLayout for the given root directory.
     pub fn new(root: NormalizedPath, naming: NamingStrategy) -> Result<Self> {
+        naming.validate()?;
         let git_dir = root.join(".gt");
         let main_dir = root.join("main");
 
@@ -63,8 +64,13 @@ impl LayoutProvider for ContainerLayout {
     }
 
     fn feature_worktree(&self, name: &str) -> NormalizedPath {
-        let dir_name = branch_to_directory(name, self.naming);
-        self.root.join(&dir_name)
+        // An existing worktree keeps its path even if the naming would now
+        // expand differently, e.g. a template with a `{date}`
+        let registered = self.open_repo().ok().and_then(|repo| {
+            let wt = repo.find_worktree(&self.naming.worktree_name(name)).ok()?;
+            Some(NormalizedPath::new(wt.path()))
+        });
+        registered.unwrap_or_else(|| self.root.join(&branch_to_directory(name, &self.naming)))
     }
 
     fn list_worktrees(&self) -> Result<Vec<WorktreeInfo>> {
@@ -79,27 +85,10 @@ impl LayoutProvider for ContainerLayout {
                 None => continue,
             };
 
-            let wt = repo.find_worktree(name)?;
-            let wt_path = wt.path();
-
-            // Get branch for this worktree
-            let wt_repo = Repository::open(wt_path)?;
-            let branch = wt_repo
-                .head()
-                .ok()
-                .and_then(|h| h.shorthand().map(String::from))
-                .unwrap_or_else(|| "HEAD".into());
-
             // Compare against self.main_dir instead of checking name == "main"
-            let wt_normalized = NormalizedPath::new(wt_path);
-            let is_main = wt_normalized.as_str() == self.main_dir.as_str();
-
-            result.push(WorktreeInfo {
-                name: name.to_string(),
-                path: wt_normalized,
-                branch,
-                is_main,
-            });
+            result.push(helpers::linked_worktree_info(repo, name, |path| {
+                path.as_str() == self.main_dir.as_str()
+            })?);
         }
 
         Ok(result)
@@ -109,7 +98,7 @@ impl LayoutProvider for ContainerLayout {
         tracing::debug!(name, base, "Creating feature worktree");
         let repo = self.open_repo()?;
         let worktree_path = self.feature_worktree(name);
-        let dir_name = branch_to_directory(name, self.naming);
+        let dir_name = self.naming.worktree_name(name);
 
         // Check if worktree already exists
         if worktree_path.exists() {
@@ -132,11 +121,30 @@ impl LayoutProvider for ContainerLayout {
     fn remove_feature(&self, name: &str) -> Result<()> {
         tracing::debug!(name, "Removing feature worktree");
         let repo = self.open_repo()?;
-        let dir_name = branch_to_directory(name, self.naming);
+        let dir_name = self.naming.worktree_name(name);
 
         helpers::remove_worktree_and_branch(repo, &dir_name)
     }
 
+    fn lock_worktree(&self, name: &str, reason: Option<&str>) -> Result<()> {
+        let repo = self.open_repo()?;
+        let dir_name = self.naming.worktree_name(name);
+
+        helpers::lock_worktree(repo, &dir_name, reason)
+    }
+
+    fn unlock_worktree(&self, name: &str) -> Result<()> {
+        let repo = self.open_repo()?;
+        let dir_name = self.naming.worktree_name(name);
+
+        helpers::unlock_worktree(repo, &dir_name)
+    }
+
+    fn prune_worktrees(&self) -> Result<Vec<String>> {
+        let repo = self.open_repo()?;
+        helpers::prune_worktrees(repo)
+    }
+
     fn current_branch(&self) -> Result<String> {
         let repo = self.open_repo()?;
         helpers::get_current_branch(repo).map(|opt| opt.unwrap_or_else(|| "HEAD".to_string()))
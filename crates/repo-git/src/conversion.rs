@@ -0,0 +1,151 @@
+//! Converting a git database between the classic and container layouts
+//!
+//! A classic checkout keeps its index and HEAD in `.git/`. In the container
+//! layout the database is bare and the main branch is checked out as a
+//! linked worktree, whose index and HEAD live in `.gt/worktrees/{name}/`.
+//! These functions move that per-checkout state between the two places, so
+//! a checkout can change layout without touching its files or staged
+//! changes. Moving the files and renaming `.git` to `.gt` is up to the
+//! caller.
+
+use std::fs;
+use std::path::Path;
+
+use git2::Repository;
+use repo_fs::NormalizedPath;
+
+use crate::{Error, Result};
+
+fn io_err(path: &Path) -> impl FnOnce(std::io::Error) -> Error + '_ {
+    move |e| Error::Fs(repo_fs::Error::io(path, e))
+}
+
+/// Names of the linked worktrees registered in the database at `git_dir`, sorted.
+pub fn linked_worktrees(git_dir: &NormalizedPath) -> Result<Vec<String>> {
+    let dir = git_dir.join("worktrees").to_native();
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut names: Vec<String> = fs::read_dir(&dir)
+        .map_err(io_err(&dir))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Fail unless the checkout at `checkout` can change layout: HEAD must be
+/// on a branch and no merge, rebase or similar operation in progress.
+pub fn ensure_convertible(checkout: &NormalizedPath) -> Result<()> {
+    let repo = Repository::open(checkout.to_native())?;
+    let hint = if repo.head_detached()? {
+        "HEAD is detached; check out a branch first."
+    } else if repo.state() != git2::RepositoryState::Clean {
+        "An operation such as a merge or rebase is in progress; finish or abort it first."
+    } else {
+        return Ok(());
+    };
+    Err(Error::LayoutUnsupported {
+        operation: "change mode".to_string(),
+        layout: checkout.to_string(),
+        hint: hint.to_string(),
+    })
+}
+
+/// Name of the worktree checked out at `worktree`, i.e. its directory name.
+fn worktree_name(worktree: &NormalizedPath) -> Result<String> {
+    worktree
+        .file_name()
+        .map(str::to_string)
+        .ok_or_else(|| Error::LayoutUnsupported {
+            operation: "change mode".to_string(),
+            layout: "container".to_string(),
+            hint: format!("{} has no directory name", worktree),
+        })
+}
+
+/// Turn the classic database at `git_dir` into a bare container database
+/// with the checkout at `worktree` as its linked worktree.
+///
+/// `git_dir` must already be at its container location (e.g. `.gt/`) and
+/// the checkout's files already moved to `worktree`. Fails with
+/// [`Error::WorktreesExist`] if the database has linked worktrees, whose
+/// administrative files would point at the old location.
+pub fn attach_main_worktree(git_dir: &NormalizedPath, worktree: &NormalizedPath) -> Result<()> {
+    let existing = linked_worktrees(git_dir)?;
+    if !existing.is_empty() {
+        return Err(Error::WorktreesExist { names: existing });
+    }
+
+    let admin = git_dir.join("worktrees").join(&worktree_name(worktree)?);
+    let admin_dir = admin.to_native();
+    fs::create_dir_all(&admin_dir).map_err(io_err(&admin_dir))?;
+
+    let index = git_dir.join("index").to_native();
+    if index.exists() {
+        fs::rename(&index, admin_dir.join("index")).map_err(io_err(&index))?;
+    }
+    let head = git_dir.join("HEAD").to_native();
+    fs::copy(&head, admin_dir.join("HEAD")).map_err(io_err(&head))?;
+    for (name, content) in [
+        ("commondir", "../..".to_string()),
+        (
+            "gitdir",
+            worktree.join(".git").to_native().display().to_string(),
+        ),
+    ] {
+        let path = admin_dir.join(name);
+        fs::write(&path, format!("{}\n", content)).map_err(io_err(&path))?;
+    }
+    let link = worktree.join(".git").to_native();
+    fs::write(&link, format!("gitdir: {}\n", admin_dir.display())).map_err(io_err(&link))?;
+
+    Repository::open_bare(git_dir.to_native())?
+        .config()?
+        .set_bool("core.bare", true)?;
+    Ok(())
+}
+
+/// Turn the container database at `git_dir` back into a classic database
+/// for the checkout at `worktree`, the reverse of [`attach_main_worktree`].
+///
+/// Fails with [`Error::WorktreesExist`] if any other worktree is still
+/// registered. Renaming `git_dir` to `.git` inside the checkout is up to
+/// the caller.
+pub fn detach_main_worktree(git_dir: &NormalizedPath, worktree: &NormalizedPath) -> Result<()> {
+    let name = worktree_name(worktree)?;
+    let others: Vec<String> = linked_worktrees(git_dir)?
+        .into_iter()
+        .filter(|other| *other != name)
+        .collect();
+    if !others.is_empty() {
+        return Err(Error::WorktreesExist { names: others });
+    }
+
+    let admin_dir = git_dir.join("worktrees").join(&name).to_native();
+    if !admin_dir.is_dir() {
+        return Err(Error::WorktreeNotFound { name });
+    }
+    let index = admin_dir.join("index");
+    if index.exists() {
+        fs::rename(&index, git_dir.join("index").to_native()).map_err(io_err(&index))?;
+    }
+    let head = admin_dir.join("HEAD");
+    fs::copy(&head, git_dir.join("HEAD").to_native()).map_err(io_err(&head))?;
+    fs::remove_dir_all(&admin_dir).map_err(io_err(&admin_dir))?;
+    let worktrees_dir = git_dir.join("worktrees").to_native();
+    // Only succeeds once empty, which it now is
+    let _ = fs::remove_dir(&worktrees_dir);
+
+    let link = worktree.join(".git").to_native();
+    if link.is_file() {
+        fs::remove_file(&link).map_err(io_err(&link))?;
+    }
+
+    Repository::open_bare(git_dir.to_native())?
+        .config()?
+        .set_bool("core.bare", false)?;
+    Ok(())
+}
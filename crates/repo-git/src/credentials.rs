@@ -0,0 +1,266 @@
+//! Authentication for push and pull
+//!
+//! libgit2 asks for credentials through a callback, calling it again after
+//! each rejected attempt. The callback built here works through the
+//! candidates in order, never repeating one, and never prompts:
+//!
+//! 1. an explicit key from [`CredentialOptions::ssh_key`]
+//! 2. the ssh-agent
+//! 3. the default keys `~/.ssh/id_ed25519`, `~/.ssh/id_ecdsa` and `~/.ssh/id_rsa`
+//! 4. an HTTPS token from [`CredentialOptions::token`] or `GIT_TOKEN`
+//! 5. the configured git credential helper
+//!
+//! Once every candidate has been rejected the operation fails with
+//! [`Error::AuthFailed`].
+//!
+//! The same callbacks watch the calling thread's
+//! [`CancelToken`](repo_fs::CancelToken): once it is cancelled the next
+//! credential request or progress report aborts the operation with
+//! [`Error::Cancelled`].
+
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+
+use git2::{Cred, CredentialType, RemoteCallbacks, Repository};
+use repo_fs::cancel;
+
+use crate::Error;
+
+/// Environment variable holding an HTTPS token.
+pub const TOKEN_ENV: &str = "GIT_TOKEN";
+
+/// Key files tried in `~/.ssh/`, in order.
+const DEFAULT_KEYS: [&str; 3] = ["id_ed25519", "id_ecdsa", "id_rsa"];
+
+/// How push and pull authenticate with a remote
+#[derive(Debug, Clone, Default)]
+pub struct CredentialOptions {
+    /// Private key to try before the ssh-agent and default keys
+    pub ssh_key: Option<PathBuf>,
+    /// Passphrase for `ssh_key`
+    pub passphrase: Option<String>,
+    /// HTTPS token; falls back to the `GIT_TOKEN` environment variable
+    pub token: Option<String>,
+}
+
+impl CredentialOptions {
+    /// Options that try `key` before the ssh-agent and default keys.
+    pub fn with_ssh_key(key: impl Into<PathBuf>) -> Self {
+        Self {
+            ssh_key: Some(key.into()),
+            ..Self::default()
+        }
+    }
+}
+
+/// One way of authenticating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Attempt {
+    SshKey(PathBuf),
+    SshAgent,
+    Token(String),
+    CredentialHelper,
+}
+
+/// Candidates for the credential types libgit2 allows, in order.
+fn candidates(options: &CredentialOptions, allowed: CredentialType) -> Vec<Attempt> {
+    let mut attempts = Vec::new();
+    if allowed.contains(CredentialType::SSH_KEY) {
+        attempts.extend(options.ssh_key.clone().map(Attempt::SshKey));
+        attempts.push(Attempt::SshAgent);
+        if let Some(home) = dirs::home_dir() {
+            attempts.extend(
+                DEFAULT_KEYS
+                    .iter()
+                    .map(|name| home.join(".ssh").join(name))
+                    .filter(|path| path.is_file())
+                    .map(Attempt::SshKey),
+            );
+        }
+    }
+    if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+        let token = options
+            .token
+            .clone()
+            .or_else(|| std::env::var(TOKEN_ENV).ok())
+            .filter(|token| !token.is_empty());
+        attempts.extend(token.map(Attempt::Token));
+        attempts.push(Attempt::CredentialHelper);
+    }
+    attempts
+}
+
+/// Supplies credentials for one remote operation and remembers whether
+/// they ran out or the operation was cancelled.
+pub(crate) struct Authenticator<'a> {
+    options: &'a CredentialOptions,
+    config: Option<git2::Config>,
+    tried: RefCell<Vec<Attempt>>,
+    exhausted: Cell<bool>,
+    cancelled: Cell<bool>,
+}
+
+impl<'a> Authenticator<'a> {
+    pub(crate) fn new(repo: &Repository, options: &'a CredentialOptions) -> Self {
+        Self {
+            options,
+            config: repo.config().ok(),
+            tried: RefCell::new(Vec::new()),
+            exhausted: Cell::new(false),
+            cancelled: Cell::new(false),
+        }
+    }
+
+    /// Callbacks that answer credential requests from this authenticator
+    /// and abort the operation once it is cancelled.
+    pub(crate) fn callbacks(&self) -> RemoteCallbacks<'_> {
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.credentials(|url, username, allowed| self.credentials(url, username, allowed));
+        callbacks.transfer_progress(|_| self.proceed());
+        callbacks.sideband_progress(|_| self.proceed());
+        callbacks
+    }
+
+    /// Whether the operation may go on; false once it is cancelled.
+    fn proceed(&self) -> bool {
+        if cancel::is_cancelled() {
+            self.cancelled.set(true);
+        }
+        !self.cancelled.get()
+    }
+
+    fn credentials(
+        &self,
+        url: &str,
+        username: Option<&str>,
+        allowed: CredentialType,
+    ) -> std::result::Result<Cred, git2::Error> {
+        if !self.proceed() {
+            return Err(git2::Error::from_str("operation cancelled"));
+        }
+        let user = username.unwrap_or("git");
+        // SSH asks for the user name before it asks for a key
+        if allowed == CredentialType::USERNAME {
+            return Cred::username(user);
+        }
+
+        loop {
+            let next = candidates(self.options, allowed)
+                .into_iter()
+                .find(|attempt| !self.tried.borrow().contains(attempt));
+            let Some(attempt) = next else {
+                self.exhausted.set(true);
+                return Err(git2::Error::from_str("no credentials were accepted"));
+            };
+            self.tried.borrow_mut().push(attempt.clone());
+
+            let cred = match attempt {
+                Attempt::SshKey(key) => {
+                    Cred::ssh_key(user, None, &key, self.options.passphrase.as_deref())
+                }
+                Attempt::SshAgent => Cred::ssh_key_from_agent(user),
+                Attempt::Token(token) => Cred::userpass_plaintext(user, &token),
+                Attempt::CredentialHelper => match &self.config {
+                    Some(config) => Cred::credential_helper(config, url, username),
+                    None => continue,
+                },
+            };
+            // A candidate that cannot even be built falls through to the next
+            if let Ok(cred) = cred {
+                return Ok(cred);
+            }
+        }
+    }
+
+    /// Map a failed remote operation to [`Error::Cancelled`] if it was
+    /// cancelled, to [`Error::AuthFailed`] if it failed to authenticate, or
+    /// to `other` otherwise.
+    pub(crate) fn error(
+        &self,
+        remote: &str,
+        e: git2::Error,
+        other: impl FnOnce(git2::Error) -> Error,
+    ) -> Error {
+        if self.cancelled.get() {
+            Error::Cancelled
+        } else if self.exhausted.get() || e.code() == git2::ErrorCode::Auth {
+            Error::AuthFailed {
+                remote: remote.to_string(),
+                message: e.message().to_string(),
+            }
+        } else {
+            other(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_explicit_key_comes_first() {
+        let options = CredentialOptions::with_ssh_key("/keys/deploy");
+        let attempts = candidates(&options, CredentialType::SSH_KEY);
+        assert_eq!(attempts[0], Attempt::SshKey(PathBuf::from("/keys/deploy")));
+        assert_eq!(attempts[1], Attempt::SshAgent);
+    }
+
+    #[test]
+    fn test_token_before_credential_helper() {
+        let options = CredentialOptions {
+            token: Some("secret".to_string()),
+            ..CredentialOptions::default()
+        };
+        assert_eq!(
+            candidates(&options, CredentialType::USER_PASS_PLAINTEXT),
+            vec![
+                Attempt::Token("secret".to_string()),
+                Attempt::CredentialHelper
+            ]
+        );
+    }
+
+    #[test]
+    fn test_each_candidate_is_tried_once() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        let options = CredentialOptions {
+            token: Some("secret".to_string()),
+            ..CredentialOptions::default()
+        };
+        let mut auth = Authenticator::new(&repo, &options);
+        // Only an empty config, so no credential helper of this machine runs
+        let config_path = temp.path().join("gitconfig");
+        std::fs::write(&config_path, "").unwrap();
+        auth.config = Some(git2::Config::open(&config_path).unwrap());
+        let url = "https://example.invalid/repo.git";
+
+        // The token, then nothing: without a helper its candidate is skipped
+        let mut granted = 0;
+        while auth
+            .credentials(url, None, CredentialType::USER_PASS_PLAINTEXT)
+            .is_ok()
+        {
+            granted += 1;
+            assert!(granted <= 1, "a candidate was repeated");
+        }
+        assert_eq!(granted, 1);
+        assert_eq!(
+            *auth.tried.borrow(),
+            vec![
+                Attempt::Token("secret".to_string()),
+                Attempt::CredentialHelper
+            ]
+        );
+        assert!(auth.exhausted.get());
+
+        let err = auth.error("origin", git2::Error::from_str("rejected"), |_| {
+            Error::PushFailed {
+                message: String::new(),
+            }
+        });
+        assert!(matches!(err, Error::AuthFailed { ref remote, .. } if remote == "origin"));
+    }
+}
@@ -20,6 +20,18 @@ pub enum Error {
     #[error("Worktree '{name}' not found")]
     WorktreeNotFound { name: String },
 
+    #[error("Worktrees still exist: {}. Remove them first.", .names.join(", "))]
+    WorktreesExist { names: Vec<String> },
+
+    #[error("Worktree '{name}' is locked{}", .reason.as_ref().map(|r| format!(": {}", r)).unwrap_or_default())]
+    WorktreeLocked {
+        name: String,
+        reason: Option<String>,
+    },
+
+    #[error("Invalid worktree naming template '{template}': {message}")]
+    InvalidNamingTemplate { template: String, message: String },
+
     #[error("Branch '{name}' not found")]
     BranchNotFound { name: String },
 
@@ -36,8 +48,8 @@ pub enum Error {
     #[error("No upstream branch configured for '{branch}'")]
     NoUpstreamBranch { branch: String },
 
-    #[error("Merge conflict: {message}")]
-    MergeConflict { message: String },
+    #[error("Merge conflict in {}", .files.join(", "))]
+    MergeConflict { files: Vec<String> },
 
     #[error("Cannot fast-forward: {message}")]
     CannotFastForward { message: String },
@@ -48,6 +60,15 @@ pub enum Error {
     #[error("Pull failed: {message}")]
     PullFailed { message: String },
 
+    #[error("Fetch failed: {message}")]
+    FetchFailed { message: String },
+
+    #[error("Authentication with remote '{remote}' failed: {message}")]
+    AuthFailed { remote: String, message: String },
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
     #[error("Working tree has uncommitted changes. Commit or stash your changes first.")]
     DirtyWorkingTree,
 }
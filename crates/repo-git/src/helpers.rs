@@ -2,11 +2,17 @@
 //!
 //! These functions encapsulate common git2 patterns used by multiple layout providers.
 
+use std::cell::{Cell, RefCell};
 use std::path::Path;
 
-use git2::{BranchType, MergeOptions, Repository, WorktreeAddOptions, WorktreePruneOptions};
+use git2::{
+    BranchType, Index, IndexAddOption, MergeOptions, Oid, Repository, ResetType, Signature,
+    WorktreeAddOptions, WorktreeLockStatus, WorktreePruneOptions,
+};
+use repo_fs::NormalizedPath;
 
-use crate::{Error, Result};
+use crate::credentials::{Authenticator, CredentialOptions};
+use crate::{Error, Result, WorktreeInfo};
 
 /// Create a new worktree with an associated branch.
 ///
@@ -63,11 +69,14 @@ pub fn create_worktree_with_branch(
 /// * `repo` - The repository containing the worktree
 /// * `name` - The name of the worktree/branch to remove
 pub fn remove_worktree_and_branch(repo: &Repository, name: &str) -> Result<()> {
-    let wt = repo
-        .find_worktree(name)
-        .map_err(|_| Error::WorktreeNotFound {
+    let wt = find_worktree(repo, name)?;
+
+    if let WorktreeLockStatus::Locked(reason) = wt.is_locked()? {
+        return Err(Error::WorktreeLocked {
             name: name.to_string(),
-        })?;
+            reason,
+        });
+    }
 
     // Configure prune options to remove valid worktrees and their directories
     let mut prune_opts = WorktreePruneOptions::new();
@@ -91,6 +100,79 @@ pub fn remove_worktree_and_branch(repo: &Repository, name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Describe a linked worktree, including its lock and prune status.
+///
+/// The branch is reported as `HEAD` if the worktree cannot be opened,
+/// e.g. because its directory was deleted.
+pub fn linked_worktree_info(
+    repo: &Repository,
+    name: &str,
+    is_main: impl Fn(&NormalizedPath) -> bool,
+) -> Result<WorktreeInfo> {
+    let wt = repo.find_worktree(name)?;
+    let path = NormalizedPath::new(wt.path());
+
+    let branch = Repository::open(wt.path())
+        .ok()
+        .and_then(|r| r.head().ok()?.shorthand().map(String::from))
+        .unwrap_or_else(|| "HEAD".into());
+
+    let (locked, lock_reason) = match wt.is_locked()? {
+        WorktreeLockStatus::Unlocked => (false, None),
+        WorktreeLockStatus::Locked(reason) => (true, reason),
+    };
+
+    Ok(WorktreeInfo {
+        name: name.to_string(),
+        is_main: is_main(&path),
+        path,
+        branch,
+        locked,
+        lock_reason,
+        prunable: wt.is_prunable(None)?,
+    })
+}
+
+/// Lock a worktree so it is never pruned.
+pub fn lock_worktree(repo: &Repository, name: &str, reason: Option<&str>) -> Result<()> {
+    find_worktree(repo, name)?.lock(reason)?;
+    Ok(())
+}
+
+/// Unlock a worktree.
+pub fn unlock_worktree(repo: &Repository, name: &str) -> Result<()> {
+    find_worktree(repo, name)?.unlock()?;
+    Ok(())
+}
+
+/// Prune worktrees whose directories are gone.
+///
+/// Locked worktrees are never pruned. Returns the names of the pruned
+/// worktrees.
+pub fn prune_worktrees(repo: &Repository) -> Result<Vec<String>> {
+    let mut pruned = Vec::new();
+    for name in repo.worktrees()?.iter().flatten() {
+        let wt = repo.find_worktree(name)?;
+        if let WorktreeLockStatus::Locked(_) = wt.is_locked()? {
+            tracing::debug!(worktree = %name, "Skipping locked worktree");
+            continue;
+        }
+        if wt.is_prunable(None)? {
+            wt.prune(None)?;
+            pruned.push(name.to_string());
+        }
+    }
+    Ok(pruned)
+}
+
+/// Find a worktree by name.
+fn find_worktree(repo: &Repository, name: &str) -> Result<git2::Worktree> {
+    repo.find_worktree(name)
+        .map_err(|_| Error::WorktreeNotFound {
+            name: name.to_string(),
+        })
+}
+
 /// Check that the working tree has no uncommitted changes.
 ///
 /// Returns `Err(Error::DirtyWorkingTree)` if there are modified or deleted
@@ -122,6 +204,120 @@ pub fn get_current_branch(repo: &Repository) -> Result<Option<String>> {
     }
 }
 
+/// Stage all changes and commit them to HEAD.
+///
+/// New, modified and deleted files are staged; ignored files are left out.
+/// On a repository with no commits yet (unborn HEAD) this creates the root
+/// commit. `author` is used as both author and committer, defaulting to the
+/// repository's configured signature.
+pub fn commit_all(repo: &Repository, message: &str, author: Option<Signature>) -> Result<Oid> {
+    let mut index = repo.index()?;
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    index.update_all(["*"], None)?;
+    index.write()?;
+
+    let tree = repo.find_tree(index.write_tree()?)?;
+    commit_tree(repo, message, author, &tree)
+}
+
+/// Commit the working-directory relative `paths`, as they are on disk, to
+/// HEAD.
+///
+/// Unlike [`commit_all`], nothing else is committed: other edits, untracked
+/// files and changes the user already staged stay as they are. A path that
+/// no longer exists is deleted; an ignored path that is not tracked is left
+/// out. The paths are staged in the index too, so they show as committed.
+pub fn commit_paths(
+    repo: &Repository,
+    message: &str,
+    author: Option<Signature>,
+    paths: &[&Path],
+) -> Result<Oid> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| git2::Error::from_str("cannot commit paths in a bare repository"))?
+        .to_path_buf();
+
+    // Start from HEAD rather than the index, so staged changes stay out
+    let mut tree_index = Index::new()?;
+    if let Some(head) = head_commit(repo)? {
+        tree_index.read_tree(&head.tree()?)?;
+    }
+    let mut index = repo.index()?;
+    for &path in paths {
+        let tracked = tree_index.get_path(path, 0).is_some();
+        if !tracked && repo.is_path_ignored(path)? {
+            continue;
+        }
+        if workdir.join(path).is_file() {
+            let blob = repo.blob_path(&workdir.join(path))?;
+            let mode = tree_index.get_path(path, 0).map_or(0o100644, |e| e.mode);
+            tree_index.add(&index_entry(path, blob, mode))?;
+            index.add_path(path)?;
+        } else if tracked {
+            tree_index.remove_path(path)?;
+            index.remove_path(path)?;
+        }
+    }
+    index.write()?;
+
+    let tree = repo.find_tree(tree_index.write_tree_to(repo)?)?;
+    commit_tree(repo, message, author, &tree)
+}
+
+/// Index entry for the blob `id` at `path`
+fn index_entry(path: &Path, id: Oid, mode: u32) -> git2::IndexEntry {
+    let time = git2::IndexTime::new(0, 0);
+    git2::IndexEntry {
+        ctime: time,
+        mtime: time,
+        dev: 0,
+        ino: 0,
+        mode,
+        uid: 0,
+        gid: 0,
+        file_size: 0,
+        id,
+        flags: 0,
+        flags_extended: 0,
+        path: path.to_string_lossy().replace('\\', "/").into_bytes(),
+    }
+}
+
+/// The commit HEAD points to, or `None` on an unborn HEAD.
+fn head_commit(repo: &Repository) -> Result<Option<git2::Commit<'_>>> {
+    match repo.head() {
+        Ok(head) => Ok(Some(head.peel_to_commit()?)),
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Commit `tree` on top of HEAD.
+fn commit_tree(
+    repo: &Repository,
+    message: &str,
+    author: Option<Signature>,
+    tree: &git2::Tree<'_>,
+) -> Result<Oid> {
+    let signature = match author {
+        Some(sig) => sig,
+        None => repo.signature()?,
+    };
+    // An unborn HEAD has no parent: this is the root commit
+    let parent = head_commit(repo)?;
+    let parents: Vec<_> = parent.iter().collect();
+
+    Ok(repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        tree,
+        &parents,
+    )?)
+}
+
 /// Push a branch to a remote repository.
 ///
 /// # Arguments
@@ -129,11 +325,13 @@ pub fn get_current_branch(repo: &Repository) -> Result<Option<String>> {
 /// * `remote` - Remote name (defaults to "origin" if None)
 /// * `branch` - Branch to push (defaults to current branch if None)
 /// * `current_branch_fn` - Function to get the current branch name
+/// * `credentials` - How to authenticate with the remote
 pub fn push(
     repo: &Repository,
     remote: Option<&str>,
     branch: Option<&str>,
     current_branch_fn: impl FnOnce() -> Result<String>,
+    credentials: &CredentialOptions,
 ) -> Result<()> {
     let remote_name = remote.unwrap_or("origin");
     let branch_name = match branch {
@@ -149,11 +347,14 @@ pub fn push(
 
     let refspec = format!("refs/heads/{}:refs/heads/{}", branch_name, branch_name);
 
-    remote
-        .push(&[&refspec], None)
-        .map_err(|e| Error::PushFailed {
+    let auth = Authenticator::new(repo, credentials);
+    let mut options = git2::PushOptions::new();
+    options.remote_callbacks(auth.callbacks());
+    remote.push(&[&refspec], Some(&mut options)).map_err(|e| {
+        auth.error(remote_name, e, |e| Error::PushFailed {
             message: e.message().to_string(),
-        })?;
+        })
+    })?;
 
     Ok(())
 }
@@ -166,12 +367,14 @@ pub fn push(
 /// * `branch` - Branch to pull (defaults to current branch if None)
 /// * `current_branch_fn` - Function to get the current branch name
 /// * `checkout_repo` - Optional different repo for checking out HEAD (e.g., main worktree)
+/// * `credentials` - How to authenticate with the remote
 pub fn pull(
     repo: &Repository,
     remote: Option<&str>,
     branch: Option<&str>,
     current_branch_fn: impl FnOnce() -> Result<String>,
     checkout_repo: Option<&Repository>,
+    credentials: &CredentialOptions,
 ) -> Result<()> {
     let remote_name = remote.unwrap_or("origin");
     let branch_name = match branch {
@@ -185,10 +388,15 @@ pub fn pull(
             name: remote_name.to_string(),
         })?;
 
+    let auth = Authenticator::new(repo, credentials);
+    let mut options = git2::FetchOptions::new();
+    options.remote_callbacks(auth.callbacks());
     remote
-        .fetch(&[&branch_name], None, None)
-        .map_err(|e| Error::PullFailed {
-            message: format!("Fetch failed: {}", e.message()),
+        .fetch(&[&branch_name], Some(&mut options), None)
+        .map_err(|e| {
+            auth.error(remote_name, e, |e| Error::PullFailed {
+                message: format!("Fetch failed: {}", e.message()),
+            })
         })?;
 
     let fetch_head = repo
@@ -236,18 +444,141 @@ pub fn pull(
     })
 }
 
+/// Counts from a fetch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FetchStats {
+    /// Objects received from the remote
+    pub received_objects: usize,
+    /// Bytes received from the remote
+    pub received_bytes: usize,
+    /// Local references created or moved
+    pub updated_refs: usize,
+}
+
+/// Fetch from a remote repository without touching any local branch.
+///
+/// # Arguments
+/// * `repo` - The repository to fetch into
+/// * `remote` - Remote name (defaults to "origin" if None)
+/// * `refspecs` - Refspecs to fetch (the remote's configured ones if empty)
+/// * `credentials` - How to authenticate with the remote
+pub fn fetch(
+    repo: &Repository,
+    remote: Option<&str>,
+    refspecs: &[&str],
+    credentials: &CredentialOptions,
+) -> Result<FetchStats> {
+    let remote_name = remote.unwrap_or("origin");
+    let mut remote = repo
+        .find_remote(remote_name)
+        .map_err(|_| Error::RemoteNotFound {
+            name: remote_name.to_string(),
+        })?;
+
+    let updated_refs = Cell::new(0);
+    let auth = Authenticator::new(repo, credentials);
+    let mut callbacks = auth.callbacks();
+    callbacks.update_tips(|_, _, _| {
+        updated_refs.set(updated_refs.get() + 1);
+        true
+    });
+    let mut options = git2::FetchOptions::new();
+    options.remote_callbacks(callbacks);
+    remote
+        .fetch(refspecs, Some(&mut options), None)
+        .map_err(|e| {
+            auth.error(remote_name, e, |e| Error::FetchFailed {
+                message: e.message().to_string(),
+            })
+        })?;
+
+    let stats = remote.stats();
+    Ok(FetchStats {
+        received_objects: stats.received_objects(),
+        received_bytes: stats.received_bytes(),
+        updated_refs: updated_refs.get(),
+    })
+}
+
+/// Delete remote-tracking references whose branch no longer exists on the
+/// remote.
+///
+/// Returns the names of the deleted references, e.g.
+/// `refs/remotes/origin/feature`.
+pub fn prune_remote(
+    repo: &Repository,
+    remote: Option<&str>,
+    credentials: &CredentialOptions,
+) -> Result<Vec<String>> {
+    let remote_name = remote.unwrap_or("origin");
+    let mut remote = repo
+        .find_remote(remote_name)
+        .map_err(|_| Error::RemoteNotFound {
+            name: remote_name.to_string(),
+        })?;
+
+    let pruned = RefCell::new(Vec::new());
+    let auth = Authenticator::new(repo, credentials);
+    let to_error = |e| {
+        auth.error(remote_name, e, |e: git2::Error| Error::FetchFailed {
+            message: format!("Prune failed: {}", e.message()),
+        })
+    };
+    let mut connection = remote
+        .connect_auth(git2::Direction::Fetch, Some(auth.callbacks()), None)
+        .map_err(to_error)?;
+    let mut callbacks = auth.callbacks();
+    callbacks.update_tips(|refname, _, _| {
+        pruned.borrow_mut().push(refname.to_string());
+        true
+    });
+    connection
+        .remote()
+        .prune(Some(callbacks))
+        .map_err(to_error)?;
+    drop(connection);
+
+    let mut pruned = pruned.into_inner();
+    pruned.sort();
+    Ok(pruned)
+}
+
+/// Paths with conflicts in `index`, sorted and without duplicates.
+fn conflicted_paths(index: &Index) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+        if let Some(entry) = entry {
+            files.push(String::from_utf8_lossy(&entry.path).into_owned());
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
 /// Merge a source branch into the current branch.
 ///
+/// A merge that conflicts returns [`Error::MergeConflict`] listing the
+/// conflicted paths. With `abort_on_conflict` the repository is reset to
+/// the pre-merge HEAD first; this discards uncommitted changes, so the
+/// working tree must be clean. Otherwise the conflict markers and merge
+/// state are left in place for the caller to resolve and commit, or to
+/// abort with `git merge --abort`.
+///
 /// # Arguments
 /// * `repo` - The repository (used for branch lookup and merge analysis)
 /// * `source` - The branch name to merge from
 /// * `current_branch_fn` - Function to get the current branch name
 /// * `merge_repo` - Optional different repo for performing the merge (e.g., main worktree)
+/// * `abort_on_conflict` - Whether to undo a conflicting merge
 pub fn merge(
     repo: &Repository,
     source: &str,
     current_branch_fn: impl FnOnce() -> Result<String>,
     merge_repo: Option<&Repository>,
+    abort_on_conflict: bool,
 ) -> Result<()> {
     let source_branch =
         repo.find_branch(source, BranchType::Local)
@@ -282,22 +613,27 @@ pub fn merge(
 
     // Normal merge
     let mr = merge_repo.unwrap_or(repo);
+    if abort_on_conflict {
+        guard_clean_worktree(mr)?;
+    }
+    let head_commit = mr.head()?.peel_to_commit()?;
     let mut merge_opts = MergeOptions::new();
     let annotated_for_merge = mr.find_annotated_commit(source_commit.id())?;
     mr.merge(&[&annotated_for_merge], Some(&mut merge_opts), None)?;
 
     let mut index = mr.index()?;
     if index.has_conflicts() {
-        mr.cleanup_state()?;
-        return Err(Error::MergeConflict {
-            message: format!("Merge of '{}' resulted in conflicts", source),
-        });
+        let files = conflicted_paths(&index)?;
+        if abort_on_conflict {
+            mr.reset(head_commit.as_object(), ResetType::Hard, None)?;
+            mr.cleanup_state()?;
+        }
+        return Err(Error::MergeConflict { files });
     }
 
     let signature = mr.signature()?;
     let tree_id = index.write_tree()?;
     let tree = mr.find_tree(tree_id)?;
-    let head_commit = mr.head()?.peel_to_commit()?;
     let source_commit_in_mr = mr.find_commit(source_commit.id())?;
 
     let message = format!("Merge branch '{}'", source);
@@ -336,4 +672,92 @@ mod tests {
         // Default branch is either "main" or "master" depending on git config
         assert!(branch == Some("main".to_string()) || branch == Some("master".to_string()));
     }
+
+    #[test]
+    fn test_commit_all_creates_root_and_child_commits() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let sig = Signature::now("Test", "test@example.com").unwrap();
+        std::fs::write(temp_dir.path().join(".gitignore"), "ignored.txt\n").unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(temp_dir.path().join("ignored.txt"), "x").unwrap();
+
+        // Unborn HEAD: the first commit is a root commit
+        let root = commit_all(&repo, "Initial", Some(sig.clone())).unwrap();
+        assert_eq!(repo.find_commit(root).unwrap().parent_count(), 0);
+
+        // Staged changes, including a deletion, are committed on top of HEAD
+        std::fs::write(temp_dir.path().join("b.txt"), "b").unwrap();
+        std::fs::remove_file(temp_dir.path().join("a.txt")).unwrap();
+        repo.index().unwrap().add_path(Path::new("b.txt")).unwrap();
+        let oid = commit_all(&repo, "Add b", Some(sig)).unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.id(), oid);
+        assert_eq!(head.parent_id(0).unwrap(), root);
+        let tree = head.tree().unwrap();
+        assert!(tree.get_name("b.txt").is_some());
+        assert!(tree.get_name("a.txt").is_none());
+        assert!(tree.get_name("ignored.txt").is_none());
+    }
+
+    #[test]
+    fn test_commit_paths_commits_only_given_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let sig = Signature::now("Test", "test@example.com").unwrap();
+        std::fs::write(root.join("generated.md"), "v1").unwrap();
+        std::fs::write(root.join("retired.md"), "old").unwrap();
+        std::fs::write(root.join("notes.txt"), "mine").unwrap();
+        let base = commit_all(&repo, "Initial", Some(sig.clone())).unwrap();
+
+        std::fs::write(root.join("generated.md"), "v2").unwrap();
+        std::fs::remove_file(root.join("retired.md")).unwrap();
+        std::fs::write(root.join("notes.txt"), "edited").unwrap();
+        std::fs::write(root.join("untracked.txt"), "new").unwrap();
+        std::fs::write(root.join("staged.txt"), "staged").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("staged.txt")).unwrap();
+        index.write().unwrap();
+
+        let oid = commit_paths(
+            &repo,
+            "Sync",
+            Some(sig),
+            &[Path::new("generated.md"), Path::new("retired.md")],
+        )
+        .unwrap();
+
+        let commit = repo.find_commit(oid).unwrap();
+        assert_eq!(commit.parent_id(0).unwrap(), base);
+        let tree = commit.tree().unwrap();
+        let blob = tree
+            .get_name("generated.md")
+            .unwrap()
+            .to_object(&repo)
+            .unwrap();
+        assert_eq!(blob.as_blob().unwrap().content(), b"v2");
+        assert!(tree.get_name("retired.md").is_none());
+        let notes = tree
+            .get_name("notes.txt")
+            .unwrap()
+            .to_object(&repo)
+            .unwrap();
+        assert_eq!(notes.as_blob().unwrap().content(), b"mine");
+        assert!(tree.get_name("untracked.txt").is_none());
+        assert!(tree.get_name("staged.txt").is_none());
+
+        // The user's staged file is still staged, the synced files are clean
+        let statuses = repo.statuses(None).unwrap();
+        let status = |path: &str| {
+            statuses
+                .iter()
+                .find(|e| e.path() == Some(path))
+                .map(|e| e.status())
+        };
+        assert_eq!(status("staged.txt"), Some(git2::Status::INDEX_NEW));
+        assert_eq!(status("generated.md"), None);
+        assert_eq!(status("retired.md"), None);
+    }
 }
@@ -19,6 +19,10 @@ use repo_fs::NormalizedPath;
 /// │   └── feature-x/
 /// └── src/           # Main branch files
 /// ```
+///
+/// Worktrees share the main repository's `.git`. Since they live inside the
+/// main worktree, `.worktrees/` is added to `.git/info/exclude` when the
+/// first feature is created so they never show up as untracked files.
 pub struct InRepoWorktreesLayout {
     root: NormalizedPath,
     git_dir: NormalizedPath,
@@ -30,6 +34,7 @@ pub struct InRepoWorktreesLayout {
 impl InRepoWorktreesLayout {
     /// Create a new InRepoWorktreesLayout for the given root directory.
     pub fn new(root: NormalizedPath, naming: NamingStrategy) -> Result<Self> {
+        naming.validate()?;
         let git_dir = root.join(".git");
         let worktrees_dir = root.join(".worktrees");
 
@@ -51,6 +56,37 @@ impl InRepoWorktreesLayout {
         let _ = self.repo_cache.set(repo);
         Ok(self.repo_cache.get().expect("just initialized"))
     }
+
+    /// Hide `.worktrees/` from the main worktree's status via `info/exclude`.
+    fn exclude_worktrees_dir(&self, repo: &Repository) -> Result<()> {
+        const PATTERN: &str = "/.worktrees/";
+
+        let exclude = repo.path().join("info").join("exclude");
+        let io_err = |e: std::io::Error| Error::Fs(repo_fs::Error::io(&exclude, e));
+        let mut content = match std::fs::read_to_string(&exclude) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(io_err(e)),
+        };
+        if content.lines().any(|line| {
+            matches!(
+                line.trim(),
+                PATTERN | ".worktrees/" | ".worktrees" | "/.worktrees"
+            )
+        }) {
+            return Ok(());
+        }
+
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(PATTERN);
+        content.push('\n');
+        Ok(repo_fs::io::write_text(
+            &NormalizedPath::new(&exclude),
+            &content,
+        )?)
+    }
 }
 
 impl LayoutProvider for InRepoWorktreesLayout {
@@ -63,8 +99,16 @@ impl LayoutProvider for InRepoWorktreesLayout {
     }
 
     fn feature_worktree(&self, name: &str) -> NormalizedPath {
-        let dir_name = branch_to_directory(name, self.naming);
-        self.worktrees_dir.join(&dir_name)
+        // An existing worktree keeps its path even if the naming would now
+        // expand differently, e.g. a template with a `{date}`
+        let registered = self.open_repo().ok().and_then(|repo| {
+            let wt = repo.find_worktree(&self.naming.worktree_name(name)).ok()?;
+            Some(NormalizedPath::new(wt.path()))
+        });
+        registered.unwrap_or_else(|| {
+            self.worktrees_dir
+                .join(&branch_to_directory(name, &self.naming))
+        })
     }
 
     fn list_worktrees(&self) -> Result<Vec<WorktreeInfo>> {
@@ -77,6 +121,9 @@ impl LayoutProvider for InRepoWorktreesLayout {
             path: self.root.clone(),
             branch: main_branch,
             is_main: true,
+            locked: false,
+            lock_reason: None,
+            prunable: false,
         }];
 
         // Add linked worktrees
@@ -87,22 +134,7 @@ impl LayoutProvider for InRepoWorktreesLayout {
                 None => continue,
             };
 
-            let wt = repo.find_worktree(name)?;
-            let wt_path = wt.path();
-
-            let wt_repo = Repository::open(wt_path)?;
-            let branch = wt_repo
-                .head()
-                .ok()
-                .and_then(|h| h.shorthand().map(String::from))
-                .unwrap_or_else(|| "HEAD".into());
-
-            result.push(WorktreeInfo {
-                name: name.to_string(),
-                path: NormalizedPath::new(wt_path),
-                branch,
-                is_main: false,
-            });
+            result.push(helpers::linked_worktree_info(repo, name, |_| false)?);
         }
 
         Ok(result)
@@ -112,7 +144,7 @@ impl LayoutProvider for InRepoWorktreesLayout {
         tracing::debug!(name, base, "Creating feature worktree");
         let repo = self.open_repo()?;
         let worktree_path = self.feature_worktree(name);
-        let dir_name = branch_to_directory(name, self.naming);
+        let dir_name = self.naming.worktree_name(name);
 
         // Check if worktree already exists
         if worktree_path.exists() {
@@ -125,6 +157,7 @@ impl LayoutProvider for InRepoWorktreesLayout {
         // Ensure .worktrees directory exists
         std::fs::create_dir_all(self.worktrees_dir.to_native())
             .map_err(|e| Error::Fs(repo_fs::Error::io(self.worktrees_dir.to_native(), e)))?;
+        self.exclude_worktrees_dir(repo)?;
 
         helpers::create_worktree_with_branch(
             repo,
@@ -139,11 +172,30 @@ impl LayoutProvider for InRepoWorktreesLayout {
     fn remove_feature(&self, name: &str) -> Result<()> {
         tracing::debug!(name, "Removing feature worktree");
         let repo = self.open_repo()?;
-        let dir_name = branch_to_directory(name, self.naming);
+        let dir_name = self.naming.worktree_name(name);
 
         helpers::remove_worktree_and_branch(repo, &dir_name)
     }
 
+    fn lock_worktree(&self, name: &str, reason: Option<&str>) -> Result<()> {
+        let repo = self.open_repo()?;
+        let dir_name = self.naming.worktree_name(name);
+
+        helpers::lock_worktree(repo, &dir_name, reason)
+    }
+
+    fn unlock_worktree(&self, name: &str) -> Result<()> {
+        let repo = self.open_repo()?;
+        let dir_name = self.naming.worktree_name(name);
+
+        helpers::unlock_worktree(repo, &dir_name)
+    }
+
+    fn prune_worktrees(&self) -> Result<Vec<String>> {
+        let repo = self.open_repo()?;
+        helpers::prune_worktrees(repo)
+    }
+
     fn current_branch(&self) -> Result<String> {
         let repo = self.open_repo()?;
         helpers::get_current_branch(repo).map(|opt| opt.unwrap_or_else(|| "HEAD".to_string()))
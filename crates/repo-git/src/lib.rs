@@ -5,19 +5,26 @@
 pub mod classic;
 pub mod commits;
 pub mod container;
+pub mod conversion;
+pub mod credentials;
 pub mod error;
 pub mod helpers;
 pub mod in_repo_worktrees;
 pub mod naming;
 pub mod provider;
+pub mod status;
 
 pub use classic::ClassicLayout;
 pub use commits::{CommitInfo, list_recent_commits};
 pub use container::ContainerLayout;
+pub use credentials::CredentialOptions;
 pub use error::{Error, Result};
 pub use helpers::{
-    create_worktree_with_branch, get_current_branch, merge, pull, push, remove_worktree_and_branch,
+    FetchStats, commit_all, commit_paths, create_worktree_with_branch, fetch, get_current_branch,
+    lock_worktree, merge, prune_remote, prune_worktrees, pull, push, remove_worktree_and_branch,
+    unlock_worktree,
 };
 pub use in_repo_worktrees::InRepoWorktreesLayout;
 pub use naming::NamingStrategy;
 pub use provider::{LayoutProvider, WorktreeInfo};
+pub use status::{BranchStatus, WorkingTreeStatus, branch_status, working_tree_status};
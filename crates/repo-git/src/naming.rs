@@ -1,7 +1,12 @@
 //! Branch name to directory name mapping strategies
 
+use crate::{Error, Result};
+
+/// Placeholders a [`NamingStrategy::Template`] pattern may use
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &["branch", "slug", "date"];
+
 /// Strategy for converting branch names to directory names.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum NamingStrategy {
     /// Convert slashes to dashes, remove unsafe characters.
     /// `feat/user-auth` -> `feat-user-auth`
@@ -11,16 +16,136 @@ pub enum NamingStrategy {
     /// Preserve slashes as directory hierarchy.
     /// `feat/user-auth` -> `feat/user-auth`
     Hierarchical,
+
+    /// Expand a pattern with `{branch}` (hierarchical name), `{slug}` and
+    /// `{date}` (`YYYY-MM-DD`) placeholders, e.g. `wt/{slug}`.
+    /// `feat/user-auth` -> `wt/feat-user-auth`
+    ///
+    /// Construct with [`NamingStrategy::template`] to validate the pattern.
+    Template(String),
+}
+
+impl NamingStrategy {
+    /// Create a template strategy, rejecting unknown placeholders.
+    pub fn template(pattern: impl Into<String>) -> Result<Self> {
+        let strategy = Self::Template(pattern.into());
+        strategy.validate()?;
+        Ok(strategy)
+    }
+
+    /// Check that a template strategy only uses known placeholders.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::Template(pattern) => parse_template(pattern).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Name of the git worktree and branch created for `branch`.
+    ///
+    /// Equal to the directory name, except for templates, whose expansion
+    /// may change over time (`{date}`) or contain slashes; those use the slug.
+    pub fn worktree_name(&self, branch: &str) -> String {
+        match self {
+            Self::Template(_) => slugify(branch),
+            _ => branch_to_directory(branch, self),
+        }
+    }
 }
 
 /// Convert a branch name to a directory name using the given strategy.
-pub fn branch_to_directory(branch: &str, strategy: NamingStrategy) -> String {
+pub fn branch_to_directory(branch: &str, strategy: &NamingStrategy) -> String {
     match strategy {
         NamingStrategy::Slug => slugify(branch),
         NamingStrategy::Hierarchical => sanitize_hierarchical(branch),
+        NamingStrategy::Template(pattern) => {
+            let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+            expand_template(pattern, branch, &date)
+        }
     }
 }
 
+/// A parsed template segment
+enum Segment<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Split a template into literals and known placeholders.
+fn parse_template(pattern: &str) -> Result<Vec<Segment<'_>>> {
+    let invalid = |message: String| Error::InvalidNamingTemplate {
+        template: pattern.to_string(),
+        message,
+    };
+
+    let mut segments = Vec::new();
+    let mut rest = pattern;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(invalid("unmatched '}'".into()));
+        }
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| invalid("unterminated placeholder".into()))?;
+        let name = &rest[start + 1..start + end];
+        if !TEMPLATE_PLACEHOLDERS.contains(&name) {
+            return Err(invalid(format!(
+                "unknown placeholder '{{{}}}', expected one of {{{}}}",
+                name,
+                TEMPLATE_PLACEHOLDERS.join("}, {")
+            )));
+        }
+        segments.push(Segment::Literal(&rest[..start]));
+        segments.push(Segment::Placeholder(name));
+        rest = &rest[start + end + 1..];
+    }
+    segments.push(Segment::Literal(rest));
+    Ok(segments)
+}
+
+/// Expand a template for `branch` and sanitize it to a relative path.
+///
+/// Unknown placeholders are kept literally; [`NamingStrategy::validate`]
+/// rejects them before a layout uses the template.
+fn expand_template(pattern: &str, branch: &str, date: &str) -> String {
+    let expanded = match parse_template(pattern) {
+        Ok(segments) => segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => text.to_string(),
+                Segment::Placeholder("branch") => sanitize_hierarchical(branch),
+                Segment::Placeholder("slug") => slugify(branch),
+                Segment::Placeholder(_) => date.to_string(),
+            })
+            .collect(),
+        Err(_) => pattern.to_string(),
+    };
+    sanitize_relative_path(&expanded)
+}
+
+/// Keep a path relative and free of unsafe characters.
+///
+/// Empty, `.` and `..` components are dropped and characters other than
+/// alphanumerics and `-_.@+` are replaced with dashes.
+fn sanitize_relative_path(path: &str) -> String {
+    path.split(['/', '\\'])
+        .filter(|component| !matches!(*component, "" | "." | ".."))
+        .map(|component| {
+            component
+                .chars()
+                .map(|c| {
+                    if c.is_alphanumeric() || "-_.@+".contains(c) {
+                        c
+                    } else {
+                        '-'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 /// Convert branch name to a flat slug.
 fn slugify(branch: &str) -> String {
     let mut result = String::with_capacity(branch.len());
@@ -102,7 +227,7 @@ mod tests {
 
     #[test]
     fn test_slug_empty_string() {
-        let result = branch_to_directory("", NamingStrategy::Slug);
+        let result = branch_to_directory("", &NamingStrategy::Slug);
         assert_eq!(
             result, "",
             "Empty branch name should produce empty directory name"
@@ -111,7 +236,7 @@ mod tests {
 
     #[test]
     fn test_hierarchical_empty_string() {
-        let result = branch_to_directory("", NamingStrategy::Hierarchical);
+        let result = branch_to_directory("", &NamingStrategy::Hierarchical);
         assert_eq!(
             result, "",
             "Empty branch name should produce empty directory name"
@@ -121,19 +246,19 @@ mod tests {
     #[test]
     fn test_slug_very_long_branch_name() {
         let long_name = "a".repeat(300);
-        let result = branch_to_directory(&long_name, NamingStrategy::Slug);
+        let result = branch_to_directory(&long_name, &NamingStrategy::Slug);
         assert_eq!(result.len(), 300);
     }
 
     #[test]
     fn test_hierarchical_special_characters() {
-        let result = branch_to_directory("feat:bug#123", NamingStrategy::Hierarchical);
+        let result = branch_to_directory("feat:bug#123", &NamingStrategy::Hierarchical);
         assert_eq!(result, "feat-bug-123");
     }
 
     #[test]
     fn test_hierarchical_leading_trailing_slashes() {
-        let result = branch_to_directory("/feat/auth/", NamingStrategy::Hierarchical);
+        let result = branch_to_directory("/feat/auth/", &NamingStrategy::Hierarchical);
         assert_eq!(
             result, "feat/auth",
             "Should strip leading and trailing slashes"
@@ -142,7 +267,7 @@ mod tests {
 
     #[test]
     fn test_hierarchical_multiple_consecutive_slashes() {
-        let result = branch_to_directory("feat//double//slash", NamingStrategy::Hierarchical);
+        let result = branch_to_directory("feat//double//slash", &NamingStrategy::Hierarchical);
         assert_eq!(
             result, "feat/double/slash",
             "Should collapse consecutive slashes"
@@ -151,55 +276,94 @@ mod tests {
 
     #[test]
     fn test_slug_emoji_stripped() {
-        let result = branch_to_directory("fix/bug-\u{1f41b}", NamingStrategy::Slug);
+        let result = branch_to_directory("fix/bug-\u{1f41b}", &NamingStrategy::Slug);
         assert_eq!(result, "fix-bug", "Emoji should be stripped from slug");
     }
 
     #[test]
     fn test_slug_simple_branch() {
-        let result = branch_to_directory("feature-auth", NamingStrategy::Slug);
+        let result = branch_to_directory("feature-auth", &NamingStrategy::Slug);
         assert_eq!(result, "feature-auth");
     }
 
     #[test]
     fn test_slug_branch_with_slash() {
-        let result = branch_to_directory("feat/user-auth", NamingStrategy::Slug);
+        let result = branch_to_directory("feat/user-auth", &NamingStrategy::Slug);
         assert_eq!(result, "feat-user-auth");
     }
 
     #[test]
     fn test_slug_multiple_slashes() {
-        let result = branch_to_directory("feat/user/auth/login", NamingStrategy::Slug);
+        let result = branch_to_directory("feat/user/auth/login", &NamingStrategy::Slug);
         assert_eq!(result, "feat-user-auth-login");
     }
 
     #[test]
     fn test_slug_special_characters() {
-        let result = branch_to_directory("fix:bug#123", NamingStrategy::Slug);
+        let result = branch_to_directory("fix:bug#123", &NamingStrategy::Slug);
         assert_eq!(result, "fix-bug-123");
     }
 
     #[test]
     fn test_hierarchical_simple_branch() {
-        let result = branch_to_directory("feature-auth", NamingStrategy::Hierarchical);
+        let result = branch_to_directory("feature-auth", &NamingStrategy::Hierarchical);
         assert_eq!(result, "feature-auth");
     }
 
     #[test]
     fn test_hierarchical_branch_with_slash() {
-        let result = branch_to_directory("feat/user-auth", NamingStrategy::Hierarchical);
+        let result = branch_to_directory("feat/user-auth", &NamingStrategy::Hierarchical);
         assert_eq!(result, "feat/user-auth");
     }
 
     #[test]
     fn test_slug_removes_leading_trailing_dashes() {
-        let result = branch_to_directory("/feat/", NamingStrategy::Slug);
+        let result = branch_to_directory("/feat/", &NamingStrategy::Slug);
         assert_eq!(result, "feat");
     }
 
+    #[test]
+    fn test_template_prefix_directory() {
+        let strategy = NamingStrategy::template("wt/{branch}").unwrap();
+        let result = branch_to_directory("feat/user-auth", &strategy);
+        assert_eq!(result, "wt/feat/user-auth");
+        assert_eq!(strategy.worktree_name("feat/user-auth"), "feat-user-auth");
+    }
+
+    #[test]
+    fn test_template_slug_and_date() {
+        let result = expand_template("{slug}@{date}", "feat/user-auth", "2026-01-31");
+        assert_eq!(result, "feat-user-auth@2026-01-31");
+
+        let strategy = NamingStrategy::template("{date}/{slug}").unwrap();
+        let result = branch_to_directory("fix/bug", &strategy);
+        let (date, slug) = result.split_once('/').unwrap();
+        assert_eq!(slug, "fix-bug");
+        assert_eq!(date.len(), "2026-01-31".len());
+    }
+
+    #[test]
+    fn test_template_sanitized_to_relative_path() {
+        let result = expand_template("/../wt//{branch}:x", "feat/a b", "");
+        assert_eq!(result, "wt/feat/a-b-x");
+    }
+
+    #[test]
+    fn test_template_unknown_placeholder_rejected() {
+        let err = NamingStrategy::template("{branch}@{short_sha}").unwrap_err();
+        assert!(err.to_string().contains("{short_sha}"), "{}", err);
+        assert!(NamingStrategy::template("wt/{slug").is_err());
+        assert!(NamingStrategy::template("wt}/{slug}").is_err());
+        assert!(
+            NamingStrategy::Template("{nope}".into())
+                .validate()
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_slug_collapses_multiple_dashes() {
-        let result = branch_to_directory("feat//double//slash", NamingStrategy::Slug);
+        let result = branch_to_directory("feat//double//slash", &NamingStrategy::Slug);
         assert_eq!(result, "feat-double-slash");
     }
 }
@@ -17,6 +17,15 @@ pub struct WorktreeInfo {
 
     /// Whether this is the main/primary worktree
     pub is_main: bool,
+
+    /// Whether the worktree is locked against pruning
+    pub locked: bool,
+
+    /// Reason given when the worktree was locked, if any
+    pub lock_reason: Option<String>,
+
+    /// Whether git considers the worktree prunable (its directory is gone)
+    pub prunable: bool,
 }
 
 /// Trait for layout-agnostic git operations.
@@ -48,8 +57,21 @@ pub trait LayoutProvider {
     fn create_feature(&self, name: &str, base: Option<&str>) -> Result<NormalizedPath>;
 
     /// Remove a feature worktree
+    ///
+    /// Fails with `Error::WorktreeLocked` if the worktree is locked.
     fn remove_feature(&self, name: &str) -> Result<()>;
 
+    /// Lock a feature worktree so it is never pruned
+    fn lock_worktree(&self, name: &str, reason: Option<&str>) -> Result<()>;
+
+    /// Unlock a feature worktree
+    fn unlock_worktree(&self, name: &str) -> Result<()>;
+
+    /// Prune worktrees whose directories are gone, skipping locked ones
+    ///
+    /// Returns the names of the pruned worktrees.
+    fn prune_worktrees(&self) -> Result<Vec<String>>;
+
     /// Get the current branch name
     fn current_branch(&self) -> Result<String>;
 }
@@ -0,0 +1,277 @@
+//! Working tree status of a git repository.
+
+use std::path::Path;
+
+use git2::{BranchType, Repository, Status, StatusOptions};
+
+use crate::Result;
+
+/// Status of a repository's working tree, index, and upstream tracking.
+///
+/// Paths are relative to the working directory, with `/` separators.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkingTreeStatus {
+    /// Current branch, or `None` if HEAD is detached.
+    pub branch: Option<String>,
+
+    /// Commits on the current branch that its upstream does not have.
+    pub ahead: usize,
+
+    /// Commits on the upstream that the current branch does not have.
+    pub behind: usize,
+
+    /// Paths with changes staged in the index.
+    pub staged: Vec<String>,
+
+    /// Tracked paths with changes not yet staged.
+    pub unstaged: Vec<String>,
+
+    /// Paths not tracked by git (ignored files are left out).
+    pub untracked: Vec<String>,
+}
+
+/// Upstream and working tree status of one branch, as shown in branch
+/// listings.
+///
+/// Each field is `None` when it does not apply: `ahead` and `behind`
+/// without an upstream, `dirty` when the working tree was not checked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchStatus {
+    /// Commits on the branch that its upstream does not have.
+    pub ahead: Option<usize>,
+
+    /// Commits on the upstream that the branch does not have.
+    pub behind: Option<usize>,
+
+    /// Whether the working tree has uncommitted changes, counting
+    /// untracked but not ignored files.
+    pub dirty: Option<bool>,
+}
+
+/// Status of local branch `name` in the repository whose working tree is
+/// at `path`.
+///
+/// The working tree is only checked when `check_dirty` is set, since
+/// reading it can be slow; it should be the branch's own checkout.
+pub fn branch_status(path: &Path, name: &str, check_dirty: bool) -> Result<BranchStatus> {
+    let repo = Repository::open(path)?;
+    let (ahead, behind) = match upstream_ahead_behind(&repo, name)? {
+        Some((ahead, behind)) => (Some(ahead), Some(behind)),
+        None => (None, None),
+    };
+
+    let dirty = if check_dirty {
+        let mut options = StatusOptions::new();
+        options.include_untracked(true).include_ignored(false);
+        Some(!repo.statuses(Some(&mut options))?.is_empty())
+    } else {
+        None
+    };
+
+    Ok(BranchStatus {
+        ahead,
+        behind,
+        dirty,
+    })
+}
+
+/// Collect the working tree status of `repo`.
+///
+/// A path can be both staged and unstaged when it was changed again after
+/// staging. `ahead` and `behind` are zero when the branch has no upstream.
+pub fn working_tree_status(repo: &Repository) -> Result<WorkingTreeStatus> {
+    // Read HEAD symbolically so an unborn branch still has a name
+    let head = repo.find_reference("HEAD")?;
+    let branch = head
+        .symbolic_target()
+        .and_then(|target| target.strip_prefix("refs/heads/"))
+        .map(String::from);
+
+    let (ahead, behind) = match &branch {
+        Some(name) => ahead_behind(repo, name)?,
+        None => (0, 0),
+    };
+
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+
+    let mut status = WorkingTreeStatus {
+        branch,
+        ahead,
+        behind,
+        ..Default::default()
+    };
+
+    for entry in repo.statuses(Some(&mut options))?.iter() {
+        let Some(path) = entry.path() else {
+            continue;
+        };
+        let flags = entry.status();
+
+        if flags.intersects(
+            Status::INDEX_NEW
+                | Status::INDEX_MODIFIED
+                | Status::INDEX_DELETED
+                | Status::INDEX_RENAMED
+                | Status::INDEX_TYPECHANGE,
+        ) {
+            status.staged.push(path.to_string());
+        }
+        if flags.intersects(
+            Status::WT_MODIFIED
+                | Status::WT_DELETED
+                | Status::WT_RENAMED
+                | Status::WT_TYPECHANGE
+                | Status::CONFLICTED,
+        ) {
+            status.unstaged.push(path.to_string());
+        }
+        if flags.contains(Status::WT_NEW) {
+            status.untracked.push(path.to_string());
+        }
+    }
+
+    Ok(status)
+}
+
+/// Commits ahead of and behind the upstream of local branch `name`.
+fn ahead_behind(repo: &Repository, name: &str) -> Result<(usize, usize)> {
+    Ok(upstream_ahead_behind(repo, name)?.unwrap_or((0, 0)))
+}
+
+/// Commits ahead of and behind the upstream of local branch `name`, or
+/// `None` if it has no upstream.
+fn upstream_ahead_behind(repo: &Repository, name: &str) -> Result<Option<(usize, usize)>> {
+    let Ok(branch) = repo.find_branch(name, BranchType::Local) else {
+        // Unborn branch
+        return Ok(None);
+    };
+    let Ok(upstream) = branch.upstream() else {
+        return Ok(None);
+    };
+
+    match (branch.get().target(), upstream.get().target()) {
+        (Some(local), Some(remote)) => Ok(Some(repo.graph_ahead_behind(local, remote)?)),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn commit_file(repo: &Repository, path: &str, content: &str) {
+        let workdir = repo.workdir().unwrap();
+        fs::write(workdir.join(path), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parents: Vec<_> = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parents: Vec<_> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, path, &tree, &parents)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_status_classifies_changes() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        commit_file(&repo, "tracked.txt", "one");
+        commit_file(&repo, "staged.txt", "one");
+
+        fs::write(temp.path().join("tracked.txt"), "two").unwrap();
+        fs::write(temp.path().join("staged.txt"), "two").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("staged.txt")).unwrap();
+        index.write().unwrap();
+        fs::create_dir(temp.path().join("new")).unwrap();
+        fs::write(temp.path().join("new/file.txt"), "").unwrap();
+
+        let status = working_tree_status(&repo).unwrap();
+
+        assert!(status.branch.is_some());
+        assert_eq!((status.ahead, status.behind), (0, 0));
+        assert_eq!(status.staged, vec!["staged.txt"]);
+        assert_eq!(status.unstaged, vec!["tracked.txt"]);
+        assert_eq!(status.untracked, vec!["new/file.txt"]);
+    }
+
+    #[test]
+    fn test_status_on_unborn_branch() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        fs::write(temp.path().join("file.txt"), "").unwrap();
+
+        let status = working_tree_status(&repo).unwrap();
+
+        assert!(status.branch.is_some());
+        assert_eq!(status.untracked, vec!["file.txt"]);
+    }
+
+    #[test]
+    fn test_branch_status_against_upstream() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        commit_file(&repo, "file.txt", "one");
+        let name = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        // Track a copy of the branch, then move ahead of it
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.remote("origin", "https://example.invalid/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/main", head.id(), true, "test")
+            .unwrap();
+        let mut config = repo.config().unwrap();
+        config
+            .set_str(&format!("branch.{name}.remote"), "origin")
+            .unwrap();
+        config
+            .set_str(&format!("branch.{name}.merge"), "refs/heads/main")
+            .unwrap();
+        commit_file(&repo, "file.txt", "two");
+
+        let status = branch_status(temp.path(), &name, true).unwrap();
+        assert_eq!(
+            status,
+            BranchStatus {
+                ahead: Some(1),
+                behind: Some(0),
+                dirty: Some(false),
+            }
+        );
+
+        fs::write(temp.path().join("untracked.txt"), "").unwrap();
+        assert_eq!(
+            branch_status(temp.path(), &name, true).unwrap().dirty,
+            Some(true)
+        );
+        assert_eq!(
+            branch_status(temp.path(), &name, false).unwrap().dirty,
+            None
+        );
+    }
+
+    #[test]
+    fn test_branch_status_without_upstream() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        commit_file(&repo, "file.txt", "one");
+        let name = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let status = branch_status(temp.path(), &name, false).unwrap();
+        assert_eq!(status, BranchStatus::default());
+    }
+}
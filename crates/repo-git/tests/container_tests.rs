@@ -128,6 +128,27 @@ fn test_container_slug_naming() {
     layout.remove_feature("feat/user-auth").unwrap();
 }
 
+#[test]
+fn test_container_template_naming() {
+    let temp = TempDir::new().unwrap();
+    let root = NormalizedPath::new(temp.path());
+    let naming = NamingStrategy::template("wt/{slug}").unwrap();
+    let layout = ContainerLayout::new(root.clone(), naming).unwrap();
+
+    assert_eq!(
+        layout.feature_worktree("feat/user-auth"),
+        root.join("wt/feat-user-auth")
+    );
+}
+
+#[test]
+fn test_container_rejects_invalid_template() {
+    let temp = TempDir::new().unwrap();
+    let naming = NamingStrategy::Template("{branch}@{short_sha}".into());
+    let result = ContainerLayout::new(NormalizedPath::new(temp.path()), naming);
+    assert!(result.is_err());
+}
+
 #[test]
 fn test_container_create_duplicate_feature_returns_error() {
     let (_temp, layout) = setup_container_repo();
@@ -0,0 +1,85 @@
+//! Tests for converting git databases between classic and container layouts
+
+use repo_fs::NormalizedPath;
+use repo_git::conversion::{attach_main_worktree, detach_main_worktree, linked_worktrees};
+use repo_git::{ContainerLayout, Error, LayoutProvider, NamingStrategy};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn setup_classic_repo(root: &Path) {
+    git(root, &["init", "-b", "main"]);
+    git(root, &["config", "user.email", "test@example.com"]);
+    git(root, &["config", "user.name", "Test User"]);
+    git(root, &["config", "commit.gpgSign", "false"]);
+    fs::write(root.join("README.md"), "# Test").unwrap();
+    git(root, &["add", "README.md"]);
+    git(root, &["commit", "-m", "Initial commit"]);
+}
+
+#[test]
+fn test_classic_to_container_and_back() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    setup_classic_repo(root);
+    // Staged changes must survive both conversions
+    fs::write(root.join("staged.txt"), "staged").unwrap();
+    git(root, &["add", "staged.txt"]);
+
+    let container = NormalizedPath::new(root);
+    let git_dir = container.join(".gt");
+    let main = container.join("main");
+    fs::rename(root.join(".git"), git_dir.to_native()).unwrap();
+    fs::create_dir(main.to_native()).unwrap();
+    for name in ["README.md", "staged.txt"] {
+        fs::rename(root.join(name), main.to_native().join(name)).unwrap();
+    }
+    attach_main_worktree(&git_dir, &main).unwrap();
+
+    let layout = ContainerLayout::new(container.clone(), NamingStrategy::Slug).unwrap();
+    assert_eq!(layout.current_branch().unwrap(), "main");
+    assert_eq!(linked_worktrees(&git_dir).unwrap(), vec!["main"]);
+    assert_eq!(
+        git(&main.to_native(), &["status", "--porcelain"]),
+        "A  staged.txt"
+    );
+    assert_eq!(git(&git_dir.to_native(), &["config", "core.bare"]), "true");
+
+    detach_main_worktree(&git_dir, &main).unwrap();
+    for name in ["README.md", "staged.txt"] {
+        fs::rename(main.to_native().join(name), root.join(name)).unwrap();
+    }
+    fs::remove_dir(main.to_native()).unwrap();
+    fs::rename(git_dir.to_native(), root.join(".git")).unwrap();
+
+    assert_eq!(git(root, &["status", "--porcelain"]), "A  staged.txt");
+    assert_eq!(git(root, &["rev-parse", "--abbrev-ref", "HEAD"]), "main");
+    assert!(!root.join(".git/worktrees").exists());
+}
+
+#[test]
+fn test_conversion_refuses_with_feature_worktrees() {
+    let temp = TempDir::new().unwrap();
+    let root = &temp.path().join("repo");
+    fs::create_dir(root).unwrap();
+    setup_classic_repo(root);
+    git(root, &["worktree", "add", "-b", "feature", "../feature-wt"]);
+
+    let git_dir = NormalizedPath::new(root.join(".git"));
+    let err = attach_main_worktree(&git_dir, &NormalizedPath::new(root.join("main"))).unwrap_err();
+    match err {
+        Error::WorktreesExist { names } => assert_eq!(names, vec!["feature-wt"]),
+        other => panic!("Expected WorktreesExist, got: {other}"),
+    }
+}
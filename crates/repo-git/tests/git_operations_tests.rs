@@ -1,11 +1,11 @@
 //! Tests for git push/pull/merge operations
 
 use repo_fs::NormalizedPath;
-use repo_git::NamingStrategy;
 use repo_git::classic::ClassicLayout;
 use repo_git::container::ContainerLayout;
 use repo_git::in_repo_worktrees::InRepoWorktreesLayout;
 use repo_git::provider::LayoutProvider;
+use repo_git::{CredentialOptions, NamingStrategy};
 use std::fs;
 use std::process::Command;
 use tempfile::TempDir;
@@ -64,7 +64,13 @@ fn test_classic_push_no_remote_returns_error() {
     let (_temp, layout) = setup_classic_repo_with_git();
     let repo = layout.open_repo().unwrap();
 
-    let result = repo_git::push(&repo, None, None, || layout.current_branch());
+    let result = repo_git::push(
+        &repo,
+        None,
+        None,
+        || layout.current_branch(),
+        &CredentialOptions::default(),
+    );
     assert!(result.is_err());
 
     let err = result.unwrap_err();
@@ -81,7 +87,13 @@ fn test_classic_push_named_remote_not_found() {
     let (_temp, layout) = setup_classic_repo_with_git();
     let repo = layout.open_repo().unwrap();
 
-    let result = repo_git::push(&repo, Some("upstream"), None, || layout.current_branch());
+    let result = repo_git::push(
+        &repo,
+        Some("upstream"),
+        None,
+        || layout.current_branch(),
+        &CredentialOptions::default(),
+    );
     assert!(result.is_err());
 
     let err = result.unwrap_err();
@@ -94,7 +106,14 @@ fn test_classic_pull_no_remote_returns_error() {
     let (_temp, layout) = setup_classic_repo_with_git();
     let repo = layout.open_repo().unwrap();
 
-    let result = repo_git::pull(&repo, None, None, || layout.current_branch(), None);
+    let result = repo_git::pull(
+        &repo,
+        None,
+        None,
+        || layout.current_branch(),
+        None,
+        &CredentialOptions::default(),
+    );
     assert!(result.is_err());
 
     let err = result.unwrap_err();
@@ -106,6 +125,178 @@ fn test_classic_pull_no_remote_returns_error() {
     );
 }
 
+#[test]
+fn test_classic_push_and_pull_file_remote() {
+    let (temp, layout) = setup_classic_repo_with_git();
+    let root = temp.path();
+    let git = |dir: &std::path::Path, args: &[&str]| {
+        let output = Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+    };
+    let remotes = TempDir::new().unwrap();
+    let remote_dir = remotes.path().join("remote.git");
+    git(root, &["init", "--bare", remote_dir.to_str().unwrap()]);
+    let url = format!("file://{}", remote_dir.display());
+    git(root, &["remote", "add", "origin", &url]);
+
+    // Local transports never ask for credentials, so a missing key is harmless
+    let credentials = CredentialOptions::with_ssh_key(root.join("no-such-key"));
+    let repo = layout.open_repo().unwrap();
+    let branch = layout.current_branch().unwrap();
+    repo_git::push(&repo, None, None, || layout.current_branch(), &credentials).unwrap();
+
+    // Another clone pushes a commit for us to pull
+    let other = remotes.path().join("other");
+    git(root, &["clone", &url, other.to_str().unwrap()]);
+    git(&other, &["config", "user.email", "test@example.com"]);
+    git(&other, &["config", "user.name", "Test User"]);
+    git(&other, &["config", "commit.gpgSign", "false"]);
+    fs::write(other.join("remote.txt"), "from remote").unwrap();
+    git(&other, &["add", "remote.txt"]);
+    git(&other, &["commit", "-m", "Remote commit"]);
+    git(&other, &["push", "origin", &branch]);
+
+    repo_git::pull(
+        &repo,
+        None,
+        None,
+        || layout.current_branch(),
+        None,
+        &credentials,
+    )
+    .unwrap();
+    assert!(root.join("remote.txt").exists());
+}
+
+#[test]
+fn test_classic_fetch_and_prune_file_remote() {
+    let (temp, layout) = setup_classic_repo_with_git();
+    let root = temp.path();
+    let git = |dir: &std::path::Path, args: &[&str]| {
+        let output = Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+    };
+    let remotes = TempDir::new().unwrap();
+    let remote_dir = remotes.path().join("remote.git");
+    git(root, &["init", "--bare", remote_dir.to_str().unwrap()]);
+    let url = format!("file://{}", remote_dir.display());
+    git(root, &["remote", "add", "origin", &url]);
+    let credentials = CredentialOptions::default();
+    let repo = layout.open_repo().unwrap();
+    repo_git::push(&repo, None, None, || layout.current_branch(), &credentials).unwrap();
+
+    // Another clone pushes a new branch
+    let other = remotes.path().join("other");
+    git(root, &["clone", &url, other.to_str().unwrap()]);
+    git(&other, &["config", "user.email", "test@example.com"]);
+    git(&other, &["config", "user.name", "Test User"]);
+    git(&other, &["config", "commit.gpgSign", "false"]);
+    git(&other, &["checkout", "-b", "feature"]);
+    fs::write(other.join("feature.txt"), "feature").unwrap();
+    git(&other, &["add", "feature.txt"]);
+    git(&other, &["commit", "-m", "Feature commit"]);
+    git(&other, &["push", "origin", "feature"]);
+
+    let stats = repo_git::fetch(&repo, None, &[], &credentials).unwrap();
+    assert!(stats.received_objects > 0);
+    assert!(stats.updated_refs >= 1);
+    assert!(repo.find_reference("refs/remotes/origin/feature").is_ok());
+    // Fetching never touches the checkout
+    assert!(!root.join("feature.txt").exists());
+
+    // Nothing new to fetch the second time
+    let stats = repo_git::fetch(&repo, Some("origin"), &[], &credentials).unwrap();
+    assert_eq!(stats.updated_refs, 0);
+
+    git(&other, &["push", "origin", "--delete", "feature"]);
+    let pruned = repo_git::prune_remote(&repo, None, &credentials).unwrap();
+    assert_eq!(pruned, vec!["refs/remotes/origin/feature"]);
+    assert!(repo.find_reference("refs/remotes/origin/feature").is_err());
+    assert!(
+        repo_git::prune_remote(&repo, None, &credentials)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+/// A pull waiting on a remote that asks for credentials stops once its
+/// caller cancels, instead of working through every credential candidate.
+#[test]
+fn test_classic_pull_cancelled_while_authenticating() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    let (temp, layout) = setup_classic_repo_with_git();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/repo.git", listener.local_addr().unwrap());
+    Command::new("git")
+        .current_dir(temp.path())
+        .args(["remote", "add", "origin", &url])
+        .output()
+        .unwrap();
+
+    // The remote cancels the pull, as a timed-out caller would, and then
+    // demands credentials
+    let token = repo_fs::CancelToken::new();
+    let canceller = token.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap_or(0) > 0 && line != "\r\n" {
+                line.clear();
+            }
+            canceller.cancel();
+            let _ = stream.write_all(
+                b"HTTP/1.1 401 Unauthorized\r\n\
+                  WWW-Authenticate: Basic realm=\"repo\"\r\n\
+                  Content-Length: 0\r\n\r\n",
+            );
+        }
+    });
+
+    let _scope = token.enter();
+    let repo = layout.open_repo().unwrap();
+    let result = repo_git::pull(
+        &repo,
+        None,
+        None,
+        || layout.current_branch(),
+        None,
+        &CredentialOptions {
+            token: Some("secret".to_string()),
+            ..CredentialOptions::default()
+        },
+    );
+    assert!(
+        matches!(result, Err(repo_git::Error::Cancelled)),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn test_classic_fetch_named_remote_not_found() {
+    let (_temp, layout) = setup_classic_repo_with_git();
+    let repo = layout.open_repo().unwrap();
+
+    let err =
+        repo_git::fetch(&repo, Some("upstream"), &[], &CredentialOptions::default()).unwrap_err();
+    assert!(
+        matches!(err, repo_git::Error::RemoteNotFound { ref name } if name == "upstream"),
+        "Expected RemoteNotFound, got: {}",
+        err
+    );
+}
+
 #[test]
 fn test_classic_merge_branch_not_found() {
     let (_temp, layout) = setup_classic_repo_with_git();
@@ -116,6 +307,7 @@ fn test_classic_merge_branch_not_found() {
         "nonexistent-branch",
         || layout.current_branch(),
         None,
+        true,
     );
     assert!(result.is_err());
 
@@ -162,7 +354,7 @@ fn test_classic_merge_fast_forward() {
 
     // Merge feature branch (should fast-forward)
     let repo = layout.open_repo().unwrap();
-    let result = repo_git::merge(&repo, "feature", || layout.current_branch(), None);
+    let result = repo_git::merge(&repo, "feature", || layout.current_branch(), None, true);
     assert!(result.is_ok(), "Merge failed: {:?}", result);
 
     // Verify the file exists after merge
@@ -183,10 +375,65 @@ fn test_classic_merge_already_up_to_date() {
 
     // Merge should succeed (already up to date)
     let repo = layout.open_repo().unwrap();
-    let result = repo_git::merge(&repo, "feature", || layout.current_branch(), None);
+    let result = repo_git::merge(&repo, "feature", || layout.current_branch(), None, true);
     assert!(result.is_ok(), "Merge failed: {:?}", result);
 }
 
+/// Commit conflicting edits to README.md on `feature` and the main branch.
+fn diverge_with_conflict(root: &std::path::Path, main_branch: &str) {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .current_dir(root)
+            .args(args)
+            .output()
+            .unwrap();
+    };
+    git(&["checkout", "-b", "feature"]);
+    fs::write(root.join("README.md"), "# Feature").unwrap();
+    fs::write(root.join("feature.txt"), "Feature content").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-m", "Feature commit"]);
+    git(&["checkout", main_branch]);
+    fs::write(root.join("README.md"), "# Main").unwrap();
+    git(&["commit", "-am", "Main commit"]);
+}
+
+#[test]
+fn test_classic_merge_conflict() {
+    let (temp, layout) = setup_classic_repo_with_git();
+    let root = temp.path();
+    let main_branch = layout.current_branch().unwrap();
+    diverge_with_conflict(root, &main_branch);
+    let repo = layout.open_repo().unwrap();
+    let head = repo.head().unwrap().target().unwrap();
+
+    // Aborting restores the pre-merge state
+    let err =
+        repo_git::merge(&repo, "feature", || layout.current_branch(), None, true).unwrap_err();
+    match err {
+        repo_git::Error::MergeConflict { files } => assert_eq!(files, vec!["README.md"]),
+        other => panic!("Expected MergeConflict, got: {other}"),
+    }
+    assert_eq!(repo.head().unwrap().target().unwrap(), head);
+    assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    assert_eq!(
+        fs::read_to_string(root.join("README.md")).unwrap(),
+        "# Main"
+    );
+    assert!(!root.join("feature.txt").exists());
+    assert!(!repo.index().unwrap().has_conflicts());
+
+    // Otherwise the conflict is left for the caller to resolve
+    let err =
+        repo_git::merge(&repo, "feature", || layout.current_branch(), None, false).unwrap_err();
+    assert!(matches!(err, repo_git::Error::MergeConflict { ref files } if files == &["README.md"]));
+    assert_eq!(repo.state(), git2::RepositoryState::Merge);
+    assert!(repo.index().unwrap().has_conflicts());
+    let readme = fs::read_to_string(root.join("README.md")).unwrap();
+    assert!(readme.contains("<<<<<<<") && readme.contains(">>>>>>>"));
+    assert!(root.join("feature.txt").exists());
+}
+
 // ============================================================================
 // Container Layout Tests
 // ============================================================================
@@ -261,7 +508,13 @@ fn test_container_push_no_remote_returns_error() {
     let (_temp, layout) = setup_container_repo_with_git();
     let repo = layout.open_repo().unwrap();
 
-    let result = repo_git::push(repo, None, None, || layout.current_branch());
+    let result = repo_git::push(
+        repo,
+        None,
+        None,
+        || layout.current_branch(),
+        &CredentialOptions::default(),
+    );
     assert!(result.is_err());
 
     let err = result.unwrap_err();
@@ -278,7 +531,14 @@ fn test_container_pull_no_remote_returns_error() {
     let (_temp, layout) = setup_container_repo_with_git();
     let repo = layout.open_repo().unwrap();
 
-    let result = repo_git::pull(repo, None, None, || layout.current_branch(), None);
+    let result = repo_git::pull(
+        repo,
+        None,
+        None,
+        || layout.current_branch(),
+        None,
+        &CredentialOptions::default(),
+    );
     assert!(result.is_err());
 
     let err = result.unwrap_err();
@@ -295,7 +555,13 @@ fn test_container_merge_branch_not_found() {
     let (_temp, layout) = setup_container_repo_with_git();
     let repo = layout.open_repo().unwrap();
 
-    let result = repo_git::merge(repo, "nonexistent-branch", || layout.current_branch(), None);
+    let result = repo_git::merge(
+        repo,
+        "nonexistent-branch",
+        || layout.current_branch(),
+        None,
+        true,
+    );
     assert!(result.is_err());
 
     let err = result.unwrap_err();
@@ -339,6 +605,7 @@ fn test_container_merge_fast_forward() {
         "feature",
         || layout.current_branch(),
         Some(&main_repo),
+        true,
     );
     assert!(result.is_ok(), "Merge failed: {:?}", result);
 
@@ -405,7 +672,13 @@ fn test_in_repo_push_no_remote_returns_error() {
     let (_temp, layout) = setup_in_repo_worktrees_with_git();
     let repo = layout.open_repo().unwrap();
 
-    let result = repo_git::push(repo, None, None, || layout.current_branch());
+    let result = repo_git::push(
+        repo,
+        None,
+        None,
+        || layout.current_branch(),
+        &CredentialOptions::default(),
+    );
     assert!(result.is_err());
 
     let err = result.unwrap_err();
@@ -422,7 +695,14 @@ fn test_in_repo_pull_no_remote_returns_error() {
     let (_temp, layout) = setup_in_repo_worktrees_with_git();
     let repo = layout.open_repo().unwrap();
 
-    let result = repo_git::pull(repo, None, None, || layout.current_branch(), None);
+    let result = repo_git::pull(
+        repo,
+        None,
+        None,
+        || layout.current_branch(),
+        None,
+        &CredentialOptions::default(),
+    );
     assert!(result.is_err());
 
     let err = result.unwrap_err();
@@ -439,7 +719,13 @@ fn test_in_repo_merge_branch_not_found() {
     let (_temp, layout) = setup_in_repo_worktrees_with_git();
     let repo = layout.open_repo().unwrap();
 
-    let result = repo_git::merge(repo, "nonexistent-branch", || layout.current_branch(), None);
+    let result = repo_git::merge(
+        repo,
+        "nonexistent-branch",
+        || layout.current_branch(),
+        None,
+        true,
+    );
     assert!(result.is_err());
 
     let err = result.unwrap_err();
@@ -482,7 +768,7 @@ fn test_in_repo_merge_fast_forward() {
 
     // Merge feature branch
     let repo = layout.open_repo().unwrap();
-    let result = repo_git::merge(repo, "feature", || layout.current_branch(), None);
+    let result = repo_git::merge(repo, "feature", || layout.current_branch(), None, true);
     assert!(result.is_ok(), "Merge failed: {:?}", result);
 
     // Verify the file exists in root after merge
@@ -113,6 +113,99 @@ fn test_in_repo_slug_naming() {
     layout.remove_feature("feat/user-auth").unwrap();
 }
 
+#[test]
+fn test_in_repo_nested_branch_is_listed_under_worktrees() {
+    let (temp, layout) = setup_in_repo_worktrees();
+
+    let path = layout.create_feature("feat/api/v2", None).unwrap();
+    assert_eq!(
+        path,
+        NormalizedPath::new(temp.path().join(".worktrees/feat-api-v2"))
+    );
+    assert_eq!(path, layout.feature_worktree("feat/api/v2"));
+
+    let info = layout
+        .list_worktrees()
+        .unwrap()
+        .into_iter()
+        .find(|wt| wt.name == "feat-api-v2")
+        .unwrap();
+    assert_eq!(info.path, path);
+    assert!(!info.is_main);
+
+    // Feature worktrees share the main repository's .git
+    let worktree_git = fs::read_to_string(path.join(".git").to_native()).unwrap();
+    assert!(worktree_git.starts_with("gitdir:"));
+    assert!(worktree_git.contains(".git/worktrees/"));
+
+    layout.remove_feature("feat/api/v2").unwrap();
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_in_repo_worktrees_dir_is_excluded_from_status() {
+    let (temp, layout) = setup_in_repo_worktrees();
+    layout.create_feature("excluded", None).unwrap();
+    layout.create_feature("excluded-too", None).unwrap();
+
+    let exclude = fs::read_to_string(temp.path().join(".git/info/exclude")).unwrap();
+    assert_eq!(exclude.matches("/.worktrees/").count(), 1);
+
+    let status = Command::new("git")
+        .current_dir(temp.path())
+        .args(["status", "--porcelain"])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&status.stdout).is_empty());
+}
+
+#[test]
+fn test_in_repo_create_duplicate_feature_returns_error() {
+    let (_temp, layout) = setup_in_repo_worktrees();
+
+    let path = layout.create_feature("dup-feature", None).unwrap();
+    assert!(path.exists());
+
+    let result = layout.create_feature("dup-feature", None);
+    assert!(matches!(
+        result,
+        Err(repo_git::Error::WorktreeExists { .. })
+    ));
+
+    layout.remove_feature("dup-feature").unwrap();
+}
+
+#[test]
+fn test_in_repo_remove_nonexistent_feature_returns_error() {
+    let (_temp, layout) = setup_in_repo_worktrees();
+
+    let result = layout.remove_feature("nonexistent-feature");
+    assert!(
+        result.is_err(),
+        "Removing a non-existent feature should return an error"
+    );
+}
+
+#[test]
+fn test_in_repo_template_naming_with_date() {
+    let (temp, _) = setup_in_repo_worktrees();
+    let root = NormalizedPath::new(temp.path());
+    let naming = NamingStrategy::template("{slug}-{date}").unwrap();
+    let layout = InRepoWorktreesLayout::new(root.clone(), naming).unwrap();
+
+    let path = layout.create_feature("feat/auth", None).unwrap();
+    let dir = path.as_str().rsplit('/').next().unwrap().to_string();
+    assert!(dir.starts_with("feat-auth-20"), "got {}", dir);
+    assert!(path.exists());
+    assert_eq!(layout.feature_worktree("feat/auth"), path);
+
+    // The worktree is found by its slug, whatever the date it was created on
+    let listed = layout.list_worktrees().unwrap();
+    assert!(listed.iter().any(|wt| wt.name == "feat-auth"));
+    layout.remove_feature("feat/auth").unwrap();
+    assert!(!path.exists());
+}
+
 #[test]
 fn test_in_repo_current_branch() {
     let (_temp, layout) = setup_in_repo_worktrees();
@@ -120,3 +213,51 @@ fn test_in_repo_current_branch() {
     // Git init creates main or master depending on config
     assert!(branch == "main" || branch == "master");
 }
+
+#[test]
+fn test_in_repo_locked_worktree_is_listed_and_never_pruned() {
+    let (_temp, layout) = setup_in_repo_worktrees();
+    let path = layout.create_feature("locked-feature", None).unwrap();
+
+    layout
+        .lock_worktree("locked-feature", Some("on a removable drive"))
+        .unwrap();
+
+    let info = layout
+        .list_worktrees()
+        .unwrap()
+        .into_iter()
+        .find(|wt| wt.name == "locked-feature")
+        .unwrap();
+    assert!(info.locked);
+    assert_eq!(info.lock_reason.as_deref(), Some("on a removable drive"));
+    assert!(!info.prunable);
+
+    // A locked worktree refuses removal
+    assert!(matches!(
+        layout.remove_feature("locked-feature"),
+        Err(repo_git::Error::WorktreeLocked { .. })
+    ));
+
+    // Delete the directory behind git's back: prune must still skip it
+    fs::remove_dir_all(path.to_native()).unwrap();
+    assert!(layout.prune_worktrees().unwrap().is_empty());
+    assert!(
+        layout
+            .list_worktrees()
+            .unwrap()
+            .iter()
+            .any(|wt| wt.name == "locked-feature")
+    );
+
+    // Once unlocked, the stale worktree is pruned
+    layout.unlock_worktree("locked-feature").unwrap();
+    assert_eq!(layout.prune_worktrees().unwrap(), vec!["locked-feature"]);
+    assert!(
+        !layout
+            .list_worktrees()
+            .unwrap()
+            .iter()
+            .any(|wt| wt.name == "locked-feature")
+    );
+}
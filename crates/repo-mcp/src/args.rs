@@ -0,0 +1,234 @@
+//! Tool argument types
+//!
+//! Each tool that takes arguments has an `*Args` struct here. The structs
+//! are deserialized by the handlers and also generate the `input_schema`
+//! published in each [`ToolDefinition`](crate::ToolDefinition), so the
+//! advertised schema and the accepted arguments cannot drift apart.
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{Error, Result};
+
+/// Deserialize tool arguments, treating missing arguments as an empty object.
+pub(crate) fn parse<T: DeserializeOwned>(arguments: Value) -> Result<T> {
+    let arguments = if arguments.is_null() {
+        Value::Object(Default::default())
+    } else {
+        arguments
+    };
+    serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))
+}
+
+/// Arguments for repo_init
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct RepoInitArgs {
+    /// Project name
+    pub name: String,
+    /// Repository mode
+    #[serde(default)]
+    #[schemars(with = "String", extend("enum" = ["standard", "worktrees"]))]
+    pub mode: Option<String>,
+    /// Tools to enable
+    #[serde(default)]
+    #[schemars(with = "Vec<String>")]
+    pub tools: Option<Vec<String>>,
+    /// Extensions to enable (by name or source URL)
+    #[serde(default)]
+    #[schemars(with = "Vec<String>")]
+    pub extensions: Option<Vec<String>>,
+}
+
+/// Arguments for repo_sync and repo_fix
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct SyncArgs {
+    /// Preview changes without applying
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Arguments for branch_create
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct BranchCreateArgs {
+    /// Branch name
+    pub name: String,
+    /// Base branch (defaults to main)
+    #[serde(default)]
+    #[schemars(with = "String")]
+    pub base: Option<String>,
+}
+
+/// Arguments for branch_delete
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct BranchDeleteArgs {
+    /// Branch name to delete
+    pub name: String,
+}
+
+/// Arguments for branch_checkout
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct BranchCheckoutArgs {
+    /// Branch name to switch to
+    pub name: String,
+}
+
+/// Arguments for branch_rename
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct BranchRenameArgs {
+    /// Current branch name
+    pub old: String,
+    /// New branch name
+    pub new: String,
+}
+
+/// Arguments for branch_prune
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct BranchPruneArgs {
+    /// Report what would be removed without removing it
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Also remove worktrees with uncommitted or untracked changes
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Arguments for git_push
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct GitPushArgs {
+    /// Remote name (defaults to origin)
+    #[serde(default)]
+    #[schemars(with = "String")]
+    pub remote: Option<String>,
+    /// Branch to push (defaults to current)
+    #[serde(default)]
+    #[schemars(with = "String")]
+    pub branch: Option<String>,
+}
+
+/// Arguments for git_pull
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct GitPullArgs {
+    /// Remote name (defaults to origin)
+    #[serde(default)]
+    #[schemars(with = "String")]
+    pub remote: Option<String>,
+    /// Branch to pull (defaults to current)
+    #[serde(default)]
+    #[schemars(with = "String")]
+    pub branch: Option<String>,
+}
+
+/// Arguments for git_fetch
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct GitFetchArgs {
+    /// Remote name (defaults to origin)
+    #[serde(default)]
+    #[schemars(with = "String")]
+    pub remote: Option<String>,
+    /// Refspecs to fetch (defaults to the remote's configured refspecs)
+    #[serde(default)]
+    #[schemars(with = "Vec<String>")]
+    pub refspecs: Option<Vec<String>>,
+    /// Also delete remote-tracking branches gone from the remote (defaults
+    /// to false)
+    #[serde(default)]
+    #[schemars(with = "bool")]
+    pub prune: Option<bool>,
+}
+
+/// Arguments for git_merge
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct GitMergeArgs {
+    /// Branch to merge from
+    pub source: String,
+    /// Undo the merge if it conflicts (defaults to true); otherwise the
+    /// conflict markers are left in the working tree
+    #[serde(default)]
+    #[schemars(with = "bool")]
+    pub abort_on_conflict: Option<bool>,
+}
+
+/// Arguments for tool_add
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ToolAddArgs {
+    /// Tool name (e.g., vscode, cursor, claude)
+    pub name: String,
+    /// Enable the tool even if it is not built in or defined in
+    /// .repository/tools/
+    #[serde(default)]
+    pub allow_unknown: bool,
+}
+
+/// Arguments for tool_remove
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ToolRemoveArgs {
+    /// Tool name to remove
+    pub name: String,
+}
+
+/// Arguments for rule_add
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct RuleAddArgs {
+    /// Rule identifier
+    pub id: String,
+    /// Rule content/instructions
+    pub content: String,
+    /// Tags for categorization
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Arguments for rule_remove
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct RuleRemoveArgs {
+    /// Rule ID to remove
+    pub id: String,
+}
+
+/// Arguments for preset_add
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct PresetAddArgs {
+    /// Preset name (e.g., env:python, env:node, env:rust)
+    pub name: String,
+}
+
+/// Arguments for preset_remove
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct PresetRemoveArgs {
+    /// Preset name to remove
+    pub name: String,
+}
+
+/// Arguments for extension_install
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ExtensionInstallArgs {
+    /// Git URL of the extension repository
+    pub source: String,
+    /// Only report the presets the extension requires instead of adding
+    /// them to config.toml and applying them
+    #[serde(default)]
+    pub no_deps: bool,
+}
+
+/// Arguments for extension_add
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ExtensionAddArgs {
+    /// Name of the known extension to add
+    pub name: String,
+}
+
+/// Arguments for extension_init
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ExtensionInitArgs {
+    /// Name for the new extension
+    pub name: String,
+}
+
+/// Arguments for extension_remove
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ExtensionRemoveArgs {
+    /// Name of the extension to remove
+    pub name: String,
+}
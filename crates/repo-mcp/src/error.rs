@@ -63,4 +63,15 @@ pub enum Error {
     /// Unknown resource requested
     #[error("unknown resource: {0}")]
     UnknownResource(String),
+
+    /// Unknown prompt requested
+    #[error("unknown prompt: {0}")]
+    UnknownPrompt(String),
+
+    /// Tool call exceeded its configured timeout
+    #[error("tool '{tool}' timed out after {elapsed:.1?}")]
+    Timeout {
+        tool: String,
+        elapsed: std::time::Duration,
+    },
 }
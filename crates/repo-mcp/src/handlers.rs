@@ -7,24 +7,55 @@
 //! tokio runtime, even though the current implementations perform synchronous I/O.
 //! This allows for future migration to async file operations without API changes.
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
 use git2::Repository;
-use repo_core::{
-    CheckStatus, Manifest, Mode, ModeBackend, StandardBackend, SyncEngine, SyncOptions,
-    WorktreeBackend,
-};
+use repo_core::context::detect;
+use repo_core::ledger::RulesArgs;
+use repo_core::{BranchPruneOptions, CheckStatus, Mode, SyncOptions};
+use repo_extensions::is_git_url;
 use repo_fs::NormalizedPath;
-use repo_git::{ClassicLayout, ContainerLayout, LayoutProvider};
-use repo_meta::Registry;
-use serde::Deserialize;
+use repo_git::CredentialOptions;
+use repo_meta::{DefinitionLoader, Registry};
+use repo_tools::ToolDispatcher;
 use serde_json::{Value, json};
 
+use crate::args::{
+    self, BranchCheckoutArgs, BranchCreateArgs, BranchDeleteArgs, BranchPruneArgs,
+    BranchRenameArgs, ExtensionAddArgs, ExtensionInitArgs, ExtensionInstallArgs,
+    ExtensionRemoveArgs, GitFetchArgs, GitMergeArgs, GitPullArgs, GitPushArgs, PresetAddArgs,
+    PresetRemoveArgs, RepoInitArgs, RuleAddArgs, RuleRemoveArgs, SyncArgs, ToolAddArgs,
+    ToolRemoveArgs,
+};
 use crate::{Error, Result};
 
+/// Tool calls that write to `.repository/`
+const CONFIG_WRITING_TOOLS: &[&str] = &[
+    "repo_init",
+    "tool_add",
+    "tool_remove",
+    "rule_add",
+    "rule_remove",
+    "preset_add",
+    "preset_remove",
+    "extension_install",
+];
+
 /// Handle a tool call by dispatching to the appropriate handler
+///
+/// Cached definitions are invalidated after any call that writes config,
+/// so later calls never see stale definitions.
 pub async fn handle_tool_call(root: &Path, tool_name: &str, arguments: Value) -> Result<Value> {
+    let result = dispatch_tool_call(root, tool_name, arguments).await;
+    if CONFIG_WRITING_TOOLS.contains(&tool_name) {
+        DefinitionLoader::invalidate();
+    }
+    result
+}
+
+async fn dispatch_tool_call(root: &Path, tool_name: &str, arguments: Value) -> Result<Value> {
     match tool_name {
         // Repository Lifecycle
         "repo_check" => handle_repo_check(root).await,
@@ -36,16 +67,20 @@ pub async fn handle_tool_call(root: &Path, tool_name: &str, arguments: Value) ->
         "branch_list" => handle_branch_list(root).await,
         "branch_create" => handle_branch_create(root, arguments).await,
         "branch_delete" => handle_branch_delete(root, arguments).await,
+        "branch_checkout" => handle_branch_checkout(root, arguments).await,
+        "branch_rename" => handle_branch_rename(root, arguments).await,
+        "branch_prune" => handle_branch_prune(root, arguments).await,
 
         // Git Primitives
         "git_push" => handle_git_push(root, arguments).await,
         "git_pull" => handle_git_pull(root, arguments).await,
+        "git_fetch" => handle_git_fetch(root, arguments).await,
         "git_merge" => handle_git_merge(root, arguments).await,
 
-
         // Configuration Management
         "tool_add" => handle_tool_add(root, arguments).await,
         "tool_remove" => handle_tool_remove(root, arguments).await,
+        "rules_list" => handle_rules_list(root).await,
         "rule_add" => handle_rule_add(root, arguments).await,
         "rule_remove" => handle_rule_remove(root, arguments).await,
 
@@ -55,11 +90,12 @@ pub async fn handle_tool_call(root: &Path, tool_name: &str, arguments: Value) ->
         "preset_remove" => handle_preset_remove(root, arguments).await,
 
         // Extension Management
-        "extension_install" => handle_extension_install(arguments).await,
+        "extension_install" => handle_extension_install(root, arguments).await,
         "extension_add" => handle_extension_add(arguments).await,
         "extension_init" => handle_extension_init(arguments).await,
         "extension_remove" => handle_extension_remove(arguments).await,
         "extension_list" => handle_extension_list().await,
+        "extension_verify" => handle_extension_verify(root).await,
 
         _ => Err(Error::UnknownTool(tool_name.to_string())),
     }
@@ -71,15 +107,15 @@ pub async fn handle_tool_call(root: &Path, tool_name: &str, arguments: Value) ->
 
 /// Handle repo_check - Check configuration validity and consistency
 async fn handle_repo_check(root: &Path) -> Result<Value> {
-    let ctx = RepoContext::new(root)?;
-    let engine = ctx.sync_engine()?;
-    let report = engine.check().map_err(Error::Core)?;
+    let repo = open_repository(root)?;
+    let report = repo.check().map_err(Error::Core)?;
 
     Ok(json!({
         "status": format!("{:?}", report.status),
         "healthy": report.status == CheckStatus::Healthy,
         "drifted": report.drifted.len(),
         "missing": report.missing.len(),
+        "corrupt": report.corrupt.len(),
         "details": {
             "drifted": report.drifted.iter().map(|d| json!({
                 "intent_id": d.intent_id,
@@ -93,6 +129,12 @@ async fn handle_repo_check(root: &Path) -> Result<Value> {
                 "file": m.file,
                 "description": m.description,
             })).collect::<Vec<_>>(),
+            "corrupt": report.corrupt.iter().map(|c| json!({
+                "intent_id": c.intent_id,
+                "tool": c.tool,
+                "file": c.file,
+                "description": c.description,
+            })).collect::<Vec<_>>(),
             "messages": report.messages,
         }
     }))
@@ -100,37 +142,26 @@ async fn handle_repo_check(root: &Path) -> Result<Value> {
 
 /// Handle repo_sync - Regenerate tool configurations from rules
 async fn handle_repo_sync(root: &Path, arguments: Value) -> Result<Value> {
-    let ctx = RepoContext::new(root)?;
-    let engine = ctx.sync_engine()?;
-
-    let dry_run = arguments
-        .get("dry_run")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
+    let SyncArgs { dry_run } = args::parse(arguments)?;
 
-    let options = SyncOptions { dry_run };
-    let report = engine.sync_with_options(options).map_err(Error::Core)?;
+    let repo = open_repository(root)?;
+    let report = repo.sync(SyncOptions { dry_run }).map_err(Error::Core)?;
 
     Ok(json!({
         "success": report.success,
         "dry_run": dry_run,
         "actions": report.actions,
         "errors": report.errors,
+        "warnings": report.warnings,
     }))
 }
 
 /// Handle repo_fix - Repair configuration inconsistencies
 async fn handle_repo_fix(root: &Path, arguments: Value) -> Result<Value> {
-    let ctx = RepoContext::new(root)?;
-    let engine = ctx.sync_engine()?;
+    let SyncArgs { dry_run } = args::parse(arguments)?;
 
-    let dry_run = arguments
-        .get("dry_run")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false);
-
-    let options = SyncOptions { dry_run };
-    let report = engine.fix_with_options(options).map_err(Error::Core)?;
+    let repo = open_repository(root)?;
+    let report = repo.fix(SyncOptions { dry_run }).map_err(Error::Core)?;
 
     Ok(json!({
         "success": report.success,
@@ -140,22 +171,9 @@ async fn handle_repo_fix(root: &Path, arguments: Value) -> Result<Value> {
     }))
 }
 
-/// Arguments for repo_init
-#[derive(Debug, Deserialize)]
-struct RepoInitArgs {
-    name: String,
-    #[serde(default)]
-    mode: Option<String>,
-    #[serde(default)]
-    tools: Option<Vec<String>>,
-    #[serde(default)]
-    extensions: Option<Vec<String>>,
-}
-
 /// Handle repo_init - Initialize a new repository configuration
 async fn handle_repo_init(root: &Path, arguments: Value) -> Result<Value> {
-    let args: RepoInitArgs =
-        serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))?;
+    let args: RepoInitArgs = args::parse(arguments)?;
 
     let normalized_root = NormalizedPath::new(root);
 
@@ -262,9 +280,8 @@ mode = "{}"
 
 /// Handle branch_list - List active branches
 async fn handle_branch_list(root: &Path) -> Result<Value> {
-    let ctx = RepoContext::new(root)?;
-    let backend = ctx.backend()?;
-    let branches = backend.list_branches().map_err(Error::Core)?;
+    let repo = open_repository(root)?;
+    let branches = repo.list_branches().map_err(Error::Core)?;
 
     let branch_data: Vec<Value> = branches
         .iter()
@@ -274,6 +291,9 @@ async fn handle_branch_list(root: &Path) -> Result<Value> {
                 "path": b.path.as_ref().map(|p| p.as_str().to_string()),
                 "is_current": b.is_current,
                 "is_main": b.is_main,
+                "ahead": b.ahead,
+                "behind": b.behind,
+                "dirty": b.dirty,
             })
         })
         .collect();
@@ -284,14 +304,6 @@ async fn handle_branch_list(root: &Path) -> Result<Value> {
     }))
 }
 
-/// Arguments for branch_create
-#[derive(Debug, Deserialize)]
-struct BranchCreateArgs {
-    name: String,
-    #[serde(default)]
-    base: Option<String>,
-}
-
 /// Validate a branch name for safety.
 ///
 /// Rejects names that could be interpreted as git flags, contain path traversal
@@ -342,8 +354,7 @@ fn validate_branch_name(name: &str) -> Result<()> {
 
 /// Handle branch_create - Create a new branch (with worktree in worktrees mode)
 async fn handle_branch_create(root: &Path, arguments: Value) -> Result<Value> {
-    let args: BranchCreateArgs =
-        serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))?;
+    let args: BranchCreateArgs = args::parse(arguments)?;
 
     // Validate branch names before passing to git
     validate_branch_name(&args.name)?;
@@ -351,22 +362,12 @@ async fn handle_branch_create(root: &Path, arguments: Value) -> Result<Value> {
         validate_branch_name(base)?;
     }
 
-    let ctx = RepoContext::new(root)?;
-    let backend = ctx.backend()?;
-
-    backend
-        .create_branch(&args.name, args.base.as_deref())
+    let repo = open_repository(root)?;
+    repo.create_branch(&args.name, args.base.as_deref())
         .map_err(Error::Core)?;
 
-    let path = if ctx.mode == Mode::Worktrees {
-        // In worktree mode, return the worktree path
-        // The worktree is created in the container, which is the parent of root
-        // if root is a worktree, or root itself if it's the container
-        let container = find_container(&ctx.root)?;
-        Some(container.join(&args.name))
-    } else {
-        None
-    };
+    // In worktree mode, the worktree is created in the container
+    let path = repo.container().map(|container| container.join(&args.name));
 
     Ok(json!({
         "success": true,
@@ -377,24 +378,15 @@ async fn handle_branch_create(root: &Path, arguments: Value) -> Result<Value> {
     }))
 }
 
-/// Arguments for branch_delete
-#[derive(Debug, Deserialize)]
-struct BranchDeleteArgs {
-    name: String,
-}
-
 /// Handle branch_delete - Remove a branch and its worktree
 async fn handle_branch_delete(root: &Path, arguments: Value) -> Result<Value> {
-    let args: BranchDeleteArgs =
-        serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))?;
+    let args: BranchDeleteArgs = args::parse(arguments)?;
 
     // Validate branch name before passing to git
     validate_branch_name(&args.name)?;
 
-    let ctx = RepoContext::new(root)?;
-    let backend = ctx.backend()?;
-
-    backend.delete_branch(&args.name).map_err(Error::Core)?;
+    let repo = open_repository(root)?;
+    repo.delete_branch(&args.name).map_err(Error::Core)?;
 
     Ok(json!({
         "success": true,
@@ -403,28 +395,95 @@ async fn handle_branch_delete(root: &Path, arguments: Value) -> Result<Value> {
     }))
 }
 
+/// Handle branch_checkout - Switch to a branch
+async fn handle_branch_checkout(root: &Path, arguments: Value) -> Result<Value> {
+    let args: BranchCheckoutArgs = args::parse(arguments)?;
+
+    // Validate branch name before passing to git
+    validate_branch_name(&args.name)?;
+
+    let repo = open_repository(root)?;
+    let path = repo.switch_branch(&args.name).map_err(Error::Core)?;
+
+    Ok(json!({
+        "success": true,
+        "branch": args.name,
+        "path": path.as_str(),
+        "message": format!("Switched to branch '{}'", args.name),
+    }))
+}
+
+/// Handle branch_rename - Rename a branch (and move its worktree)
+async fn handle_branch_rename(root: &Path, arguments: Value) -> Result<Value> {
+    let args: BranchRenameArgs = args::parse(arguments)?;
+
+    // Validate branch names before passing to git
+    validate_branch_name(&args.old)?;
+    validate_branch_name(&args.new)?;
+
+    let repo = open_repository(root)?;
+    repo.rename_branch(&args.old, &args.new)
+        .map_err(Error::Core)?;
+
+    Ok(json!({
+        "success": true,
+        "old": args.old,
+        "new": args.new,
+        "message": format!("Renamed branch '{}' to '{}'", args.old, args.new),
+    }))
+}
+
+/// Handle branch_prune - Remove branches merged into the main branch
+async fn handle_branch_prune(root: &Path, arguments: Value) -> Result<Value> {
+    let args: BranchPruneArgs = args::parse(arguments)?;
+
+    let repo = open_repository(root)?;
+    let merged = repo
+        .prune_branches(BranchPruneOptions {
+            dry_run: args.dry_run,
+            force: args.force,
+        })
+        .map_err(Error::Core)?;
+
+    let branch_data: Vec<Value> = merged
+        .iter()
+        .map(|b| {
+            json!({
+                "name": b.name,
+                "path": b.path.as_ref().map(|p| p.as_str().to_string()),
+                "pruned": b.is_pruned(),
+                "skipped": b.skipped,
+            })
+        })
+        .collect();
+    let pruned = merged.iter().filter(|b| b.is_pruned()).count();
+
+    Ok(json!({
+        "success": true,
+        "dry_run": args.dry_run,
+        "branches": branch_data,
+        "pruned": pruned,
+        "kept": merged.len() - pruned,
+        "message": format!(
+            "{} {} merged branch(es), kept {}",
+            if args.dry_run { "Would remove" } else { "Removed" },
+            pruned,
+            merged.len() - pruned
+        ),
+    }))
+}
+
 // ============================================================================
 // Git Primitive Handlers
 // ============================================================================
 
-/// Arguments for git_push
-#[derive(Debug, Deserialize)]
-struct GitPushArgs {
-    #[serde(default)]
-    remote: Option<String>,
-    #[serde(default)]
-    branch: Option<String>,
-}
-
 /// Handle git_push - Push current branch to remote
 async fn handle_git_push(root: &Path, arguments: Value) -> Result<Value> {
-    let args: GitPushArgs =
-        serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))?;
+    let args: GitPushArgs = args::parse(arguments)?;
 
-    let ctx = RepoContext::new(root)?;
-    let provider = create_git_provider(&ctx.root, ctx.mode)?;
-    let repo = Repository::open(provider.main_worktree().to_native())
-        .map_err(repo_git::Error::from)?;
+    let provider = open_repository(root)?.git_layout().map_err(Error::Core)?;
+    let repo =
+        Repository::open(provider.main_worktree().to_native()).map_err(repo_git::Error::from)?;
 
     let remote_name = args.remote.as_deref().unwrap_or("origin");
     let branch_ref = args.branch.as_deref();
@@ -438,7 +497,13 @@ async fn handle_git_push(root: &Path, arguments: Value) -> Result<Value> {
     };
 
     let current_branch_fn = || provider.current_branch();
-    repo_git::push(&repo, Some(remote_name), branch_ref, current_branch_fn)?;
+    repo_git::push(
+        &repo,
+        Some(remote_name),
+        branch_ref,
+        current_branch_fn,
+        &CredentialOptions::default(),
+    )?;
 
     Ok(json!({
         "success": true,
@@ -448,24 +513,13 @@ async fn handle_git_push(root: &Path, arguments: Value) -> Result<Value> {
     }))
 }
 
-/// Arguments for git_pull
-#[derive(Debug, Deserialize)]
-struct GitPullArgs {
-    #[serde(default)]
-    remote: Option<String>,
-    #[serde(default)]
-    branch: Option<String>,
-}
-
 /// Handle git_pull - Pull changes from remote
 async fn handle_git_pull(root: &Path, arguments: Value) -> Result<Value> {
-    let args: GitPullArgs =
-        serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))?;
+    let args: GitPullArgs = args::parse(arguments)?;
 
-    let ctx = RepoContext::new(root)?;
-    let provider = create_git_provider(&ctx.root, ctx.mode)?;
-    let repo = Repository::open(provider.main_worktree().to_native())
-        .map_err(repo_git::Error::from)?;
+    let provider = open_repository(root)?.git_layout().map_err(Error::Core)?;
+    let repo =
+        Repository::open(provider.main_worktree().to_native()).map_err(repo_git::Error::from)?;
 
     let remote_name = args.remote.as_deref().unwrap_or("origin");
     let branch_ref = args.branch.as_deref();
@@ -479,7 +533,14 @@ async fn handle_git_pull(root: &Path, arguments: Value) -> Result<Value> {
     };
 
     let current_branch_fn = || provider.current_branch();
-    repo_git::pull(&repo, Some(remote_name), branch_ref, current_branch_fn, None)?;
+    repo_git::pull(
+        &repo,
+        Some(remote_name),
+        branch_ref,
+        current_branch_fn,
+        None,
+        &CredentialOptions::default(),
+    )?;
 
     Ok(json!({
         "success": true,
@@ -489,24 +550,74 @@ async fn handle_git_pull(root: &Path, arguments: Value) -> Result<Value> {
     }))
 }
 
-/// Arguments for git_merge
-#[derive(Debug, Deserialize)]
-struct GitMergeArgs {
-    source: String,
+/// Handle git_fetch - Fetch from remote without touching the checkout
+async fn handle_git_fetch(root: &Path, arguments: Value) -> Result<Value> {
+    let args: GitFetchArgs = args::parse(arguments)?;
+
+    let provider = open_repository(root)?.git_layout().map_err(Error::Core)?;
+    let repo =
+        Repository::open(provider.main_worktree().to_native()).map_err(repo_git::Error::from)?;
+
+    let remote_name = args.remote.as_deref().unwrap_or("origin");
+    let refspecs = args.refspecs.unwrap_or_default();
+    let refspecs: Vec<&str> = refspecs.iter().map(String::as_str).collect();
+    let credentials = CredentialOptions::default();
+
+    let stats = repo_git::fetch(&repo, Some(remote_name), &refspecs, &credentials)?;
+    let pruned = if args.prune.unwrap_or(false) {
+        repo_git::prune_remote(&repo, Some(remote_name), &credentials)?
+    } else {
+        Vec::new()
+    };
+
+    Ok(json!({
+        "success": true,
+        "remote": remote_name,
+        "received_objects": stats.received_objects,
+        "received_bytes": stats.received_bytes,
+        "updated_refs": stats.updated_refs,
+        "pruned": pruned,
+        "message": format!("Fetched from '{}'", remote_name),
+    }))
 }
 
 /// Handle git_merge - Merge a branch into the current branch
+///
+/// A conflicting merge is reported as an unsuccessful result listing the
+/// conflicted files, rather than as an error.
 async fn handle_git_merge(root: &Path, arguments: Value) -> Result<Value> {
-    let args: GitMergeArgs =
-        serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))?;
+    let args: GitMergeArgs = args::parse(arguments)?;
+    let abort = args.abort_on_conflict.unwrap_or(true);
 
-    let ctx = RepoContext::new(root)?;
-    let provider = create_git_provider(&ctx.root, ctx.mode)?;
-    let repo = Repository::open(provider.main_worktree().to_native())
-        .map_err(repo_git::Error::from)?;
+    let provider = open_repository(root)?.git_layout().map_err(Error::Core)?;
+    let repo =
+        Repository::open(provider.main_worktree().to_native()).map_err(repo_git::Error::from)?;
 
     let current_branch_fn = || provider.current_branch();
-    repo_git::merge(&repo, &args.source, current_branch_fn, None)?;
+    match repo_git::merge(&repo, &args.source, current_branch_fn, None, abort) {
+        Ok(()) => {}
+        Err(repo_git::Error::MergeConflict { files }) => {
+            let message = if abort {
+                format!(
+                    "Merge of '{}' conflicts; the merge was aborted",
+                    args.source
+                )
+            } else {
+                format!(
+                    "Merge of '{}' conflicts; resolve the conflicts and commit",
+                    args.source
+                )
+            };
+            return Ok(json!({
+                "success": false,
+                "source": args.source,
+                "aborted": abort,
+                "conflicts": files,
+                "message": message,
+            }));
+        }
+        Err(e) => return Err(e.into()),
+    }
 
     Ok(json!({
         "success": true,
@@ -515,100 +626,57 @@ async fn handle_git_merge(root: &Path, arguments: Value) -> Result<Value> {
     }))
 }
 
-/// Create a LayoutProvider for git operations based on detected mode.
-fn create_git_provider(
-    root: &NormalizedPath,
-    mode: Mode,
-) -> Result<Box<dyn LayoutProvider>> {
-    match mode {
-        Mode::Standard => {
-            let layout = ClassicLayout::new(root.clone())?;
-            Ok(Box::new(layout))
-        }
-        Mode::Worktrees => {
-            let layout = ContainerLayout::new(root.clone(), Default::default())?;
-            Ok(Box::new(layout))
-        }
-    }
-}
-
 // ============================================================================
 // Configuration Management Handlers
 // ============================================================================
 
-/// Arguments for tool_add
-#[derive(Debug, Deserialize)]
-struct ToolAddArgs {
-    name: String,
-}
-
 /// Handle tool_add - Enable a tool for this repository
 async fn handle_tool_add(root: &Path, arguments: Value) -> Result<Value> {
-    let args: ToolAddArgs =
-        serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))?;
-
-    let normalized_root = NormalizedPath::new(root);
-    let config_path = find_config_path(&normalized_root)?;
-
-    // Read existing config
-    let content = fs::read_to_string(config_path.as_ref())?;
-    let mut manifest: Manifest = toml::from_str(&content)?;
+    let args: ToolAddArgs = args::parse(arguments)?;
+
+    let repo = open_configured_repository(root)?;
+    let unknown = ToolDispatcher::from_repository(repo.root())
+        .validate_name(&args.name)
+        .err();
+    if let Some(unknown) = &unknown
+        && !args.allow_unknown
+    {
+        return Err(Error::InvalidArgument(format!(
+            "{}. Set allow_unknown to enable it anyway.",
+            unknown
+        )));
+    }
 
-    // Check if tool already exists
-    if manifest.tools.contains(&args.name) {
+    if !repo.add_tool(&args.name).map_err(Error::Core)? {
         return Ok(json!({
             "success": false,
             "message": format!("Tool '{}' is already enabled", args.name),
         }));
     }
 
-    // Add the tool
-    manifest.tools.push(args.name.clone());
-
-    // Serialize and write back
-    let new_content = serialize_manifest(&manifest)?;
-    fs::write(config_path.as_ref(), &new_content)?;
-
-    Ok(json!({
+    let mut response = json!({
         "success": true,
         "tool": args.name,
         "message": format!("Enabled tool '{}'", args.name),
-    }))
-}
-
-/// Arguments for tool_remove
-#[derive(Debug, Deserialize)]
-struct ToolRemoveArgs {
-    name: String,
+    });
+    if let Some(unknown) = unknown {
+        response["warning"] = json!(unknown.to_string());
+    }
+    Ok(response)
 }
 
 /// Handle tool_remove - Disable a tool for this repository
 async fn handle_tool_remove(root: &Path, arguments: Value) -> Result<Value> {
-    let args: ToolRemoveArgs =
-        serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))?;
+    let args: ToolRemoveArgs = args::parse(arguments)?;
 
-    let normalized_root = NormalizedPath::new(root);
-    let config_path = find_config_path(&normalized_root)?;
-
-    // Read existing config
-    let content = fs::read_to_string(config_path.as_ref())?;
-    let mut manifest: Manifest = toml::from_str(&content)?;
-
-    // Check if tool exists
-    if !manifest.tools.contains(&args.name) {
+    let repo = open_configured_repository(root)?;
+    if !repo.remove_tool(&args.name).map_err(Error::Core)? {
         return Ok(json!({
             "success": false,
             "message": format!("Tool '{}' is not enabled", args.name),
         }));
     }
 
-    // Remove the tool
-    manifest.tools.retain(|t| t != &args.name);
-
-    // Serialize and write back
-    let new_content = serialize_manifest(&manifest)?;
-    fs::write(config_path.as_ref(), &new_content)?;
-
     Ok(json!({
         "success": true,
         "tool": args.name,
@@ -616,41 +684,109 @@ async fn handle_tool_remove(root: &Path, arguments: Value) -> Result<Value> {
     }))
 }
 
-/// Arguments for rule_add
-#[derive(Debug, Deserialize)]
-struct RuleAddArgs {
-    id: String,
-    content: String,
+/// Characters of rule content included in `rules_list` previews
+const RULE_PREVIEW_CHARS: usize = 200;
+
+/// Handle rules_list - List rules with their tags and where they are synced
+///
+/// Rules come from the rule files in `.repository/rules/` and from the rule
+/// registry. A rule's `projected_to` lists the tools whose `rules:<tool>`
+/// intent in the ledger includes it.
+async fn handle_rules_list(root: &Path) -> Result<Value> {
+    let repo = open_initialized_repository(root)?;
+    let registry = repo.rule_registry().map_err(Error::Core)?;
+    let ledger = repo
+        .sync_engine()
+        .and_then(|engine| engine.load_ledger())
+        .map_err(Error::Core)?;
+
+    // Intents record their rules by UUID, with the ID alongside
+    let mut projected: HashMap<String, Vec<String>> = HashMap::new();
+    for intent in ledger.intents() {
+        let Some(tool) = intent.id.strip_prefix("rules:") else {
+            continue;
+        };
+        let args: RulesArgs = intent.typed_args().map_err(Error::Core)?;
+        for (uuid, entry) in args.rules.iter().flatten() {
+            let id = Some(entry.id.as_str())
+                .filter(|id| !id.is_empty())
+                .or_else(|| {
+                    let uuid = uuid.parse().ok()?;
+                    Some(registry.get_rule(uuid)?.id.as_str())
+                });
+            if let Some(id) = id {
+                projected
+                    .entry(id.to_string())
+                    .or_default()
+                    .push(tool.to_string());
+            }
+        }
+    }
+    let entry = |id: &str, uuid: Option<String>, tags: &[String], content: &str| {
+        let mut tools = projected.get(id).cloned().unwrap_or_default();
+        tools.sort();
+        tools.dedup();
+        json!({
+            "id": id,
+            "uuid": uuid,
+            "tags": tags,
+            "preview": rule_preview(content),
+            "projected": !tools.is_empty(),
+            "projected_to": tools,
+        })
+    };
+
+    let file_ids = repo.list_rules().map_err(Error::Core)?;
+    let mut rules = Vec::new();
+    for id in &file_ids {
+        let (tags, content) = repo.read_rule(id).map_err(Error::Core)?;
+        rules.push((id.clone(), entry(id, None, &tags, &content)));
+    }
+    for rule in registry.all_rules() {
+        if !file_ids.contains(&rule.id) {
+            let uuid = Some(rule.uuid.to_string());
+            rules.push((
+                rule.id.clone(),
+                entry(&rule.id, uuid, &rule.tags, &rule.content),
+            ));
+        }
+    }
+    rules.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(json!({
+        "rules": rules.into_iter().map(|(_, rule)| rule).collect::<Vec<_>>(),
+    }))
+}
+
+/// The start of `content` on one line, cut at [`RULE_PREVIEW_CHARS`]
+fn rule_preview(content: &str) -> String {
+    let line = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    match line.char_indices().nth(RULE_PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line,
+    }
 }
 
 /// Handle rule_add - Add a custom rule to the repository
 async fn handle_rule_add(root: &Path, arguments: Value) -> Result<Value> {
-    let args: RuleAddArgs =
-        serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))?;
-
-    let normalized_root = NormalizedPath::new(root);
-    let rules_dir = find_rules_dir(&normalized_root)?;
+    let args: RuleAddArgs = args::parse(arguments)?;
 
     // Validate rule ID
-    repo_core::validate_rule_id(&args.id)
-        .map_err(|e| Error::InvalidArgument(e.to_string()))?;
+    repo_core::validate_rule_id(&args.id).map_err(|e| Error::InvalidArgument(e.to_string()))?;
 
-    // Create the rule file
-    let rule_path = rules_dir.join(&format!("{}.md", args.id));
+    let repo = open_initialized_repository(root)?;
 
     // Check if rule already exists
-    if rule_path.exists() {
+    if repo.rule_path(&args.id).map_err(Error::Core)?.exists() {
         return Ok(json!({
             "success": false,
             "message": format!("Rule '{}' already exists", args.id),
         }));
     }
 
-    // Ensure rules directory exists
-    fs::create_dir_all(rules_dir.as_ref())?;
-
-    // Write the rule file
-    fs::write(rule_path.as_ref(), &args.content)?;
+    let rule_path = repo
+        .add_rule(&args.id, &args.content, &args.tags)
+        .map_err(Error::Core)?;
 
     Ok(json!({
         "success": true,
@@ -660,38 +796,21 @@ async fn handle_rule_add(root: &Path, arguments: Value) -> Result<Value> {
     }))
 }
 
-/// Arguments for rule_remove
-#[derive(Debug, Deserialize)]
-struct RuleRemoveArgs {
-    id: String,
-}
-
 /// Handle rule_remove - Delete a rule from the repository
 async fn handle_rule_remove(root: &Path, arguments: Value) -> Result<Value> {
-    let args: RuleRemoveArgs =
-        serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))?;
+    let args: RuleRemoveArgs = args::parse(arguments)?;
 
     // Validate rule ID
-    repo_core::validate_rule_id(&args.id)
-        .map_err(|e| Error::InvalidArgument(e.to_string()))?;
-
-    let normalized_root = NormalizedPath::new(root);
-    let rules_dir = find_rules_dir(&normalized_root)?;
+    repo_core::validate_rule_id(&args.id).map_err(|e| Error::InvalidArgument(e.to_string()))?;
 
-    // Find the rule file
-    let rule_path = rules_dir.join(&format!("{}.md", args.id));
-
-    // Check if rule exists
-    if !rule_path.exists() {
+    let repo = open_initialized_repository(root)?;
+    if !repo.remove_rule(&args.id).map_err(Error::Core)? {
         return Ok(json!({
             "success": false,
             "message": format!("Rule '{}' does not exist", args.id),
         }));
     }
 
-    // Remove the rule file
-    fs::remove_file(rule_path.as_ref())?;
-
     Ok(json!({
         "success": true,
         "rule": args.id,
@@ -705,11 +824,9 @@ async fn handle_rule_remove(root: &Path, arguments: Value) -> Result<Value> {
 
 /// Handle preset_list - List configured presets and available preset types
 async fn handle_preset_list(root: &Path) -> Result<Value> {
-    let normalized_root = NormalizedPath::new(root);
-    let config_path = find_config_path(&normalized_root)?;
-
-    let content = fs::read_to_string(config_path.as_ref())?;
-    let manifest: Manifest = toml::from_str(&content)?;
+    let manifest = open_configured_repository(root)?
+        .manifest()
+        .map_err(Error::Core)?;
 
     let configured: Vec<Value> = manifest
         .presets
@@ -732,37 +849,22 @@ async fn handle_preset_list(root: &Path) -> Result<Value> {
     }))
 }
 
-/// Arguments for preset_add
-#[derive(Debug, Deserialize)]
-struct PresetAddArgs {
-    name: String,
-}
-
 /// Handle preset_add - Add a preset to the repository configuration
 async fn handle_preset_add(root: &Path, arguments: Value) -> Result<Value> {
-    let args: PresetAddArgs =
-        serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))?;
+    let args: PresetAddArgs = args::parse(arguments)?;
 
-    let normalized_root = NormalizedPath::new(root);
-    let config_path = find_config_path(&normalized_root)?;
-
-    let content = fs::read_to_string(config_path.as_ref())?;
-    let mut manifest: Manifest = toml::from_str(&content)?;
-
-    // Check if preset already exists
-    if manifest.presets.contains_key(&args.name) {
+    // Add the preset with an empty config
+    let repo = open_configured_repository(root)?;
+    if !repo
+        .add_preset(&args.name, json!({}))
+        .map_err(Error::Core)?
+    {
         return Ok(json!({
             "success": false,
             "message": format!("Preset '{}' is already configured", args.name),
         }));
     }
 
-    // Add the preset with an empty config
-    manifest.presets.insert(args.name.clone(), json!({}));
-
-    let new_content = serialize_manifest(&manifest)?;
-    fs::write(config_path.as_ref(), &new_content)?;
-
     Ok(json!({
         "success": true,
         "preset": args.name,
@@ -770,33 +872,18 @@ async fn handle_preset_add(root: &Path, arguments: Value) -> Result<Value> {
     }))
 }
 
-/// Arguments for preset_remove
-#[derive(Debug, Deserialize)]
-struct PresetRemoveArgs {
-    name: String,
-}
-
 /// Handle preset_remove - Remove a preset from the repository configuration
 async fn handle_preset_remove(root: &Path, arguments: Value) -> Result<Value> {
-    let args: PresetRemoveArgs =
-        serde_json::from_value(arguments).map_err(|e| Error::InvalidArgument(e.to_string()))?;
+    let args: PresetRemoveArgs = args::parse(arguments)?;
 
-    let normalized_root = NormalizedPath::new(root);
-    let config_path = find_config_path(&normalized_root)?;
-
-    let content = fs::read_to_string(config_path.as_ref())?;
-    let mut manifest: Manifest = toml::from_str(&content)?;
-
-    if manifest.presets.remove(&args.name).is_none() {
+    let repo = open_configured_repository(root)?;
+    if !repo.remove_preset(&args.name).map_err(Error::Core)? {
         return Ok(json!({
             "success": false,
             "message": format!("Preset '{}' is not configured", args.name),
         }));
     }
 
-    let new_content = serialize_manifest(&manifest)?;
-    fs::write(config_path.as_ref(), &new_content)?;
-
     Ok(json!({
         "success": true,
         "preset": args.name,
@@ -808,26 +895,71 @@ async fn handle_preset_remove(root: &Path, arguments: Value) -> Result<Value> {
 // Extension Management Handlers
 // ============================================================================
 
-/// Handle extension_install - Install an extension from a URL or local path
-async fn handle_extension_install(_arguments: Value) -> Result<Value> {
-    Err(Error::NotImplemented("extension_install".to_string()))
+/// Handle extension_install - Install an extension from a git URL
+///
+/// Runs the same install as `repo extension install`: the source is cloned
+/// at the `ref` configured for it in config.toml, if any, checked for tool
+/// collisions and preset requirements, and recorded in the extension lock
+/// file and config.toml. Required presets are configured and applied.
+async fn handle_extension_install(root: &Path, arguments: Value) -> Result<Value> {
+    let args: ExtensionInstallArgs = args::parse(arguments)?;
+    if !is_git_url(&args.source) {
+        return Err(Error::InvalidArgument(format!(
+            "extension_install takes a git URL, got '{}'",
+            args.source
+        )));
+    }
+
+    let repo = open_configured_repository(root)?;
+    let install = repo
+        .install_extension(&args.source, args.no_deps)
+        .map_err(Error::Core)?;
+
+    Ok(json!({
+        "success": true,
+        "name": install.locked.name,
+        "version": install.locked.version,
+        "commit": install.locked.commit,
+        "path": install.path.display().to_string(),
+        "collisions": install.collisions.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        "presets_added": install.presets_added.iter().map(|(preset, _)| preset).collect::<Vec<_>>(),
+        "presets_applied": install.presets_applied,
+    }))
 }
 
 /// Handle extension_add - Add a known extension by name from the registry
-async fn handle_extension_add(_arguments: Value) -> Result<Value> {
+async fn handle_extension_add(arguments: Value) -> Result<Value> {
+    let ExtensionAddArgs { name } = args::parse(arguments)?;
+    tracing::debug!(extension = %name, "extension_add is not implemented");
     Err(Error::NotImplemented("extension_add".to_string()))
 }
 
 /// Handle extension_init - Initialize a new extension scaffold
-async fn handle_extension_init(_arguments: Value) -> Result<Value> {
+async fn handle_extension_init(arguments: Value) -> Result<Value> {
+    let ExtensionInitArgs { name } = args::parse(arguments)?;
+    tracing::debug!(extension = %name, "extension_init is not implemented");
     Err(Error::NotImplemented("extension_init".to_string()))
 }
 
 /// Handle extension_remove - Remove an installed extension
-async fn handle_extension_remove(_arguments: Value) -> Result<Value> {
+async fn handle_extension_remove(arguments: Value) -> Result<Value> {
+    let ExtensionRemoveArgs { name } = args::parse(arguments)?;
+    tracing::debug!(extension = %name, "extension_remove is not implemented");
     Err(Error::NotImplemented("extension_remove".to_string()))
 }
 
+/// Handle extension_verify - Verify installed extensions against the lock file
+///
+/// Returns the per-extension report and, as drift, the installed extensions
+/// missing from the lock; `passed` is false if any check failed or any
+/// extension is missing from the lock.
+async fn handle_extension_verify(root: &Path) -> Result<Value> {
+    let repo = open_repository(root)?;
+    let report = repo_extensions::verify(repo.root().as_ref())
+        .map_err(|e| Error::InvalidRepository(e.to_string()))?;
+    Ok(serde_json::to_value(report)?)
+}
+
 /// Handle extension_list - List installed and known extensions
 async fn handle_extension_list() -> Result<Value> {
     use repo_extensions::ExtensionRegistry;
@@ -860,130 +992,44 @@ async fn handle_extension_list() -> Result<Value> {
 // Helper Functions
 // ============================================================================
 
-/// Repository context with mode and normalized root path.
-/// This reduces duplication in handlers that need mode detection.
-struct RepoContext {
-    root: NormalizedPath,
-    mode: Mode,
-}
-
-impl RepoContext {
-    /// Create a new repository context from a path
-    fn new(path: &Path) -> Result<Self> {
-        let root = NormalizedPath::new(path);
-        let mode = detect_mode(&root)?;
-        Ok(Self { root, mode })
-    }
-
-    /// Create a SyncEngine for this repository
-    fn sync_engine(&self) -> Result<SyncEngine> {
-        SyncEngine::new(self.root.clone(), self.mode).map_err(Error::Core)
-    }
-
-    /// Create a ModeBackend for this repository
-    fn backend(&self) -> Result<Box<dyn ModeBackend>> {
-        create_backend(&self.root, self.mode)
-    }
-}
-
-/// Detect the repository mode from filesystem markers and configuration.
+/// Open the repository containing `path`
 ///
-/// Delegates to [`repo_core::detect_mode`] which checks filesystem markers
-/// (`.gt`, `.git`) and falls back to `.repository/config.toml` via ConfigResolver.
-/// Defaults to Standard mode when no indicators are found.
-fn detect_mode(root: &NormalizedPath) -> Result<Mode> {
-    repo_core::detect_mode(root).map_err(Error::Core)
-}
-
-/// Create the appropriate backend for the detected mode
-fn create_backend(root: &NormalizedPath, mode: Mode) -> Result<Box<dyn ModeBackend>> {
-    match mode {
-        Mode::Standard => {
-            let backend = StandardBackend::new(root.clone()).map_err(Error::Core)?;
-            Ok(Box::new(backend))
-        }
-        Mode::Worktrees => {
-            // For worktrees, we need to find the container
-            let container = find_container(root)?;
-            let backend = WorktreeBackend::new(container).map_err(Error::Core)?;
-            Ok(Box::new(backend))
-        }
-    }
-}
-
-/// Find the container directory for a worktree setup
-fn find_container(root: &NormalizedPath) -> Result<NormalizedPath> {
-    // If root contains .gt, it's the container
-    if root.join(".gt").exists() {
-        return Ok(root.clone());
-    }
-
-    // Otherwise, check parent directory
-    if let Some(parent) = root.as_ref().parent() {
-        let parent_path = NormalizedPath::new(parent);
-        if parent_path.join(".gt").exists() {
-            return Ok(parent_path);
-        }
-    }
-
-    // Not a worktree container
-    Err(Error::InvalidArgument(
-        "Not a worktree container: .gt not found".to_string(),
-    ))
+/// Uses [`repo_core::Repository::discover`] so the MCP server resolves the
+/// same root as the CLI; falls back to the detected root, or `path` itself,
+/// when the repository is not fully initialized.
+fn open_repository(path: &Path) -> Result<repo_core::Repository> {
+    repo_core::Repository::discover(path).or_else(|_| {
+        let root = detect(path)
+            .root
+            .unwrap_or_else(|| NormalizedPath::new(path));
+        repo_core::Repository::open(root).map_err(Error::Core)
+    })
 }
 
-/// Find the config.toml path
-fn find_config_path(root: &NormalizedPath) -> Result<NormalizedPath> {
-    // Check .repository/config.toml in root
-    let config_path = root.join(".repository/config.toml");
-    if config_path.exists() {
-        return Ok(config_path);
-    }
-
-    // Check parent for worktree mode
-    if let Some(parent) = root.as_ref().parent() {
-        let parent_path = NormalizedPath::new(parent);
-        let config_path = parent_path.join(".repository/config.toml");
-        if config_path.exists() {
-            return Ok(config_path);
-        }
+/// Open the repository containing `path`, requiring `.repository/config.toml`
+fn open_configured_repository(path: &Path) -> Result<repo_core::Repository> {
+    if detect(path).config_path.is_none() {
+        return Err(Error::InvalidArgument(
+            "Config file not found (.repository/config.toml)".to_string(),
+        ));
     }
-
-    Err(Error::InvalidArgument(
-        "Config file not found (.repository/config.toml)".to_string(),
-    ))
+    open_repository(path)
 }
 
-/// Find the rules directory
-fn find_rules_dir(root: &NormalizedPath) -> Result<NormalizedPath> {
-    // Check .repository/rules in root
-    let rules_dir = root.join(".repository/rules");
-    if rules_dir.exists() || root.join(".repository").exists() {
-        return Ok(rules_dir);
-    }
-
-    // Check parent for worktree mode
-    if let Some(parent) = root.as_ref().parent() {
-        let parent_path = NormalizedPath::new(parent);
-        let rules_dir = parent_path.join(".repository/rules");
-        if rules_dir.exists() || parent_path.join(".repository").exists() {
-            return Ok(rules_dir);
-        }
+/// Open the repository containing `path`, requiring `.repository/`
+fn open_initialized_repository(path: &Path) -> Result<repo_core::Repository> {
+    if !detect(path).is_repo() {
+        return Err(Error::InvalidArgument(
+            "Repository not initialized (.repository not found)".to_string(),
+        ));
     }
-
-    Err(Error::InvalidArgument(
-        "Repository not initialized (.repository not found)".to_string(),
-    ))
-}
-
-/// Serialize a manifest back to TOML format
-fn serialize_manifest(manifest: &Manifest) -> Result<String> {
-    Ok(manifest.to_toml())
+    open_repository(path)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use repo_core::Manifest;
     use tempfile::TempDir;
 
     fn create_test_repo(dir: &std::path::Path) {
@@ -1132,11 +1178,199 @@ mod tests {
             // They may return other errors (e.g., no remote, no branch) but
             // the key assertion is that NotImplemented is gone.
             if let Err(Error::NotImplemented(name)) = &result {
-                panic!("{} still returns NotImplemented - it should be implemented now", name);
+                panic!(
+                    "{} still returns NotImplemented - it should be implemented now",
+                    name
+                );
             }
         }
     }
 
+    #[tokio::test]
+    async fn test_git_merge_reports_conflicted_files() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .current_dir(root)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "-b", "main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test User"]);
+        git(&["config", "commit.gpgSign", "false"]);
+        create_test_repo(root);
+        fs::write(root.join("notes.txt"), "base\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "Initial"]);
+        git(&["checkout", "-b", "feature"]);
+        fs::write(root.join("notes.txt"), "feature\n").unwrap();
+        git(&["commit", "-am", "Feature"]);
+        git(&["checkout", "main"]);
+        fs::write(root.join("notes.txt"), "main\n").unwrap();
+        git(&["commit", "-am", "Main"]);
+
+        let result = handle_tool_call(root, "git_merge", json!({"source": "feature"}))
+            .await
+            .unwrap();
+        assert_eq!(result["success"], false);
+        assert_eq!(result["aborted"], true);
+        assert_eq!(result["conflicts"], json!(["notes.txt"]));
+        assert_eq!(
+            fs::read_to_string(root.join("notes.txt")).unwrap(),
+            "main\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_git_fetch_reports_new_branches() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("repo");
+        let remote = temp.path().join("remote.git");
+        fs::create_dir(&root).unwrap();
+        let git = |dir: &Path, args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .current_dir(dir)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?} failed", args);
+        };
+        git(&root, &["init", "-b", "main"]);
+        git(&root, &["config", "user.email", "test@example.com"]);
+        git(&root, &["config", "user.name", "Test User"]);
+        git(&root, &["config", "commit.gpgSign", "false"]);
+        create_test_repo(&root);
+        git(&root, &["add", "."]);
+        git(&root, &["commit", "-m", "Initial"]);
+        git(&root, &["clone", "--bare", ".", remote.to_str().unwrap()]);
+        git(
+            &root,
+            &["remote", "add", "origin", remote.to_str().unwrap()],
+        );
+        git(&root, &["fetch", "origin"]);
+        // The remote renames main to feature
+        git(&remote, &["branch", "-m", "main", "feature"]);
+
+        let result = handle_tool_call(&root, "git_fetch", json!({"prune": true}))
+            .await
+            .unwrap();
+        assert_eq!(result["success"], true);
+        assert_eq!(result["remote"], "origin");
+        assert_eq!(result["pruned"], json!(["refs/remotes/origin/main"]));
+        let repo = Repository::open(&root).unwrap();
+        assert!(repo.find_reference("refs/remotes/origin/feature").is_ok());
+    }
+
+    /// A git repository with one commit on `main` and a `feature` branch
+    fn git_repo_with_feature(root: &Path) {
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .current_dir(root)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "-b", "main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test User"]);
+        git(&["config", "commit.gpgSign", "false"]);
+        create_test_repo(root);
+        git(&["add", "."]);
+        git(&["commit", "-m", "Initial"]);
+        git(&["branch", "feature"]);
+    }
+
+    #[tokio::test]
+    async fn test_branch_checkout() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git_repo_with_feature(root);
+
+        let result = handle_tool_call(root, "branch_checkout", json!({"name": "feature"}))
+            .await
+            .unwrap();
+        assert_eq!(result["success"], true);
+        assert_eq!(result["path"], NormalizedPath::new(root).as_str());
+        let repo = Repository::open(root).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("feature"));
+
+        let result = handle_tool_call(root, "branch_checkout", json!({"name": "--orphan"})).await;
+        assert!(matches!(result, Err(Error::InvalidArgument(_))));
+    }
+
+    #[tokio::test]
+    async fn test_branch_rename() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git_repo_with_feature(root);
+
+        let result = handle_tool_call(
+            root,
+            "branch_rename",
+            json!({"old": "feature", "new": "feature-2"}),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result["success"], true);
+        let repo = Repository::open(root).unwrap();
+        assert!(
+            repo.find_branch("feature", git2::BranchType::Local)
+                .is_err()
+        );
+        assert!(
+            repo.find_branch("feature-2", git2::BranchType::Local)
+                .is_ok()
+        );
+
+        let result = handle_tool_call(
+            root,
+            "branch_rename",
+            json!({"old": "feature-2", "new": "../escape"}),
+        )
+        .await;
+        assert!(matches!(result, Err(Error::InvalidArgument(_))));
+    }
+
+    #[tokio::test]
+    async fn test_branch_prune_dry_run() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .current_dir(root)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "-b", "main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test User"]);
+        git(&["config", "commit.gpgSign", "false"]);
+        create_test_repo(root);
+        git(&["add", "."]);
+        git(&["commit", "-m", "Initial"]);
+        git(&["checkout", "-b", "done"]);
+        git(&["commit", "--allow-empty", "-m", "Done"]);
+        git(&["checkout", "main"]);
+        git(&["merge", "--no-ff", "-m", "Merge", "done"]);
+
+        let result = handle_tool_call(root, "branch_prune", json!({"dry_run": true}))
+            .await
+            .unwrap();
+        assert_eq!(result["success"], true);
+        assert_eq!(result["pruned"], 1);
+        assert_eq!(result["branches"][0]["name"], "done");
+        assert_eq!(result["branches"][0]["pruned"], true);
+        let repo = Repository::open(root).unwrap();
+        assert!(repo.find_branch("done", git2::BranchType::Local).is_ok());
+    }
+
     #[tokio::test]
     async fn test_handle_tool_add() {
         let temp = TempDir::new().unwrap();
@@ -1154,12 +1388,41 @@ mod tests {
         assert!(result.is_ok());
         let value = result.unwrap();
         assert_eq!(value.get("success"), Some(&json!(true)));
+        assert!(value.get("warning").is_none());
 
         // Verify tool was added
         let content = fs::read_to_string(temp.path().join(".repository/config.toml")).unwrap();
         assert!(content.contains("vscode"));
     }
 
+    #[tokio::test]
+    async fn test_handle_tool_add_unknown_suggests_close_match() {
+        let temp = TempDir::new().unwrap();
+        create_test_repo(temp.path());
+
+        let err = handle_tool_call(temp.path(), "tool_add", json!({ "name": "cursr" }))
+            .await
+            .unwrap_err();
+        assert!(matches!(&err, Error::InvalidArgument(m) if m.contains("did you mean 'cursor'")));
+        let content = fs::read_to_string(temp.path().join(".repository/config.toml")).unwrap();
+        assert!(!content.contains("cursr"));
+
+        let value = handle_tool_call(
+            temp.path(),
+            "tool_add",
+            json!({ "name": "cursr", "allow_unknown": true }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(value["success"], json!(true));
+        assert!(
+            value["warning"]
+                .as_str()
+                .unwrap()
+                .contains("did you mean 'cursor'")
+        );
+    }
+
     #[tokio::test]
     async fn test_handle_tool_add_duplicate() {
         let temp = TempDir::new().unwrap();
@@ -1241,6 +1504,95 @@ mod tests {
         assert_eq!(content, "Do not use unsafe code blocks.");
     }
 
+    #[tokio::test]
+    async fn test_handle_rules_list() {
+        let temp = TempDir::new().unwrap();
+        create_test_repo(temp.path());
+
+        for (id, content, tags) in [
+            (
+                "no-unsafe",
+                "Do not use unsafe code blocks.",
+                json!(["rust", "safety"]),
+            ),
+            (
+                "snake-case",
+                "Use snake_case for variables.",
+                json!(["python"]),
+            ),
+        ] {
+            let result = handle_tool_call(
+                temp.path(),
+                "rule_add",
+                json!({ "id": id, "content": content, "tags": tags }),
+            )
+            .await
+            .unwrap();
+            assert_eq!(result["success"], true);
+        }
+
+        let result = handle_tool_call(temp.path(), "rules_list", json!({}))
+            .await
+            .unwrap();
+        let rules = result["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "no-unsafe");
+        assert_eq!(rules[0]["tags"], json!(["rust", "safety"]));
+        assert_eq!(rules[0]["preview"], "Do not use unsafe code blocks.");
+        assert_eq!(rules[0]["projected"], false);
+        assert_eq!(rules[1]["id"], "snake-case");
+        assert_eq!(rules[1]["tags"], json!(["python"]));
+    }
+
+    #[tokio::test]
+    async fn test_handle_rules_list_projected() {
+        let temp = TempDir::new().unwrap();
+        create_test_repo(temp.path());
+        fs::write(
+            temp.path().join(".repository/config.toml"),
+            "tools = [\"cursor\"]\n\n[core]\nmode = \"standard\"\n",
+        )
+        .unwrap();
+        let mut registry =
+            repo_core::RuleRegistry::new(temp.path().join(".repository/rules/registry.toml"));
+        registry
+            .add_rule("style", "Use 4 spaces.", vec!["format".into()])
+            .unwrap();
+        handle_tool_call(temp.path(), "repo_sync", json!({}))
+            .await
+            .unwrap();
+
+        let result = handle_tool_call(temp.path(), "rules_list", json!({}))
+            .await
+            .unwrap();
+        let rule = &result["rules"][0];
+        assert_eq!(rule["id"], "style");
+        assert!(rule["uuid"].is_string());
+        assert_eq!(rule["tags"], json!(["format"]));
+        assert_eq!(rule["projected"], true);
+        assert_eq!(rule["projected_to"], json!(["cursor"]));
+    }
+
+    #[tokio::test]
+    async fn test_handle_rules_list_malformed_intent() {
+        let temp = TempDir::new().unwrap();
+        create_test_repo(temp.path());
+        let mut ledger = repo_core::Ledger::new();
+        ledger.add_intent(repo_core::Intent::new(
+            "rules:cursor".to_string(),
+            json!({"rules": 3}),
+        ));
+        ledger
+            .save(&temp.path().join(".repository/ledger.toml"))
+            .unwrap();
+
+        let result = handle_tool_call(temp.path(), "rules_list", json!({})).await;
+        assert!(matches!(
+            result,
+            Err(Error::Core(repo_core::Error::MalformedIntent { .. }))
+        ));
+    }
+
     #[tokio::test]
     async fn test_handle_rule_add_invalid_id() {
         let temp = TempDir::new().unwrap();
@@ -1310,22 +1662,20 @@ mod tests {
     }
 
     #[test]
-    fn test_detect_mode_standard() {
+    fn test_open_repository_mode_standard() {
         let temp = TempDir::new().unwrap();
         fs::create_dir_all(temp.path().join(".git")).unwrap();
 
-        let root = NormalizedPath::new(temp.path());
-        let mode = detect_mode(&root).unwrap();
+        let mode = open_repository(temp.path()).unwrap().mode();
         assert_eq!(mode, Mode::Standard);
     }
 
     #[test]
-    fn test_detect_mode_worktrees() {
+    fn test_open_repository_mode_worktrees() {
         let temp = TempDir::new().unwrap();
         fs::create_dir_all(temp.path().join(".gt")).unwrap();
 
-        let root = NormalizedPath::new(temp.path());
-        let mode = detect_mode(&root).unwrap();
+        let mode = open_repository(temp.path()).unwrap().mode();
         assert_eq!(mode, Mode::Worktrees);
     }
 
@@ -1342,7 +1692,7 @@ mod tests {
         )
         .unwrap();
 
-        let result = serialize_manifest(&manifest).unwrap();
+        let result = manifest.to_toml();
         // toml::to_string_pretty may format arrays multi-line
         assert!(result.contains("vscode"));
         assert!(result.contains("cursor"));
@@ -1360,25 +1710,141 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_handle_extension_install_returns_not_implemented() {
+    async fn test_handle_extension_install_records_lock_entry() {
         let temp = TempDir::new().unwrap();
-        let result = handle_tool_call(
-            temp.path(),
-            "extension_install",
-            json!({ "source": "https://github.com/example/ext.git" }),
+        // A bare repository holding the extension, served over file://
+        let bare = git2::Repository::init_bare(temp.path().join("ext.git")).unwrap();
+        let blob = bare
+            .blob(b"[extension]\nname = \"ext\"\nversion = \"0.2.0\"\n")
+            .unwrap();
+        let mut tree = bare.treebuilder(None).unwrap();
+        tree.insert("repo_extension.toml", blob, 0o100644).unwrap();
+        let tree = bare.find_tree(tree.write().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let commit = bare
+            .commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap()
+            .to_string();
+        let url = format!("file://{}", temp.path().join("ext.git").display());
+
+        let root = temp.path().join("repo");
+        create_test_repo(&root);
+        fs::write(
+            root.join(".repository/config.toml"),
+            format!(
+                "tools = []\n\n[core]\nmode = \"standard\"\n\n\
+                 [extensions.\"ext\"]\nsource = \"{url}\"\nref = \"{commit}\" # pinned\n"
+            ),
         )
-        .await;
+        .unwrap();
+
+        let result = handle_tool_call(&root, "extension_install", json!({ "source": url }))
+            .await
+            .unwrap();
+        assert_eq!(result["success"], true);
+        assert_eq!(result["name"], "ext");
+        assert_eq!(result["commit"], commit);
+
+        let lock = repo_extensions::LockFile::load(&root).unwrap();
+        let entry = lock.get("ext").unwrap();
+        assert_eq!(entry.source, url);
+        assert_eq!(entry.version, "0.2.0");
+        assert_eq!(entry.commit.as_deref(), Some(commit.as_str()));
+        assert!(lock.verify(&root).is_empty());
+        assert!(
+            root.join(".repository/extensions/ext/repo_extension.toml")
+                .is_file()
+        );
+        let config = fs::read_to_string(root.join(".repository/config.toml")).unwrap();
+        assert!(config.contains(&format!("ref = \"{commit}\" # pinned\n")));
+
+        let result =
+            handle_tool_call(&root, "extension_install", json!({ "source": "./ext" })).await;
+        assert!(matches!(result, Err(Error::InvalidArgument(_))));
+    }
 
-        assert!(result.is_err(), "extension_install must return an error");
+    #[tokio::test]
+    async fn test_handle_extension_install_checks_preset_constraints() {
+        let temp = TempDir::new().unwrap();
+        let bare = git2::Repository::init_bare(temp.path().join("ext.git")).unwrap();
+        let blob = bare
+            .blob(
+                b"[extension]\nname = \"ext\"\nversion = \"0.1.0\"\n\n\
+                  [requires.python]\nversion = \">=999\"\n",
+            )
+            .unwrap();
+        let mut tree = bare.treebuilder(None).unwrap();
+        tree.insert("repo_extension.toml", blob, 0o100644).unwrap();
+        let tree = bare.find_tree(tree.write().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        bare.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+        let url = format!("file://{}", temp.path().join("ext.git").display());
+
+        let root = temp.path().join("repo");
+        create_test_repo(&root);
+
+        let result = handle_tool_call(&root, "extension_install", json!({ "source": url })).await;
         match result {
-            Err(Error::NotImplemented(name)) => {
-                assert_eq!(name, "extension_install");
+            Err(Error::Core(repo_core::Error::UnsatisfiedPresets { name, requirements })) => {
+                assert_eq!(name, "ext");
+                assert!(
+                    requirements.contains("env:python >=999"),
+                    "got: {requirements}"
+                );
             }
-            other => panic!(
-                "Expected NotImplemented error for extension_install, got: {:?}",
-                other
-            ),
+            other => panic!("Expected UnsatisfiedPresets, got: {:?}", other),
         }
+        assert!(!root.join(".repository/extensions/ext").exists());
+
+        // With no_deps the requirement is only reported
+        let result = handle_tool_call(
+            &root,
+            "extension_install",
+            json!({ "source": url, "no_deps": true }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result["success"], true);
+        assert_eq!(result["presets_added"], json!([]));
+        assert!(root.join(".repository/extensions/ext").is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_handle_extension_install_applies_added_presets() {
+        let temp = TempDir::new().unwrap();
+        let bare = git2::Repository::init_bare(temp.path().join("ext.git")).unwrap();
+        let blob = bare
+            .blob(
+                b"[extension]\nname = \"ext\"\nversion = \"0.1.0\"\n\n[runtime]\ntype = \"rust\"\n",
+            )
+            .unwrap();
+        let mut tree = bare.treebuilder(None).unwrap();
+        tree.insert("repo_extension.toml", blob, 0o100644).unwrap();
+        let tree = bare.find_tree(tree.write().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        bare.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+        let url = format!("file://{}", temp.path().join("ext.git").display());
+
+        let root = temp.path().join("repo");
+        create_test_repo(&root);
+
+        let result = handle_tool_call(&root, "extension_install", json!({ "source": url }))
+            .await
+            .unwrap();
+        assert_eq!(result["presets_added"], json!(["env:rust"]));
+        let applied = &result["presets_applied"][0];
+        assert_eq!(applied["preset"], "env:rust");
+        assert_eq!(applied["supported"], true);
+        // Without a pinned toolchain the Rust provider only detects
+        assert!(
+            applied["actions"][0]
+                .as_str()
+                .unwrap()
+                .contains("Rust environment detected"),
+            "got: {applied}"
+        );
     }
 
     #[tokio::test]
@@ -1440,10 +1906,19 @@ mod tests {
         // Extension handlers should still return NotImplemented
         let temp = TempDir::new().unwrap();
 
-        let extension_tools = ["extension_install", "extension_add", "extension_init", "extension_remove"];
+        let extension_tools = ["extension_add", "extension_init", "extension_remove"];
 
         for tool in extension_tools.iter() {
+            // Arguments are still validated
             let result = handle_tool_call(temp.path(), tool, json!({})).await;
+            assert!(
+                matches!(result, Err(Error::InvalidArgument(_))),
+                "{} must reject missing arguments, got: {:?}",
+                tool,
+                result
+            );
+
+            let result = handle_tool_call(temp.path(), tool, json!({ "name": "ext" })).await;
             assert!(result.is_err(), "{} must return an error", tool);
             match result {
                 Err(Error::NotImplemented(_)) => {}
@@ -1472,4 +1947,34 @@ mod tests {
                 .any(|e| e.get("name") == Some(&json!("vaultspec")))
         );
     }
+
+    #[tokio::test]
+    async fn test_handle_extension_verify() {
+        let temp = TempDir::new().unwrap();
+        create_test_repo(temp.path());
+
+        let value = handle_tool_call(temp.path(), "extension_verify", json!({}))
+            .await
+            .unwrap();
+        assert_eq!(value["passed"], json!(true));
+
+        // A locked extension whose directory is gone fails verification
+        fs::write(
+            temp.path().join(".repository/extensions.lock"),
+            "[[extension]]\nname = \"ghost\"\nversion = \"1.0.0\"\nsource = \"local\"\n",
+        )
+        .unwrap();
+        let value = handle_tool_call(temp.path(), "extension_verify", json!({}))
+            .await
+            .unwrap();
+        assert_eq!(value["passed"], json!(false));
+        assert_eq!(value["extensions"][0]["name"], json!("ghost"));
+        assert_eq!(
+            value["extensions"][0]["checks"][0]["check"],
+            json!("directory")
+        );
+        assert_eq!(value["extensions"][0]["checks"][0]["passed"], json!(false));
+        // Reported once, not again as drift
+        assert_eq!(value["drift"], json!([]));
+    }
 }
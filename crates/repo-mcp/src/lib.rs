@@ -29,23 +29,43 @@
 //! - Git primitives (push, pull, merge)
 //! - Configuration management (tools, presets, rules)
 //!
+//! Tool arguments are validated against each tool's JSON Schema before
+//! dispatch, and every tool call is bounded by a per-tool timeout (see
+//! [`ToolTimeouts`]).
+//!
 //! # Resources
 //!
 //! Read-only resources exposed:
 //! - `repo://config` - Repository configuration
 //! - `repo://state` - Computed state from ledger
 //! - `repo://rules` - Aggregated active rules
+//! - `repo://ledger` - Ledger intents and projections as JSON
+//!
+//! # Prompts
+//!
+//! Message templates for guided workflows, with arguments described by the
+//! input schema of the tool each one drives:
+//! - `init_repository` - Set up a project with `repo_init`
+//! - `add_rule_guided` - Draft and add a rule with `rule_add`
 
+mod args;
 pub mod error;
 pub mod handlers;
+pub mod prompts;
 pub mod protocol;
 pub mod resource_handlers;
 pub mod resources;
+mod schema;
 pub mod server;
+pub mod timeout;
 pub mod tools;
 
 pub use error::{Error, Result};
 pub use handlers::handle_tool_call;
+pub use prompts::{PromptDefinition, get_prompt, get_prompt_definitions};
 pub use resource_handlers::read_resource;
 pub use server::RepoMcpServer;
-pub use tools::{ToolContent, ToolDefinition, ToolResult, get_tool_definitions};
+pub use timeout::{DEFAULT_TOOL_TIMEOUT, ToolTimeouts};
+pub use tools::{
+    ToolContent, ToolDefinition, ToolResult, get_tool_definitions, validate_tool_arguments,
+};
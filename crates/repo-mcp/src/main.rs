@@ -6,7 +6,7 @@
 //! # Usage
 //!
 //! ```bash
-//! repo-mcp [--root <path>]
+//! repo-mcp [--root <path>] [--timeout <secs>] [--tool-timeout <tool>=<secs>]...
 //! ```
 //!
 //! # Environment Variables
@@ -20,9 +20,10 @@
 //! - Logs go to stderr (to avoid interfering with the protocol)
 
 use std::path::PathBuf;
+use std::time::Duration;
 
 use clap::Parser;
-use repo_mcp::RepoMcpServer;
+use repo_mcp::{DEFAULT_TOOL_TIMEOUT, RepoMcpServer, ToolTimeouts};
 
 /// MCP server for Repository Manager
 #[derive(Parser)]
@@ -33,6 +34,25 @@ struct Args {
     /// Repository root path
     #[arg(short, long, default_value = ".")]
     root: PathBuf,
+
+    /// Default tool call timeout in seconds
+    #[arg(long, value_name = "SECS", default_value_t = DEFAULT_TOOL_TIMEOUT.as_secs())]
+    timeout: u64,
+
+    /// Per-tool timeout override (repeatable), e.g. `--tool-timeout git_pull=300`
+    #[arg(long, value_name = "TOOL=SECS", value_parser = parse_tool_timeout)]
+    tool_timeout: Vec<(String, u64)>,
+}
+
+/// Parse a `TOOL=SECS` timeout override
+fn parse_tool_timeout(s: &str) -> Result<(String, u64), String> {
+    let (tool, secs) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected TOOL=SECS, got '{}'", s))?;
+    let secs = secs
+        .parse()
+        .map_err(|_| format!("invalid number of seconds: '{}'", secs))?;
+    Ok((tool.to_string(), secs))
 }
 
 #[tokio::main]
@@ -50,7 +70,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     tracing::info!(root = ?args.root, "Starting repo-mcp server");
 
-    let mut server = RepoMcpServer::new(args.root);
+    let timeouts = args.tool_timeout.into_iter().fold(
+        ToolTimeouts::new(Duration::from_secs(args.timeout)),
+        |timeouts, (tool, secs)| timeouts.with_tool(tool, Duration::from_secs(secs)),
+    );
+
+    let mut server = RepoMcpServer::new(args.root).with_timeouts(timeouts);
     server.run().await?;
 
     Ok(())
@@ -0,0 +1,290 @@
+//! MCP Prompt implementations
+//!
+//! Prompts are message templates for common repository workflows that a
+//! client can offer to the user and fill in. Each prompt drives one tool,
+//! and its arguments take their descriptions and allowed values from that
+//! tool's input schema, so the two never disagree.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::tools::{ToolContent, ToolDefinition, get_tool_definitions};
+use crate::{Error, Result};
+
+/// Prompt definition for MCP protocol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptDefinition {
+    pub name: String,
+    pub description: String,
+    pub arguments: Vec<PromptArgument>,
+}
+
+/// An argument a prompt can be filled in with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptArgument {
+    pub name: String,
+    pub description: String,
+    pub required: bool,
+}
+
+/// A message in a rendered prompt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptMessage {
+    pub role: String,
+    pub content: ToolContent,
+}
+
+/// Result from getting a prompt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptResult {
+    pub description: String,
+    pub messages: Vec<PromptMessage>,
+}
+
+/// A prompt, the tool it drives, and which of the tool's arguments it takes
+struct PromptSpec {
+    name: &'static str,
+    description: &'static str,
+    tool: &'static str,
+    /// Tool argument names, and whether the prompt requires them
+    arguments: &'static [(&'static str, bool)],
+}
+
+const PROMPTS: &[PromptSpec] = &[
+    PromptSpec {
+        name: "init_repository",
+        description: "Set up Repository Manager for a project, choosing a mode and tools",
+        tool: "repo_init",
+        arguments: &[("name", true), ("mode", false), ("tools", false)],
+    },
+    PromptSpec {
+        name: "add_rule_guided",
+        description: "Draft a rule for the repository's agents and add it",
+        tool: "rule_add",
+        arguments: &[("id", true), ("content", false), ("tags", false)],
+    },
+];
+
+/// Get all available prompt definitions
+pub fn get_prompt_definitions() -> Vec<PromptDefinition> {
+    let tools = get_tool_definitions();
+    PROMPTS
+        .iter()
+        .map(|spec| PromptDefinition {
+            name: spec.name.to_string(),
+            description: spec.description.to_string(),
+            arguments: prompt_arguments(spec, &tools),
+        })
+        .collect()
+}
+
+/// Render the prompt `name` with `arguments` filled in
+///
+/// Fails with [`Error::UnknownPrompt`] for an unknown prompt and with
+/// [`Error::InvalidArgument`] if a required argument is missing.
+pub fn get_prompt(name: &str, arguments: &HashMap<String, String>) -> Result<PromptResult> {
+    let spec = PROMPTS
+        .iter()
+        .find(|spec| spec.name == name)
+        .ok_or_else(|| Error::UnknownPrompt(name.to_string()))?;
+    let value = |key: &str| {
+        arguments
+            .get(key)
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+    };
+    for (argument, required) in spec.arguments {
+        if *required && value(argument).is_none() {
+            return Err(Error::InvalidArgument(format!(
+                "prompt '{}' requires argument '{}'",
+                name, argument
+            )));
+        }
+    }
+
+    let text = match spec.name {
+        "init_repository" => init_repository(
+            value("name").unwrap_or_default(),
+            value("mode"),
+            value("tools"),
+        ),
+        _ => add_rule_guided(
+            value("id").unwrap_or_default(),
+            value("content"),
+            value("tags"),
+        ),
+    };
+    Ok(PromptResult {
+        description: spec.description.to_string(),
+        messages: vec![PromptMessage {
+            role: "user".to_string(),
+            content: ToolContent::Text { text },
+        }],
+    })
+}
+
+fn init_repository(name: &str, mode: Option<&str>, tools: Option<&str>) -> String {
+    let mut text = format!(
+        "Set up Repository Manager for the project \"{}\" with the `repo_init` tool.\n\n",
+        name
+    );
+    match mode {
+        Some(mode) => text.push_str(&format!("Use the \"{}\" mode.\n", mode)),
+        None => text.push_str(
+            "Ask me which mode to use: \"standard\" for a single checkout, or \
+             \"worktrees\" for one worktree per branch.\n",
+        ),
+    }
+    match tools {
+        Some(tools) => text.push_str(&format!(
+            "Enable these tools: {}.\n",
+            list(tools).join(", ")
+        )),
+        None => text.push_str(
+            "Look at the files in the project to suggest which AI tools to enable, \
+             and confirm them with me.\n",
+        ),
+    }
+    text.push_str("\nAfterwards, run `repo_check` and report any problems it finds.");
+    text
+}
+
+fn add_rule_guided(id: &str, content: Option<&str>, tags: Option<&str>) -> String {
+    let mut text = format!(
+        "Add a rule \"{}\" for the agents working in this repository with the `rule_add` tool.\n\n",
+        id
+    );
+    match content {
+        Some(content) => text.push_str(&format!(
+            "Start from this instruction and tighten it into clear, imperative guidance:\n\n{}\n",
+            content
+        )),
+        None => text.push_str(
+            "Ask me what the rule should cover, then draft the instruction as short, \
+             imperative guidance.\n",
+        ),
+    }
+    if let Some(tags) = tags {
+        text.push_str(&format!("Tag it with: {}.\n", list(tags).join(", ")));
+    }
+    text.push_str(
+        "\nCheck `rules_list` first so the rule doesn't repeat an existing one, \
+         and show me the draft before adding it.",
+    );
+    text
+}
+
+/// Split a comma-separated argument into its items
+fn list(value: &str) -> Vec<&str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Describe a prompt's arguments from its tool's input schema
+fn prompt_arguments(spec: &PromptSpec, tools: &[ToolDefinition]) -> Vec<PromptArgument> {
+    let schema = tools
+        .iter()
+        .find(|tool| tool.name == spec.tool)
+        .map(|tool| &tool.input_schema);
+    spec.arguments
+        .iter()
+        .map(|(name, required)| {
+            let property = schema.and_then(|s| s["properties"].get(*name));
+            PromptArgument {
+                name: name.to_string(),
+                description: property.map(describe).unwrap_or_default(),
+                required: *required,
+            }
+        })
+        .collect()
+}
+
+/// A schema property's description, with its allowed values or list hint
+fn describe(property: &Value) -> String {
+    let mut description = property["description"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    if let Some(values) = property["enum"].as_array() {
+        let values: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+        description.push_str(&format!(" (one of: {})", values.join(", ")));
+    } else if property["type"] == "array" {
+        description.push_str(" (comma-separated)");
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_prompt_arguments_come_from_tool_schemas() {
+        let prompts = get_prompt_definitions();
+        let init = prompts
+            .iter()
+            .find(|p| p.name == "init_repository")
+            .unwrap();
+        let mode = init.arguments.iter().find(|a| a.name == "mode").unwrap();
+        assert_eq!(
+            mode.description,
+            "Repository mode (one of: standard, worktrees)"
+        );
+        assert!(!mode.required);
+        let tools = init.arguments.iter().find(|a| a.name == "tools").unwrap();
+        assert_eq!(tools.description, "Tools to enable (comma-separated)");
+        assert!(
+            init.arguments
+                .iter()
+                .any(|a| a.name == "name" && a.required)
+        );
+
+        let rule = prompts
+            .iter()
+            .find(|p| p.name == "add_rule_guided")
+            .unwrap();
+        assert!(rule.arguments.iter().all(|a| !a.description.is_empty()));
+    }
+
+    #[test]
+    fn test_get_prompt_fills_in_arguments() {
+        let result = get_prompt(
+            "init_repository",
+            &args(&[("name", "demo"), ("tools", "claude, cursor")]),
+        )
+        .unwrap();
+        let ToolContent::Text { text } = &result.messages[0].content;
+        assert_eq!(result.messages[0].role, "user");
+        assert!(text.contains("\"demo\""));
+        assert!(text.contains("Enable these tools: claude, cursor."));
+        assert!(text.contains("Ask me which mode"));
+
+        let result = get_prompt("add_rule_guided", &args(&[("id", "naming")])).unwrap();
+        let ToolContent::Text { text } = &result.messages[0].content;
+        assert!(text.contains("`rule_add`") && text.contains("Ask me what the rule"));
+    }
+
+    #[test]
+    fn test_get_prompt_errors() {
+        assert!(matches!(
+            get_prompt("nope", &HashMap::new()),
+            Err(Error::UnknownPrompt(_))
+        ));
+        assert!(matches!(
+            get_prompt("add_rule_guided", &args(&[("id", " ")])),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! JSON-RPC 2.0 message structures for MCP communication.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -91,6 +93,7 @@ pub struct InitializeResult {
 pub struct ServerCapabilities {
     pub tools: Option<ToolsCapability>,
     pub resources: Option<ResourcesCapability>,
+    pub prompts: Option<PromptsCapability>,
 }
 
 #[derive(Debug, Serialize)]
@@ -106,6 +109,12 @@ pub struct ResourcesCapability {
     pub list_changed: Option<bool>,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptsCapability {
+    pub list_changed: Option<bool>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ServerInfo {
     pub name: String,
@@ -126,6 +135,14 @@ pub struct ReadResourceParams {
     pub uri: String,
 }
 
+/// Prompt get params
+#[derive(Debug, Deserialize)]
+pub struct GetPromptParams {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -244,6 +261,9 @@ mod tests {
                     subscribe: Some(false),
                     list_changed: Some(false),
                 }),
+                prompts: Some(PromptsCapability {
+                    list_changed: Some(false),
+                }),
             },
             server_info: ServerInfo {
                 name: "repo-mcp".to_string(),
@@ -284,6 +304,17 @@ mod tests {
         assert_eq!(params.uri, "repo://config");
     }
 
+    #[test]
+    fn test_get_prompt_params_deserialize() {
+        let json = r#"{"name": "init_repository", "arguments": {"name": "demo"}}"#;
+        let params: GetPromptParams = serde_json::from_str(json).unwrap();
+        assert_eq!(params.name, "init_repository");
+        assert_eq!(params.arguments["name"], "demo");
+
+        let params: GetPromptParams = serde_json::from_str(r#"{"name": "x"}"#).unwrap();
+        assert!(params.arguments.is_empty());
+    }
+
     #[test]
     fn test_response_success_format() {
         let response = JsonRpcResponse::success(
@@ -358,6 +389,7 @@ mod tests {
         let caps = ServerCapabilities {
             tools: None,
             resources: None,
+            prompts: None,
         };
         let json = serde_json::to_string(&caps).unwrap();
         // Null values should still serialize (they're not skipped)
@@ -8,6 +8,9 @@
 
 use std::path::Path;
 
+use repo_core::Ledger;
+use repo_meta::schema::{REDACTED, SecretRedaction};
+use serde_json::json;
 use tracing::warn;
 
 use crate::resources::ResourceContent;
@@ -32,6 +35,7 @@ pub async fn read_resource(root: &Path, uri: &str) -> Result<ResourceContent> {
         "repo://config" => read_config(root).await,
         "repo://state" => read_state(root).await,
         "repo://rules" => read_rules(root).await,
+        "repo://ledger" => read_ledger(root).await,
         _ => Err(Error::UnknownResource(uri.to_string())),
     }
 }
@@ -56,10 +60,12 @@ fn read_file_bounded(path: &std::path::Path) -> std::result::Result<String, std:
 }
 
 /// Read repository configuration from .repository/config.toml
+///
+/// Values of secret-looking keys are redacted (see [`redact_config`]).
 async fn read_config(root: &Path) -> Result<ResourceContent> {
     let config_path = root.join(".repository/config.toml");
     let text = match read_file_bounded(&config_path) {
-        Ok(content) => content,
+        Ok(content) => redact_config(&content),
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
             "# No configuration found\ntools = []\n\n[core]\nmode = \"worktrees\"\n".to_string()
         }
@@ -76,6 +82,56 @@ async fn read_config(root: &Path) -> Result<ResourceContent> {
     })
 }
 
+/// Hide the values of secret-looking keys in config.toml content.
+///
+/// Patterns listed in `core.secret_patterns` are redacted on top of the
+/// defaults. Content that doesn't parse is withheld, since its secrets
+/// can't be found.
+fn redact_config(content: &str) -> String {
+    let mut doc = match content.parse::<toml_edit::DocumentMut>() {
+        Ok(doc) => doc,
+        Err(e) => return format!("# Error parsing configuration: {}\n", e.message()),
+    };
+    let extra: Vec<String> = doc
+        .get("core")
+        .and_then(|core| core.get("secret_patterns"))
+        .and_then(|patterns| patterns.as_array())
+        .map(|patterns| {
+            patterns
+                .iter()
+                .filter_map(|p| p.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    let redaction = SecretRedaction::default().with_patterns(extra);
+    redact_toml(doc.as_table_mut(), &redaction);
+    doc.to_string()
+}
+
+/// Replace string values under secret keys, at any depth, keeping layout.
+fn redact_toml(table: &mut dyn toml_edit::TableLike, redaction: &SecretRedaction) {
+    for (key, item) in table.iter_mut() {
+        if let Some(value) = item.as_value_mut()
+            && value.is_str()
+            && redaction.is_secret(key.get())
+        {
+            let decor = value.decor().clone();
+            *value = REDACTED.into();
+            *value.decor_mut() = decor;
+        } else if let Some(table) = item.as_table_like_mut() {
+            redact_toml(table, redaction);
+        } else if let Some(tables) = item.as_array_of_tables_mut() {
+            for table in tables.iter_mut() {
+                redact_toml(table, redaction);
+            }
+        } else if let Some(array) = item.as_array_mut() {
+            for table in array.iter_mut().filter_map(|v| v.as_inline_table_mut()) {
+                redact_toml(table, redaction);
+            }
+        }
+    }
+}
+
 /// Read repository state from .repository/ledger.toml
 async fn read_state(root: &Path) -> Result<ResourceContent> {
     let ledger_path = root.join(".repository/ledger.toml");
@@ -97,6 +153,33 @@ async fn read_state(root: &Path) -> Result<ResourceContent> {
     })
 }
 
+/// Read the parsed ledger from .repository/ledger.toml as JSON
+///
+/// Unlike `repo://state`, the intents and their projections are returned as
+/// structured data, for inspecting drift. A missing ledger reads as an empty
+/// one.
+async fn read_ledger(root: &Path) -> Result<ResourceContent> {
+    let ledger_path = root.join(".repository/ledger.toml");
+    let ledger = match read_file_bounded(&ledger_path) {
+        Ok(content) => toml::from_str::<Ledger>(&content).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Ledger::new()),
+        Err(e) => Err(e.to_string()),
+    };
+    let value = match ledger {
+        Ok(ledger) => serde_json::to_value(&ledger)?,
+        Err(e) => {
+            warn!("Failed to read ledger at {}: {}", ledger_path.display(), e);
+            json!({ "error": format!("Error reading ledger: {}", e) })
+        }
+    };
+
+    Ok(ResourceContent {
+        uri: "repo://ledger".to_string(),
+        mime_type: "application/json".to_string(),
+        text: serde_json::to_string_pretty(&value)?,
+    })
+}
+
 /// Maximum number of rule files to read
 const MAX_RULE_FILES: usize = 500;
 
@@ -182,6 +265,36 @@ mod tests {
         assert!(result.text.contains("cursor"));
     }
 
+    #[tokio::test]
+    async fn test_read_config_resource_redacts_secrets() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join(".repository")).unwrap();
+        fs::write(
+            temp.path().join(".repository/config.toml"),
+            r#"tools = ["cursor"]
+
+[core]
+secret_patterns = ["*_PASSWORD"]
+
+[extensions.search.env]
+SEARCH_API_KEY = "sk-123"   # from the team vault
+DB_PASSWORD = "hunter2"
+LOG_LEVEL = "debug"
+"#,
+        )
+        .unwrap();
+
+        let result = read_resource(temp.path(), "repo://config").await.unwrap();
+        assert!(!result.text.contains("sk-123"));
+        assert!(!result.text.contains("hunter2"));
+        assert!(
+            result
+                .text
+                .contains("SEARCH_API_KEY = \"<redacted>\"   # from the team vault")
+        );
+        assert!(result.text.contains("LOG_LEVEL = \"debug\""));
+    }
+
     #[tokio::test]
     async fn test_read_config_resource_missing() {
         let temp = TempDir::new().unwrap();
@@ -218,6 +331,44 @@ mod tests {
         assert!(result.text.contains("No ledger"));
     }
 
+    #[tokio::test]
+    async fn test_read_ledger_resource() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join(".git")).unwrap();
+        fs::create_dir_all(temp.path().join(".repository")).unwrap();
+        fs::write(
+            temp.path().join(".repository/config.toml"),
+            "tools = [\"claude\"]\n\n[core]\nmode = \"standard\"\n",
+        )
+        .unwrap();
+        crate::handlers::handle_tool_call(temp.path(), "repo_sync", json!({}))
+            .await
+            .unwrap();
+
+        let result = read_resource(temp.path(), "repo://ledger").await.unwrap();
+        assert_eq!(result.uri, "repo://ledger");
+        assert_eq!(result.mime_type, "application/json");
+
+        let ledger: serde_json::Value = serde_json::from_str(&result.text).unwrap();
+        let intents = ledger["intents"].as_array().unwrap();
+        let projection = intents
+            .iter()
+            .flat_map(|intent| intent["projections"].as_array().unwrap())
+            .find(|p| p["tool"] == "claude")
+            .expect("sync should record a projection for claude");
+        assert_eq!(projection["file"], "CLAUDE.md");
+        assert!(projection["kind"]["backend"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_read_ledger_resource_missing() {
+        let temp = TempDir::new().unwrap();
+
+        let result = read_resource(temp.path(), "repo://ledger").await.unwrap();
+        let ledger: serde_json::Value = serde_json::from_str(&result.text).unwrap();
+        assert_eq!(ledger["intents"], json!([]));
+    }
+
     #[tokio::test]
     async fn test_read_rules_resource() {
         let temp = TempDir::new().unwrap();
@@ -44,6 +44,12 @@ pub fn get_resource_definitions() -> Vec<ResourceDefinition> {
             description: "Aggregated view of all active rules".to_string(),
             mime_type: "text/markdown".to_string(),
         },
+        ResourceDefinition {
+            uri: "repo://ledger".to_string(),
+            name: "Ledger".to_string(),
+            description: "Intents and their projections from .repository/ledger.toml".to_string(),
+            mime_type: "application/json".to_string(),
+        },
     ]
 }
 
@@ -54,12 +60,13 @@ mod tests {
     #[test]
     fn test_get_resource_definitions() {
         let resources = get_resource_definitions();
-        assert_eq!(resources.len(), 3);
+        assert_eq!(resources.len(), 4);
 
         let uris: Vec<&str> = resources.iter().map(|r| r.uri.as_str()).collect();
         assert!(uris.contains(&"repo://config"));
         assert!(uris.contains(&"repo://state"));
         assert!(uris.contains(&"repo://rules"));
+        assert!(uris.contains(&"repo://ledger"));
     }
 
     #[test]
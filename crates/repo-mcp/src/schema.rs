@@ -0,0 +1,291 @@
+//! JSON Schema support for tool arguments
+//!
+//! Schemas are generated from the `*Args` types with `schemars` and checked
+//! with a small validator covering the keywords those schemas use: `type`,
+//! `enum`, `const`, `minimum`/`maximum`, `anyOf`/`oneOf`, `required`,
+//! `properties`, `additionalProperties` and `items`.
+
+use schemars::JsonSchema;
+use schemars::generate::SchemaSettings;
+use serde_json::{Map, Value, json};
+
+/// Generate the `input_schema` for an argument type.
+///
+/// Subschemas are inlined and the meta-schema URI, title and type-level
+/// description are dropped; the tool description already covers the latter.
+pub(crate) fn input_schema<T: JsonSchema>() -> Value {
+    let generator = SchemaSettings::draft07()
+        .with(|s| {
+            s.inline_subschemas = true;
+            s.meta_schema = None;
+        })
+        .into_generator();
+    let mut schema = generator.into_root_schema_for::<T>().to_value();
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("title");
+        object.remove("description");
+
+        // Optional fields get `"default": null`, which contradicts their type
+        let properties = object.get_mut("properties").and_then(Value::as_object_mut);
+        for property in properties.into_iter().flat_map(|p| p.values_mut()) {
+            if let Some(property) = property.as_object_mut()
+                && property.get("default") == Some(&Value::Null)
+            {
+                property.remove("default");
+            }
+        }
+    }
+    schema
+}
+
+/// Schema for tools that take no arguments.
+pub(crate) fn no_arguments() -> Value {
+    json!({
+        "type": "object",
+        "properties": {}
+    })
+}
+
+/// Validate `value` against `schema`, describing the first violation found.
+///
+/// A `null` value is treated as an empty object, matching how missing
+/// `arguments` are handled on dispatch.
+pub(crate) fn validate(schema: &Value, value: &Value) -> Result<(), String> {
+    if value.is_null() {
+        return check(schema, &Value::Object(Map::new()), "");
+    }
+    check(schema, value, "")
+}
+
+fn check(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        // `true` schemas accept anything
+        return Ok(());
+    };
+
+    if let Some(expected) = schema.get("type")
+        && !type_matches(expected, value)
+    {
+        return Err(format!(
+            "{} must be of type {}, got {}",
+            describe(path),
+            expected_types(expected),
+            type_name(value)
+        ));
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+        return Err(format!(
+            "{} must be one of {}, got {}",
+            describe(path),
+            allowed.join(", "),
+            value
+        ));
+    }
+
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        return Err(format!("{} must be {}", describe(path), expected));
+    }
+
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(Value::as_f64)
+            && n < min
+        {
+            return Err(format!("{} must be at least {}", describe(path), min));
+        }
+        if let Some(max) = schema.get("maximum").and_then(Value::as_f64)
+            && n > max
+        {
+            return Err(format!("{} must be at most {}", describe(path), max));
+        }
+    }
+
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(options) = schema.get(keyword).and_then(Value::as_array)
+            && !options.iter().any(|s| check(s, value, path).is_ok())
+        {
+            return Err(format!(
+                "{} does not match any allowed form",
+                describe(path)
+            ));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    return Err(format!("missing required property `{}`", join(path, name)));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+        for (name, item) in object {
+            match properties.and_then(|p| p.get(name)) {
+                Some(property) => check(property, item, &join(path, name))?,
+                None if closed => {
+                    return Err(format!("unknown property `{}`", join(path, name)));
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            check(item_schema, item, &format!("{}[{}]", path, i))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn type_matches(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => is_type(name, value),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|name| is_type(name, value)),
+        _ => true,
+    }
+}
+
+fn is_type(name: &str, value: &Value) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => true,
+    }
+}
+
+fn expected_types(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        other => other.as_str().unwrap_or("any").to_string(),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn describe(path: &str) -> String {
+    if path.is_empty() {
+        "arguments".to_string()
+    } else {
+        format!("`{}`", path)
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::RepoInitArgs;
+
+    #[test]
+    fn test_generated_schema_shape() {
+        let schema = input_schema::<RepoInitArgs>();
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["name"]));
+        assert_eq!(schema["properties"]["name"]["type"], "string");
+        assert_eq!(schema["properties"]["name"]["description"], "Project name");
+        assert_eq!(
+            schema["properties"]["mode"]["enum"],
+            json!(["standard", "worktrees"])
+        );
+        assert_eq!(schema["properties"]["tools"]["items"]["type"], "string");
+        assert!(schema["properties"]["mode"].get("default").is_none());
+        assert!(schema.get("$schema").is_none());
+        assert!(schema.get("title").is_none());
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_arguments() {
+        let schema = input_schema::<RepoInitArgs>();
+        let args = json!({"name": "demo", "mode": "worktrees", "tools": ["claude"]});
+        assert_eq!(validate(&schema, &args), Ok(()));
+        assert_eq!(validate(&no_arguments(), &Value::Null), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_reports_violations() {
+        let schema = input_schema::<RepoInitArgs>();
+        let cases = [
+            (json!({}), "missing required property `name`"),
+            (json!(null), "missing required property `name`"),
+            (
+                json!({"name": 7}),
+                "`name` must be of type string, got number",
+            ),
+            (
+                json!({"name": "x", "mode": "bare"}),
+                "`mode` must be one of \"standard\", \"worktrees\", got \"bare\"",
+            ),
+            (
+                json!({"name": "x", "tools": ["a", 1]}),
+                "`tools[1]` must be of type string, got number",
+            ),
+            (json!([]), "arguments must be of type object, got array"),
+        ];
+        for (args, expected) in cases {
+            assert_eq!(
+                validate(&schema, &args),
+                Err(expected.to_string()),
+                "{}",
+                args
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_combinators_and_bounds() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "limit": {"type": "integer", "minimum": 1, "maximum": 10},
+                "target": {"anyOf": [{"type": "string"}, {"type": "null"}]}
+            },
+            "additionalProperties": false
+        });
+        assert!(validate(&schema, &json!({"limit": 5, "target": null})).is_ok());
+        assert!(validate(&schema, &json!({"limit": 0})).is_err());
+        assert!(validate(&schema, &json!({"limit": 1.5})).is_err());
+        assert!(validate(&schema, &json!({"target": 3})).is_err());
+        assert_eq!(
+            validate(&schema, &json!({"extra": true})),
+            Err("unknown property `extra`".to_string())
+        );
+    }
+}
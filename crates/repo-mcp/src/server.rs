@@ -6,16 +6,22 @@
 use std::io::{BufRead, Write};
 use std::path::PathBuf;
 
+use repo_fs::RobustnessConfig;
 use serde_json::{Value, json};
 
 use crate::handlers::handle_tool_call;
+use crate::prompts::{get_prompt, get_prompt_definitions};
 use crate::protocol::{
-    InitializeResult, JsonRpcRequest, JsonRpcResponse, ReadResourceParams, ResourcesCapability,
-    ServerCapabilities, ServerInfo, ToolCallParams, ToolsCapability,
+    GetPromptParams, InitializeResult, JsonRpcRequest, JsonRpcResponse, PromptsCapability,
+    ReadResourceParams, ResourcesCapability, ServerCapabilities, ServerInfo, ToolCallParams,
+    ToolsCapability,
 };
 use crate::resource_handlers::read_resource;
 use crate::resources::{ResourceDefinition, get_resource_definitions};
-use crate::tools::{ToolDefinition, ToolResult, get_tool_definitions};
+use crate::timeout::{ToolTimeouts, WriteGate, run_with_timeout};
+use crate::tools::{
+    ToolDefinition, ToolResult, get_tool_definitions, is_read_only_tool, validate_tool_arguments,
+};
 use crate::{Error, Result};
 
 /// MCP Server for Repository Manager
@@ -49,6 +55,15 @@ pub struct RepoMcpServer {
 
     /// Available MCP resources
     resources: Vec<ResourceDefinition>,
+
+    /// Per-tool call timeouts
+    timeouts: ToolTimeouts,
+
+    /// Serializes tool calls that write to the repository
+    writes: WriteGate,
+
+    /// Robustness settings tool calls write files with
+    robustness: RobustnessConfig,
 }
 
 impl RepoMcpServer {
@@ -63,9 +78,18 @@ impl RepoMcpServer {
             initialized: false,
             tools: Vec::new(),
             resources: Vec::new(),
+            timeouts: ToolTimeouts::default(),
+            writes: WriteGate::new(),
+            robustness: RobustnessConfig::default(),
         }
     }
 
+    /// Set the timeouts applied to tool calls
+    pub fn with_timeouts(mut self, timeouts: ToolTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
     /// Initialize the server
     ///
     /// This loads the repository configuration and prepares
@@ -73,22 +97,33 @@ impl RepoMcpServer {
     pub async fn initialize(&mut self) -> Result<()> {
         tracing::info!(root = ?self.root, "Initializing MCP server");
 
+        // Resolve the repository the same way the CLI does
+        let context = repo_core::context::detect(&self.root);
+        tracing::debug!(trail = %context.explain(), "Detected repository context");
+
         // Validate that .repository/ directory exists
-        let repo_dir = self.root.join(".repository");
-        if !repo_dir.is_dir() {
+        if !context.is_repo() {
             return Err(Error::InvalidRepository(
                 "Not a repository-manager project: .repository/ directory not found. Run `repo init` first.".to_string(),
             ));
         }
 
         // Validate that .repository/config.toml exists
-        let config_path = repo_dir.join("config.toml");
-        if !config_path.is_file() {
+        if context.is_partial() {
             return Err(Error::InvalidRepository(
                 "Missing .repository/config.toml. Repository structure is incomplete. Run `repo init` to create it.".to_string(),
             ));
         }
 
+        // Honor write verification configured for this repository
+        if let Some(config_path) = &context.config_path
+            && let Ok(content) = std::fs::read_to_string(config_path.to_native())
+            && repo_core::Manifest::parse(&content).is_ok_and(|m| m.core.verify_writes)
+        {
+            self.robustness = self.robustness.with_verify_writes(true);
+            tracing::info!("Write verification enabled");
+        }
+
         // Load tool and resource definitions
         self.tools = get_tool_definitions();
         self.resources = get_resource_definitions();
@@ -161,6 +196,8 @@ impl RepoMcpServer {
                 self.handle_resources_read(request.id, request.params)
                     .await?
             }
+            "prompts/list" => self.handle_prompts_list(request.id).await?,
+            "prompts/get" => self.handle_prompts_get(request.id, request.params).await?,
             _ => JsonRpcResponse::error(
                 request.id,
                 -32601,
@@ -185,6 +222,9 @@ impl RepoMcpServer {
                     subscribe: Some(false),
                     list_changed: Some(false),
                 }),
+                prompts: Some(PromptsCapability {
+                    list_changed: Some(false),
+                }),
             },
             server_info: ServerInfo {
                 name: "repo-mcp".to_string(),
@@ -221,11 +261,29 @@ impl RepoMcpServer {
 
     /// Handle tools/call request
     ///
-    /// Executes the requested tool and returns the result.
+    /// Validates the arguments against the tool's input schema, then executes
+    /// the tool and returns the result. Schema violations and tools that run
+    /// past their configured timeout are reported as error results.
     async fn handle_tools_call(&self, id: Option<Value>, params: Value) -> Result<JsonRpcResponse> {
         let tool_params: ToolCallParams = serde_json::from_value(params)?;
 
-        match handle_tool_call(&self.root, &tool_params.name, tool_params.arguments).await {
+        let outcome = match validate_tool_arguments(&tool_params.name, &tool_params.arguments) {
+            Ok(()) => {
+                let limit = self.timeouts.for_tool(&tool_params.name);
+                let root = self.root.clone();
+                let name = tool_params.name.clone();
+                let gate = (!is_read_only_tool(&name)).then_some(&self.writes);
+                let robustness = self.robustness;
+                run_with_timeout(&tool_params.name, limit, gate, move || async move {
+                    let _robustness = robustness.enter();
+                    handle_tool_call(&root, &name, tool_params.arguments).await
+                })
+                .await
+            }
+            Err(e) => Err(e),
+        };
+
+        match outcome {
             Ok(result) => {
                 // Convert Value result to ToolResult format
                 let tool_result = ToolResult::text(serde_json::to_string_pretty(&result)?);
@@ -298,6 +356,38 @@ impl RepoMcpServer {
         }
     }
 
+    /// Handle prompts/list request
+    ///
+    /// Returns the list of available prompts.
+    async fn handle_prompts_list(&self, id: Option<Value>) -> Result<JsonRpcResponse> {
+        let prompts = get_prompt_definitions();
+        Ok(JsonRpcResponse::success(
+            id,
+            json!({ "prompts": serde_json::to_value(prompts)? }),
+        ))
+    }
+
+    /// Handle prompts/get request
+    ///
+    /// Renders the requested prompt with the given arguments. Unknown
+    /// prompts and missing required arguments are invalid params.
+    async fn handle_prompts_get(
+        &self,
+        id: Option<Value>,
+        params: Value,
+    ) -> Result<JsonRpcResponse> {
+        let get_params: GetPromptParams = serde_json::from_value(params)?;
+
+        match get_prompt(&get_params.name, &get_params.arguments) {
+            Ok(result) => Ok(JsonRpcResponse::success(id, serde_json::to_value(result)?)),
+            Err(e) => Ok(JsonRpcResponse::error(
+                id,
+                -32602,
+                format!("Prompt error: {}", e),
+            )),
+        }
+    }
+
     /// Get the repository root path
     pub fn root(&self) -> &PathBuf {
         &self.root
@@ -317,6 +407,11 @@ impl RepoMcpServer {
     pub fn resources(&self) -> &[ResourceDefinition] {
         &self.resources
     }
+
+    /// Get the configured tool timeouts
+    pub fn timeouts(&self) -> &ToolTimeouts {
+        &self.timeouts
+    }
 }
 
 #[cfg(test)]
@@ -355,6 +450,24 @@ mod tests {
         assert!(server.resources().is_empty());
     }
 
+    #[test]
+    fn server_with_timeouts() {
+        use std::time::Duration;
+
+        let server = RepoMcpServer::new(PathBuf::from("/tmp/test")).with_timeouts(
+            ToolTimeouts::new(Duration::from_secs(10))
+                .with_tool("git_pull", Duration::from_secs(1)),
+        );
+        assert_eq!(
+            server.timeouts().for_tool("git_pull"),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            server.timeouts().for_tool("repo_check"),
+            Duration::from_secs(10)
+        );
+    }
+
     #[tokio::test]
     async fn server_initialization() {
         let temp = create_valid_repo_dir();
@@ -391,13 +504,14 @@ mod tests {
 
         // Should have loaded resources
         assert!(!server.resources().is_empty());
-        assert_eq!(server.resources().len(), 3);
+        assert_eq!(server.resources().len(), 4);
 
         // Verify expected resources
         let resource_uris: Vec<&str> = server.resources().iter().map(|r| r.uri.as_str()).collect();
         assert!(resource_uris.contains(&"repo://config"));
         assert!(resource_uris.contains(&"repo://state"));
         assert!(resource_uris.contains(&"repo://rules"));
+        assert!(resource_uris.contains(&"repo://ledger"));
     }
 
     #[tokio::test]
@@ -484,6 +598,25 @@ mod tests {
         assert!(response.contains("unknown tool"));
     }
 
+    #[tokio::test]
+    async fn test_handle_tools_call_rejects_invalid_arguments() {
+        let temp = TempDir::new().unwrap();
+        let server = RepoMcpServer::new(PathBuf::from(temp.path()));
+
+        let request = r#"{"jsonrpc":"2.0","id":6,"method":"tools/call","params":{"name":"repo_init","arguments":{"mode":"standard"}}}"#;
+
+        let response: Value =
+            serde_json::from_str(&server.handle_message(request).await.unwrap()).unwrap();
+        assert_eq!(response["result"]["is_error"], true);
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert_eq!(
+            text,
+            "invalid argument: repo_init: missing required property `name`"
+        );
+        // Rejected before dispatch, so nothing was initialized
+        assert!(!temp.path().join(".repository").exists());
+    }
+
     #[tokio::test]
     async fn test_handle_resources_read() {
         let (_temp, server) = setup_initialized_server().await;
@@ -0,0 +1,308 @@
+//! Per-tool execution timeouts
+//!
+//! Tool handlers perform blocking I/O (libgit2, filesystem, preset checks),
+//! so a stalled handler would otherwise hang the whole stdio loop. Each call
+//! runs on the blocking thread pool and the server stops waiting once the
+//! tool's limit elapses, answering with [`Error::Timeout`].
+//!
+//! Each call runs under its own [`CancelToken`], which is cancelled when
+//! the call times out: subprocesses the handler started (git, hooks,
+//! preset tools) are killed, libgit2 remote operations abort at their next
+//! credential request or progress report, and the handler stops at its
+//! next cancellation check. Tools that
+//! write to the repository pass a [`WriteGate`], which the handler holds
+//! until it has actually stopped, so a later write never runs alongside a
+//! cancelled one that is still unwinding.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use repo_core::CancelToken;
+use tokio::sync::Mutex;
+
+use crate::{Error, Result};
+
+/// Default limit applied to tools without an explicit override.
+pub const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Timeout configuration for tool calls.
+#[derive(Debug, Clone)]
+pub struct ToolTimeouts {
+    default: Duration,
+    overrides: HashMap<String, Duration>,
+}
+
+impl Default for ToolTimeouts {
+    fn default() -> Self {
+        Self::new(DEFAULT_TOOL_TIMEOUT)
+    }
+}
+
+impl ToolTimeouts {
+    /// Create a configuration with the given default limit.
+    pub fn new(default: Duration) -> Self {
+        Self {
+            default,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Override the limit for a single tool.
+    pub fn with_tool(mut self, tool: impl Into<String>, limit: Duration) -> Self {
+        self.overrides.insert(tool.into(), limit);
+        self
+    }
+
+    /// The limit that applies to `tool`.
+    pub fn for_tool(&self, tool: &str) -> Duration {
+        self.overrides.get(tool).copied().unwrap_or(self.default)
+    }
+}
+
+/// Lets one writing tool call run at a time, cancelled ones included.
+#[derive(Debug, Clone, Default)]
+pub struct WriteGate(Arc<Mutex<()>>);
+
+impl WriteGate {
+    /// Create an open gate.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Run a tool handler, giving up after `limit`.
+///
+/// The future is built and driven on a blocking-pool thread so that
+/// synchronous work inside the handler cannot starve the timer. The
+/// handler runs under a [`CancelToken`] that is cancelled if `limit`
+/// elapses. With a `gate`, the call first waits for earlier writers,
+/// cancelled ones included, and that wait counts towards `limit`.
+pub async fn run_with_timeout<F, Fut, T>(
+    tool: &str,
+    limit: Duration,
+    gate: Option<&WriteGate>,
+    handler: F,
+) -> Result<T>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T>>,
+    T: Send + 'static,
+{
+    let runtime = tokio::runtime::Handle::current();
+    let started = Instant::now();
+    let timed_out = || {
+        let elapsed = started.elapsed();
+        tracing::warn!(tool, ?elapsed, "Tool call timed out");
+        Err(Error::Timeout {
+            tool: tool.to_string(),
+            elapsed,
+        })
+    };
+
+    let permit = match gate {
+        Some(gate) => match tokio::time::timeout(limit, gate.0.clone().lock_owned()).await {
+            Ok(permit) => Some(permit),
+            Err(_) => return timed_out(),
+        },
+        None => None,
+    };
+    let token = CancelToken::new();
+    let cancel = token.clone();
+    // The permit moves into the task, so the gate stays closed until a
+    // cancelled handler has stopped
+    let task = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        let _scope = token.enter();
+        runtime.block_on(handler())
+    });
+
+    match tokio::time::timeout(limit.saturating_sub(started.elapsed()), task).await {
+        Ok(Ok(result)) => result,
+        // Blocking tasks cannot be aborted, so a join error is a panic
+        Ok(Err(join_error)) => std::panic::resume_unwind(join_error.into_panic()),
+        Err(_) => {
+            cancel.cancel();
+            timed_out()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_tool_uses_override_then_default() {
+        let timeouts = ToolTimeouts::new(Duration::from_secs(5))
+            .with_tool("git_pull", Duration::from_secs(60));
+
+        assert_eq!(timeouts.for_tool("git_pull"), Duration::from_secs(60));
+        assert_eq!(timeouts.for_tool("repo_check"), Duration::from_secs(5));
+        assert_eq!(
+            ToolTimeouts::default().for_tool("anything"),
+            DEFAULT_TOOL_TIMEOUT
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fast_handler_returns_result() {
+        let result =
+            run_with_timeout("fast", Duration::from_secs(5), None, || async { Ok(42) }).await;
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[tokio::test]
+    async fn test_slow_handler_times_out_within_bound() {
+        let limit = Duration::from_millis(100);
+        let started = Instant::now();
+
+        let result: Result<()> = run_with_timeout("slow", limit, None, || async {
+            std::thread::sleep(Duration::from_millis(1500));
+            Ok(())
+        })
+        .await;
+
+        let waited = started.elapsed();
+        match result {
+            Err(Error::Timeout { tool, elapsed }) => {
+                assert_eq!(tool, "slow");
+                assert!(elapsed >= limit);
+            }
+            other => panic!("Expected timeout, got {:?}", other),
+        }
+        assert!(waited < Duration::from_secs(1), "waited {:?}", waited);
+    }
+
+    #[tokio::test]
+    async fn test_handler_error_is_propagated() {
+        let result: Result<()> = run_with_timeout("bad", Duration::from_secs(5), None, || async {
+            Err(Error::InvalidArgument("nope".to_string()))
+        })
+        .await;
+        assert!(matches!(result, Err(Error::InvalidArgument(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_timed_out_handler_is_cancelled() {
+        let gate = WriteGate::new();
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let result: Result<()> = run_with_timeout(
+            "slow",
+            Duration::from_millis(100),
+            Some(&gate),
+            || async move {
+                let mut sleep = std::process::Command::new("sleep");
+                let output = repo_core::cancel::output(sleep.arg("30"));
+                sender.send(output.map(|_| ())).unwrap();
+                Ok(())
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(Error::Timeout { .. })));
+
+        // The child is killed rather than left to run for 30 seconds, so
+        // the next writer gets the gate well within its limit
+        let started = Instant::now();
+        let written = run_with_timeout("write", Duration::from_secs(5), Some(&gate), || async {
+            Ok(())
+        })
+        .await;
+        assert!(written.is_ok());
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(matches!(
+            receiver.recv_timeout(Duration::from_secs(1)).unwrap(),
+            Err(repo_core::Error::Cancelled)
+        ));
+    }
+
+    /// A pull stuck on a remote that keeps asking for credentials stops
+    /// once it times out, so it does not hold the gate against later writes.
+    #[tokio::test]
+    async fn test_timed_out_pull_releases_gate() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/repo.git", listener.local_addr().unwrap());
+        repo.remote("origin", &url).unwrap();
+        drop(repo);
+
+        // Every request is answered slowly with a demand for credentials
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap_or(0) > 0 && line != "\r\n" {
+                    line.clear();
+                }
+                std::thread::sleep(Duration::from_millis(200));
+                let _ = stream.write_all(
+                    b"HTTP/1.1 401 Unauthorized\r\n\
+                      WWW-Authenticate: Basic realm=\"repo\"\r\n\
+                      Content-Length: 0\r\n\r\n",
+                );
+            }
+        });
+
+        let gate = WriteGate::new();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let root = temp.path().to_path_buf();
+        let result: Result<()> = run_with_timeout(
+            "git_pull",
+            Duration::from_millis(100),
+            Some(&gate),
+            || async move {
+                let repo = git2::Repository::open(&root).unwrap();
+                let credentials = repo_git::CredentialOptions {
+                    token: Some("secret".to_string()),
+                    ..Default::default()
+                };
+                let pulled = repo_git::pull(
+                    &repo,
+                    None,
+                    Some("main"),
+                    || Ok("main".to_string()),
+                    None,
+                    &credentials,
+                );
+                sender.send(pulled).unwrap();
+                Ok(())
+            },
+        )
+        .await;
+        assert!(matches!(result, Err(Error::Timeout { .. })));
+
+        let written = run_with_timeout("write", Duration::from_secs(5), Some(&gate), || async {
+            Ok(())
+        })
+        .await;
+        assert!(written.is_ok());
+        assert!(matches!(
+            receiver.recv_timeout(Duration::from_secs(1)).unwrap(),
+            Err(repo_git::Error::Cancelled)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_waiting_for_gate_counts_towards_limit() {
+        let gate = WriteGate::new();
+        let _held = gate.0.clone().lock_owned().await;
+        let started = Instant::now();
+
+        let result: Result<()> =
+            run_with_timeout("write", Duration::from_millis(100), Some(&gate), || async {
+                panic!("handler must not run while the gate is held")
+            })
+            .await;
+
+        assert!(matches!(result, Err(Error::Timeout { .. })));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -14,16 +14,21 @@
 //! ## Branch Management
 //! - `branch_create` - Create a new branch (with worktree in worktrees mode)
 //! - `branch_delete` - Remove a branch and its worktree
+//! - `branch_checkout` - Switch to a branch
+//! - `branch_rename` - Rename a branch (and move its worktree)
 //! - `branch_list` - List active branches
+//! - `branch_prune` - Remove branches merged into the main branch
 //!
 //! ## Git Primitives (Not Yet Implemented)
 //! - `git_push` - Push current branch (returns NotImplemented)
 //! - `git_pull` - Pull updates (returns NotImplemented)
-//! - `git_merge` - Merge target branch (returns NotImplemented)
+//! - `git_fetch` - Fetch from a remote without touching the checkout
+//! - `git_merge` - Merge target branch
 //!
 //! ## Configuration Management
 //! - `tool_add` - Enable a tool
 //! - `tool_remove` - Disable a tool
+//! - `rules_list` - List rules with their tags and synced tools
 //! - `rule_add` - Add a custom rule
 //! - `rule_remove` - Delete a rule
 //!
@@ -33,13 +38,44 @@
 //! - `preset_remove` - Remove a preset from configuration
 //!
 //! ## Extension Management
-//! - `extension_install` - Install an extension from a URL or path
+//! - `extension_install` - Install an extension from a git URL
 //! - `extension_add` - Add a known extension by name
 //! - `extension_init` - Initialize a new extension scaffold
 //! - `extension_remove` - Remove an installed extension
 //! - `extension_list` - List installed and known extensions
+//! - `extension_verify` - Verify installed extensions against the lock file
 //!
+//! # Argument Schemas
+//!
+//! Each tool's `input_schema` is generated from its `*Args` type, and
+//! [`validate_tool_arguments`] checks incoming arguments against it before
+//! dispatch.
+use std::sync::LazyLock;
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::args::{
+    BranchCheckoutArgs, BranchCreateArgs, BranchDeleteArgs, BranchPruneArgs, BranchRenameArgs,
+    ExtensionAddArgs, ExtensionInitArgs, ExtensionInstallArgs, ExtensionRemoveArgs, GitFetchArgs,
+    GitMergeArgs, GitPullArgs, GitPushArgs, PresetAddArgs, PresetRemoveArgs, RepoInitArgs,
+    RuleAddArgs, RuleRemoveArgs, SyncArgs, ToolAddArgs, ToolRemoveArgs,
+};
+use crate::schema::{input_schema, no_arguments, validate};
+use crate::{Error, Result};
+
+/// Tools that only read the repository; every other tool may write to it.
+const READ_ONLY_TOOLS: &[&str] = &[
+    "repo_check",
+    "branch_list",
+    "rules_list",
+    "preset_list",
+    "extension_list",
+    "extension_verify",
+];
+
+/// Tool definitions used for argument validation, built once.
+static TOOL_DEFINITIONS: LazyLock<Vec<ToolDefinition>> = LazyLock::new(get_tool_definitions);
 
 /// Tool definition for MCP protocol
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,322 +130,172 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
         ToolDefinition {
             name: "repo_init".to_string(),
             description: "Initialize a new repository configuration".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Project name"
-                    },
-                    "mode": {
-                        "type": "string",
-                        "enum": ["standard", "worktrees"],
-                        "description": "Repository mode"
-                    },
-                    "tools": {
-                        "type": "array",
-                        "items": { "type": "string" },
-                        "description": "Tools to enable"
-                    },
-                    "extensions": {
-                        "type": "array",
-                        "items": { "type": "string" },
-                        "description": "Extensions to enable (by name or source URL)"
-                    }
-                },
-                "required": ["name"]
-            }),
+            input_schema: input_schema::<RepoInitArgs>(),
         },
         ToolDefinition {
             name: "repo_check".to_string(),
             description: "Check configuration validity and consistency".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {}
-            }),
+            input_schema: no_arguments(),
         },
         ToolDefinition {
             name: "repo_sync".to_string(),
             description: "Regenerate tool configurations from rules".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "dry_run": {
-                        "type": "boolean",
-                        "description": "Preview changes without applying"
-                    }
-                }
-            }),
+            input_schema: input_schema::<SyncArgs>(),
         },
         ToolDefinition {
             name: "repo_fix".to_string(),
             description: "Repair configuration inconsistencies".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "dry_run": {
-                        "type": "boolean",
-                        "description": "Preview fixes without applying"
-                    }
-                }
-            }),
+            input_schema: input_schema::<SyncArgs>(),
         },
         // Branch Management
         ToolDefinition {
             name: "branch_create".to_string(),
             description: "Create a new branch (with worktree in worktrees mode)".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Branch name"
-                    },
-                    "base": {
-                        "type": "string",
-                        "description": "Base branch (defaults to main)"
-                    }
-                },
-                "required": ["name"]
-            }),
+            input_schema: input_schema::<BranchCreateArgs>(),
         },
         ToolDefinition {
             name: "branch_delete".to_string(),
             description: "Remove a branch and its worktree".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Branch name to delete"
-                    }
-                },
-                "required": ["name"]
-            }),
+            input_schema: input_schema::<BranchDeleteArgs>(),
+        },
+        ToolDefinition {
+            name: "branch_checkout".to_string(),
+            description: "Switch to a branch, returning the directory to work in".to_string(),
+            input_schema: input_schema::<BranchCheckoutArgs>(),
+        },
+        ToolDefinition {
+            name: "branch_rename".to_string(),
+            description: "Rename a branch (and move its worktree in worktrees mode)".to_string(),
+            input_schema: input_schema::<BranchRenameArgs>(),
         },
         ToolDefinition {
             name: "branch_list".to_string(),
             description: "List active branches".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {}
-            }),
+            input_schema: no_arguments(),
+        },
+        ToolDefinition {
+            name: "branch_prune".to_string(),
+            description: "Remove branches (and their worktrees) merged into the main branch"
+                .to_string(),
+            input_schema: input_schema::<BranchPruneArgs>(),
         },
         // Git Primitives (not yet implemented - will return NotImplemented error)
         ToolDefinition {
             name: "git_push".to_string(),
             description: "[Not implemented] Push current branch to remote".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "remote": {
-                        "type": "string",
-                        "description": "Remote name (defaults to origin)"
-                    },
-                    "branch": {
-                        "type": "string",
-                        "description": "Branch to push (defaults to current)"
-                    }
-                }
-            }),
+            input_schema: input_schema::<GitPushArgs>(),
         },
         ToolDefinition {
             name: "git_pull".to_string(),
             description: "[Not implemented] Pull updates from remote".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "remote": {
-                        "type": "string",
-                        "description": "Remote name (defaults to origin)"
-                    },
-                    "branch": {
-                        "type": "string",
-                        "description": "Branch to pull (defaults to current)"
-                    }
-                }
-            }),
+            input_schema: input_schema::<GitPullArgs>(),
+        },
+        ToolDefinition {
+            name: "git_fetch".to_string(),
+            description: "Fetch from remote without changing the checkout, optionally pruning deleted branches".to_string(),
+            input_schema: input_schema::<GitFetchArgs>(),
         },
         ToolDefinition {
             name: "git_merge".to_string(),
-            description: "[Not implemented] Merge target branch into current branch".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "source": {
-                        "type": "string",
-                        "description": "Branch to merge from"
-                    }
-                },
-                "required": ["source"]
-            }),
+            description: "Merge target branch into current branch, reporting conflicted files".to_string(),
+            input_schema: input_schema::<GitMergeArgs>(),
         },
         // Configuration Management
         ToolDefinition {
             name: "tool_add".to_string(),
             description: "Enable a tool for this repository".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Tool name (e.g., vscode, cursor, claude)"
-                    }
-                },
-                "required": ["name"]
-            }),
+            input_schema: input_schema::<ToolAddArgs>(),
         },
         ToolDefinition {
             name: "tool_remove".to_string(),
             description: "Disable a tool for this repository".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Tool name to remove"
-                    }
-                },
-                "required": ["name"]
-            }),
+            input_schema: input_schema::<ToolRemoveArgs>(),
+        },
+        ToolDefinition {
+            name: "rules_list".to_string(),
+            description: "List rules with their tags, a content preview, and the tools they are synced to"
+                .to_string(),
+            input_schema: no_arguments(),
         },
         ToolDefinition {
             name: "rule_add".to_string(),
             description: "Add a custom rule to the repository".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "id": {
-                        "type": "string",
-                        "description": "Rule identifier"
-                    },
-                    "content": {
-                        "type": "string",
-                        "description": "Rule content/instructions"
-                    }
-                },
-                "required": ["id", "content"]
-            }),
+            input_schema: input_schema::<RuleAddArgs>(),
         },
         ToolDefinition {
             name: "rule_remove".to_string(),
             description: "Delete a rule from the repository".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "id": {
-                        "type": "string",
-                        "description": "Rule ID to remove"
-                    }
-                },
-                "required": ["id"]
-            }),
+            input_schema: input_schema::<RuleRemoveArgs>(),
         },
         // Preset Management
         ToolDefinition {
             name: "preset_list".to_string(),
             description: "List configured presets and available preset types".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {}
-            }),
+            input_schema: no_arguments(),
         },
         ToolDefinition {
             name: "preset_add".to_string(),
             description: "Add a preset to the repository configuration".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Preset name (e.g., env:python, env:node, env:rust)"
-                    }
-                },
-                "required": ["name"]
-            }),
+            input_schema: input_schema::<PresetAddArgs>(),
         },
         ToolDefinition {
             name: "preset_remove".to_string(),
             description: "Remove a preset from the repository configuration".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Preset name to remove"
-                    }
-                },
-                "required": ["name"]
-            }),
+            input_schema: input_schema::<PresetRemoveArgs>(),
         },
         // Extension Management
         ToolDefinition {
             name: "extension_install".to_string(),
-            description: "Install an extension from a URL or local path".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "source": {
-                        "type": "string",
-                        "description": "Source URL or local path for the extension"
-                    }
-                },
-                "required": ["source"]
-            }),
+            description: "Install an extension from a git URL".to_string(),
+            input_schema: input_schema::<ExtensionInstallArgs>(),
         },
         ToolDefinition {
             name: "extension_add".to_string(),
             description: "Add a known extension by name from the registry".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Name of the known extension to add"
-                    }
-                },
-                "required": ["name"]
-            }),
+            input_schema: input_schema::<ExtensionAddArgs>(),
         },
         ToolDefinition {
             name: "extension_init".to_string(),
             description: "Initialize a new extension scaffold".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Name for the new extension"
-                    }
-                },
-                "required": ["name"]
-            }),
+            input_schema: input_schema::<ExtensionInitArgs>(),
         },
         ToolDefinition {
             name: "extension_remove".to_string(),
             description: "Remove an installed extension".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "name": {
-                        "type": "string",
-                        "description": "Name of the extension to remove"
-                    }
-                },
-                "required": ["name"]
-            }),
+            input_schema: input_schema::<ExtensionRemoveArgs>(),
         },
         ToolDefinition {
             name: "extension_list".to_string(),
             description: "List installed and known extensions".to_string(),
-            input_schema: serde_json::json!({
-                "type": "object",
-                "properties": {}
-            }),
+            input_schema: no_arguments(),
+        },
+        ToolDefinition {
+            name: "extension_verify".to_string(),
+            description: "Verify installed extensions against the lock file (directory, version, commit, entry points, unlocked installs)".to_string(),
+            input_schema: no_arguments(),
         },
     ]
 }
 
+/// Whether the tool only reads the repository.
+///
+/// Unknown tools count as writers.
+pub fn is_read_only_tool(tool_name: &str) -> bool {
+    READ_ONLY_TOOLS.contains(&tool_name)
+}
+
+/// Validate tool arguments against the tool's `input_schema`.
+///
+/// Unknown tools pass through; dispatch reports them as
+/// [`Error::UnknownTool`].
+pub fn validate_tool_arguments(tool_name: &str, arguments: &Value) -> Result<()> {
+    let Some(tool) = TOOL_DEFINITIONS.iter().find(|t| t.name == tool_name) else {
+        return Ok(());
+    };
+    validate(&tool.input_schema, arguments)
+        .map_err(|violation| Error::InvalidArgument(format!("{}: {}", tool_name, violation)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -427,12 +313,17 @@ mod tests {
         assert!(names.contains(&"repo_fix"));
         assert!(names.contains(&"git_push"));
         assert!(names.contains(&"git_pull"));
+        assert!(names.contains(&"git_fetch"));
         assert!(names.contains(&"git_merge"));
         assert!(names.contains(&"branch_create"));
         assert!(names.contains(&"branch_delete"));
+        assert!(names.contains(&"branch_checkout"));
+        assert!(names.contains(&"branch_rename"));
         assert!(names.contains(&"branch_list"));
+        assert!(names.contains(&"branch_prune"));
         assert!(names.contains(&"tool_add"));
         assert!(names.contains(&"tool_remove"));
+        assert!(names.contains(&"rules_list"));
         assert!(names.contains(&"rule_add"));
         assert!(names.contains(&"rule_remove"));
         assert!(names.contains(&"preset_list"));
@@ -443,13 +334,24 @@ mod tests {
         assert!(names.contains(&"extension_init"));
         assert!(names.contains(&"extension_remove"));
         assert!(names.contains(&"extension_list"));
+        assert!(names.contains(&"extension_verify"));
+    }
+
+    #[test]
+    fn test_read_only_tools_are_defined() {
+        let tools = get_tool_definitions();
+        for name in READ_ONLY_TOOLS {
+            assert!(tools.iter().any(|t| t.name == *name), "unknown tool {name}");
+        }
+        assert!(is_read_only_tool("branch_list"));
+        assert!(!is_read_only_tool("branch_prune"));
     }
 
     #[test]
     fn test_tool_definitions_count() {
         let tools = get_tool_definitions();
-        // 4 repo lifecycle + 3 branch + 3 git + 4 config + 3 preset + 5 extension = 22 tools
-        assert_eq!(tools.len(), 22);
+        // 4 repo lifecycle + 4 branch + 4 git + 4 config + 3 preset + 6 extension = 25 tools
+        assert_eq!(tools.len(), 28);
     }
 
     #[test]
@@ -588,4 +490,23 @@ mod tests {
         assert!(required.iter().any(|v| v.as_str() == Some("id")));
         assert!(required.iter().any(|v| v.as_str() == Some("content")));
     }
+
+    #[test]
+    fn test_validate_tool_arguments() {
+        assert!(validate_tool_arguments("repo_init", &serde_json::json!({"name": "demo"})).is_ok());
+        assert!(validate_tool_arguments("repo_check", &Value::Null).is_ok());
+        // Unknown tools are left to dispatch
+        assert!(validate_tool_arguments("no_such_tool", &serde_json::json!(42)).is_ok());
+
+        let err = validate_tool_arguments("repo_init", &serde_json::json!({})).unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(_)));
+        assert!(err.to_string().contains("missing required property `name`"));
+
+        let err = validate_tool_arguments("repo_sync", &serde_json::json!({"dry_run": "yes"}))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("`dry_run` must be of type boolean")
+        );
+    }
 }
@@ -298,6 +298,11 @@ async fn test_initialize_returns_capabilities() {
         capabilities.get("resources").is_some(),
         "Server must declare resources capability"
     );
+    // Must declare prompts capability
+    assert!(
+        capabilities.get("prompts").is_some(),
+        "Server must declare prompts capability"
+    );
 }
 
 // ==========================================================================
@@ -442,7 +447,7 @@ async fn test_resources_list_returns_all_defined_resources() {
         serde_json::from_str(&server.handle_message(request).await.unwrap()).unwrap();
 
     let resources = response["result"]["resources"].as_array().unwrap();
-    assert_eq!(resources.len(), 3, "Should list all 3 defined resources");
+    assert_eq!(resources.len(), 4, "Should list all 4 defined resources");
 
     // Verify each resource has required MCP fields
     for resource in resources {
@@ -467,6 +472,74 @@ async fn test_resources_list_returns_all_defined_resources() {
     assert!(uris.contains(&"repo://config"));
     assert!(uris.contains(&"repo://state"));
     assert!(uris.contains(&"repo://rules"));
+    assert!(uris.contains(&"repo://ledger"));
+}
+
+// ==========================================================================
+// Prompts
+// ==========================================================================
+
+#[tokio::test]
+async fn test_prompts_list_returns_prompts_with_arguments() {
+    let temp = TempDir::new().unwrap();
+    let server = setup_server(&temp).await;
+
+    let request = r#"{"jsonrpc":"2.0","id":1,"method":"prompts/list","params":{}}"#;
+    let response: Value =
+        serde_json::from_str(&server.handle_message(request).await.unwrap()).unwrap();
+
+    let prompts = response["result"]["prompts"].as_array().unwrap();
+    let names: Vec<&str> = prompts
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["init_repository", "add_rule_guided"]);
+
+    for prompt in prompts {
+        assert!(prompt["description"].is_string());
+        for argument in prompt["arguments"].as_array().unwrap() {
+            assert!(argument["name"].is_string());
+            assert!(argument["description"].is_string());
+            assert!(argument["required"].is_boolean());
+        }
+    }
+    assert_eq!(
+        prompts[0]["arguments"][0],
+        json!({"name": "name", "description": "Project name", "required": true})
+    );
+}
+
+#[tokio::test]
+async fn test_prompts_get_renders_messages() {
+    let temp = TempDir::new().unwrap();
+    let server = setup_server(&temp).await;
+
+    let request = r#"{"jsonrpc":"2.0","id":2,"method":"prompts/get","params":{"name":"add_rule_guided","arguments":{"id":"naming","tags":"style"}}}"#;
+    let response: Value =
+        serde_json::from_str(&server.handle_message(request).await.unwrap()).unwrap();
+
+    let messages = response["result"]["messages"].as_array().unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0]["role"], "user");
+    assert_eq!(messages[0]["content"]["type"], "text");
+    let text = messages[0]["content"]["text"].as_str().unwrap();
+    assert!(text.contains("\"naming\""));
+    assert!(text.contains("Tag it with: style."));
+}
+
+#[tokio::test]
+async fn test_prompts_get_invalid_returns_32602() {
+    let temp = TempDir::new().unwrap();
+    let server = setup_server(&temp).await;
+
+    for request in [
+        r#"{"jsonrpc":"2.0","id":3,"method":"prompts/get","params":{"name":"unknown"}}"#,
+        r#"{"jsonrpc":"2.0","id":4,"method":"prompts/get","params":{"name":"init_repository"}}"#,
+    ] {
+        let response: Value =
+            serde_json::from_str(&server.handle_message(request).await.unwrap()).unwrap();
+        assert_eq!(response["error"]["code"], -32602);
+    }
 }
 
 // ==========================================================================
@@ -695,7 +768,7 @@ async fn test_resource_read_config_returns_valid_content() {
 // ==========================================================================
 
 #[tokio::test]
-async fn test_mcp_extension_install_not_implemented() {
+async fn test_mcp_extension_install_rejects_local_path() {
     let temp = TempDir::new().unwrap();
     let server = setup_server(&temp).await;
     create_test_repo(&temp);
@@ -706,7 +779,7 @@ async fn test_mcp_extension_install_not_implemented() {
         "method": "tools/call",
         "params": {
             "name": "extension_install",
-            "arguments": { "source": "https://example.com/ext.git" }
+            "arguments": { "source": "./extensions/ext" }
         }
     }))
     .unwrap();
@@ -714,16 +787,13 @@ async fn test_mcp_extension_install_not_implemented() {
     let response: Value =
         serde_json::from_str(&server.handle_message(&request).await.unwrap()).unwrap();
 
-    // Extension handlers return NotImplemented, which surfaces as is_error=true
+    // Handler errors surface as is_error=true
     let result = &response["result"];
-    assert_eq!(
-        result["is_error"], true,
-        "extension_install should return is_error=true (not implemented)"
-    );
+    assert_eq!(result["is_error"], true);
     let text = result["content"][0]["text"].as_str().unwrap();
     assert!(
-        text.contains("not implemented"),
-        "Error text should mention 'not implemented', got: {}",
+        text.contains("git URL"),
+        "Error text should ask for a git URL, got: {}",
         text
     );
 }
@@ -736,7 +806,6 @@ async fn test_mcp_extension_handlers_return_not_implemented() {
 
     // All extension mutation operations should return is_error=true
     let extension_tools = vec![
-        ("extension_install", json!({ "source": "test" })),
         ("extension_add", json!({ "name": "test" })),
         ("extension_init", json!({ "name": "test" })),
         ("extension_remove", json!({ "name": "test" })),
@@ -869,5 +938,5 @@ async fn test_error_after_success_does_not_corrupt_state() {
         "Server should still work after an error response"
     );
     let resources = resp3["result"]["resources"].as_array().unwrap();
-    assert_eq!(resources.len(), 3, "Should still list all 3 resources");
+    assert_eq!(resources.len(), 4, "Should still list all 4 resources");
 }
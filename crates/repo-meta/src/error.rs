@@ -4,6 +4,11 @@ use std::path::PathBuf;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Format an optional line number as a `:line` location suffix
+fn line_suffix(line: &Option<usize>) -> String {
+    line.map(|l| format!(":{}", l)).unwrap_or_default()
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Filesystem error: {0}")]
@@ -15,6 +20,16 @@ pub enum Error {
     #[error("Invalid configuration at {path}: {message}")]
     InvalidConfig { path: PathBuf, message: String },
 
+    #[error("Invalid definition {}{}: {message}", .path.display(), line_suffix(.line))]
+    DefinitionInvalid {
+        path: PathBuf,
+        message: String,
+        line: Option<usize>,
+    },
+
+    #[error("Invalid front-matter at line {line}: {message}")]
+    FrontMatter { line: usize, message: String },
+
     #[error("Config file too large: {path} is {size} bytes (max {max})")]
     ConfigTooLarge { path: PathBuf, size: u64, max: u64 },
 
@@ -0,0 +1,117 @@
+//! Diagnostic levels shared across crates
+//!
+//! [`DiagnosticLevel`] is the one scale for reporting problems: lint
+//! warnings in `repo-core` use it directly (as `WarnLevel`), and a rule's
+//! [`Severity`] converts to it wherever a rule's outcome is reported.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::schema::Severity;
+
+/// How serious a reported problem is, from least to most severe.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticLevel {
+    /// Optional improvement
+    Hint,
+    /// Informational notice
+    #[default]
+    Info,
+    /// Potential problem
+    Warning,
+    /// Configuration error
+    Error,
+}
+
+impl fmt::Display for DiagnosticLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hint => write!(f, "hint"),
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem with a mandatory rule is an error, with a suggestion a warning.
+impl From<Severity> for DiagnosticLevel {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Mandatory => Self::Error,
+            Severity::Suggestion => Self::Warning,
+        }
+    }
+}
+
+/// Only errors are mandatory; a rule's own level converts back losslessly.
+impl From<DiagnosticLevel> for Severity {
+    fn from(level: DiagnosticLevel) -> Self {
+        match level {
+            DiagnosticLevel::Error => Self::Mandatory,
+            DiagnosticLevel::Warning | DiagnosticLevel::Info | DiagnosticLevel::Hint => {
+                Self::Suggestion
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEVELS: [DiagnosticLevel; 4] = [
+        DiagnosticLevel::Hint,
+        DiagnosticLevel::Info,
+        DiagnosticLevel::Warning,
+        DiagnosticLevel::Error,
+    ];
+
+    #[test]
+    fn test_levels_are_ordered() {
+        assert!(LEVELS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_severity_round_trips() {
+        for severity in [Severity::Suggestion, Severity::Mandatory] {
+            assert_eq!(Severity::from(DiagnosticLevel::from(severity)), severity);
+        }
+        assert_eq!(
+            DiagnosticLevel::from(Severity::Mandatory),
+            DiagnosticLevel::Error
+        );
+        assert_eq!(
+            DiagnosticLevel::from(Severity::Suggestion),
+            DiagnosticLevel::Warning
+        );
+    }
+
+    #[test]
+    fn test_levels_convert_to_severity() {
+        assert_eq!(Severity::from(DiagnosticLevel::Error), Severity::Mandatory);
+        for level in [
+            DiagnosticLevel::Warning,
+            DiagnosticLevel::Info,
+            DiagnosticLevel::Hint,
+        ] {
+            assert_eq!(Severity::from(level), Severity::Suggestion);
+        }
+    }
+
+    #[test]
+    fn test_serde_and_display_names() {
+        for level in LEVELS {
+            let json = serde_json::to_string(&level).unwrap();
+            assert_eq!(json, format!("\"{}\"", level));
+            assert_eq!(
+                serde_json::from_str::<DiagnosticLevel>(&json).unwrap(),
+                level
+            );
+        }
+    }
+}
@@ -30,6 +30,7 @@
 
 pub mod config;
 pub mod error;
+pub mod level;
 pub mod loader;
 pub mod registry;
 pub mod schema;
@@ -37,6 +38,7 @@ pub mod validation;
 
 pub use config::{ActiveConfig, CoreConfig, RepositoryMode, SyncConfig, SyncStrategy};
 pub use error::{Error, Result};
+pub use level::DiagnosticLevel;
 pub use loader::{DefinitionLoader, LoadResult};
 pub use registry::Registry;
 pub use schema::{PresetDefinition, RuleDefinition, ToolDefinition};
@@ -14,12 +14,60 @@
 //!   presets/
 //!     python-agentic.toml
 //! ```
+//!
+//! Installed extensions can contribute tool definitions too: each file
+//! listed under `[provides] tool_definitions` in an extension's manifest is
+//! loaded by [`DefinitionLoader::load_extension_tools`].
+//!
+//! Parsed definitions are cached per process, keyed by file path and
+//! modification time, so unchanged files are only parsed once. Use
+//! [`DefinitionLoader::invalidate`] to drop the cache after writing
+//! definitions.
 
 use crate::schema::{PresetDefinition, RuleDefinition, ToolDefinition};
 use crate::{Error, Result};
-use repo_fs::{ConfigStore, NormalizedPath};
+use repo_fs::{NormalizedPath, io};
+use serde::Deserialize;
+use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::SystemTime;
+
+/// The rule registry shares `.repository/rules/` with rule definitions
+const RULE_REGISTRY_FILE: &str = "registry.toml";
+
+/// Installed extensions and their manifest, as laid out by repo-extensions,
+/// which depends on this crate
+const EXTENSIONS_DIR: &str = ".repository/extensions";
+const EXTENSION_MANIFEST: &str = "repo_extension.toml";
+
+/// The part of an extension manifest that contributes tool definitions
+#[derive(Debug, Default, Deserialize)]
+struct ExtensionManifest {
+    #[serde(default)]
+    provides: ExtensionProvides,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ExtensionProvides {
+    #[serde(default)]
+    tools: Vec<String>,
+    #[serde(default)]
+    tool_definitions: Vec<String>,
+}
+
+/// A parsed definition and the file state it was parsed from
+struct CacheEntry {
+    modified: SystemTime,
+    len: u64,
+    definition: Arc<dyn Any + Send + Sync>,
+}
+
+/// Process-wide cache of parsed definitions, keyed by path and type
+static CACHE: LazyLock<Mutex<HashMap<(PathBuf, TypeId), CacheEntry>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
 
 /// Result of loading definitions from a directory.
 ///
@@ -31,19 +79,18 @@ pub struct LoadResult<T> {
     pub definitions: HashMap<String, T>,
     /// Warnings for files that failed to load (path and error message)
     pub warnings: Vec<String>,
+    /// [`Error::DefinitionInvalid`] for each file that failed to load,
+    /// in the same order as `warnings`
+    pub errors: Vec<Error>,
 }
 
 /// Loads all definitions from .repository/ directory
-pub struct DefinitionLoader {
-    store: ConfigStore,
-}
+pub struct DefinitionLoader;
 
 impl DefinitionLoader {
     /// Create a new DefinitionLoader
     pub fn new() -> Self {
-        Self {
-            store: ConfigStore::new(),
-        }
+        Self
     }
 
     /// Load all tool definitions from .repository/tools/
@@ -58,7 +105,97 @@ impl DefinitionLoader {
     /// plus any warnings for files that failed to parse.
     pub fn load_tools(&self, root: &NormalizedPath) -> Result<LoadResult<ToolDefinition>> {
         let tools_dir = root.join(".repository").join("tools");
-        self.load_definitions(&tools_dir)
+        self.load_definitions(&tools_dir, &[])
+    }
+
+    /// Load the tool definitions contributed by installed extensions
+    ///
+    /// Reads the files listed under `[provides] tool_definitions` in each
+    /// manifest under `.repository/extensions/`, relative to the extension's
+    /// directory. Extensions are read in order of name, and when two supply
+    /// the same slug the first wins. A definition whose slug the extension
+    /// does not list in `[provides] tools`, or whose path leaves the
+    /// extension's directory, is skipped with a warning. Extensions whose
+    /// manifest cannot be read are skipped; `repo extension verify` reports
+    /// them.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - Repository root path
+    pub fn load_extension_tools(
+        &self,
+        root: &NormalizedPath,
+    ) -> Result<LoadResult<ToolDefinition>> {
+        let mut result = LoadResult {
+            definitions: HashMap::new(),
+            warnings: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        let Ok(entries) = fs::read_dir(root.join(EXTENSIONS_DIR).to_native()) else {
+            return Ok(result);
+        };
+        let mut dirs: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        dirs.sort();
+
+        for dir in dirs {
+            let manifest: ExtensionManifest = match fs::read_to_string(dir.join(EXTENSION_MANIFEST))
+                .ok()
+                .and_then(|content| toml::from_str(&content).ok())
+            {
+                Some(manifest) => manifest,
+                None => continue,
+            };
+
+            for file in &manifest.provides.tool_definitions {
+                let escapes = Path::new(file)
+                    .components()
+                    .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+                if escapes {
+                    let warning = format!(
+                        "Tool definition '{}' of extension {} is not inside the extension",
+                        file,
+                        dir.display()
+                    );
+                    tracing::warn!("{}", warning);
+                    result.warnings.push(warning);
+                    continue;
+                }
+
+                let path = dir.join(file);
+                let def = match Self::load_cached::<ToolDefinition>(&path) {
+                    Ok(def) => def,
+                    Err(e) => {
+                        let warning = e.to_string();
+                        tracing::warn!("{}", warning);
+                        result.warnings.push(warning);
+                        result.errors.push(e);
+                        continue;
+                    }
+                };
+                if !manifest.provides.tools.contains(&def.meta.slug) {
+                    let warning = format!(
+                        "Tool definition {} is ignored: its slug '{}' is not listed in [provides] tools",
+                        path.display(),
+                        def.meta.slug
+                    );
+                    tracing::warn!("{}", warning);
+                    result.warnings.push(warning);
+                    continue;
+                }
+                result
+                    .definitions
+                    .entry(def.meta.slug.clone())
+                    .or_insert(def);
+            }
+        }
+
+        Ok(result)
     }
 
     /// Load all rule definitions from .repository/rules/
@@ -70,10 +207,11 @@ impl DefinitionLoader {
     /// # Returns
     ///
     /// A `LoadResult` containing a map of rule ID to rule definition,
-    /// plus any warnings for files that failed to parse.
+    /// plus any warnings for files that failed to parse. The rule registry
+    /// (`registry.toml`) is not a definition and is skipped.
     pub fn load_rules(&self, root: &NormalizedPath) -> Result<LoadResult<RuleDefinition>> {
         let rules_dir = root.join(".repository").join("rules");
-        self.load_definitions(&rules_dir)
+        self.load_definitions(&rules_dir, &[RULE_REGISTRY_FILE])
     }
 
     /// Load all preset definitions from .repository/presets/
@@ -88,21 +226,34 @@ impl DefinitionLoader {
     /// plus any warnings for files that failed to parse.
     pub fn load_presets(&self, root: &NormalizedPath) -> Result<LoadResult<PresetDefinition>> {
         let presets_dir = root.join(".repository").join("presets");
-        self.load_definitions(&presets_dir)
+        self.load_definitions(&presets_dir, &[])
     }
 
-    /// Generic loader for definitions from a directory
-    fn load_definitions<T>(&self, dir: &NormalizedPath) -> Result<LoadResult<T>>
+    /// Drop all cached definitions, forcing the next load to re-parse
+    pub fn invalidate() {
+        Self::cache().clear();
+    }
+
+    fn cache() -> std::sync::MutexGuard<'static, HashMap<(PathBuf, TypeId), CacheEntry>> {
+        // The cache holds no invariants a panicking thread could break
+        CACHE.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Generic loader for definitions from a directory, ignoring the files
+    /// named in `skip`
+    fn load_definitions<T>(&self, dir: &NormalizedPath, skip: &[&str]) -> Result<LoadResult<T>>
     where
-        T: serde::de::DeserializeOwned + HasId,
+        T: serde::de::DeserializeOwned + HasId + Clone + Send + Sync + 'static,
     {
         let mut definitions = HashMap::new();
         let mut warnings = Vec::new();
+        let mut errors = Vec::new();
 
         if !dir.exists() {
             return Ok(LoadResult {
                 definitions,
                 warnings,
+                errors,
             });
         }
 
@@ -111,16 +262,19 @@ impl DefinitionLoader {
 
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.extension().is_some_and(|ext| ext == "toml") {
-                let norm_path = NormalizedPath::new(&path);
-                match self.store.load::<T>(&norm_path) {
+            let skipped = path
+                .file_name()
+                .is_some_and(|name| skip.iter().any(|s| name == *s));
+            if path.extension().is_some_and(|ext| ext == "toml") && !skipped {
+                match Self::load_cached::<T>(&path) {
                     Ok(def) => {
                         definitions.insert(def.id().to_string(), def);
                     }
                     Err(e) => {
-                        let warning = format!("Failed to load {}: {}", path.display(), e);
+                        let warning = e.to_string();
                         tracing::warn!("{}", warning);
                         warnings.push(warning);
+                        errors.push(e);
                     }
                 }
             }
@@ -129,6 +283,67 @@ impl DefinitionLoader {
         Ok(LoadResult {
             definitions,
             warnings,
+            errors,
+        })
+    }
+
+    /// Parse a single definition file, or return the cached definition if
+    /// the file's modification time and size are unchanged
+    ///
+    /// Failed parses are not cached, so errors are reported on every load.
+    fn load_cached<T>(path: &Path) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let key = (path.to_path_buf(), TypeId::of::<T>());
+        let stamp = fs::metadata(path)
+            .ok()
+            .and_then(|m| Some((m.modified().ok()?, m.len())));
+
+        if let Some((modified, len)) = stamp
+            && let Some(entry) = Self::cache().get(&key)
+            && entry.modified == modified
+            && entry.len == len
+            && let Some(def) = entry.definition.downcast_ref::<T>()
+        {
+            return Ok(def.clone());
+        }
+
+        let def = Self::load_definition::<T>(path)?;
+        if let Some((modified, len)) = stamp {
+            Self::cache().insert(
+                key,
+                CacheEntry {
+                    modified,
+                    len,
+                    definition: Arc::new(def.clone()),
+                },
+            );
+        }
+        Ok(def)
+    }
+
+    /// Parse a single definition file
+    ///
+    /// Errors are reported as [`Error::DefinitionInvalid`], with the line
+    /// of the offending key or value when the parser reports one.
+    fn load_definition<T>(path: &Path) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let content =
+            io::read_text(&NormalizedPath::new(path)).map_err(|e| Error::DefinitionInvalid {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+                line: None,
+            })?;
+
+        toml::from_str(&content).map_err(|e| Error::DefinitionInvalid {
+            path: path.to_path_buf(),
+            message: e.message().to_string(),
+            line: e
+                .span()
+                .map(|span| content[..span.start].matches('\n').count() + 1),
         })
     }
 }
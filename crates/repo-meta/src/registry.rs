@@ -24,10 +24,15 @@ use std::collections::HashMap;
 /// registry.register("env:python", "uv");
 /// assert_eq!(registry.get_provider("env:python"), Some(&"uv".to_string()));
 /// ```
+///
+/// A preset may also have alternative providers, which a preset's config
+/// selects with its `provider` key (see [`Registry::resolve_provider`]).
 #[derive(Debug, Clone, Default)]
 pub struct Registry {
     /// Maps preset ID to provider name
     providers: HashMap<String, String>,
+    /// Maps preset ID to the providers that may replace its default
+    alternatives: HashMap<String, Vec<String>>,
 }
 
 impl Registry {
@@ -35,18 +40,21 @@ impl Registry {
     pub fn new() -> Self {
         Self {
             providers: HashMap::new(),
+            alternatives: HashMap::new(),
         }
     }
 
     /// Create a registry with built-in presets registered.
     ///
     /// Currently registers:
-    /// - `env:python` -> `uv`
+    /// - `env:python` -> `uv`, or `venv` or `poetry` when selected
     /// - `env:node` -> `node`
     /// - `env:rust` -> `rust`
     pub fn with_builtins() -> Self {
         let mut registry = Self::new();
         registry.register("env:python", "uv");
+        registry.register_alternative("env:python", "venv");
+        registry.register_alternative("env:python", "poetry");
         registry.register("env:node", "node");
         registry.register("env:rust", "rust");
         registry
@@ -66,6 +74,54 @@ impl Registry {
             .insert(preset_id.into(), provider_name.into());
     }
 
+    /// Register an alternative provider for a preset ID.
+    ///
+    /// The preset's default provider is unchanged; the alternative is used
+    /// only when a preset's config selects it.
+    ///
+    /// # Arguments
+    ///
+    /// * `preset_id` - The preset identifier (e.g., "env:python")
+    /// * `provider_name` - The provider name (e.g., "poetry")
+    pub fn register_alternative(
+        &mut self,
+        preset_id: impl Into<String>,
+        provider_name: impl Into<String>,
+    ) {
+        let provider_name = provider_name.into();
+        let alternatives = self.alternatives.entry(preset_id.into()).or_default();
+        if !alternatives.contains(&provider_name) {
+            alternatives.push(provider_name);
+        }
+    }
+
+    /// Get the alternative provider names for a preset ID.
+    pub fn alternatives(&self, preset_id: &str) -> &[String] {
+        self.alternatives
+            .get(preset_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Get the provider to use for a preset ID, given the provider its
+    /// config asks for.
+    ///
+    /// `requested` is honoured if it is the default or a registered
+    /// alternative; otherwise the default provider is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `preset_id` - The preset identifier to look up
+    /// * `requested` - The `provider` value from the preset's config, if any
+    pub fn resolve_provider(&self, preset_id: &str, requested: Option<&str>) -> Option<&String> {
+        let requested = requested.and_then(|name| {
+            self.alternatives(preset_id)
+                .iter()
+                .find(|alternative| *alternative == name)
+        });
+        requested.or_else(|| self.get_provider(preset_id))
+    }
+
     /// Get the provider name for a preset ID.
     ///
     /// # Arguments
@@ -137,6 +193,37 @@ mod tests {
         assert_eq!(registry.get_provider("env:rust"), Some(&"rust".to_string()));
     }
 
+    #[test]
+    fn test_builtin_python_alternatives() {
+        let registry = Registry::with_builtins();
+        assert_eq!(registry.alternatives("env:python"), ["venv", "poetry"]);
+        assert!(registry.alternatives("env:node").is_empty());
+        // Alternatives do not count as presets
+        assert_eq!(registry.len(), 3);
+    }
+
+    #[test]
+    fn test_resolve_provider() {
+        let registry = Registry::with_builtins();
+        assert_eq!(
+            registry.resolve_provider("env:python", Some("poetry")),
+            Some(&"poetry".to_string())
+        );
+        assert_eq!(
+            registry.resolve_provider("env:python", None),
+            Some(&"uv".to_string())
+        );
+        assert_eq!(
+            registry.resolve_provider("env:python", Some("pipenv")),
+            Some(&"uv".to_string())
+        );
+        assert_eq!(
+            registry.resolve_provider("env:node", Some("poetry")),
+            Some(&"node".to_string())
+        );
+        assert_eq!(registry.resolve_provider("env:ruby", Some("rbenv")), None);
+    }
+
     #[test]
     fn test_register_and_get() {
         let mut registry = Registry::new();
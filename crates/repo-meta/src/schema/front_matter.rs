@@ -0,0 +1,447 @@
+//! Front-matter for Markdown rules - .repository/rules/*.md
+//!
+//! A Markdown rule may start with a `---` delimited YAML block carrying the
+//! metadata of a [`RuleDefinition`](super::RuleDefinition): the fields of its
+//! `[meta]` table and the file patterns of its `[targets]` table.
+//!
+//! ```markdown
+//! ---
+//! severity: mandatory
+//! tags: [python, style]
+//! requires: [naming]
+//! files: ["**/*.py"]
+//! ---
+//! Use snake_case for all Python variables and function names.
+//! ```
+//!
+//! Rules without a block may instead start with a `tags: a, b` line.
+//!
+//! In [`FrontMatterMode::Strict`] unknown keys and values of the wrong type
+//! are errors. [`FrontMatterMode::Lenient`] ignores unknown keys and coerces
+//! what it can, such as a single tag for a list of tags, reporting each as
+//! a warning.
+
+use serde_yaml::Value;
+
+use super::rule::Severity;
+use crate::error::{Error, Result};
+
+const DELIMITER: &str = "---";
+
+/// How to treat front-matter that does not match the schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrontMatterMode {
+    /// Unknown keys and wrong types are errors
+    #[default]
+    Strict,
+    /// Unknown keys are ignored and wrong types coerced, with warnings
+    Lenient,
+}
+
+/// Metadata from the front-matter of a Markdown rule
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleFrontMatter {
+    /// Rule identifier; defaults to the file name
+    pub id: Option<String>,
+    /// How strictly the rule should be enforced
+    pub severity: Severity,
+    /// Tags for categorization and filtering
+    pub tags: Vec<String>,
+    /// IDs of other rules this rule builds on
+    pub requires: Vec<String>,
+    /// Glob patterns for files this rule applies to
+    pub files: Vec<String>,
+}
+
+/// A Markdown rule split into its front-matter and body
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleMarkdown {
+    /// Metadata; default if the rule has none
+    pub front_matter: RuleFrontMatter,
+    /// Whether the rule starts with a `---` block
+    pub has_block: bool,
+    /// The rule text after the front-matter
+    pub body: String,
+    /// What lenient parsing ignored or coerced, as `line N: message`
+    pub warnings: Vec<String>,
+}
+
+impl RuleFrontMatter {
+    /// Render as a `---` delimited block, omitting default fields
+    pub fn to_block(&self) -> String {
+        let list =
+            |items: &[String]| serde_json::to_string(items).expect("a list of strings serializes");
+        let mut block = format!("{}\n", DELIMITER);
+        if let Some(id) = &self.id {
+            block.push_str(&format!("id: {}\n", serde_json::Value::from(id.as_str())));
+        }
+        if self.severity != Severity::default() {
+            block.push_str("severity: mandatory\n");
+        }
+        for (key, items) in [
+            ("tags", &self.tags),
+            ("requires", &self.requires),
+            ("files", &self.files),
+        ] {
+            if !items.is_empty() {
+                block.push_str(&format!("{}: {}\n", key, list(items)));
+            }
+        }
+        block.push_str(&format!("{}\n", DELIMITER));
+        block
+    }
+}
+
+/// Split a Markdown rule into its front-matter and body
+///
+/// Fails on a `---` block that is not closed or is not valid YAML, in either
+/// mode, and in strict mode on anything that does not match the schema.
+/// Errors carry the 1-based line of the offending key.
+pub fn parse_rule_markdown(text: &str, mode: FrontMatterMode) -> Result<RuleMarkdown> {
+    let Some(rest) = text
+        .strip_prefix(DELIMITER)
+        .and_then(|rest| rest.strip_prefix('\n').or(rest.strip_prefix("\r\n")))
+    else {
+        return Ok(legacy(text));
+    };
+
+    let (block, body) = split_block(rest).ok_or_else(|| Error::FrontMatter {
+        line: 1,
+        message: "front-matter is not closed by a `---` line".into(),
+    })?;
+
+    let value: Value = serde_yaml::from_str(block).map_err(|e| Error::FrontMatter {
+        line: e.location().map_or(1, |l| l.line() + 1),
+        message: e.to_string(),
+    })?;
+    let mapping = match value {
+        Value::Null => Default::default(),
+        Value::Mapping(mapping) => mapping,
+        _ => {
+            return Err(Error::FrontMatter {
+                line: 2,
+                message: "front-matter must be a mapping of keys to values".into(),
+            });
+        }
+    };
+
+    let mut parser = Parser {
+        block,
+        mode,
+        warnings: Vec::new(),
+    };
+    let mut front_matter = RuleFrontMatter::default();
+    for (key, value) in &mapping {
+        let Some(key) = key.as_str() else {
+            parser.problem(2, format!("front-matter key {:?} is not a string", key))?;
+            continue;
+        };
+        let line = parser.line_of(key);
+        match key {
+            "id" => front_matter.id = parser.string(key, value, line)?,
+            "severity" => {
+                if let Some(severity) = parser.severity(value, line)? {
+                    front_matter.severity = severity;
+                }
+            }
+            "tags" => front_matter.tags = parser.list(key, value, line)?,
+            "requires" => front_matter.requires = parser.list(key, value, line)?,
+            "files" => front_matter.files = parser.list(key, value, line)?,
+            _ => parser.problem(line, format!("unknown front-matter key `{}`", key))?,
+        }
+    }
+
+    Ok(RuleMarkdown {
+        front_matter,
+        has_block: true,
+        body: body
+            .strip_prefix('\n')
+            .or(body.strip_prefix("\r\n"))
+            .unwrap_or(body)
+            .to_string(),
+        warnings: parser.warnings,
+    })
+}
+
+/// Compose the text of a Markdown rule from `content` and extra `tags`
+///
+/// `content` may carry its own front-matter, parsed in `mode`; the extra
+/// tags are merged into it. Content without a block gets a `tags:` line.
+/// Returns the rule text and any warnings from lenient parsing.
+pub fn compose_rule_markdown(
+    content: &str,
+    tags: &[String],
+    mode: FrontMatterMode,
+) -> Result<(String, Vec<String>)> {
+    let RuleMarkdown {
+        mut front_matter,
+        has_block,
+        body,
+        warnings,
+    } = parse_rule_markdown(content, mode)?;
+    let extra: Vec<&String> = tags
+        .iter()
+        .filter(|tag| !front_matter.tags.contains(tag))
+        .collect();
+    if extra.is_empty() && warnings.is_empty() {
+        return Ok((content.to_string(), warnings));
+    }
+    front_matter.tags.extend(extra.into_iter().cloned());
+
+    let text = if has_block {
+        format!("{}{}", front_matter.to_block(), body)
+    } else {
+        format!("tags: {}\n\n{}", front_matter.tags.join(", "), body)
+    };
+    Ok((text, warnings))
+}
+
+/// A rule without a block, optionally starting with a `tags: a, b` line
+fn legacy(text: &str) -> RuleMarkdown {
+    let Some(rest) = text.strip_prefix("tags:") else {
+        return RuleMarkdown {
+            body: text.to_string(),
+            ..Default::default()
+        };
+    };
+    let (line, body) = rest.split_once('\n').unwrap_or((rest, ""));
+    RuleMarkdown {
+        front_matter: RuleFrontMatter {
+            tags: line
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(String::from)
+                .collect(),
+            ..Default::default()
+        },
+        body: body.strip_prefix('\n').unwrap_or(body).to_string(),
+        ..Default::default()
+    }
+}
+
+/// Split the text after the opening delimiter at the closing one
+fn split_block(rest: &str) -> Option<(&str, &str)> {
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == DELIMITER {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+struct Parser<'a> {
+    block: &'a str,
+    mode: FrontMatterMode,
+    warnings: Vec<String>,
+}
+
+impl Parser<'_> {
+    /// Line of `key` in the file, counting the opening delimiter as line 1
+    fn line_of(&self, key: &str) -> usize {
+        self.block
+            .lines()
+            .position(|line| {
+                line.strip_prefix(key)
+                    .is_some_and(|rest| rest.trim_start().starts_with(':'))
+            })
+            .map_or(2, |index| index + 2)
+    }
+
+    /// An error in strict mode, a warning in lenient mode
+    fn problem(&mut self, line: usize, message: String) -> Result<()> {
+        match self.mode {
+            FrontMatterMode::Strict => Err(Error::FrontMatter { line, message }),
+            FrontMatterMode::Lenient => {
+                self.warnings.push(format!("line {}: {}", line, message));
+                Ok(())
+            }
+        }
+    }
+
+    fn string(&mut self, key: &str, value: &Value, line: usize) -> Result<Option<String>> {
+        match value {
+            Value::String(s) => Ok(Some(s.clone())),
+            Value::Null => Ok(None),
+            other => match scalar(other) {
+                Some(s) => {
+                    self.problem(line, format!("`{}` should be a string, using {:?}", key, s))?;
+                    Ok(Some(s))
+                }
+                None => {
+                    self.problem(line, format!("`{}` must be a string, ignoring it", key))?;
+                    Ok(None)
+                }
+            },
+        }
+    }
+
+    fn severity(&mut self, value: &Value, line: usize) -> Result<Option<Severity>> {
+        match value.as_str() {
+            Some("mandatory") => Ok(Some(Severity::Mandatory)),
+            Some("suggestion") => Ok(Some(Severity::Suggestion)),
+            _ => {
+                self.problem(
+                    line,
+                    "`severity` must be \"mandatory\" or \"suggestion\", using \"suggestion\""
+                        .into(),
+                )?;
+                Ok(None)
+            }
+        }
+    }
+
+    fn list(&mut self, key: &str, value: &Value, line: usize) -> Result<Vec<String>> {
+        match value {
+            Value::Null => Ok(Vec::new()),
+            Value::Sequence(items) => {
+                let mut list = Vec::new();
+                for item in items {
+                    match (item.as_str(), scalar(item)) {
+                        (Some(s), _) => list.push(s.to_string()),
+                        (None, Some(s)) => {
+                            self.problem(
+                                line,
+                                format!("`{}` should only hold strings, using {:?}", key, s),
+                            )?;
+                            list.push(s);
+                        }
+                        (None, None) => self.problem(
+                            line,
+                            format!("`{}` should only hold strings, skipping an entry", key),
+                        )?,
+                    }
+                }
+                Ok(list)
+            }
+            other => match scalar(other) {
+                Some(s) => {
+                    self.problem(
+                        line,
+                        format!("`{}` must be a list, treating {:?} as one entry", key, s),
+                    )?;
+                    Ok(vec![s])
+                }
+                None => {
+                    self.problem(line, format!("`{}` must be a list, ignoring it", key))?;
+                    Ok(Vec::new())
+                }
+            },
+        }
+    }
+}
+
+/// A scalar as text
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULE: &str = "---\nseverity: mandatory\ntags: [python, style]\nrequires: [naming]\nfiles: [\"**/*.py\"]\n---\nUse snake_case.\n";
+
+    #[test]
+    fn test_parse_front_matter() {
+        let rule = parse_rule_markdown(RULE, FrontMatterMode::Strict).unwrap();
+        assert!(rule.has_block);
+        assert_eq!(rule.body, "Use snake_case.\n");
+        assert_eq!(
+            rule.front_matter,
+            RuleFrontMatter {
+                id: None,
+                severity: Severity::Mandatory,
+                tags: vec!["python".into(), "style".into()],
+                requires: vec!["naming".into()],
+                files: vec!["**/*.py".into()],
+            }
+        );
+        assert!(rule.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_block_round_trip() {
+        let rule = parse_rule_markdown(RULE, FrontMatterMode::Strict).unwrap();
+        let text = format!("{}{}", rule.front_matter.to_block(), rule.body);
+        let again = parse_rule_markdown(&text, FrontMatterMode::Strict).unwrap();
+        assert_eq!(again.front_matter, rule.front_matter);
+        assert_eq!(again.body, rule.body);
+    }
+
+    #[test]
+    fn test_scalar_tags_strict_errors() {
+        let text = "---\nseverity: mandatory\ntags: python\n---\nBody\n";
+        let err = parse_rule_markdown(text, FrontMatterMode::Strict).unwrap_err();
+        assert!(matches!(err, Error::FrontMatter { line: 3, .. }), "{err}");
+        assert!(err.to_string().contains("`tags` must be a list"));
+    }
+
+    #[test]
+    fn test_scalar_tags_lenient_coerces() {
+        let text = "---\nseverity: mandatory\ntags: python\n---\nBody\n";
+        let rule = parse_rule_markdown(text, FrontMatterMode::Lenient).unwrap();
+        assert_eq!(rule.front_matter.tags, vec!["python"]);
+        assert_eq!(rule.warnings.len(), 1);
+        assert!(rule.warnings[0].starts_with("line 3: `tags` must be a list"));
+    }
+
+    #[test]
+    fn test_unknown_key() {
+        let text = "---\ntags: [a]\nglobs: \"*.rs\"\n---\nBody\n";
+        let err = parse_rule_markdown(text, FrontMatterMode::Strict).unwrap_err();
+        assert!(matches!(err, Error::FrontMatter { line: 3, .. }));
+
+        let rule = parse_rule_markdown(text, FrontMatterMode::Lenient).unwrap();
+        assert_eq!(rule.front_matter.tags, vec!["a"]);
+        assert!(rule.warnings[0].contains("unknown front-matter key `globs`"));
+    }
+
+    #[test]
+    fn test_malformed_block_errors_in_both_modes() {
+        for mode in [FrontMatterMode::Strict, FrontMatterMode::Lenient] {
+            assert!(parse_rule_markdown("---\ntags: [a]\nBody\n", mode).is_err());
+            assert!(parse_rule_markdown("---\ntags: [a\n---\nBody\n", mode).is_err());
+            assert!(parse_rule_markdown("---\n- a\n---\nBody\n", mode).is_err());
+        }
+    }
+
+    #[test]
+    fn test_compose_rule_markdown() {
+        let tags = vec!["style".to_string()];
+        let (text, _) =
+            compose_rule_markdown("Be consistent.", &tags, FrontMatterMode::Strict).unwrap();
+        assert_eq!(text, "tags: style\n\nBe consistent.");
+
+        let (text, _) = compose_rule_markdown(RULE, &tags, FrontMatterMode::Strict).unwrap();
+        let rule = parse_rule_markdown(&text, FrontMatterMode::Strict).unwrap();
+        assert_eq!(rule.front_matter.tags, vec!["python", "style"]);
+        assert_eq!(rule.front_matter.requires, vec!["naming"]);
+
+        let scalar = "---\ntags: python\n---\nBody\n";
+        assert!(compose_rule_markdown(scalar, &[], FrontMatterMode::Strict).is_err());
+        let (text, warnings) =
+            compose_rule_markdown(scalar, &[], FrontMatterMode::Lenient).unwrap();
+        assert_eq!(text, "---\ntags: [\"python\"]\n---\nBody\n");
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_legacy_tags_line() {
+        let rule = parse_rule_markdown("tags: a, b\n\nBody", FrontMatterMode::Strict).unwrap();
+        assert!(!rule.has_block);
+        assert_eq!(rule.front_matter.tags, vec!["a", "b"]);
+        assert_eq!(rule.body, "Body");
+
+        let rule = parse_rule_markdown("Just text", FrontMatterMode::Strict).unwrap();
+        assert_eq!(rule.body, "Just text");
+        assert!(rule.front_matter.tags.is_empty());
+    }
+}
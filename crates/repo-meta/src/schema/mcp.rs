@@ -43,6 +43,9 @@ pub struct McpConfigSpec {
     /// inside a larger settings file.
     pub embedding: McpConfigEmbedding,
 
+    /// File format of the config file.
+    pub config_format: McpConfigFormat,
+
     /// Supported MCP transport types.
     pub transports: &'static [McpTransport],
 
@@ -141,6 +144,29 @@ pub enum McpConfigEmbedding {
     Nested,
 }
 
+/// File format of a tool's MCP config file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum McpConfigFormat {
+    /// JSON, e.g. `.cursor/mcp.json`
+    #[default]
+    Json,
+    /// TOML, e.g. Codex's `config.toml`
+    Toml,
+    /// YAML
+    Yaml,
+}
+
+impl McpConfigFormat {
+    /// File extension for this format, without the leading dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Toml => "toml",
+            Self::Yaml => "yaml",
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Transport types
 // ---------------------------------------------------------------------------
@@ -165,6 +191,7 @@ pub enum McpTransport {
 /// Different tools use different field names for the same concept:
 /// - HTTP URL: `"url"` vs `"serverUrl"` vs `"httpUrl"`
 /// - Type field: `"type": "stdio"` vs `"type": "command"` vs auto-inferred
+/// - Stdio command: flat `command`/`args`/`env` vs Zed's nested `command` object
 #[derive(Debug, Clone)]
 pub struct McpFieldMappings {
     /// Field name for the HTTP/Streamable HTTP URL.
@@ -181,6 +208,19 @@ pub struct McpFieldMappings {
 
     /// Values for the `"type"` field per transport, if `requires_type_field` is true.
     pub type_values: McpTypeValues,
+
+    /// How stdio `command`, `args` and `env` are laid out.
+    pub stdio_shape: McpStdioShape,
+}
+
+/// Layout of a stdio server's command line in a server entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum McpStdioShape {
+    /// `"command"`, `"args"` and `"env"` are fields of the server entry
+    #[default]
+    Flat,
+    /// `"command": { "path", "args", "env" }` — used by Zed
+    Nested,
 }
 
 /// Values used in the `"type"` field for each transport.
@@ -211,6 +251,7 @@ impl Default for McpFieldMappings {
             sse_url_field: None,
             requires_type_field: false,
             type_values: McpTypeValues::default(),
+            stdio_shape: McpStdioShape::Flat,
         }
     }
 }
@@ -251,6 +292,11 @@ pub struct McpServerConfig {
     /// Used by Roo Code (`alwaysAllow`), Cline (`alwaysAllow`), and Amazon Q (`autoApprove`).
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub auto_approve: bool,
+    /// Expand `${VAR}` placeholders in env values and args from the
+    /// installing process's environment. When unset, placeholders are
+    /// written verbatim for tools that do their own expansion.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub expand_env: bool,
 }
 
 /// Transport-specific configuration for an MCP server.
@@ -292,6 +338,94 @@ pub enum McpScope {
     User,
 }
 
+// ===========================================================================
+// Secret redaction
+// ===========================================================================
+
+/// Env key patterns whose values are redacted by default.
+pub const DEFAULT_SECRET_PATTERNS: &[&str] = &["*_TOKEN", "*_KEY", "*_SECRET"];
+
+/// Placeholder shown instead of a redacted value.
+pub const REDACTED: &str = "<redacted>";
+
+/// Key patterns whose values are hidden when configs are displayed.
+///
+/// Patterns are matched case-insensitively against the whole key, and `*`
+/// matches any run of characters. Redaction is for display only; configs
+/// written to tools always carry the real values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretRedaction {
+    patterns: Vec<String>,
+}
+
+impl Default for SecretRedaction {
+    fn default() -> Self {
+        Self::new(DEFAULT_SECRET_PATTERNS.iter().copied())
+    }
+}
+
+impl SecretRedaction {
+    /// Redact keys matching any of `patterns`.
+    pub fn new(patterns: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            patterns: patterns.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Add more patterns on top of the current ones.
+    pub fn with_patterns(mut self, patterns: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.patterns.extend(patterns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Whether the value of `key` should be hidden.
+    pub fn is_secret(&self, key: &str) -> bool {
+        let key = key.to_ascii_uppercase();
+        self.patterns
+            .iter()
+            .any(|pattern| wildcard_match(&pattern.to_ascii_uppercase(), &key))
+    }
+
+    /// Replace string values under secret keys, at any depth, with
+    /// [`REDACTED`].
+    pub fn redact_json(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if value.is_string() && self.is_secret(key) {
+                        *value = serde_json::Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_json(value);
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => {
+                items.iter_mut().for_each(|item| self.redact_json(item));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Match `text` against `pattern`, where `*` matches any run of characters.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let Some(mut text) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let last = parts.pop().unwrap_or_default();
+    for part in parts {
+        match text.find(part) {
+            Some(at) => text = &text[at + part.len()..],
+            None => return false,
+        }
+    }
+    text.len() >= last.len() && text.ends_with(last)
+}
+
 // ===========================================================================
 // Operation result types
 // ===========================================================================
@@ -377,6 +511,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("\"type\":\"stdio\""));
@@ -392,6 +527,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("\"type\":\"http\""));
@@ -410,6 +546,33 @@ mod tests {
         assert_ne!(McpEnvSyntax::DollarSign, McpEnvSyntax::VsCodeInput);
     }
 
+    #[test]
+    fn test_secret_redaction_patterns() {
+        let redaction = SecretRedaction::default();
+        assert!(redaction.is_secret("GITHUB_TOKEN"));
+        assert!(redaction.is_secret("openai_api_key"));
+        assert!(redaction.is_secret("CLIENT_SECRET"));
+        assert!(!redaction.is_secret("TOKEN"));
+        assert!(!redaction.is_secret("LOG_LEVEL"));
+
+        let custom = SecretRedaction::new(["DB_*_PASSWORD"]);
+        assert!(custom.is_secret("DB_MAIN_PASSWORD"));
+        assert!(!custom.is_secret("DB_PASSWORD_HINT"));
+    }
+
+    #[test]
+    fn test_secret_redaction_json() {
+        let mut value = serde_json::json!({
+            "command": "npx",
+            "args": ["server"],
+            "env": {"GITHUB_TOKEN": "ghp_123", "LOG_LEVEL": "debug"},
+        });
+        SecretRedaction::default().redact_json(&mut value);
+        assert_eq!(value["env"]["GITHUB_TOKEN"], REDACTED);
+        assert_eq!(value["env"]["LOG_LEVEL"], "debug");
+        assert_eq!(value["command"], "npx");
+    }
+
     #[test]
     fn test_mcp_scope() {
         assert_ne!(McpScope::Project, McpScope::User);
@@ -425,6 +588,7 @@ mod tests {
             },
             env: None,
             auto_approve: true,
+            expand_env: false,
         };
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("\"auto_approve\":true"));
@@ -438,6 +602,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json2 = serde_json::to_string(&config2).unwrap();
         assert!(!json2.contains("auto_approve"));
@@ -485,6 +650,7 @@ mod tests {
             },
             env: None,
             auto_approve: true,
+            expand_env: false,
         };
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("\"auto_approve\":true"));
@@ -500,6 +666,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = serde_json::to_string(&config).unwrap();
         assert!(!json.contains("auto_approve"));
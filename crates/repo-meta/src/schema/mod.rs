@@ -5,19 +5,26 @@
 //!
 //! - `.repository/tools/*.toml` - Tool definitions
 //! - `.repository/rules/*.toml` - Rule definitions
+//! - `.repository/rules/*.md` - Markdown rules, with optional front-matter
 //! - `.repository/presets/*.toml` - Preset definitions
 
+pub mod front_matter;
 pub mod mcp;
 pub mod preset;
 pub mod rule;
 pub mod tool;
 
+pub use front_matter::{
+    FrontMatterMode, RuleFrontMatter, RuleMarkdown, compose_rule_markdown, parse_rule_markdown,
+};
 pub use mcp::{
-    McpConfigEmbedding, McpConfigSpec, McpEnvSyntax, McpFieldMappings, McpScope, McpServerConfig,
-    McpSyncResult, McpTransport, McpTransportConfig, McpTypeValues, McpUserPath, McpVerifyResult,
+    DEFAULT_SECRET_PATTERNS, McpConfigEmbedding, McpConfigFormat, McpConfigSpec, McpEnvSyntax,
+    McpFieldMappings, McpScope, McpServerConfig, McpStdioShape, McpSyncResult, McpTransport,
+    McpTransportConfig, McpTypeValues, McpUserPath, McpVerifyResult, REDACTED, SecretRedaction,
 };
 pub use preset::{PresetDefinition, PresetMeta, PresetRequires, PresetRules};
 pub use rule::{RuleContent, RuleDefinition, RuleExamples, RuleMeta, RuleTargets, Severity};
 pub use tool::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta, ToolSchemaKeys,
+    ConfigType, DEFAULT_DIRECTORY_PATTERN, RulesLayout, ToolCapabilities, ToolDefinition,
+    ToolIntegrationConfig, ToolMeta, ToolSchemaKeys, WriterStrategy,
 };
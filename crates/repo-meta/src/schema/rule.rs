@@ -50,6 +50,9 @@ pub struct RuleMeta {
     /// Tags for categorization and filtering
     #[serde(default)]
     pub tags: Vec<String>,
+    /// IDs of other rules this rule builds on
+    #[serde(default)]
+    pub requires: Vec<String>,
 }
 
 /// Rule severity level
@@ -10,6 +10,8 @@
 //! name = "Cursor"
 //! slug = "cursor"
 //! description = "AI-first code editor"
+//! # Replace the built-in integration for this slug
+//! override = true
 //!
 //! [integration]
 //! config_path = ".cursorrules"
@@ -42,6 +44,134 @@ pub struct ToolDefinition {
     pub schema_keys: Option<ToolSchemaKeys>,
 }
 
+impl ToolDefinition {
+    /// Render as a commented TOML file for `.repository/tools/`
+    ///
+    /// Every field is written out with a comment explaining it; unset
+    /// optional fields are left commented out so they can be filled in.
+    pub fn to_commented_toml(&self) -> String {
+        let mut out = String::new();
+        let mut line = |text: String| {
+            out.push_str(&text);
+            out.push('\n');
+        };
+        let optional = |key: &str, value: &Option<String>, example: &str| match value {
+            Some(v) => format!("{} = {}", key, quote(v)),
+            None => format!("# {} = {}", key, quote(example)),
+        };
+
+        line(format!("# Tool definition for {}", self.meta.name));
+        line(format!(
+            "# Save as .repository/tools/{}.toml; it is loaded on the next sync.",
+            self.meta.slug
+        ));
+        line(String::new());
+
+        line("[meta]".into());
+        line("# Display name".into());
+        line(format!("name = {}", quote(&self.meta.name)));
+        line("# Identifier used in config.toml `tools = [...]`".into());
+        line(format!("slug = {}", quote(&self.meta.slug)));
+        line("# One-line description".into());
+        line(optional("description", &self.meta.description, "My tool"));
+        line("# Replace the built-in tool with the same slug".into());
+        line(if self.meta.override_builtin {
+            "override = true".into()
+        } else {
+            "# override = true".into()
+        });
+        line("# Command `repo open` runs; {path} is the worktree directory".into());
+        line(optional(
+            "open_command",
+            &self.meta.open_command,
+            "mytool {path}",
+        ));
+        line(String::new());
+
+        let integration = &self.integration;
+        line("[integration]".into());
+        line("# Primary config file, relative to the repository root".into());
+        line(format!("config_path = {}", quote(&integration.config_path)));
+        line("# File format: \"text\", \"json\", \"toml\", \"yaml\", \"markdown\", or".into());
+        line("# { directory = { pattern = \"{nn}-{id}.md\" } } for one file per rule".into());
+        line(format!("type = {}", inline_value(&integration.config_type)));
+        line("# Other files or directories the tool reads".into());
+        line(format!(
+            "additional_paths = {}",
+            inline_value(&integration.additional_paths)
+        ));
+        line("# \"single\": all rules in config_path as managed blocks;".into());
+        line("# \"per-file\": one file per rule inside config_path".into());
+        line(format!(
+            "rules_layout = {}",
+            inline_value(&integration.rules_layout)
+        ));
+        line("# File name template for the per-file layout".into());
+        line(optional(
+            "rules_filename",
+            &integration.rules_filename,
+            "{index:02}-{id}.md",
+        ));
+        line("# How rules are merged into config_path, instead of by type:".into());
+        line("# { strategy = \"json-merge\", target_path = \"ai.rules\" },".into());
+        line("# { strategy = \"markdown-section\", heading = \"AI Rules\" }, or".into());
+        line("# { strategy = \"text-replace\" } to replace the whole file".into());
+        line(match &integration.writer {
+            Some(writer) => format!("writer = {}", inline_value(writer)),
+            None => "# writer = { strategy = \"text-replace\" }".into(),
+        });
+        line(String::new());
+
+        let capabilities = &self.capabilities;
+        line("[capabilities]".into());
+        line("# The tool reads custom instructions / rules".into());
+        line(format!(
+            "supports_custom_instructions = {}",
+            capabilities.supports_custom_instructions
+        ));
+        line("# The tool reads MCP server configuration".into());
+        line(format!("supports_mcp = {}", capabilities.supports_mcp));
+        line("# The tool reads a rules directory".into());
+        line(format!(
+            "supports_rules_directory = {}",
+            capabilities.supports_rules_directory
+        ));
+        line(String::new());
+
+        let keys = self.schema_keys.clone().unwrap_or_default();
+        line("# Keys to write into structured (json, toml, yaml) config files".into());
+        line("[schema]".into());
+        line("# Key for custom instructions".into());
+        line(optional(
+            "instruction_key",
+            &keys.instruction_key,
+            "instructions",
+        ));
+        line("# Key for MCP servers".into());
+        line(optional("mcp_key", &keys.mcp_key, "mcpServers"));
+        line("# Key for the Python interpreter path".into());
+        line(optional(
+            "python_path_key",
+            &keys.python_path_key,
+            "python.defaultInterpreterPath",
+        ));
+
+        out
+    }
+}
+
+/// Quote a string as a TOML basic string.
+fn quote(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+/// Render a serializable value as an inline TOML value.
+fn inline_value<T: Serialize>(value: &T) -> String {
+    toml::Value::try_from(value)
+        .map(|v| v.to_string())
+        .unwrap_or_default()
+}
+
 /// Basic metadata about a tool
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ToolMeta {
@@ -52,6 +182,22 @@ pub struct ToolMeta {
     /// Optional description of the tool
     #[serde(default)]
     pub description: Option<String>,
+    /// Replace the built-in integration with the same slug
+    ///
+    /// Without this, a repository definition whose slug matches a built-in
+    /// tool is ignored, so a copied definition can't shadow it by accident.
+    #[serde(
+        default,
+        rename = "override",
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub override_builtin: bool,
+    /// Command launching the tool in a directory, used by `repo open`
+    ///
+    /// `{path}` is replaced by the worktree path, e.g. `"code {path}"` or
+    /// `"cursor --new-window {path}"`; without it the path is appended.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_command: Option<String>,
 }
 
 /// Configuration for how to integrate with the tool
@@ -65,10 +211,89 @@ pub struct ToolIntegrationConfig {
     /// Additional config paths (e.g., directories like ".cursor/rules/")
     #[serde(default)]
     pub additional_paths: Vec<String>,
+    /// How rules are laid out at `config_path`
+    #[serde(default)]
+    pub rules_layout: RulesLayout,
+    /// File name template for the `per-file` layout (e.g., `"{index:02}-{id}.md"`)
+    ///
+    /// Defaults to [`DEFAULT_DIRECTORY_PATTERN`] when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules_filename: Option<String>,
+    /// How rules are merged into `config_path`
+    ///
+    /// Overrides the writer chosen by `type` when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub writer: Option<WriterStrategy>,
 }
 
-/// Configuration file format types
+impl ToolIntegrationConfig {
+    /// Per-rule file name pattern, if rules are written one file per rule.
+    ///
+    /// Set either by a [`ConfigType::Directory`] type or by
+    /// `rules_layout = "per-file"`.
+    pub fn rule_file_pattern(&self) -> Option<&str> {
+        match (&self.config_type, self.rules_layout) {
+            (ConfigType::Directory { pattern }, _) => Some(pattern),
+            (_, RulesLayout::PerFile) => Some(
+                self.rules_filename
+                    .as_deref()
+                    .unwrap_or(DEFAULT_DIRECTORY_PATTERN),
+            ),
+            (_, RulesLayout::Single) => None,
+        }
+    }
+}
+
+/// How rules are laid out in a tool's config
+///
+/// ```toml
+/// [integration]
+/// config_path = ".agent/rules"
+/// type = "markdown"
+/// rules_layout = "per-file"
+/// rules_filename = "{index:02}-{id}.md"
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum RulesLayout {
+    /// All rules go into the single config file as managed blocks
+    #[default]
+    Single,
+    /// One file per rule inside the `config_path` directory
+    PerFile,
+}
+
+/// How rules are merged into a tool's config file
+///
+/// ```toml
+/// [integration]
+/// config_path = ".tool/settings.json"
+/// type = "json"
+/// writer = { strategy = "json-merge", target_path = "ai.rules" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "strategy", rename_all = "kebab-case")]
+pub enum WriterStrategy {
+    /// Merge into a JSON file, keeping the keys already there
+    JsonMerge {
+        /// Dotted path of the key receiving the rules (e.g., `"ai.rules"`)
+        ///
+        /// Missing objects along the path are created. Defaults to the
+        /// `instruction_key` schema key.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target_path: Option<String>,
+    },
+    /// Keep the rules in a managed block under a `##` section
+    MarkdownSection {
+        /// Section heading, without the `##`; the section is created if absent
+        heading: String,
+    },
+    /// Replace the whole file with the rules
+    TextReplace,
+}
+
+/// Configuration file format types
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ConfigType {
     /// Plain text file (e.g., .cursorrules)
@@ -82,6 +307,28 @@ pub enum ConfigType {
     Yaml,
     /// Markdown format (e.g., CLAUDE.md)
     Markdown,
+    /// Directory with one file per rule (e.g., `.agent/rules/`)
+    ///
+    /// `pattern` names each file; `{nn}` expands to the two-digit rule
+    /// position, `{index}` / `{index:0N}` to the position zero-padded to
+    /// `N` digits, and `{id}` to the sanitized rule id. Positions start at 1.
+    ///
+    /// ```toml
+    /// [integration]
+    /// config_path = ".agent/rules/"
+    /// type = { directory = { pattern = "{nn}-{id}.md" } }
+    /// ```
+    Directory {
+        #[serde(default = "default_directory_pattern")]
+        pattern: String,
+    },
+}
+
+/// Default per-rule file name pattern for [`ConfigType::Directory`].
+pub const DEFAULT_DIRECTORY_PATTERN: &str = "{nn}-{id}.md";
+
+fn default_directory_pattern() -> String {
+    DEFAULT_DIRECTORY_PATTERN.to_string()
 }
 
 /// Tool capabilities flags
@@ -145,6 +392,8 @@ type = "text"
         assert_eq!(def.meta.name, "Cursor");
         assert_eq!(def.meta.slug, "cursor");
         assert!(def.meta.description.is_none());
+        assert!(!def.meta.override_builtin);
+        assert!(def.meta.open_command.is_none());
         assert_eq!(def.integration.config_path, ".cursorrules");
         assert_eq!(def.integration.config_type, ConfigType::Text);
         assert!(!def.capabilities.supports_custom_instructions);
@@ -158,6 +407,8 @@ type = "text"
 name = "Cursor"
 slug = "cursor"
 description = "AI-first code editor"
+override = true
+open_command = "cursor --new-window {path}"
 
 [integration]
 config_path = ".cursorrules"
@@ -180,6 +431,11 @@ mcp_key = "mcpServers"
             def.meta.description,
             Some("AI-first code editor".to_string())
         );
+        assert!(def.meta.override_builtin);
+        assert_eq!(
+            def.meta.open_command.as_deref(),
+            Some("cursor --new-window {path}")
+        );
         assert_eq!(def.integration.config_type, ConfigType::Text);
         assert_eq!(def.integration.additional_paths, vec![".cursor/rules/"]);
         assert!(def.capabilities.supports_custom_instructions);
@@ -267,4 +523,154 @@ type = "{}"
             assert_eq!(def.integration.config_type, expected);
         }
     }
+
+    #[test]
+    fn test_config_type_directory() {
+        let toml = r#"
+[meta]
+name = "Antigravity"
+slug = "antigravity"
+
+[integration]
+config_path = ".agent/rules/"
+type = { directory = { pattern = "rule-{id}.md" } }
+"#;
+
+        let def: ToolDefinition = toml::from_str(toml).unwrap();
+        assert_eq!(
+            def.integration.config_type,
+            ConfigType::Directory {
+                pattern: "rule-{id}.md".to_string()
+            }
+        );
+
+        // Pattern is optional
+        let toml = toml.replace(r#"{ pattern = "rule-{id}.md" }"#, "{}");
+        let def: ToolDefinition = toml::from_str(&toml).unwrap();
+        assert_eq!(
+            def.integration.config_type,
+            ConfigType::Directory {
+                pattern: DEFAULT_DIRECTORY_PATTERN.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_rules_layout_per_file() {
+        let toml = r#"
+[meta]
+name = "Antigravity"
+slug = "antigravity"
+
+[integration]
+config_path = ".agent/rules"
+type = "markdown"
+rules_layout = "per-file"
+rules_filename = "{index:02}-{id}.md"
+"#;
+
+        let def: ToolDefinition = toml::from_str(toml).unwrap();
+        assert_eq!(def.integration.rules_layout, RulesLayout::PerFile);
+        assert_eq!(
+            def.integration.rule_file_pattern(),
+            Some("{index:02}-{id}.md")
+        );
+
+        // Template falls back to the default pattern
+        let toml = toml.replace("rules_filename = \"{index:02}-{id}.md\"\n", "");
+        let def: ToolDefinition = toml::from_str(&toml).unwrap();
+        assert_eq!(
+            def.integration.rule_file_pattern(),
+            Some(DEFAULT_DIRECTORY_PATTERN)
+        );
+
+        // Single-file tools have no per-rule pattern
+        let toml = toml.replace("rules_layout = \"per-file\"\n", "");
+        let def: ToolDefinition = toml::from_str(&toml).unwrap();
+        assert_eq!(def.integration.rules_layout, RulesLayout::Single);
+        assert_eq!(def.integration.rule_file_pattern(), None);
+    }
+
+    #[test]
+    fn test_writer_strategy() {
+        let toml = r#"
+[meta]
+name = "Tool"
+slug = "tool"
+
+[integration]
+config_path = ".tool/settings.json"
+type = "json"
+writer = { strategy = "json-merge", target_path = "ai.rules" }
+"#;
+        let def: ToolDefinition = toml::from_str(toml).unwrap();
+        assert_eq!(
+            def.integration.writer,
+            Some(WriterStrategy::JsonMerge {
+                target_path: Some("ai.rules".into())
+            })
+        );
+
+        let toml = toml.replace(
+            "{ strategy = \"json-merge\", target_path = \"ai.rules\" }",
+            "{ strategy = \"text-replace\" }",
+        );
+        let def: ToolDefinition = toml::from_str(&toml).unwrap();
+        assert_eq!(def.integration.writer, Some(WriterStrategy::TextReplace));
+
+        // A markdown section needs its heading
+        let toml = toml.replace("\"text-replace\"", "\"markdown-section\"");
+        assert!(toml::from_str::<ToolDefinition>(&toml).is_err());
+    }
+
+    #[test]
+    fn test_commented_toml_round_trips() {
+        let def = ToolDefinition {
+            meta: ToolMeta {
+                name: "Team \"Cursor\"".into(),
+                slug: "cursor".into(),
+                description: None,
+                override_builtin: true,
+                open_command: None,
+            },
+            integration: ToolIntegrationConfig {
+                config_path: ".cursor/rules/".into(),
+                config_type: ConfigType::Directory {
+                    pattern: "{id}.mdc".into(),
+                },
+                additional_paths: vec![".cursorrules".into()],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: Some(WriterStrategy::MarkdownSection {
+                    heading: "AI Rules".into(),
+                }),
+            },
+            capabilities: ToolCapabilities {
+                supports_custom_instructions: true,
+                supports_mcp: false,
+                supports_rules_directory: true,
+            },
+            schema_keys: Some(ToolSchemaKeys {
+                mcp_key: Some("mcpServers".into()),
+                ..Default::default()
+            }),
+        };
+
+        let text = def.to_commented_toml();
+        assert!(text.contains("# Display name"));
+        assert!(text.contains("# description = \"My tool\""));
+        assert!(text.contains("# open_command = \"mytool {path}\""));
+
+        let parsed: ToolDefinition = toml::from_str(&text).unwrap();
+        assert_eq!(parsed.meta.name, "Team \"Cursor\"");
+        assert!(parsed.meta.override_builtin);
+        assert!(parsed.meta.description.is_none());
+        assert_eq!(parsed.integration.config_type, def.integration.config_type);
+        assert_eq!(parsed.integration.additional_paths, vec![".cursorrules"]);
+        assert_eq!(parsed.integration.writer, def.integration.writer);
+        assert!(parsed.capabilities.supports_rules_directory);
+        let keys = parsed.schema_keys.unwrap();
+        assert_eq!(keys.mcp_key.as_deref(), Some("mcpServers"));
+        assert!(keys.instruction_key.is_none());
+    }
 }
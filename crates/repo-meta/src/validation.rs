@@ -16,6 +16,7 @@ impl KnownToolSlugs {
             "claude",
             "claude_desktop",
             "cline",
+            "codex",
             "copilot",
             "cursor",
             "gemini",
@@ -229,6 +229,71 @@ name = "Invalid"
     assert!(result.warnings[0].contains("invalid.toml"));
 }
 
+#[test]
+fn test_loader_reports_path_and_line_of_invalid_definition() {
+    let temp = TempDir::new().unwrap();
+    let tools_dir = temp.path().join(".repository").join("tools");
+    fs::create_dir_all(&tools_dir).unwrap();
+
+    // `type` is misspelled, so line 7 has an unknown variant
+    fs::write(
+        tools_dir.join("broken.toml"),
+        r#"[meta]
+name = "Broken"
+slug = "broken"
+
+[integration]
+config_path = ".broken"
+type = "txt"
+"#,
+    )
+    .unwrap();
+
+    let loader = DefinitionLoader::new();
+    let result = loader
+        .load_tools(&NormalizedPath::new(temp.path()))
+        .unwrap();
+
+    assert!(result.definitions.is_empty());
+    assert_eq!(result.errors.len(), 1);
+    match &result.errors[0] {
+        repo_meta::Error::DefinitionInvalid {
+            path,
+            message,
+            line,
+        } => {
+            assert!(path.ends_with("broken.toml"));
+            assert_eq!(*line, Some(7));
+            assert!(message.contains("txt"), "{}", message);
+        }
+        other => panic!("Unexpected error: {:?}", other),
+    }
+    assert!(
+        result.warnings[0].contains("broken.toml:7"),
+        "{}",
+        result.warnings[0]
+    );
+}
+
+#[test]
+fn test_load_rules_skips_rule_registry() {
+    let temp = TempDir::new().unwrap();
+    let rules_dir = temp.path().join(".repository").join("rules");
+    fs::create_dir_all(&rules_dir).unwrap();
+    fs::write(
+        rules_dir.join("registry.toml"),
+        "version = \"1.0\"\nrules = []\n",
+    )
+    .unwrap();
+
+    let loader = DefinitionLoader::new();
+    let result = loader
+        .load_rules(&NormalizedPath::new(temp.path()))
+        .unwrap();
+    assert!(result.definitions.is_empty());
+    assert!(result.warnings.is_empty());
+}
+
 #[test]
 fn test_loader_returns_empty_for_nonexistent_directory() {
     let temp = TempDir::new().unwrap();
@@ -251,3 +316,115 @@ fn test_loader_returns_empty_for_nonexistent_directory() {
         .unwrap();
     assert!(result.definitions.is_empty());
 }
+
+#[test]
+fn test_loader_caches_definitions_by_mtime() {
+    let temp = TempDir::new().unwrap();
+    let tools_dir = temp.path().join(".repository").join("tools");
+    fs::create_dir_all(&tools_dir).unwrap();
+    let path = tools_dir.join("cursor.toml");
+    let root = NormalizedPath::new(temp.path());
+    let tool = |name: &str| {
+        format!(
+            "[meta]\nname = \"{}\"\nslug = \"cursor\"\n\n[integration]\nconfig_path = \".cursorrules\"\ntype = \"text\"\n",
+            name
+        )
+    };
+    let set_mtime = |mtime: std::time::SystemTime| {
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+    };
+
+    fs::write(&path, tool("Cursor")).unwrap();
+    let mtime = fs::metadata(&path).unwrap().modified().unwrap();
+    let loader = DefinitionLoader::new();
+    let name = |loader: &DefinitionLoader| {
+        loader.load_tools(&root).unwrap().definitions["cursor"]
+            .meta
+            .name
+            .clone()
+    };
+    assert_eq!(name(&loader), "Cursor");
+
+    // Same size and mtime: the untouched file is served from cache,
+    // even by a new loader
+    fs::write(&path, tool("Cursed")).unwrap();
+    set_mtime(mtime);
+    assert_eq!(name(&DefinitionLoader::new()), "Cursor");
+
+    // A newer mtime triggers a reload
+    set_mtime(mtime + std::time::Duration::from_secs(10));
+    assert_eq!(name(&loader), "Cursed");
+
+    // Invalidation forces a reload regardless of mtime
+    fs::write(&path, tool("Cursor")).unwrap();
+    set_mtime(mtime + std::time::Duration::from_secs(10));
+    assert_eq!(name(&loader), "Cursed");
+    DefinitionLoader::invalidate();
+    assert_eq!(name(&loader), "Cursor");
+}
+
+#[test]
+fn test_load_extension_tools() {
+    let temp = TempDir::new().unwrap();
+    let extensions = temp.path().join(".repository").join("extensions");
+    let tool = |slug: &str, name: &str| {
+        format!(
+            "[meta]\nname = \"{name}\"\nslug = \"{slug}\"\n\n\
+             [integration]\nconfig_path = \".{slug}.md\"\ntype = \"markdown\"\n"
+        )
+    };
+    let install = |name: &str, provides: &str, files: &[(&str, String)]| {
+        let dir = extensions.join(name);
+        fs::create_dir_all(dir.join("tools")).unwrap();
+        fs::write(
+            dir.join("repo_extension.toml"),
+            format!(
+                "[extension]\nname = \"{name}\"\nversion = \"0.1.0\"\n\n[provides]\n{provides}\n"
+            ),
+        )
+        .unwrap();
+        for (file, content) in files {
+            fs::write(dir.join(file), content).unwrap();
+        }
+    };
+
+    install(
+        "a-kit",
+        "tools = [\"kittool\"]\ntool_definitions = [\"tools/kittool.toml\", \"tools/unlisted.toml\"]",
+        &[
+            ("tools/kittool.toml", tool("kittool", "Kit Tool")),
+            ("tools/unlisted.toml", tool("unlisted", "Unlisted")),
+        ],
+    );
+    // The first extension by name wins a shared slug
+    install(
+        "b-kit",
+        "tools = [\"kittool\"]\ntool_definitions = [\"tools/kittool.toml\", \"../a-kit/tools/unlisted.toml\"]",
+        &[("tools/kittool.toml", tool("kittool", "Other Kit Tool"))],
+    );
+    // Extensions without tool definitions contribute nothing
+    install("c-kit", "tools = [\"vscode\"]", &[]);
+
+    let loaded = DefinitionLoader::new()
+        .load_extension_tools(&NormalizedPath::new(temp.path()))
+        .unwrap();
+    assert_eq!(loaded.definitions.len(), 1);
+    assert_eq!(loaded.definitions["kittool"].meta.name, "Kit Tool");
+    assert_eq!(loaded.warnings.len(), 2, "{:?}", loaded.warnings);
+    assert!(loaded.warnings[0].contains("'unlisted' is not listed"));
+    assert!(loaded.warnings[1].contains("is not inside the extension"));
+
+    // Repository definitions are loaded separately
+    assert!(
+        DefinitionLoader::new()
+            .load_tools(&NormalizedPath::new(temp.path()))
+            .unwrap()
+            .definitions
+            .is_empty()
+    );
+}
@@ -3,6 +3,9 @@
 use repo_fs::{NormalizedPath, WorkspaceLayout};
 use std::collections::HashMap;
 
+/// Files marking the root of a Python package
+pub const PYTHON_MANIFESTS: &[&str] = &["pyproject.toml", "setup.py", "setup.cfg"];
+
 /// Context passed to providers for check/apply operations
 #[derive(Debug, Clone)]
 pub struct Context {
@@ -11,16 +14,20 @@ pub struct Context {
     pub config: HashMap<String, toml::Value>,
     /// Optional tag for venv naming (e.g., "main-win-py311")
     pub venv_tag: Option<String>,
+    /// Directory the operation was started from (e.g., a monorepo sub-package)
+    pub working_dir: NormalizedPath,
 }
 
 impl Context {
     pub fn new(layout: WorkspaceLayout, config: HashMap<String, toml::Value>) -> Self {
         let root = layout.root.clone();
+        let working_dir = layout.active_context.clone();
         Self {
             layout,
             root,
             config,
             venv_tag: None,
+            working_dir,
         }
     }
 
@@ -30,6 +37,45 @@ impl Context {
         self
     }
 
+    /// Create a context started from `dir` instead of the active context
+    pub fn with_working_dir(mut self, dir: NormalizedPath) -> Self {
+        self.working_dir = dir;
+        self
+    }
+
+    /// Find the nearest directory containing one of `markers`
+    ///
+    /// Walks up from the working directory to the root (inclusive) and
+    /// returns the first directory holding any of the marker files. Falls
+    /// back to the root if none is found or the working directory lies
+    /// outside the root.
+    pub fn package_root(&self, markers: &[&str]) -> NormalizedPath {
+        let root = self.root.as_str().trim_end_matches('/');
+        let mut dir = self.working_dir.clone();
+        loop {
+            let current = dir.as_str().trim_end_matches('/');
+            if current != root && !current.starts_with(&format!("{}/", root)) {
+                break;
+            }
+            if markers.iter().any(|marker| dir.join(marker).exists()) {
+                return dir;
+            }
+            if current == root {
+                break;
+            }
+            match dir.parent() {
+                Some(parent) => dir = parent,
+                None => break,
+            }
+        }
+        self.root.clone()
+    }
+
+    /// Root of the Python package the working directory belongs to
+    pub fn python_root(&self) -> NormalizedPath {
+        self.package_root(PYTHON_MANIFESTS)
+    }
+
     pub fn get_string(&self, key: &str) -> Option<String> {
         self.config
             .get(key)
@@ -51,10 +97,12 @@ impl Context {
     /// Returns:
     /// - `.venv` if no tag is set
     /// - `.venv-{tag}` if a tag is set
+    ///
+    /// The venv lives in the nearest Python package (see [`Self::python_root`]).
     pub fn venv_path(&self) -> NormalizedPath {
         match &self.venv_tag {
-            Some(tag) => self.root.join(&format!(".venv-{}", tag)),
-            None => self.root.join(".venv"),
+            Some(tag) => self.tagged_venv_path(tag),
+            None => self.python_root().join(".venv"),
         }
     }
 
@@ -62,7 +110,7 @@ impl Context {
     ///
     /// Returns `.venv-{tag}` for the given tag.
     pub fn tagged_venv_path(&self, tag: &str) -> NormalizedPath {
-        self.root.join(&format!(".venv-{}", tag))
+        self.python_root().join(&format!(".venv-{}", tag))
     }
 }
 
@@ -120,4 +168,37 @@ mod tests {
         let ctx = ctx.with_venv_tag("test-tag");
         assert_eq!(ctx.venv_tag, Some("test-tag".to_string()));
     }
+
+    #[test]
+    fn test_package_root_finds_nearest_manifest() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("pyproject.toml"), "").unwrap();
+        std::fs::create_dir_all(temp.path().join("packages/api/src")).unwrap();
+        std::fs::write(temp.path().join("packages/api/pyproject.toml"), "").unwrap();
+
+        let ctx = make_test_context(&temp, None)
+            .with_working_dir(NormalizedPath::new(temp.path().join("packages/api/src")));
+
+        assert_eq!(
+            ctx.python_root(),
+            NormalizedPath::new(temp.path().join("packages/api"))
+        );
+    }
+
+    #[test]
+    fn test_package_root_falls_back_to_root() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("docs")).unwrap();
+        let root = NormalizedPath::new(temp.path());
+
+        let ctx = make_test_context(&temp, None)
+            .with_working_dir(NormalizedPath::new(temp.path().join("docs")));
+        assert_eq!(ctx.python_root(), root);
+
+        // A working directory outside the root is ignored
+        let outside = TempDir::new().unwrap();
+        std::fs::write(outside.path().join("pyproject.toml"), "").unwrap();
+        let ctx = ctx.with_working_dir(NormalizedPath::new(outside.path()));
+        assert_eq!(ctx.python_root(), root);
+    }
 }
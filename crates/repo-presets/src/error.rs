@@ -32,4 +32,7 @@ pub enum Error {
 
     #[error("Preset check failed: {message}")]
     CheckFailed { message: String },
+
+    #[error("Rollback failed: {message}")]
+    RollbackFailed { message: String },
 }
@@ -5,7 +5,9 @@
 
 pub mod context;
 pub mod error;
+pub mod lockfile;
 pub mod node;
+mod process;
 pub mod provider;
 pub mod python;
 pub mod rust;
@@ -14,7 +16,8 @@ pub use context::Context;
 pub use error::{Error, Result};
 pub use node::NodeProvider;
 pub use provider::{
-    ActionType, ApplyReport, ApplyStatus, PresetCheckReport, PresetProvider, PresetStatus,
+    ActionType, AppliedAction, ApplyReport, ApplyStatus, PresetCheckReport, PresetProvider,
+    PresetStatus, rollback_actions,
 };
-pub use python::{UvProvider, VenvProvider};
+pub use python::{PoetryProvider, UvProvider, VenvProvider};
 pub use rust::RustProvider;
@@ -0,0 +1,110 @@
+//! Lock file freshness checks
+//!
+//! An installed environment is out of date when a lock file was modified
+//! after the package manager last installed from it. Package managers touch
+//! a marker inside the environment on every install (e.g. npm's
+//! `node_modules/.package-lock.json`), so comparing modification times tells
+//! "there but needs refresh" apart from "up to date".
+
+use repo_fs::NormalizedPath;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Lock files recording resolved Python dependencies
+pub const PYTHON_LOCKS: &[&str] = &["uv.lock", "poetry.lock", "pylock.toml", "requirements.txt"];
+
+/// Lock files recording resolved Node.js dependencies
+pub const NODE_LOCKS: &[&str] = &[
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lock",
+];
+
+/// Markers npm, pnpm and yarn update in `node_modules` on install
+pub const NODE_INSTALL_MARKERS: &[&str] = &[
+    ".package-lock.json",
+    ".modules.yaml",
+    ".yarn-state.yml",
+    ".yarn-integrity",
+];
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Name of the first lock file in `dir` modified after every path in `installed`
+///
+/// The newest modification time among the existing `installed` paths is
+/// taken as the time of the last install. Returns `None` if no lock file
+/// exists or none of `installed` does.
+pub fn newer_lock<'a>(
+    dir: &NormalizedPath,
+    locks: &[&'a str],
+    installed: &[NormalizedPath],
+) -> Option<&'a str> {
+    let installed_at = installed
+        .iter()
+        .filter_map(|path| modified(path.as_ref()))
+        .max()?;
+    locks.iter().copied().find(|lock| {
+        modified(dir.join(lock).as_ref()).is_some_and(|changed| changed > installed_at)
+    })
+}
+
+/// Site-packages directory of the virtual environment at `venv`, if present
+pub fn site_packages(venv: &NormalizedPath) -> Option<NormalizedPath> {
+    if cfg!(windows) {
+        let dir = venv.join("Lib").join("site-packages");
+        return dir.exists().then_some(dir);
+    }
+    let lib = std::fs::read_dir(venv.join("lib").as_ref()).ok()?;
+    lib.filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("python"))
+        .map(|entry| NormalizedPath::new(entry.path().join("site-packages")))
+        .find(|dir| dir.exists())
+}
+
+/// Paths a Python installer updates in the virtual environment at `venv`
+pub fn venv_install_markers(venv: &NormalizedPath) -> Vec<NormalizedPath> {
+    let mut markers = vec![venv.join("pyvenv.cfg")];
+    markers.extend(site_packages(venv));
+    markers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{self, File};
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn touch(path: &Path, time: SystemTime) {
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_newer_lock() {
+        let temp = TempDir::new().unwrap();
+        let dir = NormalizedPath::new(temp.path());
+        let now = SystemTime::now();
+        fs::write(temp.path().join("uv.lock"), "").unwrap();
+        fs::write(temp.path().join("marker"), "").unwrap();
+        let marker = vec![dir.join("marker")];
+
+        touch(&temp.path().join("uv.lock"), now - Duration::from_secs(60));
+        assert_eq!(newer_lock(&dir, PYTHON_LOCKS, &marker), None);
+
+        touch(&temp.path().join("uv.lock"), now + Duration::from_secs(60));
+        assert_eq!(newer_lock(&dir, PYTHON_LOCKS, &marker), Some("uv.lock"));
+
+        // Without an install marker there is nothing to compare against
+        assert_eq!(newer_lock(&dir, PYTHON_LOCKS, &[dir.join("absent")]), None);
+    }
+}
@@ -1,21 +1,112 @@
 //! Node.js environment detection provider
 
 use crate::context::Context;
-use crate::error::Result;
-use crate::provider::{ActionType, ApplyReport, PresetCheckReport, PresetProvider, PresetStatus};
+use crate::error::{Error, Result};
+use crate::lockfile::{NODE_INSTALL_MARKERS, NODE_LOCKS, newer_lock};
+use crate::process;
+use crate::provider::{
+    ActionType, AppliedAction, ApplyReport, PresetCheckReport, PresetProvider, PresetStatus,
+};
 use async_trait::async_trait;
+use repo_fs::NormalizedPath;
+use std::fmt;
 use std::process::Stdio;
 use tokio::process::Command;
 
-/// Provider for detecting Node.js environments.
+/// Node.js package manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Npm,
+    Pnpm,
+    Yarn,
+}
+
+impl PackageManager {
+    /// Lock file each package manager writes, in order of preference
+    const LOCK_FILES: &[(&str, PackageManager)] = &[
+        ("pnpm-lock.yaml", PackageManager::Pnpm),
+        ("yarn.lock", PackageManager::Yarn),
+        ("package-lock.json", PackageManager::Npm),
+        ("npm-shrinkwrap.json", PackageManager::Npm),
+    ];
+
+    /// Command that runs the package manager
+    pub fn command(&self) -> &'static str {
+        match self {
+            Self::Npm => "npm",
+            Self::Pnpm => "pnpm",
+            Self::Yarn => "yarn",
+        }
+    }
+
+    /// Program to spawn; package managers are batch scripts on Windows
+    fn program(&self) -> String {
+        if cfg!(windows) {
+            format!("{}.cmd", self.command())
+        } else {
+            self.command().to_string()
+        }
+    }
+}
+
+impl fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.command())
+    }
+}
+
+/// Package manager chosen from the lock files in a package directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageManagerDetection {
+    /// The package manager to install with
+    pub manager: PackageManager,
+    /// Lock files found, in order of preference
+    pub lock_files: Vec<&'static str>,
+}
+
+impl PackageManagerDetection {
+    /// Detect the package manager from the lock files in `dir`
+    ///
+    /// pnpm is preferred over yarn, and yarn over npm, when lock files of
+    /// several package managers exist. Without a lock file npm is used.
+    pub fn detect(dir: &NormalizedPath) -> Self {
+        let found: Vec<_> = PackageManager::LOCK_FILES
+            .iter()
+            .filter(|(lock, _)| dir.join(lock).exists())
+            .collect();
+        Self {
+            manager: found.first().map_or(PackageManager::Npm, |(_, m)| *m),
+            lock_files: found.iter().map(|(lock, _)| *lock).collect(),
+        }
+    }
+
+    /// Whether lock files of more than one package manager exist
+    pub fn is_ambiguous(&self) -> bool {
+        PackageManager::LOCK_FILES
+            .iter()
+            .any(|(lock, m)| *m != self.manager && self.lock_files.contains(lock))
+    }
+
+    /// Check report detail naming the package manager and why it was chosen
+    fn describe(&self) -> String {
+        match self.lock_files.first() {
+            Some(lock) => format!("Package manager: {} ({})", self.manager, lock),
+            None => format!("Package manager: {} (no lock file)", self.manager),
+        }
+    }
+}
+
+/// Provider for Node.js environments.
 ///
-/// This is a detection-only provider that checks for:
+/// `check` looks for:
 /// - `package.json` file exists
 /// - `node_modules` directory exists
 /// - `node` command available on PATH
+/// - no lock file changed since dependencies were installed
 ///
-/// Unlike Python providers, this does not create or manage environments,
-/// it only detects their presence.
+/// and reports the package manager, picked from the lock files by
+/// [`PackageManagerDetection::detect`]. `apply` installs dependencies with
+/// that package manager.
 pub struct NodeProvider;
 
 impl NodeProvider {
@@ -26,14 +117,15 @@ impl NodeProvider {
 
     /// Check if Node.js is available on the system.
     async fn check_node_available(&self) -> bool {
-        Command::new("node")
-            .arg("--version")
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status()
-            .await
-            .map(|s| s.success())
-            .unwrap_or(false)
+        process::status(
+            Command::new("node")
+                .arg("--version")
+                .stdout(Stdio::null())
+                .stderr(Stdio::null()),
+        )
+        .await
+        .map(|s| s.success())
+        .unwrap_or(false)
     }
 
     /// Check if Node.js is available (synchronous version for testing).
@@ -47,14 +139,19 @@ impl NodeProvider {
             .unwrap_or(false)
     }
 
-    /// Check if package.json exists in the project root.
+    /// Nearest directory with a package.json, walking up from the working directory.
+    fn package_dir(&self, context: &Context) -> NormalizedPath {
+        context.package_root(&["package.json"])
+    }
+
+    /// Check if package.json exists in the package directory.
     fn check_package_json_exists(&self, context: &Context) -> bool {
-        context.root.join("package.json").exists()
+        self.package_dir(context).join("package.json").exists()
     }
 
-    /// Check if node_modules directory exists in the project root.
+    /// Check if node_modules directory exists in the package directory.
     fn check_node_modules_exists(&self, context: &Context) -> bool {
-        context.root.join("node_modules").exists()
+        self.package_dir(context).join("node_modules").exists()
     }
 }
 
@@ -71,6 +168,75 @@ impl PresetProvider for NodeProvider {
     }
 
     async fn check(&self, context: &Context) -> Result<PresetCheckReport> {
+        let mut report = self.check_environment(context).await?;
+        if report.status == PresetStatus::Missing && report.action == ActionType::None {
+            // Not a Node.js project
+            return Ok(report);
+        }
+
+        let detection = PackageManagerDetection::detect(&self.package_dir(context));
+        if detection.is_ambiguous() {
+            let reason = format!(
+                "Lock files of several package managers found ({}); using {}",
+                detection.lock_files.join(", "),
+                detection.manager
+            );
+            if report.status == PresetStatus::Healthy {
+                report = PresetCheckReport::degraded(reason);
+                report.action = ActionType::None;
+            } else {
+                report.details.push(reason);
+            }
+        }
+        report.details.push(detection.describe());
+        Ok(report)
+    }
+
+    async fn apply(&self, context: &Context) -> Result<ApplyReport> {
+        let package_dir = self.package_dir(context);
+        if !package_dir.join("package.json").exists() {
+            return Ok(ApplyReport::failure(vec![format!(
+                "No package.json found in {}",
+                package_dir
+            )]));
+        }
+
+        let node_modules = package_dir.join("node_modules");
+        let mut performed = Vec::new();
+        if !node_modules.exists() {
+            performed.push(AppliedAction::CreatedDir(node_modules));
+        }
+
+        let manager = PackageManagerDetection::detect(&package_dir).manager;
+        let status = process::status(
+            Command::new(manager.program())
+                .arg("install")
+                .current_dir(package_dir.to_native()),
+        )
+        .await
+        .map_err(|_| Error::CommandNotFound {
+            command: manager.command().to_string(),
+        })?;
+
+        if !status.success() {
+            return Ok(ApplyReport::failure(vec![format!(
+                "{} install failed in {}",
+                manager, package_dir
+            )])
+            .with_performed(performed));
+        }
+
+        Ok(ApplyReport::success(vec![format!(
+            "Installed dependencies with {} in {}",
+            manager, package_dir
+        )])
+        .with_performed(performed))
+    }
+}
+
+impl NodeProvider {
+    /// Check package.json, node_modules and node itself.
+    async fn check_environment(&self, context: &Context) -> Result<PresetCheckReport> {
         let mut details = Vec::new();
 
         // Check if package.json exists
@@ -106,26 +272,36 @@ impl PresetProvider for NodeProvider {
             return Ok(PresetCheckReport::broken(details.join("; ")));
         }
 
+        let package_dir = self.package_dir(context);
+        let manager = PackageManagerDetection::detect(&package_dir).manager;
         if !has_node_modules {
             // Node.js project with node, but dependencies not installed
-            return Ok(PresetCheckReport {
-                status: PresetStatus::Missing,
-                details: vec![
-                    "Dependencies not installed. Run npm install or yarn install.".to_string(),
-                ],
-                action: ActionType::Install,
-            });
+            return Ok(PresetCheckReport::missing(format!(
+                "Dependencies not installed. Run {} install.",
+                manager
+            )));
         }
 
-        // Everything is present
-        Ok(PresetCheckReport::healthy())
-    }
+        // Dependencies installed from an older lock file
+        let node_modules = package_dir.join("node_modules");
+        let mut markers: Vec<_> = NODE_INSTALL_MARKERS
+            .iter()
+            .map(|marker| node_modules.join(marker))
+            .collect();
+        markers.push(node_modules);
+        if let Some(lock) = newer_lock(&package_dir, NODE_LOCKS, &markers) {
+            let mut report = PresetCheckReport::degraded(format!(
+                "{} changed after dependencies were last installed",
+                lock
+            ));
+            report
+                .details
+                .push(format!("Run {} install to update them.", manager));
+            return Ok(report);
+        }
 
-    async fn apply(&self, _context: &Context) -> Result<ApplyReport> {
-        Ok(ApplyReport::detection_only(vec![
-            "Node environment detected. This provider does not perform setup.".to_string(),
-            "Install dependencies manually with npm/yarn/pnpm.".to_string(),
-        ]))
+        // Everything is present and up to date
+        Ok(PresetCheckReport::healthy())
     }
 }
 
@@ -249,25 +425,115 @@ mod tests {
         assert_eq!(report.status, PresetStatus::Healthy);
     }
 
+    #[test]
+    fn test_detect_package_manager_from_lock_file() {
+        for (lock, manager) in [
+            ("pnpm-lock.yaml", PackageManager::Pnpm),
+            ("yarn.lock", PackageManager::Yarn),
+            ("package-lock.json", PackageManager::Npm),
+            ("npm-shrinkwrap.json", PackageManager::Npm),
+        ] {
+            let temp = TempDir::new().unwrap();
+            fs::write(temp.path().join(lock), "").unwrap();
+
+            let detection = PackageManagerDetection::detect(&NormalizedPath::new(temp.path()));
+            assert_eq!(detection.manager, manager, "{lock}");
+            assert_eq!(detection.lock_files, vec![lock]);
+            assert!(!detection.is_ambiguous());
+        }
+    }
+
+    #[test]
+    fn test_detect_package_manager_defaults_to_npm() {
+        let temp = TempDir::new().unwrap();
+        let detection = PackageManagerDetection::detect(&NormalizedPath::new(temp.path()));
+        assert_eq!(detection.manager, PackageManager::Npm);
+        assert!(detection.lock_files.is_empty());
+        assert_eq!(detection.describe(), "Package manager: npm (no lock file)");
+    }
+
+    #[test]
+    fn test_detect_package_manager_ambiguous() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("yarn.lock"), "").unwrap();
+        fs::write(temp.path().join("package-lock.json"), "").unwrap();
+
+        let detection = PackageManagerDetection::detect(&NormalizedPath::new(temp.path()));
+        assert_eq!(detection.manager, PackageManager::Yarn);
+        assert_eq!(detection.lock_files, vec!["yarn.lock", "package-lock.json"]);
+        assert!(detection.is_ambiguous());
+
+        // Both npm lock files are not ambiguous
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("package-lock.json"), "").unwrap();
+        fs::write(temp.path().join("npm-shrinkwrap.json"), "").unwrap();
+        assert!(!PackageManagerDetection::detect(&NormalizedPath::new(temp.path())).is_ambiguous());
+    }
+
     #[tokio::test]
-    async fn test_apply_is_detection_only() {
+    async fn test_check_reports_package_manager() {
         let provider = NodeProvider::new();
         let temp = TempDir::new().unwrap();
         let context = make_test_context(&temp);
 
-        let report = provider.apply(&context).await.unwrap();
-        assert!(
-            report.is_detection_only(),
-            "NodeProvider.apply() must return DetectionOnly status, got: {:?}",
-            report.status
-        );
+        if !provider.check_node_available().await {
+            eprintln!("Skipping test: Node not available");
+            return;
+        }
+
+        fs::write(temp.path().join("package.json"), "{}").unwrap();
+        fs::write(temp.path().join("pnpm-lock.yaml"), "").unwrap();
+
+        let report = provider.check(&context).await.unwrap();
+        assert_eq!(report.status, PresetStatus::Missing);
         assert!(
-            !report.is_success(),
-            "NodeProvider.apply() must NOT be Success — it does no real work"
+            report
+                .details
+                .contains(&"Dependencies not installed. Run pnpm install.".to_string())
         );
         assert!(
-            !report.is_failure(),
-            "NodeProvider.apply() must NOT be Failed — detection itself succeeds"
+            report
+                .details
+                .contains(&"Package manager: pnpm (pnpm-lock.yaml)".to_string())
         );
     }
+
+    #[tokio::test]
+    async fn test_check_ambiguous_lock_files_is_degraded() {
+        let provider = NodeProvider::new();
+        let temp = TempDir::new().unwrap();
+        let context = make_test_context(&temp);
+
+        if !provider.check_node_available().await {
+            eprintln!("Skipping test: Node not available");
+            return;
+        }
+
+        fs::write(temp.path().join("package.json"), "{}").unwrap();
+        fs::write(temp.path().join("pnpm-lock.yaml"), "").unwrap();
+        fs::write(temp.path().join("yarn.lock"), "").unwrap();
+        fs::create_dir(temp.path().join("node_modules")).unwrap();
+        fs::write(temp.path().join("node_modules/.modules.yaml"), "").unwrap();
+
+        let report = provider.check(&context).await.unwrap();
+        match report.status {
+            PresetStatus::Degraded { reason } => {
+                assert!(reason.contains("pnpm-lock.yaml, yarn.lock"), "{reason}");
+                assert!(reason.ends_with("using pnpm"), "{reason}");
+            }
+            other => panic!("Expected Degraded, got: {other:?}"),
+        }
+        assert_eq!(report.action, ActionType::None);
+    }
+
+    #[tokio::test]
+    async fn test_apply_without_package_json_fails() {
+        let provider = NodeProvider::new();
+        let temp = TempDir::new().unwrap();
+        let context = make_test_context(&temp);
+
+        let report = provider.apply(&context).await.unwrap();
+        assert!(report.is_failure());
+        assert!(report.errors[0].contains("No package.json"));
+    }
 }
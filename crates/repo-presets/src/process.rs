@@ -0,0 +1,81 @@
+//! Subprocesses that stop when their caller gives up
+//!
+//! Providers run their tools through [`status`] and [`output`] rather than
+//! awaiting [`Command`] directly. When the caller's
+//! [`CancelToken`](repo_fs::CancelToken) is cancelled, the child is killed
+//! and the call fails with [`io::ErrorKind::Interrupted`].
+
+use std::future::Future;
+use std::io;
+use std::process::{ExitStatus, Output};
+
+use repo_fs::cancel::{self, CancelToken, POLL_INTERVAL};
+use tokio::process::Command;
+
+/// Run `command` to completion and return its exit status.
+pub(crate) async fn status(command: &mut Command) -> io::Result<ExitStatus> {
+    until_cancelled(command.kill_on_drop(true).status()).await
+}
+
+/// Run `command` to completion and collect its output.
+pub(crate) async fn output(command: &mut Command) -> io::Result<Output> {
+    until_cancelled(command.kill_on_drop(true).output()).await
+}
+
+/// Drive `work` until it finishes or the current token is cancelled, in
+/// which case it is dropped, killing the child it spawned.
+async fn until_cancelled<T>(work: impl Future<Output = io::Result<T>>) -> io::Result<T> {
+    // Taken before the first await, while still on the caller's thread
+    let Some(token) = cancel::current() else {
+        return work.await;
+    };
+    if token.is_cancelled() {
+        return Err(cancelled());
+    }
+    tokio::select! {
+        result = work => result,
+        () = wait_for(&token) => Err(cancelled()),
+    }
+}
+
+async fn wait_for(token: &CancelToken) {
+    while !token.is_cancelled() {
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+fn cancelled() -> io::Error {
+    io::Error::new(io::ErrorKind::Interrupted, "operation cancelled")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[tokio::test]
+    async fn test_status_without_token_runs_command() {
+        let mut git = Command::new("git");
+        git.arg("--version").stdout(std::process::Stdio::null());
+        let status = status(&mut git).await.unwrap();
+        assert!(status.success());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cancel_kills_running_child() {
+        let token = CancelToken::new();
+        let canceller = token.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            canceller.cancel();
+        });
+
+        let started = Instant::now();
+        let _scope = token.enter();
+        let result = output(Command::new("sleep").arg("30")).await;
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Interrupted);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
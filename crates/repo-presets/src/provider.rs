@@ -1,13 +1,19 @@
 //! PresetProvider trait and related types
 
-use crate::Result;
 use crate::context::Context;
+use crate::{Error, Result};
 use async_trait::async_trait;
+use repo_fs::NormalizedPath;
 
 /// Status of a preset after checking
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PresetStatus {
     Healthy,
+    /// The environment exists but is out of date, e.g. installed from an
+    /// older lock file
+    Degraded {
+        reason: String,
+    },
     Missing,
     Drifted,
     Broken,
@@ -39,6 +45,18 @@ impl PresetCheckReport {
         }
     }
 
+    /// Report an environment that exists but needs a refresh
+    ///
+    /// `reason` is recorded in the status and as the first detail.
+    pub fn degraded(reason: impl Into<String>) -> Self {
+        let reason = reason.into();
+        Self {
+            details: vec![reason.clone()],
+            status: PresetStatus::Degraded { reason },
+            action: ActionType::Update,
+        }
+    }
+
     pub fn missing(detail: impl Into<String>) -> Self {
         Self {
             status: PresetStatus::Missing,
@@ -76,12 +94,26 @@ pub enum ApplyStatus {
     Failed,
 }
 
+/// A filesystem change made by `apply`, recorded so it can be undone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppliedAction {
+    /// Created a directory that did not exist before, e.g. a venv
+    CreatedDir(NormalizedPath),
+    /// Moved a file out of the way
+    MovedFile {
+        from: NormalizedPath,
+        to: NormalizedPath,
+    },
+}
+
 /// Report from applying a preset
 #[derive(Debug, Clone)]
 pub struct ApplyReport {
     pub status: ApplyStatus,
     pub actions_taken: Vec<String>,
     pub errors: Vec<String>,
+    /// Changes made, in order, for [`PresetProvider::rollback`]
+    pub performed: Vec<AppliedAction>,
 }
 
 impl ApplyReport {
@@ -90,6 +122,7 @@ impl ApplyReport {
             status: ApplyStatus::Success,
             actions_taken: actions,
             errors: vec![],
+            performed: vec![],
         }
     }
 
@@ -98,6 +131,7 @@ impl ApplyReport {
             status: ApplyStatus::DetectionOnly,
             actions_taken: messages,
             errors: vec![],
+            performed: vec![],
         }
     }
 
@@ -106,9 +140,16 @@ impl ApplyReport {
             status: ApplyStatus::Failed,
             actions_taken: vec![],
             errors,
+            performed: vec![],
         }
     }
 
+    /// Record the changes that were made
+    pub fn with_performed(mut self, performed: Vec<AppliedAction>) -> Self {
+        self.performed = performed;
+        self
+    }
+
     pub fn is_success(&self) -> bool {
         matches!(self.status, ApplyStatus::Success)
     }
@@ -122,10 +163,79 @@ impl ApplyReport {
     }
 }
 
+/// Undo `actions`, newest first
+///
+/// Every action is attempted even if an earlier one cannot be undone.
+///
+/// # Errors
+///
+/// Returns [`Error::RollbackFailed`] listing the actions that could not be
+/// undone.
+pub fn rollback_actions(actions: &[AppliedAction]) -> Result<()> {
+    let mut failures = Vec::new();
+    for action in actions.iter().rev() {
+        let result = match action {
+            AppliedAction::CreatedDir(dir) if dir.exists() => std::fs::remove_dir_all(dir.as_ref()),
+            AppliedAction::CreatedDir(_) => Ok(()),
+            AppliedAction::MovedFile { from, to } => std::fs::rename(to.as_ref(), from.as_ref()),
+        };
+        if let Err(e) = result {
+            failures.push(format!("{:?}: {}", action, e));
+        }
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::RollbackFailed {
+            message: failures.join("; "),
+        })
+    }
+}
+
 /// Core trait for preset providers
 #[async_trait]
 pub trait PresetProvider: Send + Sync {
     fn id(&self) -> &str;
     async fn check(&self, context: &Context) -> Result<PresetCheckReport>;
     async fn apply(&self, context: &Context) -> Result<ApplyReport>;
+
+    /// Undo the changes recorded in `report` by a failed [`Self::apply`]
+    async fn rollback(&self, _context: &Context, report: &ApplyReport) -> Result<()> {
+        rollback_actions(&report.performed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rollback_actions() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        let venv = root.join(".venv");
+        fs::create_dir_all(venv.join("bin").as_ref()).unwrap();
+        fs::write(root.join("config.bak").as_ref(), "original").unwrap();
+
+        let actions = vec![
+            AppliedAction::MovedFile {
+                from: root.join("config"),
+                to: root.join("config.bak"),
+            },
+            AppliedAction::CreatedDir(venv.clone()),
+        ];
+        rollback_actions(&actions).unwrap();
+
+        assert!(!venv.exists());
+        assert_eq!(
+            fs::read_to_string(root.join("config").as_ref()).unwrap(),
+            "original"
+        );
+
+        // Undoing again fails for the move but still handles the directory
+        let err = rollback_actions(&actions).unwrap_err();
+        assert!(matches!(err, Error::RollbackFailed { .. }));
+    }
 }
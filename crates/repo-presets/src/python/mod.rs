@@ -1,7 +1,9 @@
 //! Python environment providers
 
+mod poetry;
 mod uv;
 mod venv;
 
+pub use poetry::PoetryProvider;
 pub use uv::UvProvider;
 pub use venv::VenvProvider;
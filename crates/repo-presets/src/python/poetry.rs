@@ -0,0 +1,211 @@
+//! Poetry-based Python environment provider
+
+use crate::context::Context;
+use crate::error::{Error, Result};
+use crate::lockfile::{newer_lock, venv_install_markers};
+use crate::process;
+use crate::provider::{ApplyReport, PresetCheckReport, PresetProvider};
+use async_trait::async_trait;
+use repo_fs::NormalizedPath;
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// Lock file Poetry resolves dependencies into
+const POETRY_LOCK: &str = "poetry.lock";
+
+/// Provider for Python environments managed by Poetry.
+///
+/// Applies to projects whose `pyproject.toml` has a `[tool.poetry]`
+/// section (https://python-poetry.org/). Poetry keeps the environment in
+/// the project's `.venv` when `virtualenvs.in-project` is set, and in its
+/// cache directory otherwise; both are found.
+pub struct PoetryProvider;
+
+impl PoetryProvider {
+    /// Create a new PoetryProvider instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check if poetry is available on the system.
+    async fn check_poetry_available(&self) -> bool {
+        process::status(
+            Command::new("poetry")
+                .arg("--version")
+                .stdout(Stdio::null())
+                .stderr(Stdio::null()),
+        )
+        .await
+        .map(|s| s.success())
+        .unwrap_or(false)
+    }
+
+    /// Check if the project at `root` is configured for Poetry.
+    pub fn is_poetry_project(root: &NormalizedPath) -> bool {
+        std::fs::read_to_string(root.join("pyproject.toml").as_ref())
+            .ok()
+            .and_then(|content| content.parse::<toml::Table>().ok())
+            .is_some_and(|pyproject| {
+                pyproject
+                    .get("tool")
+                    .and_then(|tool| tool.get("poetry"))
+                    .is_some_and(toml::Value::is_table)
+            })
+    }
+
+    /// Locate the project's environment, in `.venv` or wherever Poetry
+    /// created it.
+    async fn find_environment(&self, context: &Context) -> Option<NormalizedPath> {
+        let venv_path = context.venv_path();
+        if has_python(&venv_path) {
+            return Some(venv_path);
+        }
+
+        let output = process::output(
+            Command::new("poetry")
+                .args(["env", "info", "--path"])
+                .current_dir(context.python_root().to_native())
+                .stderr(Stdio::null()),
+        )
+        .await
+        .ok()?;
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !output.status.success() || path.is_empty() {
+            return None;
+        }
+        let path = NormalizedPath::new(path);
+        has_python(&path).then_some(path)
+    }
+}
+
+impl Default for PoetryProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a virtual environment with a Python interpreter exists at `venv`
+fn has_python(venv: &NormalizedPath) -> bool {
+    let python_path = if cfg!(windows) {
+        venv.join("Scripts").join("python.exe")
+    } else {
+        venv.join("bin").join("python")
+    };
+    python_path.exists()
+}
+
+#[async_trait]
+impl PresetProvider for PoetryProvider {
+    fn id(&self) -> &str {
+        "env:python"
+    }
+
+    async fn check(&self, context: &Context) -> Result<PresetCheckReport> {
+        let root = context.python_root();
+        if !Self::is_poetry_project(&root) {
+            return Ok(PresetCheckReport::broken(
+                "No Poetry project: pyproject.toml has no [tool.poetry] section",
+            ));
+        }
+
+        if !self.check_poetry_available().await {
+            return Ok(PresetCheckReport::broken(
+                "poetry not found. Install Poetry: https://python-poetry.org/docs/",
+            ));
+        }
+
+        let Some(venv_path) = self.find_environment(context).await else {
+            let mut report = PresetCheckReport::missing("Poetry environment not found");
+            report
+                .details
+                .push("Run `poetry install` to create it".to_string());
+            return Ok(report);
+        };
+
+        if !root.join(POETRY_LOCK).exists() {
+            let mut report = PresetCheckReport::degraded("poetry.lock not found");
+            report
+                .details
+                .push("Run `poetry lock` to pin the dependencies".to_string());
+            return Ok(report);
+        }
+
+        if let Some(lock) = newer_lock(&root, &[POETRY_LOCK], &venv_install_markers(&venv_path)) {
+            let mut report = PresetCheckReport::degraded(format!(
+                "{} changed after the environment was last installed",
+                lock
+            ));
+            report
+                .details
+                .push("Run `poetry install` to update the environment".to_string());
+            return Ok(report);
+        }
+
+        Ok(PresetCheckReport::healthy())
+    }
+
+    async fn apply(&self, context: &Context) -> Result<ApplyReport> {
+        let root = context.python_root();
+        if !Self::is_poetry_project(&root) {
+            return Ok(ApplyReport::failure(vec![format!(
+                "No [tool.poetry] section in {}",
+                root.join("pyproject.toml")
+            )]));
+        }
+
+        let status = process::status(
+            Command::new("poetry")
+                .arg("install")
+                .current_dir(root.to_native()),
+        )
+        .await
+        .map_err(|_| Error::CommandNotFound {
+            command: "poetry".to_string(),
+        })?;
+
+        if !status.success() {
+            return Ok(ApplyReport::failure(vec![
+                "poetry install failed".to_string(),
+            ]));
+        }
+
+        Ok(ApplyReport::success(vec![format!(
+            "Installed Poetry dependencies in {}",
+            root
+        )]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_poetry_provider_id() {
+        let provider = PoetryProvider::new();
+        assert_eq!(provider.id(), "env:python");
+    }
+
+    #[test]
+    fn test_is_poetry_project() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        assert!(!PoetryProvider::is_poetry_project(&root));
+
+        fs::write(
+            temp.path().join("pyproject.toml"),
+            "[project]\nname = \"app\"\n",
+        )
+        .unwrap();
+        assert!(!PoetryProvider::is_poetry_project(&root));
+
+        fs::write(
+            temp.path().join("pyproject.toml"),
+            "[tool.poetry]\nname = \"app\"\n\n[tool.poetry.dependencies]\npython = \"^3.12\"\n",
+        )
+        .unwrap();
+        assert!(PoetryProvider::is_poetry_project(&root));
+    }
+}
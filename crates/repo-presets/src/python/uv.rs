@@ -2,8 +2,12 @@
 
 use crate::context::Context;
 use crate::error::{Error, Result};
-use crate::provider::{ApplyReport, PresetCheckReport, PresetProvider};
+use crate::lockfile::{PYTHON_LOCKS, newer_lock, venv_install_markers};
+use crate::process;
+use crate::provider::{AppliedAction, ApplyReport, PresetCheckReport, PresetProvider};
 use async_trait::async_trait;
+use repo_fs::NormalizedPath;
+use std::path::PathBuf;
 use std::process::Stdio;
 use tokio::process::Command;
 
@@ -11,24 +15,35 @@ use tokio::process::Command;
 ///
 /// This provider handles creation and management of Python virtual environments
 /// using the uv package manager (https://docs.astral.sh/uv/).
-pub struct UvProvider;
+pub struct UvProvider {
+    /// Program run for uv commands
+    program: PathBuf,
+}
 
 impl UvProvider {
     /// Create a new UvProvider instance.
     pub fn new() -> Self {
-        Self
+        Self::with_program("uv")
+    }
+
+    /// Create a provider that runs `program` in place of `uv`.
+    pub fn with_program(program: impl Into<PathBuf>) -> Self {
+        Self {
+            program: program.into(),
+        }
     }
 
     /// Check if uv is available on the system.
     async fn check_uv_available(&self) -> bool {
-        Command::new("uv")
-            .arg("--version")
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status()
-            .await
-            .map(|s| s.success())
-            .unwrap_or(false)
+        process::status(
+            Command::new(&self.program)
+                .arg("--version")
+                .stdout(Stdio::null())
+                .stderr(Stdio::null()),
+        )
+        .await
+        .map(|s| s.success())
+        .unwrap_or(false)
     }
 
     /// Check if a virtual environment exists at the expected location.
@@ -41,6 +56,36 @@ impl UvProvider {
         };
         python_path.exists()
     }
+
+    /// Install the project's dependencies into `venv_path`.
+    ///
+    /// Syncs from `uv.lock` when the project has one, and installs
+    /// `requirements.txt` otherwise. Returns `None` when there is nothing
+    /// to install, and whether the install succeeded otherwise.
+    async fn install_dependencies(
+        &self,
+        root: &NormalizedPath,
+        venv_path: &NormalizedPath,
+    ) -> Result<Option<bool>> {
+        let mut command = Command::new(&self.program);
+        if root.join("uv.lock").exists() {
+            command
+                .args(["sync", "--frozen"])
+                .env("UV_PROJECT_ENVIRONMENT", venv_path.to_native());
+        } else if root.join("requirements.txt").exists() {
+            command
+                .args(["pip", "install", "--python"])
+                .arg(venv_path.to_native())
+                .args(["-r", "requirements.txt"]);
+        } else {
+            return Ok(None);
+        }
+
+        let status = process::status(command.current_dir(root.to_native()))
+            .await
+            .map_err(|_| Error::UvNotFound)?;
+        Ok(Some(status.success()))
+    }
 }
 
 impl Default for UvProvider {
@@ -68,32 +113,77 @@ impl PresetProvider for UvProvider {
             return Ok(PresetCheckReport::missing("Virtual environment not found"));
         }
 
+        let venv_path = context.venv_path();
+        if let Some(lock) = newer_lock(
+            &context.python_root(),
+            PYTHON_LOCKS,
+            &venv_install_markers(&venv_path),
+        ) {
+            let mut report = PresetCheckReport::degraded(format!(
+                "{} changed after the virtual environment was last synced",
+                lock
+            ));
+            report
+                .details
+                .push("Run `uv sync` to update the environment".to_string());
+            return Ok(report);
+        }
+
         Ok(PresetCheckReport::healthy())
     }
 
     async fn apply(&self, context: &Context) -> Result<ApplyReport> {
+        let root = context.python_root();
         let venv_path = context.venv_path();
         let python_version = context.python_version();
 
-        let status = Command::new("uv")
-            .args(["venv", "--python", &python_version])
-            .arg(venv_path.to_native())
-            .current_dir(context.root.to_native())
-            .status()
-            .await
-            .map_err(|_| Error::UvNotFound)?;
+        // Only a venv this run creates is removed on rollback
+        let mut performed = Vec::new();
+        if !venv_path.exists() {
+            performed.push(AppliedAction::CreatedDir(venv_path.clone()));
+        }
+
+        let status = process::status(
+            Command::new(&self.program)
+                .args(["venv", "--python", &python_version])
+                .arg(venv_path.to_native())
+                .current_dir(root.to_native()),
+        )
+        .await
+        .map_err(|_| Error::UvNotFound)?;
 
         if !status.success() {
             return Ok(ApplyReport::failure(vec![format!(
                 "Failed to create venv with Python {}",
                 python_version
-            )]));
+            )])
+            .with_performed(performed));
+        }
+
+        let mut actions = vec![format!("Created virtual environment at {}", venv_path)];
+        let installed = match self.install_dependencies(&root, &venv_path).await {
+            Ok(installed) => installed,
+            Err(e) => {
+                return Ok(ApplyReport::failure(vec![format!(
+                    "Failed to install dependencies into {}: {}",
+                    venv_path, e
+                )])
+                .with_performed(performed));
+            }
+        };
+        match installed {
+            Some(false) => {
+                return Ok(ApplyReport::failure(vec![format!(
+                    "Failed to install dependencies into {}",
+                    venv_path
+                )])
+                .with_performed(performed));
+            }
+            Some(true) => actions.push("Installed dependencies".to_string()),
+            None => {}
         }
 
-        Ok(ApplyReport::success(vec![format!(
-            "Created virtual environment at {}",
-            venv_path
-        )]))
+        Ok(ApplyReport::success(actions).with_performed(performed))
     }
 }
 
@@ -103,7 +193,7 @@ mod tests {
 
     #[test]
     fn test_uv_provider_default() {
-        let provider = UvProvider;
+        let provider = UvProvider::default();
         assert_eq!(provider.id(), "env:python");
     }
 }
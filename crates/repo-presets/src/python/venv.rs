@@ -2,7 +2,9 @@
 
 use crate::context::Context;
 use crate::error::{Error, Result};
-use crate::provider::{ApplyReport, PresetCheckReport, PresetProvider};
+use crate::lockfile::{PYTHON_LOCKS, newer_lock, venv_install_markers};
+use crate::process;
+use crate::provider::{AppliedAction, ApplyReport, PresetCheckReport, PresetProvider};
 use async_trait::async_trait;
 use repo_fs::NormalizedPath;
 use std::path::Path;
@@ -27,14 +29,15 @@ impl VenvProvider {
 
     /// Check if Python is available on the system.
     async fn check_python_available(&self) -> bool {
-        Command::new("python")
-            .arg("--version")
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status()
-            .await
-            .map(|s| s.success())
-            .unwrap_or(false)
+        process::status(
+            Command::new("python")
+                .arg("--version")
+                .stdout(Stdio::null())
+                .stderr(Stdio::null()),
+        )
+        .await
+        .map(|s| s.success())
+        .unwrap_or(false)
     }
 
     /// Check if Python is available (synchronous version for testing).
@@ -103,13 +106,14 @@ impl VenvProvider {
         let venv_name = format!(".venv-{}", tag);
         let venv_path = NormalizedPath::new(root).join(&venv_name);
 
-        let status = Command::new("python")
-            .args(["-m", "venv"])
-            .arg(venv_path.as_ref())
-            .current_dir(root)
-            .status()
-            .await
-            .map_err(|_| Error::PythonNotFound)?;
+        let status = process::status(
+            Command::new("python")
+                .args(["-m", "venv"])
+                .arg(venv_path.as_ref())
+                .current_dir(root),
+        )
+        .await
+        .map_err(|_| Error::PythonNotFound)?;
 
         if !status.success() {
             return Err(Error::VenvCreationFailed {
@@ -164,30 +168,53 @@ impl PresetProvider for VenvProvider {
             return Ok(PresetCheckReport::missing("Virtual environment not found"));
         }
 
+        let venv_path = context.venv_path();
+        if let Some(lock) = newer_lock(
+            &context.python_root(),
+            PYTHON_LOCKS,
+            &venv_install_markers(&venv_path),
+        ) {
+            let mut report = PresetCheckReport::degraded(format!(
+                "{} changed after packages were last installed into the virtual environment",
+                lock
+            ));
+            report
+                .details
+                .push("Reinstall the dependencies into the virtual environment".to_string());
+            return Ok(report);
+        }
+
         Ok(PresetCheckReport::healthy())
     }
 
     async fn apply(&self, context: &Context) -> Result<ApplyReport> {
         let venv_path = context.venv_path();
+        let mut performed = Vec::new();
+        if !venv_path.exists() {
+            performed.push(AppliedAction::CreatedDir(venv_path.clone()));
+        }
 
-        let status = Command::new("python")
-            .args(["-m", "venv"])
-            .arg(venv_path.to_native())
-            .current_dir(context.root.to_native())
-            .status()
-            .await
-            .map_err(|_| Error::PythonNotFound)?;
+        let status = process::status(
+            Command::new("python")
+                .args(["-m", "venv"])
+                .arg(venv_path.to_native())
+                .current_dir(context.python_root().to_native()),
+        )
+        .await
+        .map_err(|_| Error::PythonNotFound)?;
 
         if !status.success() {
             return Ok(ApplyReport::failure(vec![
                 "Failed to create virtual environment with python -m venv".to_string(),
-            ]));
+            ])
+            .with_performed(performed));
         }
 
         Ok(ApplyReport::success(vec![format!(
             "Created virtual environment at {}",
             venv_path
-        )]))
+        )])
+        .with_performed(performed))
     }
 }
 
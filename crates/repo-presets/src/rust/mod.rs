@@ -1,5 +1,7 @@
 //! Rust environment providers
 
 mod rust_provider;
+mod toolchain;
 
 pub use rust_provider::RustProvider;
+pub use toolchain::{PinnedToolchain, TOOLCHAIN_FILES};
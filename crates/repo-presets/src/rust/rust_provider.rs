@@ -1,17 +1,22 @@
 //! Rust environment detection provider
 
+use super::toolchain::PinnedToolchain;
 use crate::context::Context;
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::process;
 use crate::provider::{ActionType, ApplyReport, PresetCheckReport, PresetProvider, PresetStatus};
 use async_trait::async_trait;
+use repo_fs::NormalizedPath;
 use std::process::Stdio;
 use tokio::process::Command;
 
-/// Provider for detecting Rust development environments.
+/// Provider for Rust development environments.
 ///
-/// This provider is detection-only - it checks whether a project uses Rust
-/// (has Cargo.toml) and whether rustc is available on the system PATH.
-/// It does not perform any installation or modification actions.
+/// `check` looks for Cargo.toml and rustc on the system PATH and, if the
+/// project pins a toolchain in `rust-toolchain.toml` or `rust-toolchain`,
+/// reports drift when the active rustc is not from the pinned channel.
+/// `apply` installs a pinned toolchain with rustup when it is missing;
+/// otherwise Rust installations are left to the user.
 pub struct RustProvider;
 
 impl RustProvider {
@@ -22,14 +27,15 @@ impl RustProvider {
 
     /// Check if rustc is available on the system PATH.
     async fn check_rustc_available(&self) -> bool {
-        Command::new("rustc")
-            .arg("--version")
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status()
-            .await
-            .map(|s| s.success())
-            .unwrap_or(false)
+        process::status(
+            Command::new("rustc")
+                .arg("--version")
+                .stdout(Stdio::null())
+                .stderr(Stdio::null()),
+        )
+        .await
+        .map(|s| s.success())
+        .unwrap_or(false)
     }
 
     /// Check if rustc is available (synchronous version for testing).
@@ -47,6 +53,82 @@ impl RustProvider {
     fn check_cargo_toml_exists(&self, context: &Context) -> bool {
         context.root.join("Cargo.toml").exists()
     }
+
+    /// `rustc --version` as run in `dir`, where rustup applies the
+    /// directory's pinned toolchain, or None if rustc fails there.
+    async fn rustc_version(&self, dir: &NormalizedPath) -> Option<String> {
+        let output = process::output(
+            Command::new("rustc")
+                .arg("--version")
+                .current_dir(dir.to_native())
+                // Never let rustup install the pinned toolchain behind our back
+                .env("RUSTUP_AUTO_INSTALL", "0")
+                .stderr(Stdio::null()),
+        )
+        .await
+        .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Whether rustup has `toolchain` installed, or None without rustup.
+    async fn toolchain_installed(&self, toolchain: &PinnedToolchain) -> Option<bool> {
+        let output = process::output(
+            Command::new("rustup")
+                .args(["toolchain", "list"])
+                .stderr(Stdio::null()),
+        )
+        .await
+        .ok()
+        .filter(|output| output.status.success())?;
+        // e.g. "1.85.0-x86_64-unknown-linux-gnu (active, default)"
+        let prefix = format!("{}-", toolchain.channel);
+        Some(String::from_utf8_lossy(&output.stdout).lines().any(|line| {
+            let name = line.split_whitespace().next().unwrap_or_default();
+            name == toolchain.channel || name.starts_with(&prefix)
+        }))
+    }
+
+    /// Compare the active rustc against the toolchain pinned in the project.
+    async fn check_toolchain(&self, context: &Context) -> PresetCheckReport {
+        let toolchain = match PinnedToolchain::read(&context.root) {
+            Ok(Some(toolchain)) => toolchain,
+            Ok(None) => return PresetCheckReport::healthy(),
+            Err(e) => return PresetCheckReport::broken(e.to_string()),
+        };
+
+        let active = if self.toolchain_installed(&toolchain).await == Some(false) {
+            None
+        } else {
+            self.rustc_version(&context.root).await
+        };
+        match active {
+            Some(active) if toolchain.matches(&active) => PresetCheckReport {
+                details: vec![format!(
+                    "{} pins {}; active {}",
+                    toolchain.file, toolchain.channel, active
+                )],
+                ..PresetCheckReport::healthy()
+            },
+            Some(active) => PresetCheckReport::drifted(format!(
+                "{} pins Rust {} but the active toolchain is {}",
+                toolchain.file, toolchain.channel, active
+            )),
+            None => {
+                // What rustc reports outside the project
+                let default = self
+                    .rustc_version(&NormalizedPath::new(std::env::temp_dir()))
+                    .await
+                    .unwrap_or_else(|| "unknown".to_string());
+                PresetCheckReport::drifted(format!(
+                    "{} pins Rust {} but it is not installed; the active toolchain is {}",
+                    toolchain.file, toolchain.channel, default
+                ))
+            }
+        }
+    }
 }
 
 impl Default for RustProvider {
@@ -78,14 +160,45 @@ impl PresetProvider for RustProvider {
             ));
         }
 
-        Ok(PresetCheckReport::healthy())
+        Ok(self.check_toolchain(context).await)
     }
 
-    async fn apply(&self, _context: &Context) -> Result<ApplyReport> {
-        Ok(ApplyReport::detection_only(vec![
-            "Rust environment detected. This provider does not perform setup.".to_string(),
-            "Manage Rust installations with rustup.".to_string(),
-        ]))
+    async fn apply(&self, context: &Context) -> Result<ApplyReport> {
+        let Some(toolchain) = PinnedToolchain::read(&context.root)? else {
+            return Ok(ApplyReport::detection_only(vec![
+                "Rust environment detected. This provider does not perform setup.".to_string(),
+                "Manage Rust installations with rustup.".to_string(),
+            ]));
+        };
+
+        if self.toolchain_installed(&toolchain).await == Some(true) {
+            return Ok(ApplyReport::success(vec![format!(
+                "Rust toolchain {} is already installed",
+                toolchain.channel
+            )]));
+        }
+
+        let status = process::status(
+            Command::new("rustup")
+                .args(["toolchain", "install", &toolchain.channel])
+                .current_dir(context.root.to_native()),
+        )
+        .await
+        .map_err(|_| Error::CommandNotFound {
+            command: "rustup".to_string(),
+        })?;
+
+        if !status.success() {
+            return Ok(ApplyReport::failure(vec![format!(
+                "rustup toolchain install {} failed",
+                toolchain.channel
+            )]));
+        }
+
+        Ok(ApplyReport::success(vec![format!(
+            "Installed Rust toolchain {} pinned by {}",
+            toolchain.channel, toolchain.file
+        )]))
     }
 }
 
@@ -183,6 +296,78 @@ mod tests {
         assert_eq!(report.action, ActionType::None);
     }
 
+    #[tokio::test]
+    async fn test_check_pinned_toolchain_drift() {
+        let provider = RustProvider::new();
+        if !provider.check_rustc_available_sync() {
+            eprintln!("Skipping test: rustc not available");
+            return;
+        }
+
+        for file in ["rust-toolchain.toml", "rust-toolchain"] {
+            let temp = TempDir::new().unwrap();
+            std::fs::write(temp.path().join("Cargo.toml"), "[package]\nname = \"test\"").unwrap();
+            let content = if file == "rust-toolchain" {
+                "1.0.0\n"
+            } else {
+                "[toolchain]\nchannel = \"1.0.0\"\n"
+            };
+            std::fs::write(temp.path().join(file), content).unwrap();
+
+            let report = provider.check(&make_test_context(&temp)).await.unwrap();
+            assert_eq!(report.status, PresetStatus::Drifted, "{file}");
+            assert_eq!(report.action, ActionType::Repair);
+            let detail = &report.details[0];
+            assert!(
+                detail.starts_with(&format!("{file} pins Rust 1.0.0")),
+                "{detail}"
+            );
+            assert!(detail.contains("rustc "), "{detail}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_pinned_toolchain_matches() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("Cargo.toml"), "[package]\nname = \"test\"").unwrap();
+        let context = make_test_context(&temp);
+        let provider = RustProvider::new();
+
+        let Some(active) = provider.rustc_version(&context.root).await else {
+            eprintln!("Skipping test: rustc not available");
+            return;
+        };
+        let stable = PinnedToolchain {
+            channel: "stable".to_string(),
+            file: "rust-toolchain",
+        };
+        if !stable.matches(&active) || provider.toolchain_installed(&stable).await == Some(false) {
+            eprintln!("Skipping test: active rustc is not stable");
+            return;
+        }
+        std::fs::write(temp.path().join("rust-toolchain"), "stable\n").unwrap();
+
+        let report = provider.check(&context).await.unwrap();
+        assert_eq!(report.status, PresetStatus::Healthy, "{:?}", report.details);
+        assert!(report.details[0].starts_with("rust-toolchain pins stable"));
+    }
+
+    #[tokio::test]
+    async fn test_check_invalid_toolchain_file_is_broken() {
+        let provider = RustProvider::new();
+        if !provider.check_rustc_available_sync() {
+            eprintln!("Skipping test: rustc not available");
+            return;
+        }
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("Cargo.toml"), "[package]\nname = \"test\"").unwrap();
+        std::fs::write(temp.path().join("rust-toolchain.toml"), "[toolchain\n").unwrap();
+
+        let report = provider.check(&make_test_context(&temp)).await.unwrap();
+        assert_eq!(report.status, PresetStatus::Broken);
+        assert!(report.details[0].contains("Invalid rust-toolchain.toml"));
+    }
+
     #[tokio::test]
     async fn test_apply_returns_detection_only() {
         let temp = TempDir::new().unwrap();
@@ -0,0 +1,190 @@
+//! Pinned Rust toolchains
+//!
+//! rustup pins a project's toolchain with `rust-toolchain.toml`:
+//!
+//! ```toml
+//! [toolchain]
+//! channel = "1.85.0"
+//! ```
+//!
+//! or with the legacy `rust-toolchain` file, which holds either the same
+//! TOML or just the channel on one line. When both exist, rustup uses the
+//! legacy file.
+
+use crate::error::{Error, Result};
+use repo_fs::NormalizedPath;
+
+/// Toolchain files rustup reads, in order of precedence
+pub const TOOLCHAIN_FILES: &[&str] = &["rust-toolchain", "rust-toolchain.toml"];
+
+/// A toolchain pinned by a project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedToolchain {
+    /// Channel, e.g. `stable`, `1.85.0` or `nightly-2025-01-15`
+    pub channel: String,
+    /// The file that pins it
+    pub file: &'static str,
+}
+
+impl PinnedToolchain {
+    /// Read the toolchain pinned in `dir`, if any
+    ///
+    /// A toolchain file without a channel, e.g. one that only lists
+    /// components, pins nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::CheckFailed`] if the toolchain file cannot be read
+    /// or parsed.
+    pub fn read(dir: &NormalizedPath) -> Result<Option<Self>> {
+        let Some(file) = TOOLCHAIN_FILES.iter().find(|file| dir.join(file).is_file()) else {
+            return Ok(None);
+        };
+        let content = std::fs::read_to_string(dir.join(file).to_native()).map_err(|e| {
+            Error::CheckFailed {
+                message: format!("Cannot read {}: {}", file, e),
+            }
+        })?;
+        let channel = Self::parse(&content).map_err(|message| Error::CheckFailed {
+            message: format!("Invalid {}: {}", file, message),
+        })?;
+        Ok(channel.map(|channel| Self { channel, file }))
+    }
+
+    /// The channel pinned by toolchain file `content`
+    fn parse(content: &str) -> std::result::Result<Option<String>, String> {
+        let trimmed = content.trim();
+        // Legacy files may hold just the channel
+        if !trimmed.is_empty() && !trimmed.contains(['\n', '=', '[']) {
+            return Ok(Some(trimmed.to_string()));
+        }
+        let file: toml::Table = content
+            .parse()
+            .map_err(|e: toml::de::Error| e.message().to_string())?;
+        let channel = file
+            .get("toolchain")
+            .and_then(|toolchain| toolchain.get("channel"))
+            .and_then(|channel| channel.as_str())
+            .map(str::trim)
+            .filter(|channel| !channel.is_empty());
+        Ok(channel.map(str::to_string))
+    }
+
+    /// Whether `rustc --version` output `version` comes from this channel
+    ///
+    /// Channels that name no release, such as custom toolchains, cannot be
+    /// compared and always match.
+    pub fn matches(&self, version: &str) -> bool {
+        // "rustc 1.85.0 (4d91de4e4 2025-02-17)" or "rustc 1.86.0-nightly (...)"
+        let Some(release) = version.split_whitespace().nth(1) else {
+            return true;
+        };
+        let (number, prerelease) = release.split_once('-').unwrap_or((release, ""));
+
+        let channel = self.channel.as_str();
+        if channel == "stable" {
+            return prerelease.is_empty();
+        }
+        for named in ["beta", "nightly"] {
+            if channel == named || channel.starts_with(&format!("{}-", named)) {
+                return prerelease.starts_with(named);
+            }
+        }
+        // "1.85.0" or "1.85", optionally followed by a target triple
+        let pinned = channel.split('-').next().unwrap_or(channel);
+        if !pinned.starts_with(|c: char| c.is_ascii_digit()) {
+            return true;
+        }
+        prerelease.is_empty() && (number == pinned || number.starts_with(&format!("{}.", pinned)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn pinned(channel: &str) -> PinnedToolchain {
+        PinnedToolchain {
+            channel: channel.to_string(),
+            file: "rust-toolchain.toml",
+        }
+    }
+
+    #[test]
+    fn test_read_toolchain_toml() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"1.85.0\"\ncomponents = [\"clippy\"]\n",
+        )
+        .unwrap();
+
+        let toolchain = PinnedToolchain::read(&NormalizedPath::new(temp.path())).unwrap();
+        assert_eq!(toolchain, Some(pinned("1.85.0")));
+    }
+
+    #[test]
+    fn test_read_legacy_toolchain_file() {
+        let temp = TempDir::new().unwrap();
+        let dir = NormalizedPath::new(temp.path());
+        fs::write(temp.path().join("rust-toolchain"), "nightly-2025-01-15\n").unwrap();
+        // The legacy file wins
+        fs::write(
+            temp.path().join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"stable\"\n",
+        )
+        .unwrap();
+
+        let toolchain = PinnedToolchain::read(&dir).unwrap().unwrap();
+        assert_eq!(toolchain.channel, "nightly-2025-01-15");
+        assert_eq!(toolchain.file, "rust-toolchain");
+
+        // It may also hold TOML
+        fs::write(
+            temp.path().join("rust-toolchain"),
+            "[toolchain]\nchannel = \"beta\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            PinnedToolchain::read(&dir).unwrap().unwrap().channel,
+            "beta"
+        );
+    }
+
+    #[test]
+    fn test_read_without_channel_or_file() {
+        let temp = TempDir::new().unwrap();
+        let dir = NormalizedPath::new(temp.path());
+        assert_eq!(PinnedToolchain::read(&dir).unwrap(), None);
+
+        fs::write(
+            temp.path().join("rust-toolchain.toml"),
+            "[toolchain]\ncomponents = [\"rustfmt\"]\n",
+        )
+        .unwrap();
+        assert_eq!(PinnedToolchain::read(&dir).unwrap(), None);
+
+        fs::write(temp.path().join("rust-toolchain.toml"), "channel = [").unwrap();
+        assert!(PinnedToolchain::read(&dir).is_err());
+    }
+
+    #[test]
+    fn test_matches_version() {
+        let stable = "rustc 1.85.0 (4d91de4e4 2025-02-17)";
+        let nightly = "rustc 1.86.0-nightly (a567209da 2025-01-14)";
+
+        assert!(pinned("1.85.0").matches(stable));
+        assert!(pinned("1.85").matches(stable));
+        assert!(pinned("1.85.0-x86_64-unknown-linux-gnu").matches(stable));
+        assert!(!pinned("1.84.1").matches(stable));
+        assert!(!pinned("1.8").matches(stable));
+        assert!(pinned("stable").matches(stable));
+        assert!(!pinned("stable").matches(nightly));
+        assert!(pinned("nightly-2025-01-15").matches(nightly));
+        assert!(!pinned("nightly").matches(stable));
+        assert!(!pinned("1.86.0").matches(nightly));
+        assert!(pinned("my-custom-toolchain").matches(stable));
+    }
+}
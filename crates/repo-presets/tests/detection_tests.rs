@@ -240,26 +240,28 @@ async fn test_node_package_json_without_node_modules() {
 }
 
 #[tokio::test]
-async fn test_node_apply_is_detection_only() {
+async fn test_node_apply_installs_with_npm() {
     let temp = TempDir::new().unwrap();
     let context = create_test_context(&temp);
     let provider = NodeProvider::new();
 
-    let report = provider.apply(&context).await.unwrap();
-    assert!(
-        report.is_detection_only(),
-        "NodeProvider.apply() must return DetectionOnly status, got: {:?}",
-        report.status
-    );
-    assert_eq!(
-        report.status,
-        ApplyStatus::DetectionOnly,
-        "Status must be DetectionOnly, not Success or Failed"
-    );
-    assert!(
-        !report.is_success(),
-        "NodeProvider.apply() must NOT return Success — it does no real work"
-    );
+    // An empty package.json needs no network to install
+    fs::write(
+        temp.path().join("package.json"),
+        r#"{"name": "empty", "version": "1.0.0"}"#,
+    )
+    .unwrap();
+
+    let report = match provider.apply(&context).await {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Skipping test: npm not available ({e})");
+            return;
+        }
+    };
+    assert_eq!(report.status, ApplyStatus::Success, "{:?}", report.errors);
+    assert!(report.actions_taken[0].contains("with npm"));
+    assert!(temp.path().join("package-lock.json").exists());
 }
 
 // ==========================================================================
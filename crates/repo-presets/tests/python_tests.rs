@@ -7,7 +7,7 @@ use repo_fs::{LayoutMode, NormalizedPath, WorkspaceLayout};
 use repo_presets::PresetStatus;
 use repo_presets::context::Context;
 use repo_presets::provider::PresetProvider;
-use repo_presets::python::UvProvider;
+use repo_presets::python::{PoetryProvider, UvProvider, VenvProvider};
 use std::collections::HashMap;
 use std::fs;
 use tempfile::TempDir;
@@ -29,7 +29,7 @@ async fn test_uv_provider_id() {
 
 #[tokio::test]
 async fn test_uv_provider_default() {
-    let provider = UvProvider;
+    let provider = UvProvider::default();
     assert_eq!(provider.id(), "env:python");
 }
 
@@ -171,6 +171,85 @@ async fn test_context_default_provider() {
     assert_eq!(context.provider(), "uv");
 }
 
+#[tokio::test]
+async fn test_context_poetry_provider() {
+    let temp = TempDir::new().unwrap();
+    let mut config = HashMap::new();
+    config.insert(
+        "provider".to_string(),
+        toml::Value::String("poetry".to_string()),
+    );
+
+    let context = create_context_with_config(&temp, config);
+    assert_eq!(context.provider(), "poetry");
+}
+
+fn poetry_available() -> bool {
+    std::process::Command::new("poetry")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn write_poetry_project(temp: &TempDir) {
+    fs::write(
+        temp.path().join("pyproject.toml"),
+        "[tool.poetry]\nname = \"test-project\"\nversion = \"0.1.0\"\n\n\
+         [tool.poetry.dependencies]\npython = \"^3.12\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("poetry.lock"),
+        "[metadata]\nlock-version = \"2.0\"\n",
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_poetry_check_with_missing_environment() {
+    let temp = TempDir::new().unwrap();
+    write_poetry_project(&temp);
+    // An empty .venv is not an environment
+    fs::create_dir(temp.path().join(".venv")).unwrap();
+
+    let context = create_test_context(&temp);
+    let provider = PoetryProvider::new();
+    assert!(PoetryProvider::is_poetry_project(&context.python_root()));
+
+    let report = provider.check(&context).await.unwrap();
+
+    if poetry_available() {
+        assert_eq!(report.status, PresetStatus::Missing);
+        assert!(
+            report.details.iter().any(|d| d.contains("poetry install")),
+            "Missing environment should point at poetry install, got {:?}",
+            report.details
+        );
+    } else {
+        assert_eq!(report.status, PresetStatus::Broken);
+        assert!(report.details[0].contains("poetry not found"));
+    }
+}
+
+#[tokio::test]
+async fn test_poetry_check_without_poetry_config() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("pyproject.toml"),
+        "[project]\nname = \"test-project\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let context = create_test_context(&temp);
+    let report = PoetryProvider::new().check(&context).await.unwrap();
+
+    assert_eq!(report.status, PresetStatus::Broken);
+    assert!(report.details[0].contains("[tool.poetry]"));
+
+    let apply = PoetryProvider::new().apply(&context).await.unwrap();
+    assert!(apply.is_failure());
+}
+
 #[tokio::test]
 async fn test_uv_check_report_details_are_actionable() {
     // Verify that check reports contain useful details, not empty strings
@@ -228,3 +307,160 @@ async fn test_uv_check_with_project_files_but_no_venv() {
         "Project with Python files but no venv must not be healthy"
     );
 }
+
+#[tokio::test]
+async fn test_monorepo_detects_sub_package_venv() {
+    // Root project and a sub-package, each with its own manifest; only the
+    // sub-package has a venv
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("pyproject.toml"),
+        "[project]\nname = \"root\"\n",
+    )
+    .unwrap();
+    let package = temp.path().join("packages/api");
+    fs::create_dir_all(package.join("src")).unwrap();
+    fs::write(
+        package.join("pyproject.toml"),
+        "[project]\nname = \"api\"\n",
+    )
+    .unwrap();
+    let python_bin = if cfg!(windows) {
+        package.join(".venv/Scripts/python.exe")
+    } else {
+        package.join(".venv/bin/python")
+    };
+    fs::create_dir_all(python_bin.parent().unwrap()).unwrap();
+    fs::write(&python_bin, "").unwrap();
+
+    let root_context = create_test_context(&temp);
+    let provider = VenvProvider::new();
+    assert!(!provider.check_venv_at_path(&root_context.venv_path()));
+
+    let context = root_context.with_working_dir(NormalizedPath::new(package.join("src")));
+    assert_eq!(
+        context.venv_path(),
+        NormalizedPath::new(package.join(".venv"))
+    );
+    assert!(provider.check_venv_at_path(&context.venv_path()));
+}
+
+#[tokio::test]
+async fn test_venv_with_outdated_lock_reports_degraded() {
+    let provider = VenvProvider::new();
+    if !provider.check_python_available_sync() {
+        eprintln!("Skipping test: Python not available");
+        return;
+    }
+
+    // A venv whose packages were installed before uv.lock last changed
+    let temp = TempDir::new().unwrap();
+    let venv = temp.path().join(".venv");
+    let python_bin = if cfg!(windows) {
+        venv.join("Scripts/python.exe")
+    } else {
+        venv.join("bin/python")
+    };
+    fs::create_dir_all(python_bin.parent().unwrap()).unwrap();
+    fs::write(&python_bin, "").unwrap();
+    fs::write(venv.join("pyvenv.cfg"), "home = /usr/bin\n").unwrap();
+    fs::write(temp.path().join("uv.lock"), "version = 1\n").unwrap();
+
+    let installed = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+    fs::File::options()
+        .write(true)
+        .open(venv.join("pyvenv.cfg"))
+        .unwrap()
+        .set_modified(installed)
+        .unwrap();
+
+    let context = create_test_context(&temp);
+    let report = provider.check(&context).await.unwrap();
+    assert!(
+        matches!(&report.status, PresetStatus::Degraded { reason } if reason.contains("uv.lock")),
+        "Venv older than its lock file must be degraded, got {:?}",
+        report.status
+    );
+    assert_eq!(report.action, repo_presets::ActionType::Update);
+
+    // Once the environment is refreshed it is healthy again
+    fs::File::options()
+        .write(true)
+        .open(venv.join("pyvenv.cfg"))
+        .unwrap()
+        .set_modified(std::time::SystemTime::now())
+        .unwrap();
+    let report = provider.check(&context).await.unwrap();
+    assert_eq!(report.status, PresetStatus::Healthy);
+}
+
+/// Write an executable stand-in for uv to `dir` that runs `script`
+#[cfg(unix)]
+fn fake_uv(dir: &std::path::Path, script: &str) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = dir.join("uv");
+    fs::write(&path, format!("#!/bin/sh\n{script}")).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}
+
+/// Creates the venv at `uv venv --python <version> <path>`
+#[cfg(unix)]
+const FAKE_UV_VENV: &str =
+    "if [ \"$1\" = venv ]; then mkdir -p \"$4/bin\" && touch \"$4/bin/python\"; exit 0; fi\n";
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_uv_apply_rolls_back_venv_when_install_fails() {
+    let temp = TempDir::new().unwrap();
+    let bin = TempDir::new().unwrap();
+    // The stand-in rejects the invalid requirement as uv would, so the
+    // install after venv creation fails
+    fs::write(temp.path().join("requirements.txt"), "not a requirement!\n").unwrap();
+    let uv = fake_uv(
+        bin.path(),
+        &format!("{FAKE_UV_VENV}echo \"error: invalid requirement\" >&2\nexit 2\n"),
+    );
+
+    let context = create_test_context(&temp);
+    let venv = context.venv_path();
+    let provider = UvProvider::with_program(uv);
+
+    let report = provider.apply(&context).await.unwrap();
+    assert!(report.is_failure(), "Install must fail: {:?}", report);
+    assert_eq!(
+        report.performed,
+        vec![repo_presets::AppliedAction::CreatedDir(venv.clone())]
+    );
+
+    provider.rollback(&context, &report).await.unwrap();
+    assert!(!venv.exists(), "Rollback must remove the venv it created");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_uv_apply_rolls_back_venv_when_install_cannot_start() {
+    let temp = TempDir::new().unwrap();
+    let bin = TempDir::new().unwrap();
+    fs::write(temp.path().join("requirements.txt"), "requests\n").unwrap();
+    // uv disappears after creating the venv, so the install cannot be spawned
+    let uv = fake_uv(
+        bin.path(),
+        "if [ \"$1\" = venv ]; then mkdir -p \"$4/bin\" && rm \"$0\"; fi\n",
+    );
+
+    let context = create_test_context(&temp);
+    let venv = context.venv_path();
+    let provider = UvProvider::with_program(uv);
+
+    let report = provider.apply(&context).await.unwrap();
+    assert!(report.is_failure(), "Install must fail: {:?}", report);
+    assert_eq!(
+        report.performed,
+        vec![repo_presets::AppliedAction::CreatedDir(venv.clone())]
+    );
+
+    provider.rollback(&context, &report).await.unwrap();
+    assert!(!venv.exists(), "Rollback must remove the venv it created");
+}
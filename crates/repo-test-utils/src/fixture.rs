@@ -0,0 +1,114 @@
+//! Generated large repositories for benchmarks and performance tests.
+//!
+//! [`large_repo`] builds a repository with any number of registry rules
+//! and tools, so sync and check can be measured at scale. The rules vary
+//! the way real ones do: some are scoped to file patterns, some to a
+//! single tool, and all carry tags.
+
+use std::fs;
+
+use crate::repo::TestRepo;
+
+/// A rule produced by [`generate_rules`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedRule {
+    /// Rule ID, e.g. `rule-007`
+    pub id: String,
+    /// Markdown content
+    pub content: String,
+    /// Tags for categorization
+    pub tags: Vec<String>,
+    /// Tools the rule is limited to; empty means every tool
+    pub tools: Vec<String>,
+    /// File patterns the rule targets; empty means every file
+    pub paths: Vec<String>,
+}
+
+/// Generate `count` rules with deterministic, varied content.
+///
+/// Every third rule targets file patterns and every fifth is limited to
+/// `claude`.
+pub fn generate_rules(count: usize) -> Vec<GeneratedRule> {
+    (0..count)
+        .map(|i| GeneratedRule {
+            id: format!("rule-{:03}", i),
+            content: format!(
+                "# Rule {i}\n\n\
+                 Follow convention {i} in every change.\n\n\
+                 - Prefer explicit names over abbreviations ({i}).\n\
+                 - Keep functions short and focused.\n\
+                 - Document why, not what.\n"
+            ),
+            tags: vec![format!("area-{}", i % 7), "generated".to_string()],
+            tools: if i % 5 == 4 {
+                vec!["claude".to_string()]
+            } else {
+                Vec::new()
+            },
+            paths: if i % 3 == 2 {
+                vec![format!("src/module_{}/**/*.rs", i)]
+            } else {
+                Vec::new()
+            },
+        })
+        .collect()
+}
+
+/// Render `rules` as a `.repository/rules/registry.toml`.
+pub fn registry_toml(rules: &[GeneratedRule]) -> String {
+    let quoted = |items: &[String]| {
+        items
+            .iter()
+            .map(|item| format!("{:?}", item))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut toml = String::from("version = \"1.0\"\n");
+    for (i, rule) in rules.iter().enumerate() {
+        toml.push_str(&format!(
+            "\n[[rules]]\n\
+             uuid = \"00000000-0000-4000-8000-{:012x}\"\n\
+             id = {:?}\n\
+             content = {:?}\n\
+             created = \"2026-01-01T00:00:00Z\"\n\
+             updated = \"2026-01-01T00:00:00Z\"\n\
+             tags = [{}]\n",
+            i,
+            rule.id,
+            rule.content,
+            quoted(&rule.tags),
+        ));
+        if !rule.tools.is_empty() {
+            toml.push_str(&format!("tools = [{}]\n", quoted(&rule.tools)));
+        }
+        if !rule.paths.is_empty() {
+            toml.push_str(&format!("paths = [{}]\n", quoted(&rule.paths)));
+        }
+        toml.push_str(&format!(
+            "content_hash = {:?}\n",
+            repo_fs::checksum::compute_content_checksum(&rule.content)
+        ));
+    }
+    toml
+}
+
+/// A standard-mode repository with `rules` generated registry rules and
+/// `tools` enabled, not yet synced.
+///
+/// # Panics
+/// Panics if the filesystem operations fail.
+pub fn large_repo(rules: usize, tools: &[&str]) -> TestRepo {
+    let mut repo = TestRepo::new();
+    crate::git::fake_git_dir(repo.root());
+    repo.init_repo_manager("standard", tools, &[]);
+
+    let rules_dir = repo.root().join(".repository").join("rules");
+    fs::create_dir_all(&rules_dir).unwrap();
+    fs::write(
+        rules_dir.join("registry.toml"),
+        registry_toml(&generate_rules(rules)),
+    )
+    .unwrap();
+    repo
+}
@@ -5,8 +5,10 @@
 //!
 //! # Modules
 //!
+//! - [`fixture`] — generated large repositories for benchmarks
 //! - [`git`] — git repository fixtures at three realism levels
 //! - [`repo`] — [`TestRepo`] builder for full repository-manager setup
 
+pub mod fixture;
 pub mod git;
 pub mod repo;
@@ -1,15 +1,35 @@
 //! Aider integration for Repository Manager.
 //!
-//! Manages `.aider.conf.yml` configuration file.
+//! Aider loads coding conventions from the Markdown files listed under
+//! `read:` in `.aider.conf.yml`. Rules are written to `CONVENTIONS.md` as
+//! managed blocks, and a managed entry in the `read:` list points Aider at
+//! that file. The rest of `.aider.conf.yml` belongs to the user: only the
+//! entry between `# repo:block:` markers is ever written or removed.
 //!
 //! Reference: https://aider.chat/docs/config/aider_conf.html
 
-use crate::generic::GenericToolIntegration;
+use crate::error::Result;
+use crate::integration::{ConfigLocation, Rule, SyncContext, ToolIntegration, validate_config};
+use repo_blocks::{FormatHandler, YamlFormatHandler, parse_blocks, remove_block, upsert_block};
+use repo_fs::{NormalizedPath, io};
 use repo_meta::schema::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
 };
+use uuid::Uuid;
 
-/// Creates an Aider integration.
+/// Aider's project configuration file.
+pub const CONFIG_FILE: &str = ".aider.conf.yml";
+
+/// Conventions file receiving the rules.
+pub const CONVENTIONS_FILE: &str = "CONVENTIONS.md";
+
+/// Block holding our `read:` entry; fixed so every sync finds the same block.
+const READ_ENTRY_BLOCK: Uuid = Uuid::from_u128(0x9f3c_2a71_5d04_4e8b_a6c1_3b7e_d2f0_8a15);
+
+/// Returns the ToolDefinition for Aider.
+///
+/// This provides the schema metadata for the registry while AiderIntegration
+/// handles the actual sync logic.
 ///
 /// Configuration files:
 /// - `.aider.conf.yml` - Project configuration (YAML)
@@ -17,17 +37,22 @@ use repo_meta::schema::{
 ///
 /// Config priority: home dir < git root < current dir (last wins)
 /// Environment variables: AIDER_xxx
-pub fn aider_integration() -> GenericToolIntegration {
-    GenericToolIntegration::new(ToolDefinition {
+pub fn aider_definition() -> ToolDefinition {
+    ToolDefinition {
         meta: ToolMeta {
             name: "Aider".into(),
             slug: "aider".into(),
             description: Some("Aider AI pair programming CLI".into()),
+            override_builtin: false,
+            open_command: None,
         },
         integration: ToolIntegrationConfig {
-            config_path: ".aider.conf.yml".into(),
+            config_path: CONFIG_FILE.into(),
             config_type: ConfigType::Yaml,
-            additional_paths: vec!["CONVENTIONS.md".into()],
+            additional_paths: vec![CONVENTIONS_FILE.into()],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             supports_custom_instructions: true,
@@ -35,13 +60,210 @@ pub fn aider_integration() -> GenericToolIntegration {
             supports_rules_directory: false,
         },
         schema_keys: None,
-    })
+    }
+}
+
+/// Creates an Aider integration.
+pub fn aider_integration() -> AiderIntegration {
+    AiderIntegration::new()
+}
+
+/// Aider integration.
+///
+/// Syncs rules to `CONVENTIONS.md` and keeps `CONVENTIONS.md` in the
+/// `read:` list of `.aider.conf.yml`.
+#[derive(Debug, Default)]
+pub struct AiderIntegration;
+
+impl AiderIntegration {
+    /// Creates a new Aider integration.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Write each rule as a managed block in the conventions file.
+    fn sync_conventions(path: &NormalizedPath, rules: &[Rule]) -> Result<()> {
+        let mut content = if path.exists() {
+            io::read_text(path)?
+        } else {
+            String::new()
+        };
+
+        for rule in rules {
+            content = upsert_block(&content, &rule.id, &rule.content)?;
+        }
+
+        io::write_text(path, &content)?;
+        Ok(())
+    }
+
+    /// Add our entry to the `read:` list, creating the config if needed.
+    fn sync_read_entry(path: &NormalizedPath) -> Result<()> {
+        let content = if path.exists() {
+            io::read_text(path)?
+        } else {
+            String::new()
+        };
+
+        let updated = with_read_entry(&content);
+        if updated != content || !path.exists() {
+            io::write_text(path, &updated)?;
+        }
+        Ok(())
+    }
+}
+
+impl ToolIntegration for AiderIntegration {
+    fn name(&self) -> &str {
+        "aider"
+    }
+
+    fn config_locations(&self) -> Vec<ConfigLocation> {
+        vec![
+            ConfigLocation::file(CONFIG_FILE, ConfigType::Yaml),
+            ConfigLocation::file(CONVENTIONS_FILE, ConfigType::Markdown),
+        ]
+    }
+
+    fn sync(&self, context: &SyncContext, rules: &[Rule]) -> Result<()> {
+        let rules = context.render_rules(rules);
+        Self::sync_conventions(&context.root.join(CONVENTIONS_FILE), &rules)?;
+        Self::sync_read_entry(&context.root.join(CONFIG_FILE))
+    }
+
+    fn shared_files(&self) -> Vec<String> {
+        vec![CONFIG_FILE.into(), CONVENTIONS_FILE.into()]
+    }
+
+    fn remove_managed(&self, root: &NormalizedPath) -> Result<()> {
+        let config = root.join(CONFIG_FILE);
+        if config.exists() {
+            let content = io::read_text(&config)?;
+            let remaining = YamlFormatHandler::new().remove_block(&content, READ_ENTRY_BLOCK);
+            write_or_delete(&config, &content, &remaining)?;
+        }
+
+        let conventions = root.join(CONVENTIONS_FILE);
+        if conventions.exists() {
+            let content = io::read_text(&conventions)?;
+            let mut remaining = content.clone();
+            for block in parse_blocks(&content) {
+                remaining = remove_block(&remaining, &block.uuid)?;
+            }
+            write_or_delete(&conventions, &content, &remaining)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_output(&self, content: &str) -> Result<()> {
+        validate_config(self.name(), CONFIG_FILE, &ConfigType::Yaml, content)
+    }
+}
+
+/// Write `remaining` back if it changed, deleting the file if nothing but
+/// whitespace is left.
+fn write_or_delete(path: &NormalizedPath, original: &str, remaining: &str) -> Result<()> {
+    if remaining.trim().is_empty() {
+        std::fs::remove_file(path.to_native()).map_err(|e| crate::Error::SyncFailed {
+            tool: "aider".into(),
+            message: format!("Failed to remove {}: {}", path.as_str(), e),
+        })?;
+    } else if remaining != original {
+        io::write_text(path, remaining)?;
+    }
+    Ok(())
+}
+
+/// Add a managed `CONVENTIONS.md` entry to the `read:` list of `content`.
+///
+/// A block-style `read:` list gets the entry appended as its last item,
+/// wrapped in block markers; without a `read:` key, a managed `read:` list
+/// is appended to the file. Content already listing the conventions file,
+/// or whose `read:` value is written inline (`read: [a.md]`), is returned
+/// unchanged.
+fn with_read_entry(content: &str) -> String {
+    let handler = YamlFormatHandler::new();
+    if handler.has_block(content, READ_ENTRY_BLOCK) {
+        return content.to_string();
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(key) = lines.iter().position(|l| l.starts_with("read:")) else {
+        let block = format!("read:\n  - {}", CONVENTIONS_FILE);
+        return handler.write_block(content, READ_ENTRY_BLOCK, &block) + "\n";
+    };
+
+    let inline = lines[key]["read:".len()..].trim();
+    if !inline.is_empty() && !inline.starts_with('#') {
+        if !inline.contains(CONVENTIONS_FILE) {
+            tracing::warn!(
+                "{} lists `read:` inline; add {} to it manually",
+                CONFIG_FILE,
+                CONVENTIONS_FILE
+            );
+        }
+        return content.to_string();
+    }
+
+    // Items of the list: indented lines, or `- ` at column 0 (both are
+    // valid YAML), interleaved with blank lines and comments
+    let mut last_item = key;
+    let mut indent = "  ";
+    for (i, line) in lines.iter().enumerate().skip(key + 1) {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t']) && !trimmed.starts_with('-') {
+            break;
+        }
+        if let Some(item) = trimmed.strip_prefix('-') {
+            if item.trim().trim_matches(['"', '\'']) == CONVENTIONS_FILE {
+                return content.to_string();
+            }
+            if last_item == key {
+                indent = &line[..line.len() - trimmed.len()];
+            }
+        }
+        last_item = i;
+    }
+
+    let entry = format!(
+        "# repo:block:{id}\n{indent}- {file}\n# /repo:block:{id}",
+        id = READ_ENTRY_BLOCK,
+        indent = indent,
+        file = CONVENTIONS_FILE
+    );
+    let mut out: Vec<&str> = lines[..=last_item].to_vec();
+    out.push(&entry);
+    out.extend_from_slice(&lines[last_item + 1..]);
+    out.join("\n") + "\n"
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::integration::ToolIntegration;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn rule(id: &str, content: &str) -> Rule {
+        Rule {
+            id: id.into(),
+            content: content.into(),
+            paths: vec![],
+        }
+    }
+
+    fn read_list(content: &str) -> Vec<String> {
+        let value: serde_yaml::Value = serde_yaml::from_str(content).unwrap();
+        value["read"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect()
+    }
 
     #[test]
     fn test_name() {
@@ -57,4 +279,93 @@ mod tests {
         assert_eq!(locations[0].path, ".aider.conf.yml");
         assert_eq!(locations[1].path, "CONVENTIONS.md");
     }
+
+    #[test]
+    fn test_sync_writes_conventions_and_read_entry() {
+        let temp = TempDir::new().unwrap();
+        let context = SyncContext::new(NormalizedPath::new(temp.path()));
+
+        aider_integration()
+            .sync(&context, &[rule("style", "Use snake_case.")])
+            .unwrap();
+
+        let conventions = fs::read_to_string(temp.path().join(CONVENTIONS_FILE)).unwrap();
+        assert!(conventions.contains("<!-- repo:block:style -->"));
+        assert!(conventions.contains("Use snake_case."));
+
+        let config = fs::read_to_string(temp.path().join(CONFIG_FILE)).unwrap();
+        assert_eq!(read_list(&config), vec![CONVENTIONS_FILE]);
+    }
+
+    #[test]
+    fn test_read_entry_joins_existing_list() {
+        let content = "# My settings\nmodel: sonnet\nread:\n  - NOTES.md # team notes\n\nauto-commits: false\n";
+
+        let updated = with_read_entry(content);
+
+        assert_eq!(read_list(&updated), vec!["NOTES.md", CONVENTIONS_FILE]);
+        assert!(updated.starts_with("# My settings\nmodel: sonnet\n"));
+        assert!(updated.contains("  - NOTES.md # team notes\n"));
+        assert!(updated.ends_with("\nauto-commits: false\n"));
+        // Idempotent
+        assert_eq!(with_read_entry(&updated), updated);
+    }
+
+    #[test]
+    fn test_read_entry_matches_unindented_items() {
+        let updated = with_read_entry("read:\n- NOTES.md\n");
+        assert!(updated.contains(&format!("\n- {}\n", CONVENTIONS_FILE)));
+        assert_eq!(read_list(&updated), vec!["NOTES.md", CONVENTIONS_FILE]);
+    }
+
+    #[test]
+    fn test_read_entry_skips_listed_or_inline_read() {
+        let listed = "read:\n  - \"CONVENTIONS.md\"\n";
+        assert_eq!(with_read_entry(listed), listed);
+
+        let inline = "read: [NOTES.md]\n";
+        assert_eq!(with_read_entry(inline), inline);
+    }
+
+    #[test]
+    fn test_remove_managed_keeps_user_config() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        let user_config = "model: sonnet # preferred\nread:\n  - NOTES.md\n";
+        fs::write(temp.path().join(CONFIG_FILE), user_config).unwrap();
+
+        let integration = aider_integration();
+        integration
+            .sync(&SyncContext::new(root.clone()), &[rule("style", "x")])
+            .unwrap();
+        integration.remove_managed(&root).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(temp.path().join(CONFIG_FILE)).unwrap(),
+            user_config
+        );
+        // Nothing but our blocks: the conventions file goes away
+        assert!(!temp.path().join(CONVENTIONS_FILE).exists());
+    }
+
+    #[test]
+    fn test_remove_managed_deletes_created_config() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+
+        let integration = aider_integration();
+        integration
+            .sync(&SyncContext::new(root.clone()), &[rule("style", "x")])
+            .unwrap();
+        fs::write(
+            temp.path().join(CONVENTIONS_FILE),
+            fs::read_to_string(temp.path().join(CONVENTIONS_FILE)).unwrap() + "\nOur own notes.\n",
+        )
+        .unwrap();
+        integration.remove_managed(&root).unwrap();
+
+        assert!(!temp.path().join(CONFIG_FILE).exists());
+        let conventions = fs::read_to_string(temp.path().join(CONVENTIONS_FILE)).unwrap();
+        assert_eq!(conventions.trim(), "Our own notes.");
+    }
 }
@@ -6,7 +6,7 @@
 
 use crate::generic::GenericToolIntegration;
 use repo_meta::schema::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
 };
 
 /// Creates an Amazon Q Developer integration.
@@ -22,11 +22,16 @@ pub fn amazonq_integration() -> GenericToolIntegration {
             name: "Amazon Q".into(),
             slug: "amazonq".into(),
             description: Some("Amazon Q Developer AI assistant".into()),
+            override_builtin: false,
+            open_command: None,
         },
         integration: ToolIntegrationConfig {
             config_path: ".amazonq/rules/".into(),
             config_type: ConfigType::Markdown,
             additional_paths: vec![],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             supports_custom_instructions: true,
@@ -4,7 +4,7 @@
 
 use crate::generic::GenericToolIntegration;
 use repo_meta::schema::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
 };
 
 /// Creates a Claude integration.
@@ -17,11 +17,16 @@ pub fn claude_integration() -> GenericToolIntegration {
             name: "Claude".into(),
             slug: "claude".into(),
             description: Some("Anthropic Claude AI assistant".into()),
+            override_builtin: false,
+            open_command: None,
         },
         integration: ToolIntegrationConfig {
             config_path: "CLAUDE.md".into(),
             config_type: ConfigType::Markdown,
             additional_paths: vec![".claude/rules/".into()],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             supports_custom_instructions: true,
@@ -82,10 +87,12 @@ mod tests {
             Rule {
                 id: "project-context".to_string(),
                 content: "This is a Rust project using cargo.".to_string(),
+                paths: vec![],
             },
             Rule {
                 id: "coding-standards".to_string(),
                 content: "Follow Rust best practices.".to_string(),
+                paths: vec![],
             },
         ];
 
@@ -114,6 +121,7 @@ mod tests {
         let rules = vec![Rule {
             id: "context".to_string(),
             content: "Initial context".to_string(),
+            paths: vec![],
         }];
 
         let integration = claude_integration();
@@ -123,6 +131,7 @@ mod tests {
         let rules = vec![Rule {
             id: "context".to_string(),
             content: "Updated context".to_string(),
+            paths: vec![],
         }];
         integration.sync(&context, &rules).unwrap();
 
@@ -149,6 +158,7 @@ mod tests {
         let rules = vec![Rule {
             id: "auto-context".to_string(),
             content: "Managed context".to_string(),
+            paths: vec![],
         }];
 
         let integration = claude_integration();
@@ -7,7 +7,7 @@
 
 use crate::generic::GenericToolIntegration;
 use repo_meta::schema::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
 };
 
 /// Creates a Claude Desktop integration.
@@ -26,6 +26,8 @@ pub fn claude_desktop_integration() -> GenericToolIntegration {
             name: "Claude Desktop".into(),
             slug: "claude_desktop".into(),
             description: Some("Claude Desktop GUI application".into()),
+            override_builtin: false,
+            open_command: None,
         },
         integration: ToolIntegrationConfig {
             // Claude Desktop has no project-level rules file;
@@ -33,6 +35,9 @@ pub fn claude_desktop_integration() -> GenericToolIntegration {
             config_path: ".claude-desktop".into(),
             config_type: ConfigType::Text,
             additional_paths: vec![],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             supports_custom_instructions: false,
@@ -6,7 +6,7 @@
 
 use crate::generic::GenericToolIntegration;
 use repo_meta::schema::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
 };
 
 /// Creates a Cline integration.
@@ -23,11 +23,16 @@ pub fn cline_integration() -> GenericToolIntegration {
             name: "Cline".into(),
             slug: "cline".into(),
             description: Some("Cline AI coding assistant for VS Code".into()),
+            override_builtin: false,
+            open_command: None,
         },
         integration: ToolIntegrationConfig {
             config_path: ".clinerules".into(),
             config_type: ConfigType::Text,
             additional_paths: vec![".clinerules/".into()],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             supports_custom_instructions: true,
@@ -72,6 +77,7 @@ mod tests {
         let rules = vec![Rule {
             id: "coding-style".to_string(),
             content: "Use TypeScript strict mode.".to_string(),
+            paths: vec![],
         }];
 
         let integration = cline_integration();
@@ -0,0 +1,177 @@
+//! OpenAI Codex CLI integration for Repository Manager.
+//!
+//! Manages `AGENTS.md` file using managed blocks for rule content.
+//!
+//! Codex reads MCP servers from `~/.codex/config.toml`; see
+//! [`crate::mcp_registry`] for that spec.
+
+use crate::generic::GenericToolIntegration;
+use repo_meta::schema::{
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+};
+
+/// Creates a Codex CLI integration.
+///
+/// Returns a GenericToolIntegration configured for Codex's `AGENTS.md` file.
+/// Uses raw content mode (no headers), like the other agent instruction files.
+pub fn codex_integration() -> GenericToolIntegration {
+    GenericToolIntegration::new(ToolDefinition {
+        meta: ToolMeta {
+            name: "Codex".into(),
+            slug: "codex".into(),
+            description: Some("Codex CLI - OpenAI's coding agent".into()),
+            override_builtin: false,
+            open_command: None,
+        },
+        integration: ToolIntegrationConfig {
+            config_path: "AGENTS.md".into(),
+            config_type: ConfigType::Markdown,
+            additional_paths: vec![],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
+        },
+        capabilities: ToolCapabilities {
+            supports_custom_instructions: true,
+            supports_mcp: true,
+            supports_rules_directory: false,
+        },
+        schema_keys: None,
+    })
+    .with_raw_content(true)
+}
+
+/// Type alias for backward compatibility.
+///
+/// Prefer using `codex_integration()` factory function for new code.
+pub type CodexIntegration = GenericToolIntegration;
+
+/// Creates a new Codex integration (legacy API).
+///
+/// # Deprecated
+/// Use `codex_integration()` instead.
+#[deprecated(note = "Use codex_integration() instead")]
+pub fn new() -> GenericToolIntegration {
+    codex_integration()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::{Rule, SyncContext, ToolIntegration};
+    use repo_fs::NormalizedPath;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_name() {
+        let integration = codex_integration();
+        assert_eq!(integration.name(), "codex");
+    }
+
+    #[test]
+    fn test_config_locations() {
+        let integration = codex_integration();
+        let locations = integration.config_locations();
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].path, "AGENTS.md");
+    }
+
+    #[test]
+    fn test_sync_creates_agents_md() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp_dir.path());
+
+        let context = SyncContext::new(root);
+        let rules = vec![
+            Rule {
+                id: "rule-1".to_string(),
+                content: "First rule content".to_string(),
+                paths: vec![],
+            },
+            Rule {
+                id: "rule-2".to_string(),
+                content: "Second rule content".to_string(),
+                paths: vec![],
+            },
+        ];
+
+        let integration = codex_integration();
+        integration.sync(&context, &rules).unwrap();
+
+        let agents_md_path = temp_dir.path().join("AGENTS.md");
+        assert!(agents_md_path.exists());
+
+        let content = fs::read_to_string(&agents_md_path).unwrap();
+        assert!(content.contains("<!-- repo:block:rule-1 -->"));
+        assert!(content.contains("First rule content"));
+        assert!(content.contains("<!-- /repo:block:rule-1 -->"));
+        assert!(content.contains("<!-- repo:block:rule-2 -->"));
+        assert!(content.contains("Second rule content"));
+        assert!(content.contains("<!-- /repo:block:rule-2 -->"));
+    }
+
+    #[test]
+    fn test_sync_uses_managed_blocks() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp_dir.path());
+
+        // Create with initial rule
+        let context = SyncContext::new(root.clone());
+        let rules = vec![Rule {
+            id: "my-rule".to_string(),
+            content: "Original content".to_string(),
+            paths: vec![],
+        }];
+
+        let integration = codex_integration();
+        integration.sync(&context, &rules).unwrap();
+
+        // Update the same rule
+        let rules = vec![Rule {
+            id: "my-rule".to_string(),
+            content: "Updated content".to_string(),
+            paths: vec![],
+        }];
+        integration.sync(&context, &rules).unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join("AGENTS.md")).unwrap();
+
+        // Should have updated content, not duplicated blocks
+        assert!(content.contains("Updated content"));
+        assert!(!content.contains("Original content"));
+
+        // Should only have one block marker pair
+        assert_eq!(content.matches("<!-- repo:block:my-rule -->").count(), 1);
+    }
+
+    #[test]
+    fn test_sync_preserves_manual_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp_dir.path());
+
+        // Create AGENTS.md with manual content
+        let manual_content = "# Manual rules\n\nDo not modify managed blocks below.\n";
+        fs::write(temp_dir.path().join("AGENTS.md"), manual_content).unwrap();
+
+        let context = SyncContext::new(root);
+        let rules = vec![Rule {
+            id: "auto-rule".to_string(),
+            content: "Automated rule".to_string(),
+            paths: vec![],
+        }];
+
+        let integration = codex_integration();
+        integration.sync(&context, &rules).unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join("AGENTS.md")).unwrap();
+
+        // Manual content should be preserved
+        assert!(content.contains("# Manual rules"));
+        assert!(content.contains("Do not modify"));
+
+        // Managed block should be added
+        assert!(content.contains("<!-- repo:block:auto-rule -->"));
+        assert!(content.contains("Automated rule"));
+    }
+}
@@ -1,22 +1,25 @@
 //! GitHub Copilot integration for Repository Manager.
 //!
 //! Manages `.github/copilot-instructions.md` file using managed blocks.
-//! Also supports path-specific instructions in `.github/instructions/`.
+//! Rules with target paths are written as path-specific instructions in
+//! `.github/instructions/` instead.
 //!
 //! Reference: https://docs.github.com/copilot/customizing-copilot/adding-custom-instructions-for-github-copilot
 
 use crate::generic::GenericToolIntegration;
 use repo_meta::schema::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
 };
 
 /// Creates a GitHub Copilot integration.
 ///
 /// Configuration files:
-/// - `.github/copilot-instructions.md` - Main instructions file (Markdown)
-/// - `.github/instructions/` - Directory for path-specific `.instructions.md` files
+/// - `.github/copilot-instructions.md` - Main instructions file (Markdown),
+///   holding rules without target paths
+/// - `.github/instructions/` - One `{id}.instructions.md` file per rule with
+///   target paths
 ///
-/// Format: Markdown with optional YAML frontmatter for path-specific files:
+/// Format: Markdown, with YAML frontmatter for path-specific files:
 /// ```yaml
 /// ---
 /// applyTo: "**/*.py"
@@ -28,11 +31,16 @@ pub fn copilot_integration() -> GenericToolIntegration {
             name: "GitHub Copilot".into(),
             slug: "copilot".into(),
             description: Some("GitHub Copilot AI coding assistant".into()),
+            override_builtin: false,
+            open_command: None,
         },
         integration: ToolIntegrationConfig {
             config_path: ".github/copilot-instructions.md".into(),
             config_type: ConfigType::Markdown,
             additional_paths: vec![".github/instructions/".into()],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             supports_custom_instructions: true,
@@ -41,6 +49,7 @@ pub fn copilot_integration() -> GenericToolIntegration {
         },
         schema_keys: None,
     })
+    .with_scoped_rules_dir(".github/instructions/")
 }
 
 #[cfg(test)]
@@ -80,6 +89,7 @@ mod tests {
         let rules = vec![Rule {
             id: "python-style".to_string(),
             content: "Use type hints for all function parameters.".to_string(),
+            paths: vec![],
         }];
 
         let integration = copilot_integration();
@@ -92,4 +102,86 @@ mod tests {
         assert!(content.contains("python-style"));
         assert!(content.contains("Use type hints"));
     }
+
+    #[test]
+    fn test_sync_writes_scoped_rules_to_instruction_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp_dir.path());
+
+        let context = SyncContext::new(root);
+        let rules = vec![
+            Rule {
+                id: "general".to_string(),
+                content: "Be concise.".to_string(),
+                paths: vec![],
+            },
+            Rule {
+                id: "python-style".to_string(),
+                content: "Use type hints.".to_string(),
+                paths: vec!["**/*.py".to_string(), "**/*.pyi".to_string()],
+            },
+        ];
+
+        let integration = copilot_integration();
+        integration.sync(&context, &rules).unwrap();
+
+        let main =
+            fs::read_to_string(temp_dir.path().join(".github/copilot-instructions.md")).unwrap();
+        assert!(main.contains("<!-- repo:block:general -->"));
+        assert!(!main.contains("python-style"));
+
+        let scoped = fs::read_to_string(
+            temp_dir
+                .path()
+                .join(".github/instructions/python-style.instructions.md"),
+        )
+        .unwrap();
+        assert!(scoped.starts_with("---\napplyTo: \"**/*.py,**/*.pyi\"\n---\n"));
+        assert!(scoped.contains("Use type hints."));
+
+        // Untargeted rules do not get instruction files
+        assert!(
+            !temp_dir
+                .path()
+                .join(".github/instructions/general.instructions.md")
+                .exists()
+        );
+        assert_eq!(
+            integration.rule_files(&rules),
+            vec![".github/instructions/python-style.instructions.md"]
+        );
+    }
+
+    #[test]
+    fn test_sync_removes_instruction_files_of_removed_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp_dir.path());
+        let instructions = temp_dir.path().join(".github/instructions");
+
+        let context = SyncContext::new(root);
+        let scoped = |id: &str| Rule {
+            id: id.to_string(),
+            content: format!("{id} content"),
+            paths: vec!["src/**".to_string()],
+        };
+
+        let integration = copilot_integration();
+        integration
+            .sync(&context, &[scoped("keep"), scoped("drop")])
+            .unwrap();
+        fs::write(
+            instructions.join("manual.instructions.md"),
+            "---\napplyTo: \"**\"\n---\n\nHand-written.\n",
+        )
+        .unwrap();
+
+        integration.sync(&context, &[scoped("keep")]).unwrap();
+
+        assert!(instructions.join("keep.instructions.md").exists());
+        assert!(!instructions.join("drop.instructions.md").exists());
+        assert!(
+            instructions.join("manual.instructions.md").exists(),
+            "hand-written instruction files must be preserved"
+        );
+    }
 }
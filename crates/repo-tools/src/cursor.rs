@@ -1,27 +1,66 @@
 //! Cursor integration for Repository Manager.
 //!
-//! Manages `.cursorrules` file using managed blocks for rule content.
+//! Manages either the legacy `.cursorrules` file, using managed blocks for
+//! rule content, or the `.cursor/rules/` directory with one MDC file per
+//! rule. See [`CursorMode`].
 
 use crate::generic::GenericToolIntegration;
 use repo_meta::schema::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
 };
+use serde::{Deserialize, Serialize};
+
+/// Where Cursor rules are written.
+///
+/// Selected by `format` in the `[tool_settings.cursor]` table of
+/// `config.toml`:
+///
+/// ```toml
+/// [tool_settings.cursor]
+/// format = "mdc"
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CursorMode {
+    /// A single `.cursorrules` file with managed blocks
+    #[default]
+    Legacy,
+    /// One `.cursor/rules/{id}.mdc` file per rule, with YAML front matter
+    Mdc,
+}
 
 /// Creates a Cursor integration.
 ///
 /// Returns a GenericToolIntegration configured for Cursor's `.cursorrules` file.
 /// Uses raw content mode (no headers) for backward compatibility.
 pub fn cursor_integration() -> GenericToolIntegration {
+    cursor_integration_with_mode(CursorMode::Legacy)
+}
+
+/// Creates a Cursor integration writing rules in the given mode.
+pub fn cursor_integration_with_mode(mode: CursorMode) -> GenericToolIntegration {
+    match mode {
+        CursorMode::Legacy => legacy_integration(),
+        CursorMode::Mdc => mdc_integration(),
+    }
+}
+
+fn legacy_integration() -> GenericToolIntegration {
     GenericToolIntegration::new(ToolDefinition {
         meta: ToolMeta {
             name: "Cursor".into(),
             slug: "cursor".into(),
             description: Some("Cursor AI IDE".into()),
+            override_builtin: false,
+            open_command: Some("cursor {path}".into()),
         },
         integration: ToolIntegrationConfig {
             config_path: ".cursorrules".into(),
             config_type: ConfigType::Text,
             additional_paths: vec![],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             supports_custom_instructions: true,
@@ -33,6 +72,34 @@ pub fn cursor_integration() -> GenericToolIntegration {
     .with_raw_content(true)
 }
 
+fn mdc_integration() -> GenericToolIntegration {
+    GenericToolIntegration::new(ToolDefinition {
+        meta: ToolMeta {
+            name: "Cursor".into(),
+            slug: "cursor".into(),
+            description: Some("Cursor AI IDE".into()),
+            override_builtin: false,
+            open_command: Some("cursor {path}".into()),
+        },
+        integration: ToolIntegrationConfig {
+            config_path: ".cursor/rules/".into(),
+            config_type: ConfigType::Directory {
+                pattern: "{id}.mdc".into(),
+            },
+            additional_paths: vec![],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
+        },
+        capabilities: ToolCapabilities {
+            supports_custom_instructions: true,
+            supports_mcp: true,
+            supports_rules_directory: true,
+        },
+        schema_keys: None,
+    })
+}
+
 /// Type alias for backward compatibility.
 ///
 /// Prefer using `cursor_integration()` factory function for new code.
@@ -79,10 +146,12 @@ mod tests {
             Rule {
                 id: "rule-1".to_string(),
                 content: "First rule content".to_string(),
+                paths: vec![],
             },
             Rule {
                 id: "rule-2".to_string(),
                 content: "Second rule content".to_string(),
+                paths: vec![],
             },
         ];
 
@@ -111,6 +180,7 @@ mod tests {
         let rules = vec![Rule {
             id: "my-rule".to_string(),
             content: "Original content".to_string(),
+            paths: vec![],
         }];
 
         let integration = cursor_integration();
@@ -120,6 +190,7 @@ mod tests {
         let rules = vec![Rule {
             id: "my-rule".to_string(),
             content: "Updated content".to_string(),
+            paths: vec![],
         }];
         integration.sync(&context, &rules).unwrap();
 
@@ -146,6 +217,7 @@ mod tests {
         let rules = vec![Rule {
             id: "auto-rule".to_string(),
             content: "Automated rule".to_string(),
+            paths: vec![],
         }];
 
         let integration = cursor_integration();
@@ -161,4 +233,28 @@ mod tests {
         assert!(content.contains("<!-- repo:block:auto-rule -->"));
         assert!(content.contains("Automated rule"));
     }
+
+    #[test]
+    fn test_mdc_mode_writes_rule_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp_dir.path());
+
+        let integration = cursor_integration_with_mode(CursorMode::Mdc);
+        assert_eq!(integration.config_locations()[0].path, ".cursor/rules/");
+
+        let context = SyncContext::new(root);
+        let rules = vec![Rule {
+            id: "style".to_string(),
+            content: "Use 4 spaces".to_string(),
+            paths: vec![],
+        }];
+        integration.sync(&context, &rules).unwrap();
+
+        assert!(!temp_dir.path().join(".cursorrules").exists());
+        let content = fs::read_to_string(temp_dir.path().join(".cursor/rules/style.mdc")).unwrap();
+        assert_eq!(
+            content,
+            "---\ndescription: \"style\"\nalwaysApply: true\n---\n\nUse 4 spaces\n"
+        );
+    }
 }
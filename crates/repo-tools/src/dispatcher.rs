@@ -2,6 +2,15 @@
 //!
 //! The dispatcher uses ToolRegistry as the single source of truth for tool
 //! definitions, eliminating the previous 3-location duplication.
+//!
+//! Definitions loaded from `.repository/tools/` are dispatched through
+//! [`GenericToolIntegration`]. A definition whose slug matches a built-in
+//! tool replaces it only when it sets `override = true` in `[meta]`;
+//! otherwise the built-in wins and a warning is recorded.
+//!
+//! Installed extensions can contribute definitions as well (see
+//! [`DefinitionLoader::load_extension_tools`]). They are dispatched the same
+//! way but never replace a built-in or repository tool.
 
 use crate::aider::aider_integration;
 use crate::amazonq::amazonq_integration;
@@ -9,20 +18,64 @@ use crate::antigravity::antigravity_integration;
 use crate::claude::claude_integration;
 use crate::claude_desktop::claude_desktop_integration;
 use crate::cline::cline_integration;
+use crate::codex::codex_integration;
 use crate::copilot::copilot_integration;
-use crate::cursor::cursor_integration;
+use crate::cursor::{CursorMode, cursor_integration_with_mode};
 use crate::error::Result;
 use crate::gemini::gemini_integration;
 use crate::generic::GenericToolIntegration;
-use crate::integration::{Rule, SyncContext, ToolIntegration};
+use crate::integration::{Rule, SyncContext, ToolIntegration, validate_synced_output};
 use crate::jetbrains::jetbrains_integration;
-use crate::registry::{BUILTIN_COUNT, ToolRegistration, ToolRegistry};
+use crate::registry::{
+    ToolRegistration, ToolRegistry, ToolSource, UnknownTool, validate_tool_name,
+};
 use crate::roo::roo_integration;
 use crate::vscode::VSCodeIntegration;
-use crate::windsurf::windsurf_integration;
+use crate::windsurf::{WindsurfMode, windsurf_integration_with_mode};
 use crate::zed::zed_integration;
-use repo_meta::schema::ToolDefinition;
-use std::collections::HashMap;
+use repo_fs::NormalizedPath;
+use repo_meta::DefinitionLoader;
+use repo_meta::schema::{ToolCapabilities, ToolDefinition};
+use std::collections::{HashMap, HashSet};
+
+/// Which implementation of a tool is active, and why.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolResolution {
+    /// Built-in integration; no repository definition exists
+    Builtin,
+    /// Built-in integration; the repository definition does not set `override = true`
+    BuiltinShadowed,
+    /// Repository definition replacing the built-in (`override = true`)
+    Override,
+    /// Repository definition for a tool with no built-in
+    Repository,
+    /// Extension definition for a tool with no built-in or repository definition
+    Extension,
+}
+
+impl ToolResolution {
+    /// Where the active implementation comes from.
+    pub fn source(&self) -> ToolSource {
+        match self {
+            Self::Builtin | Self::BuiltinShadowed => ToolSource::Builtin,
+            Self::Override | Self::Repository => ToolSource::Repository,
+            Self::Extension => ToolSource::Extension,
+        }
+    }
+
+    /// Why this implementation is active.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::Builtin => "no repository definition",
+            Self::BuiltinShadowed => {
+                "repository definition ignored; set `override = true` in [meta] to use it"
+            }
+            Self::Override => "repository definition sets `override = true`",
+            Self::Repository => "no built-in tool with this slug",
+            Self::Extension => "provided by an installed extension",
+        }
+    }
+}
 
 /// Dispatches sync operations to appropriate tool integrations.
 ///
@@ -33,6 +86,14 @@ pub struct ToolDispatcher {
     registry: ToolRegistry,
     /// Additional schema-defined tools (loaded from .repository/tools/)
     schema_tools: HashMap<String, ToolDefinition>,
+    /// Slugs of the schema-defined tools contributed by extensions
+    extension_tools: HashSet<String>,
+    /// Problems found while registering schema-defined tools
+    warnings: Vec<String>,
+    /// Where the built-in Cursor integration writes rules
+    cursor_mode: CursorMode,
+    /// Where the built-in Windsurf integration writes rules
+    windsurf_mode: WindsurfMode,
 }
 
 impl ToolDispatcher {
@@ -41,32 +102,140 @@ impl ToolDispatcher {
         Self {
             registry: ToolRegistry::with_builtins(),
             schema_tools: HashMap::new(),
+            extension_tools: HashSet::new(),
+            warnings: Vec::new(),
+            cursor_mode: CursorMode::default(),
+            windsurf_mode: WindsurfMode::default(),
         }
     }
 
+    /// Set where the built-in Cursor integration writes rules.
+    pub fn with_cursor_mode(mut self, mode: CursorMode) -> Self {
+        self.cursor_mode = mode;
+        self
+    }
+
+    /// Set where the built-in Windsurf integration writes rules.
+    pub fn with_windsurf_mode(mut self, mode: WindsurfMode) -> Self {
+        self.windsurf_mode = mode;
+        self
+    }
+
     /// Create a dispatcher with pre-loaded tool definitions.
     pub fn with_definitions(definitions: HashMap<String, ToolDefinition>) -> Self {
         let mut dispatcher = Self::new();
-        for (_, def) in definitions {
+        let mut definitions: Vec<_> = definitions.into_values().collect();
+        definitions.sort_by(|a, b| a.meta.slug.cmp(&b.meta.slug));
+        for def in definitions {
             dispatcher.register(def);
         }
         dispatcher
     }
 
+    /// Create a dispatcher with the built-in tools, the definitions in
+    /// the repository's `.repository/tools/` and those contributed by
+    /// installed extensions.
+    ///
+    /// Definitions that fail to load are skipped and reported in
+    /// [`warnings`](Self::warnings).
+    pub fn from_repository(root: &NormalizedPath) -> Self {
+        let loader = DefinitionLoader::new();
+        let mut dispatcher = match loader.load_tools(root) {
+            Ok(loaded) => {
+                let mut dispatcher = Self::with_definitions(loaded.definitions);
+                dispatcher.warnings.extend(loaded.warnings);
+                dispatcher
+            }
+            Err(e) => {
+                let warning = format!("Failed to load tool definitions: {}", e);
+                tracing::warn!("{}", warning);
+                let mut dispatcher = Self::new();
+                dispatcher.warnings.push(warning);
+                dispatcher
+            }
+        };
+
+        match loader.load_extension_tools(root) {
+            Ok(loaded) => {
+                let mut definitions: Vec<_> = loaded.definitions.into_values().collect();
+                definitions.sort_by(|a, b| a.meta.slug.cmp(&b.meta.slug));
+                for def in definitions {
+                    dispatcher.register_extension(def);
+                }
+                dispatcher.warnings.extend(loaded.warnings);
+            }
+            Err(e) => {
+                let warning = format!("Failed to load extension tool definitions: {}", e);
+                tracing::warn!("{}", warning);
+                dispatcher.warnings.push(warning);
+            }
+        }
+        dispatcher
+    }
+
     /// Register a schema-defined tool.
+    ///
+    /// If a built-in tool has the same slug, the definition replaces it only
+    /// when it sets `override = true`; otherwise the built-in stays active
+    /// and a warning is recorded.
     pub fn register(&mut self, definition: ToolDefinition) {
-        self.schema_tools
-            .insert(definition.meta.slug.clone(), definition);
+        let slug = definition.meta.slug.clone();
+        if let Some(existing) = self.registry.get(&slug) {
+            if definition.meta.override_builtin {
+                // The definition names the tool; the category stays the built-in's
+                let mut registration = existing.clone();
+                registration.name = definition.meta.name.clone();
+                registration.definition = definition.clone();
+                registration.source = ToolSource::Repository;
+                self.registry.register(registration);
+            } else {
+                if existing.source == ToolSource::Repository {
+                    // An earlier definition overrode the built-in; restore it
+                    if let Some(builtin) = Self::builtin_registration(&slug) {
+                        self.registry.register(builtin);
+                    }
+                }
+                let warning = format!(
+                    "Tool definition '{}' has the same slug as a built-in tool and is ignored; \
+                     set `override = true` in [meta] to replace the built-in",
+                    slug
+                );
+                tracing::warn!("{}", warning);
+                self.warnings.push(warning);
+            }
+        }
+        self.schema_tools.insert(slug, definition);
+    }
+
+    /// Register a tool definition contributed by an extension.
+    ///
+    /// Extensions never replace a tool: if a built-in or repository tool has
+    /// the same slug, the definition is ignored. `check` reports such
+    /// collisions, so no warning is recorded here.
+    pub fn register_extension(&mut self, definition: ToolDefinition) {
+        let slug = definition.meta.slug.clone();
+        if self.has_tool(&slug) {
+            tracing::debug!("Extension tool '{}' is shadowed and ignored", slug);
+            return;
+        }
+        self.extension_tools.insert(slug.clone());
+        self.schema_tools.insert(slug, definition);
     }
 
     /// Get an integration for a tool by name.
     ///
     /// For built-in tools, returns optimized implementations.
-    /// For schema-defined tools, returns GenericToolIntegration.
+    /// For schema-defined tools, including overrides, returns
+    /// GenericToolIntegration.
     pub fn get_integration(&self, tool_name: &str) -> Option<Box<dyn ToolIntegration>> {
-        // Check built-in tools in registry
-        if self.registry.contains(tool_name) {
-            return Self::create_builtin_integration(tool_name);
+        // Check tools in registry
+        if let Some(reg) = self.registry.get(tool_name) {
+            return match reg.source {
+                ToolSource::Builtin => self.create_builtin_integration(tool_name),
+                ToolSource::Repository | ToolSource::Extension => Some(Box::new(
+                    GenericToolIntegration::new(reg.definition.clone()),
+                )),
+            };
         }
 
         // Fall back to schema-defined generic integration
@@ -75,18 +244,26 @@ impl ToolDispatcher {
         })
     }
 
+    /// Find the built-in registration for a slug.
+    fn builtin_registration(slug: &str) -> Option<ToolRegistration> {
+        crate::registry::builtin_registrations()
+            .into_iter()
+            .find(|r| r.slug == slug)
+    }
+
     /// Create a built-in integration by name.
     ///
     /// Returns `None` if the tool name is not recognized.
-    fn create_builtin_integration(name: &str) -> Option<Box<dyn ToolIntegration>> {
+    fn create_builtin_integration(&self, name: &str) -> Option<Box<dyn ToolIntegration>> {
         let integration: Box<dyn ToolIntegration> = match name {
             "vscode" => Box::new(VSCodeIntegration::new()),
-            "cursor" => Box::new(cursor_integration()),
+            "cursor" => Box::new(cursor_integration_with_mode(self.cursor_mode)),
             "claude" => Box::new(claude_integration()),
             "claude_desktop" => Box::new(claude_desktop_integration()),
-            "windsurf" => Box::new(windsurf_integration()),
+            "windsurf" => Box::new(windsurf_integration_with_mode(self.windsurf_mode)),
             "antigravity" => Box::new(antigravity_integration()),
             "gemini" => Box::new(gemini_integration()),
+            "codex" => Box::new(codex_integration()),
             "copilot" => Box::new(copilot_integration()),
             "cline" => Box::new(cline_integration()),
             "roo" => Box::new(roo_integration()),
@@ -96,11 +273,7 @@ impl ToolDispatcher {
             "amazonq" => Box::new(amazonq_integration()),
             _ => {
                 // Try to find in builtin registrations as fallback
-                match crate::registry::builtin_registrations()
-                    .into_iter()
-                    .find(|r| r.slug == name)
-                    .map(|r| r.definition)
-                {
+                match Self::builtin_registration(name).map(|r| r.definition) {
                     Some(def) => Box::new(GenericToolIntegration::new(def)),
                     None => return None,
                 }
@@ -114,6 +287,50 @@ impl ToolDispatcher {
         self.registry.contains(tool_name) || self.schema_tools.contains_key(tool_name)
     }
 
+    /// Check `name` against the available tools (built-in + schema-defined).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnknownTool`] if no tool is available under `name`.
+    pub fn validate_name(&self, name: &str) -> std::result::Result<(), UnknownTool> {
+        let available = self.list_available();
+        validate_tool_name(name, available.iter().map(String::as_str))
+    }
+
+    /// Which implementation of a tool is active, and why.
+    ///
+    /// Returns `None` if the tool is unknown.
+    pub fn resolution(&self, slug: &str) -> Option<ToolResolution> {
+        let defined = self.schema_tools.contains_key(slug);
+        match self.registry.get(slug).map(|r| r.source) {
+            Some(ToolSource::Repository | ToolSource::Extension) => Some(ToolResolution::Override),
+            Some(ToolSource::Builtin) if defined => Some(ToolResolution::BuiltinShadowed),
+            Some(ToolSource::Builtin) => Some(ToolResolution::Builtin),
+            None if self.extension_tools.contains(slug) => Some(ToolResolution::Extension),
+            None if defined => Some(ToolResolution::Repository),
+            None => None,
+        }
+    }
+
+    /// Get the active definition of a tool by name.
+    ///
+    /// Resolves in the same order as [`get_integration`](Self::get_integration).
+    pub fn definition(&self, slug: &str) -> Option<&ToolDefinition> {
+        self.registry
+            .get(slug)
+            .map(|reg| &reg.definition)
+            .or_else(|| self.schema_tools.get(slug))
+    }
+
+    /// Get the declared capabilities of a tool by name.
+    ///
+    /// Resolves in the same order as [`get_integration`](Self::get_integration):
+    /// registrations first (built-ins and overrides), then schema-defined tools.
+    /// Returns `None` if the tool is unknown.
+    pub fn capabilities(&self, slug: &str) -> Option<ToolCapabilities> {
+        self.definition(slug).map(|def| def.capabilities.clone())
+    }
+
     /// Sync rules to all specified tools.
     ///
     /// Returns the list of tool names that were successfully synced.
@@ -128,6 +345,7 @@ impl ToolDispatcher {
         for name in tool_names {
             if let Some(integration) = self.get_integration(name) {
                 integration.sync(context, rules)?;
+                validate_synced_output(integration.as_ref(), &context.root)?;
                 synced.push(name.clone());
             }
         }
@@ -155,8 +373,10 @@ impl ToolDispatcher {
     }
 
     /// Get the total number of registered tools.
+    ///
+    /// A schema-defined tool with the same slug as a built-in counts once.
     pub fn total_tool_count(&self) -> usize {
-        BUILTIN_COUNT + self.schema_tools.len()
+        self.list_available().len()
     }
 
     /// Get a registration by slug (for capability checking).
@@ -168,6 +388,11 @@ impl ToolDispatcher {
     pub fn registry(&self) -> &ToolRegistry {
         &self.registry
     }
+
+    /// Warnings recorded while loading and registering schema-defined tools.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
 }
 
 impl Default for ToolDispatcher {
@@ -179,7 +404,8 @@ impl Default for ToolDispatcher {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use repo_meta::schema::{ConfigType, ToolCapabilities, ToolIntegrationConfig, ToolMeta};
+    use crate::registry::BUILTIN_COUNT;
+    use repo_meta::schema::{ConfigType, RulesLayout, ToolIntegrationConfig, ToolMeta};
 
     fn create_custom_tool_definition() -> ToolDefinition {
         ToolDefinition {
@@ -187,11 +413,16 @@ mod tests {
                 name: "CustomTool".to_string(),
                 slug: "customtool".to_string(),
                 description: Some("A custom tool for testing".to_string()),
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: ".customtool/rules.md".to_string(),
                 config_type: ConfigType::Markdown,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities {
                 supports_custom_instructions: true,
@@ -301,6 +532,59 @@ mod tests {
         assert!(dispatcher.get_integration("unknown").is_none());
     }
 
+    #[test]
+    fn test_capabilities_builtin() {
+        let dispatcher = ToolDispatcher::new();
+
+        let claude = dispatcher.capabilities("claude").unwrap();
+        assert!(claude.supports_mcp);
+        assert!(claude.supports_custom_instructions);
+
+        // Aider only consumes rules, it has no MCP support
+        let aider = dispatcher.capabilities("aider").unwrap();
+        assert!(!aider.supports_mcp);
+        assert!(aider.supports_custom_instructions);
+    }
+
+    #[test]
+    fn test_capabilities_schema_tool() {
+        let mut dispatcher = ToolDispatcher::new();
+        dispatcher.register(create_custom_tool_definition());
+
+        let caps = dispatcher.capabilities("customtool").unwrap();
+        assert!(caps.supports_custom_instructions);
+        assert!(!caps.supports_mcp);
+        assert!(!caps.supports_rules_directory);
+    }
+
+    #[test]
+    fn test_capabilities_unknown_tool_returns_none() {
+        let dispatcher = ToolDispatcher::new();
+        assert!(dispatcher.capabilities("unknown_tool").is_none());
+    }
+
+    #[test]
+    fn test_cursor_mode_selects_rules_location() {
+        let dispatcher = ToolDispatcher::new();
+        let integration = dispatcher.get_integration("cursor").unwrap();
+        assert_eq!(integration.config_locations()[0].path, ".cursorrules");
+
+        let dispatcher = ToolDispatcher::new().with_cursor_mode(CursorMode::Mdc);
+        let integration = dispatcher.get_integration("cursor").unwrap();
+        assert_eq!(integration.config_locations()[0].path, ".cursor/rules/");
+    }
+
+    #[test]
+    fn test_windsurf_mode_selects_rules_location() {
+        let dispatcher = ToolDispatcher::new();
+        let integration = dispatcher.get_integration("windsurf").unwrap();
+        assert_eq!(integration.config_locations()[0].path, ".windsurfrules");
+
+        let dispatcher = ToolDispatcher::new().with_windsurf_mode(WindsurfMode::Directory);
+        let integration = dispatcher.get_integration("windsurf").unwrap();
+        assert_eq!(integration.config_locations()[0].path, ".windsurf/rules/");
+    }
+
     #[test]
     fn test_registry_access() {
         let dispatcher = ToolDispatcher::new();
@@ -318,4 +602,204 @@ mod tests {
         assert!(reg.is_some());
         assert_eq!(reg.unwrap().name, "Cursor");
     }
+
+    fn cursor_definition(override_builtin: bool) -> ToolDefinition {
+        let mut def = create_custom_tool_definition();
+        def.meta.name = "Team Cursor".to_string();
+        def.meta.slug = "cursor".to_string();
+        def.meta.override_builtin = override_builtin;
+        def.integration.config_path = ".cursor/team-rules.md".to_string();
+        def
+    }
+
+    #[test]
+    fn test_override_replaces_builtin() {
+        let mut dispatcher = ToolDispatcher::new();
+        dispatcher.register(cursor_definition(true));
+
+        assert_eq!(
+            dispatcher.resolution("cursor"),
+            Some(ToolResolution::Override)
+        );
+        let reg = dispatcher.get_registration("cursor").unwrap();
+        assert_eq!(reg.source, ToolSource::Repository);
+        // The display name comes from the definition, the category from the built-in
+        assert_eq!(reg.name, "Team Cursor");
+        assert_eq!(reg.category, crate::registry::ToolCategory::Ide);
+
+        let integration = dispatcher.get_integration("cursor").unwrap();
+        assert_eq!(
+            integration.config_locations()[0].path,
+            ".cursor/team-rules.md"
+        );
+        assert!(dispatcher.warnings().is_empty());
+        assert_eq!(dispatcher.total_tool_count(), BUILTIN_COUNT);
+    }
+
+    #[test]
+    fn test_builtin_wins_without_override() {
+        let mut dispatcher = ToolDispatcher::new();
+        dispatcher.register(cursor_definition(false));
+
+        assert_eq!(
+            dispatcher.resolution("cursor"),
+            Some(ToolResolution::BuiltinShadowed)
+        );
+        assert_eq!(
+            dispatcher.get_registration("cursor").unwrap().source,
+            ToolSource::Builtin
+        );
+        let integration = dispatcher.get_integration("cursor").unwrap();
+        assert_eq!(integration.config_locations()[0].path, ".cursorrules");
+
+        assert_eq!(dispatcher.warnings().len(), 1);
+        assert!(dispatcher.warnings()[0].contains("override = true"));
+    }
+
+    #[test]
+    fn test_later_definition_without_override_restores_builtin() {
+        let mut dispatcher = ToolDispatcher::new();
+        dispatcher.register(cursor_definition(true));
+        dispatcher.register(cursor_definition(false));
+
+        assert_eq!(
+            dispatcher.resolution("cursor"),
+            Some(ToolResolution::BuiltinShadowed)
+        );
+        assert_eq!(
+            dispatcher
+                .definition("cursor")
+                .unwrap()
+                .integration
+                .config_path,
+            ".cursorrules"
+        );
+    }
+
+    #[test]
+    fn test_repository_only_tool() {
+        let mut dispatcher = ToolDispatcher::new();
+        dispatcher.register(create_custom_tool_definition());
+
+        assert_eq!(
+            dispatcher.resolution("customtool"),
+            Some(ToolResolution::Repository)
+        );
+        assert_eq!(
+            dispatcher.resolution("customtool").unwrap().source(),
+            ToolSource::Repository
+        );
+        assert!(dispatcher.get_registration("customtool").is_none());
+        assert!(dispatcher.warnings().is_empty());
+        assert_eq!(
+            dispatcher.resolution("vscode"),
+            Some(ToolResolution::Builtin)
+        );
+        assert_eq!(dispatcher.resolution("unknown"), None);
+    }
+
+    #[test]
+    fn test_from_repository_loads_overrides() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let tools_dir = temp.path().join(".repository").join("tools");
+        std::fs::create_dir_all(&tools_dir).unwrap();
+        std::fs::write(
+            tools_dir.join("cursor.toml"),
+            "[meta]\nname = \"Cursor\"\nslug = \"cursor\"\noverride = true\n\n\
+             [integration]\nconfig_path = \".cursor/team-rules.md\"\ntype = \"markdown\"\n",
+        )
+        .unwrap();
+
+        let dispatcher = ToolDispatcher::from_repository(&NormalizedPath::new(temp.path()));
+        assert_eq!(
+            dispatcher.resolution("cursor"),
+            Some(ToolResolution::Override)
+        );
+    }
+
+    #[test]
+    fn test_extension_tool_never_replaces_existing_tools() {
+        let mut dispatcher = ToolDispatcher::new();
+        dispatcher.register(create_custom_tool_definition());
+        dispatcher.register_extension(cursor_definition(true));
+        let mut custom = create_custom_tool_definition();
+        custom.integration.config_path = ".ext/rules.md".to_string();
+        dispatcher.register_extension(custom);
+
+        assert_eq!(
+            dispatcher.resolution("cursor"),
+            Some(ToolResolution::Builtin)
+        );
+        assert_eq!(
+            dispatcher.resolution("customtool"),
+            Some(ToolResolution::Repository)
+        );
+        assert_eq!(
+            dispatcher
+                .definition("customtool")
+                .unwrap()
+                .integration
+                .config_path,
+            ".customtool/rules.md"
+        );
+    }
+
+    #[test]
+    fn test_from_repository_loads_extension_tools() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let ext_dir = temp.path().join(".repository/extensions/kit");
+        std::fs::create_dir_all(&ext_dir).unwrap();
+        std::fs::write(
+            ext_dir.join("repo_extension.toml"),
+            "[extension]\nname = \"kit\"\nversion = \"0.1.0\"\n\n\
+             [provides]\ntools = [\"kittool\"]\ntool_definitions = [\"kittool.toml\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            ext_dir.join("kittool.toml"),
+            "[meta]\nname = \"Kit Tool\"\nslug = \"kittool\"\n\n\
+             [integration]\nconfig_path = \".kittool.md\"\ntype = \"markdown\"\n",
+        )
+        .unwrap();
+
+        let dispatcher = ToolDispatcher::from_repository(&NormalizedPath::new(temp.path()));
+        assert_eq!(
+            dispatcher.resolution("kittool"),
+            Some(ToolResolution::Extension)
+        );
+        assert_eq!(
+            dispatcher.resolution("kittool").unwrap().source(),
+            ToolSource::Extension
+        );
+        assert!(dispatcher.list_available().contains(&"kittool".to_string()));
+
+        let context = SyncContext::new(NormalizedPath::new(temp.path()));
+        let rules = vec![Rule {
+            id: "style".to_string(),
+            content: "Use tabs".to_string(),
+            paths: vec![],
+        }];
+        let synced = dispatcher
+            .sync_all(&context, &["kittool".to_string()], &rules)
+            .unwrap();
+        assert_eq!(synced, vec!["kittool"]);
+        let content = std::fs::read_to_string(temp.path().join(".kittool.md")).unwrap();
+        assert!(content.contains("Use tabs"));
+    }
+
+    #[test]
+    fn test_sync_all_builtins_produce_valid_output() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let context = SyncContext::new(NormalizedPath::new(temp.path()));
+        let dispatcher = ToolDispatcher::new();
+        let tools = dispatcher.list_available();
+        let rules = vec![Rule {
+            id: "style".to_string(),
+            content: "Use \"double quotes\" and: colons".to_string(),
+            paths: vec![],
+        }];
+
+        let synced = dispatcher.sync_all(&context, &tools, &rules).unwrap();
+        assert_eq!(synced, tools);
+    }
 }
@@ -15,12 +15,25 @@ pub enum Error {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("Content error: {0}")]
+    Content(#[from] repo_content::Error),
+
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
     #[error("Tool config not found at {path}")]
     ConfigNotFound { path: PathBuf },
 
     #[error("Sync failed for {tool}: {message}")]
     SyncFailed { tool: String, message: String },
 
+    #[error("{tool} produced invalid {path}: {message}")]
+    InvalidOutput {
+        tool: String,
+        path: String,
+        message: String,
+    },
+
     #[error("MCP config error for {tool}: {message}")]
     McpConfig { tool: String, message: String },
 
@@ -4,7 +4,7 @@
 
 use crate::generic::GenericToolIntegration;
 use repo_meta::schema::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
 };
 
 /// Creates a Gemini CLI integration.
@@ -17,11 +17,16 @@ pub fn gemini_integration() -> GenericToolIntegration {
             name: "Gemini".into(),
             slug: "gemini".into(),
             description: Some("Gemini CLI - Google's AI coding assistant".into()),
+            override_builtin: false,
+            open_command: None,
         },
         integration: ToolIntegrationConfig {
             config_path: "GEMINI.md".into(),
             config_type: ConfigType::Text,
             additional_paths: vec![],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             supports_custom_instructions: true,
@@ -79,10 +84,12 @@ mod tests {
             Rule {
                 id: "rule-1".to_string(),
                 content: "First rule content".to_string(),
+                paths: vec![],
             },
             Rule {
                 id: "rule-2".to_string(),
                 content: "Second rule content".to_string(),
+                paths: vec![],
             },
         ];
 
@@ -111,6 +118,7 @@ mod tests {
         let rules = vec![Rule {
             id: "my-rule".to_string(),
             content: "Original content".to_string(),
+            paths: vec![],
         }];
 
         let integration = gemini_integration();
@@ -120,6 +128,7 @@ mod tests {
         let rules = vec![Rule {
             id: "my-rule".to_string(),
             content: "Updated content".to_string(),
+            paths: vec![],
         }];
         integration.sync(&context, &rules).unwrap();
 
@@ -146,6 +155,7 @@ mod tests {
         let rules = vec![Rule {
             id: "auto-rule".to_string(),
             content: "Automated rule".to_string(),
+            paths: vec![],
         }];
 
         let integration = gemini_integration();
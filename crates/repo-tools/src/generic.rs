@@ -5,11 +5,18 @@
 //! tools to be added without writing Rust code.
 
 use crate::error::Result;
-use crate::integration::{ConfigLocation, ConfigType, Rule, SyncContext, ToolIntegration};
-use repo_blocks::upsert_block;
+use crate::integration::{
+    ConfigLocation, ConfigType, Rule, SyncContext, ToolIntegration, validate_config,
+};
+use crate::translator::{
+    INSTRUCTIONS_EXTENSION, TranslatedContent, instructions_file_name, is_mdc_pattern,
+    render_instructions, render_mdc,
+};
+use crate::writer::{SchemaKeys, WriterRegistry, update_json_file};
+use repo_blocks::{insert_block, parse_blocks, remove_block_reported, upsert_block};
 use repo_fs::{NormalizedPath, io};
-use repo_meta::schema::ToolDefinition;
-use serde_json::{Value, json};
+use repo_meta::schema::{DEFAULT_DIRECTORY_PATTERN, ToolDefinition, WriterStrategy};
+use serde_json::json;
 
 /// Sanitize a string for use as a filename.
 fn sanitize_filename(s: &str) -> String {
@@ -24,6 +31,78 @@ fn sanitize_filename(s: &str) -> String {
         .collect()
 }
 
+/// A placeholder in a per-rule file name pattern.
+enum Placeholder {
+    /// Rule position (1-based), zero-padded to the given width.
+    Index(usize),
+    /// Sanitized rule id.
+    Id,
+}
+
+/// Parse a placeholder at the start of `pattern`, returning it and the rest.
+///
+/// Recognizes `{nn}`, `{index}`, `{index:0N}` and `{id}`.
+fn parse_placeholder(pattern: &str) -> Option<(Placeholder, &str)> {
+    let (name, rest) = pattern.strip_prefix('{')?.split_once('}')?;
+    let placeholder = match name {
+        "nn" => Placeholder::Index(2),
+        "index" => Placeholder::Index(1),
+        "id" => Placeholder::Id,
+        _ => Placeholder::Index(name.strip_prefix("index:")?.parse().ok()?),
+    };
+    Some((placeholder, rest))
+}
+
+/// Expand a per-rule file name pattern for the rule at `index` (0-based).
+fn expand_pattern(pattern: &str, index: usize, rule_id: &str) -> String {
+    let mut name = String::new();
+    let mut rest = pattern;
+    while !rest.is_empty() {
+        if let Some((placeholder, after)) = parse_placeholder(rest) {
+            match placeholder {
+                Placeholder::Index(width) => {
+                    name.push_str(&format!("{:0width$}", index + 1, width = width))
+                }
+                Placeholder::Id => name.push_str(&sanitize_filename(rule_id)),
+            }
+            rest = after;
+        } else {
+            let mut chars = rest.chars();
+            name.extend(chars.next());
+            rest = chars.as_str();
+        }
+    }
+    name
+}
+
+/// Check whether `name` could have been produced by [`expand_pattern`].
+///
+/// Index placeholders match one or more ASCII digits and `{id}` one or
+/// more characters that survive [`sanitize_filename`].
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    match parse_placeholder(pattern) {
+        Some((Placeholder::Index(_), rest)) => {
+            let digits = name.bytes().take_while(u8::is_ascii_digit).count();
+            return (1..=digits).any(|n| matches_pattern(rest, &name[n..]));
+        }
+        Some((Placeholder::Id, rest)) => {
+            return name
+                .char_indices()
+                .take_while(|(_, c)| c.is_alphanumeric() || *c == '-' || *c == '_')
+                .any(|(i, c)| matches_pattern(rest, &name[i + c.len_utf8()..]));
+        }
+        None => {}
+    }
+
+    let mut chars = pattern.chars();
+    match chars.next() {
+        None => name.is_empty(),
+        Some(c) => name
+            .strip_prefix(c)
+            .is_some_and(|name| matches_pattern(chars.as_str(), name)),
+    }
+}
+
 /// Generic tool integration driven by ToolDefinition schema.
 ///
 /// This implementation uses the schema to determine:
@@ -35,6 +114,10 @@ pub struct GenericToolIntegration {
     definition: ToolDefinition,
     /// If true, insert rule content directly without adding headers
     raw_content: bool,
+    /// Directory receiving one instructions file per rule with target paths
+    scoped_rules_dir: Option<String>,
+    /// Renders the file of each rule for one-file-per-rule configs
+    rule_renderer: Option<fn(&Rule) -> String>,
 }
 
 impl GenericToolIntegration {
@@ -43,6 +126,8 @@ impl GenericToolIntegration {
         Self {
             definition,
             raw_content: false,
+            scoped_rules_dir: None,
+            rule_renderer: None,
         }
     }
 
@@ -60,6 +145,25 @@ impl GenericToolIntegration {
         self
     }
 
+    /// Write rules with target paths to their own instructions files.
+    ///
+    /// Each rule with `paths` is written to `{dir}{id}.instructions.md` with
+    /// an `applyTo` front matter listing its globs, the format GitHub Copilot
+    /// uses for path-specific instructions. Only rules without `paths` go
+    /// to the primary config and other additional paths. `dir` is relative
+    /// to the repository root and ends with `/`.
+    pub fn with_scoped_rules_dir(mut self, dir: impl Into<String>) -> Self {
+        self.scoped_rules_dir = Some(dir.into());
+        self
+    }
+
+    /// Render the file of each rule with `render` when writing one file
+    /// per rule, e.g. to add tool-specific front matter.
+    pub fn with_rule_renderer(mut self, render: fn(&Rule) -> String) -> Self {
+        self.rule_renderer = Some(render);
+        self
+    }
+
     /// Get the underlying tool definition.
     pub fn definition(&self) -> &ToolDefinition {
         &self.definition
@@ -121,11 +225,64 @@ impl GenericToolIntegration {
     }
 
     /// Write rules as individual files to an explicit directory path.
-    fn sync_to_directory_at_path(
+    fn sync_to_directory_at_path(&self, dir_path: &NormalizedPath, rules: &[Rule]) -> Result<()> {
+        self.write_rule_files(dir_path, rules, DEFAULT_DIRECTORY_PATTERN)?;
+        Ok(())
+    }
+
+    /// Sync a tool that writes one file per rule.
+    ///
+    /// Used for [`ConfigType::Directory`] and `rules_layout = "per-file"`.
+    /// Writes one file per rule named by `pattern`, in the order the rules
+    /// are given, then removes files matching the pattern that belong to
+    /// rules no longer present.
+    fn sync_directory_type(
         &self,
-        dir_path: &NormalizedPath,
+        context: &SyncContext,
         rules: &[Rule],
+        pattern: &str,
+    ) -> Result<()> {
+        let dir_path = self.config_path(&context.root);
+        let written = self.write_rule_files(&dir_path, rules, pattern)?;
+        self.remove_stale_rule_files(&dir_path, pattern, &written)
+    }
+
+    /// Remove files in `dir_path` matching `pattern` that were not just written.
+    fn remove_stale_rule_files(
+        &self,
+        dir_path: &NormalizedPath,
+        pattern: &str,
+        written: &[String],
     ) -> Result<()> {
+        let entries =
+            std::fs::read_dir(dir_path.to_native()).map_err(|e| crate::Error::SyncFailed {
+                tool: self.definition.meta.slug.clone(),
+                message: format!("Failed to read directory {}: {}", dir_path.as_str(), e),
+            })?;
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let is_file = entry.file_type().is_ok_and(|t| t.is_file());
+            if !is_file || written.contains(&name) || !matches_pattern(pattern, &name) {
+                continue;
+            }
+
+            std::fs::remove_file(entry.path()).map_err(|e| crate::Error::SyncFailed {
+                tool: self.definition.meta.slug.clone(),
+                message: format!("Failed to remove stale rule file {}: {}", name, e),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Write one file per rule into `dir_path`, returning the file names.
+    fn write_rule_files(
+        &self,
+        dir_path: &NormalizedPath,
+        rules: &[Rule],
+        pattern: &str,
+    ) -> Result<Vec<String>> {
         let native = dir_path.to_native();
 
         // If a regular file exists at this path, remove it first so we can
@@ -150,20 +307,27 @@ impl GenericToolIntegration {
         }
 
         // Write each rule to a separate file
+        let mut written = Vec::with_capacity(rules.len());
         for (i, rule) in rules.iter().enumerate() {
-            let filename = format!("{:02}-{}.md", i + 1, sanitize_filename(&rule.id));
+            let filename = expand_pattern(pattern, i, &rule.id);
             let file_path = dir_path.join(&filename);
 
-            let content = if self.raw_content {
+            let content = if let Some(render) = self.rule_renderer {
+                render(rule)
+            } else if is_mdc_pattern(pattern) {
+                // Rules without target paths always apply
+                render_mdc(&rule.id, &rule.paths, &rule.content)
+            } else if self.raw_content {
                 rule.content.clone()
             } else {
                 format!("# {}\n\n{}", rule.id, rule.content)
             };
 
             io::write_text(&file_path, &content)?;
+            written.push(filename);
         }
 
-        Ok(())
+        Ok(written)
     }
 
     /// Sync rules to a YAML config file using proper YAML comments.
@@ -219,18 +383,7 @@ impl GenericToolIntegration {
         context: &SyncContext,
         rules: &[Rule],
     ) -> Result<()> {
-        // Load existing or create new
-        let mut settings: Value = if path.exists() {
-            let content = io::read_text(path)?;
-            serde_json::from_str(&content)?
-        } else {
-            json!({})
-        };
-
-        // Ensure we have an object
-        if !settings.is_object() {
-            settings = json!({});
-        }
+        let mut updates = Vec::new();
 
         // Apply schema-driven keys
         if let Some(ref schema_keys) = self.definition.schema_keys {
@@ -238,41 +391,77 @@ impl GenericToolIntegration {
             if let (Some(key), Some(python_path)) =
                 (&schema_keys.python_path_key, &context.python_path)
             {
-                settings[key] = json!(python_path.as_str());
+                updates.push((key.clone(), json!(python_path.as_str())));
             }
 
             // Custom instructions (concatenate all rules)
             if let Some(ref key) = schema_keys.instruction_key
                 && !rules.is_empty()
             {
-                let instructions: String = if self.raw_content {
-                    rules
-                        .iter()
-                        .map(|r| r.content.as_str())
-                        .collect::<Vec<_>>()
-                        .join("\n\n")
-                } else {
-                    rules
-                        .iter()
-                        .map(|r| format!("## {}\n{}", r.id, r.content))
-                        .collect::<Vec<_>>()
-                        .join("\n\n")
-                };
-                settings[key] = json!(instructions);
+                updates.push((key.clone(), json!(self.instructions(rules, "##"))));
             }
 
             // MCP servers
-            if let (Some(key), Some(mcp_servers)) =
-                (&schema_keys.mcp_key, &context.mcp_servers)
-            {
-                settings[key] = mcp_servers.clone();
+            if let (Some(key), Some(mcp_servers)) = (&schema_keys.mcp_key, &context.mcp_servers) {
+                updates.push((key.clone(), mcp_servers.clone()));
             }
         }
 
-        let content = serde_json::to_string_pretty(&settings)?;
-        io::write_text(path, &content)?;
+        update_json_file(path, &updates)
+    }
 
-        Ok(())
+    /// Concatenate rules into one instructions text.
+    ///
+    /// Each rule is headed by `{heading} {id}` unless in raw content mode.
+    fn instructions(&self, rules: &[Rule], heading: &str) -> String {
+        rules
+            .iter()
+            .map(|r| {
+                if self.raw_content {
+                    r.content.clone()
+                } else {
+                    format!("{} {}\n{}", heading, r.id, r.content)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// Sync rules to the primary config with the writer its strategy selects.
+    ///
+    /// Markdown sections head each rule one level below the section.
+    fn sync_with_writer(
+        &self,
+        context: &SyncContext,
+        rules: &[Rule],
+        strategy: &WriterStrategy,
+    ) -> Result<()> {
+        let integration = &self.definition.integration;
+        let heading = match strategy {
+            WriterStrategy::MarkdownSection { .. } => "###",
+            _ => "##",
+        };
+
+        let mut content = TranslatedContent::empty();
+        content.format = integration.config_type.clone();
+        if !rules.is_empty() {
+            content.instructions = Some(self.instructions(rules, heading));
+        }
+        content.mcp_servers = context.mcp_servers.clone();
+        let keys = self.definition.schema_keys.as_ref();
+        if let (Some(key), Some(python_path)) = (
+            keys.and_then(|k| k.python_path_key.as_ref()),
+            &context.python_path,
+        ) {
+            content = content.with_data(key.clone(), json!(python_path.as_str()));
+        }
+
+        let keys = keys.map(SchemaKeys::from);
+        WriterRegistry::new().resolve(integration).write(
+            &self.config_path(&context.root),
+            &content,
+            keys.as_ref(),
+        )
     }
 
     /// Sync rules to a markdown config file using managed blocks.
@@ -291,6 +480,11 @@ impl GenericToolIntegration {
     /// - Everything else -> Text sync
     fn sync_additional_paths(&self, context: &SyncContext, rules: &[Rule]) -> Result<()> {
         for additional_path in &self.definition.integration.additional_paths {
+            // Written by sync_scoped_rules instead
+            if self.scoped_rules_dir.as_ref() == Some(additional_path) {
+                continue;
+            }
+
             let resolved = context.root.join(additional_path);
 
             if additional_path.ends_with('/') {
@@ -313,6 +507,102 @@ impl GenericToolIntegration {
 
         Ok(())
     }
+
+    /// Split rules into those with target paths and the rest.
+    ///
+    /// Without a scoped rules directory, every rule is unscoped.
+    fn partition_scoped(&self, rules: &[Rule]) -> (Vec<Rule>, Vec<Rule>) {
+        if self.scoped_rules_dir.is_none() {
+            return (Vec::new(), rules.to_vec());
+        }
+        rules.iter().cloned().partition(|r| !r.paths.is_empty())
+    }
+
+    /// Remove blocks of scoped rules from a managed-block config file.
+    ///
+    /// A rule that gains target paths moves from the primary config to its
+    /// own instructions file.
+    fn remove_scoped_blocks(&self, path: &NormalizedPath, rules: &[Rule]) -> Result<()> {
+        if !path.is_file() {
+            return Ok(());
+        }
+        let mut content = io::read_text(path)?;
+        let mut removed_any = false;
+        for rule in rules {
+            let (remaining, removed) = remove_block_reported(&content, &rule.id);
+            content = remaining;
+            removed_any |= removed;
+        }
+        if removed_any {
+            io::write_text(path, &content)?;
+        }
+        Ok(())
+    }
+
+    /// Write one instructions file per scoped rule, then remove the
+    /// instructions files of rules no longer present.
+    ///
+    /// Rule content is wrapped in a managed block, so only files written by
+    /// a previous sync are removed; hand-written instructions files in the
+    /// same directory are left alone.
+    fn sync_scoped_rules(&self, dir_path: &NormalizedPath, rules: &[Rule]) -> Result<()> {
+        if rules.is_empty() && !dir_path.exists() {
+            return Ok(());
+        }
+
+        if !dir_path.exists() {
+            std::fs::create_dir_all(dir_path.as_ref()).map_err(|e| crate::Error::SyncFailed {
+                tool: self.definition.meta.slug.clone(),
+                message: format!("Failed to create directory: {}", e),
+            })?;
+        }
+
+        let mut written = Vec::with_capacity(rules.len());
+        for rule in rules {
+            let body = if self.raw_content {
+                rule.content.clone()
+            } else {
+                format!(
+                    "# {}
+
+{}",
+                    rule.id, rule.content
+                )
+            };
+            let content = render_instructions(&rule.paths, &insert_block("", &rule.id, &body));
+
+            let filename = instructions_file_name(&rule.id);
+            io::write_text(&dir_path.join(&filename), &content)?;
+            written.push(filename);
+        }
+
+        let entries =
+            std::fs::read_dir(dir_path.to_native()).map_err(|e| crate::Error::SyncFailed {
+                tool: self.definition.meta.slug.clone(),
+                message: format!("Failed to read directory {}: {}", dir_path.as_str(), e),
+            })?;
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let is_file = entry.file_type().is_ok_and(|t| t.is_file());
+            if !is_file || written.contains(&name) || !name.ends_with(INSTRUCTIONS_EXTENSION) {
+                continue;
+            }
+
+            let managed = std::fs::read_to_string(entry.path())
+                .is_ok_and(|content| !parse_blocks(&content).is_empty());
+            if !managed {
+                continue;
+            }
+
+            std::fs::remove_file(entry.path()).map_err(|e| crate::Error::SyncFailed {
+                tool: self.definition.meta.slug.clone(),
+                message: format!("Failed to remove stale instructions file {}: {}", name, e),
+            })?;
+        }
+
+        Ok(())
+    }
 }
 
 impl ToolIntegration for GenericToolIntegration {
@@ -325,23 +615,29 @@ impl ToolIntegration for GenericToolIntegration {
     }
 
     fn config_locations(&self) -> Vec<ConfigLocation> {
-        let config_type = self.definition.integration.config_type;
+        let config_type = &self.definition.integration.config_type;
         let primary_path = &self.definition.integration.config_path;
 
-        // Check if primary path is a directory (ends with /)
-        let mut locations = if primary_path.ends_with('/') {
-            vec![ConfigLocation::directory(primary_path, config_type)]
+        // Per-file layouts report the directory so drift checks verify its
+        // contents rather than a single file checksum
+        let mut locations = if let Some(pattern) = self.definition.integration.rule_file_pattern() {
+            let directory = ConfigType::Directory {
+                pattern: pattern.to_string(),
+            };
+            vec![ConfigLocation::directory(primary_path, directory)]
+        } else if primary_path.ends_with('/') {
+            vec![ConfigLocation::directory(primary_path, config_type.clone())]
         } else {
-            vec![ConfigLocation::file(primary_path, config_type)]
+            vec![ConfigLocation::file(primary_path, config_type.clone())]
         };
 
         for path in &self.definition.integration.additional_paths {
             // Paths ending with / are directories
             let is_dir = path.ends_with('/');
             if is_dir {
-                locations.push(ConfigLocation::directory(path, config_type));
+                locations.push(ConfigLocation::directory(path, config_type.clone()));
             } else {
-                locations.push(ConfigLocation::file(path, config_type));
+                locations.push(ConfigLocation::file(path, config_type.clone()));
             }
         }
 
@@ -349,28 +645,89 @@ impl ToolIntegration for GenericToolIntegration {
     }
 
     fn sync(&self, context: &SyncContext, rules: &[Rule]) -> Result<()> {
-        match self.definition.integration.config_type {
-            ConfigType::Text => self.sync_text(context, rules)?,
-            ConfigType::Json => self.sync_json(context, rules)?,
-            ConfigType::Markdown => self.sync_markdown(context, rules)?,
-            ConfigType::Yaml => self.sync_yaml(context, rules)?,
-            ConfigType::Toml => {
-                // TOML uses # comments like YAML
-                self.sync_yaml(context, rules)?;
+        let rules = context.render_rules(rules);
+        let (scoped, unscoped) = self.partition_scoped(&rules);
+        let rules = unscoped.as_slice();
+
+        if let Some(pattern) = self.definition.integration.rule_file_pattern() {
+            self.sync_directory_type(context, rules, pattern)?;
+        } else if let Some(strategy) = &self.definition.integration.writer {
+            self.sync_with_writer(context, rules, strategy)?;
+        } else {
+            match &self.definition.integration.config_type {
+                ConfigType::Text => self.sync_text(context, rules)?,
+                ConfigType::Json => self.sync_json(context, rules)?,
+                ConfigType::Markdown => self.sync_markdown(context, rules)?,
+                ConfigType::Yaml => self.sync_yaml(context, rules)?,
+                ConfigType::Toml => {
+                    // TOML uses # comments like YAML
+                    self.sync_yaml(context, rules)?;
+                }
+                // Always has a pattern, handled above
+                ConfigType::Directory { .. } => unreachable!(),
             }
         }
 
         // Sync additional paths (if any)
         self.sync_additional_paths(context, rules)?;
 
+        if let Some(dir) = &self.scoped_rules_dir {
+            self.sync_scoped_rules(&context.root.join(dir), &scoped)?;
+            self.remove_scoped_blocks(&self.config_path(&context.root), &scoped)?;
+        }
+
         Ok(())
     }
+
+    fn validate_output(&self, content: &str) -> Result<()> {
+        let integration = &self.definition.integration;
+        if integration.rule_file_pattern().is_some() {
+            return Ok(());
+        }
+        validate_config(
+            self.name(),
+            &integration.config_path,
+            &integration.config_type,
+            content,
+        )
+    }
+
+    fn rule_files(&self, rules: &[Rule]) -> Vec<String> {
+        let (scoped, unscoped) = self.partition_scoped(rules);
+        let mut files = Vec::new();
+
+        if let Some(pattern) = self.definition.integration.rule_file_pattern() {
+            let dir = self
+                .definition
+                .integration
+                .config_path
+                .trim_end_matches('/');
+            files.extend(
+                unscoped
+                    .iter()
+                    .enumerate()
+                    .map(|(i, rule)| format!("{}/{}", dir, expand_pattern(pattern, i, &rule.id))),
+            );
+        }
+
+        if let Some(dir) = &self.scoped_rules_dir {
+            files.extend(
+                scoped
+                    .iter()
+                    .map(|rule| format!("{}{}", dir, instructions_file_name(&rule.id))),
+            );
+        }
+
+        files
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use repo_meta::schema::{ToolCapabilities, ToolIntegrationConfig, ToolMeta, ToolSchemaKeys};
+    use repo_meta::schema::{
+        RulesLayout, ToolCapabilities, ToolIntegrationConfig, ToolMeta, ToolSchemaKeys,
+    };
     use std::fs;
     use tempfile::TempDir;
 
@@ -380,11 +737,16 @@ mod tests {
                 name: "Test Tool".to_string(),
                 slug: "test-tool".to_string(),
                 description: Some("A test tool".to_string()),
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: ".testrules".to_string(),
                 config_type: ConfigType::Text,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities::default(),
             schema_keys: None,
@@ -425,6 +787,7 @@ mod tests {
         let rules = vec![Rule {
             id: "test-rule".to_string(),
             content: "Test content".to_string(),
+            paths: vec![],
         }];
 
         integration.sync(&context, &rules).unwrap();
@@ -435,6 +798,103 @@ mod tests {
         assert!(content.contains("<!-- repo:block:test-rule -->"));
     }
 
+    #[test]
+    fn test_sync_renders_context_vars() {
+        let temp = TempDir::new().unwrap();
+        let integration = GenericToolIntegration::new(create_text_definition());
+
+        let context = SyncContext::new(NormalizedPath::new(temp.path()))
+            .with_var("project_name", "acme")
+            .with_python(NormalizedPath::new("/usr/bin/python3"));
+        let rules = vec![Rule {
+            id: "naming".to_string(),
+            content: "Prefix modules with ${project_name}_; run ${python_path}; keep ${unset}"
+                .to_string(),
+            paths: vec![],
+        }];
+
+        integration.sync(&context, &rules).unwrap();
+
+        let content = fs::read_to_string(temp.path().join(".testrules")).unwrap();
+        let block = repo_blocks::find_block(&content, "naming").unwrap();
+        assert!(
+            block
+                .content
+                .contains("Prefix modules with acme_; run /usr/bin/python3; keep ${unset}"),
+            "{}",
+            block.content
+        );
+    }
+
+    #[test]
+    fn test_sync_json_merge_at_target_path() {
+        let temp = TempDir::new().unwrap();
+        let mut def = create_text_definition();
+        def.integration.config_path = "settings.json".to_string();
+        def.integration.config_type = ConfigType::Json;
+        def.integration.writer = Some(WriterStrategy::JsonMerge {
+            target_path: Some("ai.rules".to_string()),
+        });
+        def.schema_keys = Some(ToolSchemaKeys {
+            mcp_key: Some("mcpServers".to_string()),
+            ..Default::default()
+        });
+        fs::write(
+            temp.path().join("settings.json"),
+            "{\n    // Model choice\n    \"ai\": {\"model\": \"fast\"}\n}\n",
+        )
+        .unwrap();
+
+        let context = SyncContext::new(NormalizedPath::new(temp.path()))
+            .with_mcp_servers(json!({"docs": {"command": "docs-server"}}));
+        let rules = vec![Rule {
+            id: "style".to_string(),
+            content: "Be terse.".to_string(),
+            paths: vec![],
+        }];
+        GenericToolIntegration::new(def)
+            .sync(&context, &rules)
+            .unwrap();
+
+        let written = fs::read_to_string(temp.path().join("settings.json")).unwrap();
+        assert!(written.contains("// Model choice"));
+        let json = repo_content::jsonc::parse(&written).unwrap();
+        assert_eq!(json["ai"]["model"], "fast");
+        assert_eq!(json["ai"]["rules"], "## style\nBe terse.");
+        assert_eq!(json["mcpServers"]["docs"]["command"], "docs-server");
+    }
+
+    #[test]
+    fn test_sync_markdown_section() {
+        let temp = TempDir::new().unwrap();
+        let mut def = create_text_definition();
+        def.integration.config_path = "AGENTS.md".to_string();
+        def.integration.config_type = ConfigType::Markdown;
+        def.integration.writer = Some(WriterStrategy::MarkdownSection {
+            heading: "AI Rules".to_string(),
+        });
+        fs::write(
+            temp.path().join("AGENTS.md"),
+            "# Project\n\n## Build\n\nRun make.\n",
+        )
+        .unwrap();
+
+        let context = SyncContext::new(NormalizedPath::new(temp.path()));
+        let rules = vec![Rule {
+            id: "style".to_string(),
+            content: "Be terse.".to_string(),
+            paths: vec![],
+        }];
+        GenericToolIntegration::new(def)
+            .sync(&context, &rules)
+            .unwrap();
+
+        let written = fs::read_to_string(temp.path().join("AGENTS.md")).unwrap();
+        let section = written.find("## AI Rules").unwrap();
+        assert!(written.find("## Build").unwrap() < section);
+        assert!(written[section..].contains("### style\nBe terse."));
+    }
+
     #[test]
     fn test_sync_json_with_schema_keys() {
         let temp = TempDir::new().unwrap();
@@ -444,11 +904,16 @@ mod tests {
                 name: "JSON Tool".to_string(),
                 slug: "json-tool".to_string(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: "config.json".to_string(),
                 config_type: ConfigType::Json,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities::default(),
             schema_keys: Some(ToolSchemaKeys {
@@ -465,6 +930,7 @@ mod tests {
         let rules = vec![Rule {
             id: "rule1".to_string(),
             content: "Content 1".to_string(),
+            paths: vec![],
         }];
 
         integration.sync(&context, &rules).unwrap();
@@ -485,11 +951,16 @@ mod tests {
                 name: "MCP Tool".to_string(),
                 slug: "mcp-tool".to_string(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: "config.json".to_string(),
                 config_type: ConfigType::Json,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities {
                 supports_custom_instructions: false,
@@ -510,8 +981,7 @@ mod tests {
                 "args": ["serve.py", "--port", "8080"]
             }
         });
-        let context = SyncContext::new(NormalizedPath::new(temp.path()))
-            .with_mcp_servers(mcp_data);
+        let context = SyncContext::new(NormalizedPath::new(temp.path())).with_mcp_servers(mcp_data);
 
         // No rules — just MCP config
         integration.sync(&context, &[]).unwrap();
@@ -519,8 +989,14 @@ mod tests {
         let content = fs::read_to_string(temp.path().join("config.json")).unwrap();
         let json: serde_json::Value = serde_json::from_str(&content).unwrap();
 
-        assert!(json["mcpServers"].is_object(), "mcpServers must exist as object");
-        assert_eq!(json["mcpServers"]["my-server"]["command"], "/usr/bin/python3");
+        assert!(
+            json["mcpServers"].is_object(),
+            "mcpServers must exist as object"
+        );
+        assert_eq!(
+            json["mcpServers"]["my-server"]["command"],
+            "/usr/bin/python3"
+        );
         assert_eq!(json["mcpServers"]["my-server"]["args"][0], "serve.py");
         assert_eq!(json["mcpServers"]["my-server"]["args"][1], "--port");
         assert_eq!(json["mcpServers"]["my-server"]["args"][2], "8080");
@@ -535,11 +1011,16 @@ mod tests {
                 name: "No MCP Key".to_string(),
                 slug: "no-mcp-key".to_string(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: "config.json".to_string(),
                 config_type: ConfigType::Json,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities::default(),
             // No mcp_key in schema_keys
@@ -552,8 +1033,7 @@ mod tests {
 
         let integration = GenericToolIntegration::new(definition);
         let mcp_data = serde_json::json!({"server": {"command": "test"}});
-        let context = SyncContext::new(NormalizedPath::new(temp.path()))
-            .with_mcp_servers(mcp_data);
+        let context = SyncContext::new(NormalizedPath::new(temp.path())).with_mcp_servers(mcp_data);
 
         integration.sync(&context, &[]).unwrap();
 
@@ -577,18 +1057,27 @@ mod tests {
                 "old-server": {"command": "old"}
             }
         });
-        fs::write(&config_path, serde_json::to_string_pretty(&existing).unwrap()).unwrap();
+        fs::write(
+            &config_path,
+            serde_json::to_string_pretty(&existing).unwrap(),
+        )
+        .unwrap();
 
         let definition = ToolDefinition {
             meta: ToolMeta {
                 name: "Test".to_string(),
                 slug: "test".to_string(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: "config.json".to_string(),
                 config_type: ConfigType::Json,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities {
                 supports_custom_instructions: false,
@@ -604,8 +1093,7 @@ mod tests {
 
         let integration = GenericToolIntegration::new(definition);
         let mcp_data = serde_json::json!({"new-server": {"command": "new"}});
-        let context = SyncContext::new(NormalizedPath::new(temp.path()))
-            .with_mcp_servers(mcp_data);
+        let context = SyncContext::new(NormalizedPath::new(temp.path())).with_mcp_servers(mcp_data);
 
         integration.sync(&context, &[]).unwrap();
 
@@ -631,11 +1119,16 @@ mod tests {
                 name: "Text Extra".to_string(),
                 slug: "text-extra".to_string(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: ".primary-rules".to_string(),
                 config_type: ConfigType::Text,
                 additional_paths: vec![".secondary-rules".to_string()],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities::default(),
             schema_keys: None,
@@ -646,6 +1139,7 @@ mod tests {
         let rules = vec![Rule {
             id: "my-rule".to_string(),
             content: "Do the thing.".to_string(),
+            paths: vec![],
         }];
 
         integration.sync(&context, &rules).unwrap();
@@ -686,11 +1180,16 @@ mod tests {
                 name: "Md Extra".to_string(),
                 slug: "md-extra".to_string(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: ".primary.md".to_string(),
                 config_type: ConfigType::Markdown,
                 additional_paths: vec!["CONVENTIONS.md".to_string()],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities::default(),
             schema_keys: None,
@@ -701,6 +1200,7 @@ mod tests {
         let rules = vec![Rule {
             id: "conv-rule".to_string(),
             content: "Follow conventions.".to_string(),
+            paths: vec![],
         }];
 
         integration.sync(&context, &rules).unwrap();
@@ -737,11 +1237,16 @@ mod tests {
                 name: "Json Extra".to_string(),
                 slug: "json-extra".to_string(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: ".primary-rules".to_string(),
                 config_type: ConfigType::Text,
                 additional_paths: vec![".tool/settings.json".to_string()],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities::default(),
             schema_keys: Some(ToolSchemaKeys {
@@ -756,6 +1261,7 @@ mod tests {
         let rules = vec![Rule {
             id: "json-rule".to_string(),
             content: "JSON rule content.".to_string(),
+            paths: vec![],
         }];
 
         integration.sync(&context, &rules).unwrap();
@@ -793,11 +1299,16 @@ mod tests {
                 name: "Dir Extra".to_string(),
                 slug: "dir-extra".to_string(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: "PRIMARY.md".to_string(),
                 config_type: ConfigType::Markdown,
                 additional_paths: vec![".tool/rules/".to_string()],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities::default(),
             schema_keys: None,
@@ -809,10 +1320,12 @@ mod tests {
             Rule {
                 id: "rule-alpha".to_string(),
                 content: "Alpha content.".to_string(),
+                paths: vec![],
             },
             Rule {
                 id: "rule-beta".to_string(),
                 content: "Beta content.".to_string(),
+                paths: vec![],
             },
         ];
 
@@ -824,7 +1337,10 @@ mod tests {
 
         // Additional directory must be created
         let dir = temp.path().join(".tool/rules");
-        assert!(dir.is_dir(), "Additional directory path must be a directory");
+        assert!(
+            dir.is_dir(),
+            "Additional directory path must be a directory"
+        );
 
         // Per-rule files must exist
         let rule1 = dir.join("01-rule-alpha.md");
@@ -858,11 +1374,16 @@ mod tests {
                 name: "Block Check".to_string(),
                 slug: "block-check".to_string(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: ".primary".to_string(),
                 config_type: ConfigType::Text,
                 additional_paths: vec![".secondary".to_string()],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities::default(),
             schema_keys: None,
@@ -874,18 +1395,19 @@ mod tests {
             Rule {
                 id: "block-a".to_string(),
                 content: "Content for block A.".to_string(),
+                paths: vec![],
             },
             Rule {
                 id: "block-b".to_string(),
                 content: "Content for block B.".to_string(),
+                paths: vec![],
             },
         ];
 
         integration.sync(&context, &rules).unwrap();
 
         // Verify secondary file has actual managed block structure, not empty
-        let secondary_content =
-            fs::read_to_string(temp.path().join(".secondary")).unwrap();
+        let secondary_content = fs::read_to_string(temp.path().join(".secondary")).unwrap();
 
         // Must have opening and closing markers for both blocks
         assert!(
@@ -931,11 +1453,16 @@ mod tests {
                 name: "No Extra".to_string(),
                 slug: "no-extra".to_string(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: ".only-file".to_string(),
                 config_type: ConfigType::Text,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities::default(),
             schema_keys: None,
@@ -946,6 +1473,7 @@ mod tests {
         let rules = vec![Rule {
             id: "solo-rule".to_string(),
             content: "Solo content.".to_string(),
+            paths: vec![],
         }];
 
         integration.sync(&context, &rules).unwrap();
@@ -965,13 +1493,169 @@ mod tests {
             entries.len(),
             1,
             "Only primary file should exist, found: {:?}",
-            entries
-                .iter()
-                .map(|e| e.file_name())
-                .collect::<Vec<_>>()
+            entries.iter().map(|e| e.file_name()).collect::<Vec<_>>()
         );
     }
 
+    #[test]
+    fn test_matches_pattern() {
+        let pattern = DEFAULT_DIRECTORY_PATTERN;
+        assert!(matches_pattern(pattern, "01-rule-a.md"));
+        assert!(matches_pattern(pattern, "123-my_rule.md"));
+        assert!(!matches_pattern(pattern, "notes.md"));
+        assert!(!matches_pattern(pattern, "01-.md"));
+        assert!(!matches_pattern(pattern, "01-rule.txt"));
+        assert!(matches_pattern("rule-{id}.mdc", "rule-style.mdc"));
+        assert_eq!(expand_pattern(pattern, 0, "a/b"), "01-a-b.md");
+
+        let pattern = "{index:03}-{id}.md";
+        assert_eq!(expand_pattern(pattern, 4, "style"), "005-style.md");
+        assert_eq!(expand_pattern("{index}_{id}", 11, "x"), "12_x");
+        assert!(matches_pattern(pattern, "005-style.md"));
+        assert!(!matches_pattern(pattern, "style.md"));
+        // Unknown placeholders are literal text
+        assert_eq!(expand_pattern("{name}-{id}", 0, "x"), "{name}-x");
+    }
+
+    #[test]
+    fn test_per_file_layout() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        let tools_dir = temp.path().join(".repository/tools");
+        fs::create_dir_all(&tools_dir).unwrap();
+        fs::write(
+            tools_dir.join("perfile.toml"),
+            r#"
+[meta]
+name = "Per File"
+slug = "perfile"
+
+[integration]
+config_path = ".perfile/rules"
+type = "markdown"
+rules_layout = "per-file"
+rules_filename = "{index:03}-{id}.md"
+"#,
+        )
+        .unwrap();
+
+        let loaded = repo_meta::DefinitionLoader::new()
+            .load_tools(&root)
+            .unwrap();
+        let integration = GenericToolIntegration::new(loaded.definitions["perfile"].clone());
+
+        let locations = integration.config_locations();
+        assert!(locations[0].is_directory);
+        assert_eq!(
+            locations[0].config_type,
+            ConfigType::Directory {
+                pattern: "{index:03}-{id}.md".to_string()
+            }
+        );
+
+        let rule = |id: &str| Rule {
+            id: id.to_string(),
+            content: format!("Content of {}", id),
+            paths: vec![],
+        };
+        let context = SyncContext::new(root);
+        let dir = temp.path().join(".perfile/rules");
+
+        integration
+            .sync(&context, &[rule("first"), rule("second")])
+            .unwrap();
+        assert!(dir.join("001-first.md").is_file());
+        assert!(dir.join("002-second.md").is_file());
+        assert!(!temp.path().join(".perfile/rules.md").exists());
+
+        // Rules are numbered in the order given; stale files are removed
+        integration.sync(&context, &[rule("second")]).unwrap();
+        let names: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["001-second.md"]);
+    }
+
+    #[test]
+    fn test_directory_type_tool_from_toml() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        let tools_dir = temp.path().join(".repository/tools");
+        fs::create_dir_all(&tools_dir).unwrap();
+        fs::write(
+            tools_dir.join("dirtool.toml"),
+            r#"
+[meta]
+name = "Dir Tool"
+slug = "dirtool"
+
+[integration]
+config_path = ".dirtool/rules"
+type = { directory = {} }
+
+[capabilities]
+supports_custom_instructions = true
+supports_rules_directory = true
+"#,
+        )
+        .unwrap();
+
+        let loaded = repo_meta::DefinitionLoader::new()
+            .load_tools(&root)
+            .unwrap();
+        let def = loaded.definitions["dirtool"].clone();
+        assert_eq!(
+            def.integration.config_type,
+            ConfigType::Directory {
+                pattern: DEFAULT_DIRECTORY_PATTERN.to_string()
+            }
+        );
+
+        let integration = GenericToolIntegration::new(def);
+        assert!(integration.config_locations()[0].is_directory);
+
+        let rule = |id: &str| Rule {
+            id: id.to_string(),
+            content: format!("Content of {}", id),
+            paths: vec![],
+        };
+        let context = SyncContext::new(root);
+        let dir = temp.path().join(".dirtool/rules");
+
+        // A user file that does not match the pattern must survive cleanup
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("README.md"), "user notes").unwrap();
+
+        integration
+            .sync(&context, &[rule("alpha"), rule("beta"), rule("gamma")])
+            .unwrap();
+        let mut names: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            ["01-alpha.md", "02-beta.md", "03-gamma.md", "README.md"]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("02-beta.md")).unwrap(),
+            "# beta\n\nContent of beta"
+        );
+
+        // Removing a rule renumbers the rest and deletes stale files
+        integration
+            .sync(&context, &[rule("alpha"), rule("gamma")])
+            .unwrap();
+        let mut names: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["01-alpha.md", "02-gamma.md", "README.md"]);
+    }
+
     #[test]
     fn test_antigravity_path_is_directory() {
         // Import the antigravity integration to verify its config_path
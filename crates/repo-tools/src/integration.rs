@@ -1,7 +1,8 @@
 //! ToolIntegration trait for syncing to external tools
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use repo_fs::NormalizedPath;
+use std::collections::BTreeMap;
 
 // Re-export ConfigType for convenience
 pub use repo_meta::schema::ConfigType;
@@ -11,6 +12,8 @@ pub use repo_meta::schema::ConfigType;
 pub struct Rule {
     pub id: String,
     pub content: String,
+    /// Glob patterns of the files the rule targets; empty means every file
+    pub paths: Vec<String>,
 }
 
 /// Context for tool sync operations
@@ -23,6 +26,11 @@ pub struct SyncContext {
     /// This is a JSON object where keys are server names and values are
     /// their full configuration (command, args, env, etc.).
     pub mcp_servers: Option<serde_json::Value>,
+    /// Template variables, referenced from rule content as `${name}`.
+    ///
+    /// Tool writers may also read them directly, e.g. to embed the project
+    /// name in a config file.
+    pub vars: BTreeMap<String, String>,
 }
 
 impl SyncContext {
@@ -31,10 +39,14 @@ impl SyncContext {
             root,
             python_path: None,
             mcp_servers: None,
+            vars: BTreeMap::new(),
         }
     }
 
+    /// Set the Python interpreter, also available to rules as `${python_path}`.
     pub fn with_python(mut self, path: NormalizedPath) -> Self {
+        self.vars
+            .insert("python_path".to_string(), path.as_str().to_string());
         self.python_path = Some(path);
         self
     }
@@ -43,6 +55,44 @@ impl SyncContext {
         self.mcp_servers = Some(servers);
         self
     }
+
+    /// Set a template variable, replacing any previous value.
+    pub fn with_var(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set several template variables, replacing any previous values.
+    pub fn with_vars<K, V>(mut self, vars: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.vars
+            .extend(vars.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    /// Substitute `${name}` references to [`vars`](SyncContext::vars) in
+    /// `content`. References to unknown variables are left as written.
+    pub fn render(&self, content: &str) -> String {
+        let mut result = content.to_string();
+        for (key, value) in &self.vars {
+            result = result.replace(&format!("${{{}}}", key), value);
+        }
+        result
+    }
+
+    /// Rules with their content passed through [`render`](SyncContext::render).
+    pub fn render_rules(&self, rules: &[Rule]) -> Vec<Rule> {
+        rules
+            .iter()
+            .map(|rule| Rule {
+                content: self.render(&rule.content),
+                ..rule.clone()
+            })
+            .collect()
+    }
 }
 
 /// Describes a configuration location for a tool.
@@ -91,5 +141,223 @@ pub trait ToolIntegration {
     fn config_locations(&self) -> Vec<ConfigLocation>;
 
     /// Sync rules to this tool's configuration files.
+    ///
+    /// Implementations render rule content with [`SyncContext::render_rules`]
+    /// before writing it.
     fn sync(&self, context: &SyncContext, rules: &[Rule]) -> Result<()>;
+
+    /// Files that [`sync`](ToolIntegration::sync) writes for individual
+    /// rules, relative to the repository root.
+    ///
+    /// Directory locations are not tracked as a whole, so callers record
+    /// these files individually to notice when one goes missing.
+    fn rule_files(&self, _rules: &[Rule]) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Config files, relative to the repository root, that this tool shares
+    /// with the user.
+    ///
+    /// Removing the tool must not delete these files; callers use
+    /// [`remove_managed`](ToolIntegration::remove_managed) for them instead.
+    fn shared_files(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Remove the managed content [`sync`](ToolIntegration::sync) wrote into
+    /// the [`shared_files`](ToolIntegration::shared_files), leaving user
+    /// content in place.
+    fn remove_managed(&self, _root: &NormalizedPath) -> Result<()> {
+        Ok(())
+    }
+
+    /// Check that `content` of the primary config file, the first of the
+    /// [`config_locations`](ToolIntegration::config_locations), is well-formed.
+    ///
+    /// Integrations override this for their config format, usually with
+    /// [`validate_config`]; [`validate_synced_output`] calls it after a sync.
+    fn validate_output(&self, _content: &str) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Check that `content` written by `tool` to `path` parses as `config_type`.
+///
+/// JSON may contain comments and trailing commas. Markdown and text files
+/// must have balanced managed block markers. Failures are reported as [`Error::InvalidOutput`].
+pub fn validate_config(
+    tool: &str,
+    path: &str,
+    config_type: &ConfigType,
+    content: &str,
+) -> Result<()> {
+    let result = match config_type {
+        ConfigType::Json => repo_content::jsonc::parse(content)
+            .map(drop)
+            .map_err(|e| e.to_string()),
+        ConfigType::Yaml => serde_yaml::from_str::<serde_yaml::Value>(content)
+            .map(drop)
+            .map_err(|e| e.to_string()),
+        ConfigType::Toml => content
+            .parse::<toml::Table>()
+            .map(drop)
+            .map_err(|e| e.to_string()),
+        ConfigType::Markdown | ConfigType::Text => validate_block_markers(content),
+        ConfigType::Directory { .. } => Ok(()),
+    };
+    result.map_err(|message| Error::InvalidOutput {
+        tool: tool.to_string(),
+        path: path.to_string(),
+        message,
+    })
+}
+
+/// Check that every `<!-- repo:block:ID -->` marker is closed, in order.
+fn validate_block_markers(content: &str) -> std::result::Result<(), String> {
+    let mut open: Option<&str> = None;
+    for line in content.lines().map(str::trim) {
+        let Some(marker) = line
+            .strip_prefix("<!-- ")
+            .and_then(|rest| rest.strip_suffix(" -->"))
+        else {
+            continue;
+        };
+        if let Some(id) = marker.strip_prefix("/repo:block:") {
+            match open.take() {
+                Some(opened) if opened == id => {}
+                _ => return Err(format!("unexpected end of managed block '{id}'")),
+            }
+        } else if let Some(id) = marker.strip_prefix("repo:block:") {
+            if let Some(opened) = open {
+                return Err(format!("managed block '{opened}' is not closed"));
+            }
+            open = Some(id);
+        }
+    }
+    match open {
+        Some(id) => Err(format!("managed block '{id}' is not closed")),
+        None => Ok(()),
+    }
+}
+
+/// Validate the primary config file an integration just synced.
+///
+/// Reads the first of the
+/// [`config_locations`](ToolIntegration::config_locations) and passes it to
+/// [`validate_output`](ToolIntegration::validate_output), so a bug that
+/// writes malformed config fails the sync instead of going unnoticed. Tools
+/// whose primary location is a directory, or that wrote nothing, pass.
+pub fn validate_synced_output(
+    integration: &dyn ToolIntegration,
+    root: &NormalizedPath,
+) -> Result<()> {
+    let Some(location) = integration.config_locations().into_iter().next() else {
+        return Ok(());
+    };
+    let path = root.join(&location.path);
+    if location.is_directory || !path.is_file() {
+        return Ok(());
+    }
+
+    let content = repo_fs::io::read_text(&path)?;
+    integration.validate_output(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Writes rule content into JSON without escaping it, as a buggy
+    /// integration might.
+    struct RawJsonIntegration;
+
+    impl ToolIntegration for RawJsonIntegration {
+        fn name(&self) -> &str {
+            "raw-json"
+        }
+
+        fn config_locations(&self) -> Vec<ConfigLocation> {
+            vec![ConfigLocation::file("out.json", ConfigType::Json)]
+        }
+
+        fn sync(&self, context: &SyncContext, rules: &[Rule]) -> Result<()> {
+            let body: Vec<String> = rules
+                .iter()
+                .map(|r| format!("\"{}\": \"{}\"", r.id, r.content))
+                .collect();
+            let content = format!("{{{}}}", body.join(", "));
+            repo_fs::io::write_text(&context.root.join("out.json"), &content)?;
+            Ok(())
+        }
+
+        fn validate_output(&self, content: &str) -> Result<()> {
+            validate_config(self.name(), "out.json", &ConfigType::Json, content)
+        }
+    }
+
+    fn rule(content: &str) -> Rule {
+        Rule {
+            id: "style".to_string(),
+            content: content.to_string(),
+            paths: vec![],
+        }
+    }
+
+    #[test]
+    fn test_validate_synced_output_rejects_invalid_json() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        let context = SyncContext::new(root.clone());
+
+        RawJsonIntegration
+            .sync(&context, &[rule("Use tabs")])
+            .unwrap();
+        validate_synced_output(&RawJsonIntegration, &root).unwrap();
+
+        // A quote in the rule breaks out of the string
+        RawJsonIntegration
+            .sync(&context, &[rule("Use \"tabs\"")])
+            .unwrap();
+        let err = validate_synced_output(&RawJsonIntegration, &root).unwrap_err();
+        assert!(matches!(err, Error::InvalidOutput { .. }));
+        assert!(
+            err.to_string()
+                .starts_with("raw-json produced invalid out.json: "),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_with_vars_overrides_earlier_values() {
+        let context = SyncContext::new(NormalizedPath::new("/repo"))
+            .with_var("name", "old")
+            .with_vars([("name", "new"), ("team", "core")]);
+
+        assert_eq!(context.vars["name"], "new");
+        assert_eq!(context.render("${team}/${name}/$name"), "core/new/$name");
+    }
+
+    #[test]
+    fn test_validate_config_formats() {
+        let check = |config_type: ConfigType, content: &str| {
+            validate_config("tool", "file", &config_type, content).is_ok()
+        };
+
+        assert!(check(ConfigType::Json, "{\"a\": 1}"));
+        assert!(!check(ConfigType::Json, "{\"a\": }"));
+        assert!(check(ConfigType::Toml, "a = 1\n"));
+        assert!(!check(ConfigType::Toml, "a = [\n"));
+        assert!(check(ConfigType::Yaml, "a: [1, 2]\n"));
+        assert!(!check(ConfigType::Yaml, "a: [1, 2\n"));
+
+        let block = "<!-- repo:block:a -->\nx\n<!-- /repo:block:a -->\n";
+        assert!(check(ConfigType::Markdown, block));
+        assert!(check(ConfigType::Text, "no blocks here"));
+        assert!(!check(ConfigType::Markdown, "<!-- repo:block:a -->\nx\n"));
+        assert!(!check(
+            ConfigType::Markdown,
+            "<!-- repo:block:a -->\n<!-- /repo:block:b -->\n"
+        ));
+    }
 }
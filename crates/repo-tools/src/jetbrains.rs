@@ -6,7 +6,7 @@
 
 use crate::generic::GenericToolIntegration;
 use repo_meta::schema::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
 };
 
 /// Creates a JetBrains AI Assistant integration.
@@ -24,11 +24,16 @@ pub fn jetbrains_integration() -> GenericToolIntegration {
             name: "JetBrains AI".into(),
             slug: "jetbrains".into(),
             description: Some("JetBrains AI Assistant for IntelliJ IDEs".into()),
+            override_builtin: false,
+            open_command: None,
         },
         integration: ToolIntegrationConfig {
             config_path: ".aiassistant/rules/".into(),
             config_type: ConfigType::Markdown,
             additional_paths: vec![".aiignore".into()],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             supports_custom_instructions: true,
@@ -23,6 +23,7 @@ pub mod antigravity;
 pub mod claude;
 pub mod claude_desktop;
 pub mod cline;
+pub mod codex;
 pub mod copilot;
 pub mod cursor;
 pub mod dispatcher;
@@ -43,36 +44,48 @@ pub mod windsurf;
 pub mod writer;
 pub mod zed;
 
-pub use aider::aider_integration;
+pub use aider::{AiderIntegration, aider_definition, aider_integration};
 pub use amazonq::amazonq_integration;
 pub use antigravity::{AntigravityIntegration, antigravity_integration};
 pub use claude::{ClaudeIntegration, claude_integration};
 pub use claude_desktop::claude_desktop_integration;
 pub use cline::cline_integration;
+pub use codex::{CodexIntegration, codex_integration};
 pub use copilot::copilot_integration;
-pub use cursor::{CursorIntegration, cursor_integration};
-pub use dispatcher::ToolDispatcher;
+pub use cursor::{CursorIntegration, CursorMode, cursor_integration, cursor_integration_with_mode};
+pub use dispatcher::{ToolDispatcher, ToolResolution};
 pub use error::{Error, Result};
 pub use gemini::{GeminiIntegration, gemini_integration};
 pub use generic::GenericToolIntegration;
-pub use integration::{ConfigLocation, ConfigType, Rule, SyncContext, ToolIntegration};
+pub use integration::{
+    ConfigLocation, ConfigType, Rule, SyncContext, ToolIntegration, validate_config,
+    validate_synced_output,
+};
 pub use jetbrains::jetbrains_integration;
 pub use roo::roo_integration;
 pub use vscode::{VSCodeIntegration, vscode_definition};
-pub use windsurf::{WindsurfIntegration, windsurf_integration};
+pub use windsurf::{
+    WindsurfIntegration, WindsurfMode, windsurf_integration, windsurf_integration_with_mode,
+};
 pub use zed::zed_integration;
 
 // Registry types
 pub use registry::{
-    BUILTIN_COUNT, ToolCategory, ToolRegistration, ToolRegistry, builtin_registrations,
+    BUILTIN_COUNT, ToolCategory, ToolRegistration, ToolRegistry, ToolSource, UnknownTool,
+    builtin_registrations, close_matches, validate_tool_name,
 };
 
 // Translator types
-pub use translator::{CapabilityTranslator, RuleTranslator, TranslatedContent};
+pub use translator::{
+    CapabilityTranslator, INSTRUCTIONS_EXTENSION, MDC_EXTENSION, RuleTranslator, TranslatedContent,
+    TranslatedFile, WINDSURF_RULE_CHAR_LIMIT, WINDSURF_RULES_DIR, WINDSURF_TRIGGER_TAG,
+    WINDSURF_TRIGGERS, instructions_file_name, mdc_file_name, render_instructions, render_mdc,
+    render_windsurf_rule, windsurf_rule_file_name, windsurf_trigger,
+};
 
 // Writer types
 pub use writer::{
-    ConfigWriter, JsonWriter, MarkdownWriter, SchemaKeys, TextWriter, WriterRegistry,
+    ConfigWriter, JsonWriter, MarkdownWriter, SchemaKeys, TextWriter, WriterRegistry, YamlWriter,
 };
 
 // MCP registry, translation, and installation
@@ -2,17 +2,35 @@
 //!
 //! Provides operations to install, remove, list, verify, and sync MCP server
 //! definitions across different tool configurations. Uses [`McpConfigSpec`] to
-//! adapt to each tool's native JSON format and file locations.
+//! adapt to each tool's native format and file locations.
+//!
+//! Configs are handled as JSON values, whatever the file format given by
+//! [`McpConfigSpec::config_format`]. TOML configs are written back by editing
+//! only the server entries, so the rest of the file keeps its comments and
+//! layout; YAML configs are rewritten whole.
+//!
+//! Before an install overwrites a server entry, or a sync updates or removes
+//! one, the whole config file is backed up to
+//! `.repository/backups/mcp/{tool}/{timestamp}.{ext}`, with the config's
+//! file extension.
+//!
+//! Servers with [`McpServerConfig::expand_env`] set have `${VAR}`
+//! placeholders in their env values and args filled in from the current
+//! environment at install time; otherwise placeholders are written as-is.
+//! [`McpInstaller::list`] hides the values of secret-looking keys (see
+//! [`SecretRedaction`]), but the configs written to disk are never redacted.
 
 use crate::error::{Error, Result};
 use crate::mcp_registry::mcp_config_spec;
 use crate::mcp_translate::to_tool_json;
 use repo_fs::NormalizedPath;
+use repo_fs::io::RobustnessConfig;
 use repo_meta::schema::{
-    McpConfigSpec, McpScope, McpServerConfig, McpSyncResult, McpVerifyResult,
+    McpConfigFormat, McpConfigSpec, McpScope, McpServerConfig, McpSyncResult, McpTransportConfig,
+    McpVerifyResult, SecretRedaction,
 };
 use serde_json::{Map, Value, json};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::warn;
 
 /// Manages MCP server installations for a specific tool.
@@ -24,6 +42,8 @@ pub struct McpInstaller {
     slug: String,
     spec: McpConfigSpec,
     root: NormalizedPath,
+    backups: bool,
+    redaction: SecretRedaction,
 }
 
 impl McpInstaller {
@@ -38,9 +58,26 @@ impl McpInstaller {
             slug: slug.to_string(),
             spec,
             root,
+            backups: true,
+            redaction: SecretRedaction::default(),
         })
     }
 
+    /// Disable backing up the config before server entries are overwritten
+    /// or removed.
+    pub fn without_backups(mut self) -> Self {
+        self.backups = false;
+        self
+    }
+
+    /// Set which keys have their values hidden by [`list`](Self::list).
+    ///
+    /// Defaults to [`SecretRedaction::default`].
+    pub fn with_redaction(mut self, redaction: SecretRedaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
     // -----------------------------------------------------------------------
     // Private helpers
     // -----------------------------------------------------------------------
@@ -72,13 +109,13 @@ impl McpInstaller {
     fn config_path(&self, scope: McpScope) -> Result<PathBuf> {
         match scope {
             McpScope::Project => {
-                let rel =
-                    self.spec
-                        .project_path
-                        .ok_or_else(|| Error::McpScopeNotSupported {
-                            tool: self.slug.clone(),
-                            scope: "project".into(),
-                        })?;
+                let rel = self
+                    .spec
+                    .project_path
+                    .ok_or_else(|| Error::McpScopeNotSupported {
+                        tool: self.slug.clone(),
+                        scope: "project".into(),
+                    })?;
                 Ok(self.root.join(rel).to_native())
             }
             McpScope::User => {
@@ -97,8 +134,8 @@ impl McpInstaller {
         }
     }
 
-    /// Read the config file and parse as JSON. Returns an empty object if the
-    /// file doesn't exist.
+    /// Read the config file and parse it as a JSON object. Returns an empty
+    /// object if the file doesn't exist.
     fn read_config(&self, scope: McpScope) -> Result<(PathBuf, Value)> {
         let path = self.config_path(scope)?;
         let value = if path.exists() {
@@ -109,11 +146,19 @@ impl McpInstaller {
             if content.trim().is_empty() {
                 json!({})
             } else {
-                let parsed: Value =
-                    serde_json::from_str(&content).map_err(|e| Error::McpConfig {
-                        tool: self.slug.clone(),
-                        message: format!("Failed to parse {}: {e}", path.display()),
-                    })?;
+                let parsed = match self.spec.config_format {
+                    McpConfigFormat::Json => {
+                        serde_json::from_str(&content).map_err(|e| e.to_string())
+                    }
+                    McpConfigFormat::Toml => toml::from_str(&content).map_err(|e| e.to_string()),
+                    McpConfigFormat::Yaml => {
+                        serde_yaml::from_str(&content).map_err(|e| e.to_string())
+                    }
+                };
+                let parsed: Value = parsed.map_err(|e| Error::McpConfig {
+                    tool: self.slug.clone(),
+                    message: format!("Failed to parse {}: {e}", path.display()),
+                })?;
                 if !parsed.is_object() {
                     return Err(Error::McpConfig {
                         tool: self.slug.clone(),
@@ -132,31 +177,136 @@ impl McpInstaller {
         Ok((path, value))
     }
 
-    /// Write JSON to the config file, creating parent directories as needed.
+    /// Write the config file, creating parent directories as needed.
     ///
     /// Uses atomic write-to-temp-then-rename to prevent config corruption if
     /// the process is interrupted mid-write.
-    fn write_config(&self, path: &PathBuf, value: &Value) -> Result<()> {
+    fn write_config(&self, path: &Path, value: &Value) -> Result<()> {
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| Error::McpConfig {
                 tool: self.slug.clone(),
                 message: format!("Failed to create directory {}: {e}", parent.display()),
             })?;
         }
-        let mut content = serde_json::to_string_pretty(value)?;
-        content.push('\n');
+        let content = match self.spec.config_format {
+            McpConfigFormat::Json => {
+                let mut content = serde_json::to_string_pretty(value)?;
+                content.push('\n');
+                content
+            }
+            McpConfigFormat::Toml => self.render_toml(path, value)?,
+            McpConfigFormat::Yaml => serde_yaml::to_string(value)?,
+        };
 
-        // Atomic write: write to a sibling temp file, then rename.
-        let tmp_path = path.with_extension("tmp");
-        std::fs::write(&tmp_path, content.as_bytes()).map_err(|e| Error::McpConfig {
-            tool: self.slug.clone(),
-            message: format!("Failed to write {}: {e}", tmp_path.display()),
-        })?;
-        std::fs::rename(&tmp_path, path).map_err(|e| Error::McpConfig {
+        repo_fs::io::write_atomic_unlocked(path, content.as_bytes(), &RobustnessConfig::current())
+            .map_err(|e| Error::McpConfig {
+                tool: self.slug.clone(),
+                message: format!("Failed to write {}: {e}", path.display()),
+            })
+    }
+
+    /// Render `value` as TOML, editing the existing file at `path` in place.
+    ///
+    /// Only the server entries are rewritten, and only those that changed;
+    /// other top-level keys are added if missing but otherwise left as they
+    /// are, since the installer never changes them.
+    fn render_toml(&self, path: &Path, value: &Value) -> Result<String> {
+        let parse_error = |e: &dyn std::fmt::Display| Error::McpConfig {
             tool: self.slug.clone(),
-            message: format!("Failed to rename {} -> {}: {e}", tmp_path.display(), path.display()),
-        })?;
-        Ok(())
+            message: format!("Failed to parse {}: {e}", path.display()),
+        };
+        let existing = if path.exists() {
+            std::fs::read_to_string(path).map_err(|e| Error::McpConfig {
+                tool: self.slug.clone(),
+                message: format!("Failed to read {}: {e}", path.display()),
+            })?
+        } else {
+            String::new()
+        };
+        let mut doc: toml_edit::DocumentMut = existing.parse().map_err(|e| parse_error(&e))?;
+        let current: Value = toml::from_str(&existing).map_err(|e| parse_error(&e))?;
+
+        let Some(root) = value.as_object() else {
+            return Ok(doc.to_string());
+        };
+        for (key, item) in root {
+            if key != self.spec.servers_key
+                && !doc.contains_key(key)
+                && let Some(item) = toml_item(item)
+            {
+                doc.insert(key, item);
+            }
+        }
+
+        let servers = root
+            .get(self.spec.servers_key)
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        let current_servers = current
+            .get(self.spec.servers_key)
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+
+        if !doc
+            .get(self.spec.servers_key)
+            .is_some_and(|i| i.is_table_like())
+        {
+            let mut table = toml_edit::Table::new();
+            table.set_implicit(true);
+            doc.insert(self.spec.servers_key, toml_edit::Item::Table(table));
+        }
+        let table = doc[self.spec.servers_key]
+            .as_table_like_mut()
+            .expect("invariant: servers key was just made a table");
+
+        let stale: Vec<String> = table
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .filter(|name| !servers.contains_key(name))
+            .collect();
+        for name in stale {
+            table.remove(&name);
+        }
+        for (name, server) in &servers {
+            if current_servers.get(name) != Some(server)
+                && let Some(item) = toml_item(server)
+            {
+                table.insert(name, item);
+            }
+        }
+
+        Ok(doc.to_string())
+    }
+
+    /// Back up the config as it was before a change that overwrites or
+    /// removes server entries.
+    ///
+    /// The backup is written to
+    /// `.repository/backups/mcp/{tool}/{timestamp}.{ext}`, next to the tool
+    /// backups managed by `BackupManager`. Nothing is written if backups are
+    /// disabled or the config file did not exist yet.
+    fn backup_config(&self, path: &Path, original: &Value) -> Result<()> {
+        if !self.backups || !path.exists() {
+            return Ok(());
+        }
+        let dir = self
+            .root
+            .join(".repository")
+            .join("backups")
+            .join("mcp")
+            .join(&self.slug)
+            .to_native();
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ").to_string();
+        let ext = self.spec.config_format.extension();
+        let mut backup_path = dir.join(format!("{stamp}.{ext}"));
+        let mut n = 1;
+        while backup_path.exists() {
+            backup_path = dir.join(format!("{stamp}-{n}.{ext}"));
+            n += 1;
+        }
+        self.write_config(&backup_path, original)
     }
 
     /// Get the servers map from a config value.
@@ -182,6 +332,23 @@ impl McpInstaller {
             .expect("invariant: servers_key value is always inserted as json!({})")
     }
 
+    /// Translate `config` into the tool's native JSON, expanding `${VAR}`
+    /// placeholders first if the config asks for it.
+    fn server_json(&self, server_name: &str, config: &McpServerConfig) -> Result<Value> {
+        if !config.expand_env {
+            return Ok(to_tool_json(config, &self.spec));
+        }
+        let expanded = expand_config(config, &|var| std::env::var(var).ok()).map_err(|var| {
+            Error::McpConfig {
+                tool: self.slug.clone(),
+                message: format!(
+                    "server '{server_name}' uses environment variable {var}, which is not set"
+                ),
+            }
+        })?;
+        Ok(to_tool_json(&expanded, &self.spec))
+    }
+
     // -----------------------------------------------------------------------
     // Public API
     // -----------------------------------------------------------------------
@@ -189,7 +356,7 @@ impl McpInstaller {
     /// Install an MCP server into the tool's config at the given scope.
     ///
     /// If a server with the same name already exists, it is overwritten and a
-    /// warning is logged.
+    /// warning is logged. The previous config is backed up first.
     pub fn install(
         &self,
         scope: McpScope,
@@ -198,7 +365,8 @@ impl McpInstaller {
     ) -> Result<()> {
         Self::validate_server_name(server_name)?;
         let (path, mut root_value) = self.read_config(scope)?;
-        let tool_json = to_tool_json(config, &self.spec);
+        let original = root_value.clone();
+        let tool_json = self.server_json(server_name, config)?;
         let servers = self.get_or_create_servers(&mut root_value);
         if servers.contains_key(server_name) {
             warn!(
@@ -206,6 +374,7 @@ impl McpInstaller {
                 server = server_name,
                 "overwriting existing server entry with the same name"
             );
+            self.backup_config(&path, &original)?;
         }
         servers.insert(server_name.to_string(), tool_json);
         self.write_config(&path, &root_value)
@@ -214,13 +383,15 @@ impl McpInstaller {
     /// Remove an MCP server from the tool's config.
     ///
     /// Returns `Ok(true)` if the server was found and removed, `Ok(false)` if
-    /// the server was not present.
+    /// the server was not present. The previous config is backed up first.
     pub fn remove(&self, scope: McpScope, server_name: &str) -> Result<bool> {
         Self::validate_server_name(server_name)?;
         let (path, mut root_value) = self.read_config(scope)?;
+        let original = root_value.clone();
         let servers = self.get_or_create_servers(&mut root_value);
         let removed = servers.remove(server_name).is_some();
         if removed {
+            self.backup_config(&path, &original)?;
             self.write_config(&path, &root_value)?;
         }
         Ok(removed)
@@ -228,7 +399,8 @@ impl McpInstaller {
 
     /// List all MCP servers installed in the tool's config at the given scope.
     ///
-    /// Returns a list of `(server_name, server_json)` pairs.
+    /// Returns a list of `(server_name, server_json)` pairs, with secret
+    /// values redacted.
     pub fn list(&self, scope: McpScope) -> Result<Vec<(String, Value)>> {
         let (_path, root_value) = self.read_config(scope)?;
         let servers = match self.get_servers(&root_value) {
@@ -237,7 +409,11 @@ impl McpInstaller {
         };
         Ok(servers
             .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
+            .map(|(k, v)| {
+                let mut v = v.clone();
+                self.redaction.redact_json(&mut v);
+                (k.clone(), v)
+            })
             .collect())
     }
 
@@ -311,6 +487,9 @@ impl McpInstaller {
     /// `previously_managed` is the set of server names that were managed by
     /// the repo-manager in a prior sync. This is how we tell "ours, now
     /// removed" apart from "user-added". Pass an empty slice on the first sync.
+    ///
+    /// If any entry is updated or removed, the previous config is backed up
+    /// before it is rewritten.
     pub fn sync(
         &self,
         scope: McpScope,
@@ -322,6 +501,7 @@ impl McpInstaller {
         }
 
         let (path, mut root_value) = self.read_config(scope)?;
+        let original = root_value.clone();
         let servers = self.get_or_create_servers(&mut root_value);
 
         let mut added = Vec::new();
@@ -334,10 +514,10 @@ impl McpInstaller {
             previously_managed.iter().map(|s| s.as_str()).collect();
 
         // Compute the desired state for every managed server.
-        let mut desired: std::collections::BTreeMap<String, Value> = managed_servers
-            .iter()
-            .map(|(name, config)| (name.clone(), to_tool_json(config, &self.spec)))
-            .collect();
+        let mut desired = std::collections::BTreeMap::new();
+        for (name, config) in managed_servers {
+            desired.insert(name.clone(), self.server_json(name, config)?);
+        }
 
         // Walk existing servers and reconcile with the desired state.
         let existing_names: Vec<String> = servers.keys().cloned().collect();
@@ -391,6 +571,9 @@ impl McpInstaller {
             unchanged,
         };
 
+        if !result.updated.is_empty() || !result.removed.is_empty() {
+            self.backup_config(&path, &original)?;
+        }
         if !result.is_empty() {
             self.write_config(&path, &root_value)?;
         }
@@ -399,6 +582,59 @@ impl McpInstaller {
     }
 }
 
+/// Copy of `config` with `${VAR}` placeholders in env values and args
+/// replaced by `lookup(VAR)`.
+///
+/// Returns the name of the first variable `lookup` can't resolve.
+fn expand_config(
+    config: &McpServerConfig,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> std::result::Result<McpServerConfig, String> {
+    let mut expanded = config.clone();
+    if let Some(env) = &mut expanded.env {
+        for value in env.values_mut() {
+            *value = expand_placeholders(value, lookup)?;
+        }
+    }
+    if let McpTransportConfig::Stdio { args, .. } = &mut expanded.transport {
+        for arg in args {
+            *arg = expand_placeholders(arg, lookup)?;
+        }
+    }
+    Ok(expanded)
+}
+
+/// Replace each `${VAR}` in `text` with `lookup(VAR)`.
+///
+/// Only plain variable names are expanded; other forms such as
+/// `${env:VAR}` or `${VAR:-default}` are left for the tool to handle.
+fn expand_placeholders(
+    text: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> std::result::Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let name = after.find('}').map(|end| &after[..end]).filter(|name| {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+        match name {
+            Some(name) => {
+                out.push_str(&lookup(name).ok_or_else(|| name.to_string())?);
+                rest = &after[name.len() + 1..];
+            }
+            None => {
+                out.push_str("${");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
 /// Get the user's home directory.
 fn home_dir() -> Result<PathBuf> {
     // Use $HOME on Unix, %USERPROFILE% on Windows.
@@ -408,6 +644,49 @@ fn home_dir() -> Result<PathBuf> {
         .map_err(|_| Error::HomeDirNotFound)
 }
 
+/// Convert a JSON value to a TOML item.
+///
+/// Objects become standard tables whose own object fields are inline tables,
+/// matching `[mcp_servers.name]` entries with `env = { ... }`. Nulls have no
+/// TOML equivalent and yield `None`; null fields are dropped.
+fn toml_item(value: &Value) -> Option<toml_edit::Item> {
+    match value {
+        Value::Object(map) => {
+            let mut table = toml_edit::Table::new();
+            for (key, field) in map {
+                if let Some(field) = toml_value(field) {
+                    table.insert(key, toml_edit::Item::Value(field));
+                }
+            }
+            Some(toml_edit::Item::Table(table))
+        }
+        other => toml_value(other).map(toml_edit::Item::Value),
+    }
+}
+
+/// Convert a JSON value to a TOML value, with objects as inline tables.
+fn toml_value(value: &Value) -> Option<toml_edit::Value> {
+    Some(match value {
+        Value::Null => return None,
+        Value::Bool(b) => (*b).into(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into(),
+            None => n.as_f64()?.into(),
+        },
+        Value::String(s) => s.as_str().into(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(toml_value)
+            .collect::<toml_edit::Array>()
+            .into(),
+        Value::Object(map) => map
+            .iter()
+            .filter_map(|(k, v)| Some((k.as_str(), toml_value(v)?)))
+            .collect::<toml_edit::InlineTable>()
+            .into(),
+    })
+}
+
 /// Return a human-readable name for the JSON value type.
 fn json_type_name(v: &Value) -> &'static str {
     match v {
@@ -440,6 +719,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         }
     }
 
@@ -451,6 +731,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         }
     }
 
@@ -565,9 +846,7 @@ mod tests {
 
         // .cursor/mcp.json parent dir doesn't exist yet
         let config = stdio_config("test");
-        installer
-            .install(McpScope::Project, "s1", &config)
-            .unwrap();
+        installer.install(McpScope::Project, "s1", &config).unwrap();
 
         let path = temp.path().join(".cursor").join("mcp.json");
         assert!(path.exists());
@@ -610,6 +889,97 @@ mod tests {
         assert_eq!(servers[0].1["serverUrl"], "https://example.com/mcp");
     }
 
+    fn config_with_env(expand_env: bool) -> McpServerConfig {
+        McpServerConfig {
+            transport: McpTransportConfig::Stdio {
+                command: "server".into(),
+                args: vec!["--root=${CARGO_MANIFEST_DIR}".into(), "${env:HOME}".into()],
+                cwd: None,
+            },
+            env: Some(BTreeMap::from([(
+                "API_TOKEN".to_string(),
+                "${CARGO_MANIFEST_DIR}".to_string(),
+            )])),
+            auto_approve: false,
+            expand_env,
+        }
+    }
+
+    #[test]
+    fn test_install_expands_env_placeholders() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        let installer = McpInstaller::new("cursor", root).unwrap();
+
+        installer
+            .install(McpScope::Project, "plain", &config_with_env(false))
+            .unwrap();
+        installer
+            .install(McpScope::Project, "expanded", &config_with_env(true))
+            .unwrap();
+
+        let content = std::fs::read_to_string(temp.path().join(".cursor/mcp.json")).unwrap();
+        let json: Value = serde_json::from_str(&content).unwrap();
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let plain = &json["mcpServers"]["plain"];
+        assert_eq!(plain["args"][0], "--root=${CARGO_MANIFEST_DIR}");
+        assert_eq!(plain["env"]["API_TOKEN"], "${CARGO_MANIFEST_DIR}");
+        let expanded = &json["mcpServers"]["expanded"];
+        assert_eq!(expanded["args"][0], format!("--root={dir}"));
+        assert_eq!(expanded["args"][1], "${env:HOME}");
+        assert_eq!(expanded["env"]["API_TOKEN"], dir);
+    }
+
+    #[test]
+    fn test_install_rejects_unset_env_placeholder() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        let installer = McpInstaller::new("cursor", root).unwrap();
+        let mut config = config_with_env(true);
+        config.env = Some(BTreeMap::from([(
+            "API_TOKEN".to_string(),
+            "${REPO_TEST_UNSET_VARIABLE}".to_string(),
+        )]));
+
+        let err = installer
+            .install(McpScope::Project, "s1", &config)
+            .unwrap_err();
+        assert!(err.to_string().contains("REPO_TEST_UNSET_VARIABLE"));
+        assert!(!temp.path().join(".cursor/mcp.json").exists());
+    }
+
+    #[test]
+    fn test_list_redacts_secrets_but_config_keeps_them() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        let installer = McpInstaller::new("cursor", root).unwrap();
+        let mut config = stdio_config("server");
+        config.env = Some(BTreeMap::from([
+            ("GITHUB_TOKEN".to_string(), "ghp_123".to_string()),
+            ("DB_PASSWORD".to_string(), "hunter2".to_string()),
+            ("LOG_LEVEL".to_string(), "debug".to_string()),
+        ]));
+        installer.install(McpScope::Project, "s1", &config).unwrap();
+
+        let servers = installer.list(McpScope::Project).unwrap();
+        let env = &servers[0].1["env"];
+        assert_eq!(env["GITHUB_TOKEN"], repo_meta::schema::REDACTED);
+        assert_eq!(env["DB_PASSWORD"], "hunter2");
+        assert_eq!(env["LOG_LEVEL"], "debug");
+
+        let installer =
+            installer.with_redaction(SecretRedaction::default().with_patterns(["*_PASSWORD"]));
+        let servers = installer.list(McpScope::Project).unwrap();
+        assert_eq!(
+            servers[0].1["env"]["DB_PASSWORD"],
+            repo_meta::schema::REDACTED
+        );
+
+        let content = std::fs::read_to_string(temp.path().join(".cursor/mcp.json")).unwrap();
+        assert!(content.contains("ghp_123"));
+        assert!(content.contains("hunter2"));
+    }
+
     // -- Remove --------------------------------------------------------------
 
     #[test]
@@ -719,9 +1089,7 @@ mod tests {
         managed.insert("s1".into(), stdio_config("cmd1"));
         managed.insert("s2".into(), stdio_config("cmd2"));
 
-        let result = installer
-            .sync(McpScope::Project, &managed, &[])
-            .unwrap();
+        let result = installer.sync(McpScope::Project, &managed, &[]).unwrap();
         assert_eq!(result.added.len(), 2);
         assert!(result.updated.is_empty());
         assert!(result.removed.is_empty());
@@ -741,9 +1109,7 @@ mod tests {
         // Sync managed servers (not including user-server)
         let mut managed = BTreeMap::new();
         managed.insert("managed-server".into(), stdio_config("managed"));
-        let result = installer
-            .sync(McpScope::Project, &managed, &[])
-            .unwrap();
+        let result = installer.sync(McpScope::Project, &managed, &[]).unwrap();
 
         // user-server should still be there
         let all = installer.list(McpScope::Project).unwrap();
@@ -834,9 +1200,7 @@ mod tests {
         // Sync with updated version
         let mut managed = BTreeMap::new();
         managed.insert("s1".into(), stdio_config("new"));
-        let result = installer
-            .sync(McpScope::Project, &managed, &[])
-            .unwrap();
+        let result = installer.sync(McpScope::Project, &managed, &[]).unwrap();
 
         assert!(result.added.is_empty());
         assert_eq!(result.updated, vec!["s1"]);
@@ -857,9 +1221,7 @@ mod tests {
 
         let mut managed = BTreeMap::new();
         managed.insert("s1".into(), stdio_config("test"));
-        let result = installer
-            .sync(McpScope::Project, &managed, &[])
-            .unwrap();
+        let result = installer.sync(McpScope::Project, &managed, &[]).unwrap();
 
         assert!(result.is_empty());
         assert_eq!(result.unchanged, vec!["s1"]);
@@ -878,9 +1240,7 @@ mod tests {
 
         // Sync with empty set — nothing should change
         let managed = BTreeMap::new();
-        let result = installer
-            .sync(McpScope::Project, &managed, &[])
-            .unwrap();
+        let result = installer.sync(McpScope::Project, &managed, &[]).unwrap();
         assert!(result.is_empty());
         assert!(result.added.is_empty());
         assert!(result.removed.is_empty());
@@ -889,6 +1249,152 @@ mod tests {
         assert_eq!(all.len(), 1);
     }
 
+    // -- Backups -------------------------------------------------------------
+
+    fn backups(temp: &TempDir, tool: &str) -> Vec<PathBuf> {
+        let dir = temp
+            .path()
+            .join(".repository")
+            .join("backups")
+            .join("mcp")
+            .join(tool);
+        match std::fs::read_dir(dir) {
+            Ok(entries) => entries.map(|e| e.unwrap().path()).collect(),
+            Err(_) => vec![],
+        }
+    }
+
+    #[test]
+    fn test_install_overwrite_creates_backup() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        let installer = McpInstaller::new("cursor", root).unwrap();
+
+        installer
+            .install(McpScope::Project, "s1", &stdio_config("old"))
+            .unwrap();
+        assert!(backups(&temp, "cursor").is_empty());
+
+        installer
+            .install(McpScope::Project, "s1", &stdio_config("new"))
+            .unwrap();
+
+        let backups = backups(&temp, "cursor");
+        assert_eq!(backups.len(), 1);
+        assert_eq!(backups[0].extension().unwrap(), "json");
+        let backup: Value =
+            serde_json::from_str(&std::fs::read_to_string(&backups[0]).unwrap()).unwrap();
+        assert_eq!(backup["mcpServers"]["s1"]["command"], "old");
+    }
+
+    #[test]
+    fn test_without_backups_skips_backup() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        let installer = McpInstaller::new("cursor", root).unwrap().without_backups();
+
+        installer
+            .install(McpScope::Project, "s1", &stdio_config("old"))
+            .unwrap();
+        installer
+            .install(McpScope::Project, "s1", &stdio_config("new"))
+            .unwrap();
+
+        assert!(backups(&temp, "cursor").is_empty());
+    }
+
+    #[test]
+    fn test_sync_removal_creates_backup() {
+        let temp = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp.path());
+        let installer = McpInstaller::new("cursor", root).unwrap();
+
+        let mut managed = BTreeMap::new();
+        managed.insert("s1".to_string(), stdio_config("cmd"));
+        installer.sync(McpScope::Project, &managed, &[]).unwrap();
+        assert!(backups(&temp, "cursor").is_empty());
+
+        installer
+            .sync(McpScope::Project, &BTreeMap::new(), &["s1".to_string()])
+            .unwrap();
+
+        let backups = backups(&temp, "cursor");
+        assert_eq!(backups.len(), 1);
+        let backup: Value =
+            serde_json::from_str(&std::fs::read_to_string(&backups[0]).unwrap()).unwrap();
+        assert_eq!(backup["mcpServers"]["s1"]["command"], "cmd");
+    }
+
+    // -- Claude Code project scope (.mcp.json) -------------------------------
+
+    fn write_claude_project_config(temp: &TempDir) -> PathBuf {
+        let path = temp.path().join(".mcp.json");
+        std::fs::write(
+            &path,
+            r#"{
+  "mcpServers": {
+    "user-db": { "type": "stdio", "command": "db-mcp", "args": ["--ro"] }
+  }
+}
+"#,
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_claude_project_install_preserves_user_servers() {
+        let temp = TempDir::new().unwrap();
+        let path = write_claude_project_config(&temp);
+        let installer = McpInstaller::new("claude", NormalizedPath::new(temp.path())).unwrap();
+
+        installer
+            .install(McpScope::Project, "repo-tools", &stdio_config("repo-mcp"))
+            .unwrap();
+
+        let written: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let servers = written["mcpServers"].as_object().unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers["user-db"]["command"], "db-mcp");
+        assert_eq!(servers["user-db"]["args"], json!(["--ro"]));
+        assert_eq!(servers["repo-tools"]["command"], "repo-mcp");
+        assert_eq!(servers["repo-tools"]["type"], "stdio");
+
+        let result = installer.verify(McpScope::Project, "repo-tools").unwrap();
+        assert!(result.exists);
+        assert!(result.issues.is_empty());
+    }
+
+    #[test]
+    fn test_claude_project_sync_preserves_user_servers() {
+        let temp = TempDir::new().unwrap();
+        write_claude_project_config(&temp);
+        let installer = McpInstaller::new("claude", NormalizedPath::new(temp.path())).unwrap();
+
+        let mut managed = BTreeMap::new();
+        managed.insert("repo-tools".to_string(), stdio_config("repo-mcp"));
+        let result = installer.sync(McpScope::Project, &managed, &[]).unwrap();
+        assert_eq!(result.added, vec!["repo-tools"]);
+
+        let result = installer
+            .sync(
+                McpScope::Project,
+                &BTreeMap::new(),
+                &["repo-tools".to_string()],
+            )
+            .unwrap();
+        assert_eq!(result.removed, vec!["repo-tools"]);
+
+        let names: Vec<String> = installer
+            .list(McpScope::Project)
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["user-db"]);
+    }
+
     // -- Scope not supported -------------------------------------------------
 
     #[test]
@@ -958,9 +1464,13 @@ mod tests {
             .install(McpScope::Project, "s1", &stdio_config("test"))
             .unwrap();
 
-        let tmp_path = temp.path().join(".cursor").join("mcp.tmp");
-        assert!(
-            !tmp_path.exists(),
+        let entries: Vec<_> = std::fs::read_dir(temp.path().join(".cursor"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(
+            entries,
+            ["mcp.json"],
             "temp file should be cleaned up after atomic write"
         );
     }
@@ -999,6 +1509,122 @@ mod tests {
         assert!(json["mcpServers"]["new-server"].is_object());
     }
 
+    // -- TOML configs ----------------------------------------------------------
+
+    /// Codex installer reading its config from the project root instead of
+    /// the home directory, so tests don't touch the real `~/.codex`.
+    fn codex_installer(temp: &TempDir) -> McpInstaller {
+        let mut spec = mcp_config_spec("codex").unwrap();
+        spec.project_path = Some("config.toml");
+        McpInstaller {
+            slug: "codex".into(),
+            spec,
+            root: NormalizedPath::new(temp.path()),
+            backups: true,
+            redaction: SecretRedaction::default(),
+        }
+    }
+
+    #[test]
+    fn test_toml_install_preserves_user_config() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "# my defaults\nmodel = \"o3\"\n\n[mcp_servers.mine]\ncommand = \"mine\" # keep me\n",
+        )
+        .unwrap();
+
+        let installer = codex_installer(&temp);
+        let mut config = stdio_config("npx");
+        config.env = Some(BTreeMap::from([("KEY".into(), "value".into())]));
+        installer
+            .install(McpScope::Project, "docs", &config)
+            .unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# my defaults\nmodel = \"o3\"\n"));
+        assert!(content.contains("command = \"mine\" # keep me"));
+        assert!(content.contains("[mcp_servers.docs]"));
+        assert!(!content.contains("[mcp_servers]\n"));
+
+        let parsed: toml::Table = toml::from_str(&content).unwrap();
+        assert_eq!(
+            parsed["mcp_servers"]["docs"]["command"].as_str(),
+            Some("npx")
+        );
+        assert_eq!(
+            parsed["mcp_servers"]["docs"]["env"]["KEY"].as_str(),
+            Some("value")
+        );
+
+        let names: Vec<_> = installer
+            .list(McpScope::Project)
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["docs", "mine"]);
+    }
+
+    /// Cursor installer writing its project config in `format`.
+    fn installer_with_format(temp: &TempDir, format: McpConfigFormat) -> McpInstaller {
+        let mut spec = mcp_config_spec("cursor").unwrap();
+        spec.config_format = format;
+        spec.project_path = Some(match format {
+            McpConfigFormat::Json => "mcp.json",
+            McpConfigFormat::Toml => "mcp.toml",
+            McpConfigFormat::Yaml => "mcp.yaml",
+        });
+        McpInstaller {
+            slug: "cursor".into(),
+            spec,
+            root: NormalizedPath::new(temp.path()),
+            backups: true,
+            redaction: SecretRedaction::default(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_in_each_format() {
+        for format in [
+            McpConfigFormat::Json,
+            McpConfigFormat::Toml,
+            McpConfigFormat::Yaml,
+        ] {
+            let temp = TempDir::new().unwrap();
+            let installer = installer_with_format(&temp, format);
+            let scope = McpScope::Project;
+
+            installer.install(scope, "a", &stdio_config("one")).unwrap();
+            installer
+                .install(scope, "b", &http_config("http://x"))
+                .unwrap();
+            let verified = installer.verify(scope, "a").unwrap();
+            assert!(verified.exists && verified.issues.is_empty(), "{format:?}");
+            assert_eq!(installer.list(scope).unwrap().len(), 2, "{format:?}");
+
+            // Overwriting backs up the file in its own format
+            installer.install(scope, "a", &stdio_config("two")).unwrap();
+            let backups: Vec<_> =
+                std::fs::read_dir(temp.path().join(".repository/backups/mcp/cursor"))
+                    .unwrap()
+                    .map(|e| e.unwrap().path())
+                    .collect();
+            assert_eq!(backups.len(), 1, "{format:?}");
+            assert_eq!(backups[0].extension().unwrap(), format.extension());
+
+            let managed = BTreeMap::from([("a".to_string(), stdio_config("two"))]);
+            let result = installer.sync(scope, &managed, &["b".to_string()]).unwrap();
+            assert_eq!(result.unchanged, vec!["a"], "{format:?}");
+            assert_eq!(result.removed, vec!["b"], "{format:?}");
+
+            assert!(installer.remove(scope, "a").unwrap(), "{format:?}");
+            assert!(installer.list(scope).unwrap().is_empty(), "{format:?}");
+            assert!(!installer.verify(scope, "a").unwrap().exists);
+        }
+    }
+
     // -- List on empty/nonexistent config ------------------------------------
 
     #[test]
@@ -1045,5 +1671,14 @@ mod tests {
         let content = std::fs::read_to_string(&path).unwrap();
         let json: Value = serde_json::from_str(&content).unwrap();
         assert!(json.get("context_servers").is_some());
+        // Zed nests the command line in a "command" object
+        assert_eq!(json["context_servers"]["s1"]["command"]["path"], "test");
+        assert!(
+            installer
+                .verify(McpScope::Project, "s1")
+                .unwrap()
+                .issues
+                .is_empty()
+        );
     }
 }
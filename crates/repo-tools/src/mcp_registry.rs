@@ -11,8 +11,8 @@
 //! 3. Add the slug to [`MCP_CAPABLE_TOOLS`].
 
 use repo_meta::schema::{
-    McpConfigEmbedding, McpConfigSpec, McpEnvSyntax, McpFieldMappings, McpTransport,
-    McpTypeValues, McpUserPath,
+    McpConfigEmbedding, McpConfigFormat, McpConfigSpec, McpEnvSyntax, McpFieldMappings, McpStdioShape,
+    McpTransport, McpTypeValues, McpUserPath,
 };
 
 /// All tool slugs that support MCP, in alphabetical order.
@@ -22,6 +22,7 @@ pub const MCP_CAPABLE_TOOLS: &[&str] = &[
     "claude",
     "claude_desktop",
     "cline",
+    "codex",
     "copilot",
     "cursor",
     "gemini",
@@ -50,6 +51,7 @@ pub fn mcp_config_spec(slug: &str) -> Option<McpConfigSpec> {
         "cline" => Some(cline_mcp_spec()),
         "roo" => Some(roo_mcp_spec()),
         "amazonq" => Some(amazonq_mcp_spec()),
+        "codex" => Some(codex_mcp_spec()),
         _ => None,
     }
 }
@@ -68,6 +70,7 @@ fn claude_mcp_spec() -> McpConfigSpec {
         project_path: Some(".mcp.json"),
         user_path: Some(McpUserPath::HomeRelative(".claude.json")),
         embedding: McpConfigEmbedding::Dedicated,
+        config_format: McpConfigFormat::Json,
         transports: &[McpTransport::Stdio, McpTransport::Http],
         field_mappings: McpFieldMappings {
             http_url_field: "url",
@@ -78,6 +81,7 @@ fn claude_mcp_spec() -> McpConfigSpec {
                 http: Some("http"),
                 sse: None, // SSE deprecated in Claude Code
             },
+            stdio_shape: McpStdioShape::Flat,
         },
         env_syntax: Some(McpEnvSyntax::DollarBrace),
     }
@@ -97,12 +101,14 @@ fn claude_desktop_mcp_spec() -> McpConfigSpec {
             windows: "AppData/Roaming/Claude/claude_desktop_config.json",
         }),
         embedding: McpConfigEmbedding::Nested, // MCP is inside a larger config file
+        config_format: McpConfigFormat::Json,
         transports: &[McpTransport::Stdio],
         field_mappings: McpFieldMappings {
             http_url_field: "url",
             sse_url_field: None,
             requires_type_field: false,
             type_values: McpTypeValues::default(),
+            stdio_shape: McpStdioShape::Flat,
         },
         env_syntax: None,
     }
@@ -118,12 +124,14 @@ fn gemini_mcp_spec() -> McpConfigSpec {
         project_path: Some(".gemini/settings.json"),
         user_path: Some(McpUserPath::HomeRelative(".gemini/settings.json")),
         embedding: McpConfigEmbedding::Nested, // settings.json has other keys too
+        config_format: McpConfigFormat::Json,
         transports: &[McpTransport::Stdio, McpTransport::Http, McpTransport::Sse],
         field_mappings: McpFieldMappings {
-            http_url_field: "httpUrl", // Gemini uses "httpUrl" for Streamable HTTP
+            http_url_field: "httpUrl",  // Gemini uses "httpUrl" for Streamable HTTP
             sse_url_field: Some("url"), // and "url" for SSE
             requires_type_field: false,
             type_values: McpTypeValues::default(),
+            stdio_shape: McpStdioShape::Flat,
         },
         env_syntax: Some(McpEnvSyntax::DollarSign),
     }
@@ -139,12 +147,14 @@ fn cursor_mcp_spec() -> McpConfigSpec {
         project_path: Some(".cursor/mcp.json"),
         user_path: Some(McpUserPath::HomeRelative(".cursor/mcp.json")),
         embedding: McpConfigEmbedding::Dedicated,
+        config_format: McpConfigFormat::Json,
         transports: &[McpTransport::Stdio, McpTransport::Http],
         field_mappings: McpFieldMappings {
             http_url_field: "url",
             sse_url_field: None,
             requires_type_field: false, // Cursor auto-infers transport from fields
             type_values: McpTypeValues::default(),
+            stdio_shape: McpStdioShape::Flat,
         },
         env_syntax: Some(McpEnvSyntax::DollarEnvColon),
     }
@@ -162,12 +172,14 @@ fn windsurf_mcp_spec() -> McpConfigSpec {
             ".codeium/windsurf/mcp_config.json",
         )),
         embedding: McpConfigEmbedding::Dedicated,
+        config_format: McpConfigFormat::Json,
         transports: &[McpTransport::Stdio, McpTransport::Http, McpTransport::Sse],
         field_mappings: McpFieldMappings {
             http_url_field: "serverUrl", // Windsurf uses "serverUrl"
             sse_url_field: None,
             requires_type_field: false,
             type_values: McpTypeValues::default(),
+            stdio_shape: McpStdioShape::Flat,
         },
         env_syntax: Some(McpEnvSyntax::DollarEnvColon),
     }
@@ -187,6 +199,7 @@ fn vscode_mcp_spec() -> McpConfigSpec {
             windows: "AppData/Roaming/Code/User/mcp.json",
         }),
         embedding: McpConfigEmbedding::Dedicated,
+        config_format: McpConfigFormat::Json,
         transports: &[McpTransport::Stdio, McpTransport::Http],
         field_mappings: McpFieldMappings {
             http_url_field: "url",
@@ -197,6 +210,7 @@ fn vscode_mcp_spec() -> McpConfigSpec {
                 http: Some("http"),
                 sse: None, // SSE deprecated in VS Code
             },
+            stdio_shape: McpStdioShape::Flat,
         },
         env_syntax: Some(McpEnvSyntax::VsCodeInput),
     }
@@ -223,12 +237,14 @@ fn antigravity_mcp_spec() -> McpConfigSpec {
             ".gemini/antigravity/mcp_config.json",
         )),
         embedding: McpConfigEmbedding::Dedicated,
+        config_format: McpConfigFormat::Json,
         transports: &[McpTransport::Stdio, McpTransport::Http, McpTransport::Sse],
         field_mappings: McpFieldMappings {
             http_url_field: "serverUrl", // Antigravity uses "serverUrl"
             sse_url_field: None,
             requires_type_field: false,
             type_values: McpTypeValues::default(),
+            stdio_shape: McpStdioShape::Flat,
         },
         env_syntax: None, // Antigravity does not support env var interpolation
     }
@@ -244,6 +260,7 @@ fn jetbrains_mcp_spec() -> McpConfigSpec {
         project_path: Some(".junie/mcp/mcp.json"),
         user_path: Some(McpUserPath::HomeRelative(".junie/mcp/mcp.json")),
         embedding: McpConfigEmbedding::Dedicated,
+        config_format: McpConfigFormat::Json,
         transports: &[McpTransport::Stdio, McpTransport::Http, McpTransport::Sse],
         field_mappings: McpFieldMappings {
             http_url_field: "url",
@@ -254,6 +271,7 @@ fn jetbrains_mcp_spec() -> McpConfigSpec {
                 http: Some("url"),      // JetBrains uses "url" not "http"
                 sse: Some("sse"),
             },
+            stdio_shape: McpStdioShape::Flat,
         },
         env_syntax: None,
     }
@@ -273,12 +291,14 @@ fn zed_mcp_spec() -> McpConfigSpec {
             windows: ".config/zed/settings.json", // Zed on Windows uses same layout
         }),
         embedding: McpConfigEmbedding::Nested, // Part of larger settings.json
+        config_format: McpConfigFormat::Json,
         transports: &[McpTransport::Stdio, McpTransport::Http],
         field_mappings: McpFieldMappings {
             http_url_field: "url",
             sse_url_field: None,
             requires_type_field: false,
             type_values: McpTypeValues::default(),
+            stdio_shape: McpStdioShape::Nested, // command: { path, args, env }
         },
         env_syntax: None,
     }
@@ -297,12 +317,14 @@ fn cline_mcp_spec() -> McpConfigSpec {
             filename: "cline_mcp_settings.json",
         }),
         embedding: McpConfigEmbedding::Dedicated,
+        config_format: McpConfigFormat::Json,
         transports: &[McpTransport::Stdio, McpTransport::Http, McpTransport::Sse],
         field_mappings: McpFieldMappings {
             http_url_field: "url",
             sse_url_field: None,
             requires_type_field: false,
             type_values: McpTypeValues::default(),
+            stdio_shape: McpStdioShape::Flat,
         },
         env_syntax: Some(McpEnvSyntax::DollarEnvColon), // ${env:VAR} in args array
     }
@@ -321,6 +343,7 @@ fn roo_mcp_spec() -> McpConfigSpec {
             filename: "cline_mcp_settings.json",
         }),
         embedding: McpConfigEmbedding::Dedicated,
+        config_format: McpConfigFormat::Json,
         transports: &[McpTransport::Stdio, McpTransport::Http, McpTransport::Sse],
         field_mappings: McpFieldMappings {
             http_url_field: "url",
@@ -331,6 +354,7 @@ fn roo_mcp_spec() -> McpConfigSpec {
                 http: Some("streamable-http"),
                 sse: Some("sse"),
             },
+            stdio_shape: McpStdioShape::Flat,
         },
         env_syntax: Some(McpEnvSyntax::DollarEnvColon),
     }
@@ -350,6 +374,7 @@ fn amazonq_mcp_spec() -> McpConfigSpec {
         project_path: Some(".amazonq/mcp.json"),
         user_path: Some(McpUserPath::HomeRelative(".aws/amazonq/mcp.json")),
         embedding: McpConfigEmbedding::Dedicated,
+        config_format: McpConfigFormat::Json,
         transports: &[McpTransport::Stdio, McpTransport::Http],
         field_mappings: McpFieldMappings {
             http_url_field: "url",
@@ -360,6 +385,32 @@ fn amazonq_mcp_spec() -> McpConfigSpec {
                 http: Some("http"),
                 sse: None,
             },
+            stdio_shape: McpStdioShape::Flat,
+        },
+        env_syntax: None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 14. OpenAI Codex CLI
+// ---------------------------------------------------------------------------
+
+fn codex_mcp_spec() -> McpConfigSpec {
+    // Servers are `[mcp_servers.<name>]` tables in ~/.codex/config.toml,
+    // next to the rest of the Codex settings
+    McpConfigSpec {
+        servers_key: "mcp_servers",
+        project_path: None,
+        user_path: Some(McpUserPath::HomeRelative(".codex/config.toml")),
+        embedding: McpConfigEmbedding::Nested,
+        config_format: McpConfigFormat::Toml,
+        transports: &[McpTransport::Stdio, McpTransport::Http],
+        field_mappings: McpFieldMappings {
+            http_url_field: "url",
+            sse_url_field: None,
+            requires_type_field: false,
+            type_values: McpTypeValues::default(),
+            stdio_shape: McpStdioShape::Flat,
         },
         env_syntax: None,
     }
@@ -413,6 +464,14 @@ mod tests {
         assert_eq!(spec.servers_key, "context_servers");
     }
 
+    #[test]
+    fn test_codex_uses_toml_user_config() {
+        let spec = mcp_config_spec("codex").unwrap();
+        assert_eq!(spec.servers_key, "mcp_servers");
+        assert_eq!(spec.config_format, McpConfigFormat::Toml);
+        assert!(spec.project_path.is_none());
+    }
+
     #[test]
     fn test_claude_desktop_is_separate_from_claude() {
         let code = mcp_config_spec("claude").unwrap();
@@ -466,9 +525,9 @@ mod tests {
 
     #[test]
     fn test_mcp_capable_tools_count() {
-        // 12 original tools with MCP support + claude_desktop = 13
+        // 12 original tools with MCP support + claude_desktop + codex = 14
         // (copilot shares VS Code config but is a separate entry)
-        assert_eq!(MCP_CAPABLE_TOOLS.len(), 13);
+        assert_eq!(MCP_CAPABLE_TOOLS.len(), 14);
     }
 
     #[test]
@@ -3,8 +3,15 @@
 //! Each tool has its own conventions for field names, type values, and env var syntax.
 //! This module converts a tool-agnostic `McpServerConfig` into the JSON structure
 //! that each tool expects, and vice versa.
+//!
+//! Zed ([`McpStdioShape::Nested`]) wraps a stdio server's command line in an
+//! object, and has no working directory setting:
+//!
+//! ```json
+//! { "command": { "path": "npx", "args": ["-y", "server"], "env": { "KEY": "value" } } }
+//! ```
 
-use repo_meta::schema::{McpConfigSpec, McpServerConfig, McpTransportConfig};
+use repo_meta::schema::{McpConfigSpec, McpServerConfig, McpStdioShape, McpTransportConfig};
 use serde_json::{Map, Value, json};
 use std::collections::BTreeMap;
 
@@ -14,6 +21,7 @@ use std::collections::BTreeMap;
 /// - Field naming: `url` vs `serverUrl` vs `httpUrl`
 /// - Type field: present/absent, and values like `"stdio"` vs `"command"`
 /// - Auto-approve: mapped to tool-specific fields
+/// - Stdio shape: flat fields, or Zed's nested `command` object (which drops `cwd`)
 ///
 /// `auto_approve` is intentionally **not** emitted here because each tool
 /// uses a different field name (`alwaysAllow`, `autoApprove`, etc.).
@@ -22,11 +30,22 @@ pub fn to_tool_json(config: &McpServerConfig, spec: &McpConfigSpec) -> Value {
     let fm = &spec.field_mappings;
 
     match &config.transport {
+        McpTransportConfig::Stdio { command, args, .. }
+            if fm.stdio_shape == McpStdioShape::Nested =>
+        {
+            let mut cmd = Map::new();
+            cmd.insert("path".into(), json!(command));
+            cmd.insert("args".into(), json!(args));
+            cmd.insert("env".into(), json!(config.env.clone().unwrap_or_default()));
+            obj.insert("command".into(), Value::Object(cmd));
+            return Value::Object(obj);
+        }
         McpTransportConfig::Stdio { command, args, cwd } => {
             if fm.requires_type_field
-                && let Some(type_val) = fm.type_values.stdio {
-                    obj.insert("type".into(), json!(type_val));
-                }
+                && let Some(type_val) = fm.type_values.stdio
+            {
+                obj.insert("type".into(), json!(type_val));
+            }
             obj.insert("command".into(), json!(command));
             if !args.is_empty() {
                 obj.insert("args".into(), json!(args));
@@ -80,6 +99,12 @@ pub fn from_tool_json(value: &Value, spec: &McpConfigSpec) -> Option<McpServerCo
     let obj = value.as_object()?;
     let fm = &spec.field_mappings;
 
+    if let Some(cmd) = obj.get("command").and_then(|v| v.as_object())
+        && fm.stdio_shape == McpStdioShape::Nested
+    {
+        return from_nested_command(cmd);
+    }
+
     // Determine transport type.
     let transport = if obj.contains_key("command") {
         // Stdio transport: presence of "command" is the distinguishing signal.
@@ -149,6 +174,41 @@ pub fn from_tool_json(value: &Value, spec: &McpConfigSpec) -> Option<McpServerCo
         transport,
         env,
         auto_approve: false,
+        expand_env: false,
+    })
+}
+
+/// Parse a nested `{ "path", "args", "env" }` command object.
+fn from_nested_command(cmd: &Map<String, Value>) -> Option<McpServerConfig> {
+    let command = cmd.get("path")?.as_str()?.to_string();
+    let args = cmd
+        .get("args")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    let env = cmd
+        .get("env")
+        .and_then(|v| v.as_object())
+        .map(|map| {
+            map.iter()
+                .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                .collect::<BTreeMap<String, String>>()
+        })
+        .filter(|m| !m.is_empty());
+
+    Some(McpServerConfig {
+        transport: McpTransportConfig::Stdio {
+            command,
+            args,
+            cwd: None,
+        },
+        env,
+        auto_approve: false,
+        expand_env: false,
     })
 }
 
@@ -225,6 +285,7 @@ mod tests {
             },
             env: Some(BTreeMap::from([("KEY".into(), "value".into())])),
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["type"], "stdio");
@@ -244,6 +305,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["serverUrl"], "https://example.com/mcp");
@@ -261,6 +323,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["httpUrl"], "https://example.com/mcp");
@@ -277,6 +340,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["url"], "https://example.com/sse");
@@ -294,6 +358,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["type"], "command"); // JetBrains uses "command" not "stdio"
@@ -313,6 +378,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["type"], "streamable-http");
@@ -332,6 +398,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert!(json.get("type").is_none());
@@ -349,6 +416,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["type"], "http");
@@ -367,6 +435,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["type"], "stdio");
@@ -388,6 +457,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["httpUrl"], "https://example.com/mcp");
@@ -405,6 +475,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["type"], "sse");
@@ -423,6 +494,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert!(json.get("type").is_none());
@@ -441,6 +513,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert!(json.get("type").is_none());
@@ -459,6 +532,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["type"], "http");
@@ -477,6 +551,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["cwd"], "/home/user/project");
@@ -494,6 +569,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert_eq!(json["type"], "command");
@@ -515,6 +591,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert!(json.get("env").is_none());
@@ -531,6 +608,7 @@ mod tests {
             },
             env: Some(BTreeMap::new()),
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert!(json.get("env").is_none());
@@ -551,6 +629,7 @@ mod tests {
             },
             env: None,
             auto_approve: true,
+            expand_env: false,
         };
         let json = to_tool_json(&config, &spec);
         assert!(json.get("auto_approve").is_none());
@@ -586,6 +665,7 @@ mod tests {
             },
             env: Some(BTreeMap::from([("KEY".into(), "val".into())])),
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&original, &spec);
         let roundtripped = from_tool_json(&json, &spec).unwrap();
@@ -615,6 +695,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&original, &spec);
         let roundtripped = from_tool_json(&json, &spec).unwrap();
@@ -641,6 +722,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&original, &spec);
         assert_eq!(json["url"], "https://example.com/sse");
@@ -665,6 +747,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&original, &spec);
         assert_eq!(json["httpUrl"], "https://example.com/http");
@@ -692,6 +775,7 @@ mod tests {
                 ("BAZ".into(), "qux".into()),
             ])),
             auto_approve: false,
+            expand_env: false,
         };
         let json = to_tool_json(&original, &spec);
         assert_eq!(json["type"], "stdio");
@@ -714,6 +798,73 @@ mod tests {
         assert_eq!(env["FOO"], "bar");
     }
 
+    // Test Zed nests command, args and env in a "command" object
+    #[test]
+    fn test_to_tool_json_zed_stdio_nested() {
+        let spec = mcp_config_spec("zed").unwrap();
+        let config = McpServerConfig {
+            transport: McpTransportConfig::Stdio {
+                command: "npx".into(),
+                args: vec!["-y".into(), "some-server".into()],
+                cwd: Some("/ignored".into()),
+            },
+            env: Some(BTreeMap::from([("KEY".into(), "value".into())])),
+            auto_approve: false,
+            expand_env: false,
+        };
+        let json = to_tool_json(&config, &spec);
+        assert_eq!(
+            json,
+            json!({
+                "command": {
+                    "path": "npx",
+                    "args": ["-y", "some-server"],
+                    "env": {"KEY": "value"}
+                }
+            })
+        );
+    }
+
+    // Test roundtrip: to_tool_json -> from_tool_json for a Zed stdio server
+    #[test]
+    fn test_roundtrip_stdio_zed() {
+        let spec = mcp_config_spec("zed").unwrap();
+        let original = McpServerConfig {
+            transport: McpTransportConfig::Stdio {
+                command: "npx".into(),
+                args: vec!["-y".into(), "server".into()],
+                cwd: None,
+            },
+            env: Some(BTreeMap::from([("TOKEN".into(), "abc".into())])),
+            auto_approve: false,
+            expand_env: false,
+        };
+        let json = to_tool_json(&original, &spec);
+        let recovered = from_tool_json(&json, &spec).unwrap();
+        assert_eq!(json!(recovered), json!(original));
+    }
+
+    // Test Zed remote servers keep the flat "url" shape
+    #[test]
+    fn test_roundtrip_http_zed() {
+        let spec = mcp_config_spec("zed").unwrap();
+        let original = McpServerConfig {
+            transport: McpTransportConfig::Http {
+                url: "https://example.com/mcp".into(),
+                headers: None,
+            },
+            env: None,
+            auto_approve: false,
+            expand_env: false,
+        };
+        let json = to_tool_json(&original, &spec);
+        assert_eq!(json, json!({"url": "https://example.com/mcp"}));
+        assert_eq!(
+            json!(from_tool_json(&json, &spec).unwrap()),
+            json!(original)
+        );
+    }
+
     // -----------------------------------------------------------------------
     // from_tool_json error/edge-case tests
     // -----------------------------------------------------------------------
@@ -757,6 +908,7 @@ mod tests {
             },
             env: None,
             auto_approve: false,
+            expand_env: false,
         };
         for slug in MCP_CAPABLE_TOOLS {
             let spec = mcp_config_spec(slug).unwrap();
@@ -765,10 +917,12 @@ mod tests {
                 json.is_object(),
                 "to_tool_json for {slug} must return an object"
             );
-            assert_eq!(
-                json["command"], "test-server",
-                "command field wrong for {slug}"
-            );
+            let command = if *slug == "zed" {
+                &json["command"]["path"]
+            } else {
+                &json["command"]
+            };
+            assert_eq!(command, "test-server", "command field wrong for {slug}");
         }
     }
 }
@@ -6,12 +6,12 @@
 
 use super::{ToolCategory, ToolRegistration};
 use crate::{
-    aider, amazonq, antigravity, claude, claude_desktop, cline, copilot, cursor, gemini, jetbrains,
-    roo, vscode, windsurf, zed,
+    aider, amazonq, antigravity, claude, claude_desktop, cline, codex, copilot, cursor, gemini,
+    jetbrains, roo, vscode, windsurf, zed,
 };
 
 /// Number of built-in tools.
-pub const BUILTIN_COUNT: usize = 14;
+pub const BUILTIN_COUNT: usize = 15;
 
 /// Returns all built-in tool registrations.
 ///
@@ -57,7 +57,7 @@ pub fn builtin_registrations() -> Vec<ToolRegistration> {
             ToolCategory::Ide,
             antigravity::antigravity_integration().definition().clone(),
         ),
-        // CLI Agents (4 tools)
+        // CLI Agents (5 tools)
         ToolRegistration::new(
             "claude",
             "Claude Code",
@@ -76,7 +76,7 @@ pub fn builtin_registrations() -> Vec<ToolRegistration> {
             "aider",
             "Aider",
             ToolCategory::CliAgent,
-            aider::aider_integration().definition().clone(),
+            aider::aider_definition(),
         ),
         ToolRegistration::new(
             "gemini",
@@ -84,6 +84,12 @@ pub fn builtin_registrations() -> Vec<ToolRegistration> {
             ToolCategory::CliAgent,
             gemini::gemini_integration().definition().clone(),
         ),
+        ToolRegistration::new(
+            "codex",
+            "Codex CLI",
+            ToolCategory::CliAgent,
+            codex::codex_integration().definition().clone(),
+        ),
         // Autonomous Agents (2 tools)
         ToolRegistration::new(
             "cline",
@@ -148,6 +154,7 @@ mod tests {
         assert!(slugs.contains("claude_desktop"));
         assert!(slugs.contains("aider"));
         assert!(slugs.contains("gemini"));
+        assert!(slugs.contains("codex"));
 
         // Autonomous
         assert!(slugs.contains("cline"));
@@ -180,7 +187,7 @@ mod tests {
             .count();
 
         assert_eq!(ide_count, 6);
-        assert_eq!(cli_count, 4);
+        assert_eq!(cli_count, 5);
         assert_eq!(auto_count, 2);
         assert_eq!(copilot_count, 2);
     }
@@ -197,4 +204,18 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_editors_have_open_commands() {
+        let regs = builtin_registrations();
+        let openable: HashSet<_> = regs
+            .iter()
+            .filter(|r| r.open_command().is_some_and(|c| c.contains("{path}")))
+            .map(|r| r.slug.as_str())
+            .collect();
+        assert_eq!(
+            openable,
+            HashSet::from(["cursor", "vscode", "windsurf", "zed"])
+        );
+    }
 }
@@ -4,9 +4,11 @@
 //! eliminating the 3-location duplication in the old dispatcher.
 
 mod builtins;
+mod names;
 mod store;
 mod types;
 
 pub use builtins::{BUILTIN_COUNT, builtin_registrations};
+pub use names::{UnknownTool, close_matches, validate_tool_name};
 pub use store::ToolRegistry;
-pub use types::{ToolCategory, ToolRegistration};
+pub use types::{ToolCategory, ToolRegistration, ToolSource};
@@ -0,0 +1,161 @@
+//! Tool name validation
+//!
+//! Unknown tool names are usually typos, so validation suggests the known
+//! names closest to them by edit distance.
+
+use std::fmt;
+
+use super::{ToolCategory, ToolRegistry};
+
+/// Maximum number of suggestions offered for an unknown name.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A tool name that is not in the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownTool {
+    /// The name as given
+    pub name: String,
+    /// Known names close to it, closest first
+    pub suggestions: Vec<String>,
+}
+
+impl fmt::Display for UnknownTool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown tool '{}'", self.name)?;
+        if !self.suggestions.is_empty() {
+            let quoted: Vec<String> = self
+                .suggestions
+                .iter()
+                .map(|s| format!("'{}'", s))
+                .collect();
+            write!(f, ", did you mean {}?", quoted.join(" or "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Check `name` against the `known` tool names.
+///
+/// # Errors
+///
+/// Returns [`UnknownTool`], with the closest known names, if `name` is not
+/// one of them.
+pub fn validate_tool_name<'a>(
+    name: &str,
+    known: impl IntoIterator<Item = &'a str>,
+) -> Result<(), UnknownTool> {
+    let known: Vec<&str> = known.into_iter().collect();
+    if known.contains(&name) {
+        return Ok(());
+    }
+    Err(UnknownTool {
+        name: name.to_string(),
+        suggestions: close_matches(name, known)
+            .into_iter()
+            .map(String::from)
+            .collect(),
+    })
+}
+
+/// The `candidates` close enough to `name` to be what was meant, closest
+/// first.
+///
+/// A candidate is close if it is within one edit per three characters of
+/// `name` (at least one), ignoring case.
+pub fn close_matches<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Vec<&'a str> {
+    let name = name.to_lowercase();
+    let limit = (name.chars().count() / 3).max(1);
+    let mut matches: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(&name, &candidate.to_lowercase()), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .collect();
+    matches.sort();
+    matches.truncate(MAX_SUGGESTIONS);
+    matches
+        .into_iter()
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// Levenshtein distance between `a` and `b`, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+impl ToolCategory {
+    /// All categories, in display order.
+    pub const ALL: [ToolCategory; 4] = [
+        ToolCategory::Ide,
+        ToolCategory::CliAgent,
+        ToolCategory::Autonomous,
+        ToolCategory::Copilot,
+    ];
+
+    /// Human-readable name of the category.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ToolCategory::Ide => "IDE",
+            ToolCategory::CliAgent => "CLI Agent",
+            ToolCategory::Autonomous => "Autonomous Agent",
+            ToolCategory::Copilot => "Copilot",
+        }
+    }
+}
+
+impl ToolRegistry {
+    /// Check `name` against the registered tools.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnknownTool`] if no tool is registered under `name`.
+    pub fn validate_name(&self, name: &str) -> Result<(), UnknownTool> {
+        validate_tool_name(name, self.list())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("cursor", "cursor"), 0);
+        assert_eq!(edit_distance("curosr", "cursor"), 2);
+        assert_eq!(edit_distance("claud", "claude"), 1);
+        assert_eq!(edit_distance("", "zed"), 3);
+    }
+
+    #[test]
+    fn test_validate_name_suggests_close_matches() {
+        let registry = ToolRegistry::with_builtins();
+        assert!(registry.validate_name("cursor").is_ok());
+
+        let err = registry.validate_name("Claud").unwrap_err();
+        assert_eq!(err.suggestions, ["claude"]);
+        assert_eq!(
+            err.to_string(),
+            "Unknown tool 'Claud', did you mean 'claude'?"
+        );
+
+        let err = registry.validate_name("curosr").unwrap_err();
+        assert_eq!(err.suggestions, ["cursor"]);
+
+        let err = registry.validate_name("notepad").unwrap_err();
+        assert!(err.suggestions.is_empty());
+        assert_eq!(err.to_string(), "Unknown tool 'notepad'");
+    }
+}
@@ -99,7 +99,7 @@ impl Default for ToolRegistry {
 mod tests {
     use super::*;
     use repo_meta::schema::{
-        ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+        ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
     };
 
     fn make_def(slug: &str) -> ToolDefinition {
@@ -108,11 +108,16 @@ mod tests {
                 name: slug.to_uppercase(),
                 slug: slug.into(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: format!(".{}", slug),
                 config_type: ConfigType::Text,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities::default(),
             schema_keys: None,
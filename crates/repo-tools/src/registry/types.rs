@@ -17,6 +17,28 @@ pub enum ToolCategory {
     Copilot,
 }
 
+/// Where a tool registration came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolSource {
+    /// Compiled into repo-tools
+    Builtin,
+    /// Loaded from a definition in `.repository/tools/`
+    Repository,
+    /// Loaded from a definition shipped by an installed extension
+    Extension,
+}
+
+impl std::fmt::Display for ToolSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Builtin => write!(f, "builtin"),
+            Self::Repository => write!(f, "repository"),
+            Self::Extension => write!(f, "extension"),
+        }
+    }
+}
+
 /// Complete tool registration containing all metadata and definition.
 #[derive(Debug, Clone)]
 pub struct ToolRegistration {
@@ -30,6 +52,8 @@ pub struct ToolRegistration {
     pub priority: u8,
     /// Full tool definition with capabilities and integration config
     pub definition: ToolDefinition,
+    /// Where the registration came from
+    pub source: ToolSource,
 }
 
 impl ToolRegistration {
@@ -46,6 +70,7 @@ impl ToolRegistration {
             category,
             priority: 50, // Default middle priority
             definition,
+            source: ToolSource::Builtin,
         }
     }
 
@@ -55,6 +80,25 @@ impl ToolRegistration {
         self
     }
 
+    /// Set the source (builder pattern).
+    pub fn with_source(mut self, source: ToolSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// The tool's effective definition, as a `.repository/tools/` file would
+    /// declare it.
+    ///
+    /// Uses the registration's slug and display name, so the result
+    /// describes this registration even where the integration's own
+    /// metadata differs.
+    pub fn to_definition(&self) -> ToolDefinition {
+        let mut definition = self.definition.clone();
+        definition.meta.slug = self.slug.clone();
+        definition.meta.name = self.name.clone();
+        definition
+    }
+
     /// Check if the tool supports custom instructions.
     pub fn supports_instructions(&self) -> bool {
         self.definition.capabilities.supports_custom_instructions
@@ -74,12 +118,19 @@ impl ToolRegistration {
     pub fn has_any_capability(&self) -> bool {
         self.supports_instructions() || self.supports_mcp() || self.supports_rules_directory()
     }
+
+    /// Command template `repo open` uses to launch the tool, if any.
+    pub fn open_command(&self) -> Option<&str> {
+        self.definition.meta.open_command.as_deref()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use repo_meta::schema::{ConfigType, ToolCapabilities, ToolIntegrationConfig, ToolMeta};
+    use repo_meta::schema::{
+        ConfigType, RulesLayout, ToolCapabilities, ToolIntegrationConfig, ToolMeta,
+    };
 
     fn make_def() -> ToolDefinition {
         ToolDefinition {
@@ -87,11 +138,16 @@ mod tests {
                 name: "Test".into(),
                 slug: "test".into(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: ".test".into(),
                 config_type: ConfigType::Text,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities::default(),
             schema_keys: None,
@@ -105,6 +161,16 @@ mod tests {
         assert_eq!(reg.name, "Test Tool");
         assert_eq!(reg.category, ToolCategory::Ide);
         assert_eq!(reg.priority, 50);
+        assert_eq!(reg.source, ToolSource::Builtin);
+    }
+
+    #[test]
+    fn test_to_definition() {
+        let reg = ToolRegistration::new("test", "Test Tool", ToolCategory::Ide, make_def());
+        let def = reg.to_definition();
+        assert_eq!(def.meta.slug, "test");
+        assert_eq!(def.meta.name, "Test Tool");
+        assert_eq!(def.integration.config_path, ".test");
     }
 
     #[test]
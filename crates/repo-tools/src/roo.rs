@@ -6,7 +6,7 @@
 
 use crate::generic::GenericToolIntegration;
 use repo_meta::schema::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
 };
 
 /// Creates a Roo Code integration.
@@ -24,12 +24,17 @@ pub fn roo_integration() -> GenericToolIntegration {
             name: "Roo Code".into(),
             slug: "roo".into(),
             description: Some("Roo Code AI assistant (fork of Cline)".into()),
+            override_builtin: false,
+            open_command: None,
         },
         integration: ToolIntegrationConfig {
             // Primary path is the rules directory
             config_path: ".roo/rules/".into(),
             config_type: ConfigType::Markdown,
             additional_paths: vec![".roomodes".into()],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             supports_custom_instructions: true,
@@ -54,17 +54,14 @@ impl ToolCapabilitySyncer {
         }
 
         // Translate rules and MCP config for this tool
-        let content = CapabilityTranslator::translate_with_mcp(
-            tool,
-            rules,
-            self.mcp_servers.as_ref(),
-        );
+        let content =
+            CapabilityTranslator::translate_with_mcp(tool, rules, self.mcp_servers.as_ref());
         if content.is_empty() {
             return Ok(false);
         }
 
         // Get the appropriate writer
-        let writer = self.writers.get_writer(tool.integration.config_type);
+        let writer = self.writers.resolve(&tool.integration);
 
         // Convert schema keys
         let keys = tool.schema_keys.as_ref().map(SchemaKeys::from);
@@ -109,8 +106,8 @@ impl Default for ToolCapabilitySyncer {
 mod tests {
     use super::*;
     use repo_meta::schema::{
-        ConfigType, RuleContent, RuleMeta, Severity, ToolCapabilities, ToolIntegrationConfig,
-        ToolMeta,
+        ConfigType, RuleContent, RuleMeta, RulesLayout, Severity, ToolCapabilities,
+        ToolIntegrationConfig, ToolMeta,
     };
     use std::fs;
     use tempfile::TempDir;
@@ -121,11 +118,16 @@ mod tests {
                 name: slug.to_uppercase(),
                 slug: slug.into(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: format!(".{}", slug),
                 config_type: ConfigType::Text,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities {
                 supports_custom_instructions: supports_instructions,
@@ -142,6 +144,7 @@ mod tests {
                 id: id.into(),
                 severity: Severity::Mandatory,
                 tags: vec![],
+                requires: vec![],
             },
             content: RuleContent {
                 instruction: format!("{} content", id),
@@ -229,11 +232,16 @@ mod tests {
                 name: slug.to_uppercase(),
                 slug: slug.into(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: format!(".{}/settings.json", slug),
                 config_type: ConfigType::Json,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities {
                 supports_custom_instructions: false,
@@ -35,12 +35,13 @@ impl CapabilityTranslator {
         mcp_servers: Option<&Value>,
     ) -> TranslatedContent {
         let mut content = TranslatedContent::empty();
-        content.format = tool.integration.config_type;
+        content.format = tool.integration.config_type.clone();
 
         // Custom instructions (if supported)
         if tool.capabilities.supports_custom_instructions {
             let rule_content = RuleTranslator::translate(tool, rules);
             content.instructions = rule_content.instructions;
+            content.files = rule_content.files;
         }
 
         // MCP servers (if tool supports MCP and config is provided)
@@ -79,8 +80,8 @@ impl CapabilityTranslator {
 mod tests {
     use super::*;
     use repo_meta::schema::{
-        ConfigType, RuleContent, RuleMeta, Severity, ToolCapabilities, ToolIntegrationConfig,
-        ToolMeta,
+        ConfigType, RuleContent, RuleMeta, RulesLayout, Severity, ToolCapabilities,
+        ToolIntegrationConfig, ToolMeta,
     };
 
     fn make_tool(instructions: bool, mcp: bool, rules_dir: bool) -> ToolDefinition {
@@ -89,11 +90,16 @@ mod tests {
                 name: "Test".into(),
                 slug: "test".into(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: ".test".into(),
                 config_type: ConfigType::Markdown,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities {
                 supports_custom_instructions: instructions,
@@ -110,6 +116,7 @@ mod tests {
                 id: id.into(),
                 severity: Severity::Mandatory,
                 tags: vec![],
+                requires: vec![],
             },
             content: RuleContent {
                 instruction: format!("Rule {} content", id),
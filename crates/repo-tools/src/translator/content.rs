@@ -18,6 +18,17 @@ pub struct TranslatedContent {
     pub mcp_servers: Option<Value>,
     /// Additional data to merge into config
     pub data: HashMap<String, Value>,
+    /// Whole files to write, for tools that take one file per rule
+    pub files: Vec<TranslatedFile>,
+}
+
+/// A complete file produced by translation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranslatedFile {
+    /// File name, relative to the tool's config directory
+    pub name: String,
+    /// Full file content
+    pub content: String,
 }
 
 impl TranslatedContent {
@@ -37,7 +48,19 @@ impl TranslatedContent {
 
     /// Check if this content has anything to write.
     pub fn is_empty(&self) -> bool {
-        self.instructions.is_none() && self.mcp_servers.is_none() && self.data.is_empty()
+        self.instructions.is_none()
+            && self.mcp_servers.is_none()
+            && self.data.is_empty()
+            && self.files.is_empty()
+    }
+
+    /// Create content made of whole files.
+    pub fn with_files(format: ConfigType, files: Vec<TranslatedFile>) -> Self {
+        Self {
+            format,
+            files,
+            ..Default::default()
+        }
     }
 
     /// Add arbitrary data to the content.
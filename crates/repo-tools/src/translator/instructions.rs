@@ -0,0 +1,73 @@
+//! GitHub Copilot path-specific instruction files
+//!
+//! Copilot reads `.github/instructions/*.instructions.md` files: Markdown
+//! with a YAML front matter block selecting the files the instructions
+//! apply to:
+//!
+//! ```text
+//! ---
+//! applyTo: "**/*.py,**/*.pyi"
+//! ---
+//!
+//! Use type hints.
+//! ```
+
+/// File extension of path-specific instruction files.
+pub const INSTRUCTIONS_EXTENSION: &str = ".instructions.md";
+
+/// File name of the instructions file for the rule `rule_id`.
+///
+/// Characters other than alphanumerics, `-` and `_` are replaced with `-`.
+pub fn instructions_file_name(rule_id: &str) -> String {
+    let stem: String = rule_id
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{}{}", stem, INSTRUCTIONS_EXTENSION)
+}
+
+/// Render an instructions document applying `body` to the `apply_to` globs.
+///
+/// The globs are joined into a double-quoted scalar, as Copilot expects a
+/// single comma-separated string.
+pub fn render_instructions(apply_to: &[String], body: &str) -> String {
+    let globs = serde_json::Value::String(apply_to.join(",")).to_string();
+    let mut out = String::from("---\n");
+    out.push_str(&format!("applyTo: {}\n", globs));
+    out.push_str("---\n\n");
+    out.push_str(body.trim());
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name_is_sanitized() {
+        assert_eq!(
+            instructions_file_name("py-style"),
+            "py-style.instructions.md"
+        );
+        assert_eq!(instructions_file_name("../x"), "---x.instructions.md");
+    }
+
+    #[test]
+    fn test_render_joins_globs() {
+        let doc = render_instructions(
+            &["**/*.py".to_string(), "**/*.pyi".to_string()],
+            "Use type hints\n",
+        );
+        assert_eq!(
+            doc,
+            "---\napplyTo: \"**/*.py,**/*.pyi\"\n---\n\nUse type hints\n"
+        );
+    }
+}
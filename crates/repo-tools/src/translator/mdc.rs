@@ -0,0 +1,91 @@
+//! Cursor MDC rule files
+//!
+//! Cursor reads `.cursor/rules/*.mdc` files: Markdown with a YAML front
+//! matter block selecting when the rule is attached:
+//!
+//! ```text
+//! ---
+//! description: "python-style [python]"
+//! globs: "*.py,*.pyi"
+//! alwaysApply: false
+//! ---
+//!
+//! Use type hints.
+//! ```
+//!
+//! A rule with no globs is always applied.
+
+/// File extension of MDC rule files.
+pub const MDC_EXTENSION: &str = ".mdc";
+
+/// Check whether a per-rule file name pattern produces MDC files.
+pub fn is_mdc_pattern(pattern: &str) -> bool {
+    pattern.ends_with(MDC_EXTENSION)
+}
+
+/// File name of the MDC file for the rule `rule_id`.
+///
+/// Characters other than alphanumerics, `-` and `_` are replaced with `-`.
+pub fn mdc_file_name(rule_id: &str) -> String {
+    let stem: String = rule_id
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{}{}", stem, MDC_EXTENSION)
+}
+
+/// Render an MDC document from its front matter fields and body.
+///
+/// String values are emitted as double-quoted scalars so that globs such
+/// as `*.rs` (an alias in plain YAML) stay valid.
+pub fn render_mdc(description: &str, globs: &[String], body: &str) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("description: {}\n", quote(description)));
+    if !globs.is_empty() {
+        out.push_str(&format!("globs: {}\n", quote(&globs.join(","))));
+    }
+    out.push_str(&format!("alwaysApply: {}\n", globs.is_empty()));
+    out.push_str("---\n\n");
+    out.push_str(body.trim());
+    out.push('\n');
+    out
+}
+
+/// Quote a string as a YAML double-quoted scalar.
+///
+/// JSON string syntax is a subset of YAML's double-quoted style.
+fn quote(s: &str) -> String {
+    serde_json::Value::String(s.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_mdc_pattern() {
+        assert!(is_mdc_pattern("{id}.mdc"));
+        assert!(!is_mdc_pattern("{id}.md"));
+    }
+
+    #[test]
+    fn test_mdc_file_name_is_sanitized() {
+        assert_eq!(mdc_file_name("py-style"), "py-style.mdc");
+        assert_eq!(mdc_file_name("a/b"), "a-b.mdc");
+    }
+
+    #[test]
+    fn test_render_without_globs_always_applies() {
+        let doc = render_mdc("style", &[], "Use 4 spaces\n");
+        assert_eq!(
+            doc,
+            "---\ndescription: \"style\"\nalwaysApply: true\n---\n\nUse 4 spaces\n"
+        );
+    }
+}
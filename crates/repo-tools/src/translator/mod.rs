@@ -5,8 +5,17 @@
 
 mod capability;
 mod content;
+mod instructions;
+mod mdc;
 mod rules;
+mod windsurf;
 
 pub use capability::CapabilityTranslator;
-pub use content::TranslatedContent;
+pub use content::{TranslatedContent, TranslatedFile};
+pub use instructions::{INSTRUCTIONS_EXTENSION, instructions_file_name, render_instructions};
+pub use mdc::{MDC_EXTENSION, is_mdc_pattern, mdc_file_name, render_mdc};
 pub use rules::RuleTranslator;
+pub use windsurf::{
+    WINDSURF_RULE_CHAR_LIMIT, WINDSURF_RULES_DIR, WINDSURF_TRIGGER_TAG, WINDSURF_TRIGGERS,
+    render_windsurf_rule, windsurf_rule_file_name, windsurf_trigger,
+};
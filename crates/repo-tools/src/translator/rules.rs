@@ -3,7 +3,9 @@
 //! This module translates rules into tool-specific instruction format,
 //! RESPECTING the tool's declared capabilities.
 
-use super::TranslatedContent;
+use super::mdc::{MDC_EXTENSION, is_mdc_pattern, render_mdc};
+use super::{TranslatedContent, TranslatedFile};
+use repo_meta::DiagnosticLevel;
 use repo_meta::schema::{ConfigType, RuleDefinition, Severity, ToolDefinition};
 
 /// Translates rules into instructions for tools.
@@ -16,6 +18,10 @@ pub struct RuleTranslator;
 impl RuleTranslator {
     /// Translate rules for a specific tool.
     ///
+    /// Tools whose per-rule file pattern ends in `.mdc` (Cursor's rules
+    /// directory) get one [`TranslatedFile`] per rule instead of combined
+    /// instructions; see [`RuleTranslator::translate_mdc`].
+    ///
     /// Returns empty content if:
     /// - The tool doesn't support custom instructions
     /// - No rules are provided
@@ -29,21 +35,57 @@ impl RuleTranslator {
             return TranslatedContent::empty();
         }
 
-        let format = tool.integration.config_type;
-        let instructions = Self::format_rules(rules, format);
+        let format = tool.integration.config_type.clone();
+        if tool
+            .integration
+            .rule_file_pattern()
+            .is_some_and(is_mdc_pattern)
+        {
+            return TranslatedContent::with_files(format, Self::translate_mdc(rules));
+        }
+
+        let instructions = Self::format_rules(rules, &format);
         TranslatedContent::with_instructions(format, instructions)
     }
 
-    /// Format rules into a string.
-    fn format_rules(rules: &[RuleDefinition], format: ConfigType) -> String {
-        // Sort by severity (mandatory first)
-        let mut sorted: Vec<_> = rules.iter().collect();
-        sorted.sort_by_key(|r| match r.meta.severity {
-            Severity::Mandatory => 0,
-            Severity::Suggestion => 1,
-        });
+    /// Translate rules into Cursor MDC files, one `{id}.mdc` per rule.
+    ///
+    /// The front matter `description` is the rule ID followed by its tags,
+    /// and `globs` are the rule's target file patterns. Rules without file
+    /// patterns are marked `alwaysApply: true`.
+    pub fn translate_mdc(rules: &[RuleDefinition]) -> Vec<TranslatedFile> {
+        Self::sorted(rules)
+            .into_iter()
+            .map(|rule| {
+                let description = if rule.meta.tags.is_empty() {
+                    rule.meta.id.clone()
+                } else {
+                    format!("{} [{}]", rule.meta.id, rule.meta.tags.join(", "))
+                };
+                let globs = rule
+                    .targets
+                    .as_ref()
+                    .map(|t| t.file_patterns.as_slice())
+                    .unwrap_or_default();
+
+                TranslatedFile {
+                    name: format!("{}{}", rule.meta.id, MDC_EXTENSION),
+                    content: render_mdc(&description, globs, &rule.content.instruction),
+                }
+            })
+            .collect()
+    }
 
+    /// Sort rules by severity (mandatory first), keeping the given order otherwise.
+    fn sorted(rules: &[RuleDefinition]) -> Vec<&RuleDefinition> {
+        let mut sorted: Vec<_> = rules.iter().collect();
+        sorted.sort_by_key(|r| std::cmp::Reverse(DiagnosticLevel::from(r.meta.severity)));
         sorted
+    }
+
+    /// Format rules into a string.
+    fn format_rules(rules: &[RuleDefinition], format: &ConfigType) -> String {
+        Self::sorted(rules)
             .iter()
             .map(|r| Self::format_rule(r, format))
             .collect::<Vec<_>>()
@@ -51,9 +93,11 @@ impl RuleTranslator {
     }
 
     /// Format a single rule based on config type.
-    fn format_rule(rule: &RuleDefinition, format: ConfigType) -> String {
+    fn format_rule(rule: &RuleDefinition, format: &ConfigType) -> String {
         match format {
-            ConfigType::Markdown | ConfigType::Text => Self::format_markdown(rule),
+            ConfigType::Markdown | ConfigType::Text | ConfigType::Directory { .. } => {
+                Self::format_markdown(rule)
+            }
             _ => rule.content.instruction.clone(),
         }
     }
@@ -104,8 +148,8 @@ impl RuleTranslator {
 mod tests {
     use super::*;
     use repo_meta::schema::{
-        RuleContent, RuleExamples, RuleMeta, RuleTargets, ToolCapabilities, ToolIntegrationConfig,
-        ToolMeta,
+        RuleContent, RuleExamples, RuleMeta, RuleTargets, RulesLayout, ToolCapabilities,
+        ToolIntegrationConfig, ToolMeta,
     };
 
     fn make_tool(supports_instructions: bool) -> ToolDefinition {
@@ -114,11 +158,16 @@ mod tests {
                 name: "Test".into(),
                 slug: "test".into(),
                 description: None,
+                override_builtin: false,
+                open_command: None,
             },
             integration: ToolIntegrationConfig {
                 config_path: ".test".into(),
                 config_type: ConfigType::Markdown,
                 additional_paths: vec![],
+                rules_layout: RulesLayout::Single,
+                rules_filename: None,
+                writer: None,
             },
             capabilities: ToolCapabilities {
                 supports_custom_instructions: supports_instructions,
@@ -135,6 +184,7 @@ mod tests {
                 id: id.into(),
                 severity,
                 tags: vec![],
+                requires: vec![],
             },
             content: RuleContent {
                 instruction: format!("Do {} things", id),
@@ -238,4 +288,44 @@ mod tests {
         assert!(text.contains("*.rs"));
         assert!(text.contains("*.ts"));
     }
+
+    #[test]
+    fn test_mdc_target_for_tagged_rule() {
+        let mut tool = make_tool(true);
+        tool.integration.config_path = ".cursor/rules/".into();
+        tool.integration.config_type = ConfigType::Directory {
+            pattern: "{id}.mdc".into(),
+        };
+
+        let mut tagged = make_rule("python-style", Severity::Mandatory);
+        tagged.meta.tags = vec!["python".into(), "style".into()];
+        tagged.targets = Some(RuleTargets {
+            file_patterns: vec!["*.py".into(), "src/**/*.pyi".into()],
+        });
+        let untargeted = make_rule("general", Severity::Suggestion);
+
+        let content = RuleTranslator::translate(&tool, &[untargeted, tagged]);
+        assert!(content.instructions.is_none());
+        assert_eq!(content.files.len(), 2);
+        assert_eq!(content.files[0].name, "python-style.mdc");
+        assert_eq!(content.files[1].name, "general.mdc");
+
+        let (front_matter, body) = split_front_matter(&content.files[0].content);
+        let yaml: serde_yaml::Value = serde_yaml::from_str(front_matter).unwrap();
+        assert_eq!(yaml["description"], "python-style [python, style]");
+        assert_eq!(yaml["globs"], "*.py,src/**/*.pyi");
+        assert_eq!(yaml["alwaysApply"], false);
+        assert_eq!(body, "Do python-style things\n");
+
+        let (front_matter, _) = split_front_matter(&content.files[1].content);
+        let yaml: serde_yaml::Value = serde_yaml::from_str(front_matter).unwrap();
+        assert_eq!(yaml["alwaysApply"], true);
+        assert!(yaml.get("globs").is_none());
+    }
+
+    fn split_front_matter(doc: &str) -> (&str, &str) {
+        let rest = doc.strip_prefix("---\n").expect("front matter start");
+        let end = rest.find("\n---\n").expect("front matter end");
+        (&rest[..end], rest[end + 5..].trim_start_matches('\n'))
+    }
 }
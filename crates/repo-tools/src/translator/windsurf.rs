@@ -0,0 +1,131 @@
+//! Windsurf rule files
+//!
+//! Windsurf reads `.windsurf/rules/*.md` files: Markdown with a YAML front
+//! matter block selecting how the rule is activated:
+//!
+//! ```text
+//! ---
+//! trigger: glob
+//! globs: "*.py,*.pyi"
+//! ---
+//!
+//! Use type hints.
+//! ```
+//!
+//! A rule with no globs is always on. Windsurf ignores content beyond
+//! [`WINDSURF_RULE_CHAR_LIMIT`] characters in a rule file.
+
+/// Directory Windsurf reads rule files from, relative to the repository root.
+pub const WINDSURF_RULES_DIR: &str = ".windsurf/rules/";
+
+/// Maximum number of characters Windsurf reads from a rule file.
+pub const WINDSURF_RULE_CHAR_LIMIT: usize = 6000;
+
+/// Activation modes Windsurf accepts as `trigger`.
+pub const WINDSURF_TRIGGERS: &[&str] = &["always_on", "manual", "model_decision", "glob"];
+
+/// Prefix of the rule tag overriding a rule's Windsurf activation mode,
+/// e.g. `trigger:model_decision`.
+pub const WINDSURF_TRIGGER_TAG: &str = "trigger:";
+
+/// File name of the Windsurf rule file for the rule `rule_id`.
+///
+/// Characters other than alphanumerics, `-` and `_` are replaced with `-`.
+pub fn windsurf_rule_file_name(rule_id: &str) -> String {
+    let stem: String = rule_id
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{}.md", stem)
+}
+
+/// The activation mode selected by a `trigger:<mode>` tag, if any.
+///
+/// Tags naming a mode Windsurf does not know are ignored.
+pub fn windsurf_trigger(tags: &[String]) -> Option<&str> {
+    tags.iter()
+        .filter_map(|tag| tag.strip_prefix(WINDSURF_TRIGGER_TAG))
+        .find(|mode| WINDSURF_TRIGGERS.contains(mode))
+}
+
+/// Render a Windsurf rule document.
+///
+/// Without an explicit `trigger`, rules with globs are activated by them
+/// and other rules are always on. `description` is emitted for
+/// `model_decision` rules, which Windsurf activates by their description.
+pub fn render_windsurf_rule(
+    trigger: Option<&str>,
+    description: &str,
+    globs: &[String],
+    body: &str,
+) -> String {
+    let trigger = trigger.unwrap_or(if globs.is_empty() {
+        "always_on"
+    } else {
+        "glob"
+    });
+    let mut out = String::from("---\n");
+    out.push_str(&format!("trigger: {}\n", trigger));
+    if trigger == "model_decision" {
+        out.push_str(&format!("description: {}\n", quote(description)));
+    }
+    if trigger == "glob" && !globs.is_empty() {
+        out.push_str(&format!("globs: {}\n", quote(&globs.join(","))));
+    }
+    out.push_str("---\n\n");
+    out.push_str(body.trim());
+    out.push('\n');
+    out
+}
+
+/// Quote a string as a YAML double-quoted scalar.
+fn quote(s: &str) -> String {
+    serde_json::Value::String(s.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name_is_sanitized() {
+        assert_eq!(windsurf_rule_file_name("py-style"), "py-style.md");
+        assert_eq!(windsurf_rule_file_name("a/b"), "a-b.md");
+    }
+
+    #[test]
+    fn test_render_defaults_to_always_on() {
+        let doc = render_windsurf_rule(None, "style", &[], "Use 4 spaces\n");
+        assert_eq!(doc, "---\ntrigger: always_on\n---\n\nUse 4 spaces\n");
+    }
+
+    #[test]
+    fn test_render_with_globs_and_trigger_override() {
+        let globs = vec!["*.py".to_string()];
+        assert_eq!(
+            render_windsurf_rule(None, "py", &globs, "Use type hints"),
+            "---\ntrigger: glob\nglobs: \"*.py\"\n---\n\nUse type hints\n"
+        );
+        assert_eq!(
+            render_windsurf_rule(Some("model_decision"), "py", &globs, "Use type hints"),
+            "---\ntrigger: model_decision\ndescription: \"py\"\n---\n\nUse type hints\n"
+        );
+    }
+
+    #[test]
+    fn test_trigger_tag() {
+        let tags = vec![
+            "python".to_string(),
+            "trigger:sometimes".to_string(),
+            "trigger:manual".to_string(),
+        ];
+        assert_eq!(windsurf_trigger(&tags), Some("manual"));
+        assert_eq!(windsurf_trigger(&tags[..2]), None);
+    }
+}
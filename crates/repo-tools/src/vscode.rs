@@ -1,16 +1,19 @@
 //! VSCode integration for Repository Manager.
 //!
 //! Manages `.vscode/settings.json` to configure Python interpreter paths
-//! and other workspace settings.
+//! and other workspace settings. The file is JSONC: comments and trailing
+//! commas are accepted and kept when settings are written.
 
 use crate::error::Result;
-use crate::integration::{ConfigLocation, ConfigType, Rule, SyncContext, ToolIntegration};
-use repo_fs::{NormalizedPath, io};
+use crate::integration::{
+    ConfigLocation, ConfigType, Rule, SyncContext, ToolIntegration, validate_config,
+};
+use crate::writer::update_json_file;
 use repo_meta::schema::{
-    ConfigType as SchemaConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig,
-    ToolMeta, ToolSchemaKeys,
+    ConfigType as SchemaConfigType, RulesLayout, ToolCapabilities, ToolDefinition,
+    ToolIntegrationConfig, ToolMeta, ToolSchemaKeys,
 };
-use serde_json::{Value, json};
+use serde_json::json;
 
 /// Returns the ToolDefinition for VS Code.
 ///
@@ -22,11 +25,16 @@ pub fn vscode_definition() -> ToolDefinition {
             name: "VS Code".into(),
             slug: "vscode".into(),
             description: Some("Visual Studio Code IDE".into()),
+            override_builtin: false,
+            open_command: Some("code {path}".into()),
         },
         integration: ToolIntegrationConfig {
             config_path: ".vscode/settings.json".into(),
             config_type: SchemaConfigType::Json,
             additional_paths: vec![],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             // VSCode itself doesn't support custom instructions
@@ -55,24 +63,6 @@ impl VSCodeIntegration {
     pub fn new() -> Self {
         Self
     }
-
-    /// Load existing settings.json or create empty object.
-    fn load_settings(path: &NormalizedPath) -> Result<Value> {
-        if path.exists() {
-            let content = io::read_text(path)?;
-            let settings: Value = serde_json::from_str(&content)?;
-            Ok(settings)
-        } else {
-            Ok(json!({}))
-        }
-    }
-
-    /// Save settings to JSON file with pretty formatting.
-    fn save_settings(path: &NormalizedPath, settings: &Value) -> Result<()> {
-        let content = serde_json::to_string_pretty(settings)?;
-        io::write_text(path, &content)?;
-        Ok(())
-    }
 }
 
 impl ToolIntegration for VSCodeIntegration {
@@ -90,29 +80,33 @@ impl ToolIntegration for VSCodeIntegration {
     fn sync(&self, context: &SyncContext, _rules: &[Rule]) -> Result<()> {
         let settings_path = context.root.join(".vscode/settings.json");
 
-        // Load existing settings or create empty
-        let mut settings = Self::load_settings(&settings_path)?;
-
-        // Ensure settings is an object
-        if !settings.is_object() {
-            settings = json!({});
-        }
-
         // Set python interpreter path if provided
+        let mut updates = Vec::new();
         if let Some(ref python_path) = context.python_path {
-            settings["python.defaultInterpreterPath"] = json!(python_path.as_str());
+            updates.push((
+                "python.defaultInterpreterPath".to_string(),
+                json!(python_path.as_str()),
+            ));
         }
 
-        // Save settings
-        Self::save_settings(&settings_path, &settings)?;
+        update_json_file(&settings_path, &updates)
+    }
 
-        Ok(())
+    fn validate_output(&self, content: &str) -> Result<()> {
+        validate_config(
+            self.name(),
+            ".vscode/settings.json",
+            &ConfigType::Json,
+            content,
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use repo_fs::NormalizedPath;
+    use serde_json::Value;
     use std::fs;
     use tempfile::TempDir;
 
@@ -188,4 +182,31 @@ mod tests {
         // Check new setting added
         assert_eq!(settings["python.defaultInterpreterPath"], "/my/python");
     }
+
+    #[test]
+    fn test_sync_keeps_comments() {
+        let temp_dir = TempDir::new().unwrap();
+        let vscode_dir = temp_dir.path().join(".vscode");
+        fs::create_dir_all(&vscode_dir).unwrap();
+
+        let existing = "{\n    // Editor\n    \"editor.fontSize\": 14, /* px */\n    \"files.exclude\": {\n        \"**/.git\": true, // hide\n    },\n}\n";
+        fs::write(vscode_dir.join("settings.json"), existing).unwrap();
+
+        let root = NormalizedPath::new(temp_dir.path());
+        let context = SyncContext::new(root).with_python(NormalizedPath::new("/my/python"));
+        let integration = VSCodeIntegration::new();
+        integration.sync(&context, &[]).unwrap();
+        // A second sync only replaces the value it owns
+        integration.sync(&context, &[]).unwrap();
+
+        let content = fs::read_to_string(vscode_dir.join("settings.json")).unwrap();
+        assert_eq!(
+            content,
+            existing.replace(
+                "    },\n}",
+                "    },\n    \"python.defaultInterpreterPath\": \"/my/python\",\n}"
+            )
+        );
+        integration.validate_output(&content).unwrap();
+    }
 }
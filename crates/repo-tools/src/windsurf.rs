@@ -1,27 +1,68 @@
 //! Windsurf integration for Repository Manager.
 //!
-//! Manages `.windsurfrules` file using managed blocks for rule content.
+//! Manages either the legacy `.windsurfrules` file, using managed blocks for
+//! rule content, or the `.windsurf/rules/` directory with one Markdown file
+//! per rule. See [`WindsurfMode`].
 
 use crate::generic::GenericToolIntegration;
+use crate::integration::Rule;
+use crate::translator::{WINDSURF_RULES_DIR, render_windsurf_rule};
 use repo_meta::schema::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
 };
+use serde::{Deserialize, Serialize};
+
+/// Where Windsurf rules are written.
+///
+/// Selected by `format` in the `[tool_settings.windsurf]` table of
+/// `config.toml`:
+///
+/// ```toml
+/// [tool_settings.windsurf]
+/// format = "directory"
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WindsurfMode {
+    /// A single `.windsurfrules` file with managed blocks
+    #[default]
+    Legacy,
+    /// One `.windsurf/rules/{id}.md` file per rule, with YAML front matter
+    Directory,
+}
 
 /// Creates a Windsurf integration.
 ///
 /// Returns a GenericToolIntegration configured for Windsurf's `.windsurfrules` file.
 /// Uses raw content mode (no headers) for backward compatibility.
 pub fn windsurf_integration() -> GenericToolIntegration {
+    windsurf_integration_with_mode(WindsurfMode::Legacy)
+}
+
+/// Creates a Windsurf integration writing rules in the given mode.
+pub fn windsurf_integration_with_mode(mode: WindsurfMode) -> GenericToolIntegration {
+    match mode {
+        WindsurfMode::Legacy => legacy_integration(),
+        WindsurfMode::Directory => directory_integration(),
+    }
+}
+
+fn legacy_integration() -> GenericToolIntegration {
     GenericToolIntegration::new(ToolDefinition {
         meta: ToolMeta {
             name: "Windsurf".into(),
             slug: "windsurf".into(),
             description: Some("Windsurf AI IDE".into()),
+            override_builtin: false,
+            open_command: Some("windsurf {path}".into()),
         },
         integration: ToolIntegrationConfig {
             config_path: ".windsurfrules".into(),
             config_type: ConfigType::Text,
             additional_paths: vec![],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             supports_custom_instructions: true,
@@ -33,6 +74,40 @@ pub fn windsurf_integration() -> GenericToolIntegration {
     .with_raw_content(true)
 }
 
+fn directory_integration() -> GenericToolIntegration {
+    GenericToolIntegration::new(ToolDefinition {
+        meta: ToolMeta {
+            name: "Windsurf".into(),
+            slug: "windsurf".into(),
+            description: Some("Windsurf AI IDE".into()),
+            override_builtin: false,
+            open_command: Some("windsurf {path}".into()),
+        },
+        integration: ToolIntegrationConfig {
+            config_path: WINDSURF_RULES_DIR.into(),
+            config_type: ConfigType::Directory {
+                pattern: "{id}.md".into(),
+            },
+            additional_paths: vec![],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
+        },
+        capabilities: ToolCapabilities {
+            supports_custom_instructions: true,
+            supports_mcp: true,
+            supports_rules_directory: true,
+        },
+        schema_keys: None,
+    })
+    .with_rule_renderer(render_rule)
+}
+
+/// Render a rule file with activation-mode front matter.
+fn render_rule(rule: &Rule) -> String {
+    render_windsurf_rule(None, &rule.id, &rule.paths, &rule.content)
+}
+
 /// Type alias for backward compatibility.
 ///
 /// Prefer using `windsurf_integration()` factory function for new code.
@@ -79,10 +154,12 @@ mod tests {
             Rule {
                 id: "rule-1".to_string(),
                 content: "First rule content".to_string(),
+                paths: vec![],
             },
             Rule {
                 id: "rule-2".to_string(),
                 content: "Second rule content".to_string(),
+                paths: vec![],
             },
         ];
 
@@ -111,6 +188,7 @@ mod tests {
         let rules = vec![Rule {
             id: "my-rule".to_string(),
             content: "Original content".to_string(),
+            paths: vec![],
         }];
 
         let integration = windsurf_integration();
@@ -120,6 +198,7 @@ mod tests {
         let rules = vec![Rule {
             id: "my-rule".to_string(),
             content: "Updated content".to_string(),
+            paths: vec![],
         }];
         integration.sync(&context, &rules).unwrap();
 
@@ -146,6 +225,7 @@ mod tests {
         let rules = vec![Rule {
             id: "auto-rule".to_string(),
             content: "Automated rule".to_string(),
+            paths: vec![],
         }];
 
         let integration = windsurf_integration();
@@ -161,4 +241,32 @@ mod tests {
         assert!(content.contains("<!-- repo:block:auto-rule -->"));
         assert!(content.contains("Automated rule"));
     }
+
+    #[test]
+    fn test_directory_mode_writes_rule_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp_dir.path());
+
+        let integration = windsurf_integration_with_mode(WindsurfMode::Directory);
+        assert_eq!(integration.config_locations()[0].path, ".windsurf/rules/");
+
+        let context = SyncContext::new(root);
+        let rule = |id: &str| Rule {
+            id: id.to_string(),
+            content: "Use 4 spaces".to_string(),
+            paths: vec![],
+        };
+        integration
+            .sync(&context, &[rule("style"), rule("naming")])
+            .unwrap();
+
+        assert!(!temp_dir.path().join(".windsurfrules").exists());
+        let content = fs::read_to_string(temp_dir.path().join(".windsurf/rules/style.md")).unwrap();
+        assert_eq!(content, "---\ntrigger: always_on\n---\n\nUse 4 spaces\n");
+
+        // Files of removed rules are cleaned up
+        integration.sync(&context, &[rule("style")]).unwrap();
+        assert!(temp_dir.path().join(".windsurf/rules/style.md").exists());
+        assert!(!temp_dir.path().join(".windsurf/rules/naming.md").exists());
+    }
 }
@@ -1,69 +1,172 @@
 //! JSON config writer with semantic merge
 //!
 //! This writer preserves existing JSON keys while updating managed fields.
+//! Existing files may be JSONC; their comments are kept.
 
 use super::{ConfigWriter, SchemaKeys};
 use crate::error::Result;
 use crate::translator::TranslatedContent;
+use repo_content::jsonc;
+use repo_content::path::PathSegment;
 use repo_fs::{NormalizedPath, io};
-use serde_json::{Value, json};
+use serde_json::{Map, Value, json};
 
 /// JSON config writer that semantically merges content.
 ///
 /// Features:
-/// - Preserves existing keys in the JSON file
+/// - Preserves existing keys, comments and formatting in the JSON file
 /// - Uses schema_keys to place instructions and MCP config
+/// - Can place instructions at a nested key path instead
 /// - Merges additional data from TranslatedContent
-pub struct JsonWriter;
+pub struct JsonWriter {
+    /// Key path receiving the instructions, overriding `instruction_key`
+    target_path: Option<Vec<String>>,
+}
 
 impl JsonWriter {
     /// Create a new JSON writer.
     pub fn new() -> Self {
-        Self
+        Self { target_path: None }
     }
 
-    /// Parse existing JSON file or return empty object.
+    /// Create a JSON writer that places instructions at a nested key path.
     ///
-    /// Returns an empty object if the file does not exist. Propagates I/O
-    /// and parse errors so callers can distinguish missing files from corrupted ones.
-    fn parse_existing(path: &NormalizedPath) -> crate::error::Result<Value> {
-        if !path.exists() {
-            return Ok(json!({}));
+    /// `path` is dotted, e.g. `"ai.rules"`. Objects missing along the path
+    /// are created; the other keys of those already there are kept.
+    pub fn with_target_path(path: &str) -> Self {
+        let keys = path
+            .split('.')
+            .filter(|key| !key.is_empty())
+            .map(String::from)
+            .collect::<Vec<_>>();
+        Self {
+            target_path: (!keys.is_empty()).then_some(keys),
         }
-        let content = io::read_text(path).map_err(|e| {
-            tracing::warn!("Failed to read existing JSON config at {}: {}", path.as_str(), e);
-            e
-        })?;
-        let value = serde_json::from_str(&content)?;
-        Ok(value)
     }
 
-    /// Merge content into existing JSON.
-    fn merge(existing: &mut Value, content: &TranslatedContent, keys: Option<&SchemaKeys>) {
-        let obj = match existing.as_object_mut() {
-            Some(o) => o,
-            None => return,
-        };
-
-        // Merge instructions if key specified
-        if let (Some(instructions), Some(k)) = (&content.instructions, keys)
-            && let Some(ref key) = k.instruction_key
-        {
-            obj.insert(key.clone(), json!(instructions));
+    /// Collect the key paths to write from the translated content.
+    fn updates(
+        &self,
+        content: &TranslatedContent,
+        keys: Option<&SchemaKeys>,
+    ) -> Vec<(Vec<String>, Value)> {
+        let mut updates = Vec::new();
+
+        // Merge instructions at the target path or instruction key
+        if let Some(instructions) = &content.instructions {
+            let key = keys.and_then(|k| k.instruction_key.clone());
+            if let Some(path) = self.target_path.clone().or(key.map(|key| vec![key])) {
+                updates.push((path, json!(instructions)));
+            }
         }
 
         // Merge MCP servers if key specified
         if let (Some(mcp), Some(k)) = (&content.mcp_servers, keys)
             && let Some(ref key) = k.mcp_key
         {
-            obj.insert(key.clone(), mcp.clone());
+            updates.push((vec![key.clone()], mcp.clone()));
         }
 
         // Merge additional data
         for (key, value) in &content.data {
-            obj.insert(key.clone(), value.clone());
+            updates.push((vec![key.clone()], value.clone()));
+        }
+        updates
+    }
+}
+
+/// Set top-level keys in a JSON config file, creating it if needed.
+///
+/// Existing files are read as JSONC and each key is spliced into the
+/// original text, so comments, trailing commas, key order and formatting
+/// outside the written values survive. A file whose root is not an object
+/// is replaced. Propagates I/O and parse errors so a corrupted file is
+/// never silently overwritten.
+pub(crate) fn update_json_file(path: &NormalizedPath, updates: &[(String, Value)]) -> Result<()> {
+    let updates: Vec<(Vec<String>, Value)> = updates
+        .iter()
+        .map(|(key, value)| (vec![key.clone()], value.clone()))
+        .collect();
+    update_json_paths(path, &updates)
+}
+
+/// Set values at key paths in a JSON config file, creating it if needed.
+///
+/// Like [`update_json_file`], but each value goes to a path of nested
+/// keys; objects missing along the path are created.
+fn update_json_paths(path: &NormalizedPath, updates: &[(Vec<String>, Value)]) -> Result<()> {
+    if path.exists() {
+        let mut source = io::read_text(path).map_err(|e| {
+            tracing::warn!(
+                "Failed to read existing JSON config at {}: {}",
+                path.as_str(),
+                e
+            );
+            e
+        })?;
+        if jsonc::parse(&source)?.is_object() {
+            for (keys, value) in updates {
+                source = set_nested(&source, keys, value)?;
+            }
+            io::write_text(path, &source)?;
+            return Ok(());
+        }
+    }
+
+    let mut settings = Map::new();
+    for (keys, value) in updates {
+        insert_nested(&mut settings, keys, value.clone());
+    }
+    io::write_text(path, &serde_json::to_string_pretty(&settings)?)?;
+    Ok(())
+}
+
+/// Splice `value` into JSONC `source` at `keys`.
+///
+/// The deepest object that already exists along `keys` receives the rest
+/// of the path as new nested objects.
+fn set_nested(source: &str, keys: &[String], value: &Value) -> Result<String> {
+    let root = jsonc::parse(source)?;
+    let mut node = &root;
+    let mut depth = 0;
+    while depth + 1 < keys.len() {
+        match node.get(&keys[depth]) {
+            Some(child) if child.is_object() => {
+                node = child;
+                depth += 1;
+            }
+            _ => break,
+        }
+    }
+
+    let nested = keys[depth + 1..]
+        .iter()
+        .rev()
+        .fold(value.clone(), |inner, key| {
+            Value::Object(Map::from_iter([(key.clone(), inner)]))
+        });
+    let path: Vec<PathSegment> = keys[..=depth]
+        .iter()
+        .cloned()
+        .map(PathSegment::Key)
+        .collect();
+    Ok(jsonc::set(source, &path, &nested)?)
+}
+
+/// Insert `value` into `map` at `keys`, creating objects along the way.
+fn insert_nested(map: &mut Map<String, Value>, keys: &[String], value: Value) {
+    let Some((last, parents)) = keys.split_last() else {
+        return;
+    };
+    let mut map = map;
+    for key in parents {
+        let entry = map.entry(key.clone()).or_insert_with(|| json!({}));
+        if !entry.is_object() {
+            *entry = json!({});
         }
+        map = entry.as_object_mut().expect("just made an object");
     }
+    map.insert(last.clone(), value);
 }
 
 impl Default for JsonWriter {
@@ -79,18 +182,7 @@ impl ConfigWriter for JsonWriter {
         content: &TranslatedContent,
         keys: Option<&SchemaKeys>,
     ) -> Result<()> {
-        let mut existing = Self::parse_existing(path)?;
-
-        // Ensure we have an object
-        if !existing.is_object() {
-            existing = json!({});
-        }
-
-        Self::merge(&mut existing, content, keys);
-
-        // Write back with pretty formatting
-        io::write_text(path, &serde_json::to_string_pretty(&existing)?)?;
-        Ok(())
+        update_json_paths(path, &self.updates(content, keys))
     }
 
     fn can_handle(&self, path: &NormalizedPath) -> bool {
@@ -217,6 +309,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_target_path_creates_nested_objects() {
+        let temp = TempDir::new().unwrap();
+        let path = NormalizedPath::new(temp.path()).join("settings.json");
+        let writer = JsonWriter::with_target_path("ai.rules");
+        let keys = SchemaKeys {
+            instruction_key: Some("instructions".into()),
+            ..Default::default()
+        };
+
+        writer
+            .write(&path, &make_content(Some("Be terse.")), Some(&keys))
+            .unwrap();
+        let json: Value =
+            serde_json::from_str(&fs::read_to_string(path.as_ref()).unwrap()).unwrap();
+        assert_eq!(json, json!({"ai": {"rules": "Be terse."}}));
+
+        // Merges into existing objects, keeping their keys and comments
+        fs::write(
+            path.as_ref(),
+            "{\n    // AI settings\n    \"ai\": {\n        \"model\": \"fast\"\n    },\n    \"other\": 1\n}\n",
+        )
+        .unwrap();
+        writer
+            .write(&path, &make_content(Some("Be terse.")), Some(&keys))
+            .unwrap();
+        let written = fs::read_to_string(path.as_ref()).unwrap();
+        assert!(written.contains("// AI settings"));
+        let json = jsonc::parse(&written).unwrap();
+        assert_eq!(
+            json,
+            json!({"ai": {"model": "fast", "rules": "Be terse."}, "other": 1})
+        );
+
+        // Updating the value in place
+        writer
+            .write(&path, &make_content(Some("Be brief.")), Some(&keys))
+            .unwrap();
+        let json = jsonc::parse(&fs::read_to_string(path.as_ref()).unwrap()).unwrap();
+        assert_eq!(json["ai"]["rules"], "Be brief.");
+        assert!(json.get("instructions").is_none());
+    }
+
     #[test]
     fn test_can_handle() {
         let writer = JsonWriter::new();
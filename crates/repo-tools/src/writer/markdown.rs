@@ -17,12 +17,24 @@ const MANAGED_END: &str = "<!-- repo:managed:end -->";
 /// - Preserves all user content outside the managed section
 /// - Creates managed section if it doesn't exist
 /// - Updates only the content between markers
-pub struct MarkdownWriter;
+/// - Can keep the managed section under a named `##` heading
+pub struct MarkdownWriter {
+    /// Heading of the `##` section holding the managed block
+    heading: Option<String>,
+}
 
 impl MarkdownWriter {
     /// Create a new Markdown writer.
     pub fn new() -> Self {
-        Self
+        Self { heading: None }
+    }
+
+    /// Create a Markdown writer that keeps the managed section at the end
+    /// of the `## {heading}` section, adding the heading if it is absent.
+    pub fn in_section(heading: impl Into<String>) -> Self {
+        Self {
+            heading: Some(heading.into()),
+        }
     }
 
     /// Parse existing file, returning (user_content, managed_content).
@@ -74,6 +86,41 @@ impl MarkdownWriter {
 
         out
     }
+
+    /// Combine user content and managed content, placing the managed
+    /// section at the end of the `## {heading}` section.
+    fn combine_in_section(user: &str, heading: &str, managed: &str) -> String {
+        let title = format!("## {}", heading);
+        // Offset of the end of the section, once its heading is found
+        let mut offset = 0;
+        let mut found = false;
+        for line in user.split_inclusive('\n') {
+            let text = line.trim_end();
+            if !found {
+                found = text == title;
+            } else if text.starts_with("# ") || text.starts_with("## ") {
+                break;
+            }
+            offset += line.len();
+        }
+
+        if !found {
+            let user = user.trim_end();
+            let user = if user.is_empty() {
+                title
+            } else {
+                format!("{}\n\n{}", user, title)
+            };
+            return Self::combine(&user, managed);
+        }
+        let (section, after) = user.split_at(offset);
+        let out = Self::combine(section.trim_end(), managed);
+        if after.is_empty() {
+            out
+        } else {
+            format!("{}\n{}", out, after)
+        }
+    }
 }
 
 impl Default for MarkdownWriter {
@@ -91,7 +138,11 @@ impl ConfigWriter for MarkdownWriter {
     ) -> Result<()> {
         let (user, _) = Self::parse_existing(path);
         let managed = content.instructions.as_deref().unwrap_or("");
-        io::write_text(path, &Self::combine(&user, managed))?;
+        let out = match &self.heading {
+            Some(heading) => Self::combine_in_section(&user, heading, managed),
+            None => Self::combine(&user, managed),
+        };
+        io::write_text(path, &out)?;
         Ok(())
     }
 
@@ -205,6 +256,57 @@ mod tests {
         assert!(written.contains("Updated"));
     }
 
+    #[test]
+    fn test_in_section_creates_heading() {
+        let temp = TempDir::new().unwrap();
+        let path = NormalizedPath::new(temp.path()).join("AGENTS.md");
+        fs::write(path.as_ref(), "# Project\n\nNotes.\n").unwrap();
+
+        let writer = MarkdownWriter::in_section("AI Rules");
+        writer
+            .write(&path, &make_content("Rule one"), None)
+            .unwrap();
+
+        let written = fs::read_to_string(path.as_ref()).unwrap();
+        assert_eq!(
+            written,
+            format!(
+                "# Project\n\nNotes.\n\n## AI Rules\n\n{}\nRule one\n{}\n",
+                MANAGED_START, MANAGED_END
+            )
+        );
+
+        // Rewriting is stable
+        writer
+            .write(&path, &make_content("Rule one"), None)
+            .unwrap();
+        assert_eq!(fs::read_to_string(path.as_ref()).unwrap(), written);
+    }
+
+    #[test]
+    fn test_in_section_keeps_following_sections() {
+        let temp = TempDir::new().unwrap();
+        let path = NormalizedPath::new(temp.path()).join("AGENTS.md");
+        fs::write(
+            path.as_ref(),
+            "# Project\n\n## AI Rules\n\nHand-written.\n\n### Detail\n\nMore.\n\n## Build\n\nRun make.\n",
+        )
+        .unwrap();
+
+        let writer = MarkdownWriter::in_section("AI Rules");
+        writer.write(&path, &make_content("Managed"), None).unwrap();
+        writer.write(&path, &make_content("Updated"), None).unwrap();
+
+        let written = fs::read_to_string(path.as_ref()).unwrap();
+        assert_eq!(
+            written,
+            format!(
+                "# Project\n\n## AI Rules\n\nHand-written.\n\n### Detail\n\nMore.\n\n{}\nUpdated\n{}\n\n## Build\n\nRun make.\n",
+                MANAGED_START, MANAGED_END
+            )
+        );
+    }
+
     #[test]
     fn test_can_handle() {
         let writer = MarkdownWriter::new();
@@ -6,15 +6,19 @@
 //! - **JsonWriter**: Semantic merge, preserves existing keys
 //! - **MarkdownWriter**: Section-based merge with managed markers
 //! - **TextWriter**: Full replacement (tool owns the file)
+//! - **YamlWriter**: Semantic merge, preserves existing keys
 
 mod json;
 mod markdown;
 mod registry;
 mod text;
 mod traits;
+mod yaml;
 
 pub use json::JsonWriter;
+pub(crate) use json::update_json_file;
 pub use markdown::MarkdownWriter;
 pub use registry::WriterRegistry;
 pub use text::TextWriter;
 pub use traits::{ConfigWriter, SchemaKeys};
+pub use yaml::YamlWriter;
@@ -1,13 +1,14 @@
 //! Writer registry for selecting writers by config type
 
-use super::{ConfigWriter, JsonWriter, MarkdownWriter, TextWriter};
-use repo_meta::schema::ConfigType;
+use super::{ConfigWriter, JsonWriter, MarkdownWriter, TextWriter, YamlWriter};
+use repo_meta::schema::{ConfigType, ToolIntegrationConfig, WriterStrategy};
 
 /// Registry that selects the appropriate writer for a config type.
 pub struct WriterRegistry {
     json: JsonWriter,
     markdown: MarkdownWriter,
     text: TextWriter,
+    yaml: YamlWriter,
 }
 
 impl WriterRegistry {
@@ -17,17 +18,40 @@ impl WriterRegistry {
             json: JsonWriter::new(),
             markdown: MarkdownWriter::new(),
             text: TextWriter::new(),
+            yaml: YamlWriter::new(),
         }
     }
 
     /// Get the appropriate writer for a config type.
-    pub fn get_writer(&self, config_type: ConfigType) -> &dyn ConfigWriter {
+    pub fn get_writer(&self, config_type: &ConfigType) -> &dyn ConfigWriter {
         match config_type {
             ConfigType::Json => &self.json,
-            ConfigType::Markdown => &self.markdown,
-            // YAML and TOML use text writer for now (full replacement)
+            // Directory tools get one markdown file per rule via
+            // GenericToolIntegration; a single-file write uses markdown too
+            ConfigType::Markdown | ConfigType::Directory { .. } => &self.markdown,
+            ConfigType::Yaml => &self.yaml,
+            // TOML uses text writer for now (full replacement)
             // Future: Add AST-aware writers
-            ConfigType::Text | ConfigType::Yaml | ConfigType::Toml => &self.text,
+            ConfigType::Text | ConfigType::Toml => &self.text,
+        }
+    }
+
+    /// Get the writer for a tool's integration config.
+    ///
+    /// A `writer` strategy in the config selects the writer and its
+    /// parameters; without one, the writer for the config type is used.
+    pub fn resolve(&self, integration: &ToolIntegrationConfig) -> Box<dyn ConfigWriter + '_> {
+        match &integration.writer {
+            Some(WriterStrategy::JsonMerge { target_path }) => Box::new(
+                target_path
+                    .as_deref()
+                    .map_or_else(JsonWriter::new, JsonWriter::with_target_path),
+            ),
+            Some(WriterStrategy::MarkdownSection { heading }) => {
+                Box::new(MarkdownWriter::in_section(heading.clone()))
+            }
+            Some(WriterStrategy::TextReplace) => Box::new(&self.text),
+            None => Box::new(self.get_writer(&integration.config_type)),
         }
     }
 }
@@ -46,36 +70,60 @@ mod tests {
     #[test]
     fn test_get_json_writer() {
         let registry = WriterRegistry::new();
-        let writer = registry.get_writer(ConfigType::Json);
+        let writer = registry.get_writer(&ConfigType::Json);
         assert!(writer.can_handle(&NormalizedPath::new("/test/config.json")));
     }
 
     #[test]
     fn test_get_markdown_writer() {
         let registry = WriterRegistry::new();
-        let writer = registry.get_writer(ConfigType::Markdown);
+        let writer = registry.get_writer(&ConfigType::Markdown);
         assert!(writer.can_handle(&NormalizedPath::new("/test/rules.md")));
     }
 
     #[test]
     fn test_get_text_writer() {
         let registry = WriterRegistry::new();
-        let writer = registry.get_writer(ConfigType::Text);
+        let writer = registry.get_writer(&ConfigType::Text);
         assert!(writer.can_handle(&NormalizedPath::new("/test/.cursorrules")));
     }
 
     #[test]
-    fn test_yaml_uses_text_writer() {
+    fn test_get_yaml_writer() {
         let registry = WriterRegistry::new();
-        let writer = registry.get_writer(ConfigType::Yaml);
-        // YAML uses text writer for now, which handles plain files
-        assert!(writer.can_handle(&NormalizedPath::new("/test/.rules")));
+        let writer = registry.get_writer(&ConfigType::Yaml);
+        assert!(writer.can_handle(&NormalizedPath::new("/test/config.yaml")));
+        assert!(!writer.can_handle(&NormalizedPath::new("/test/.rules")));
+    }
+
+    #[test]
+    fn test_resolve_writer_strategy() {
+        let registry = WriterRegistry::new();
+        let mut integration = ToolIntegrationConfig {
+            config_path: "settings.json".into(),
+            config_type: ConfigType::Json,
+            additional_paths: vec![],
+            rules_layout: Default::default(),
+            rules_filename: None,
+            writer: None,
+        };
+        let path = NormalizedPath::new("/test/.rules");
+        assert!(!registry.resolve(&integration).can_handle(&path));
+
+        integration.writer = Some(WriterStrategy::TextReplace);
+        assert!(registry.resolve(&integration).can_handle(&path));
+
+        integration.writer = Some(WriterStrategy::MarkdownSection {
+            heading: "Rules".into(),
+        });
+        let writer = registry.resolve(&integration);
+        assert!(writer.can_handle(&NormalizedPath::new("/test/AGENTS.md")));
     }
 
     #[test]
     fn test_toml_uses_text_writer() {
         let registry = WriterRegistry::new();
-        let writer = registry.get_writer(ConfigType::Toml);
+        let writer = registry.get_writer(&ConfigType::Toml);
         assert!(writer.can_handle(&NormalizedPath::new("/test/.rules")));
     }
 }
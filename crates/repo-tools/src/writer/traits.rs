@@ -26,6 +26,21 @@ pub trait ConfigWriter: Send + Sync {
     fn can_handle(&self, path: &NormalizedPath) -> bool;
 }
 
+impl<W: ConfigWriter + ?Sized> ConfigWriter for &W {
+    fn write(
+        &self,
+        path: &NormalizedPath,
+        content: &TranslatedContent,
+        schema_keys: Option<&SchemaKeys>,
+    ) -> Result<()> {
+        (**self).write(path, content, schema_keys)
+    }
+
+    fn can_handle(&self, path: &NormalizedPath) -> bool {
+        (**self).can_handle(path)
+    }
+}
+
 /// Schema keys for JSON config file key placement.
 ///
 /// These define where specific content types should be placed
@@ -0,0 +1,219 @@
+//! YAML config writer with semantic merge
+//!
+//! This writer preserves existing YAML keys, in their original order, while
+//! updating managed fields.
+
+use super::{ConfigWriter, SchemaKeys};
+use crate::error::Result;
+use crate::translator::TranslatedContent;
+use repo_fs::{NormalizedPath, io};
+use serde_yaml::{Mapping, Value};
+
+/// YAML config writer that semantically merges content.
+///
+/// Features:
+/// - Preserves existing keys and their order in the YAML file
+/// - Uses schema_keys to place instructions and MCP config
+/// - Merges additional data from TranslatedContent
+///
+/// Managed keys that already exist are updated in place; new keys are
+/// appended after the existing ones. Comments are not preserved.
+pub struct YamlWriter;
+
+impl YamlWriter {
+    /// Create a new YAML writer.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse existing YAML file or return an empty mapping.
+    ///
+    /// Returns an empty mapping if the file does not exist or is empty.
+    /// Propagates I/O and parse errors so callers can distinguish missing
+    /// files from corrupted ones.
+    fn parse_existing(path: &NormalizedPath) -> Result<Value> {
+        if !path.exists() {
+            return Ok(Value::Mapping(Mapping::new()));
+        }
+        let content = io::read_text(path).map_err(|e| {
+            tracing::warn!(
+                "Failed to read existing YAML config at {}: {}",
+                path.as_str(),
+                e
+            );
+            e
+        })?;
+        if content.trim().is_empty() {
+            return Ok(Value::Mapping(Mapping::new()));
+        }
+        let value = serde_yaml::from_str(&content)?;
+        Ok(value)
+    }
+
+    /// Merge content into an existing YAML mapping.
+    fn merge(
+        existing: &mut Mapping,
+        content: &TranslatedContent,
+        keys: Option<&SchemaKeys>,
+    ) -> Result<()> {
+        // Merge instructions if key specified
+        if let (Some(instructions), Some(k)) = (&content.instructions, keys)
+            && let Some(ref key) = k.instruction_key
+        {
+            existing.insert(key.as_str().into(), instructions.as_str().into());
+        }
+
+        // Merge MCP servers if key specified
+        if let (Some(mcp), Some(k)) = (&content.mcp_servers, keys)
+            && let Some(ref key) = k.mcp_key
+        {
+            existing.insert(key.as_str().into(), serde_yaml::to_value(mcp)?);
+        }
+
+        // Merge additional data, in key order so output is deterministic
+        let mut data: Vec<_> = content.data.iter().collect();
+        data.sort_by(|a, b| a.0.cmp(b.0));
+        for (key, value) in data {
+            existing.insert(key.as_str().into(), serde_yaml::to_value(value)?);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for YamlWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigWriter for YamlWriter {
+    fn write(
+        &self,
+        path: &NormalizedPath,
+        content: &TranslatedContent,
+        keys: Option<&SchemaKeys>,
+    ) -> Result<()> {
+        let mut existing = match Self::parse_existing(path)? {
+            Value::Mapping(mapping) => mapping,
+            // Ensure we have a mapping
+            _ => Mapping::new(),
+        };
+
+        Self::merge(&mut existing, content, keys)?;
+
+        io::write_text(path, &serde_yaml::to_string(&existing)?)?;
+        Ok(())
+    }
+
+    fn can_handle(&self, path: &NormalizedPath) -> bool {
+        let path = path.as_str();
+        path.ends_with(".yaml") || path.ends_with(".yml")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use repo_meta::schema::ConfigType;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_new_file() {
+        let temp = TempDir::new().unwrap();
+        let path = NormalizedPath::new(temp.path()).join("config.yaml");
+        let writer = YamlWriter::new();
+
+        let content =
+            TranslatedContent::with_instructions(ConfigType::Yaml, "Test instructions".into());
+        let keys = SchemaKeys {
+            instruction_key: Some("instructions".into()),
+            ..Default::default()
+        };
+
+        writer.write(&path, &content, Some(&keys)).unwrap();
+
+        let written = fs::read_to_string(path.as_ref()).unwrap();
+        let yaml: Value = serde_yaml::from_str(&written).unwrap();
+        assert_eq!(yaml["instructions"], "Test instructions");
+    }
+
+    #[test]
+    fn test_merge_preserves_user_keys_and_order() {
+        let temp = TempDir::new().unwrap();
+        let path = NormalizedPath::new(temp.path()).join("config.yml");
+        fs::write(
+            path.as_ref(),
+            "name: my-project\n\
+             instructions: old\n\
+             build:\n  steps:\n  - lint\n  - test\n\
+             timeout: 30\n",
+        )
+        .unwrap();
+
+        let writer = YamlWriter::new();
+        let content =
+            TranslatedContent::with_instructions(ConfigType::Yaml, "New instructions".into())
+                .with_mcp_servers(json!({"server1": {"command": "test"}}))
+                .with_data("enabled", json!(true));
+        let keys = SchemaKeys {
+            instruction_key: Some("instructions".into()),
+            mcp_key: Some("mcpServers".into()),
+            ..Default::default()
+        };
+
+        writer.write(&path, &content, Some(&keys)).unwrap();
+
+        let written = fs::read_to_string(path.as_ref()).unwrap();
+        let yaml: Value = serde_yaml::from_str(&written).unwrap();
+
+        // User keys preserved
+        assert_eq!(yaml["name"], "my-project");
+        assert_eq!(yaml["build"]["steps"][1], "test");
+        assert_eq!(yaml["timeout"], 30);
+        // Managed keys written
+        assert_eq!(yaml["instructions"], "New instructions");
+        assert_eq!(yaml["mcpServers"]["server1"]["command"], "test");
+        assert_eq!(yaml["enabled"], true);
+
+        // Existing keys keep their position; new keys follow them
+        let order: Vec<&str> = yaml
+            .as_mapping()
+            .unwrap()
+            .keys()
+            .filter_map(Value::as_str)
+            .collect();
+        assert_eq!(
+            order,
+            [
+                "name",
+                "instructions",
+                "build",
+                "timeout",
+                "mcpServers",
+                "enabled"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_yaml() {
+        let temp = TempDir::new().unwrap();
+        let path = NormalizedPath::new(temp.path()).join("config.yaml");
+        fs::write(path.as_ref(), "key: [unclosed\n").unwrap();
+
+        let writer = YamlWriter::new();
+        let content = TranslatedContent::empty().with_data("key", json!("value"));
+        assert!(writer.write(&path, &content, None).is_err());
+    }
+
+    #[test]
+    fn test_can_handle() {
+        let writer = YamlWriter::new();
+        assert!(writer.can_handle(&NormalizedPath::new("/test/config.yaml")));
+        assert!(writer.can_handle(&NormalizedPath::new("/test/.aider.conf.yml")));
+        assert!(!writer.can_handle(&NormalizedPath::new("/test/config.json")));
+    }
+}
@@ -2,18 +2,28 @@
 //!
 //! Manages `.rules` file for AI agent instructions.
 //!
+//! Rules are written to `.rules` as managed blocks with the same
+//! `<!-- repo:block:ID -->` markers as every other text config, rather than
+//! hash comments: Zed passes the file to the model as Markdown, where HTML
+//! comments are inert, and a single marker style keeps drift checks and
+//! block removal uniform across tools.
+//!
+//! MCP servers are installed into `.zed/settings.json` under
+//! `context_servers` by [`McpInstaller`](crate::McpInstaller); see
+//! [`mcp_translate`](crate::mcp_translate) for Zed's entry shape.
+//!
 //! Reference: https://zed.dev/docs/ai/rules
 
 use crate::generic::GenericToolIntegration;
 use repo_meta::schema::{
-    ConfigType, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
+    ConfigType, RulesLayout, ToolCapabilities, ToolDefinition, ToolIntegrationConfig, ToolMeta,
 };
 
 /// Creates a Zed editor integration.
 ///
 /// Configuration files:
 /// - `.rules` - Project rules file (highest priority)
-/// - `.zed/settings.json` - Project settings (for AI model config)
+/// - `.zed/settings.json` - Project settings (AI model config, `context_servers`)
 ///
 /// Priority order: .rules > .cursorrules > .windsurfrules > .clinerules >
 ///   .github/copilot-instructions.md > AGENT.md > AGENTS.md > CLAUDE.md > GEMINI.md
@@ -25,11 +35,16 @@ pub fn zed_integration() -> GenericToolIntegration {
             name: "Zed".into(),
             slug: "zed".into(),
             description: Some("Zed code editor with AI agent".into()),
+            override_builtin: false,
+            open_command: Some("zed {path}".into()),
         },
         integration: ToolIntegrationConfig {
             config_path: ".rules".into(),
             config_type: ConfigType::Text,
             additional_paths: vec![".zed/settings.json".into()],
+            rules_layout: RulesLayout::Single,
+            rules_filename: None,
+            writer: None,
         },
         capabilities: ToolCapabilities {
             supports_custom_instructions: true,
@@ -44,8 +59,10 @@ pub fn zed_integration() -> GenericToolIntegration {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::McpInstaller;
     use crate::integration::{Rule, SyncContext, ToolIntegration};
     use repo_fs::NormalizedPath;
+    use repo_meta::schema::{McpScope, McpServerConfig, McpTransportConfig};
     use std::fs;
     use tempfile::TempDir;
 
@@ -73,6 +90,7 @@ mod tests {
         let rules = vec![Rule {
             id: "code-style".to_string(),
             content: "Use Rust best practices.".to_string(),
+            paths: vec![],
         }];
 
         let integration = zed_integration();
@@ -94,4 +112,43 @@ mod tests {
             "Must have block end marker"
         );
     }
+
+    #[test]
+    fn test_sync_preserves_context_servers() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = NormalizedPath::new(temp_dir.path());
+
+        let server = McpServerConfig {
+            transport: McpTransportConfig::Stdio {
+                command: "npx".into(),
+                args: vec!["-y".into(), "server".into()],
+                cwd: None,
+            },
+            env: None,
+            auto_approve: false,
+            expand_env: false,
+        };
+        McpInstaller::new("zed", root.clone())
+            .unwrap()
+            .install(McpScope::Project, "docs", &server)
+            .unwrap();
+
+        let rules = vec![Rule {
+            id: "code-style".to_string(),
+            content: "Use Rust best practices.".to_string(),
+            paths: vec![],
+        }];
+        zed_integration()
+            .sync(&SyncContext::new(root), &rules)
+            .unwrap();
+
+        let settings: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(temp_dir.path().join(".zed/settings.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            settings["context_servers"]["docs"]["command"]["path"],
+            "npx"
+        );
+    }
 }
@@ -1,98 +1,74 @@
-//! Format validation tests for Aider .aider.conf.yml output.
+//! Format validation tests for Aider output.
 //!
 //! Category: format-validation
-//! Validates that Aider's YAML output is parseable and uses correct
-//! YAML comment syntax for block markers (not HTML comments).
+//! Validates that rules land in CONVENTIONS.md as managed blocks, and that
+//! .aider.conf.yml stays parseable YAML whose `read:` list points at the
+//! conventions file, with the user's keys and comments untouched.
 
 use repo_fs::NormalizedPath;
 use repo_tools::{Rule, SyncContext, ToolIntegration, aider_integration};
 use std::fs;
 use tempfile::TempDir;
 
+fn rules() -> Vec<Rule> {
+    vec![
+        Rule {
+            id: "rule-alpha".to_string(),
+            content: "Alpha content".to_string(),
+            paths: vec![],
+        },
+        Rule {
+            id: "rule-beta".to_string(),
+            content: "Beta content".to_string(),
+            paths: vec![],
+        },
+    ]
+}
+
+fn read_list(content: &str) -> Vec<String> {
+    let value: serde_yaml::Value = serde_yaml::from_str(content).unwrap();
+    value["read"]
+        .as_sequence()
+        .expect("read: must be a list")
+        .iter()
+        .map(|v| v.as_str().unwrap().to_string())
+        .collect()
+}
+
 #[test]
-fn aider_config_is_valid_yaml() {
+fn aider_config_is_valid_yaml_reading_conventions() {
     let temp = TempDir::new().unwrap();
-    let root = NormalizedPath::new(temp.path());
-    let context = SyncContext::new(root);
-    let rules = vec![Rule {
-        id: "style-guide".to_string(),
-        content: "Follow PEP 8 for Python code.".to_string(),
-    }];
+    let context = SyncContext::new(NormalizedPath::new(temp.path()));
 
-    let integration = aider_integration();
-    integration.sync(&context, &rules).unwrap();
+    aider_integration().sync(&context, &rules()).unwrap();
 
     let content = fs::read_to_string(temp.path().join(".aider.conf.yml")).unwrap();
-
-    // Must parse without error as YAML (all-comment YAML is valid — parses as null)
     let result: Result<serde_yaml::Value, _> = serde_yaml::from_str(&content);
     assert!(
         result.is_ok(),
         "Generated .aider.conf.yml must be valid YAML, parse error: {:?}",
         result.err()
     );
-}
-
-#[test]
-fn aider_config_uses_yaml_comment_markers_not_html() {
-    let temp = TempDir::new().unwrap();
-    let root = NormalizedPath::new(temp.path());
-    let context = SyncContext::new(root);
-    let rules = vec![Rule {
-        id: "testing".to_string(),
-        content: "Write tests for all functions.".to_string(),
-    }];
-
-    let integration = aider_integration();
-    integration.sync(&context, &rules).unwrap();
-
-    let content = fs::read_to_string(temp.path().join(".aider.conf.yml")).unwrap();
+    assert_eq!(read_list(&content), vec!["CONVENTIONS.md"]);
 
-    // Block markers must use YAML comment syntax (# prefix)
-    assert!(
-        content.contains("# repo:block:testing"),
-        "YAML block markers must use # prefix"
-    );
-    assert!(
-        content.contains("# /repo:block:testing"),
-        "YAML closing markers must use # prefix"
-    );
-
-    // Must NOT use HTML comment markers (would break YAML parsing)
-    assert!(
-        !content.contains("<!-- repo:block:"),
-        "YAML file must not contain HTML-style <!-- markers"
-    );
-    assert!(
-        !content.contains("<!-- /repo:block:"),
-        "YAML file must not contain HTML-style <!-- closing markers"
-    );
+    // Rule content belongs in the conventions file, not the config
+    assert!(!content.contains("Alpha content"));
+    assert!(!content.contains("<!-- repo:block:"));
 }
 
 #[test]
-fn aider_managed_blocks_have_matching_open_close() {
+fn aider_conventions_blocks_have_matching_open_close() {
     let temp = TempDir::new().unwrap();
-    let root = NormalizedPath::new(temp.path());
-    let context = SyncContext::new(root);
-    let rules = vec![
-        Rule {
-            id: "rule-alpha".to_string(),
-            content: "Alpha content".to_string(),
-        },
-        Rule {
-            id: "rule-beta".to_string(),
-            content: "Beta content".to_string(),
-        },
-    ];
+    let context = SyncContext::new(NormalizedPath::new(temp.path()));
+    let rules = rules();
 
-    let integration = aider_integration();
-    integration.sync(&context, &rules).unwrap();
+    aider_integration().sync(&context, &rules).unwrap();
 
-    let content = fs::read_to_string(temp.path().join(".aider.conf.yml")).unwrap();
+    let content = fs::read_to_string(temp.path().join("CONVENTIONS.md")).unwrap();
 
     for rule in &rules {
-        let open_marker = format!("# repo:block:{}", rule.id);
-        let close_marker = format!("# /repo:block:{}", rule.id);
+        let open_marker = format!("<!-- repo:block:{} -->", rule.id);
+        let close_marker = format!("<!-- /repo:block:{} -->", rule.id);
 
         let open_count = content.matches(&open_marker).count();
         let close_count = content.matches(&close_marker).count();
@@ -107,5 +83,31 @@ fn aider_managed_blocks_have_matching_open_close() {
             "Expected exactly 1 close marker for '{}', found {close_count}",
             rule.id
         );
+        assert!(content.contains(&rule.content));
     }
 }
+
+#[test]
+fn aider_sync_preserves_user_config() {
+    let temp = TempDir::new().unwrap();
+    let context = SyncContext::new(NormalizedPath::new(temp.path()));
+    let user_config = "\
+# Team defaults
+model: sonnet
+read:
+  - docs/STYLE.md  # shared style guide
+auto-commits: false
+";
+    fs::write(temp.path().join(".aider.conf.yml"), user_config).unwrap();
+
+    let integration = aider_integration();
+    integration.sync(&context, &rules()).unwrap();
+    integration.sync(&context, &rules()).unwrap();
+
+    let content = fs::read_to_string(temp.path().join(".aider.conf.yml")).unwrap();
+    assert_eq!(read_list(&content), vec!["docs/STYLE.md", "CONVENTIONS.md"]);
+    assert!(content.contains("# Team defaults\nmodel: sonnet\n"));
+    assert!(content.contains("  - docs/STYLE.md  # shared style guide\n"));
+    assert!(content.ends_with("auto-commits: false\n"));
+    assert_eq!(content.matches("- CONVENTIONS.md").count(), 1);
+}
@@ -18,6 +18,7 @@ fn antigravity_creates_rules_directory_not_file() {
     let rules = vec![Rule {
         id: "test-rule".to_string(),
         content: "Test content".to_string(),
+        paths: vec![],
     }];
 
     let integration = antigravity_integration();
@@ -43,14 +44,17 @@ fn antigravity_rule_files_follow_naming_convention() {
         Rule {
             id: "code-style".to_string(),
             content: "Style content".to_string(),
+            paths: vec![],
         },
         Rule {
             id: "testing-guidelines".to_string(),
             content: "Testing content".to_string(),
+            paths: vec![],
         },
         Rule {
             id: "naming".to_string(),
             content: "Naming content".to_string(),
+            paths: vec![],
         },
     ];
 
@@ -97,6 +101,7 @@ fn antigravity_rule_files_are_valid_markdown_without_block_markers() {
     let rules = vec![Rule {
         id: "content-rule".to_string(),
         content: "This is meaningful rule content.".to_string(),
+        paths: vec![],
     }];
 
     let integration = antigravity_integration();
@@ -22,10 +22,12 @@ fn jetbrains_creates_rules_directory_with_valid_structure() {
         Rule {
             id: "code-style".to_string(),
             content: "Use IntelliJ code style.".to_string(),
+            paths: vec![],
         },
         Rule {
             id: "testing".to_string(),
             content: "Write JUnit tests.".to_string(),
+            paths: vec![],
         },
     ];
 
@@ -77,10 +79,12 @@ fn roo_creates_rules_directory_with_valid_structure() {
         Rule {
             id: "conventions".to_string(),
             content: "Follow project conventions.".to_string(),
+            paths: vec![],
         },
         Rule {
             id: "architecture".to_string(),
             content: "Maintain modular architecture.".to_string(),
+            paths: vec![],
         },
     ];
 
@@ -43,10 +43,12 @@ fn sample_rules() -> Vec<Rule> {
         Rule {
             id: "format-alpha".to_string(),
             content: "Alpha rule content for testing.".to_string(),
+            paths: vec![],
         },
         Rule {
             id: "format-beta".to_string(),
             content: "Beta rule content\nwith multiple lines.".to_string(),
+            paths: vec![],
         },
     ]
 }
@@ -167,6 +169,7 @@ fn user_content_outside_blocks_is_preserved_after_sync() {
         let rules = vec![Rule {
             id: "auto-rule".to_string(),
             content: "Automated content".to_string(),
+            paths: vec![],
         }];
 
         integration.sync(&context, &rules).unwrap();